@@ -0,0 +1,98 @@
+/* C declarations for the `yaxpeax_x86_ffi_*` libraries. functions are prefixed by the mode they
+ * decode: `yaxpeax_x86_64_` for `long_mode`, `yaxpeax_x86_32_` for `protected_mode`, and
+ * `yaxpeax_x86_16_` for `real_mode`. only the functions for the modes a library was built with
+ * are present; `yaxpeax_x86_ffi_multiarch` has all three.
+ *
+ * decoders and instructions are opaque. allocate `yaxpeax_x86_*_decoder_size()` and
+ * `yaxpeax_x86_*_instr_size()` bytes, aligned to 16 bytes, for them.
+ */
+
+#ifndef YAXPEAX_X86_H
+#define YAXPEAX_X86_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#define YAXPEAX_X86_OPERAND_NONE 0
+#define YAXPEAX_X86_OPERAND_REGISTER 1
+#define YAXPEAX_X86_OPERAND_IMMEDIATE 2
+#define YAXPEAX_X86_OPERAND_MEMORY 3
+#define YAXPEAX_X86_OPERAND_FAR_ADDRESS 4
+
+#define YAXPEAX_X86_ACCESS_READ 0
+#define YAXPEAX_X86_ACCESS_WRITE 1
+#define YAXPEAX_X86_ACCESS_READ_WRITE 2
+#define YAXPEAX_X86_ACCESS_COND_WRITE 3
+
+#define YAXPEAX_X86_REG_CLASS_NONE 0
+#define YAXPEAX_X86_REG_CLASS_Q 1
+#define YAXPEAX_X86_REG_CLASS_D 2
+#define YAXPEAX_X86_REG_CLASS_W 3
+#define YAXPEAX_X86_REG_CLASS_B 4
+#define YAXPEAX_X86_REG_CLASS_RB 5
+#define YAXPEAX_X86_REG_CLASS_CR 6
+#define YAXPEAX_X86_REG_CLASS_DR 7
+#define YAXPEAX_X86_REG_CLASS_S 8
+#define YAXPEAX_X86_REG_CLASS_X 9
+#define YAXPEAX_X86_REG_CLASS_Y 10
+#define YAXPEAX_X86_REG_CLASS_Z 11
+#define YAXPEAX_X86_REG_CLASS_ST 12
+#define YAXPEAX_X86_REG_CLASS_MM 13
+#define YAXPEAX_X86_REG_CLASS_K 14
+#define YAXPEAX_X86_REG_CLASS_T 15
+#define YAXPEAX_X86_REG_CLASS_BND 16
+#define YAXPEAX_X86_REG_CLASS_RIP 17
+#define YAXPEAX_X86_REG_CLASS_EIP 18
+#define YAXPEAX_X86_REG_CLASS_RFLAGS 19
+#define YAXPEAX_X86_REG_CLASS_EFLAGS 20
+
+/* a register, as its class and number within that class: `Q` and `0` are `rax`. */
+typedef struct yaxpeax_x86_register {
+    uint8_t class;
+    uint8_t num;
+} yaxpeax_x86_register;
+
+/* an operand of a decoded instruction. which fields are meaningful depends on `kind`; a memory
+ * operand is `[reg + index * scale + imm]`, where `reg` and `index` may be absent. */
+typedef struct yaxpeax_x86_operand {
+    uint8_t kind;
+    uint8_t access;
+    /* in bytes, or 0 if the operand has no single width. */
+    uint8_t width;
+    uint8_t scale;
+    /* the segment selector of a far address. */
+    uint16_t segment;
+    yaxpeax_x86_register reg;
+    yaxpeax_x86_register index;
+    /* the avx512 opmask applied to this operand, if any. */
+    yaxpeax_x86_register mask;
+    int64_t imm;
+} yaxpeax_x86_operand;
+
+#define YAXPEAX_X86_DECLARE_MODE(prefix) \
+    typedef struct prefix##_decoder prefix##_decoder; \
+    typedef struct prefix##_instruction prefix##_instruction; \
+    size_t prefix##_decoder_size(void); \
+    size_t prefix##_instr_size(void); \
+    void prefix##_decoder_default(prefix##_decoder *decoder); \
+    void prefix##_decoder_minimal(prefix##_decoder *decoder); \
+    /* these return `true` if decoding failed. */ \
+    bool prefix##_decode(const uint8_t *data, uint64_t length, prefix##_instruction *inst); \
+    bool prefix##_decode_with(const prefix##_decoder *decoder, const uint8_t *data, uint64_t length, prefix##_instruction *inst); \
+    size_t prefix##_instr_length(prefix##_instruction *inst); \
+    uint32_t prefix##_opcode(const prefix##_instruction *inst); \
+    uint8_t prefix##_operand_count(const prefix##_instruction *inst); \
+    /* returns `false` if `i` is not less than the operand count. */ \
+    bool prefix##_operand(const prefix##_instruction *inst, uint8_t i, yaxpeax_x86_operand *operand); \
+    /* with the `fmt` feature. text is not nul-terminated. */ \
+    void prefix##_fmt(prefix##_instruction *inst, uint8_t *text, size_t len); \
+    size_t prefix##_operand_fmt(const prefix##_instruction *inst, uint8_t i, uint8_t *text, size_t len);
+
+YAXPEAX_X86_DECLARE_MODE(yaxpeax_x86_64)
+YAXPEAX_X86_DECLARE_MODE(yaxpeax_x86_32)
+YAXPEAX_X86_DECLARE_MODE(yaxpeax_x86_16)
+
+#undef YAXPEAX_X86_DECLARE_MODE
+
+#endif
@@ -1,5 +1,11 @@
 use yaxpeax_arch::{Arch, Decoder, LengthedInstruction, U8Reader, AddressBase};
 use yaxpeax_x86::long_mode;
+use yaxpeax_x86::long_mode::register_class;
+use yaxpeax_x86::AccessKind;
+
+mod operand;
+
+use operand::{Operand, Register};
 
 #[no_mangle]
 pub unsafe extern "C" fn yaxpeax_x86_64_decode(data: *const u8, length: u64, inst: *mut long_mode::Instruction) -> bool {
@@ -14,6 +20,161 @@ pub unsafe extern "C" fn yaxpeax_x86_64_instr_length(inst: *mut long_mode::Instr
     0.wrapping_offset(inst.len()).to_linear()
 }
 
+#[no_mangle]
+pub extern "C" fn yaxpeax_x86_64_instr_size() -> usize {
+    core::mem::size_of::<long_mode::Instruction>()
+}
+
+#[no_mangle]
+pub extern "C" fn yaxpeax_x86_64_decoder_size() -> usize {
+    core::mem::size_of::<long_mode::InstDecoder>()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn yaxpeax_x86_64_decoder_default(decoder: *mut long_mode::InstDecoder) {
+    decoder.write(long_mode::InstDecoder::default());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn yaxpeax_x86_64_decoder_minimal(decoder: *mut long_mode::InstDecoder) {
+    decoder.write(long_mode::InstDecoder::minimal());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn yaxpeax_x86_64_decode_with(decoder: *const long_mode::InstDecoder, data: *const u8, length: u64, inst: *mut long_mode::Instruction) -> bool {
+    let decoder: &long_mode::InstDecoder = &*decoder;
+    let inst: &mut long_mode::Instruction = core::mem::transmute(inst);
+    let mut reader = U8Reader::new(core::slice::from_raw_parts(data as *const u8, length as usize));
+    decoder.decode_into(inst, &mut reader).is_err()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn yaxpeax_x86_64_opcode(inst: *const long_mode::Instruction) -> u32 {
+    (*inst).opcode() as u32
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn yaxpeax_x86_64_operand_count(inst: *const long_mode::Instruction) -> u8 {
+    (*inst).operand_count()
+}
+
+fn register(reg: long_mode::RegSpec) -> Register {
+    let class = reg.class();
+    let class = if class == register_class::Q {
+        operand::REG_CLASS_Q
+    } else if class == register_class::D {
+        operand::REG_CLASS_D
+    } else if class == register_class::W {
+        operand::REG_CLASS_W
+    } else if class == register_class::B {
+        operand::REG_CLASS_B
+    } else if class == register_class::RB {
+        operand::REG_CLASS_RB
+    } else if class == register_class::CR {
+        operand::REG_CLASS_CR
+    } else if class == register_class::DR {
+        operand::REG_CLASS_DR
+    } else if class == register_class::S {
+        operand::REG_CLASS_S
+    } else if class == register_class::X {
+        operand::REG_CLASS_X
+    } else if class == register_class::Y {
+        operand::REG_CLASS_Y
+    } else if class == register_class::Z {
+        operand::REG_CLASS_Z
+    } else if class == register_class::ST {
+        operand::REG_CLASS_ST
+    } else if class == register_class::MM {
+        operand::REG_CLASS_MM
+    } else if class == register_class::K {
+        operand::REG_CLASS_K
+    } else if class == register_class::T {
+        operand::REG_CLASS_T
+    } else if class == register_class::BND {
+        operand::REG_CLASS_BND
+    } else if class == register_class::RIP {
+        operand::REG_CLASS_RIP
+    } else if class == register_class::EIP {
+        operand::REG_CLASS_EIP
+    } else if class == register_class::RFLAGS {
+        operand::REG_CLASS_RFLAGS
+    } else if class == register_class::EFLAGS {
+        operand::REG_CLASS_EFLAGS
+    } else {
+        operand::REG_CLASS_NONE
+    };
+    Register { class, num: reg.num() }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn yaxpeax_x86_64_operand(inst: *const long_mode::Instruction, i: u8, operand: *mut Operand) -> bool {
+    let inst: &long_mode::Instruction = &*inst;
+    let access = match inst.operand_access(i) {
+        Some(access) => access,
+        None => { return false; }
+    };
+    let mut op = match inst.operand(i) {
+        long_mode::Operand::ImmediateI8(imm) => Operand::immediate(imm as i64),
+        long_mode::Operand::ImmediateU8(imm) => Operand::immediate(imm as i64),
+        long_mode::Operand::ImmediateI16(imm) => Operand::immediate(imm as i64),
+        long_mode::Operand::ImmediateU16(imm) => Operand::immediate(imm as i64),
+        long_mode::Operand::ImmediateI32(imm) => Operand::immediate(imm as i64),
+        long_mode::Operand::ImmediateU32(imm) => Operand::immediate(imm as i64),
+        long_mode::Operand::ImmediateI64(imm) => Operand::immediate(imm),
+        long_mode::Operand::ImmediateU64(imm) => Operand::immediate(imm as i64),
+        long_mode::Operand::DisplacementU64(disp) => Operand::memory(Register::NONE, Register::NONE, 0, disp as i64),
+        long_mode::Operand::Register(reg) => Operand::register(register(reg)),
+        long_mode::Operand::RegisterMaskMerge(reg, mask, _) |
+        long_mode::Operand::RegisterMaskMergeSae(reg, mask, _, _) |
+        long_mode::Operand::RegisterMaskMergeSaeNoround(reg, mask, _) => {
+            Operand { mask: register(mask), ..Operand::register(register(reg)) }
+        }
+        long_mode::Operand::DisplacementU32(disp) => Operand::memory(Register::NONE, Register::NONE, 0, disp as i64),
+        long_mode::Operand::RegDeref(base) => Operand::memory(register(base), Register::NONE, 0, 0),
+        long_mode::Operand::RegDisp(base, disp) => Operand::memory(register(base), Register::NONE, 0, disp as i64),
+        long_mode::Operand::RegScale(index, scale) => Operand::memory(Register::NONE, register(index), scale, 0),
+        long_mode::Operand::RegScaleDisp(index, scale, disp) => Operand::memory(Register::NONE, register(index), scale, disp as i64),
+        long_mode::Operand::RegIndexBase(base, index) => Operand::memory(register(base), register(index), 1, 0),
+        long_mode::Operand::RegIndexBaseDisp(base, index, disp) => Operand::memory(register(base), register(index), 1, disp as i64),
+        long_mode::Operand::RegIndexBaseScale(base, index, scale) => Operand::memory(register(base), register(index), scale, 0),
+        long_mode::Operand::RegIndexBaseScaleDisp(base, index, scale, disp) => Operand::memory(register(base), register(index), scale, disp as i64),
+        long_mode::Operand::RegDerefMasked(base, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(register(base), Register::NONE, 0, 0) }
+        }
+        long_mode::Operand::RegDispMasked(base, disp, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(register(base), Register::NONE, 0, disp as i64) }
+        }
+        long_mode::Operand::RegScaleMasked(index, scale, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(Register::NONE, register(index), scale, 0) }
+        }
+        long_mode::Operand::RegScaleDispMasked(index, scale, disp, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(Register::NONE, register(index), scale, disp as i64) }
+        }
+        long_mode::Operand::RegIndexBaseMasked(base, index, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(register(base), register(index), 1, 0) }
+        }
+        long_mode::Operand::RegIndexBaseDispMasked(base, index, disp, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(register(base), register(index), 1, disp as i64) }
+        }
+        long_mode::Operand::RegIndexBaseScaleMasked(base, index, scale, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(register(base), register(index), scale, 0) }
+        }
+        long_mode::Operand::RegIndexBaseScaleDispMasked(base, index, scale, disp, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(register(base), register(index), scale, disp as i64) }
+        }
+        _ => Operand::NONE,
+    };
+    op.access = match access {
+        AccessKind::Read => operand::ACCESS_READ,
+        AccessKind::Write => operand::ACCESS_WRITE,
+        AccessKind::ReadWrite => operand::ACCESS_READ_WRITE,
+        AccessKind::CondWrite => operand::ACCESS_COND_WRITE,
+    };
+    op.width = inst.operand_width(i).unwrap_or(0);
+    operand.write(op);
+    true
+}
+
 #[cfg(feature = "fmt")]
 mod write_sink;
 
@@ -32,7 +193,20 @@ mod fmt {
 
         write!(InstructionSink { buf: res, offs: 0 }, "{}", inst).unwrap();
     }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn yaxpeax_x86_64_operand_fmt(inst: *const long_mode::Instruction, i: u8, text: *mut u8, len: usize) -> usize {
+        let inst: &long_mode::Instruction = &*inst;
+        if i >= inst.operand_count() {
+            return 0;
+        }
+        let res = core::slice::from_raw_parts_mut(text, len);
+
+        let mut sink = InstructionSink { buf: res, offs: 0 };
+        write!(sink, "{}", inst.operand(i)).unwrap();
+        sink.offs
+    }
 }
 
 #[cfg(feature = "fmt")]
-pub use fmt::yaxpeax_x86_64_fmt;
+pub use fmt::{yaxpeax_x86_64_fmt, yaxpeax_x86_64_operand_fmt};
@@ -0,0 +1,112 @@
+//! the C layout operands are described with by `yaxpeax_x86_*_operand`. these are the same for
+//! every mode, though not every mode has every register class. `include/yaxpeax_x86.h` declares
+//! the same layout and constants for C.
+
+// the constants here are for C callers; not every mode uses all of them.
+#![allow(dead_code)]
+
+/// no operand, or a register slot that is unused.
+pub const OPERAND_NONE: u8 = 0;
+/// a register, in `reg`.
+pub const OPERAND_REGISTER: u8 = 1;
+/// an immediate, in `imm`.
+pub const OPERAND_IMMEDIATE: u8 = 2;
+/// a memory operand: `[reg + index * scale + imm]`, where `reg` and `index` may be absent.
+pub const OPERAND_MEMORY: u8 = 3;
+/// a far address in 16- and 32-bit code: `imm` is the offset, and `scale` is unused. the segment
+/// selector is in `segment`.
+pub const OPERAND_FAR_ADDRESS: u8 = 4;
+
+/// the operand is read, but not written.
+pub const ACCESS_READ: u8 = 0;
+/// the operand is written, but not read.
+pub const ACCESS_WRITE: u8 = 1;
+/// the operand is read and then written.
+pub const ACCESS_READ_WRITE: u8 = 2;
+/// the operand may or may not be written, depending on a condition or mask.
+pub const ACCESS_COND_WRITE: u8 = 3;
+
+pub const REG_CLASS_NONE: u8 = 0;
+pub const REG_CLASS_Q: u8 = 1;
+pub const REG_CLASS_D: u8 = 2;
+pub const REG_CLASS_W: u8 = 3;
+pub const REG_CLASS_B: u8 = 4;
+pub const REG_CLASS_RB: u8 = 5;
+pub const REG_CLASS_CR: u8 = 6;
+pub const REG_CLASS_DR: u8 = 7;
+pub const REG_CLASS_S: u8 = 8;
+pub const REG_CLASS_X: u8 = 9;
+pub const REG_CLASS_Y: u8 = 10;
+pub const REG_CLASS_Z: u8 = 11;
+pub const REG_CLASS_ST: u8 = 12;
+pub const REG_CLASS_MM: u8 = 13;
+pub const REG_CLASS_K: u8 = 14;
+pub const REG_CLASS_T: u8 = 15;
+pub const REG_CLASS_BND: u8 = 16;
+pub const REG_CLASS_RIP: u8 = 17;
+pub const REG_CLASS_EIP: u8 = 18;
+pub const REG_CLASS_RFLAGS: u8 = 19;
+pub const REG_CLASS_EFLAGS: u8 = 20;
+
+/// a register, as its class and number within that class: `REG_CLASS_Q` and `0` are `rax`.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Register {
+    pub class: u8,
+    pub num: u8,
+}
+
+impl Register {
+    pub const NONE: Register = Register { class: REG_CLASS_NONE, num: 0 };
+}
+
+/// an operand of a decoded instruction. which fields are meaningful depends on `kind`.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Operand {
+    /// one of the `OPERAND_*` constants.
+    pub kind: u8,
+    /// one of the `ACCESS_*` constants.
+    pub access: u8,
+    /// the width of the operand in bytes, or `0` if it has no single width.
+    pub width: u8,
+    /// for memory operands, the scale of `index`.
+    pub scale: u8,
+    /// for far addresses, the segment selector.
+    pub segment: u16,
+    /// the register of a register operand, or the base of a memory operand.
+    pub reg: Register,
+    /// the index of a memory operand.
+    pub index: Register,
+    /// the `avx512` opmask applied to this operand, if any.
+    pub mask: Register,
+    /// the value of an immediate, the displacement of a memory operand, or the offset of a far
+    /// address.
+    pub imm: i64,
+}
+
+impl Operand {
+    pub const NONE: Operand = Operand {
+        kind: OPERAND_NONE,
+        access: ACCESS_READ,
+        width: 0,
+        scale: 0,
+        segment: 0,
+        reg: Register::NONE,
+        index: Register::NONE,
+        mask: Register::NONE,
+        imm: 0,
+    };
+
+    pub fn register(reg: Register) -> Operand {
+        Operand { kind: OPERAND_REGISTER, reg, ..Operand::NONE }
+    }
+
+    pub fn immediate(imm: i64) -> Operand {
+        Operand { kind: OPERAND_IMMEDIATE, imm, ..Operand::NONE }
+    }
+
+    pub fn memory(base: Register, index: Register, scale: u8, disp: i64) -> Operand {
+        Operand { kind: OPERAND_MEMORY, reg: base, index, scale, imm: disp, ..Operand::NONE }
+    }
+}
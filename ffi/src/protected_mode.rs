@@ -1,5 +1,11 @@
 use yaxpeax_arch::{Arch, Decoder, LengthedInstruction, U8Reader, AddressBase};
 use yaxpeax_x86::protected_mode;
+use yaxpeax_x86::protected_mode::register_class;
+use yaxpeax_x86::AccessKind;
+
+mod operand;
+
+use operand::{Operand, Register};
 
 #[no_mangle]
 pub unsafe extern "C" fn yaxpeax_x86_32_decode(data: *const u8, length: u64, inst: *mut protected_mode::Instruction) -> bool {
@@ -14,6 +20,152 @@ pub unsafe extern "C" fn yaxpeax_x86_32_instr_length(inst: *mut protected_mode::
     0.wrapping_offset(inst.len()).to_linear()
 }
 
+#[no_mangle]
+pub extern "C" fn yaxpeax_x86_32_instr_size() -> usize {
+    core::mem::size_of::<protected_mode::Instruction>()
+}
+
+#[no_mangle]
+pub extern "C" fn yaxpeax_x86_32_decoder_size() -> usize {
+    core::mem::size_of::<protected_mode::InstDecoder>()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn yaxpeax_x86_32_decoder_default(decoder: *mut protected_mode::InstDecoder) {
+    decoder.write(protected_mode::InstDecoder::default());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn yaxpeax_x86_32_decoder_minimal(decoder: *mut protected_mode::InstDecoder) {
+    decoder.write(protected_mode::InstDecoder::minimal());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn yaxpeax_x86_32_decode_with(decoder: *const protected_mode::InstDecoder, data: *const u8, length: u64, inst: *mut protected_mode::Instruction) -> bool {
+    let decoder: &protected_mode::InstDecoder = &*decoder;
+    let inst: &mut protected_mode::Instruction = core::mem::transmute(inst);
+    let mut reader = U8Reader::new(core::slice::from_raw_parts(data as *const u8, length as usize));
+    decoder.decode_into(inst, &mut reader).is_err()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn yaxpeax_x86_32_opcode(inst: *const protected_mode::Instruction) -> u32 {
+    (*inst).opcode() as u32
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn yaxpeax_x86_32_operand_count(inst: *const protected_mode::Instruction) -> u8 {
+    (*inst).operand_count()
+}
+
+fn register(reg: protected_mode::RegSpec) -> Register {
+    let class = reg.class();
+    let class = if class == register_class::D {
+        operand::REG_CLASS_D
+    } else if class == register_class::W {
+        operand::REG_CLASS_W
+    } else if class == register_class::B {
+        operand::REG_CLASS_B
+    } else if class == register_class::CR {
+        operand::REG_CLASS_CR
+    } else if class == register_class::DR {
+        operand::REG_CLASS_DR
+    } else if class == register_class::S {
+        operand::REG_CLASS_S
+    } else if class == register_class::X {
+        operand::REG_CLASS_X
+    } else if class == register_class::Y {
+        operand::REG_CLASS_Y
+    } else if class == register_class::Z {
+        operand::REG_CLASS_Z
+    } else if class == register_class::ST {
+        operand::REG_CLASS_ST
+    } else if class == register_class::MM {
+        operand::REG_CLASS_MM
+    } else if class == register_class::K {
+        operand::REG_CLASS_K
+    } else if class == register_class::BND {
+        operand::REG_CLASS_BND
+    } else if class == register_class::EIP {
+        operand::REG_CLASS_EIP
+    } else if class == register_class::EFLAGS {
+        operand::REG_CLASS_EFLAGS
+    } else {
+        operand::REG_CLASS_NONE
+    };
+    Register { class, num: reg.num() }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn yaxpeax_x86_32_operand(inst: *const protected_mode::Instruction, i: u8, operand: *mut Operand) -> bool {
+    let inst: &protected_mode::Instruction = &*inst;
+    let access = match inst.operand_access(i) {
+        Some(access) => access,
+        None => { return false; }
+    };
+    let mut op = match inst.operand(i) {
+        protected_mode::Operand::ImmediateI8(imm) => Operand::immediate(imm as i64),
+        protected_mode::Operand::ImmediateU8(imm) => Operand::immediate(imm as i64),
+        protected_mode::Operand::ImmediateI16(imm) => Operand::immediate(imm as i64),
+        protected_mode::Operand::ImmediateU16(imm) => Operand::immediate(imm as i64),
+        protected_mode::Operand::ImmediateI32(imm) => Operand::immediate(imm as i64),
+        protected_mode::Operand::ImmediateU32(imm) => Operand::immediate(imm as i64),
+        protected_mode::Operand::DisplacementU16(disp) => Operand::memory(Register::NONE, Register::NONE, 0, disp as i64),
+        protected_mode::Operand::AbsoluteFarAddress { segment, address } => {
+            Operand { kind: operand::OPERAND_FAR_ADDRESS, segment, imm: address as i64, ..Operand::NONE }
+        }
+        protected_mode::Operand::Register(reg) => Operand::register(register(reg)),
+        protected_mode::Operand::RegisterMaskMerge(reg, mask, _) |
+        protected_mode::Operand::RegisterMaskMergeSae(reg, mask, _, _) |
+        protected_mode::Operand::RegisterMaskMergeSaeNoround(reg, mask, _) => {
+            Operand { mask: register(mask), ..Operand::register(register(reg)) }
+        }
+        protected_mode::Operand::DisplacementU32(disp) => Operand::memory(Register::NONE, Register::NONE, 0, disp as i64),
+        protected_mode::Operand::RegDeref(base) => Operand::memory(register(base), Register::NONE, 0, 0),
+        protected_mode::Operand::RegDisp(base, disp) => Operand::memory(register(base), Register::NONE, 0, disp as i64),
+        protected_mode::Operand::RegScale(index, scale) => Operand::memory(Register::NONE, register(index), scale, 0),
+        protected_mode::Operand::RegScaleDisp(index, scale, disp) => Operand::memory(Register::NONE, register(index), scale, disp as i64),
+        protected_mode::Operand::RegIndexBase(base, index) => Operand::memory(register(base), register(index), 1, 0),
+        protected_mode::Operand::RegIndexBaseDisp(base, index, disp) => Operand::memory(register(base), register(index), 1, disp as i64),
+        protected_mode::Operand::RegIndexBaseScale(base, index, scale) => Operand::memory(register(base), register(index), scale, 0),
+        protected_mode::Operand::RegIndexBaseScaleDisp(base, index, scale, disp) => Operand::memory(register(base), register(index), scale, disp as i64),
+        protected_mode::Operand::RegDerefMasked(base, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(register(base), Register::NONE, 0, 0) }
+        }
+        protected_mode::Operand::RegDispMasked(base, disp, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(register(base), Register::NONE, 0, disp as i64) }
+        }
+        protected_mode::Operand::RegScaleMasked(index, scale, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(Register::NONE, register(index), scale, 0) }
+        }
+        protected_mode::Operand::RegScaleDispMasked(index, scale, disp, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(Register::NONE, register(index), scale, disp as i64) }
+        }
+        protected_mode::Operand::RegIndexBaseMasked(base, index, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(register(base), register(index), 1, 0) }
+        }
+        protected_mode::Operand::RegIndexBaseDispMasked(base, index, disp, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(register(base), register(index), 1, disp as i64) }
+        }
+        protected_mode::Operand::RegIndexBaseScaleMasked(base, index, scale, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(register(base), register(index), scale, 0) }
+        }
+        protected_mode::Operand::RegIndexBaseScaleDispMasked(base, index, scale, disp, mask) => {
+            Operand { mask: register(mask), ..Operand::memory(register(base), register(index), scale, disp as i64) }
+        }
+        _ => Operand::NONE,
+    };
+    op.access = match access {
+        AccessKind::Read => operand::ACCESS_READ,
+        AccessKind::Write => operand::ACCESS_WRITE,
+        AccessKind::ReadWrite => operand::ACCESS_READ_WRITE,
+        AccessKind::CondWrite => operand::ACCESS_COND_WRITE,
+    };
+    op.width = inst.operand_width(i).unwrap_or(0);
+    operand.write(op);
+    true
+}
+
 #[cfg(feature = "fmt")]
 mod write_sink;
 
@@ -32,7 +184,20 @@ mod fmt {
 
         write!(InstructionSink { buf: res, offs: 0 }, "{}", inst).unwrap();
     }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn yaxpeax_x86_32_operand_fmt(inst: *const protected_mode::Instruction, i: u8, text: *mut u8, len: usize) -> usize {
+        let inst: &protected_mode::Instruction = &*inst;
+        if i >= inst.operand_count() {
+            return 0;
+        }
+        let res = core::slice::from_raw_parts_mut(text, len);
+
+        let mut sink = InstructionSink { buf: res, offs: 0 };
+        write!(sink, "{}", inst.operand(i)).unwrap();
+        sink.offs
+    }
 }
 
 #[cfg(feature = "fmt")]
-pub use fmt::yaxpeax_x86_32_fmt;
+pub use fmt::{yaxpeax_x86_32_fmt, yaxpeax_x86_32_operand_fmt};
@@ -0,0 +1,306 @@
+//! the `yaxpeax_x86` python module. instructions are described much like capstone's python
+//! bindings describe them, with `mnemonic`, `op_str`, and `operands` of `type` `"reg"`, `"imm"`,
+//! or `"mem"`; see `README.md` for an example.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use yaxpeax_x86::{long_mode, protected_mode, real_mode, AccessKind};
+
+/// split an instruction's text into the mnemonic, with any prefixes before it, and operands.
+fn split_mnemonic(text: &str, opcode: &str) -> (String, String) {
+    let mut end = 0;
+    for word in text.split(' ') {
+        end += word.len();
+        if word == opcode {
+            break;
+        }
+        end += 1;
+    }
+    let end = core::cmp::min(end, text.len());
+    (text[..end].to_string(), text[end..].trim_start().to_string())
+}
+
+/// describe a decoded `Instruction` from `$mode` as an `Instruction` for python.
+macro_rules! describe {
+    ($mode:ident, $instr:expr, $data:expr, $address:expr) => {{
+        use yaxpeax_x86::$mode::capstone_compat::{Group, X86OpValue};
+
+        let instr = $instr;
+        let insn = instr.to_capstone($address);
+        let (mnemonic, op_str) = split_mnemonic(&instr.to_string(), &instr.opcode().to_string());
+
+        let operands = insn.detail.x86.operands().iter().map(|op| {
+            let mut operand = Operand {
+                kind: "reg",
+                size: op.size,
+                access: access_name(op.access),
+                reg: None,
+                imm: None,
+                mem: None,
+            };
+            match op.value {
+                X86OpValue::Reg(reg) => {
+                    operand.reg = Some(reg.name().to_string());
+                }
+                X86OpValue::Imm(imm) => {
+                    operand.kind = "imm";
+                    operand.imm = Some(imm);
+                }
+                X86OpValue::Mem(mem) => {
+                    operand.kind = "mem";
+                    operand.mem = Some(MemoryOperand {
+                        segment: mem.segment.map(|reg| reg.name().to_string()),
+                        base: mem.base.map(|reg| reg.name().to_string()),
+                        index: mem.index.map(|reg| reg.name().to_string()),
+                        scale: mem.scale,
+                        disp: mem.disp,
+                    });
+                }
+            }
+            operand
+        }).collect();
+
+        let groups = insn.detail.groups().iter().map(|group| {
+            match group {
+                Group::Jump => "jump".to_string(),
+                Group::Call => "call".to_string(),
+                Group::Ret => "ret".to_string(),
+                Group::Int => "int".to_string(),
+                Group::Iret => "iret".to_string(),
+                Group::Privilege => "privilege".to_string(),
+                Group::BranchRelative => "branch_relative".to_string(),
+                Group::Isa(isa) => format!("{:?}", isa).to_lowercase(),
+            }
+        }).collect();
+
+        Instruction {
+            address: $address,
+            size: insn.size as u8,
+            bytes: $data[..insn.size as usize].to_vec(),
+            mnemonic,
+            op_str,
+            operands,
+            regs_read: insn.detail.regs_read().iter().map(|reg| reg.name().to_string()).collect(),
+            regs_write: insn.detail.regs_write().iter().map(|reg| reg.name().to_string()).collect(),
+            groups,
+        }
+    }};
+}
+
+#[derive(Copy, Clone)]
+enum Decoder {
+    Real(real_mode::InstDecoder),
+    Protected(protected_mode::InstDecoder),
+    Long(long_mode::InstDecoder),
+}
+
+/// a decoder for one of `real_mode`, `protected_mode`, or `long_mode`, selected by `mode`: 16,
+/// 32, or 64. `minimal` decoders reject instructions from extensions to the base instruction
+/// set, as `InstDecoder::minimal` does.
+#[pyclass(module = "yaxpeax_x86", frozen)]
+struct InstDecoder {
+    decoder: Decoder,
+    #[pyo3(get)]
+    mode: u8,
+}
+
+#[pymethods]
+impl InstDecoder {
+    #[new]
+    #[pyo3(signature = (mode = 64, minimal = false))]
+    fn new(mode: u8, minimal: bool) -> PyResult<Self> {
+        let decoder = match (mode, minimal) {
+            (16, false) => Decoder::Real(real_mode::InstDecoder::default()),
+            (16, true) => Decoder::Real(real_mode::InstDecoder::minimal()),
+            (32, false) => Decoder::Protected(protected_mode::InstDecoder::default()),
+            (32, true) => Decoder::Protected(protected_mode::InstDecoder::minimal()),
+            (64, false) => Decoder::Long(long_mode::InstDecoder::default()),
+            (64, true) => Decoder::Long(long_mode::InstDecoder::minimal()),
+            _ => {
+                return Err(PyValueError::new_err(format!("mode must be 16, 32, or 64, not {}", mode)));
+            }
+        };
+        Ok(InstDecoder { decoder, mode })
+    }
+
+    /// decode the instruction at the start of `data`, as if it were at `address`. raises
+    /// `ValueError` if `data` does not start with an instruction.
+    #[pyo3(signature = (data, address = 0))]
+    fn decode(&self, data: &[u8], address: u64) -> PyResult<Instruction> {
+        let res = match self.decoder {
+            Decoder::Real(decoder) => {
+                decoder.decode_slice(data).map(|instr| describe!(real_mode, instr, data, address)).map_err(|e| e.to_string())
+            }
+            Decoder::Protected(decoder) => {
+                decoder.decode_slice(data).map(|instr| describe!(protected_mode, instr, data, address)).map_err(|e| e.to_string())
+            }
+            Decoder::Long(decoder) => {
+                decoder.decode_slice(data).map(|instr| describe!(long_mode, instr, data, address)).map_err(|e| e.to_string())
+            }
+        };
+        res.map_err(PyValueError::new_err)
+    }
+
+    /// decode instructions from `data`, the first at `address`, until the end of `data` or the
+    /// first bytes that do not decode. like capstone's `Cs.disasm`, decoding stops quietly at
+    /// invalid bytes; the sum of the instructions' sizes tells how far decoding got.
+    #[pyo3(signature = (data, address = 0))]
+    fn disasm(&self, data: &[u8], address: u64) -> Vec<Instruction> {
+        let mut instrs = Vec::new();
+        let mut offset = 0;
+        while offset < data.len() {
+            match self.decode(&data[offset..], address.wrapping_add(offset as u64)) {
+                Ok(instr) => {
+                    offset += instr.size as usize;
+                    instrs.push(instr);
+                }
+                Err(_) => {
+                    break;
+                }
+            }
+        }
+        instrs
+    }
+}
+
+/// a decoded instruction. `str()` of an instruction is its text, as `yaxpeax-x86` displays it.
+#[pyclass(module = "yaxpeax_x86", frozen)]
+#[derive(Clone)]
+struct Instruction {
+    /// the address the instruction was decoded at.
+    #[pyo3(get)]
+    address: u64,
+    /// the length of the instruction, in bytes.
+    #[pyo3(get)]
+    size: u8,
+    bytes: Vec<u8>,
+    /// the opcode, along with any prefixes written before it: `lock add`, `rep movs`.
+    #[pyo3(get)]
+    mnemonic: String,
+    /// the operands, as written after the mnemonic.
+    #[pyo3(get)]
+    op_str: String,
+    #[pyo3(get)]
+    operands: Vec<Operand>,
+    /// registers read that are not named by an operand.
+    #[pyo3(get)]
+    regs_read: Vec<String>,
+    /// registers written that are not named by an operand.
+    #[pyo3(get)]
+    regs_write: Vec<String>,
+    /// what sort of instruction this is: `"jump"`, `"call"`, `"ret"`, `"int"`, `"iret"`,
+    /// `"privilege"`, `"branch_relative"`, and the instruction set extension it is from.
+    #[pyo3(get)]
+    groups: Vec<String>,
+}
+
+#[pymethods]
+impl Instruction {
+    /// the bytes the instruction was decoded from.
+    #[getter]
+    fn bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new_bound(py, &self.bytes)
+    }
+
+    fn __str__(&self) -> String {
+        if self.op_str.is_empty() {
+            self.mnemonic.clone()
+        } else {
+            format!("{} {}", self.mnemonic, self.op_str)
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Instruction 0x{:x}: {}>", self.address, self.__str__())
+    }
+}
+
+/// an operand of an `Instruction`. `type` selects which of `reg`, `imm`, or `mem` is set.
+#[pyclass(module = "yaxpeax_x86", frozen, get_all)]
+#[derive(Clone)]
+struct Operand {
+    #[pyo3(name = "type")]
+    kind: &'static str,
+    /// the width of the operand in bytes, or `0` if it has no single width.
+    size: u8,
+    /// `"read"`, `"write"`, `"read_write"`, or `"cond_write"` for an operand that is only
+    /// written if a condition or mask allows it.
+    access: &'static str,
+    reg: Option<String>,
+    imm: Option<i64>,
+    mem: Option<MemoryOperand>,
+}
+
+#[pymethods]
+impl Operand {
+    fn __repr__(&self) -> String {
+        match (&self.reg, self.imm, &self.mem) {
+            (Some(reg), _, _) => format!("<Operand reg {}>", reg),
+            (_, Some(imm), _) => format!("<Operand imm {:#x}>", imm),
+            (_, _, Some(mem)) => format!("<Operand mem {}>", mem.__str__()),
+            _ => "<Operand>".to_string(),
+        }
+    }
+}
+
+/// the address of a memory operand, `segment:[base + index * scale + disp]`. `segment` is only
+/// set for a segment override, as in capstone.
+#[pyclass(module = "yaxpeax_x86", frozen, get_all)]
+#[derive(Clone)]
+struct MemoryOperand {
+    segment: Option<String>,
+    base: Option<String>,
+    index: Option<String>,
+    scale: u8,
+    disp: i64,
+}
+
+#[pymethods]
+impl MemoryOperand {
+    fn __str__(&self) -> String {
+        let mut text = String::new();
+        if let Some(segment) = &self.segment {
+            text.push_str(segment);
+            text.push(':');
+        }
+        text.push('[');
+        let mut terms = Vec::new();
+        if let Some(base) = &self.base {
+            terms.push(base.clone());
+        }
+        if let Some(index) = &self.index {
+            terms.push(format!("{} * {}", index, self.scale));
+        }
+        if self.disp != 0 || terms.is_empty() {
+            terms.push(format!("{:#x}", self.disp));
+        }
+        text.push_str(&terms.join(" + "));
+        text.push(']');
+        text
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<MemoryOperand {}>", self.__str__())
+    }
+}
+
+fn access_name(access: AccessKind) -> &'static str {
+    match access {
+        AccessKind::Read => "read",
+        AccessKind::Write => "write",
+        AccessKind::ReadWrite => "read_write",
+        AccessKind::CondWrite => "cond_write",
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "yaxpeax_x86")]
+fn yaxpeax_x86_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<InstDecoder>()?;
+    m.add_class::<Instruction>()?;
+    m.add_class::<Operand>()?;
+    m.add_class::<MemoryOperand>()?;
+    Ok(())
+}
@@ -0,0 +1,388 @@
+//! known-good decodings, for checking that a fork, wrapper, or binding of this crate decodes as
+//! the crate does.
+//!
+//! each [`TestVector`] is a sequence of bytes, the mode it is decoded in, and either the text of
+//! the instruction it decodes to with default options and the default `InstDecoder`, or that it
+//! is invalid. [`VECTORS`] holds a selection of the crate's own test cases; [`check`] and
+//! [`run`] compare any decoder against vectors, and [`self_test`] compares this crate's
+//! decoders.
+//!
+//! vectors also have a line-oriented text form, `<mode> <hex bytes> <text>`, with `!invalid` for
+//! the text of invalid vectors. [`TestVector::parse`] reads a line back, so new vectors can be
+//! kept in a file alongside the tests that use them:
+//!
+//! ```
+//! use yaxpeax_x86::conformance::{self, TestVector};
+//!
+//! let vectors = [
+//!     TestVector::parse("long 4889e5 mov rbp, rsp").unwrap(),
+//!     TestVector::parse("protected 89e5 mov ebp, esp").unwrap(),
+//! ];
+//! assert!(conformance::run(&vectors, &mut conformance::decode).is_empty());
+//! assert!(conformance::self_test().is_empty());
+//! ```
+
+use core::fmt;
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use yaxpeax_arch::LengthedInstruction;
+
+use crate::{long_mode, protected_mode, real_mode};
+
+/// the decoder a [`TestVector`] is decoded with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// `long_mode`.
+    Long,
+    /// `protected_mode`.
+    Protected,
+    /// `real_mode`.
+    Real,
+}
+
+impl Mode {
+    /// the name of this mode in the text form of vectors: `long`, `protected`, or `real`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mode::Long => "long",
+            Mode::Protected => "protected",
+            Mode::Real => "real",
+        }
+    }
+
+    /// the mode with text-form name `name`.
+    pub fn from_name(name: &str) -> Option<Mode> {
+        match name {
+            "long" => Some(Mode::Long),
+            "protected" => Some(Mode::Protected),
+            "real" => Some(Mode::Real),
+            _ => None,
+        }
+    }
+}
+
+/// how a [`TestVector`]'s bytes should decode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expected {
+    /// the bytes decode to one instruction that uses all of them, displayed as this text.
+    Display(Cow<'static, str>),
+    /// the bytes do not decode.
+    Invalid,
+}
+
+/// a sequence of bytes and how it decodes in some mode. see the [module documentation](self).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestVector {
+    pub mode: Mode,
+    pub bytes: Cow<'static, [u8]>,
+    pub expected: Expected,
+}
+
+const fn vector(mode: Mode, bytes: &'static [u8], text: &'static str) -> TestVector {
+    TestVector {
+        mode,
+        bytes: Cow::Borrowed(bytes),
+        expected: Expected::Display(Cow::Borrowed(text)),
+    }
+}
+
+const fn invalid(mode: Mode, bytes: &'static [u8]) -> TestVector {
+    TestVector {
+        mode,
+        bytes: Cow::Borrowed(bytes),
+        expected: Expected::Invalid,
+    }
+}
+
+/// why a line could not be read by [`TestVector::parse`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// the line does not start with `long`, `protected`, or `real`.
+    BadMode,
+    /// the bytes are missing, not hex, or an odd number of digits.
+    BadBytes,
+    /// there is no instruction text, or `!invalid`, after the bytes.
+    MissingText,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadMode => f.write_str("mode is not one of long, protected, or real"),
+            ParseError::BadBytes => f.write_str("bytes are not an even number of hex digits"),
+            ParseError::MissingText => f.write_str("no instruction text after the bytes"),
+        }
+    }
+}
+
+impl TestVector {
+    /// read a vector from its text form, `<mode> <hex bytes> <text>`, as `Display` writes it.
+    pub fn parse(line: &str) -> Result<TestVector, ParseError> {
+        let mut parts = line.trim().splitn(3, ' ');
+        let mode = parts.next().and_then(Mode::from_name).ok_or(ParseError::BadMode)?;
+        let hex = parts.next().ok_or(ParseError::BadBytes)?;
+        if hex.is_empty() || hex.len() % 2 != 0 {
+            return Err(ParseError::BadBytes);
+        }
+        let bytes = (0..hex.len()).step_by(2).map(|i| {
+            hex.get(i..i + 2).and_then(|digits| u8::from_str_radix(digits, 16).ok())
+        }).collect::<Option<Vec<u8>>>().ok_or(ParseError::BadBytes)?;
+        let text = parts.next().map(str::trim).filter(|text| !text.is_empty()).ok_or(ParseError::MissingText)?;
+        let expected = if text == "!invalid" {
+            Expected::Invalid
+        } else {
+            Expected::Display(Cow::Owned(text.to_string()))
+        };
+        Ok(TestVector {
+            mode,
+            bytes: Cow::Owned(bytes),
+            expected,
+        })
+    }
+}
+
+impl fmt::Display for TestVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ", self.mode.name())?;
+        for b in self.bytes.iter() {
+            write!(f, "{:02x}", b)?;
+        }
+        match &self.expected {
+            Expected::Display(text) => write!(f, " {}", text),
+            Expected::Invalid => f.write_str(" !invalid"),
+        }
+    }
+}
+
+/// a vector a decoder did not decode as expected. see [`check`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub vector: TestVector,
+    /// what the decoder produced instead: the length and text of the instruction it decoded, or
+    /// `None` if it rejected the bytes.
+    pub actual: Option<(usize, String)>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.vector)?;
+        match &self.actual {
+            Some((length, text)) => write!(f, "decoded {} bytes as `{}`", length, text),
+            None => f.write_str("rejected"),
+        }
+    }
+}
+
+/// a decoder to check: it decodes `bytes` in `mode`, returning the length and text of the
+/// instruction they decode to, or `None` if they are invalid.
+pub type DecodeFn<'a> = dyn FnMut(Mode, &[u8]) -> Option<(usize, String)> + 'a;
+
+/// decode `bytes` in `mode` with this crate's default `InstDecoder`, returning the length and
+/// text of the instruction, or `None` if the bytes are invalid. this is the decoder
+/// [`self_test`] checks.
+pub fn decode(mode: Mode, bytes: &[u8]) -> Option<(usize, String)> {
+    match mode {
+        Mode::Long => long_mode::InstDecoder::default().decode_slice(bytes).ok()
+            .map(|instr| (instr.len().to_const() as usize, instr.to_string())),
+        Mode::Protected => protected_mode::InstDecoder::default().decode_slice(bytes).ok()
+            .map(|instr| (instr.len().to_const() as usize, instr.to_string())),
+        Mode::Real => real_mode::InstDecoder::default().decode_slice(bytes).ok()
+            .map(|instr| (instr.len().to_const() as usize, instr.to_string())),
+    }
+}
+
+/// check one vector against `decode`.
+pub fn check(vector: &TestVector, decode: &mut DecodeFn) -> Result<(), Mismatch> {
+    let actual = decode(vector.mode, &vector.bytes);
+    let matches = match (&vector.expected, &actual) {
+        (Expected::Display(expected), Some((length, text))) => {
+            *length == vector.bytes.len() && text == expected
+        }
+        (Expected::Invalid, None) => true,
+        _ => false,
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err(Mismatch {
+            vector: vector.clone(),
+            actual,
+        })
+    }
+}
+
+/// check each of `vectors` against `decode`, as [`check`] does, returning every mismatch.
+pub fn run(vectors: &[TestVector], decode: &mut DecodeFn) -> Vec<Mismatch> {
+    vectors.iter().filter_map(|vector| check(vector, decode).err()).collect()
+}
+
+/// check [`VECTORS`] against this crate's decoders, returning every mismatch. a correctly built
+/// crate returns none.
+pub fn self_test() -> Vec<Mismatch> {
+    run(VECTORS, &mut decode)
+}
+
+/// a selection of this crate's decoding tests, across each mode and valid and invalid encodings.
+pub static VECTORS: &[TestVector] = &[
+    vector(Mode::Long, &[0x33, 0x08], "xor ecx, dword [rax]"),
+    vector(Mode::Long, &[0x44, 0x33, 0x81, 0x23, 0x01, 0x65, 0x43], "xor r8d, dword [rcx + 0x43650123]"),
+    vector(Mode::Long, &[0x42, 0x33, 0x04, 0x20], "xor eax, dword [rax + r12 * 1]"),
+    vector(Mode::Long, &[0x42, 0x33, 0x34, 0x25, 0x20, 0x30, 0x40, 0x50], "xor esi, dword [r12 * 1 + 0x50403020]"),
+    vector(Mode::Long, &[0x0f, 0xc4, 0xc0, 0x14], "pinsrw mm0, eax, 0x14"),
+    vector(Mode::Long, &[0x0f, 0xc5, 0xd1, 0x00], "pextrw edx, mm1, 0x0"),
+    vector(Mode::Long, &[0x0f, 0xef, 0xc2], "pxor mm0, mm2"),
+    vector(Mode::Long, &[0x0f, 0xf3, 0x02], "psllq mm0, qword [rdx]"),
+    vector(Mode::Long, &[0x4f, 0x66, 0x0f, 0x2a, 0xcf], "cvtpi2pd xmm1, mm7"),
+    vector(Mode::Long, &[0x0f, 0x01, 0x49, 0xff], "sidt ptr [rcx - 0x1]"),
+    vector(Mode::Long, &[0x0f, 0x01, 0xcf], "encls"),
+    vector(Mode::Long, &[0x0f, 0x01, 0xff], "tlbsync"),
+    vector(Mode::Long, &[0x0f, 0x01, 0xf1], "lmsw cx"),
+    vector(Mode::Long, &[0x0f, 0xae, 0x14, 0x4f], "ldmxcsr dword [rdi + rcx * 2]"),
+    vector(Mode::Long, &[0x4f, 0x0f, 0xb2, 0x00], "lss r8, tword [r8]"),
+    vector(Mode::Long, &[0x4b, 0x6b, 0x43, 0x6f, 0x6d], "imul rax, qword [r11 + 0x6f], 0x6d"),
+    vector(Mode::Long, &[0x4f, 0x0f, 0x2f, 0x00], "comiss xmm8, dword [r8]"),
+    vector(Mode::Long, &[0xf3, 0x4f, 0x0f, 0x53, 0x01], "rcpss xmm8, dword [r9]"),
+    vector(Mode::Long, &[0xf3, 0x4f, 0x0f, 0x5c, 0x01], "subss xmm8, dword [r9]"),
+    vector(Mode::Long, &[0xf3, 0x0f, 0x70, 0xc0, 0x4e], "pshufhw xmm0, xmm0, 0x4e"),
+    vector(Mode::Long, &[0x67, 0xa2, 0x93, 0x62, 0xc4, 0x00], "mov byte [0xc46293], al"),
+    vector(Mode::Long, &[0x65, 0x4c, 0x89, 0x04, 0x25, 0xa8, 0x01, 0x00, 0x00], "mov qword gs:[0x1a8], r8"),
+    vector(Mode::Long, &[0x91], "xchg eax, ecx"),
+    vector(Mode::Long, &[0xf3, 0x0f, 0xc1, 0xcc], "xadd esp, ecx"),
+    vector(Mode::Long, &[0xe0, 0x12], "loopnz $+0x12"),
+    vector(Mode::Long, &[0x48, 0x8f, 0x00], "pop qword [rax]"),
+    vector(Mode::Long, &[0x48, 0x0f, 0xa3, 0xd0], "bt rax, rdx"),
+    vector(Mode::Long, &[0xf2, 0x0f, 0x38, 0xf0, 0xc1], "crc32 eax, cl"),
+    vector(Mode::Long, &[0xcd, 0x00], "int 0x0"),
+    vector(Mode::Long, &[0xf3, 0x48, 0xab], "rep stos qword es:[rdi], rax"),
+    vector(Mode::Long, &[0x66, 0x0f, 0xae, 0x37], "clwb zmmword [rdi]"),
+    vector(Mode::Long, &[0x62, 0xf2, 0x7d, 0x48, 0x2a, 0x44, 0x40, 0x01], "vmovntdqa zmm0, zmmword [rax + rax * 2 + 0x40]"),
+    vector(Mode::Long, &[0x0f, 0x05], "syscall"),
+    vector(Mode::Long, &[0x0f, 0x16, 0xc0], "movlhps xmm0, xmm0"),
+    vector(Mode::Long, &[0x0f, 0x1f, 0x20], "nop dword [rax]"),
+    vector(Mode::Long, &[0x0f, 0x60, 0x00], "punpcklbw mm0, dword [rax]"),
+    vector(Mode::Long, &[0x0f, 0x68, 0x00], "punpckhbw mm0, qword [rax]"),
+    vector(Mode::Long, &[0x0f, 0x71, 0xe0, 0x7f], "psraw mm0, 0x7f"),
+    vector(Mode::Long, &[0x66, 0x0f, 0xac, 0xcf, 0x11], "shrd di, cx, 0x11"),
+    vector(Mode::Long, &[0xaf], "scas dword es:[rdi], eax"),
+    vector(Mode::Long, &[0x0f, 0x01, 0xdd], "clgi"),
+    vector(Mode::Long, &[0x66, 0x0f, 0x38, 0xf1, 0x06], "movbe word [rsi], ax"),
+    vector(Mode::Long, &[0x0f, 0x38, 0xca, 0x12], "sha1msg2 xmm2, xmmword [rdx]"),
+    vector(Mode::Long, &[0xf3, 0x0f, 0xc7, 0x0f], "cmpxchg8b qword [rdi]"),
+    vector(Mode::Long, &[0xd8, 0xd3], "fcom st(0), st(3)"),
+    vector(Mode::Long, &[0xd9, 0xdb], "fstpnce st(3), st(0)"),
+    vector(Mode::Long, &[0xd9, 0xf4], "fxtract"),
+    vector(Mode::Long, &[0xda, 0x23], "fisub st(0), dword [rbx]"),
+    vector(Mode::Long, &[0xdb, 0xcb], "fcmovne st(0), st(3)"),
+    vector(Mode::Long, &[0xdc, 0x33], "fdiv st(0), qword [rbx]"),
+    vector(Mode::Long, &[0xdd, 0x3b], "fnstsw word [rbx]"),
+    vector(Mode::Long, &[0xde, 0xcb], "fmulp st(3), st(0)"),
+    vector(Mode::Long, &[0xdf, 0xcb], "fxch st(0), st(3)"),
+    vector(Mode::Long, &[0x0f, 0xc7, 0x0f], "cmpxchg8b qword [rdi]"),
+    vector(Mode::Long, &[0x65, 0xf0, 0x87, 0x0f], "lock xchg dword gs:[rdi], ecx"),
+    vector(Mode::Long, &[0x66, 0xf3, 0x0f, 0x01, 0xea], "saveprevssp"),
+    vector(Mode::Long, &[0x8f, 0xe8, 0xf0, 0xa2, 0xc2, 0x30], "vpcmov xmm0, xmm1, xmm3, xmm2"),
+    vector(Mode::Long, &[0x8f, 0xe9, 0x60, 0x02, 0x31], "blci ebx, dword [rcx]"),
+    vector(Mode::Long, &[0xf3, 0x0f, 0x01, 0xef], "stui"),
+    vector(Mode::Long, &[0xf3, 0x0f, 0x01, 0xff], "psmash"),
+    invalid(Mode::Long, &[0x0f, 0xf7, 0x01]),
+    invalid(Mode::Long, &[0x0f, 0x38, 0x2b, 0x06]),
+    invalid(Mode::Long, &[0x45, 0x0f, 0x20, 0xc8]),
+    invalid(Mode::Long, &[0xc4, 0x03, 0x7d, 0x17, 0x0a, 0x77]),
+    invalid(Mode::Long, &[0xc4, 0x02, 0xb9, 0x19, 0xca]),
+    invalid(Mode::Long, &[0xc4, 0x01, 0xba, 0x16, 0xca]),
+    invalid(Mode::Long, &[0xc4, 0x01, 0x78, 0xae, 0xd9]),
+    invalid(Mode::Long, &[0xd9, 0x0c]),
+    invalid(Mode::Long, &[0xdb, 0x23]),
+    invalid(Mode::Long, &[0xde, 0xdb]),
+    invalid(Mode::Long, &[0x66, 0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30]),
+    vector(Mode::Protected, &[0x33, 0x08], "xor ecx, dword [eax]"),
+    vector(Mode::Protected, &[0x33, 0xb4, 0x25, 0x20, 0x30, 0x40, 0x50], "xor esi, dword [ebp + 0x50403020]"),
+    vector(Mode::Protected, &[0x0f, 0x76, 0xc2], "pcmpeqd mm0, mm2"),
+    vector(Mode::Protected, &[0x0f, 0xf2, 0xc2], "pslld mm0, mm2"),
+    vector(Mode::Protected, &[0x0f, 0xf9, 0x02], "psubw mm0, qword [edx]"),
+    vector(Mode::Protected, &[0x0f, 0x01, 0x51, 0xff], "lgdt ptr [ecx - 0x1]"),
+    vector(Mode::Protected, &[0x0f, 0x01, 0xd7], "enclu"),
+    vector(Mode::Protected, &[0x0f, 0x01, 0xf0], "lmsw ax"),
+    vector(Mode::Protected, &[0x0f, 0xc7, 0x6c, 0x24, 0x40], "xsaves ptr [esp + 0x40]"),
+    vector(Mode::Protected, &[0xf3, 0x0f, 0x11, 0x0c, 0xc7], "movss dword [edi + eax * 8], xmm1"),
+    vector(Mode::Protected, &[0x0f, 0x52, 0x01], "rsqrtps xmm0, xmmword [ecx]"),
+    vector(Mode::Protected, &[0x0f, 0x5d, 0x01], "minps xmm0, xmmword [ecx]"),
+    vector(Mode::Protected, &[0x67, 0xa1, 0x93, 0x62], "mov eax, dword [0x6293]"),
+    vector(Mode::Protected, &[0xf3, 0x0f, 0x6f, 0x07], "movdqu xmm0, xmmword [edi]"),
+    vector(Mode::Protected, &[0xf0, 0x0f, 0xbb, 0x17], "lock btc dword [edi], edx"),
+    vector(Mode::Protected, &[0xff, 0x18], "callf far [eax]"),
+    vector(Mode::Protected, &[0x0f, 0xb3, 0xd0], "btr eax, edx"),
+    vector(Mode::Protected, &[0xe5, 0x99], "in eax, 0x99"),
+    vector(Mode::Protected, &[0x8d, 0x0c, 0x12], "lea ecx, dword [edx + edx * 1]"),
+    vector(Mode::Protected, &[0x66, 0x0f, 0x38, 0x81, 0x2f], "invvpid ebp, xmmword [edi]"),
+    vector(Mode::Protected, &[0x0f, 0x12, 0xc0], "movhlps xmm0, xmm0"),
+    vector(Mode::Protected, &[0x0f, 0x1f, 0x20], "nop dword [eax]"),
+    vector(Mode::Protected, &[0x0f, 0x63, 0x00], "packsswb mm0, qword [eax]"),
+    vector(Mode::Protected, &[0x0f, 0x6f, 0x00], "movq mm0, qword [eax]"),
+    vector(Mode::Protected, &[0x66, 0x0f, 0xac, 0xcf, 0x11], "shrd di, cx, 0x11"),
+    vector(Mode::Protected, &[0x47], "inc edi"),
+    vector(Mode::Protected, &[0x3f], "aas"),
+    vector(Mode::Protected, &[0x0f, 0x01, 0xd9], "vmmcall"),
+    vector(Mode::Protected, &[0x0f, 0x3a, 0xcc, 0x12, 0xff], "sha1rnds4 xmm2, xmmword [edx], 0xff"),
+    vector(Mode::Protected, &[0xd8, 0x0b], "fmul st(0), dword [ebx]"),
+    vector(Mode::Protected, &[0xd9, 0x33], "fnstenv ptr [ebx]"),
+    vector(Mode::Protected, &[0xd9, 0xf3], "fpatan"),
+    vector(Mode::Protected, &[0xda, 0x3b], "fidivr st(0), dword [ebx]"),
+    vector(Mode::Protected, &[0xdb, 0xe4], "fsetpm287_nop"),
+    vector(Mode::Protected, &[0xdd, 0x0b], "fisttp qword [ebx], st(0)"),
+    vector(Mode::Protected, &[0xde, 0xc3], "faddp st(3), st(0)"),
+    vector(Mode::Protected, &[0xdf, 0xe0], "fnstsw ax"),
+    vector(Mode::Protected, &[0x67, 0x66, 0x65, 0x3e, 0x0f, 0x6d, 0xd1], "punpckhqdq xmm2, xmm1"),
+    vector(Mode::Protected, &[0xf3, 0x66, 0x0f, 0x01, 0xea], "saveprevssp"),
+    vector(Mode::Protected, &[0xf3, 0x0f, 0x38, 0xfb, 0xde], "encodekey256 ebx, esi"),
+    vector(Mode::Protected, &[0xf3, 0x0f, 0x01, 0xfe], "rmpadjust"),
+    invalid(Mode::Protected, &[0x0f, 0xf7, 0x01]),
+    invalid(Mode::Protected, &[0x0f, 0x38, 0x3a, 0x06]),
+    invalid(Mode::Protected, &[0xc4, 0xc3, 0x79, 0x06, 0xca, 0x77]),
+    invalid(Mode::Protected, &[0xc4, 0xc2, 0xfd, 0x19, 0xca]),
+    invalid(Mode::Protected, &[0xc4, 0xc1, 0xb9, 0x17, 0x0a]),
+    invalid(Mode::Protected, &[0x0f, 0x36]),
+    invalid(Mode::Protected, &[0xda, 0xf6]),
+    invalid(Mode::Protected, &[0xdd, 0xff]),
+    vector(Mode::Real, &[0xac], "lods al, byte ds:[si]"),
+    vector(Mode::Real, &[0x62, 0xf1, 0x7c, 0x28, 0x29, 0x4a, 0x01], "vmovaps ymmword [bp + si + 0x20], ymm1"),
+    vector(Mode::Real, &[0x62, 0xf1, 0x7c, 0xad, 0x54, 0xca], "vandps ymm1{k5}{z}, ymm0, ymm2"),
+    vector(Mode::Real, &[0x62, 0xf1, 0x7d, 0x3d, 0x5b, 0x4a, 0x01], "vcvtps2dq ymm1{k5}, dword [bp + si + 0x4]{1to8}"),
+    vector(Mode::Real, &[0x62, 0xf1, 0x7e, 0x0d, 0xe6, 0xca], "vcvtdq2pd xmm1{k5}, xmm2"),
+    vector(Mode::Real, &[0x62, 0xf1, 0xfc, 0xad, 0x79, 0x0a], "vcvtpd2udq xmm1{k5}{z}, ymmword [bp + si]"),
+    vector(Mode::Real, &[0x62, 0xf1, 0xfd, 0x1d, 0x5a, 0xca], "vcvtpd2ps ymm1{k5}{rne-sae}, zmm2"),
+    vector(Mode::Real, &[0x62, 0xf1, 0xfd, 0x3d, 0x5a, 0x4a, 0x01], "vcvtpd2ps xmm1{k5}, qword [bp + si + 0x8]{1to4}"),
+    vector(Mode::Real, &[0x62, 0xf1, 0xfd, 0x5d, 0x5d, 0x0a], "vminpd zmm1{k5}, zmm0, qword [bp + si]{1to8}"),
+    vector(Mode::Real, &[0x62, 0xf1, 0xfd, 0xbd, 0xc6, 0x4a, 0x01, 0xcc], "vshufpd ymm1{k5}{z}, ymm0, qword [bp + si + 0x8]{1to4}, 0xcc"),
+    vector(Mode::Real, &[0x62, 0xf1, 0xff, 0x28, 0x12, 0xca], "vmovddup ymm1, ymm2"),
+    vector(Mode::Real, &[0x62, 0xf2, 0x7d, 0x0d, 0x2b, 0xca], "vpackusdw xmm1{k5}, xmm0, xmm2"),
+    vector(Mode::Real, &[0x62, 0xf2, 0x7d, 0x28, 0x71, 0xca], "vpshldvd ymm1, ymm0, ymm2"),
+    vector(Mode::Real, &[0x62, 0xf2, 0x7d, 0x3d, 0x3d, 0x0a], "vpmaxsd ymm1{k5}, ymm0, dword [bp + si]{1to8}"),
+    vector(Mode::Real, &[0x62, 0xf2, 0x7d, 0x4d, 0xc8, 0xca], "vexp2ps zmm1{k5}, zmm2"),
+    vector(Mode::Real, &[0x62, 0xf2, 0x7d, 0x9d, 0x3b, 0x0a], "vpminud xmm1{k5}{z}, xmm0, dword [bp + si]{1to4}"),
+    vector(Mode::Real, &[0x62, 0xf2, 0x7d, 0xcd, 0x4c, 0xca], "vrcp14ps zmm1{k5}{z}, zmm2"),
+    vector(Mode::Real, &[0x62, 0xf2, 0x7e, 0x2d, 0x27, 0xca], "vptestnmd k1{k5}, ymm0, ymm2"),
+    vector(Mode::Real, &[0x62, 0xf2, 0xfd, 0x08, 0xaa, 0xca], "vfmsub213pd xmm1, xmm0, xmm2"),
+    vector(Mode::Real, &[0x62, 0xf2, 0xfd, 0x28, 0x10, 0xca], "vpsrlvw ymm1, ymm0, ymm2"),
+    vector(Mode::Real, &[0x62, 0xf2, 0xfd, 0x2d, 0xa8, 0x4a, 0x01], "vfmadd213pd ymm1{k5}, ymm0, ymmword [bp + si + 0x20]"),
+    vector(Mode::Real, &[0x62, 0xf2, 0xfd, 0x48, 0xac, 0x0a], "vfnmadd213pd zmm1, zmm0, zmmword [bp + si]"),
+    vector(Mode::Real, &[0x62, 0xf2, 0xfd, 0x5d, 0xac, 0xca], "vfnmadd213pd zmm1{k5}{ru-sae}, zmm0, zmm2"),
+    vector(Mode::Real, &[0x62, 0xf2, 0xfd, 0xad, 0x3d, 0x4a, 0x01], "vpmaxsq ymm1{k5}{z}, ymm0, ymmword [bp + si + 0x20]"),
+    vector(Mode::Real, &[0x62, 0xf2, 0xfd, 0xcd, 0xb7, 0xca], "vfmsubadd231pd zmm1{k5}{z}, zmm0, zmm2"),
+    vector(Mode::Real, &[0x62, 0xf3, 0x7d, 0x2d, 0x57, 0xca, 0xcc], "vreducess xmm1{k5}, xmm0, xmm2, 0xcc"),
+    vector(Mode::Real, &[0x62, 0xf3, 0xfd, 0x08, 0x50, 0x0a, 0xcc], "vrangepd xmm1, xmm0, xmmword [bp + si], 0xcc"),
+    vector(Mode::Real, &[0x62, 0xf3, 0xfd, 0x4d, 0x50, 0xca, 0xcc], "vrangepd zmm1{k5}, zmm0, zmm2, 0xcc"),
+    vector(Mode::Real, &[0x66, 0x0f, 0xed, 0x01], "paddsw xmm0, xmmword [bx + di]"),
+    vector(Mode::Real, &[0xc4, 0xc2, 0x7d, 0x34, 0xca], "vpmovzxwq ymm1, xmm2"),
+    invalid(Mode::Real, &[0xea, 0x5b, 0xe0, 0x00]),
+    invalid(Mode::Real, &[0x0f, 0x3a, 0x15, 0x06]),
+    invalid(Mode::Real, &[0x66, 0x2e, 0x64, 0x66, 0x0f, 0x38, 0xf8, 0xe2]),
+    invalid(Mode::Real, &[0xc4, 0xc1, 0xf9, 0x72, 0x22, 0x77]),
+    invalid(Mode::Real, &[0xc4, 0xc2, 0x7d, 0x5a, 0xca]),
+    invalid(Mode::Real, &[0xc4, 0xc3, 0xf9, 0x02, 0xca, 0x77]),
+    invalid(Mode::Real, &[0xdb, 0x23]),
+    invalid(Mode::Real, &[0xdf, 0xe6]),
+];
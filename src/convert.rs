@@ -0,0 +1,204 @@
+//! conversions between the `RegSpec` and `Operand` types of `long_mode`, `protected_mode`, and
+//! `real_mode`, so code generic over modes can move operands between them.
+//!
+//! registers and operands of 16- and 32-bit code convert to each other exactly, by `From`. those
+//! of 64-bit code convert by `TryFrom`, as 64-bit code has registers and operands that other modes
+//! lack, and the other way around.
+
+use core::convert::TryFrom;
+
+use crate::{long_mode, protected_mode, real_mode, ConversionError};
+
+/// a register bank, named the same in every mode. each mode's `RegSpec` maps its own
+/// `RegisterBank` to and from these.
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum RegisterKind {
+    Q, D, W, B, rB,
+    CR, DR, S, Eip, Rip, EFlags, RFlags,
+    X, Y, Z,
+    ST, MM,
+    K,
+    T,
+    Bnd,
+}
+
+macro_rules! register_conversion {
+    ($from:ident => $to:ident) => {
+        impl TryFrom<$from::RegSpec> for $to::RegSpec {
+            type Error = ConversionError;
+
+            fn try_from(reg: $from::RegSpec) -> Result<Self, ConversionError> {
+                let (kind, num) = reg.kind();
+                $to::RegSpec::from_kind(kind, num).ok_or(ConversionError::Register)
+            }
+        }
+    };
+    ($from:ident => $to:ident, exact) => {
+        impl From<$from::RegSpec> for $to::RegSpec {
+            fn from(reg: $from::RegSpec) -> Self {
+                let (kind, num) = reg.kind();
+                $to::RegSpec::from_kind(kind, num)
+                    .expect("every register of the source mode exists in the target mode")
+            }
+        }
+    };
+}
+
+register_conversion!(long_mode => protected_mode);
+register_conversion!(long_mode => real_mode);
+register_conversion!(protected_mode => long_mode, exact);
+register_conversion!(real_mode => long_mode, exact);
+register_conversion!(protected_mode => real_mode, exact);
+register_conversion!(real_mode => protected_mode, exact);
+
+/// define `$name`, converting a `$from::Operand` to a `$to::Operand`. operands that all modes have
+/// convert variant-for-variant; `$extra` handles the rest, matching on `Operand` for the source
+/// mode's operand and building `To` for the target mode's.
+macro_rules! operand_conversion {
+    ($name:ident, $from:ident => $to:ident, { $($extra:tt)* }) => {
+        fn $name(op: $from::Operand) -> Result<$to::Operand, ConversionError> {
+            use $from::Operand;
+            use $to::Operand as To;
+
+            fn reg(reg: $from::RegSpec) -> Result<$to::RegSpec, ConversionError> {
+                let (kind, num) = reg.kind();
+                $to::RegSpec::from_kind(kind, num).ok_or(ConversionError::Register)
+            }
+
+            fn merge(mode: $from::MergeMode) -> $to::MergeMode {
+                match mode {
+                    $from::MergeMode::Merge => $to::MergeMode::Merge,
+                    $from::MergeMode::Zero => $to::MergeMode::Zero,
+                }
+            }
+
+            fn sae(mode: $from::SaeMode) -> $to::SaeMode {
+                match mode {
+                    $from::SaeMode::RoundNearest => $to::SaeMode::RoundNearest,
+                    $from::SaeMode::RoundDown => $to::SaeMode::RoundDown,
+                    $from::SaeMode::RoundUp => $to::SaeMode::RoundUp,
+                    $from::SaeMode::RoundZero => $to::SaeMode::RoundZero,
+                }
+            }
+
+            Ok(match op {
+                Operand::ImmediateI8(imm) => To::ImmediateI8(imm),
+                Operand::ImmediateU8(imm) => To::ImmediateU8(imm),
+                Operand::ImmediateI16(imm) => To::ImmediateI16(imm),
+                Operand::ImmediateU16(imm) => To::ImmediateU16(imm),
+                Operand::ImmediateI32(imm) => To::ImmediateI32(imm),
+                Operand::ImmediateU32(imm) => To::ImmediateU32(imm),
+                Operand::Register(r) => To::Register(reg(r)?),
+                Operand::RegisterMaskMerge(r, mask, mode) => {
+                    To::RegisterMaskMerge(reg(r)?, reg(mask)?, merge(mode))
+                }
+                Operand::RegisterMaskMergeSae(r, mask, mode, rounding) => {
+                    To::RegisterMaskMergeSae(reg(r)?, reg(mask)?, merge(mode), sae(rounding))
+                }
+                Operand::RegisterMaskMergeSaeNoround(r, mask, mode) => {
+                    To::RegisterMaskMergeSaeNoround(reg(r)?, reg(mask)?, merge(mode))
+                }
+                Operand::DisplacementU32(disp) => To::DisplacementU32(disp),
+                Operand::RegDeref(base) => To::RegDeref(reg(base)?),
+                Operand::RegDisp(base, disp) => To::RegDisp(reg(base)?, disp),
+                Operand::RegScale(index, scale) => To::RegScale(reg(index)?, scale),
+                Operand::RegIndexBase(base, index) => To::RegIndexBase(reg(base)?, reg(index)?),
+                Operand::RegIndexBaseDisp(base, index, disp) => {
+                    To::RegIndexBaseDisp(reg(base)?, reg(index)?, disp)
+                }
+                Operand::RegScaleDisp(index, scale, disp) => {
+                    To::RegScaleDisp(reg(index)?, scale, disp)
+                }
+                Operand::RegIndexBaseScale(base, index, scale) => {
+                    To::RegIndexBaseScale(reg(base)?, reg(index)?, scale)
+                }
+                Operand::RegIndexBaseScaleDisp(base, index, scale, disp) => {
+                    To::RegIndexBaseScaleDisp(reg(base)?, reg(index)?, scale, disp)
+                }
+                Operand::RegDerefMasked(base, mask) => To::RegDerefMasked(reg(base)?, reg(mask)?),
+                Operand::RegDispMasked(base, disp, mask) => {
+                    To::RegDispMasked(reg(base)?, disp, reg(mask)?)
+                }
+                Operand::RegScaleMasked(index, scale, mask) => {
+                    To::RegScaleMasked(reg(index)?, scale, reg(mask)?)
+                }
+                Operand::RegIndexBaseMasked(base, index, mask) => {
+                    To::RegIndexBaseMasked(reg(base)?, reg(index)?, reg(mask)?)
+                }
+                Operand::RegIndexBaseDispMasked(base, index, disp, mask) => {
+                    To::RegIndexBaseDispMasked(reg(base)?, reg(index)?, disp, reg(mask)?)
+                }
+                Operand::RegScaleDispMasked(index, scale, disp, mask) => {
+                    To::RegScaleDispMasked(reg(index)?, scale, disp, reg(mask)?)
+                }
+                Operand::RegIndexBaseScaleMasked(base, index, scale, mask) => {
+                    To::RegIndexBaseScaleMasked(reg(base)?, reg(index)?, scale, reg(mask)?)
+                }
+                Operand::RegIndexBaseScaleDispMasked(base, index, scale, disp, mask) => {
+                    To::RegIndexBaseScaleDispMasked(reg(base)?, reg(index)?, scale, disp, reg(mask)?)
+                }
+                Operand::Nothing => To::Nothing,
+                $($extra)*
+            })
+        }
+    };
+}
+
+operand_conversion!(long_to_protected, long_mode => protected_mode, {
+    Operand::ImmediateI64(_) | Operand::ImmediateU64(_) | Operand::DisplacementU64(_) => {
+        return Err(ConversionError::Operand);
+    }
+});
+operand_conversion!(long_to_real, long_mode => real_mode, {
+    Operand::ImmediateI64(_) | Operand::ImmediateU64(_) | Operand::DisplacementU64(_) => {
+        return Err(ConversionError::Operand);
+    }
+});
+operand_conversion!(protected_to_long, protected_mode => long_mode, {
+    Operand::DisplacementU16(_) | Operand::AbsoluteFarAddress { .. } => {
+        return Err(ConversionError::Operand);
+    }
+});
+operand_conversion!(real_to_long, real_mode => long_mode, {
+    Operand::DisplacementU16(_) | Operand::AbsoluteFarAddress { .. } => {
+        return Err(ConversionError::Operand);
+    }
+});
+operand_conversion!(protected_to_real, protected_mode => real_mode, {
+    Operand::DisplacementU16(disp) => To::DisplacementU16(disp),
+    Operand::AbsoluteFarAddress { segment, address } => To::AbsoluteFarAddress { segment, address },
+});
+operand_conversion!(real_to_protected, real_mode => protected_mode, {
+    Operand::DisplacementU16(disp) => To::DisplacementU16(disp),
+    Operand::AbsoluteFarAddress { segment, address } => To::AbsoluteFarAddress { segment, address },
+});
+
+macro_rules! operand_try_from {
+    ($convert:ident, $from:ident => $to:ident) => {
+        impl TryFrom<$from::Operand> for $to::Operand {
+            type Error = ConversionError;
+
+            fn try_from(op: $from::Operand) -> Result<Self, ConversionError> {
+                $convert(op)
+            }
+        }
+    };
+}
+
+operand_try_from!(long_to_protected, long_mode => protected_mode);
+operand_try_from!(long_to_real, long_mode => real_mode);
+operand_try_from!(protected_to_long, protected_mode => long_mode);
+operand_try_from!(real_to_long, real_mode => long_mode);
+
+impl From<protected_mode::Operand> for real_mode::Operand {
+    fn from(op: protected_mode::Operand) -> Self {
+        protected_to_real(op).expect("every 32-bit operand has a 16-bit form")
+    }
+}
+
+impl From<real_mode::Operand> for protected_mode::Operand {
+    fn from(op: real_mode::Operand) -> Self {
+        real_to_protected(op).expect("every 16-bit operand has a 32-bit form")
+    }
+}
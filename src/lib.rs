@@ -121,19 +121,1545 @@ pub use protected_mode::Arch as x86_32;
 pub mod real_mode;
 pub use real_mode::Arch as x86_16;
 
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "std")]
+pub mod mode_switch;
+
+#[cfg(feature = "conformance")]
+pub mod conformance;
+
 mod safer_unchecked;
+mod convert;
+
+/// build an `InstructionPattern` from an opcode and patterns for its leading operands, in any of
+/// `long_mode`, `protected_mode`, or `real_mode`. each operand is anything that converts into
+/// that mode's `OperandPattern`: an `OperandPattern` itself, a `RegSpec` to match that register,
+/// or an `Operand` to match exactly. operands past the last one given are unconstrained.
+///
+/// for example, to find the frame pointer setup of a typical function prologue:
+/// ```
+/// use yaxpeax_x86::pattern;
+/// use yaxpeax_x86::long_mode::{InstDecoder, Opcode, OperandPattern, RegSpec};
+///
+/// let push_rbp = pattern!(Opcode::PUSH, RegSpec::rbp());
+/// let mov_rbp_rsp = pattern!(Opcode::MOV, RegSpec::rbp(), RegSpec::rsp());
+/// let stack_store = pattern!(Opcode::MOV, OperandPattern::MemoryBase(RegSpec::rsp()), OperandPattern::Any);
+///
+/// let decoder = InstDecoder::default();
+/// assert!(decoder.decode_slice(&[0x55]).unwrap().matches(&push_rbp));
+/// assert!(decoder.decode_slice(&[0x48, 0x89, 0xe5]).unwrap().matches(&mov_rbp_rsp));
+/// // mov qword [rsp + 0x8], rbx
+/// assert!(decoder.decode_slice(&[0x48, 0x89, 0x5c, 0x24, 0x08]).unwrap().matches(&stack_store));
+/// ```
+#[macro_export]
+macro_rules! pattern {
+    ($opcode:expr $(, $operand:expr)* $(,)?) => {
+        $opcode.pattern().with_operands(&[$(::core::convert::Into::into($operand)),*])
+    };
+}
+
+/// how an instruction accesses one of its operands.
+///
+/// for memory operands, this describes the access to memory itself; registers used to compute the
+/// address of a memory operand are always read.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AccessKind {
+    /// the operand is read, but not written.
+    Read,
+    /// the operand is written, but not read.
+    Write,
+    /// the operand is read and then written.
+    ReadWrite,
+    /// the operand may or may not be written, depending on a condition or mask that can't be
+    /// known at decode time. like `ReadWrite`, some or all of the operand's prior value may
+    /// survive the instruction, but the operand itself is not an input.
+    CondWrite,
+}
+
+impl AccessKind {
+    /// does this access read the operand?
+    pub fn is_read(&self) -> bool {
+        match self {
+            AccessKind::Read | AccessKind::ReadWrite => true,
+            AccessKind::Write | AccessKind::CondWrite => false,
+        }
+    }
+
+    /// does this access write the operand? this is `true` for `CondWrite`, even though the write
+    /// may not happen.
+    pub fn is_write(&self) -> bool {
+        match self {
+            AccessKind::Write | AccessKind::ReadWrite | AccessKind::CondWrite => true,
+            AccessKind::Read => false,
+        }
+    }
+}
+
+/// a processor descriptor table, loaded or stored through a pseudo-descriptor by `lgdt`, `lidt`,
+/// `sgdt`, and `sidt`.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DescriptorTable {
+    /// the global descriptor table, `gdtr`.
+    Global,
+    /// the interrupt descriptor table, `idtr`.
+    Interrupt,
+}
+
+/// the address size a memory operand is computed with, which selects how its `modrm` and `sib`
+/// bytes are read. see `Operand::addressing_form` and `Instruction::addressing_form` in each
+/// mode.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AddressingForm {
+    /// 16-bit addressing, with no `sib` byte: `modrm` selects one of `bx + si`, `bx + di`,
+    /// `bp + si`, `bp + di`, `si`, `di`, `bp`, or `bx`, with an 8- or 16-bit displacement, or a
+    /// bare 16-bit displacement. addresses wrap at 64k. the default in real mode, and selected by
+    /// an address-size prefix in protected mode.
+    Bits16,
+    /// 32-bit addressing, with a `sib` byte for scaled indices. the default in protected mode, and
+    /// selected by an address-size prefix in real mode and long mode.
+    Bits32,
+    /// 64-bit addressing, the default in long mode.
+    Bits64,
+}
+
+/// what one kind of prefix did to an instruction. see [`PrefixEffects`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PrefixEffect {
+    /// the instruction was not decoded with this prefix.
+    Absent,
+    /// the prefix selects the instruction's opcode, like the `f3` of `popcnt` or `pause`.
+    Opcode,
+    /// the prefix changes what the instruction does: the size of its operands or addresses, the
+    /// segment it accesses memory through, or whether it repeats or is locked.
+    Effective,
+    /// the prefix has no effect, like `66` on `nop`, a segment override on `lea`, or `rep` on
+    /// `ret`. hints that processors may act on but that do not change the result of the
+    /// instruction, like branch hints, `bnd`, and `xacquire`, are also ignored prefixes.
+    Ignored,
+}
+
+/// the effect of each kind of prefix an instruction was decoded with, for telling the bytes that
+/// matter from those that are semantic no-ops. see
+/// [`Instruction::prefix_effects`](long_mode::Instruction::prefix_effects).
+///
+/// only one of `rep` and `repnz` is reported, for whichever of `f3` or `f2` came last; the decoder
+/// discards the other. likewise, only the last segment override is reported, and in 64-bit code
+/// the `cs`, `ds`, `es`, and `ss` overrides that the decoder discards are not reported at all.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PrefixEffects {
+    /// a `lock` (`f0`) prefix.
+    pub lock: PrefixEffect,
+    /// a `rep` or `repz` (`f3`) prefix.
+    pub rep: PrefixEffect,
+    /// a `repnz` (`f2`) prefix.
+    pub repnz: PrefixEffect,
+    /// a segment override prefix.
+    pub segment: PrefixEffect,
+    /// an operand-size (`66`) prefix. a `66` that selects an opcode in the `0f38` or `0f3a` maps,
+    /// as for `pshufb`, is part of that opcode and reported as `Absent`, as
+    /// `Prefixes::operand_size` does; one selecting an opcode in the `0f` map, as for `addpd`, is
+    /// reported as `Opcode`.
+    pub operand_size: PrefixEffect,
+    /// an address-size (`67`) prefix.
+    pub address_size: PrefixEffect,
+    /// the `w` bit of a `rex` or `rex2` prefix. always `Absent` outside of 64-bit code.
+    pub rex_w: PrefixEffect,
+}
+
+impl PrefixEffects {
+    /// is any prefix of this instruction ignored? an instruction with no ignored prefixes can
+    /// still have redundant prefix bytes, like a second `66`, that the decoder discards before
+    /// reporting prefixes at all.
+    pub fn any_ignored(&self) -> bool {
+        [
+            self.lock,
+            self.rep,
+            self.repnz,
+            self.segment,
+            self.operand_size,
+            self.address_size,
+            self.rex_w,
+        ].contains(&PrefixEffect::Ignored)
+    }
+}
+
+/// the reasons `Instruction::synthesize` may not be able to build an instruction, in each mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SynthesizeError {
+    /// more than four operands were provided.
+    TooManyOperands,
+    /// more than one memory operand was provided.
+    TooManyMemoryOperands,
+    /// the operands name more registers than an instruction has room for: three, or two alongside
+    /// a memory operand, and a fourth only if there is no immediate.
+    TooManyRegisters,
+    /// the operands include more immediates than an instruction has room for: one, or two if one
+    /// of them is an `ImmediateU16`, as for `enter`, and there is no memory operand.
+    TooManyImmediates,
+    /// an operand that decoded instructions never have, and so can't be represented: `Nothing`,
+    /// an `ImmediateU32` or `ImmediateU64`, or an operand with an `avx512` mask.
+    UnsupportedOperand,
+}
+
+impl SynthesizeError {
+    /// a human-friendly description of this error.
+    pub fn description(&self) -> &'static str {
+        match self {
+            SynthesizeError::TooManyOperands => "too many operands",
+            SynthesizeError::TooManyMemoryOperands => "too many memory operands",
+            SynthesizeError::TooManyRegisters => "too many registers",
+            SynthesizeError::TooManyImmediates => "too many immediates",
+            SynthesizeError::UnsupportedOperand => "unsupported operand",
+        }
+    }
+}
+
+impl core::fmt::Display for SynthesizeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// why a `RegSpec` or `Operand` from one mode has no equivalent in another, for the `TryFrom`
+/// conversions between `long_mode`, `protected_mode`, and `real_mode` types.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ConversionError {
+    /// a register that does not exist in the other mode, like `rax`, `r8d`, `spl`, or `xmm8`
+    /// outside of 64-bit code.
+    Register,
+    /// an operand with no form in the other mode: a 64-bit immediate or displacement outside of
+    /// 64-bit code, or a 16-bit displacement or far address in 64-bit code.
+    Operand,
+}
+
+impl ConversionError {
+    /// a human-friendly description of this error.
+    pub fn description(&self) -> &'static str {
+        match self {
+            ConversionError::Register => "register does not exist in this mode",
+            ConversionError::Operand => "operand does not exist in this mode",
+        }
+    }
+}
+
+impl core::fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// why an instruction's relative branch target or `rip`-relative operand could not be rewritten by
+/// `Instruction::relocate` or `Instruction::retarget`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RelocateError {
+    /// the instruction was decoded without keeping its bytes; see
+    /// `InstDecoder::with_retain_bytes`.
+    NoBytes,
+    /// the instruction has no relative branch target or `rip`-relative operand.
+    NotRelative,
+    /// the new target is farther away than any form of the instruction can reach, as for a `loop`
+    /// more than 128 bytes from its target, or is outside the address space the instruction can
+    /// branch or refer to.
+    OutOfRange,
+    /// reaching the new target needs a longer form of the instruction, which would be more than
+    /// 15 bytes long.
+    TooLong,
+}
+
+impl RelocateError {
+    /// a human-friendly description of this error.
+    pub fn description(&self) -> &'static str {
+        match self {
+            RelocateError::NoBytes => "instruction bytes were not retained",
+            RelocateError::NotRelative => "instruction has no relative target",
+            RelocateError::OutOfRange => "target is out of range",
+            RelocateError::TooLong => "relocated instruction would be too long",
+        }
+    }
+}
+
+impl core::fmt::Display for RelocateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// how an immediate's encoded bits are widened to the operand size of its instruction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ImmExtension {
+    /// the immediate's top bit is copied into every higher bit, as in the `imm8` of `83 /0`
+    /// (`add r/m, imm8`) or the `imm32` of `48 05` (`add rax, imm32`).
+    Sign,
+    /// higher bits are zero, as in the `imm8` of `int`, `in`, or `enter`, and the `imm16` of `ret`.
+    Zero,
+}
+
+/// an immediate operand as its instruction uses it: the bytes it was encoded in, how those bytes
+/// are extended, and the extended value.
+///
+/// the value is extended to 64 bits; an instruction operating on fewer bits uses the low bits of
+/// that, which [`ImmValue::value_for_size`] selects:
+/// ```
+/// use yaxpeax_x86::{ImmExtension, ImmValue};
+///
+/// // the `ff` of `83 c0 ff`, `add eax, -1`.
+/// let imm = ImmValue::new(1, ImmExtension::Sign, 0xff);
+/// assert_eq!(imm.value(), 0xffff_ffff_ffff_ffff);
+/// assert_eq!(imm.value_for_size(4), 0xffff_ffff);
+///
+/// // the `ff` of `b0 ff`, `mov al, 0xff`: all of its bits are encoded.
+/// let imm = ImmValue::new(1, ImmExtension::Zero, 0xff);
+/// assert_eq!(imm.value(), 0xff);
+/// assert_eq!(imm.signed(), 255);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ImmValue {
+    size: u8,
+    extension: ImmExtension,
+    value: u64,
+}
+
+impl ImmValue {
+    /// an immediate encoded in the low `size` bytes of `bits`, extended as `extension` describes.
+    /// bits above `size` bytes are ignored.
+    ///
+    /// panics if `size` is not 1, 2, 4, or 8.
+    pub fn new(size: u8, extension: ImmExtension, bits: u64) -> ImmValue {
+        let shift = match size {
+            1 | 2 | 4 | 8 => 64 - size as u32 * 8,
+            _ => panic!("immediates are 1, 2, 4, or 8 bytes, not {}", size),
+        };
+        let value = match extension {
+            ImmExtension::Sign => (((bits << shift) as i64) >> shift) as u64,
+            ImmExtension::Zero => (bits << shift) >> shift,
+        };
+        ImmValue { size, extension, value }
+    }
+
+    /// the number of bytes the immediate is encoded in: 1, 2, 4, or 8.
+    pub fn size(&self) -> u8 {
+        self.size
+    }
+
+    /// how the encoded bytes are extended to the instruction's operand size.
+    pub fn extension(&self) -> ImmExtension {
+        self.extension
+    }
+
+    /// the immediate extended to 64 bits.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// the immediate extended to 64 bits, as a signed number. this is the `i64` an operand's
+    /// `ImmediateI*` or `ImmediateU*` variant converts to.
+    pub fn signed(&self) -> i64 {
+        self.value as i64
+    }
+
+    /// the immediate extended to an operand of `size` bytes, with any bits above `size` bytes
+    /// zero. `size` is usually the operand size of the instruction, such as 4 for
+    /// `add eax, -1`.
+    ///
+    /// panics if `size` is not 1, 2, 4, or 8.
+    pub fn value_for_size(&self, size: u8) -> u64 {
+        ImmValue::new(size, ImmExtension::Zero, self.value).value
+    }
+}
+
+/// an instruction decoded in any mode, for code generic over `long_mode`, `protected_mode`, and
+/// `real_mode`. each mode's `Instruction` implements this, with its own `Operand` as the operand
+/// type, so generic code sees operands as the mode decoded them rather than converted to another
+/// mode's.
+pub trait X86Instruction {
+    /// the operand type of the instruction's mode.
+    type Operand: X86Operand;
+
+    /// the number of operands the instruction has.
+    fn operand_count(&self) -> u8;
+
+    /// operand `i` of the instruction, as `Instruction::operand` in each mode.
+    fn operand(&self, i: u8) -> Self::Operand;
+}
+
+/// an operand of an instruction decoded in any mode. see [`X86Instruction`].
+pub trait X86Operand {
+    /// the register type of the operand's mode.
+    type Register: Copy + PartialEq + core::fmt::Debug;
+
+    /// does this operand access memory? as `Operand::is_memory` in each mode.
+    fn is_memory(&self) -> bool;
+
+    /// the registers this operand names: the register of a register operand, or the base and
+    /// index of a memory operand, followed by an `avx512` mask register other than `k0`.
+    fn registers(&self) -> OperandRegisters<Self::Register>;
+
+    /// the value of an immediate operand, sign- or zero-extended to 64 bits as its variant says,
+    /// or `None` for operands that are not immediates.
+    fn immediate(&self) -> Option<i64>;
+}
+
+/// the registers an operand names, from [`X86Operand::registers`]: at most a base, an index, and
+/// a mask.
+#[derive(Copy, Clone, Debug)]
+pub struct OperandRegisters<R> {
+    regs: [Option<R>; 3],
+    next: usize,
+}
+
+impl<R> OperandRegisters<R> {
+    pub(crate) fn new(regs: [Option<R>; 3]) -> Self {
+        OperandRegisters { regs, next: 0 }
+    }
+}
+
+impl<R: Copy> Iterator for OperandRegisters<R> {
+    type Item = R;
+
+    fn next(&mut self) -> Option<R> {
+        while self.next < self.regs.len() {
+            let reg = self.regs[self.next];
+            self.next += 1;
+            if reg.is_some() {
+                return reg;
+            }
+        }
+        None
+    }
+}
+
+/// what a write to a register operand does to the bits of the register it does not name. see
+/// `Instruction::write_behavior` in each mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum WriteBehavior {
+    /// the write replaces the whole register.
+    Full,
+    /// the write clears the bits of the register beyond those it names: a write to a 32-bit
+    /// general-purpose register in 64-bit code clears the upper half of the 64-bit register, and a
+    /// `vex`, `xop`, or `evex`-encoded write to an `xmm` or `ymm` register clears the rest of its
+    /// vector register.
+    ZeroExtend,
+    /// the write keeps the bits it does not name: writes to 8- and 16-bit general-purpose
+    /// registers, legacy sse writes to `xmm` registers, which keep the upper bits of the `ymm` and
+    /// `zmm` register, and merge-masked `avx512` writes, which keep elements whose opmask bit is
+    /// clear.
+    Merge,
+}
+
+/// what a `DecodeIterator` does when the bytes at some address do not decode. see
+/// `DecodeIterator::with_recovery` in each mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorRecovery {
+    /// end iteration, keeping the error for `DecodeIterator::error`.
+    Stop,
+    /// move on to the next byte without reporting anything.
+    SkipByte,
+    /// report the byte as `Decoded::Byte`, displayed like `.byte 0x0f`, then move on to the next
+    /// byte.
+    EmitByte,
+}
+
+/// which parts of an instruction `Instruction::semantic_hash` ignores, in each mode. by default,
+/// nothing is ignored, and two instructions hash the same only if they decoded to the same
+/// opcode and operands.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct HashOptions {
+    ignore_displacements: bool,
+    ignore_immediates: bool,
+    ignore_registers: bool,
+}
+
+impl HashOptions {
+    /// options that ignore nothing.
+    pub fn new() -> Self {
+        HashOptions::default()
+    }
+
+    /// ignore the displacements of memory operands, including absolute addresses, so
+    /// `mov eax, [rbp - 0x8]` and `mov eax, [rbp - 0x10]` hash the same.
+    pub fn ignore_displacements(mut self) -> Self {
+        self.ignore_displacements = true;
+        self
+    }
+
+    /// ignore the values of immediate operands, so `add eax, 0x1` and `add eax, 0x2` hash the
+    /// same.
+    pub fn ignore_immediates(mut self) -> Self {
+        self.ignore_immediates = true;
+        self
+    }
+
+    /// ignore which register an operand names, keeping only its bank: `add eax, ecx` and
+    /// `add edx, ebx` hash the same, but `add rax, rcx` does not.
+    pub fn ignore_registers(mut self) -> Self {
+        self.ignore_registers = true;
+        self
+    }
+}
+
+/// a coarse classification of what an instruction does. see `Opcode::category` in each mode.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Category {
+    /// jumps, conditional or otherwise, including `loop` and `jrcxz`-style branches.
+    Branch,
+    /// near and far calls.
+    Call,
+    /// returns from calls, interrupts, system calls, or other privilege transitions.
+    Ret,
+    /// software interrupts, system calls, hypercalls, and instructions that always fault, like
+    /// `ud2`.
+    Interrupt,
+    /// integer arithmetic, including comparisons.
+    Arithmetic,
+    /// bitwise operations, shifts, rotates, and bit tests and scans.
+    Logic,
+    /// moves between registers and memory, including conditional moves, `setcc`, and
+    /// exchanges.
+    DataTransfer,
+    /// instructions that set, clear, or move the arithmetic or direction flags.
+    Flag,
+    /// pushes, pops, and stack frame setup and teardown.
+    Stack,
+    /// string instructions: `movs`, `cmps`, `scas`, `lods`, and `stos`.
+    String,
+    /// port i/o, including `ins` and `outs`.
+    Io,
+    /// x87 floating point instructions.
+    X87,
+    /// mmx, 3dnow, sse, avx, and avx512 instructions, including mask register operations.
+    Simd,
+    /// aes, sha, carry-less multiplication, and galois field instructions.
+    Crypto,
+    /// processor state, privileged, virtualization, cache control, and other system
+    /// instructions.
+    System,
+    /// instructions that do nothing, including `endbr64` and x87 no-ops.
+    Nop,
+    /// the `Invalid` opcode.
+    Invalid,
+}
+
+impl Category {
+    /// can instructions in this category change the instruction pointer to somewhere other than
+    /// the next instruction?
+    pub fn is_control_flow(&self) -> bool {
+        matches!(self, Category::Branch | Category::Call | Category::Ret | Category::Interrupt)
+    }
+}
+
+/// what an `f2` or `f3` prefix on an instruction does. see `Instruction::rep_semantics` in each
+/// mode.
+///
+/// the `rep`, `repz`, and `repnz` bits of an instruction's prefixes only say which of the bytes
+/// were present; the same `f3` repeats `movs`, makes `nop` a `pause`, is part of the opcode of
+/// `popcnt`, and does nothing at all before `add`.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RepSemantics {
+    /// there is no `f2` or `f3` prefix.
+    None,
+    /// a string instruction repeated until `rcx`, `ecx`, or `cx` is zero: `movs`, `lods`,
+    /// `stos`, `ins`, and `outs`, with either prefix.
+    Repeat,
+    /// `repz cmps` or `repz scas`: repeated until the count is zero or the comparison finds a
+    /// difference.
+    RepeatWhileEqual,
+    /// `repnz cmps` or `repnz scas`: repeated until the count is zero or the comparison finds a
+    /// match.
+    RepeatWhileNotEqual,
+    /// `f2` is an `xacquire` hint for hardware lock elision.
+    Xacquire,
+    /// `f3` is an `xrelease` hint for hardware lock elision.
+    Xrelease,
+    /// the prefix is part of the opcode, as with `popcnt`, `movss`, `endbr64`, or `crc32`, and
+    /// does not repeat anything.
+    Mandatory,
+    /// `f3 90`, which is `pause` rather than `nop`.
+    Pause,
+    /// `f2` on a near `call`, `jmp`, `ret`, or conditional jump is the `bnd` prefix for mpx.
+    Bnd,
+    /// the prefix has no effect on this instruction.
+    Ignored,
+}
+
+/// the type of each element of a vector operand. see `Instruction::vector_shape` in each mode.
+///
+/// integer elements are signed where the instruction treats them as signed, as `pcmpgtb`,
+/// `psraw`, or `paddsw` do, and unsigned otherwise, including for operations like `paddb` where
+/// signedness makes no difference. bitwise operations on a whole register, such as `pand`, are
+/// described as operating on `U64` elements.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ElementType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F16,
+    BF16,
+    F32,
+    F64,
+}
+
+impl ElementType {
+    /// the size of one element of this type, in bits.
+    pub const fn bits(&self) -> u16 {
+        match self {
+            ElementType::I8 | ElementType::U8 => 8,
+            ElementType::I16 | ElementType::U16 | ElementType::F16 | ElementType::BF16 => 16,
+            ElementType::I32 | ElementType::U32 | ElementType::F32 => 32,
+            ElementType::I64 | ElementType::U64 | ElementType::F64 => 64,
+        }
+    }
+
+    /// whether this is a floating point type.
+    pub const fn is_float(&self) -> bool {
+        matches!(self,
+            ElementType::F16 | ElementType::BF16 | ElementType::F32 | ElementType::F64)
+    }
+}
+
+/// how a vector operand is divided into elements: `pshufb xmm0, xmm1` operates on sixteen `U8`,
+/// and `vaddpd ymm0, ymm1, ymm2` on four `F64`. see `Instruction::vector_shape` in each mode.
+///
+/// scalar operations like `addss` operate on one element at the bottom of their registers, and
+/// are described with a `count` of `1`.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct VectorShape {
+    /// the type of each element.
+    pub element: ElementType,
+    /// how many elements the instruction operates on.
+    pub count: u8,
+}
+
+impl VectorShape {
+    /// the number of bits of the operand the instruction operates on, `element.bits() * count`.
+    pub const fn bits(&self) -> u16 {
+        self.element.bits() * self.count as u16
+    }
+}
+
+/// how loosely `Instruction::is_nop` in each mode interprets "does nothing". each level accepts
+/// everything the levels before it do.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NopStrictness {
+    /// only `nop` itself, in any of its encodings: `90`, `66 90`, the multi-byte `0f 1f /0`
+    /// forms, and the reserved `0f 19`-`0f 1f` hint space. `endbr32`, `endbr64`, and the x87
+    /// no-ops are not included, since they are not used as padding; `endbr` in particular marks
+    /// the start of a function.
+    Canonical,
+    /// also instructions that have no architectural effect, as assemblers and compilers emit for
+    /// alignment: `xchg` or `mov` of a register with itself and `lea` of a register from itself
+    /// with no displacement, such as `mov edi, edi` and `lea esi, [esi + 0x0]`. in 64-bit code,
+    /// these are not no-ops with 32-bit registers, which zero the upper half of the register.
+    Equivalent,
+    /// also `int3`, which compilers use to fill the space between functions so that running off
+    /// the end of one traps.
+    Padding,
+}
+
+/// the privilege an instruction needs to run without faulting. see `Instruction::required_cpl` in
+/// each mode.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PrivilegeLevel {
+    /// any privilege level, including ring 3.
+    User,
+    /// any privilege level unless the operating system restricts it to ring 0 with a control
+    /// register bit: `rdtsc` and `rdtscp` with `CR4.TSD`, `rdpmc` without `CR4.PCE`, and `sgdt`,
+    /// `sidt`, `sldt`, `str`, and `smsw` with `CR4.UMIP`.
+    Configurable,
+    /// a privilege level at least as privileged as `IOPL` in `rflags`: `cli`, `sti`, and port
+    /// i/o. port i/o is also permitted at any privilege level for ports the task's i/o permission
+    /// bitmap allows.
+    Iopl,
+    /// ring 0 only.
+    Kernel,
+}
+
+/// a state component of the `xsave` feature set, numbered by its bit in `XCR0` and `IA32_XSS`.
+/// see `Instruction::xsave_layout` in each mode.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum XsaveComponent {
+    /// x87 state: the fpu control, status, and tag words, and `st(0)` through `st(7)`.
+    X87 = 0,
+    /// sse state: `xmm0` through `xmm15`, and `mxcsr`, which is stored with the x87 state.
+    Sse = 1,
+    /// the upper halves of `ymm0` through `ymm15`.
+    Avx = 2,
+    /// mpx bound registers `bnd0` through `bnd3`.
+    BndRegs = 3,
+    /// mpx `BNDCFGU` and `BNDSTATUS`.
+    BndCsr = 4,
+    /// avx512 mask registers `k0` through `k7`.
+    Opmask = 5,
+    /// the upper halves of `zmm0` through `zmm15`.
+    ZmmHi256 = 6,
+    /// all of `zmm16` through `zmm31`.
+    Hi16Zmm = 7,
+    /// processor trace state. a supervisor component.
+    Pt = 8,
+    /// the protection key rights register, `pkru`.
+    Pkru = 9,
+    /// the `PASID` of `enqcmd`. a supervisor component.
+    Pasid = 10,
+    /// user mode cet state. a supervisor component.
+    CetU = 11,
+    /// supervisor mode cet shadow stack pointers. a supervisor component.
+    CetS = 12,
+    /// hardware duty cycling state. a supervisor component.
+    Hdc = 13,
+    /// user interrupt state. a supervisor component.
+    Uintr = 14,
+    /// architectural lbr state, whose size depends on the number of lbr records. a supervisor
+    /// component.
+    Lbr = 15,
+    /// hardware p-state state. a supervisor component.
+    Hwp = 16,
+    /// amx `tilecfg`.
+    XtileCfg = 17,
+    /// amx tile registers `tmm0` through `tmm7`.
+    XtileData = 18,
+    /// apx extended general purpose registers, `r16` through `r31`. stored where mpx state
+    /// would be, since the two are not supported together.
+    Apx = 19,
+}
+
+impl XsaveComponent {
+    const ALL: [XsaveComponent; 20] = [
+        XsaveComponent::X87, XsaveComponent::Sse, XsaveComponent::Avx, XsaveComponent::BndRegs,
+        XsaveComponent::BndCsr, XsaveComponent::Opmask, XsaveComponent::ZmmHi256,
+        XsaveComponent::Hi16Zmm, XsaveComponent::Pt, XsaveComponent::Pkru, XsaveComponent::Pasid,
+        XsaveComponent::CetU, XsaveComponent::CetS, XsaveComponent::Hdc, XsaveComponent::Uintr,
+        XsaveComponent::Lbr, XsaveComponent::Hwp, XsaveComponent::XtileCfg,
+        XsaveComponent::XtileData, XsaveComponent::Apx,
+    ];
+
+    /// the component numbered `bit`, if it is one of these.
+    pub fn from_bit(bit: u8) -> Option<XsaveComponent> {
+        XsaveComponent::ALL.get(bit as usize).cloned()
+    }
+
+    /// this component's bit in `XCR0`, `IA32_XSS`, and the masks of `xsave` instructions.
+    pub fn bit(&self) -> u8 {
+        *self as u8
+    }
+
+    /// whether this component is enabled in `IA32_XSS` rather than `XCR0`, and is only saved and
+    /// restored by `xsaves` and `xrstors`.
+    pub fn is_supervisor(&self) -> bool {
+        matches!(self,
+            XsaveComponent::Pt | XsaveComponent::Pasid | XsaveComponent::CetU |
+            XsaveComponent::CetS | XsaveComponent::Hdc | XsaveComponent::Uintr |
+            XsaveComponent::Lbr | XsaveComponent::Hwp)
+    }
+
+    /// the size of this component's state, in bytes, as `cpuid` leaf `0xd` reports it. `None`
+    /// for `Lbr`, whose size depends on the processor.
+    pub fn size(&self) -> Option<u32> {
+        let size = match self {
+            XsaveComponent::X87 => 160,
+            XsaveComponent::Sse => 256,
+            XsaveComponent::Avx => 256,
+            XsaveComponent::BndRegs => 64,
+            XsaveComponent::BndCsr => 64,
+            XsaveComponent::Opmask => 64,
+            XsaveComponent::ZmmHi256 => 512,
+            XsaveComponent::Hi16Zmm => 1024,
+            XsaveComponent::Pt => 128,
+            XsaveComponent::Pkru => 8,
+            XsaveComponent::Pasid => 8,
+            XsaveComponent::CetU => 16,
+            XsaveComponent::CetS => 24,
+            XsaveComponent::Hdc => 8,
+            XsaveComponent::Uintr => 48,
+            XsaveComponent::Lbr => { return None; }
+            XsaveComponent::Hwp => 8,
+            XsaveComponent::XtileCfg => 64,
+            XsaveComponent::XtileData => 8192,
+            XsaveComponent::Apx => 128,
+        };
+        Some(size)
+    }
+
+    /// the offset of this component in the standard, uncompacted, format of the `xsave` area.
+    /// `None` for supervisor components, which are only stored in the compacted format.
+    pub fn standard_offset(&self) -> Option<u32> {
+        let offset = match self {
+            XsaveComponent::X87 => 0,
+            XsaveComponent::Sse => 160,
+            XsaveComponent::Avx => 576,
+            XsaveComponent::BndRegs => 960,
+            XsaveComponent::BndCsr => 1024,
+            XsaveComponent::Opmask => 1088,
+            XsaveComponent::ZmmHi256 => 1152,
+            XsaveComponent::Hi16Zmm => 1664,
+            XsaveComponent::Pkru => 2688,
+            XsaveComponent::XtileCfg => 2752,
+            XsaveComponent::XtileData => 2816,
+            XsaveComponent::Apx => 960,
+            _ => { return None; }
+        };
+        Some(offset)
+    }
+}
+
+/// how an instruction lays out the memory it saves state to or restores it from. see
+/// `Instruction::xsave_layout` in each mode.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum XsaveFormat {
+    /// the 512-byte area of `fxsave` and `fxrstor`, holding only x87 and sse state.
+    Fxsave,
+    /// the standard format of `xsave`, `xsaveopt`, and `xrstor`: each component at a fixed
+    /// offset, after the 512-byte legacy region and 64-byte `xsave` header. `xrstor` reads the
+    /// compacted format instead if the header says the area is compacted.
+    Standard,
+    /// the compacted format of `xsavec`: user components packed one after another after the
+    /// header, in order of their bits.
+    Compacted,
+    /// the compacted format of `xsaves` and `xrstors`, including supervisor components.
+    CompactedSupervisor,
+}
+
+/// the layout of an `xsave` area for one format and mask of requested components.
+///
+/// offsets in the compacted formats assume no component requires 64-byte alignment; `cpuid`
+/// leaf `0xd` reports which do, on processors where any does.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct XsaveLayout {
+    format: XsaveFormat,
+    mask: u64,
+}
+
+impl XsaveLayout {
+    /// the layout of an area in `format` with the components in `mask`: the instruction's
+    /// `edx:eax`, masked by `XCR0` and, for `xsaves` and `xrstors`, `IA32_XSS`. components the
+    /// format cannot hold are ignored.
+    pub fn new(format: XsaveFormat, mask: u64) -> XsaveLayout {
+        let mut mask = mask;
+        for component in XsaveComponent::ALL.iter() {
+            let unsupported = match format {
+                XsaveFormat::Fxsave => component.bit() > 1,
+                XsaveFormat::Standard | XsaveFormat::Compacted => component.is_supervisor(),
+                XsaveFormat::CompactedSupervisor => false,
+            };
+            if unsupported {
+                mask &= !(1u64 << component.bit());
+            }
+        }
+        mask &= (1u64 << XsaveComponent::ALL.len()) - 1;
+        XsaveLayout { format, mask }
+    }
+
+    /// the format of the area.
+    pub fn format(&self) -> XsaveFormat {
+        self.format
+    }
+
+    /// the components of the area, as a mask of their bits.
+    pub fn mask(&self) -> u64 {
+        self.mask
+    }
+
+    /// the components of the area, in order of their bits.
+    pub fn components(&self) -> impl Iterator<Item = XsaveComponent> {
+        let mask = self.mask;
+        XsaveComponent::ALL.iter().cloned().filter(move |component| mask & (1 << component.bit()) != 0)
+    }
+
+    /// where `component` is in the area, if it is in the area at all. x87 and sse state are
+    /// always in the 512-byte legacy region at the start of the area, even in the compacted
+    /// formats. `None` if the offset depends on the size of a `Lbr` component before it.
+    pub fn offset(&self, component: XsaveComponent) -> Option<u32> {
+        if self.mask & (1 << component.bit()) == 0 {
+            return None;
+        }
+        match self.format {
+            XsaveFormat::Fxsave | XsaveFormat::Standard => component.standard_offset(),
+            XsaveFormat::Compacted | XsaveFormat::CompactedSupervisor => {
+                if component.bit() < 2 {
+                    return component.standard_offset();
+                }
+                let mut offset = 576;
+                for prior in self.components().filter(|prior| prior.bit() >= 2 && prior.bit() < component.bit()) {
+                    offset += prior.size()?;
+                }
+                Some(offset)
+            }
+        }
+    }
+
+    /// the size of the area, in bytes: the 512 bytes of the legacy region and, for the `xsave`
+    /// formats, the 64-byte header and every requested component. `None` if the area includes
+    /// `Lbr` state, whose size depends on the processor.
+    pub fn size(&self) -> Option<u32> {
+        let mut size = match self.format {
+            XsaveFormat::Fxsave => { return Some(512); }
+            _ => 576,
+        };
+        for component in self.components() {
+            if component.bit() < 2 {
+                continue;
+            }
+            let end = self.offset(component)? + component.size()?;
+            size = core::cmp::max(size, end);
+        }
+        Some(size)
+    }
+}
+
+/// the cpu feature an instruction requires. see `Instruction::isa_extension` in each mode.
+///
+/// names follow the corresponding `InstDecoder` feature flags where they exist. instructions in
+/// the base integer instruction set of each mode report `Base`.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum IsaExtension {
+    /// no cpu feature beyond the base instruction set.
+    Base,
+    /// x87 floating point instructions.
+    X87,
+    /// `cmovcc`, `fcmovcc`, and `fcomi`-style x87 comparisons.
+    Cmov,
+    /// `cmpxchg8b`.
+    Cx8,
+    /// `cmpxchg16b`.
+    Cx16,
+    Mmx,
+    /// 3dnow! and the 3dnow! extensions.
+    Amd3DNow,
+    Sse,
+    Sse2,
+    Sse3,
+    Ssse3,
+    Sse4_1,
+    Sse4_2,
+    Sse4a,
+    Avx,
+    Avx2,
+    Fma3,
+    F16c,
+    Avx512F,
+    Avx512CD,
+    Avx512ER,
+    Avx512PF,
+    Avx512BW,
+    Avx512DQ,
+    Avx512Ifma,
+    Avx512Vbmi,
+    Avx512Vbmi2,
+    Avx512Vnni,
+    Avx512Bitalg,
+    Avx512Vpopcntdq,
+    Avx512_4vnniw,
+    Avx512_4fmaps,
+    Avx512Bf16,
+    Avx512Vp2intersect,
+    /// half-precision floating-point arithmetic and conversions, such as `vaddph` and
+    /// `vcvtph2psx`, largely in evex opcode maps 5 and 6.
+    Avx512Fp16,
+    Aesni,
+    Pclmulqdq,
+    /// 256- and 512-bit forms of the aes instructions.
+    Vaes,
+    /// 256- and 512-bit forms of `pclmulqdq`.
+    Vpclmulqdq,
+    Gfni,
+    Sha,
+    /// the aes key locker instructions.
+    KeyLocker,
+    Bmi1,
+    Bmi2,
+    Adx,
+    Popcnt,
+    Lzcnt,
+    Movbe,
+    /// `lahf` and `sahf` in 64-bit mode.
+    LahfSahf,
+    Prefetchw,
+    Rdrand,
+    Rdseed,
+    Rdtscp,
+    Rdpid,
+    /// `fxsave` and `fxrstor`.
+    Fxsr,
+    Xsave,
+    Xsaveopt,
+    Xsavec,
+    Xsaves,
+    Clflushopt,
+    Clwb,
+    Fsgsbase,
+    /// restricted transactional memory: `xbegin`, `xend`, `xabort`, and `xtest`.
+    Tsx,
+    TsxLdtrk,
+    Mpx,
+    /// cet shadow stack instructions.
+    CetSs,
+    /// cet indirect branch tracking: `endbr32` and `endbr64`.
+    CetIbt,
+    Pku,
+    /// `clac` and `stac`.
+    Smap,
+    Invpcid,
+    Ptwrite,
+    Pconfig,
+    Hreset,
+    Serialize,
+    Enqcmd,
+    Movdiri,
+    Movdir64b,
+    /// `umonitor`, `umwait`, and `tpause`.
+    Waitpkg,
+    /// user interrupts.
+    Uintr,
+    Sgx,
+    /// safer mode extensions: `getsec`.
+    Smx,
+    Vmx,
+    /// tdx and seam instructions.
+    Tdx,
+    Svm,
+    Skinit,
+    /// sev-snp instructions such as `pvalidate` and `rmpadjust`.
+    Snp,
+    /// `invlpgb` and `tlbsync`.
+    Invlpgb,
+    /// `monitorx` and `mwaitx`.
+    MonitorX,
+    Clzero,
+    Rdpru,
+    /// advanced performance extensions: `rex2`-prefixed instructions, `jmpabs`, `pushp`, and
+    /// `popp`.
+    Apx,
+    /// amx tile configuration, loads, and stores.
+    AmxTile,
+    /// amx int8 dot products: `tdpbssd` and its signed/unsigned variants.
+    AmxInt8,
+    /// `tdpbf16ps`.
+    AmxBf16,
+    /// avx10.2: 256-bit vector operations with embedded rounding, and conversions to and from
+    /// 8-bit floats. avx10 covers every vector length, so these never need `avx512vl`.
+    Avx10_2,
+    /// amd's `xop` vector instructions, such as `vpcmov` and `vpperm`.
+    Xop,
+    /// amd's trailing bit manipulation instructions, such as `blcfill` and the `xop`-encoded
+    /// `bextr` with an immediate.
+    Tbm,
+    /// amd's lightweight profiling instructions: `llwpcb`, `slwpcb`, `lwpins`, and `lwpval`.
+    Lwp,
+}
+
+/// the prefix family an instruction's opcode was encoded with. see [`EncodingInfo`].
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// a one-byte or `0f`-escaped opcode, with or without `rex` or `rex2`.
+    Legacy,
+    /// a two- or three-byte `vex` prefix (`c5` or `c4`).
+    Vex,
+    /// an amd `xop` prefix (`8f`).
+    Xop,
+    /// an `evex` prefix (`62`).
+    Evex,
+}
+
+/// the rough kind of control flow an opcode performs, as far as its first bytes tell. see
+/// [`InstructionSummary`].
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FlowClass {
+    /// the instruction continues to the next one, or is not a legacy-encoded branch.
+    Sequential,
+    /// an unconditional jump: `jmp`, `jmpf`, or an indirect `jmp`.
+    Jump,
+    /// a conditional jump: `jcc`, `loop`, `loopz`, `loopnz`, or `jcxz` and its wider forms.
+    ConditionalJump,
+    /// `call`, `callf`, or an indirect `call`.
+    Call,
+    /// `ret`, `retf`, `iret`, `sysret`, or `sysexit`.
+    Return,
+    /// `int`, `int3`, `int1`, `into`, `syscall`, or `sysenter`.
+    Interrupt,
+}
+
+/// a packed description of an instruction's encoding, read from its prefixes, opcode, and `modrm`
+/// byte without decoding it. see `InstDecoder::summarize` in each mode.
+///
+/// a summary fits in 32 bits, and is meant for cheaply filtering a stream of bytes for the
+/// instructions worth decoding in full, like only branches. it describes the opcode that would
+/// be decoded, not whether it is valid: bytes a decoder rejects can still be summarized.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct InstructionSummary(u32);
+
+impl InstructionSummary {
+    pub(crate) fn new(encoding: Encoding, map: u8, opcode: u8, modrm: Option<u8>, offset: u8, class: FlowClass) -> Self {
+        let encoding = match encoding {
+            Encoding::Legacy => 0,
+            Encoding::Vex => 1,
+            Encoding::Xop => 2,
+            Encoding::Evex => 3,
+        };
+        let class = match class {
+            FlowClass::Sequential => 0,
+            FlowClass::Jump => 1,
+            FlowClass::ConditionalJump => 2,
+            FlowClass::Call => 3,
+            FlowClass::Return => 4,
+            FlowClass::Interrupt => 5,
+        };
+        InstructionSummary(
+            opcode as u32 |
+            (modrm.unwrap_or(0) as u32) << 8 |
+            ((map & 0xf) as u32) << 16 |
+            encoding << 20 |
+            (modrm.is_some() as u32) << 22 |
+            class << 23 |
+            ((offset & 0x1f) as u32) << 26
+        )
+    }
+
+    /// the packed summary: bits 0 through 7 are the opcode byte, 8 through 15 the `modrm` byte or
+    /// `0`, 16 through 19 the opcode map, 20 and 21 the encoding as `Legacy`, `Vex`, `Xop`, and
+    /// `Evex` in that order, 22 whether there is a `modrm` byte, 23 through 25 the class as
+    /// `FlowClass` lists them, and 26 through 30 the opcode offset.
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// the opcode byte, after any prefixes, escapes, and `vex`, `evex`, `xop`, or `rex2` prefix.
+    pub fn opcode(&self) -> u8 {
+        self.0 as u8
+    }
+
+    /// the `modrm` byte after the opcode, if the opcode takes one.
+    pub fn modrm(&self) -> Option<u8> {
+        if self.has_modrm() { Some((self.0 >> 8) as u8) } else { None }
+    }
+
+    /// does the opcode take a `modrm` byte?
+    pub fn has_modrm(&self) -> bool {
+        self.0 & (1 << 22) != 0
+    }
+
+    /// the opcode map, numbered as for [`EncodingInfo::map`], except that legacy encodings are
+    /// `0` for one-byte opcodes, `1` for `0f`, `2` for `0f38`, and `3` for `0f3a`.
+    pub fn map(&self) -> u8 {
+        ((self.0 >> 16) & 0xf) as u8
+    }
+
+    /// the prefix family the opcode is encoded with.
+    pub fn encoding(&self) -> Encoding {
+        match (self.0 >> 20) & 0b11 {
+            0 => Encoding::Legacy,
+            1 => Encoding::Vex,
+            2 => Encoding::Xop,
+            _ => Encoding::Evex,
+        }
+    }
 
-const MEM_SIZE_STRINGS: [&'static str; 64] = [
+    /// the rough control flow of the opcode. only legacy encodings are ever branches.
+    pub fn class(&self) -> FlowClass {
+        match (self.0 >> 23) & 0b111 {
+            1 => FlowClass::Jump,
+            2 => FlowClass::ConditionalJump,
+            3 => FlowClass::Call,
+            4 => FlowClass::Return,
+            5 => FlowClass::Interrupt,
+            _ => FlowClass::Sequential,
+        }
+    }
+
+    /// the offset of the opcode byte from the start of the instruction, past its prefixes.
+    pub fn opcode_offset(&self) -> u8 {
+        (self.0 >> 26) as u8 & 0x1f
+    }
+}
+
+/// how an instruction was encoded, as reported by `Instruction::encoding_info` in each mode.
+///
+/// instructions with the same mnemonic and operands can have several encodings, like the `vex`
+/// and `evex` forms of `vmovaps xmm0, xmm1`; this tells them apart.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EncodingInfo {
+    /// the prefix family the opcode was encoded with.
+    pub encoding: Encoding,
+    /// the opcode map selected by the prefix's `mmmmm` field: `1` for `0f`, `2` for `0f38`, `3`
+    /// for `0f3a`, `5` and `6` for the `evex` maps of avx512-fp16, and `8` through `0xa` for
+    /// `xop`. `0` for legacy encodings.
+    pub map: u8,
+    /// the prefix's `pp` field, standing in for a mandatory prefix: `0` for none, `1` for `66`,
+    /// `2` for `f3`, and `3` for `f2`. `0` for legacy encodings.
+    pub pp: u8,
+    /// the prefix's vector length field: `L` for `vex` and `xop`, `L'L` for `evex`. `0` for
+    /// legacy encodings. for `evex` instructions with embedded rounding, these bits are the
+    /// rounding mode rather than a length.
+    pub vector_length: u8,
+    /// the prefix's `W` bit, or `rex.w` for legacy encodings.
+    pub w: bool,
+}
+
+/// callbacks for the steps a decoder takes through an instruction, so fuzzers and coverage tools
+/// can see which paths an input exercised. see `InstDecoder::decode_with_hooks` in each mode.
+///
+/// every method does nothing by default; implement only the ones of interest. offsets are in
+/// bytes from the start of the instruction.
+pub trait DecodeHooks {
+    /// the prefix byte `byte` at `offset` was consumed. a `rex2` prefix is reported as both of its
+    /// bytes; `vex`, `xop`, and `evex` prefixes select an opcode map instead, and are reported to
+    /// [`DecodeHooks::opcode_map`].
+    fn prefix(&mut self, _offset: u8, _byte: u8) {}
+
+    /// the opcode was looked up in `map` of `encoding`, numbered as in [`EncodingInfo::map`]
+    /// except that legacy encodings report `1` through `3` for the `0f`, `0f38`, and `0f3a`
+    /// escapes, and `0` for one-byte opcodes.
+    fn opcode_map(&mut self, _encoding: Encoding, _map: u8) {}
+
+    /// the `modrm` byte `byte` at `offset` was read.
+    fn modrm(&mut self, _offset: u8, _byte: u8) {}
+}
+
+/// a cpu microarchitecture whose instruction set an `InstDecoder` can be configured to match.
+/// see `InstDecoder::for_cpu` in each mode, and the presets in each mode's `uarch` module.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Cpu {
+    /// amd `K8`, launched in 2003.
+    K8,
+    /// amd `K10`, launched in 2007.
+    K10,
+    /// amd `Bulldozer`, launched in 2011.
+    Bulldozer,
+    /// amd `Piledriver`, launched in 2012.
+    Piledriver,
+    /// amd `Steamroller`, launched in 2014.
+    Steamroller,
+    /// amd `Excavator`, launched in 2015.
+    Excavator,
+    /// amd `Zen`, launched in 2017.
+    Zen,
+    /// amd `Zen 2`, launched in 2019.
+    Zen2,
+    /// amd `Zen 3`, launched in 2020.
+    Zen3,
+    /// amd `Zen 4`, launched in 2022.
+    Zen4,
+    /// intel `Netburst`, as of `Prescott`, launched in 2004.
+    Netburst,
+    /// intel `Core`, launched in 2006.
+    Core,
+    /// intel `Penryn`, launched in 2008.
+    Penryn,
+    /// intel `Nehalem`, launched in 2008.
+    Nehalem,
+    /// intel `Westmere`, launched in 2010.
+    Westmere,
+    /// intel `Sandy Bridge`, launched in 2011.
+    SandyBridge,
+    /// intel `Ivy Bridge`, launched in 2012.
+    IvyBridge,
+    /// intel `Haswell`, launched in 2013.
+    Haswell,
+    /// intel `Haswell-EX`, launched in 2015.
+    HaswellEx,
+    /// intel `Broadwell`, launched in 2014.
+    Broadwell,
+    /// intel `Skylake`, launched in 2015.
+    Skylake,
+    /// intel `Kaby Lake`, launched in 2016.
+    KabyLake,
+    /// intel `Ice Lake` client parts, launched in 2019.
+    IceLake,
+    /// intel `Alder Lake`, launched in 2021.
+    AlderLake,
+}
+
+/// the registers `cpuid` reported for one leaf and subleaf. see
+/// `InstDecoder::from_cpuid_leaves` in each mode.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CpuidLeaf {
+    /// the leaf queried, in `eax`.
+    pub leaf: u32,
+    /// the subleaf queried, in `ecx`. `0` for leaves without subleaves.
+    pub subleaf: u32,
+    pub eax: u32,
+    pub ebx: u32,
+    pub ecx: u32,
+    pub edx: u32,
+}
+
+/// a microarchitecture with performance data available through `Opcode::perf_info` in each mode.
+#[cfg(feature = "uarch-data")]
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Uarch {
+    /// intel `Haswell`. ports are numbered as intel numbers them: bit `n` of
+    /// [`PerfInfo::ports`] is port `n`.
+    Haswell,
+    /// intel `Skylake` client parts, as well as `Kaby Lake` and `Coffee Lake`. ports are numbered
+    /// as for `Haswell`.
+    SkylakeClient,
+    /// amd `Zen 2`. bits `0` through `3` of [`PerfInfo::ports`] are the integer pipes `ALU0`
+    /// through `ALU3`, bits `4` through `6` are the address generation units `AGU0` through
+    /// `AGU2`, and bits `8` through `11` are the floating point pipes `FP0` through `FP3`.
+    Zen2,
+}
+
+/// approximate performance characteristics of an instruction on some [`Uarch`].
+///
+/// figures are for the register-only form of an instruction, with typical operands. memory
+/// operands add a load and its latency, division and square root take longer or shorter depending
+/// on their inputs, and some simple operations like register-to-register `mov` may be eliminated
+/// entirely. these are good for annotating a listing, not for predicting cycle counts.
+#[cfg(feature = "uarch-data")]
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PerfInfo {
+    /// cycles from the instruction's inputs being ready to its result being ready. `0` for
+    /// instructions with no register result, like branches and `nop`.
+    pub latency: u8,
+    /// average cycles between issuing independent instances of the instruction; `0.25` for an
+    /// instruction that can issue four times each cycle.
+    pub reciprocal_throughput: f32,
+    /// the execution ports the instruction's operations can issue to, numbered as described on
+    /// each [`Uarch`].
+    pub ports: u16,
+}
+
+/// a set of bits in the `rflags`/`eflags` register.
+///
+/// each flag is at the same bit position as it is in `rflags`, so `bits()` can be compared
+/// directly against a saved flags register.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Flags {
+    bits: u32,
+}
+
+impl Flags {
+    /// carry flag.
+    pub const CF: Flags = Flags { bits: 1 << 0 };
+    /// parity flag.
+    pub const PF: Flags = Flags { bits: 1 << 2 };
+    /// auxiliary carry flag.
+    pub const AF: Flags = Flags { bits: 1 << 4 };
+    /// zero flag.
+    pub const ZF: Flags = Flags { bits: 1 << 6 };
+    /// sign flag.
+    pub const SF: Flags = Flags { bits: 1 << 7 };
+    /// trap flag.
+    pub const TF: Flags = Flags { bits: 1 << 8 };
+    /// interrupt enable flag.
+    pub const IF: Flags = Flags { bits: 1 << 9 };
+    /// direction flag.
+    pub const DF: Flags = Flags { bits: 1 << 10 };
+    /// overflow flag.
+    pub const OF: Flags = Flags { bits: 1 << 11 };
+    /// i/o privilege level - both bits.
+    pub const IOPL: Flags = Flags { bits: 0b11 << 12 };
+    /// nested task flag.
+    pub const NT: Flags = Flags { bits: 1 << 14 };
+    /// resume flag.
+    pub const RF: Flags = Flags { bits: 1 << 16 };
+    /// virtual-8086 mode flag.
+    pub const VM: Flags = Flags { bits: 1 << 17 };
+    /// alignment check/access control flag.
+    pub const AC: Flags = Flags { bits: 1 << 18 };
+    /// virtual interrupt flag.
+    pub const VIF: Flags = Flags { bits: 1 << 19 };
+    /// virtual interrupt pending flag.
+    pub const VIP: Flags = Flags { bits: 1 << 20 };
+    /// `cpuid` availability flag.
+    pub const ID: Flags = Flags { bits: 1 << 21 };
+
+    /// the six arithmetic status flags: `OF`, `SF`, `ZF`, `AF`, `PF`, and `CF`.
+    pub const STATUS: Flags = Flags { bits: 0b1000_1101_0101 };
+    /// every flag `Flags` can describe.
+    pub const ALL: Flags = Flags { bits: 0x3f7fd5 };
+
+    /// the empty set of flags.
+    pub const fn empty() -> Flags {
+        Flags { bits: 0 }
+    }
+
+    /// the flags in this set, at their bit positions in `rflags`.
+    pub const fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// is this the empty set of flags?
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// are all flags in `other` also in `self`?
+    pub const fn contains(&self, other: Flags) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    /// are any flags in `other` also in `self`?
+    pub const fn intersects(&self, other: Flags) -> bool {
+        self.bits & other.bits != 0
+    }
+
+    /// the flags in either `self` or `other`.
+    pub const fn union(self, other: Flags) -> Flags {
+        Flags { bits: self.bits | other.bits }
+    }
+}
+
+impl core::ops::BitOr for Flags {
+    type Output = Flags;
+
+    fn bitor(self, other: Flags) -> Flags {
+        self.union(other)
+    }
+}
+
+impl core::ops::BitAnd for Flags {
+    type Output = Flags;
+
+    fn bitand(self, other: Flags) -> Flags {
+        Flags { bits: self.bits & other.bits }
+    }
+}
+
+/// a set of exceptions an instruction can raise. see `Opcode::fault_classes` in each mode.
+///
+/// each exception is at the bit of its vector number, so `bits()` can be tested against a vector
+/// directly: `#PF`, vector 14, is `1 << 14`.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct FaultClasses {
+    bits: u32,
+}
+
+impl FaultClasses {
+    /// `#DE`, divide error.
+    pub const DE: FaultClasses = FaultClasses { bits: 1 << 0 };
+    /// `#DB`, debug exception.
+    pub const DB: FaultClasses = FaultClasses { bits: 1 << 1 };
+    /// `#BP`, breakpoint.
+    pub const BP: FaultClasses = FaultClasses { bits: 1 << 3 };
+    /// `#OF`, overflow.
+    pub const OF: FaultClasses = FaultClasses { bits: 1 << 4 };
+    /// `#BR`, bound range exceeded.
+    pub const BR: FaultClasses = FaultClasses { bits: 1 << 5 };
+    /// `#UD`, invalid opcode.
+    pub const UD: FaultClasses = FaultClasses { bits: 1 << 6 };
+    /// `#NM`, device not available.
+    pub const NM: FaultClasses = FaultClasses { bits: 1 << 7 };
+    /// `#TS`, invalid tss.
+    pub const TS: FaultClasses = FaultClasses { bits: 1 << 10 };
+    /// `#NP`, segment not present.
+    pub const NP: FaultClasses = FaultClasses { bits: 1 << 11 };
+    /// `#SS`, stack segment fault.
+    pub const SS: FaultClasses = FaultClasses { bits: 1 << 12 };
+    /// `#GP`, general protection.
+    pub const GP: FaultClasses = FaultClasses { bits: 1 << 13 };
+    /// `#PF`, page fault.
+    pub const PF: FaultClasses = FaultClasses { bits: 1 << 14 };
+    /// `#MF`, x87 floating point error.
+    pub const MF: FaultClasses = FaultClasses { bits: 1 << 16 };
+    /// `#AC`, alignment check.
+    pub const AC: FaultClasses = FaultClasses { bits: 1 << 17 };
+    /// `#XM`, simd floating point exception.
+    pub const XM: FaultClasses = FaultClasses { bits: 1 << 19 };
+    /// `#CP`, control protection.
+    pub const CP: FaultClasses = FaultClasses { bits: 1 << 21 };
+
+    /// the exceptions any access to memory can raise: `#GP` and `#SS` for a bad segment or
+    /// non-canonical address, `#PF`, and `#AC`.
+    pub const MEMORY: FaultClasses = FaultClasses { bits: (1 << 12) | (1 << 13) | (1 << 14) | (1 << 17) };
+
+    /// the empty set of exceptions.
+    pub const fn empty() -> FaultClasses {
+        FaultClasses { bits: 0 }
+    }
+
+    /// the exceptions in this set, at the bits of their vector numbers.
+    pub const fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// is this the empty set of exceptions?
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// are all exceptions in `other` also in `self`?
+    pub const fn contains(&self, other: FaultClasses) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    /// are any exceptions in `other` also in `self`?
+    pub const fn intersects(&self, other: FaultClasses) -> bool {
+        self.bits & other.bits != 0
+    }
+
+    /// the exceptions in either `self` or `other`.
+    pub const fn union(self, other: FaultClasses) -> FaultClasses {
+        FaultClasses { bits: self.bits | other.bits }
+    }
+}
+
+impl core::ops::BitOr for FaultClasses {
+    type Output = FaultClasses;
+
+    fn bitor(self, other: FaultClasses) -> FaultClasses {
+        self.union(other)
+    }
+}
+
+impl core::ops::BitAnd for FaultClasses {
+    type Output = FaultClasses;
+
+    fn bitand(self, other: FaultClasses) -> FaultClasses {
+        FaultClasses { bits: self.bits & other.bits }
+    }
+}
+
+const MEM_SIZE_STRINGS: [&str; 108] = [
     "byte", "word", "BUG", "dword", "ptr", "far", "BUG", "qword",
-    "BUG", "mword", "BUG", "BUG", "BUG", "BUG", "BUG", "xmmword",
+    "BUG", "tword", "BUG", "BUG", "BUG", "ptr", "BUG", "xmmword",
     "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG",
-    "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "ymmword",
+    "BUG", "BUG", "BUG", "ptr", "BUG", "BUG", "BUG", "ymmword",
     "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG",
     "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG",
     "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG",
     "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "ptr", "zmmword",
+    "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG",
+    "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG",
+    "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG",
+    "BUG", "BUG", "BUG", "BUG", "BUG", "ptr", "BUG", "BUG",
+    "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG",
+    "BUG", "BUG", "BUG", "ptr",
 ];
 
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 pub struct MemoryAccessSize {
     size: u8,
 }
@@ -164,15 +1690,23 @@ impl MemoryAccessSize {
     /// | 4            | `dword`    |
     /// | 6            | `far`      |
     /// | 8            | `qword`    |
-    /// | 10           | `mword`    |
+    /// | 10           | `tword`    |
+    /// | 14, 28       | `ptr`      |
     /// | 16           | `xmmword`  |
     /// | 32           | `ymmword`  |
     /// | 64           | `zmmword`  |
+    /// | 94, 108      | `ptr`      |
     /// | variable     | `ptr`      |
     ///
-    /// "mword" refers to an mmx-sized access - 80 bits, or 10 bytes. `mword` is also used for
-    /// 64-bit far calls, because they reference a contiguous ten bytes; two bytes of segment
-    /// selector and eight bytes of address.
+    /// "tword" refers to an 80-bit, or 10-byte, access: an x87 extended-precision float or packed
+    /// bcd number, as `fld tword [rax]` loads. `tword` is also used for 64-bit far pointers, as
+    /// `lss` and `callf` use, because they reference a contiguous ten bytes; two bytes of segment
+    /// selector and eight bytes of address. half-precision floats are `word`s.
+    ///
+    /// the x87 environment `fldenv` and `fnstenv` access is 14 bytes with a 16-bit operand size
+    /// and 28 bytes otherwise, and the state `frstor` and `fnsave` access is that and eight 10-byte
+    /// registers, 94 or 108 bytes. intel syntax has no name for these sizes, so they are `ptr`,
+    /// though `bytes_size` reports them exactly.
     ///
     /// "variable" accesses access a number of bytes dependent on the physical processor and its
     /// operating mode. this is particularly relevant for `xsave`/`xrstor`-style instructions.
@@ -194,3 +1728,309 @@ impl core::fmt::Debug for MemoryAccessSize {
         core::fmt::Display::fmt(self, f)
     }
 }
+
+/// names addresses for [`InstructionDisplayer::with_symbols`](long_mode::InstructionDisplayer::with_symbols),
+/// typically from a binary's symbol table.
+#[cfg(feature = "fmt")]
+pub trait SymbolResolver {
+    /// find the symbol containing `address`, returning its name and the offset of `address` from
+    /// the start of the symbol, or `None` if no symbol covers `address`.
+    fn resolve(&self, address: u64) -> Option<(&str, u64)>;
+}
+
+/// names the relocations applied to one instruction's bytes, for
+/// [`InstructionDisplayer::with_relocations`](long_mode::InstructionDisplayer::with_relocations),
+/// typically from an object file's relocation table.
+#[cfg(feature = "fmt")]
+pub trait RelocResolver {
+    /// find the relocation applied to the `width` bytes starting `offset` bytes into the
+    /// instruction, returning the symbol it refers to and the addend to write alongside it, or
+    /// `None` if those bytes are not relocated.
+    ///
+    /// the addend is written as given, so for a pc-relative relocation the caller decides whether
+    /// to account for the distance from the relocated field to the end of the instruction.
+    fn resolve(&self, offset: u8, width: u8) -> Option<(&str, i64)>;
+}
+
+/// which alternate spellings of mnemonics to display, for matching the output of other tools. see
+/// `DisplayOptions::with_aliases` in each mode, and `Opcode::aliases` for every spelling an opcode
+/// is known by. by default, no aliases are used.
+#[cfg(feature = "fmt")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MnemonicAliases {
+    pub(crate) gnu_conditions: bool,
+    pub(crate) sal: bool,
+    pub(crate) repz_ret: bool,
+    pub(crate) xchg_nop: bool,
+}
+
+#[cfg(feature = "fmt")]
+impl MnemonicAliases {
+    /// no aliases: every mnemonic is written as `Opcode::name` gives it.
+    pub fn new() -> Self {
+        MnemonicAliases::default()
+    }
+
+    /// the aliases gnu `objdump` uses, `with_gnu_conditions` and `with_repz_ret`.
+    pub fn gnu() -> Self {
+        MnemonicAliases::new().with_gnu_conditions().with_repz_ret()
+    }
+
+    /// write conditions as gnu tools do, naming the `e`, `ae`, and `be` conditions rather than
+    /// `z`, `nb`, and `na`: `je`, `jne`, `jae`, and `jbe` rather than `jz`, `jnz`, `jnb`, and
+    /// `jna`, and likewise for `setcc`, `cmovcc`, and `loope` and `loopne`.
+    pub fn with_gnu_conditions(mut self) -> Self {
+        self.gnu_conditions = true;
+        self
+    }
+
+    /// write `shl` as `sal`, the name for the same operation in arithmetic terms. the separate
+    /// `/6` encoding, decoded as `Opcode::SAL`, is `sal` either way.
+    pub fn with_sal(mut self) -> Self {
+        self.sal = true;
+        self
+    }
+
+    /// write the `f3` prefix on `ret`, which some compilers emit to avoid a branch predictor
+    /// penalty on older amd processors, as `repz ret`.
+    pub fn with_repz_ret(mut self) -> Self {
+        self.repz_ret = true;
+        self
+    }
+
+    /// write the one-byte `nop`, `90`, as the `xchg` of the accumulator with itself that it is
+    /// encoded as: `xchg eax, eax`, or `xchg ax, ax` with an operand-size prefix.
+    pub fn with_xchg_nop(mut self) -> Self {
+        self.xchg_nop = true;
+        self
+    }
+}
+
+/// ANSI colors for [`InstructionDisplayer::with_colors`](long_mode::InstructionDisplayer::with_colors),
+/// each given as the parameters of an SGR escape sequence, like `"31"` for red or `"1;34"` for bold
+/// blue. an empty string leaves that kind of token uncolored.
+#[cfg(all(feature = "fmt", feature = "colors"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    pub(crate) mnemonic: &'static str,
+    pub(crate) register: &'static str,
+    pub(crate) immediate: &'static str,
+    pub(crate) address: &'static str,
+}
+
+#[cfg(all(feature = "fmt", feature = "colors"))]
+impl Palette {
+    /// the default palette: bold mnemonics, blue registers, yellow immediates and displacements,
+    /// and cyan addresses and branch targets.
+    pub const fn new() -> Self {
+        Palette {
+            mnemonic: "1",
+            register: "34",
+            immediate: "33",
+            address: "36",
+        }
+    }
+
+    /// a palette that colors nothing, to build on with the `with_*` methods.
+    pub const fn plain() -> Self {
+        Palette {
+            mnemonic: "",
+            register: "",
+            immediate: "",
+            address: "",
+        }
+    }
+
+    /// color instruction mnemonics with `sgr`.
+    pub fn with_mnemonic(mut self, sgr: &'static str) -> Self {
+        self.mnemonic = sgr;
+        self
+    }
+
+    /// color registers, including those in memory operands, with `sgr`.
+    pub fn with_register(mut self, sgr: &'static str) -> Self {
+        self.register = sgr;
+        self
+    }
+
+    /// color immediates and memory operand displacements with `sgr`.
+    pub fn with_immediate(mut self, sgr: &'static str) -> Self {
+        self.immediate = sgr;
+        self
+    }
+
+    /// color absolute addresses, `rip`-relative addresses, and relative branch targets with
+    /// `sgr`.
+    pub fn with_address(mut self, sgr: &'static str) -> Self {
+        self.address = sgr;
+        self
+    }
+}
+
+#[cfg(all(feature = "fmt", feature = "colors"))]
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::new()
+    }
+}
+
+/// common model-specific registers by index, sorted by index. names are intel's `IA32_*`
+/// architectural names where there is one, and amd's names for amd-only registers.
+#[cfg(feature = "msr-names")]
+const MSR_NAMES: &[(u32, &str)] = &[
+    (0x0000_0010, "IA32_TIME_STAMP_COUNTER"),
+    (0x0000_001b, "IA32_APIC_BASE"),
+    (0x0000_003a, "IA32_FEATURE_CONTROL"),
+    (0x0000_003b, "IA32_TSC_ADJUST"),
+    (0x0000_0048, "IA32_SPEC_CTRL"),
+    (0x0000_0049, "IA32_PRED_CMD"),
+    (0x0000_008b, "IA32_BIOS_SIGN_ID"),
+    (0x0000_00c1, "IA32_PMC0"),
+    (0x0000_00c2, "IA32_PMC1"),
+    (0x0000_00e7, "IA32_MPERF"),
+    (0x0000_00e8, "IA32_APERF"),
+    (0x0000_00fe, "IA32_MTRRCAP"),
+    (0x0000_010a, "IA32_ARCH_CAPABILITIES"),
+    (0x0000_010b, "IA32_FLUSH_CMD"),
+    (0x0000_0122, "IA32_TSX_CTRL"),
+    (0x0000_0174, "IA32_SYSENTER_CS"),
+    (0x0000_0175, "IA32_SYSENTER_ESP"),
+    (0x0000_0176, "IA32_SYSENTER_EIP"),
+    (0x0000_0179, "IA32_MCG_CAP"),
+    (0x0000_017a, "IA32_MCG_STATUS"),
+    (0x0000_0186, "IA32_PERFEVTSEL0"),
+    (0x0000_0187, "IA32_PERFEVTSEL1"),
+    (0x0000_0198, "IA32_PERF_STATUS"),
+    (0x0000_0199, "IA32_PERF_CTL"),
+    (0x0000_01a0, "IA32_MISC_ENABLE"),
+    (0x0000_01d9, "IA32_DEBUGCTL"),
+    (0x0000_0277, "IA32_PAT"),
+    (0x0000_02ff, "IA32_MTRR_DEF_TYPE"),
+    (0x0000_038d, "IA32_FIXED_CTR_CTRL"),
+    (0x0000_038f, "IA32_PERF_GLOBAL_CTRL"),
+    (0x0000_0480, "IA32_VMX_BASIC"),
+    (0x0000_06a0, "IA32_U_CET"),
+    (0x0000_06a2, "IA32_S_CET"),
+    (0x0000_06a4, "IA32_PL0_SSP"),
+    (0x0000_06e0, "IA32_TSC_DEADLINE"),
+    (0x0000_0802, "IA32_X2APIC_APICID"),
+    (0x0000_080b, "IA32_X2APIC_EOI"),
+    (0x0000_0830, "IA32_X2APIC_ICR"),
+    (0x0000_0da0, "IA32_XSS"),
+    (0xc000_0080, "IA32_EFER"),
+    (0xc000_0081, "IA32_STAR"),
+    (0xc000_0082, "IA32_LSTAR"),
+    (0xc000_0083, "IA32_CSTAR"),
+    (0xc000_0084, "IA32_FMASK"),
+    (0xc000_0100, "IA32_FS_BASE"),
+    (0xc000_0101, "IA32_GS_BASE"),
+    (0xc000_0102, "IA32_KERNEL_GS_BASE"),
+    (0xc000_0103, "IA32_TSC_AUX"),
+    (0xc001_0114, "VM_CR"),
+    (0xc001_0117, "VM_HSAVE_PA"),
+];
+
+/// the name of the model-specific register at `index`, as `rdmsr` and `wrmsr` select it with
+/// `ecx`, if it is one of a table of commonly used registers.
+///
+/// ```
+/// assert_eq!(yaxpeax_x86::msr_name(0xc000_0082), Some("IA32_LSTAR"));
+/// assert_eq!(yaxpeax_x86::msr_name(0x1234_5678), None);
+/// ```
+#[cfg(feature = "msr-names")]
+pub fn msr_name(index: u32) -> Option<&'static str> {
+    MSR_NAMES.binary_search_by_key(&index, |(index, _)| *index)
+        .ok()
+        .map(|i| MSR_NAMES[i].1)
+}
+
+/// a static branch prediction hint, from a `2e` or `3e` prefix on a conditional branch. see
+/// `Instruction::branch_hint` in each mode.
+///
+/// only the pentium 4 acted on these hints, and more recent processors ignore them, but some
+/// compilers and hand-written assembly still include them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BranchHint {
+    /// a `3e` prefix, written `,pt` by some assemblers: the branch is likely taken.
+    Taken,
+    /// a `2e` prefix, written `,pn` by some assemblers: the branch is likely not taken.
+    NotTaken,
+}
+
+/// a hardware lock elision hint, from an `f2` or `f3` prefix on an instruction that accepts one.
+/// see `Instruction::hle_hint` in each mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HleHint {
+    /// `xacquire`, an `f2` prefix: begin eliding the lock the instruction's memory operand names,
+    /// executing transactionally until the matching `xrelease`.
+    Acquire,
+    /// `xrelease`, an `f3` prefix: the store that releases an elided lock, ending the elision if it
+    /// restores the lock's value from before the matching `xacquire`.
+    Release,
+}
+
+/// what an instruction does to a restricted transactional memory (`rtm`) transaction. see
+/// `Instruction::rtm_effect` in each mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RtmEffect {
+    /// `xbegin`: start a transaction, or nest one in the current transaction. if the transaction
+    /// aborts, execution resumes at the fallback address the instruction's relative operand
+    /// names, with the reason for the abort in `eax`.
+    Begin,
+    /// `xend`: commit the current transaction, if it is not nested in another.
+    End,
+    /// `xabort`: abort the current transaction, with this code in bits 31:24 of `eax` at the
+    /// fallback address.
+    Abort(u8),
+    /// `xtest`: clear `zf` if a transaction or elided lock is executing, and set it otherwise.
+    Test,
+}
+
+/// the three instructions that dispatch to sgx leaf functions by the value of `eax`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SgxFunction {
+    /// `encls`, the supervisor leaf functions that build and manage enclaves.
+    Encls,
+    /// `enclu`, the user leaf functions that enter, leave, and run inside enclaves.
+    Enclu,
+    /// `enclv`, the leaf functions for oversubscribing enclave pages across virtual machines.
+    Enclv,
+}
+
+const ENCLS_LEAVES: &[&str] = &[
+    "ECREATE", "EADD", "EINIT", "EREMOVE", "EDBGRD", "EDBGWR", "EEXTEND", "ELDB", "ELDU", "EBLOCK",
+    "EPA", "EWB", "ETRACK", "EAUG", "EMODPR", "EMODT", "ERDINFO", "ETRACKC", "ELDBC", "ELDUC",
+];
+
+const ENCLU_LEAVES: &[&str] = &[
+    "EREPORT", "EGETKEY", "EENTER", "ERESUME", "EEXIT", "EACCEPT", "EMODPE", "EACCEPTCOPY",
+    "EVERIFYREPORT2", "EDECCSSA",
+];
+
+const ENCLV_LEAVES: &[&str] = &[
+    "EDECVIRTCHILD", "EINCVIRTCHILD", "ESETCONTEXT",
+];
+
+/// the name of the leaf function `function` runs for `leaf` in `eax`, if it is a defined leaf.
+///
+/// ```
+/// use yaxpeax_x86::{sgx_leaf_name, SgxFunction};
+///
+/// assert_eq!(sgx_leaf_name(SgxFunction::Encls, 1), Some("EADD"));
+/// assert_eq!(sgx_leaf_name(SgxFunction::Enclu, 2), Some("EENTER"));
+/// assert_eq!(sgx_leaf_name(SgxFunction::Enclv, 0x80), None);
+/// ```
+pub fn sgx_leaf_name(function: SgxFunction, leaf: u32) -> Option<&'static str> {
+    let leaves = match function {
+        SgxFunction::Encls => {
+            // `EUPDATESVN` is the only leaf past the contiguous range.
+            if leaf == 0x18 {
+                return Some("EUPDATESVN");
+            }
+            ENCLS_LEAVES
+        }
+        SgxFunction::Enclu => ENCLU_LEAVES,
+        SgxFunction::Enclv => ENCLV_LEAVES,
+    };
+    leaves.get(leaf as usize).cloned()
+}
@@ -0,0 +1,59 @@
+use crate::long_mode::{AddressingForm, Instruction, Operand, RegSpec, Segment};
+
+fn displacement_address(op: &Operand) -> Option<u64> {
+    match *op {
+        Operand::DisplacementU32(address) => Some(address as u64),
+        Operand::DisplacementU64(address) => Some(address),
+        _ => None,
+    }
+}
+
+fn linear_address(segment: Segment, offset: u64, regs: &dyn RegValueSource) -> Option<u64> {
+    // 64-bit mode ignores the bases of every segment but `fs` and `gs`.
+    match segment {
+        Segment::FS | Segment::GS => Some(regs.segment_base(segment)?.wrapping_add(offset)),
+        _ => Some(offset),
+    }
+}
+
+impl Instruction {
+    /// the absolute address this instruction's `rip`-relative memory operand refers to, for the
+    /// instruction at `address`, or `None` if it has no `rip`-relative operand.
+    ///
+    /// `rip`-relative operands are relative to the end of the instruction, so this is
+    /// `address + self.len() + disp`. with an address-size prefix, `[eip + disp]` wraps to 32 bits.
+    pub fn rip_relative_target(&self, address: u64) -> Option<u64> {
+        for i in 0..self.operand_count() {
+            let (base, disp) = match self.operand(i) {
+                Operand::RegDeref(base) |
+                Operand::RegDerefMasked(base, _) => (base, 0),
+                Operand::RegDisp(base, disp) |
+                Operand::RegDispMasked(base, disp, _) => (base, disp),
+                _ => { continue; }
+            };
+            let next = address.wrapping_add(self.length as u64);
+            let target = next.wrapping_add(disp as i64 as u64);
+            if base == RegSpec::rip() {
+                return Some(target);
+            } else if base == RegSpec::eip() {
+                return Some(target & 0xffff_ffff);
+            }
+        }
+        None
+    }
+}
+
+fn displacement_addressing_form(op: &Operand) -> Option<AddressingForm> {
+    match *op {
+        Operand::DisplacementU64(_) => Some(AddressingForm::Bits64),
+        // `mov eax, [0x1234]` with an address-size prefix, or a sign-extended `sib` displacement
+        // without one.
+        _ => None,
+    }
+}
+
+fn instruction_addressing_form(inst: &Instruction) -> AddressingForm {
+    if inst.prefixes.address_size() { AddressingForm::Bits32 } else { AddressingForm::Bits64 }
+}
+
+include!("../shared/address.in");
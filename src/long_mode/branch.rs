@@ -0,0 +1,21 @@
+use crate::BranchHint;
+use crate::long_mode::{Category, Instruction, Opcode, Operand};
+
+pub(crate) fn branch_target_mask(_inst: &Instruction) -> u64 {
+    // amd processors ignore the operand-size prefix on near branches in 64-bit mode, and intel
+    // processors ignore it on all but a few; a 64-bit `rip` is written either way.
+    u64::MAX
+}
+
+fn absolute_branch_target(inst: &Instruction) -> Option<u64> {
+    // `jmpabs` carries its full 64-bit target as an immediate.
+    match (inst.opcode(), inst.operand(0)) {
+        (Opcode::JMPABS, Operand::ImmediateI64(imm)) => Some(imm as u64),
+        _ => None,
+    }
+}
+
+// `endbr32` is a nop in 64-bit code and does not end an indirect branch.
+const CET_LANDING_PAD: Opcode = Opcode::ENDBR64;
+
+include!("../shared/branch.in");
@@ -0,0 +1,18 @@
+use crate::{AccessKind, Category, Flags, IsaExtension};
+use crate::long_mode::{Instruction, MergeMode, Opcode, Operand, RegSpec, SaeMode, Segment};
+
+fn push_mode_operand(x86: &mut X86Detail, inst: &Instruction, op: &Operand, size: u8, access: AccessKind) {
+    let value = match *op {
+        Operand::ImmediateI64(imm) => X86OpValue::Imm(imm),
+        Operand::ImmediateU64(imm) => X86OpValue::Imm(imm as i64),
+        Operand::DisplacementU64(disp) => mem(inst, None, None, disp as i64),
+        _ => unreachable!("operand is handled by to_capstone"),
+    };
+    x86.push(X86Op { value, size, access, avx_bcast: None, avx_zero_opmask: false });
+}
+
+fn address_size(inst: &Instruction) -> u8 {
+    if inst.prefixes.address_size() { 4 } else { 8 }
+}
+
+include!("../shared/capstone_compat.in");
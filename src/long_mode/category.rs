@@ -0,0 +1,14 @@
+use crate::long_mode::{Category, Opcode};
+
+fn mode_category(opcode: Opcode) -> Category {
+    match opcode {
+        Opcode::JRCXZ | Opcode::JMPABS => Category::Branch,
+        Opcode::PUSHP | Opcode::POPP => Category::Stack,
+        Opcode::LDTILECFG | Opcode::STTILECFG | Opcode::TILERELEASE | Opcode::TILEZERO |
+        Opcode::TILELOADD | Opcode::TILELOADDT1 | Opcode::TILESTORED | Opcode::TDPBSSD |
+        Opcode::TDPBSUD | Opcode::TDPBUSD | Opcode::TDPBUUD | Opcode::TDPBF16PS => Category::Simd,
+        _ => Category::Invalid,
+    }
+}
+
+include!("../shared/category.in");
@@ -0,0 +1,62 @@
+use crate::convert::RegisterKind;
+use crate::long_mode::{RegSpec, RegisterBank, RegisterClass};
+
+impl RegSpec {
+    /// the bank and number of this register, as every mode's `RegSpec` can be built from.
+    pub(crate) fn kind(&self) -> (RegisterKind, u8) {
+        let kind = match self.bank {
+            RegisterBank::Q => RegisterKind::Q,
+            RegisterBank::D => RegisterKind::D,
+            RegisterBank::W => RegisterKind::W,
+            RegisterBank::B => RegisterKind::B,
+            RegisterBank::rB => RegisterKind::rB,
+            RegisterBank::CR => RegisterKind::CR,
+            RegisterBank::DR => RegisterKind::DR,
+            RegisterBank::S => RegisterKind::S,
+            RegisterBank::EIP => RegisterKind::Eip,
+            RegisterBank::RIP => RegisterKind::Rip,
+            RegisterBank::EFlags => RegisterKind::EFlags,
+            RegisterBank::RFlags => RegisterKind::RFlags,
+            RegisterBank::X => RegisterKind::X,
+            RegisterBank::Y => RegisterKind::Y,
+            RegisterBank::Z => RegisterKind::Z,
+            RegisterBank::ST => RegisterKind::ST,
+            RegisterBank::MM => RegisterKind::MM,
+            RegisterBank::K => RegisterKind::K,
+            RegisterBank::T => RegisterKind::T,
+            RegisterBank::Bnd => RegisterKind::Bnd,
+        };
+        (kind, self.num)
+    }
+
+    /// the register numbered `num` in the bank `kind`, if 64-bit code has such a register.
+    pub(crate) fn from_kind(kind: RegisterKind, num: u8) -> Option<RegSpec> {
+        let bank = match kind {
+            RegisterKind::Q => RegisterBank::Q,
+            RegisterKind::D => RegisterBank::D,
+            RegisterKind::W => RegisterBank::W,
+            RegisterKind::B => RegisterBank::B,
+            RegisterKind::rB => RegisterBank::rB,
+            RegisterKind::CR => RegisterBank::CR,
+            RegisterKind::DR => RegisterBank::DR,
+            RegisterKind::S => RegisterBank::S,
+            RegisterKind::Eip => RegisterBank::EIP,
+            RegisterKind::Rip => RegisterBank::RIP,
+            RegisterKind::EFlags => RegisterBank::EFlags,
+            RegisterKind::RFlags => RegisterBank::RFlags,
+            RegisterKind::X => RegisterBank::X,
+            RegisterKind::Y => RegisterBank::Y,
+            RegisterKind::Z => RegisterBank::Z,
+            RegisterKind::ST => RegisterBank::ST,
+            RegisterKind::MM => RegisterBank::MM,
+            RegisterKind::K => RegisterBank::K,
+            RegisterKind::T => RegisterBank::T,
+            RegisterKind::Bnd => RegisterBank::Bnd,
+        };
+        if num < (RegisterClass { kind: bank }).count() {
+            Some(RegSpec { bank, num })
+        } else {
+            None
+        }
+    }
+}
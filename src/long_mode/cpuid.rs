@@ -0,0 +1,44 @@
+use crate::CpuidLeaf;
+use crate::long_mode::InstDecoder;
+
+fn mode_cpuid_features(mut decoder: InstDecoder, leaves: &[CpuidLeaf]) -> InstDecoder {
+    if let Some(leaf) = cpuid_leaf(leaves, 7, 0) {
+        // `amx-tile`
+        if leaf.edx & (1 << 24) != 0 {
+            decoder = decoder.with_amx();
+        }
+        if leaf.ecx & (1 << 29) != 0 {
+            decoder = decoder.with_enqcmd();
+        }
+        // `uintr`
+        if leaf.edx & (1 << 5) != 0 {
+            decoder = decoder.with_uintr();
+        }
+        if leaf.edx & (1 << 14) != 0 {
+            decoder = decoder.with_serialize();
+        }
+    }
+    if let Some(leaf) = cpuid_leaf(leaves, 7, 1) {
+        // `apx_f`
+        if leaf.edx & (1 << 21) != 0 {
+            decoder = decoder.with_apx();
+        }
+        if leaf.eax & (1 << 22) != 0 {
+            decoder = decoder.with_hreset();
+        }
+    }
+    if let Some(leaf) = cpuid_leaf(leaves, 0x8000_0008, 0) {
+        if leaf.ebx & (1 << 3) != 0 {
+            decoder = decoder.with_invlpgb();
+        }
+    }
+    if let Some(leaf) = cpuid_leaf(leaves, 0x8000_001f, 0) {
+        // `sev-snp`
+        if leaf.eax & (1 << 4) != 0 {
+            decoder = decoder.with_snp();
+        }
+    }
+    decoder
+}
+
+include!("../shared/cpuid.in");
@@ -0,0 +1,21 @@
+use crate::long_mode::{AccessKind, Instruction, Operand, RegSpec, RegisterBank, WriteBehavior, X86Operand};
+
+fn written_register(inst: &Instruction, reg: RegSpec) -> (RegSpec, WriteBehavior) {
+    match reg.bank {
+        // writing a 32-bit register clears the upper half of the 64-bit register it is part of.
+        RegisterBank::D => {
+            (RegSpec { bank: RegisterBank::Q, num: reg.num }, WriteBehavior::ZeroExtend)
+        }
+        // so do `vex`, `xop`, and `evex`-encoded writes to vector registers, up to the widest
+        // vector register.
+        RegisterBank::X | RegisterBank::Y if inst.prefixes.vex().is_some() => {
+            (RegSpec { bank: RegisterBank::Z, num: reg.num }, WriteBehavior::ZeroExtend)
+        }
+        RegisterBank::B | RegisterBank::rB | RegisterBank::W | RegisterBank::X | RegisterBank::Y => {
+            (reg, WriteBehavior::Merge)
+        }
+        _ => (reg, WriteBehavior::Full),
+    }
+}
+
+include!("../shared/dataflow.in");
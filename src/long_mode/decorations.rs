@@ -0,0 +1,3 @@
+use crate::long_mode::{Instruction, MergeMode, Opcode, Operand, RegisterBank, RegSpec, SaeMode};
+
+include!("../shared/decorations.in");
@@ -0,0 +1,22 @@
+use crate::long_mode::Opcode;
+
+include!("../shared/descriptions.in");
+
+opcode_descriptions!(mode_description, {
+    JRCXZ => "Jump if RCX register is 0",
+    JMPABS => "Jump to absolute 64-bit address",
+    PUSHP => "Push with push-pop acceleration hint",
+    POPP => "Pop with push-pop acceleration hint",
+    LDTILECFG => "Load tile configuration",
+    STTILECFG => "Store tile configuration",
+    TILERELEASE => "Release tile",
+    TILEZERO => "Zero tile",
+    TILELOADD => "Load tile data",
+    TILELOADDT1 => "Load tile data with a hint to optimize for data reuse",
+    TILESTORED => "Store tile data",
+    TDPBSSD => "Dot product of signed bytes with dword accumulation into tile",
+    TDPBSUD => "Dot product of signed and unsigned bytes with dword accumulation into tile",
+    TDPBUSD => "Dot product of unsigned and signed bytes with dword accumulation into tile",
+    TDPBUUD => "Dot product of unsigned bytes with dword accumulation into tile",
+    TDPBF16PS => "Dot product of BF16 tiles accumulated into packed single precision tile",
+});
@@ -0,0 +1,1452 @@
+//! an encoder for a subset of `x86_64`, producing machine code from the same `Opcode` and `Operand`
+//! types the decoder produces.
+//!
+//! coverage is intentionally partial: general-purpose integer instructions, control flow,
+//! string instructions, and common `sse`/`sse2` forms. `vex`- and `evex`-coded instructions are
+//! not supported, and attempting to encode them reports [`EncodeError::UnsupportedOpcode`].
+//!
+//! ```
+//! use yaxpeax_x86::long_mode::{InstDecoder, InstEncoder, Opcode, Operand, RegSpec};
+//!
+//! let encoded = InstEncoder::new(Opcode::ADD)
+//!     .with_operand(Operand::Register(RegSpec::rax()))
+//!     .with_operand(Operand::ImmediateI8(1))
+//!     .encode()
+//!     .unwrap();
+//! assert_eq!(encoded.bytes(), &[0x48, 0x83, 0xc0, 0x01]);
+//!
+//! // and instructions from the decoder can be re-encoded directly:
+//! let inst = InstDecoder::default().decode_slice(&[0x33, 0x44, 0x24, 0x10]).unwrap();
+//! let encoded = InstEncoder::from_instruction(&inst).encode().unwrap();
+//! assert_eq!(encoded.bytes(), &[0x33, 0x44, 0x24, 0x10]);
+//! ```
+
+use crate::long_mode::{Instruction, Opcode, Operand, OperandSpec, RegSpec, RegisterBank, Segment};
+
+/// the reasons an [`InstEncoder`] may fail to produce bytes for an instruction.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
+pub enum EncodeError {
+    /// the opcode is valid `x86_64`, but the encoder does not know how to encode it.
+    UnsupportedOpcode,
+    /// the operands provided are not a valid combination for this opcode, or are a combination
+    /// the encoder does not support.
+    InvalidOperands,
+    /// a memory operand was provided, but its size could neither be inferred from other operands
+    /// nor was specified by [`InstEncoder::with_mem_size`].
+    AmbiguousSize,
+    /// the encoded instruction would exceed the architectural limit of 15 bytes.
+    TooLong,
+    /// the buffer provided to [`InstEncoder::encode_into`] is too small for this instruction.
+    BufferTooSmall,
+}
+
+impl EncodeError {
+    /// a human-friendly description of this error.
+    pub fn description(&self) -> &'static str {
+        match self {
+            EncodeError::UnsupportedOpcode => "unsupported opcode",
+            EncodeError::InvalidOperands => "invalid operands",
+            EncodeError::AmbiguousSize => "ambiguous operand size",
+            EncodeError::TooLong => "too long",
+            EncodeError::BufferTooSmall => "buffer too small",
+        }
+    }
+}
+
+impl core::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// the bytes of an encoded instruction. `x86_64` instructions are at most 15 bytes, so these are
+/// held inline.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EncodedInstruction {
+    bytes: [u8; 15],
+    len: u8,
+}
+
+impl EncodedInstruction {
+    /// the bytes of this instruction.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+
+    /// the length of this instruction, in bytes.
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// always `false`: an encoded instruction has at least one byte.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn push(&mut self, b: u8) -> Result<(), EncodeError> {
+        if self.len as usize >= self.bytes.len() {
+            return Err(EncodeError::TooLong);
+        }
+        self.bytes[self.len as usize] = b;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn push_le(&mut self, value: u64, width: u8) -> Result<(), EncodeError> {
+        for i in 0..width {
+            self.push((value >> (i * 8)) as u8)?;
+        }
+        Ok(())
+    }
+}
+
+/// a builder for `x86_64` instructions. operands are provided in the same order the decoder would
+/// report them, and are of the same types: `InstEncoder::new(op).with_operand(a).with_operand(b)`
+/// encodes the instruction that would be displayed as `op a, b`.
+///
+/// where an instruction has several encodings, the encoder typically picks the shortest. the
+/// exception is immediates: the width of an `Operand::Immediate*` variant selects the width of the
+/// encoded immediate, so `ImmediateI8(1)` and `ImmediateI32(1)` select `83 /0 ib` and `81 /0 id`
+/// forms of `add` respectively.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstEncoder {
+    opcode: Opcode,
+    operands: [Operand; 4],
+    operand_count: u8,
+    mem_size: u8,
+    segment: Option<Segment>,
+    lock: bool,
+    rep: bool,
+    repnz: bool,
+    // when set, prefer encodings where operand 0 is selected by `modrm.reg` or an opcode-embedded
+    // register rather than by `modrm.rm`. this is only used to round-trip decoded instructions.
+    reg_form: bool,
+}
+
+impl InstEncoder {
+    /// start building an instruction with opcode `opcode` and no operands.
+    pub fn new(opcode: Opcode) -> Self {
+        InstEncoder {
+            opcode,
+            operands: [Operand::Nothing, Operand::Nothing, Operand::Nothing, Operand::Nothing],
+            operand_count: 0,
+            mem_size: 0,
+            segment: None,
+            lock: false,
+            rep: false,
+            repnz: false,
+            reg_form: false,
+        }
+    }
+
+    /// build an encoder for the instruction `inst`, such that encoding it produces an instruction
+    /// that decodes to `inst` again.
+    ///
+    /// decoded instructions with redundant prefixes, or with alternate encodings the encoder does
+    /// not prefer, will be encoded to equivalent but not necessarily identical bytes.
+    pub fn from_instruction(inst: &Instruction) -> Self {
+        let mut encoder = InstEncoder::new(inst.opcode());
+        for i in 0..inst.operand_count() {
+            encoder = encoder.with_operand(inst.operand(i));
+        }
+        encoder.mem_size = inst.mem_size;
+        if inst.prefixes.segment != Segment::DS {
+            encoder.segment = Some(inst.prefixes.segment);
+        }
+        encoder.lock = inst.prefixes.lock();
+        encoder.rep = inst.prefixes.rep();
+        encoder.repnz = inst.prefixes.repnz();
+        encoder.reg_form = inst.operands[0] == OperandSpec::RegRRR;
+        encoder
+    }
+
+    /// append `operand` to this instruction's operands.
+    ///
+    /// panics if the instruction already has four operands.
+    pub fn with_operand(mut self, operand: Operand) -> Self {
+        assert!(self.operand_count < 4);
+        self.operands[self.operand_count as usize] = operand;
+        self.operand_count += 1;
+        self
+    }
+
+    /// specify the size, in bytes, of this instruction's memory access. this is only necessary if
+    /// the size cannot be inferred from register operands, such as for `add dword [rax], 1`.
+    pub fn with_mem_size(mut self, size: u8) -> Self {
+        self.mem_size = size;
+        self
+    }
+
+    /// apply a segment override prefix to this instruction.
+    pub fn with_segment(mut self, segment: Segment) -> Self {
+        self.segment = Some(segment);
+        self
+    }
+
+    /// apply a `lock` prefix to this instruction.
+    pub fn with_lock(mut self) -> Self {
+        self.lock = true;
+        self
+    }
+
+    /// apply a `rep` prefix to this instruction.
+    pub fn with_rep(mut self) -> Self {
+        self.rep = true;
+        self.repnz = false;
+        self
+    }
+
+    /// apply a `repnz` prefix to this instruction.
+    pub fn with_repnz(mut self) -> Self {
+        self.repnz = true;
+        self.rep = false;
+        self
+    }
+
+    /// encode this instruction into `buf`, returning the number of bytes written.
+    pub fn encode_into(&self, buf: &mut [u8]) -> Result<usize, EncodeError> {
+        let encoded = self.encode()?;
+        if buf.len() < encoded.len() {
+            return Err(EncodeError::BufferTooSmall);
+        }
+        buf[..encoded.len()].copy_from_slice(encoded.bytes());
+        Ok(encoded.len())
+    }
+
+    /// encode this instruction.
+    pub fn encode(&self) -> Result<EncodedInstruction, EncodeError> {
+        let form = self.select_form()?;
+        self.emit(&form)
+    }
+
+    fn op(&self, i: u8) -> &Operand {
+        &self.operands[i as usize]
+    }
+
+    /// the size of memory accessed by this instruction, either as specified or as inferred from
+    /// the first register operand.
+    fn operand_width(&self) -> Result<u8, EncodeError> {
+        for i in 0..self.operand_count {
+            if let Operand::Register(reg) = self.op(i) {
+                if is_gp(reg) {
+                    return Ok(reg.width());
+                }
+            }
+        }
+        if self.mem_size != 0 {
+            Ok(self.mem_size)
+        } else {
+            Err(EncodeError::AmbiguousSize)
+        }
+    }
+
+    /// the width of operand `i`, which must be a general-purpose register or memory.
+    fn rm_width(&self, i: u8) -> Result<u8, EncodeError> {
+        match self.op(i) {
+            Operand::Register(reg) if is_gp(reg) => Ok(reg.width()),
+            op if op.is_memory() && self.mem_size != 0 => Ok(self.mem_size),
+            op if op.is_memory() => Err(EncodeError::AmbiguousSize),
+            _ => Err(EncodeError::InvalidOperands),
+        }
+    }
+
+    fn select_form(&self) -> Result<Form, EncodeError> {
+        let ops = self.operand_count;
+        let opc = self.opcode;
+
+        if let Some(form) = bare_form(opc) {
+            if ops != 0 {
+                return Err(EncodeError::InvalidOperands);
+            }
+            return Ok(form);
+        }
+
+        if let Some(n) = alu_index(opc) {
+            if ops != 2 {
+                return Err(EncodeError::InvalidOperands);
+            }
+            let width = self.operand_width()?;
+            let base = n * 8;
+            return match (self.op(0), self.op(1)) {
+                (Operand::Register(dest), src) if is_imm(src) => {
+                    let short = is_accumulator(dest) && (self.reg_form || !is_imm8(src));
+                    if short {
+                        if width == 1 {
+                            Ok(Form::new(&[base + 4], width).imm(src, 1)?.plain())
+                        } else {
+                            Ok(Form::new(&[base + 5], width).imm(src, imm_z(width))?.plain())
+                        }
+                    } else {
+                        self.group1_imm(n, width, src)
+                    }
+                }
+                (dest, src) if is_imm(src) && dest.is_memory() => self.group1_imm(n, width, src),
+                (Operand::Register(dest), Operand::Register(src)) => {
+                    let byte = if width == 1 { 0 } else { 1 };
+                    if self.reg_form {
+                        Ok(Form::new(&[base + 2 + byte], width).modrm_reg(*dest, self.op(1).clone()))
+                    } else {
+                        Ok(Form::new(&[base + byte], width).modrm_reg(*src, self.op(0).clone()))
+                    }
+                }
+                (Operand::Register(dest), src) if src.is_memory() => {
+                    let byte = if width == 1 { 0 } else { 1 };
+                    Ok(Form::new(&[base + 2 + byte], width).modrm_reg(*dest, src.clone()))
+                }
+                (dest, Operand::Register(src)) if dest.is_memory() => {
+                    let byte = if width == 1 { 0 } else { 1 };
+                    Ok(Form::new(&[base + byte], width).modrm_reg(*src, dest.clone()))
+                }
+                _ => Err(EncodeError::InvalidOperands),
+            };
+        }
+
+        if let Some(n) = shift_index(opc) {
+            if ops != 2 {
+                return Err(EncodeError::InvalidOperands);
+            }
+            // the count operand, `cl` or an immediate, says nothing about the operand size.
+            let width = self.rm_width(0)?;
+            let byte = if width == 1 { 0 } else { 1 };
+            let dest = self.rm_operand(0)?;
+            return match self.op(1) {
+                Operand::Register(reg) if *reg == RegSpec::cl() => {
+                    Ok(Form::new(&[0xd2 + byte], width).modrm_digit(n, dest))
+                }
+                imm if imm_value(imm) == Some(1) => {
+                    Ok(Form::new(&[0xd0 + byte], width).modrm_digit(n, dest))
+                }
+                imm if is_imm(imm) => {
+                    Ok(Form::new(&[0xc0 + byte], width).modrm_digit(n, dest).imm(imm, 1)?)
+                }
+                _ => Err(EncodeError::InvalidOperands),
+            };
+        }
+
+        if let Some(n) = unary_index(opc) {
+            if ops != 1 {
+                // `imul` has two- and three-operand forms, handled below.
+                if opc != Opcode::IMUL {
+                    return Err(EncodeError::InvalidOperands);
+                }
+            } else {
+                let width = self.operand_width()?;
+                let byte = if width == 1 { 0 } else { 1 };
+                let dest = self.rm_operand(0)?;
+                return Ok(Form::new(&[0xf6 + byte], width).modrm_digit(n, dest));
+            }
+        }
+
+        if let Some(cc) = condition_index(opc, CONDITIONAL_JUMPS) {
+            return self.relative(&[0x70 + cc], &[0x0f, 0x80 + cc]);
+        }
+
+        if let Some(cc) = condition_index(opc, CONDITIONAL_SETS) {
+            if ops != 1 {
+                return Err(EncodeError::InvalidOperands);
+            }
+            return Ok(Form::new(&[0x0f, 0x90 + cc], 1).modrm_digit(0, self.rm_operand(0)?));
+        }
+
+        if let Some(cc) = condition_index(opc, CONDITIONAL_MOVES) {
+            return self.reg_rm(&[0x0f, 0x40 + cc]);
+        }
+
+        if let Some(sse) = SSE_FORMS.iter().find(|f| f.opcode == opc) {
+            return self.sse(sse);
+        }
+
+        match opc {
+            Opcode::MOV => self.mov(),
+            Opcode::MOVQ => self.movq(),
+            Opcode::TEST => {
+                if ops != 2 {
+                    return Err(EncodeError::InvalidOperands);
+                }
+                let width = self.operand_width()?;
+                let byte = if width == 1 { 0 } else { 1 };
+                match (self.op(0), self.op(1)) {
+                    (Operand::Register(dest), src) if is_imm(src) && is_accumulator(dest) => {
+                        Ok(Form::new(&[0xa8 + byte], width).imm(src, imm_z(width))?.plain())
+                    }
+                    (dest, src) if is_imm(src) => {
+                        Ok(Form::new(&[0xf6 + byte], width).modrm_digit(0, self.rm_operand_of(dest)?).imm(src, imm_z(width))?)
+                    }
+                    (dest, Operand::Register(src)) => {
+                        Ok(Form::new(&[0x84 + byte], width).modrm_reg(*src, self.rm_operand_of(dest)?))
+                    }
+                    _ => Err(EncodeError::InvalidOperands),
+                }
+            }
+            Opcode::XCHG => {
+                if ops != 2 {
+                    return Err(EncodeError::InvalidOperands);
+                }
+                let width = self.operand_width()?;
+                let byte = if width == 1 { 0 } else { 1 };
+                match (self.op(0), self.op(1)) {
+                    (Operand::Register(a), Operand::Register(b)) if width != 1 && is_accumulator(a) && self.reg_form => {
+                        Ok(Form::new(&[0x90], width).opcode_reg(*b))
+                    }
+                    (Operand::Register(a), Operand::Register(b)) if width != 1 && is_accumulator(b) && !is_accumulator(a) => {
+                        Ok(Form::new(&[0x90], width).opcode_reg(*a))
+                    }
+                    (a, Operand::Register(b)) => {
+                        Ok(Form::new(&[0x86 + byte], width).modrm_reg(*b, self.rm_operand_of(a)?))
+                    }
+                    (Operand::Register(a), b) if b.is_memory() => {
+                        Ok(Form::new(&[0x86 + byte], width).modrm_reg(*a, b.clone()))
+                    }
+                    _ => Err(EncodeError::InvalidOperands),
+                }
+            }
+            Opcode::LEA => {
+                if ops != 2 || !self.op(1).is_memory() {
+                    return Err(EncodeError::InvalidOperands);
+                }
+                self.reg_rm(&[0x8d])
+            }
+            Opcode::MOVZX | Opcode::MOVSX => {
+                let base = if opc == Opcode::MOVZX { 0xb6 } else { 0xbe };
+                let src_width = match self.op(1) {
+                    Operand::Register(reg) => reg.width(),
+                    _ => self.mem_size,
+                };
+                match src_width {
+                    1 => self.reg_rm(&[0x0f, base]),
+                    2 => self.reg_rm(&[0x0f, base + 1]),
+                    0 => Err(EncodeError::AmbiguousSize),
+                    _ => Err(EncodeError::InvalidOperands),
+                }
+            }
+            Opcode::MOVSXD => self.reg_rm(&[0x63]),
+            Opcode::IMUL => {
+                match ops {
+                    2 => self.reg_rm(&[0x0f, 0xaf]),
+                    3 => {
+                        let width = self.operand_width()?;
+                        let dest = self.reg_operand(0)?;
+                        let src = self.rm_operand(1)?;
+                        let imm = self.op(2);
+                        if is_imm8(imm) {
+                            Form::new(&[0x6b], width).modrm_reg(dest, src).imm(imm, 1)
+                        } else {
+                            Form::new(&[0x69], width).modrm_reg(dest, src).imm(imm, imm_z(width))
+                        }
+                    }
+                    _ => Err(EncodeError::InvalidOperands),
+                }
+            }
+            Opcode::INC | Opcode::DEC => {
+                if ops != 1 {
+                    return Err(EncodeError::InvalidOperands);
+                }
+                let width = self.operand_width()?;
+                let byte = if width == 1 { 0 } else { 1 };
+                let digit = if opc == Opcode::INC { 0 } else { 1 };
+                Ok(Form::new(&[0xfe + byte], width).modrm_digit(digit, self.rm_operand(0)?))
+            }
+            Opcode::PUSH | Opcode::POP => {
+                if ops != 1 {
+                    return Err(EncodeError::InvalidOperands);
+                }
+                let push = opc == Opcode::PUSH;
+                match self.op(0) {
+                    Operand::Register(reg) => {
+                        if reg.bank != RegisterBank::Q && reg.bank != RegisterBank::W {
+                            return Err(EncodeError::InvalidOperands);
+                        }
+                        Ok(Form::new(&[if push { 0x50 } else { 0x58 }], stack_width(reg.width())).opcode_reg(*reg))
+                    }
+                    imm if push && is_imm8(imm) => {
+                        Form::new(&[0x6a], 0).imm(imm, 1)
+                    }
+                    imm if push && is_imm(imm) => {
+                        Form::new(&[0x68], 0).imm(imm, 4)
+                    }
+                    mem if mem.is_memory() => {
+                        let width = stack_width(if self.mem_size != 0 { self.mem_size } else { 8 });
+                        if push {
+                            Ok(Form::new(&[0xff], width).modrm_digit(6, mem.clone()))
+                        } else {
+                            Ok(Form::new(&[0x8f], width).modrm_digit(0, mem.clone()))
+                        }
+                    }
+                    _ => Err(EncodeError::InvalidOperands),
+                }
+            }
+            Opcode::CALL => {
+                if ops != 1 {
+                    return Err(EncodeError::InvalidOperands);
+                }
+                match self.op(0) {
+                    Operand::ImmediateI32(_) => self.relative(&[], &[0xe8]),
+                    other => Ok(Form::new(&[0xff], 0).modrm_digit(2, self.rm_operand_of(other)?)),
+                }
+            }
+            Opcode::JMP => {
+                if ops != 1 {
+                    return Err(EncodeError::InvalidOperands);
+                }
+                match self.op(0) {
+                    Operand::ImmediateI8(_) | Operand::ImmediateI32(_) => self.relative(&[0xeb], &[0xe9]),
+                    other => Ok(Form::new(&[0xff], 0).modrm_digit(4, self.rm_operand_of(other)?)),
+                }
+            }
+            Opcode::LOOPNZ => self.relative(&[0xe0], &[]),
+            Opcode::LOOPZ => self.relative(&[0xe1], &[]),
+            Opcode::LOOP => self.relative(&[0xe2], &[]),
+            Opcode::JRCXZ => self.relative(&[0xe3], &[]),
+            Opcode::RETURN => {
+                match ops {
+                    0 => Ok(Form::new(&[0xc3], 0).plain()),
+                    1 => Form::new(&[0xc2], 0).imm(self.op(0), 2),
+                    _ => Err(EncodeError::InvalidOperands),
+                }
+            }
+            Opcode::INT => {
+                if ops != 1 {
+                    return Err(EncodeError::InvalidOperands);
+                }
+                if imm_value(self.op(0)) == Some(3) {
+                    Ok(Form::new(&[0xcc], 0).plain())
+                } else {
+                    Form::new(&[0xcd], 0).imm(self.op(0), 1)
+                }
+            }
+            Opcode::BT | Opcode::BTS | Opcode::BTR | Opcode::BTC => {
+                if ops != 2 {
+                    return Err(EncodeError::InvalidOperands);
+                }
+                let (digit, reg_opcode) = match opc {
+                    Opcode::BT => (4, 0xa3),
+                    Opcode::BTS => (5, 0xab),
+                    Opcode::BTR => (6, 0xb3),
+                    _ => (7, 0xbb),
+                };
+                let width = self.operand_width()?;
+                let dest = self.rm_operand(0)?;
+                match self.op(1) {
+                    Operand::Register(src) => Ok(Form::new(&[0x0f, reg_opcode], width).modrm_reg(*src, dest)),
+                    imm if is_imm(imm) => Form::new(&[0x0f, 0xba], width).modrm_digit(digit, dest).imm(imm, 1),
+                    _ => Err(EncodeError::InvalidOperands),
+                }
+            }
+            Opcode::BSF => self.reg_rm(&[0x0f, 0xbc]),
+            Opcode::BSR => self.reg_rm(&[0x0f, 0xbd]),
+            Opcode::BSWAP => {
+                if ops != 1 {
+                    return Err(EncodeError::InvalidOperands);
+                }
+                let reg = self.reg_operand(0)?;
+                Ok(Form::new(&[0x0f, 0xc8], reg.width()).opcode_reg(reg))
+            }
+            Opcode::XADD | Opcode::CMPXCHG => {
+                if ops != 2 {
+                    return Err(EncodeError::InvalidOperands);
+                }
+                let width = self.operand_width()?;
+                let byte = if width == 1 { 0 } else { 1 };
+                let base = if opc == Opcode::XADD { 0xc0 } else { 0xb0 };
+                Ok(Form::new(&[0x0f, base + byte], width).modrm_reg(self.reg_operand(1)?, self.rm_operand(0)?))
+            }
+            Opcode::MOVS | Opcode::STOS | Opcode::LODS | Opcode::CMPS | Opcode::SCAS => {
+                let base = match opc {
+                    Opcode::MOVS => 0xa4,
+                    Opcode::CMPS => 0xa6,
+                    Opcode::STOS => 0xaa,
+                    Opcode::LODS => 0xac,
+                    _ => 0xae,
+                };
+                let width = if self.mem_size != 0 { self.mem_size } else { self.operand_width()? };
+                let byte = if width == 1 { 0 } else { 1 };
+                // `rsi`/`rdi` are implied, so a 32-bit address size is only visible through
+                // the operands: `es:[edi]` and `ds:[esi]` need a `67` prefix.
+                let mut addr32 = false;
+                for i in 0..ops {
+                    let op = self.op(i);
+                    if op.is_memory() {
+                        addr32 |= MemoryParts::from_operand(op)?.addr32;
+                    }
+                }
+                Ok(Form::new(&[base + byte], width).plain().addr32_if(addr32))
+            }
+            _ => Err(EncodeError::UnsupportedOpcode),
+        }
+    }
+
+    fn group1_imm(&self, n: u8, width: u8, imm: &Operand) -> Result<Form, EncodeError> {
+        let dest = self.rm_operand(0)?;
+        if width == 1 {
+            Form::new(&[0x80], width).modrm_digit(n, dest).imm(imm, 1)
+        } else if is_imm8(imm) {
+            Form::new(&[0x83], width).modrm_digit(n, dest).imm(imm, 1)
+        } else {
+            Form::new(&[0x81], width).modrm_digit(n, dest).imm(imm, imm_z(width))
+        }
+    }
+
+    fn mov(&self) -> Result<Form, EncodeError> {
+        if self.operand_count != 2 {
+            return Err(EncodeError::InvalidOperands);
+        }
+
+        // moves to and from segment, control, and debug registers first: these have sizes
+        // independent of any general-purpose operand.
+        match (self.op(0), self.op(1)) {
+            (dest, Operand::Register(src)) if src.bank == RegisterBank::S => {
+                return Ok(Form::new(&[0x8c], 0).modrm_reg(*src, self.rm_operand_of(dest)?));
+            }
+            (Operand::Register(dest), src) if dest.bank == RegisterBank::S => {
+                return Ok(Form::new(&[0x8e], 0).modrm_reg(*dest, self.rm_operand_of(src)?));
+            }
+            (Operand::Register(dest), Operand::Register(src)) if src.bank == RegisterBank::CR || src.bank == RegisterBank::DR => {
+                let opc = if src.bank == RegisterBank::CR { 0x20 } else { 0x21 };
+                return Ok(Form::new(&[0x0f, opc], 0).modrm_reg(*src, Operand::Register(*dest)));
+            }
+            (Operand::Register(dest), Operand::Register(src)) if dest.bank == RegisterBank::CR || dest.bank == RegisterBank::DR => {
+                let opc = if dest.bank == RegisterBank::CR { 0x22 } else { 0x23 };
+                return Ok(Form::new(&[0x0f, opc], 0).modrm_reg(*dest, Operand::Register(*src)));
+            }
+            _ => {}
+        }
+
+        let width = self.operand_width()?;
+        let byte = if width == 1 { 0 } else { 1 };
+        match (self.op(0), self.op(1)) {
+            (Operand::Register(dest), Operand::Register(src)) => {
+                if self.reg_form {
+                    Ok(Form::new(&[0x8a + byte], width).modrm_reg(*dest, Operand::Register(*src)))
+                } else {
+                    Ok(Form::new(&[0x88 + byte], width).modrm_reg(*src, Operand::Register(*dest)))
+                }
+            }
+            (Operand::Register(dest), Operand::DisplacementU64(addr)) if is_accumulator(dest) => {
+                Ok(Form::new(&[0xa0 + byte], width).moffs(*addr))
+            }
+            (Operand::DisplacementU64(addr), Operand::Register(src)) if is_accumulator(src) => {
+                Ok(Form::new(&[0xa2 + byte], width).moffs(*addr))
+            }
+            (Operand::Register(dest), src) if src.is_memory() => {
+                Ok(Form::new(&[0x8a + byte], width).modrm_reg(*dest, src.clone()))
+            }
+            (dest, Operand::Register(src)) if dest.is_memory() => {
+                Ok(Form::new(&[0x88 + byte], width).modrm_reg(*src, dest.clone()))
+            }
+            (Operand::Register(dest), imm) if is_imm(imm) => {
+                // `c7 /0 id` is shorter than `b8+r io` for 64-bit moves when the immediate fits
+                // in a sign-extended 32 bits. decoded `b8+r` forms are kept as they were.
+                let fits_imm32 = imm_value(imm).map(|v| v as i64 == v as i32 as i64).unwrap_or(false);
+                if width == 8 && fits_imm32 && !self.reg_form {
+                    Form::new(&[0xc7], width).modrm_digit(0, Operand::Register(*dest)).imm(imm, 4)
+                } else {
+                    Form::new(&[0xb0 + byte * 8], width).opcode_reg(*dest).imm(imm, width)
+                }
+            }
+            (dest, imm) if dest.is_memory() && is_imm(imm) => {
+                Form::new(&[0xc6 + byte], width).modrm_digit(0, dest.clone()).imm(imm, imm_z(width))
+            }
+            _ => Err(EncodeError::InvalidOperands),
+        }
+    }
+
+    fn movq(&self) -> Result<Form, EncodeError> {
+        if self.operand_count != 2 {
+            return Err(EncodeError::InvalidOperands);
+        }
+        let vector = |op: &Operand| match op {
+            Operand::Register(reg) if reg.bank == RegisterBank::X || reg.bank == RegisterBank::MM => Some(*reg),
+            _ => None,
+        };
+        let gp = |op: &Operand| match op {
+            Operand::Register(reg) if reg.bank == RegisterBank::Q => Some(*reg),
+            _ => None,
+        };
+        let (dest, src) = (self.op(0), self.op(1));
+        // `66`, where the `xmm` form of an instruction is distinguished from the `mm` form by it.
+        let opsize = |reg: RegSpec| if reg.bank == RegisterBank::X { 0x66 } else { 0 };
+        match (vector(dest), vector(src)) {
+            (Some(d), _) if gp(src).is_some() => {
+                Ok(Form::new(&[0x0f, 0x6e], 0).modrm_reg(d, src.clone()).mandatory(opsize(d)).rex_w_if(true))
+            }
+            (_, Some(s)) if gp(dest).is_some() => {
+                Ok(Form::new(&[0x0f, 0x7e], 0).modrm_reg(s, dest.clone()).mandatory(opsize(s)).rex_w_if(true))
+            }
+            (Some(d), _) if d.bank == RegisterBank::X && (src.is_memory() || vector(src).map(|s| s.bank) == Some(RegisterBank::X)) => {
+                Ok(Form::new(&[0x0f, 0x7e], 0).modrm_reg(d, src.clone()).mandatory(0xf3))
+            }
+            (None, Some(s)) if s.bank == RegisterBank::X && dest.is_memory() => {
+                Ok(Form::new(&[0x0f, 0xd6], 0).modrm_reg(s, dest.clone()).mandatory(0x66))
+            }
+            (Some(d), _) if d.bank == RegisterBank::MM && (src.is_memory() || vector(src).map(|s| s.bank) == Some(RegisterBank::MM)) => {
+                Ok(Form::new(&[0x0f, 0x6f], 0).modrm_reg(d, src.clone()))
+            }
+            (None, Some(s)) if s.bank == RegisterBank::MM && dest.is_memory() => {
+                Ok(Form::new(&[0x0f, 0x7f], 0).modrm_reg(s, dest.clone()))
+            }
+            _ => Err(EncodeError::InvalidOperands),
+        }
+    }
+
+    fn sse(&self, sse: &SseForm) -> Result<Form, EncodeError> {
+        let imm_operand = if sse.imm8 {
+            if self.operand_count != 3 {
+                return Err(EncodeError::InvalidOperands);
+            }
+            Some(self.op(2))
+        } else {
+            if self.operand_count != 2 {
+                return Err(EncodeError::InvalidOperands);
+            }
+            None
+        };
+
+        let (dest, src) = (self.op(0), self.op(1));
+        // a handful of forms put the vector register in `modrm.rm` and read a general-purpose
+        // register or store to memory; everything else is `reg, reg/mem`.
+        let (reg, rm, opcode) = match (dest, src) {
+            (Operand::Register(d), s) if (d.bank == RegisterBank::X || d.bank == RegisterBank::MM) && (s.is_memory() || matches!(s, Operand::Register(_))) => {
+                (*d, s.clone(), sse.load)
+            }
+            (Operand::Register(d), Operand::Register(s)) if is_gp(d) => {
+                // `cvtsd2si`-style: gp destination in `modrm.reg`, unless this is a store form
+                // like `movd eax, xmm0` where the gp register is in `modrm.rm`.
+                if let Some(store) = sse.store {
+                    (*s, dest.clone(), store)
+                } else {
+                    (*d, src.clone(), sse.load)
+                }
+            }
+            (d, Operand::Register(s)) if d.is_memory() => {
+                match sse.store {
+                    Some(store) => (*s, d.clone(), store),
+                    None => return Err(EncodeError::InvalidOperands),
+                }
+            }
+            (Operand::Register(d), s) if is_gp(d) && s.is_memory() => {
+                (*d, s.clone(), sse.load)
+            }
+            _ => return Err(EncodeError::InvalidOperands),
+        };
+
+        // mmx forms of the integer ops omit the `66` prefix.
+        let mmx = matches!(&rm, Operand::Register(r) if r.bank == RegisterBank::MM) || reg.bank == RegisterBank::MM;
+        let mandatory = if mmx && sse.prefix == 0x66 && sse.mmx { 0 } else { sse.prefix };
+        if mmx && !sse.mmx {
+            return Err(EncodeError::InvalidOperands);
+        }
+
+        // forms with a general-purpose register operand take their `rex.w` from that register's
+        // width.
+        let gp_width = [dest, src].iter().filter_map(|op| match op {
+            Operand::Register(r) if is_gp(r) => Some(r.width()),
+            _ => None,
+        }).next();
+        let rex_w = sse.rex_w || gp_width == Some(8) || (sse.gp_mem_w && gp_width.is_none() && self.mem_size == 8 && rm.is_memory());
+
+        let mut form = Form::new(&[0x0f, opcode], 0).modrm_reg(reg, rm).mandatory(mandatory).rex_w_if(rex_w);
+        if let Some(imm) = imm_operand {
+            form = form.imm(imm, 1)?;
+        }
+        Ok(form)
+    }
+
+    /// an instruction of the form `op reg, reg/mem`, with operand size from the destination.
+    fn reg_rm(&self, opcode: &[u8]) -> Result<Form, EncodeError> {
+        if self.operand_count != 2 {
+            return Err(EncodeError::InvalidOperands);
+        }
+        let dest = self.reg_operand(0)?;
+        let src = self.rm_operand(1)?;
+        Ok(Form::new(opcode, dest.width()).modrm_reg(dest, src))
+    }
+
+    fn relative(&self, short: &[u8], near: &[u8]) -> Result<Form, EncodeError> {
+        if self.operand_count != 1 {
+            return Err(EncodeError::InvalidOperands);
+        }
+        match self.op(0) {
+            Operand::ImmediateI8(rel) if !short.is_empty() => {
+                Ok(Form::new(short, 0).rel(*rel as i64 as u64, 1))
+            }
+            Operand::ImmediateI32(rel) if !near.is_empty() => {
+                Ok(Form::new(near, 0).rel(*rel as i64 as u64, 4))
+            }
+            _ => Err(EncodeError::InvalidOperands),
+        }
+    }
+
+    fn reg_operand(&self, i: u8) -> Result<RegSpec, EncodeError> {
+        match self.op(i) {
+            Operand::Register(reg) => Ok(*reg),
+            _ => Err(EncodeError::InvalidOperands),
+        }
+    }
+
+    fn rm_operand(&self, i: u8) -> Result<Operand, EncodeError> {
+        self.rm_operand_of(self.op(i))
+    }
+
+    fn rm_operand_of(&self, op: &Operand) -> Result<Operand, EncodeError> {
+        match op {
+            Operand::Register(_) => Ok(op.clone()),
+            op if op.is_memory() => Ok(op.clone()),
+            _ => Err(EncodeError::InvalidOperands),
+        }
+    }
+
+    fn emit(&self, form: &Form) -> Result<EncodedInstruction, EncodeError> {
+        let mut out = EncodedInstruction { bytes: [0; 15], len: 0 };
+
+        let mut rex: u8 = 0;
+        let mut need_rex = false;
+        let mut forbid_rex = false;
+        let mut addr32 = form.addr32;
+
+        let mut check_byte_reg = |reg: &RegSpec| {
+            if reg.bank == RegisterBank::rB {
+                need_rex = true;
+            } else if reg.bank == RegisterBank::B && reg.num >= 4 {
+                forbid_rex = true;
+            }
+        };
+
+        let mut modrm: Option<u8> = None;
+        let mut sib: Option<u8> = None;
+        let mut disp: Option<(u32, u8)> = None;
+
+        match &form.encoding {
+            Encoding::Plain => {}
+            Encoding::OpcodeReg(reg) => {
+                check_legacy_gpr(reg)?;
+                check_byte_reg(reg);
+                if reg.num >= 8 {
+                    rex |= 0x01;
+                }
+            }
+            Encoding::Moffs(_) => {}
+            Encoding::ModRM(reg_field, rm) => {
+                let reg_num = match reg_field {
+                    RegField::Reg(reg) => {
+                        check_legacy_gpr(reg)?;
+                        check_byte_reg(reg);
+                        reg.num
+                    }
+                    RegField::Digit(d) => *d,
+                };
+                if reg_num >= 8 {
+                    rex |= 0x04;
+                }
+                let reg_bits = (reg_num & 7) << 3;
+
+                match rm {
+                    Operand::Register(reg) => {
+                        check_legacy_gpr(reg)?;
+                        check_byte_reg(reg);
+                        if reg.num >= 8 {
+                            rex |= 0x01;
+                        }
+                        modrm = Some(0xc0 | reg_bits | (reg.num & 7));
+                    }
+                    mem => {
+                        let mem = MemoryParts::from_operand(mem)?;
+                        addr32 = mem.addr32;
+                        if mem.rip {
+                            modrm = Some(reg_bits | 0b101);
+                            disp = Some((mem.disp as u32, 4));
+                        } else {
+                            let scale_bits = match mem.scale {
+                                1 => 0,
+                                2 => 1,
+                                4 => 2,
+                                8 => 3,
+                                _ => { return Err(EncodeError::InvalidOperands); }
+                            };
+                            let index_bits = match mem.index {
+                                Some(index) => {
+                                    check_legacy_gpr(&index)?;
+                                    if index.num == 4 {
+                                        // `rsp` can't be an index register.
+                                        return Err(EncodeError::InvalidOperands);
+                                    }
+                                    if index.num >= 8 {
+                                        rex |= 0x02;
+                                    }
+                                    index.num & 7
+                                }
+                                None => 0b100,
+                            };
+                            match mem.base {
+                                Some(base) => {
+                                    check_legacy_gpr(&base)?;
+                                    if base.num >= 8 {
+                                        rex |= 0x01;
+                                    }
+                                    let base_bits = base.num & 7;
+                                    let md = if mem.disp == 0 && base_bits != 0b101 {
+                                        0b00
+                                    } else if mem.disp == mem.disp as i8 as i32 {
+                                        disp = Some((mem.disp as u32, 1));
+                                        0b01
+                                    } else {
+                                        disp = Some((mem.disp as u32, 4));
+                                        0b10
+                                    };
+                                    if mem.index.is_some() || base_bits == 0b100 {
+                                        modrm = Some((md << 6) | reg_bits | 0b100);
+                                        sib = Some((scale_bits << 6) | (index_bits << 3) | base_bits);
+                                    } else {
+                                        modrm = Some((md << 6) | reg_bits | base_bits);
+                                    }
+                                }
+                                None => {
+                                    modrm = Some(reg_bits | 0b100);
+                                    sib = Some((scale_bits << 6) | (index_bits << 3) | 0b101);
+                                    disp = Some((mem.disp as u32, 4));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if form.width == 8 && form.gp_sized {
+            rex |= 0x08;
+        }
+        if form.rex_w {
+            rex |= 0x08;
+        }
+        if rex != 0 {
+            need_rex = true;
+        }
+        if need_rex && forbid_rex {
+            return Err(EncodeError::InvalidOperands);
+        }
+
+        // legacy prefixes first: segment, lock, rep, operand size, address size.
+        if let Some(segment) = self.segment {
+            out.push(match segment {
+                Segment::ES => 0x26,
+                Segment::CS => 0x2e,
+                Segment::SS => 0x36,
+                Segment::DS => 0x3e,
+                Segment::FS => 0x64,
+                Segment::GS => 0x65,
+            })?;
+        }
+        if self.lock {
+            out.push(0xf0)?;
+        }
+        // decoded `sse` instructions report their mandatory `f3`/`f2` prefix as `rep`/`repnz`, so
+        // those are only emitted here for forms without a mandatory prefix.
+        if form.mandatory == 0 {
+            if self.rep {
+                out.push(0xf3)?;
+            } else if self.repnz {
+                out.push(0xf2)?;
+            }
+        }
+        if form.width == 2 && form.gp_sized {
+            out.push(0x66)?;
+        }
+        if addr32 {
+            out.push(0x67)?;
+        }
+        if form.mandatory != 0 {
+            out.push(form.mandatory)?;
+        }
+        if need_rex {
+            out.push(0x40 | rex)?;
+        }
+
+        let opcode = &form.opcode[..form.opcode_len as usize];
+        let (last, rest) = opcode.split_last().expect("forms have at least one opcode byte");
+        for b in rest.iter() {
+            out.push(*b)?;
+        }
+        match &form.encoding {
+            Encoding::OpcodeReg(reg) => out.push(last + (reg.num & 7))?,
+            _ => out.push(*last)?,
+        }
+
+        if let Some(modrm) = modrm {
+            out.push(modrm)?;
+        }
+        if let Some(sib) = sib {
+            out.push(sib)?;
+        }
+        if let Some((disp, size)) = disp {
+            out.push_le(disp as u64, size)?;
+        }
+        if let Encoding::Moffs(addr) = &form.encoding {
+            out.push_le(*addr, 8)?;
+        }
+        if let Some((imm, size)) = form.imm {
+            out.push_le(imm, size)?;
+        }
+
+        Ok(out)
+    }
+}
+
+#[derive(Debug)]
+enum RegField {
+    Reg(RegSpec),
+    Digit(u8),
+}
+
+#[derive(Debug)]
+enum Encoding {
+    /// no modrm, no register in the opcode.
+    Plain,
+    /// the register is added to the last byte of the opcode, like `push r64`.
+    OpcodeReg(RegSpec),
+    /// a 64-bit absolute address follows the opcode, like `mov al, [moffs64]`.
+    Moffs(u64),
+    /// a modrm byte with the provided `reg` field and `rm` operand.
+    ModRM(RegField, Operand),
+}
+
+/// the details of one specific encoding of an instruction.
+#[derive(Debug)]
+struct Form {
+    opcode: [u8; 3],
+    opcode_len: u8,
+    mandatory: u8,
+    // operand size, for general-purpose operations. `gp_sized` forms apply `66` and `rex.w` based
+    // on this width.
+    width: u8,
+    gp_sized: bool,
+    rex_w: bool,
+    // a `67` prefix for forms whose memory operands are implied rather than encoded in `modrm`.
+    addr32: bool,
+    encoding: Encoding,
+    imm: Option<(u64, u8)>,
+}
+
+impl Form {
+    fn new(opcode: &[u8], width: u8) -> Self {
+        let mut bytes = [0u8; 3];
+        bytes[..opcode.len()].copy_from_slice(opcode);
+        Form {
+            opcode: bytes,
+            opcode_len: opcode.len() as u8,
+            mandatory: 0,
+            width,
+            gp_sized: width != 0,
+            rex_w: false,
+            addr32: false,
+            encoding: Encoding::Plain,
+            imm: None,
+        }
+    }
+
+    fn plain(self) -> Self {
+        self
+    }
+
+    fn modrm_reg(mut self, reg: RegSpec, rm: Operand) -> Self {
+        self.encoding = Encoding::ModRM(RegField::Reg(reg), rm);
+        self
+    }
+
+    fn modrm_digit(mut self, digit: u8, rm: Operand) -> Self {
+        self.encoding = Encoding::ModRM(RegField::Digit(digit), rm);
+        self
+    }
+
+    fn opcode_reg(mut self, reg: RegSpec) -> Self {
+        self.encoding = Encoding::OpcodeReg(reg);
+        self
+    }
+
+    fn moffs(mut self, addr: u64) -> Self {
+        self.encoding = Encoding::Moffs(addr);
+        self
+    }
+
+    fn mandatory(mut self, prefix: u8) -> Self {
+        self.mandatory = prefix;
+        self
+    }
+
+    fn rex_w_if(mut self, w: bool) -> Self {
+        self.rex_w |= w;
+        self
+    }
+
+    fn addr32_if(mut self, addr32: bool) -> Self {
+        self.addr32 |= addr32;
+        self
+    }
+
+    fn rel(mut self, rel: u64, size: u8) -> Self {
+        self.imm = Some((rel, size));
+        self
+    }
+
+    fn imm(mut self, imm: &Operand, size: u8) -> Result<Self, EncodeError> {
+        let value = imm_value(imm).ok_or(EncodeError::InvalidOperands)?;
+        // the immediate must be representable in `size` bytes, either sign- or zero-extended.
+        if size < 8 {
+            let bits = size as u32 * 8;
+            let signed_ok = {
+                let shifted = (value as i64) >> (bits - 1);
+                shifted == 0 || shifted == -1
+            };
+            let unsigned_ok = (value >> bits) == 0;
+            if !signed_ok && !unsigned_ok {
+                return Err(EncodeError::InvalidOperands);
+            }
+        }
+        self.imm = Some((value, size));
+        Ok(self)
+    }
+}
+
+/// the parts of a memory operand, independent of `Operand` variant.
+struct MemoryParts {
+    base: Option<RegSpec>,
+    index: Option<RegSpec>,
+    scale: u8,
+    disp: i32,
+    rip: bool,
+    addr32: bool,
+}
+
+impl MemoryParts {
+    fn from_operand(op: &Operand) -> Result<MemoryParts, EncodeError> {
+        let (base, index, scale, disp) = match *op {
+            Operand::DisplacementU32(disp) => (None, None, 1, disp as i32),
+            Operand::RegDeref(base) => (Some(base), None, 1, 0),
+            Operand::RegDisp(base, disp) => (Some(base), None, 1, disp),
+            Operand::RegScale(index, scale) => (None, Some(index), scale, 0),
+            Operand::RegScaleDisp(index, scale, disp) => (None, Some(index), scale, disp),
+            Operand::RegIndexBase(base, index) => (Some(base), Some(index), 1, 0),
+            Operand::RegIndexBaseDisp(base, index, disp) => (Some(base), Some(index), 1, disp),
+            Operand::RegIndexBaseScale(base, index, scale) => (Some(base), Some(index), scale, 0),
+            Operand::RegIndexBaseScaleDisp(base, index, scale, disp) => (Some(base), Some(index), scale, disp),
+            _ => { return Err(EncodeError::InvalidOperands); }
+        };
+
+        let mut parts = MemoryParts { base, index, scale, disp, rip: false, addr32: false };
+
+        // all address registers must agree on address size.
+        let mut bank = None;
+        for reg in [base, index].iter().filter_map(|r| *r) {
+            let this_bank = match reg.bank {
+                RegisterBank::RIP => { parts.rip = true; RegisterBank::Q },
+                RegisterBank::EIP => { parts.rip = true; RegisterBank::D },
+                RegisterBank::Q | RegisterBank::D => reg.bank,
+                _ => { return Err(EncodeError::InvalidOperands); }
+            };
+            if bank.map(|b| b != this_bank).unwrap_or(false) {
+                return Err(EncodeError::InvalidOperands);
+            }
+            bank = Some(this_bank);
+        }
+        if parts.rip && index.is_some() {
+            return Err(EncodeError::InvalidOperands);
+        }
+        parts.addr32 = bank == Some(RegisterBank::D);
+        Ok(parts)
+    }
+}
+
+/// an `sse`-family instruction of the form `0f xx /r`, with an optional mandatory prefix.
+struct SseForm {
+    opcode: Opcode,
+    prefix: u8,
+    // the opcode byte after `0f` for `reg, reg/mem` forms.
+    load: u8,
+    // the opcode byte after `0f` for `reg/mem, reg` forms, if any.
+    store: Option<u8>,
+    // does this form also have an mmx encoding, without the `66` prefix?
+    mmx: bool,
+    rex_w: bool,
+    // does a memory operand select `rex.w` when it is 8 bytes wide? (`movd mm0, qword [mem]` via
+    // `48 0f 6e`)
+    gp_mem_w: bool,
+    imm8: bool,
+}
+
+const fn sse(opcode: Opcode, prefix: u8, load: u8, store: Option<u8>) -> SseForm {
+    SseForm { opcode, prefix, load, store, mmx: false, rex_w: false, gp_mem_w: false, imm8: false }
+}
+
+const fn mmx_sse(opcode: Opcode, load: u8) -> SseForm {
+    SseForm { opcode, prefix: 0x66, load, store: None, mmx: true, rex_w: false, gp_mem_w: false, imm8: false }
+}
+
+const fn sse_ib(opcode: Opcode, prefix: u8, load: u8) -> SseForm {
+    SseForm { opcode, prefix, load, store: None, mmx: false, rex_w: false, gp_mem_w: false, imm8: true }
+}
+
+const SSE_FORMS: &[SseForm] = &[
+    sse(Opcode::MOVUPS, 0x00, 0x10, Some(0x11)),
+    sse(Opcode::MOVUPD, 0x66, 0x10, Some(0x11)),
+    sse(Opcode::MOVSS, 0xf3, 0x10, Some(0x11)),
+    sse(Opcode::MOVSD, 0xf2, 0x10, Some(0x11)),
+    sse(Opcode::MOVAPS, 0x00, 0x28, Some(0x29)),
+    sse(Opcode::MOVAPD, 0x66, 0x28, Some(0x29)),
+    sse(Opcode::MOVDQA, 0x66, 0x6f, Some(0x7f)),
+    sse(Opcode::MOVDQU, 0xf3, 0x6f, Some(0x7f)),
+    SseForm { opcode: Opcode::MOVD, prefix: 0x66, load: 0x6e, store: Some(0x7e), mmx: true, rex_w: false, gp_mem_w: true, imm8: false },
+    sse(Opcode::ADDPS, 0x00, 0x58, None),
+    sse(Opcode::ADDPD, 0x66, 0x58, None),
+    sse(Opcode::ADDSS, 0xf3, 0x58, None),
+    sse(Opcode::ADDSD, 0xf2, 0x58, None),
+    sse(Opcode::MULPS, 0x00, 0x59, None),
+    sse(Opcode::MULPD, 0x66, 0x59, None),
+    sse(Opcode::MULSS, 0xf3, 0x59, None),
+    sse(Opcode::MULSD, 0xf2, 0x59, None),
+    sse(Opcode::SUBPS, 0x00, 0x5c, None),
+    sse(Opcode::SUBPD, 0x66, 0x5c, None),
+    sse(Opcode::SUBSS, 0xf3, 0x5c, None),
+    sse(Opcode::SUBSD, 0xf2, 0x5c, None),
+    sse(Opcode::MINPS, 0x00, 0x5d, None),
+    sse(Opcode::MINPD, 0x66, 0x5d, None),
+    sse(Opcode::MINSS, 0xf3, 0x5d, None),
+    sse(Opcode::MINSD, 0xf2, 0x5d, None),
+    sse(Opcode::DIVPS, 0x00, 0x5e, None),
+    sse(Opcode::DIVPD, 0x66, 0x5e, None),
+    sse(Opcode::DIVSS, 0xf3, 0x5e, None),
+    sse(Opcode::DIVSD, 0xf2, 0x5e, None),
+    sse(Opcode::MAXPS, 0x00, 0x5f, None),
+    sse(Opcode::MAXPD, 0x66, 0x5f, None),
+    sse(Opcode::MAXSS, 0xf3, 0x5f, None),
+    sse(Opcode::MAXSD, 0xf2, 0x5f, None),
+    sse(Opcode::SQRTPS, 0x00, 0x51, None),
+    sse(Opcode::SQRTPD, 0x66, 0x51, None),
+    sse(Opcode::SQRTSS, 0xf3, 0x51, None),
+    sse(Opcode::SQRTSD, 0xf2, 0x51, None),
+    sse(Opcode::ANDPS, 0x00, 0x54, None),
+    sse(Opcode::ANDPD, 0x66, 0x54, None),
+    sse(Opcode::ANDNPS, 0x00, 0x55, None),
+    sse(Opcode::ANDNPD, 0x66, 0x55, None),
+    sse(Opcode::ORPS, 0x00, 0x56, None),
+    sse(Opcode::ORPD, 0x66, 0x56, None),
+    sse(Opcode::XORPS, 0x00, 0x57, None),
+    sse(Opcode::XORPD, 0x66, 0x57, None),
+    sse(Opcode::UNPCKLPS, 0x00, 0x14, None),
+    sse(Opcode::UNPCKLPD, 0x66, 0x14, None),
+    sse(Opcode::UNPCKHPS, 0x00, 0x15, None),
+    sse(Opcode::UNPCKHPD, 0x66, 0x15, None),
+    sse(Opcode::UCOMISS, 0x00, 0x2e, None),
+    sse(Opcode::UCOMISD, 0x66, 0x2e, None),
+    sse(Opcode::COMISS, 0x00, 0x2f, None),
+    sse(Opcode::COMISD, 0x66, 0x2f, None),
+    SseForm { opcode: Opcode::CVTSI2SS, prefix: 0xf3, load: 0x2a, store: None, mmx: false, rex_w: false, gp_mem_w: true, imm8: false },
+    SseForm { opcode: Opcode::CVTSI2SD, prefix: 0xf2, load: 0x2a, store: None, mmx: false, rex_w: false, gp_mem_w: true, imm8: false },
+    sse(Opcode::CVTTSS2SI, 0xf3, 0x2c, None),
+    sse(Opcode::CVTTSD2SI, 0xf2, 0x2c, None),
+    sse(Opcode::CVTSS2SI, 0xf3, 0x2d, None),
+    sse(Opcode::CVTSD2SI, 0xf2, 0x2d, None),
+    sse(Opcode::CVTSS2SD, 0xf3, 0x5a, None),
+    sse(Opcode::CVTSD2SS, 0xf2, 0x5a, None),
+    sse(Opcode::CVTPS2PD, 0x00, 0x5a, None),
+    sse(Opcode::CVTPD2PS, 0x66, 0x5a, None),
+    sse(Opcode::CVTDQ2PS, 0x00, 0x5b, None),
+    sse(Opcode::CVTPS2DQ, 0x66, 0x5b, None),
+    sse(Opcode::CVTTPS2DQ, 0xf3, 0x5b, None),
+    mmx_sse(Opcode::PUNPCKLBW, 0x60),
+    mmx_sse(Opcode::PUNPCKLWD, 0x61),
+    mmx_sse(Opcode::PUNPCKLDQ, 0x62),
+    mmx_sse(Opcode::PACKSSWB, 0x63),
+    mmx_sse(Opcode::PCMPGTB, 0x64),
+    mmx_sse(Opcode::PCMPGTW, 0x65),
+    mmx_sse(Opcode::PCMPGTD, 0x66),
+    mmx_sse(Opcode::PACKUSWB, 0x67),
+    mmx_sse(Opcode::PUNPCKHBW, 0x68),
+    mmx_sse(Opcode::PUNPCKHWD, 0x69),
+    mmx_sse(Opcode::PUNPCKHDQ, 0x6a),
+    mmx_sse(Opcode::PACKSSDW, 0x6b),
+    sse(Opcode::PUNPCKLQDQ, 0x66, 0x6c, None),
+    sse(Opcode::PUNPCKHQDQ, 0x66, 0x6d, None),
+    mmx_sse(Opcode::PCMPEQB, 0x74),
+    mmx_sse(Opcode::PCMPEQW, 0x75),
+    mmx_sse(Opcode::PCMPEQD, 0x76),
+    mmx_sse(Opcode::PADDQ, 0xd4),
+    mmx_sse(Opcode::PMULLW, 0xd5),
+    mmx_sse(Opcode::PSUBUSB, 0xd8),
+    mmx_sse(Opcode::PSUBUSW, 0xd9),
+    mmx_sse(Opcode::PMINUB, 0xda),
+    mmx_sse(Opcode::PAND, 0xdb),
+    mmx_sse(Opcode::PADDUSB, 0xdc),
+    mmx_sse(Opcode::PADDUSW, 0xdd),
+    mmx_sse(Opcode::PMAXUB, 0xde),
+    mmx_sse(Opcode::PANDN, 0xdf),
+    mmx_sse(Opcode::PAVGB, 0xe0),
+    mmx_sse(Opcode::PAVGW, 0xe3),
+    mmx_sse(Opcode::PMULHUW, 0xe4),
+    mmx_sse(Opcode::PMULHW, 0xe5),
+    mmx_sse(Opcode::PSUBSB, 0xe8),
+    mmx_sse(Opcode::PSUBSW, 0xe9),
+    mmx_sse(Opcode::PMINSW, 0xea),
+    mmx_sse(Opcode::POR, 0xeb),
+    mmx_sse(Opcode::PADDSB, 0xec),
+    mmx_sse(Opcode::PADDSW, 0xed),
+    mmx_sse(Opcode::PMAXSW, 0xee),
+    mmx_sse(Opcode::PXOR, 0xef),
+    mmx_sse(Opcode::PMULUDQ, 0xf4),
+    mmx_sse(Opcode::PMADDWD, 0xf5),
+    mmx_sse(Opcode::PSADBW, 0xf6),
+    mmx_sse(Opcode::PSUBB, 0xf8),
+    mmx_sse(Opcode::PSUBW, 0xf9),
+    mmx_sse(Opcode::PSUBD, 0xfa),
+    mmx_sse(Opcode::PSUBQ, 0xfb),
+    mmx_sse(Opcode::PADDB, 0xfc),
+    mmx_sse(Opcode::PADDW, 0xfd),
+    mmx_sse(Opcode::PADDD, 0xfe),
+    sse_ib(Opcode::PSHUFD, 0x66, 0x70),
+    sse_ib(Opcode::PSHUFHW, 0xf3, 0x70),
+    sse_ib(Opcode::PSHUFLW, 0xf2, 0x70),
+    sse_ib(Opcode::SHUFPS, 0x00, 0xc6),
+    sse_ib(Opcode::SHUFPD, 0x66, 0xc6),
+];
+
+/// instructions with no operands and a fixed encoding.
+fn bare_form(opcode: Opcode) -> Option<Form> {
+    let (bytes, width): (&[u8], u8) = match opcode {
+        Opcode::NOP => (&[0x90], 0),
+        Opcode::HLT => (&[0xf4], 0),
+        Opcode::LEAVE => (&[0xc9], 0),
+        Opcode::CBW => (&[0x98], 2),
+        Opcode::CWDE => (&[0x98], 4),
+        Opcode::CDQE => (&[0x98], 8),
+        Opcode::CWD => (&[0x99], 2),
+        Opcode::CDQ => (&[0x99], 4),
+        Opcode::CQO => (&[0x99], 8),
+        Opcode::PUSHF => (&[0x9c], 0),
+        Opcode::POPF => (&[0x9d], 0),
+        Opcode::SAHF => (&[0x9e], 0),
+        Opcode::LAHF => (&[0x9f], 0),
+        Opcode::CMC => (&[0xf5], 0),
+        Opcode::CLC => (&[0xf8], 0),
+        Opcode::STC => (&[0xf9], 0),
+        Opcode::CLI => (&[0xfa], 0),
+        Opcode::STI => (&[0xfb], 0),
+        Opcode::CLD => (&[0xfc], 0),
+        Opcode::STD => (&[0xfd], 0),
+        Opcode::SYSCALL => (&[0x0f, 0x05], 0),
+        Opcode::SYSRET => (&[0x0f, 0x07], 0),
+        Opcode::UD2 => (&[0x0f, 0x0b], 0),
+        Opcode::RDTSC => (&[0x0f, 0x31], 0),
+        Opcode::RDMSR => (&[0x0f, 0x32], 0),
+        Opcode::WRMSR => (&[0x0f, 0x30], 0),
+        Opcode::CPUID => (&[0x0f, 0xa2], 0),
+        Opcode::LFENCE => (&[0x0f, 0xae, 0xe8], 0),
+        Opcode::MFENCE => (&[0x0f, 0xae, 0xf0], 0),
+        Opcode::SFENCE => (&[0x0f, 0xae, 0xf8], 0),
+        _ => { return None; }
+    };
+    Some(Form::new(bytes, width))
+}
+
+const ALU_OPCODES: [Opcode; 8] = [
+    Opcode::ADD, Opcode::OR, Opcode::ADC, Opcode::SBB,
+    Opcode::AND, Opcode::SUB, Opcode::XOR, Opcode::CMP,
+];
+
+fn alu_index(opcode: Opcode) -> Option<u8> {
+    ALU_OPCODES.iter().position(|o| *o == opcode).map(|i| i as u8)
+}
+
+fn shift_index(opcode: Opcode) -> Option<u8> {
+    crate::long_mode::BITWISE_OPCODE_MAP.iter().position(|o| *o == opcode).map(|i| i as u8)
+}
+
+// `test` is `f6 /0`, and handled separately.
+const UNARY_OPCODES: [Opcode; 8] = [
+    Opcode::Invalid, Opcode::Invalid, Opcode::NOT, Opcode::NEG,
+    Opcode::MUL, Opcode::IMUL, Opcode::DIV, Opcode::IDIV,
+];
+
+fn unary_index(opcode: Opcode) -> Option<u8> {
+    if opcode == Opcode::Invalid {
+        return None;
+    }
+    UNARY_OPCODES.iter().position(|o| *o == opcode).map(|i| i as u8)
+}
+
+const CONDITIONAL_JUMPS: [Opcode; 16] = [
+    Opcode::JO, Opcode::JNO, Opcode::JB, Opcode::JNB,
+    Opcode::JZ, Opcode::JNZ, Opcode::JNA, Opcode::JA,
+    Opcode::JS, Opcode::JNS, Opcode::JP, Opcode::JNP,
+    Opcode::JL, Opcode::JGE, Opcode::JLE, Opcode::JG,
+];
+
+const CONDITIONAL_SETS: [Opcode; 16] = [
+    Opcode::SETO, Opcode::SETNO, Opcode::SETB, Opcode::SETAE,
+    Opcode::SETZ, Opcode::SETNZ, Opcode::SETBE, Opcode::SETA,
+    Opcode::SETS, Opcode::SETNS, Opcode::SETP, Opcode::SETNP,
+    Opcode::SETL, Opcode::SETGE, Opcode::SETLE, Opcode::SETG,
+];
+
+const CONDITIONAL_MOVES: [Opcode; 16] = [
+    Opcode::CMOVO, Opcode::CMOVNO, Opcode::CMOVB, Opcode::CMOVNB,
+    Opcode::CMOVZ, Opcode::CMOVNZ, Opcode::CMOVNA, Opcode::CMOVA,
+    Opcode::CMOVS, Opcode::CMOVNS, Opcode::CMOVP, Opcode::CMOVNP,
+    Opcode::CMOVL, Opcode::CMOVGE, Opcode::CMOVLE, Opcode::CMOVG,
+];
+
+fn condition_index(opcode: Opcode, table: [Opcode; 16]) -> Option<u8> {
+    table.iter().position(|o| *o == opcode).map(|i| i as u8)
+}
+
+fn is_gp(reg: &RegSpec) -> bool {
+    matches!(reg.bank, RegisterBank::Q | RegisterBank::D | RegisterBank::W | RegisterBank::B | RegisterBank::rB)
+}
+
+/// apx extended gprs need a `rex2` prefix, which is not emitted here.
+fn check_legacy_gpr(reg: &RegSpec) -> Result<(), EncodeError> {
+    if is_gp(reg) && reg.num >= 16 {
+        Err(EncodeError::InvalidOperands)
+    } else {
+        Ok(())
+    }
+}
+
+fn is_accumulator(reg: &RegSpec) -> bool {
+    is_gp(reg) && reg.num == 0
+}
+
+fn is_imm(op: &Operand) -> bool {
+    imm_value(op).is_some()
+}
+
+fn is_imm8(op: &Operand) -> bool {
+    matches!(op, Operand::ImmediateI8(_) | Operand::ImmediateU8(_))
+}
+
+/// the width of a `z`-sized immediate for an operation of `width` bytes: `imm16` for 16-bit
+/// operations and `imm32` otherwise.
+fn imm_z(width: u8) -> u8 {
+    match width {
+        1 => 1,
+        2 => 2,
+        _ => 4,
+    }
+}
+
+/// `push` and `pop` only operate on 16- or 64-bit quantities in 64-bit mode; 64-bit is the default
+/// and does not need `rex.w`.
+fn stack_width(width: u8) -> u8 {
+    if width == 2 { 2 } else { 0 }
+}
+
+fn imm_value(op: &Operand) -> Option<u64> {
+    match *op {
+        Operand::ImmediateI8(v) => Some(v as i64 as u64),
+        Operand::ImmediateU8(v) => Some(v as u64),
+        Operand::ImmediateI16(v) => Some(v as i64 as u64),
+        Operand::ImmediateU16(v) => Some(v as u64),
+        Operand::ImmediateI32(v) => Some(v as i64 as u64),
+        Operand::ImmediateU32(v) => Some(v as u64),
+        Operand::ImmediateI64(v) => Some(v as u64),
+        Operand::ImmediateU64(v) => Some(v),
+        _ => None,
+    }
+}
@@ -1,5 +1,8 @@
 // use crate::long_mode::{OperandSpec, DecodeError, RegSpec, RegisterBank, Instruction, Opcode};
 use crate::long_mode::{Arch, DecodeError, RegSpec, RegisterBank, Instruction, Opcode};
+use crate::Encoding;
+use crate::long_mode::bytes;
+use crate::long_mode::decorations::compressed_disp_scale;
 use crate::long_mode::{read_modrm, read_E_vex, read_imm_unsigned};
 use yaxpeax_arch::Reader;
 
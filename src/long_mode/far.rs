@@ -0,0 +1,12 @@
+use crate::long_mode::{AccessKind, DescriptorTable, Instruction, Opcode, Operand};
+
+const FAR_POINTER_LOADS: &[Opcode] = &[Opcode::LSS, Opcode::LFS, Opcode::LGS];
+
+const PSEUDO_DESCRIPTOR_BASE_WIDTH: u8 = 8;
+
+// 64-bit code has no direct far branches.
+fn immediate_far_pointer(_op: &Operand) -> Option<(u16, u32)> {
+    None
+}
+
+include!("../shared/far.in");
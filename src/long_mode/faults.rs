@@ -0,0 +1,16 @@
+use crate::long_mode::{Category, FaultClasses, Opcode};
+
+fn mode_fault_classes(opcode: Opcode) -> Option<FaultClasses> {
+    let faults = match opcode {
+        // amx instructions fault with `#UD` if tile state is not enabled, but not `#NM` or `#XM`.
+        Opcode::LDTILECFG | Opcode::STTILECFG | Opcode::TILERELEASE | Opcode::TILEZERO |
+        Opcode::TILELOADD | Opcode::TILELOADDT1 | Opcode::TILESTORED | Opcode::TDPBSSD |
+        Opcode::TDPBSUD | Opcode::TDPBUSD | Opcode::TDPBUUD | Opcode::TDPBF16PS => {
+            FaultClasses::UD | FaultClasses::MEMORY
+        }
+        _ => { return None; }
+    };
+    Some(faults)
+}
+
+include!("../shared/faults.in");
@@ -0,0 +1,7 @@
+use crate::long_mode::{Flags, Instruction, Opcode, Operand};
+
+fn mode_flags(_opcode: Opcode) -> Option<(Flags, Flags)> {
+    None
+}
+
+include!("../shared/flags.in");
@@ -0,0 +1,20 @@
+//! a canonical description of decoded instructions, for differential fuzzing against other
+//! decoders. see [`oracle`].
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use yaxpeax_arch::LengthedInstruction;
+
+use crate::AccessKind;
+use crate::long_mode::{DecodeError, InstDecoder, Instruction, Operand};
+
+const MODE_BITS: u8 = 64;
+
+/// a `rex` prefix followed by another prefix is ignored.
+fn is_discarded_prefix(b: u8) -> bool {
+    b & 0xf0 == 0x40
+}
+
+include!("../shared/fuzz_oracle.in");
@@ -0,0 +1,5 @@
+use crate::{OperandRegisters, X86Instruction, X86Operand};
+use crate::long_mode::{Instruction, Operand, RegSpec};
+use crate::long_mode::scan::immediate_value;
+
+include!("../shared/generic.in");
@@ -0,0 +1,13 @@
+use crate::HashOptions;
+use crate::long_mode::{Instruction, MergeMode, Opcode, Operand, RegSpec, SaeMode};
+
+fn semantic_mode_operand(op: &Operand) -> SemanticOperand {
+    match *op {
+        Operand::ImmediateI64(imm) => SemanticOperand::Immediate(imm),
+        Operand::ImmediateU64(imm) => SemanticOperand::Immediate(imm as i64),
+        Operand::DisplacementU64(disp) => SemanticOperand::Memory(None, None, disp as i64, None),
+        _ => unreachable!("operand is handled by semantic_operand"),
+    }
+}
+
+include!("../shared/hash.in");
@@ -0,0 +1,40 @@
+use crate::{ImmExtension, ImmValue};
+use crate::long_mode::{Instruction, Opcode, Operand, OperandSpec};
+
+impl Operand {
+    /// this operand's immediate, with the number of bytes it is encoded in and how it is
+    /// extended, if this operand is an immediate. `ImmediateI*` variants are sign-extended and
+    /// `ImmediateU*` variants zero-extended.
+    ///
+    /// 64-bit code also uses `ImmediateI64` for 4-byte immediates sign-extended to a 64-bit
+    /// operand, as in `48 05 ff ff ff ff` (`add rax, -1`), so an `ImmediateI64` reports a size of
+    /// 8 here. [`Instruction::immediate_value`] reports the size it was actually encoded in.
+    pub fn immediate_value(&self) -> Option<ImmValue> {
+        let (size, extension, bits) = match *self {
+            Operand::ImmediateI8(imm) => (1, ImmExtension::Sign, imm as u64),
+            Operand::ImmediateU8(imm) => (1, ImmExtension::Zero, imm as u64),
+            Operand::ImmediateI16(imm) => (2, ImmExtension::Sign, imm as u64),
+            Operand::ImmediateU16(imm) => (2, ImmExtension::Zero, imm as u64),
+            Operand::ImmediateI32(imm) => (4, ImmExtension::Sign, imm as u64),
+            Operand::ImmediateU32(imm) => (4, ImmExtension::Zero, imm as u64),
+            Operand::ImmediateI64(imm) => (8, ImmExtension::Sign, imm as u64),
+            Operand::ImmediateU64(imm) => (8, ImmExtension::Zero, imm),
+            _ => { return None; }
+        };
+        Some(ImmValue::new(size, extension, bits))
+    }
+}
+
+/// `imm` as operand `spec` of `instr` encoded it. only `mov r64, imm64` and `jmpabs` encode all
+/// eight bytes of an `ImmI64`; every other 64-bit operation encodes four and sign-extends them.
+fn encoded_immediate(instr: &Instruction, spec: OperandSpec, imm: ImmValue) -> ImmValue {
+    let full = instr.opcode == Opcode::JMPABS ||
+        (instr.opcode == Opcode::MOV && instr.operands[0] == OperandSpec::RegRRR);
+    if spec == OperandSpec::ImmI64 && !full {
+        ImmValue::new(4, imm.extension(), imm.value())
+    } else {
+        imm
+    }
+}
+
+include!("../shared/immediates.in");
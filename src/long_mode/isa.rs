@@ -0,0 +1,19 @@
+use crate::long_mode::{Instruction, IsaExtension, Opcode, Operand, RegisterBank};
+
+fn mode_isa_extension(inst: &Instruction) -> Option<IsaExtension> {
+    if inst.prefixes.rex2().is_some() {
+        return Some(IsaExtension::Apx);
+    }
+    match inst.opcode() {
+        // early 64-bit processors dropped `lahf` and `sahf` in 64-bit mode.
+        Opcode::LAHF | Opcode::SAHF => Some(IsaExtension::LahfSahf),
+        Opcode::LDTILECFG | Opcode::STTILECFG | Opcode::TILERELEASE | Opcode::TILEZERO |
+        Opcode::TILELOADD | Opcode::TILELOADDT1 | Opcode::TILESTORED => Some(IsaExtension::AmxTile),
+        Opcode::TDPBSSD | Opcode::TDPBSUD | Opcode::TDPBUSD |
+        Opcode::TDPBUUD => Some(IsaExtension::AmxInt8),
+        Opcode::TDPBF16PS => Some(IsaExtension::AmxBf16),
+        _ => None,
+    }
+}
+
+include!("../shared/isa.in");
@@ -0,0 +1,7 @@
+use yaxpeax_arch::{Decoder, U8Reader};
+
+use crate::long_mode::{DecodeError, ErrorRecovery, InstDecoder, Instruction};
+#[cfg(feature = "std")]
+use crate::long_mode::Opcode;
+
+include!("../shared/decode_iter.in");
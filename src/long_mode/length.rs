@@ -0,0 +1,5 @@
+use yaxpeax_arch::{Decoder, Reader};
+
+use crate::long_mode::{Arch, DecodeError, Instruction, InstDecoder};
+
+include!("../shared/length.in");
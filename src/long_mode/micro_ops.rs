@@ -0,0 +1,15 @@
+use crate::long_mode::{AccessKind, Category, Instruction, Opcode, Operand, RegSpec, X86Operand};
+
+fn stack_pointer() -> RegSpec {
+    RegSpec::rsp()
+}
+
+fn flags_register() -> RegSpec {
+    RegSpec::rflags()
+}
+
+fn tests_counter(opcode: Opcode) -> bool {
+    matches!(opcode, Opcode::LOOP | Opcode::LOOPZ | Opcode::LOOPNZ | Opcode::JRCXZ)
+}
+
+include!("../shared/micro_ops.in");
@@ -2,12 +2,128 @@ mod vex;
 mod evex;
 #[cfg(feature = "fmt")]
 mod display;
+mod encode;
+mod operands;
+mod dataflow;
+mod flags;
+mod category;
+mod faults;
+mod branch;
+mod relocate;
+mod iter;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "micro-ops")]
+mod micro_ops;
+mod bytes;
+mod xop;
+mod length;
+mod isa;
+mod address;
+mod cpuid;
+mod scan;
+mod hash;
+mod nop;
+mod privilege;
+mod decorations;
+mod x87;
+mod synthesize;
+mod aliases;
+mod rep;
+mod opcode_id;
+mod constraints;
+mod xsave;
+mod matrix;
+mod far;
+mod summary;
+mod prefix_effects;
+mod convert;
+mod generic;
+mod sgx;
+mod immediates;
+mod tsx;
+#[cfg(feature = "msr-names")]
+mod msr;
+#[cfg(feature = "fmt")]
+mod shape;
+#[cfg(all(feature = "fmt", feature = "colors"))]
+mod colors;
+#[cfg(feature = "uarch-data")]
+mod perf;
+#[cfg(feature = "opcode-descriptions")]
+mod descriptions;
 pub mod uarch;
+#[cfg(feature = "capstone-compat")]
+pub mod capstone_compat;
+#[cfg(feature = "wasm")]
+pub(crate) mod wasm;
+#[cfg(feature = "fuzz-oracle")]
+pub mod fuzz_oracle;
+#[cfg(feature = "testgen")]
+pub mod testgen;
 
 pub use crate::MemoryAccessSize;
+pub use crate::AccessKind;
+pub use crate::WriteBehavior;
+pub use crate::DescriptorTable;
+pub use crate::AddressingForm;
+pub use crate::Flags;
+pub use crate::Category;
+pub use crate::FaultClasses;
+pub use crate::RepSemantics;
+pub use crate::IsaExtension;
+pub use crate::ErrorRecovery;
+pub use crate::HashOptions;
+pub use crate::SynthesizeError;
+pub use crate::ConversionError;
+pub use crate::RelocateError;
+pub use crate::{OperandRegisters, X86Instruction, X86Operand};
+pub use crate::SgxFunction;
+pub use crate::{ImmExtension, ImmValue};
+pub use crate::{HleHint, RtmEffect};
+pub use crate::BranchHint;
+pub use crate::NopStrictness;
+pub use crate::{ElementType, VectorShape};
+pub use crate::PrivilegeLevel;
+pub use crate::{XsaveComponent, XsaveFormat, XsaveLayout};
+pub use crate::{Encoding, EncodingInfo};
+pub use crate::{PrefixEffect, PrefixEffects};
+pub use crate::{FlowClass, InstructionSummary};
+pub use crate::DecodeHooks;
+pub use crate::{Cpu, CpuidLeaf};
+#[cfg(feature = "uarch-data")]
+pub use crate::{PerfInfo, Uarch};
 
 #[cfg(feature = "fmt")]
-pub use self::display::{DisplayStyle, InstructionDisplayer};
+pub use self::display::{DisplayListingLine, DisplayOptions, DisplaySink, DisplayStyle, InstructionDisplayer, Opcodes, OperandDisplayer, RelocDisplayer, SymbolDisplayer};
+#[cfg(feature = "fmt")]
+pub use crate::SymbolResolver;
+#[cfg(feature = "fmt")]
+pub use crate::MnemonicAliases;
+#[cfg(feature = "fmt")]
+pub use crate::RelocResolver;
+#[cfg(all(feature = "fmt", feature = "colors"))]
+pub use self::colors::ColorDisplayer;
+#[cfg(all(feature = "fmt", feature = "colors"))]
+pub use crate::Palette;
+pub use self::operands::{ImplicitOperands, MemoryAccess, MemoryAccesses, Operands};
+pub use self::dataflow::RegList;
+#[cfg(feature = "micro-ops")]
+pub use self::micro_ops::{MicroOp, MicroOps, Temp, Temps};
+pub use self::branch::BranchTarget;
+pub use self::relocate::RelocatedInstruction;
+pub use self::iter::{DecodeIterator, Decoded};
+pub use self::scan::{InstructionPattern, OperandPattern, PatternScan};
+pub use self::decorations::{Avx512Decorations, EvexDispInfo, Masking};
+pub use self::aliases::RegisterAliases;
+pub use self::constraints::OperandConstraint;
+pub use self::matrix::DecodeMatrix;
+pub use self::far::{FarPointer, PseudoDescriptor};
+#[cfg(all(feature = "fmt", feature = "msr-names"))]
+pub use self::msr::MsrDisplayer;
+pub use self::address::RegValueSource;
+pub use self::bytes::{DecodeErrorDetail, DecodeErrorReason, DecodeState, ResumableDecode};
+pub use self::encode::{EncodeError, EncodedInstruction, InstEncoder};
 
 use core::cmp::PartialEq;
 use crate::safer_unchecked::unreachable_kinda_unchecked as unreachable_unchecked;
@@ -52,11 +168,12 @@ impl Hash for RegSpec {
 
 /// the condition for a conditional instruction.
 ///
-/// these are only obtained through [`Opcode::condition()`]:
+/// these are only obtained through [`Opcode::condition()`] or [`Instruction::condition()`]:
 /// ```
 /// use yaxpeax_x86::long_mode::{Opcode, ConditionCode};
 ///
 /// assert_eq!(Opcode::JB.condition(), Some(ConditionCode::B));
+/// assert_eq!(Opcode::JB.condition().unwrap().invert(), ConditionCode::AE);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ConditionCode {
@@ -78,6 +195,31 @@ pub enum ConditionCode {
     LE,
 }
 
+impl ConditionCode {
+    /// the condition that holds exactly when this one does not: `NZ` for `Z`, `AE` for `B`, and
+    /// so on. flipping a conditional branch is replacing its condition with this.
+    pub fn invert(&self) -> ConditionCode {
+        match self {
+            ConditionCode::O => ConditionCode::NO,
+            ConditionCode::NO => ConditionCode::O,
+            ConditionCode::B => ConditionCode::AE,
+            ConditionCode::AE => ConditionCode::B,
+            ConditionCode::Z => ConditionCode::NZ,
+            ConditionCode::NZ => ConditionCode::Z,
+            ConditionCode::A => ConditionCode::BE,
+            ConditionCode::BE => ConditionCode::A,
+            ConditionCode::S => ConditionCode::NS,
+            ConditionCode::NS => ConditionCode::S,
+            ConditionCode::P => ConditionCode::NP,
+            ConditionCode::NP => ConditionCode::P,
+            ConditionCode::L => ConditionCode::GE,
+            ConditionCode::GE => ConditionCode::L,
+            ConditionCode::G => ConditionCode::LE,
+            ConditionCode::LE => ConditionCode::G,
+        }
+    }
+}
+
 macro_rules! register {
     ($bank:ident, $name:ident => $num:expr, $($tail:tt)+) => {
         #[inline]
@@ -180,7 +322,7 @@ impl RegSpec {
     /// construct a `RegSpec` for qword reg `num`
     #[inline]
     pub fn q(num: u8) -> RegSpec {
-        if num >= 16 {
+        if num >= 32 {
             panic!("invalid x86 qword reg {}", num);
         }
 
@@ -203,10 +345,36 @@ impl RegSpec {
         }
     }
 
+    /// construct a `RegSpec` for amx tile reg `num`
+    #[inline]
+    pub fn tmm(num: u8) -> RegSpec {
+        if num >= 8 {
+            panic!("invalid x86 tile reg {}", num);
+        }
+
+        RegSpec {
+            num,
+            bank: RegisterBank::T
+        }
+    }
+
+    /// construct a `RegSpec` for mpx bound reg `num`
+    #[inline]
+    pub fn bnd(num: u8) -> RegSpec {
+        if num >= 4 {
+            panic!("invalid x86 bound reg {}", num);
+        }
+
+        RegSpec {
+            num,
+            bank: RegisterBank::Bnd
+        }
+    }
+
     /// construct a `RegSpec` for dword reg `num`
     #[inline]
     pub fn d(num: u8) -> RegSpec {
-        if num >= 16 {
+        if num >= 32 {
             panic!("invalid x86 dword reg {}", num);
         }
 
@@ -219,7 +387,7 @@ impl RegSpec {
     /// construct a `RegSpec` for word reg `num`
     #[inline]
     pub fn w(num: u8) -> RegSpec {
-        if num >= 16 {
+        if num >= 32 {
             panic!("invalid x86 word reg {}", num);
         }
 
@@ -232,7 +400,7 @@ impl RegSpec {
     /// construct a `RegSpec` for non-rex byte reg `num`
     #[inline]
     pub fn rb(num: u8) -> RegSpec {
-        if num >= 16 {
+        if num >= 32 {
             panic!("invalid x86 rex-byte reg {}", num);
         }
 
@@ -311,6 +479,35 @@ impl RegSpec {
         r12b => 8, r13b => 9, r14b => 14, r15b => 15
     );
 
+    // the apx extended gprs, only produced when decoding with `InstDecoder::apx`.
+    register!(Q,
+        r16 => 16, r17 => 17, r18 => 18, r19 => 19,
+        r20 => 20, r21 => 21, r22 => 22, r23 => 23,
+        r24 => 24, r25 => 25, r26 => 26, r27 => 27,
+        r28 => 28, r29 => 29, r30 => 30, r31 => 31
+    );
+
+    register!(D,
+        r16d => 16, r17d => 17, r18d => 18, r19d => 19,
+        r20d => 20, r21d => 21, r22d => 22, r23d => 23,
+        r24d => 24, r25d => 25, r26d => 26, r27d => 27,
+        r28d => 28, r29d => 29, r30d => 30, r31d => 31
+    );
+
+    register!(W,
+        r16w => 16, r17w => 17, r18w => 18, r19w => 19,
+        r20w => 20, r21w => 21, r22w => 22, r23w => 23,
+        r24w => 24, r25w => 25, r26w => 26, r27w => 27,
+        r28w => 28, r29w => 29, r30w => 30, r31w => 31
+    );
+
+    register!(rB,
+        r16b => 16, r17b => 17, r18b => 18, r19b => 19,
+        r20b => 20, r21b => 21, r22b => 22, r23b => 23,
+        r24b => 24, r25b => 25, r26b => 26, r27b => 27,
+        r28b => 28, r29b => 29, r30b => 30, r31b => 31
+    );
+
     #[inline]
     pub const fn zmm0() -> RegSpec {
         RegSpec { bank: RegisterBank::Z, num: 0 }
@@ -336,6 +533,13 @@ impl RegSpec {
         RegSpec { bank: RegisterBank::MM, num: 0 }
     }
 
+    register!(T,
+        tmm0 => 0, tmm1 => 1, tmm2 => 2, tmm3 => 3,
+        tmm4 => 4, tmm5 => 5, tmm6 => 6, tmm7 => 7
+    );
+
+    register!(Bnd, bnd0 => 0, bnd1 => 1, bnd2 => 2, bnd3 => 3);
+
     /// return the size of this register, in bytes.
     #[inline]
     pub fn width(&self) -> u8 {
@@ -356,6 +560,7 @@ enum SizeCode {
 ///
 /// `Operand::Nothing` should be unreachable in practice; any such instructions should have an
 /// operand count of 0 (or at least one fewer than the `Nothing` operand's position).
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Operand {
@@ -517,6 +722,7 @@ impl OperandSpec {
 /// the behavior for non-`avx512` instructions is equivalent to `merge`.  `zero` is only useful in
 /// conjunction with a mask register, where bits specified in the mask register correspond to
 /// unmodified items in the instruction's desination.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MergeMode {
     Merge,
@@ -532,6 +738,7 @@ impl From<bool> for MergeMode {
     }
 }
 /// an `avx512` custom rounding mode.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum SaeMode {
     RoundNearest,
@@ -855,6 +1062,8 @@ const REGISTER_CLASS_NAMES: &[&'static str] = &[
     "rip",
     "eflags",
     "rflags",
+    "tmm",
+    "bnd",
 ];
 
 /// high-level register classes in an x86 machine, such as "8-byte general purpose", "xmm", "x87",
@@ -935,6 +1144,10 @@ pub mod register_class {
     pub const MM: RegisterClass = RegisterClass { kind: RegisterBank::MM };
     /// `avx512` mask registers k0 through k7.
     pub const K: RegisterClass = RegisterClass { kind: RegisterBank::K };
+    /// `amx` tile registers tmm0 through tmm7.
+    pub const T: RegisterClass = RegisterClass { kind: RegisterBank::T };
+    /// `mpx` bound registers bnd0 through bnd3.
+    pub const BND: RegisterClass = RegisterClass { kind: RegisterBank::Bnd };
     /// the full instruction pointer register.
     pub const RIP: RegisterClass = RegisterClass { kind: RegisterBank::RIP };
     /// the low 32 bits of `rip`.
@@ -998,8 +1211,50 @@ impl RegisterClass {
             RegisterBank::K => {
                 8
             }
+            RegisterBank::T => {
+                // tile dimensions are set by `ldtilecfg`, so there is no fixed width to report.
+                0
+            }
+            RegisterBank::Bnd => {
+                16
+            }
         }
     }
+
+    /// the number of registers in this class.
+    pub fn count(&self) -> u8 {
+        match self.kind {
+            RegisterBank::Q |
+            RegisterBank::D |
+            RegisterBank::W |
+            RegisterBank::rB => {
+                // with apx, `r16` through `r31` as well.
+                32
+            }
+            RegisterBank::B => 8,
+            RegisterBank::CR |
+            RegisterBank::DR => 16,
+            RegisterBank::S => 6,
+            RegisterBank::EIP |
+            RegisterBank::RIP |
+            RegisterBank::EFlags |
+            RegisterBank::RFlags => 1,
+            RegisterBank::X |
+            RegisterBank::Y |
+            RegisterBank::Z => 32,
+            RegisterBank::ST |
+            RegisterBank::MM |
+            RegisterBank::K |
+            RegisterBank::T => 8,
+            RegisterBank::Bnd => 4,
+        }
+    }
+
+    /// every register in this class, in order of their numbers: `xmm0` through `xmm31` for `register_class::X`.
+    pub fn registers(&self) -> impl Iterator<Item = RegSpec> {
+        let bank = self.kind;
+        (0..self.count()).map(move |num| RegSpec { num, bank })
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -1011,6 +1266,8 @@ enum RegisterBank {
     X = 15, Y = 19, Z = 23,    // XMM, YMM, ZMM
     ST = 27, MM = 28,     // ST, MM regs (x87, mmx)
     K = 29, // AVX512 mask registers
+    T = 34, // AMX tile registers
+    Bnd = 35, // MPX bound registers
 }
 
 /// the segment register used by the corresponding instruction.
@@ -1018,6 +1275,7 @@ enum RegisterBank {
 /// typically this will be `ds` but can be overridden. some instructions have specific segment
 /// registers used regardless of segment prefixes, and in these cases `yaxpeax-x86` will report the
 /// actual segment register a physical processor would use.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Segment {
     DS = 0, CS, ES, FS, GS, SS
@@ -1043,6 +1301,162 @@ const BMI2: [Opcode; 8] = [
     Opcode::SHLX,
 ];
 
+const AVX10_2: [Opcode; 4] = [
+    Opcode::VCVTNE2PH2BF8,
+    Opcode::VCVTNEPH2BF8,
+    Opcode::VCVTBIASPH2BF8,
+    Opcode::VCVT2PS2PHX,
+];
+
+const AVX512_FP16: [Opcode; 106] = [
+    Opcode::VADDPH,
+    Opcode::VADDSH,
+    Opcode::VSUBPH,
+    Opcode::VSUBSH,
+    Opcode::VMULPH,
+    Opcode::VMULSH,
+    Opcode::VDIVPH,
+    Opcode::VDIVSH,
+    Opcode::VMINPH,
+    Opcode::VMINSH,
+    Opcode::VMAXPH,
+    Opcode::VMAXSH,
+    Opcode::VSQRTPH,
+    Opcode::VSQRTSH,
+    Opcode::VRCPPH,
+    Opcode::VRCPSH,
+    Opcode::VRSQRTPH,
+    Opcode::VRSQRTSH,
+    Opcode::VSCALEFPH,
+    Opcode::VSCALEFSH,
+    Opcode::VGETEXPPH,
+    Opcode::VGETEXPSH,
+    Opcode::VGETMANTPH,
+    Opcode::VGETMANTSH,
+    Opcode::VREDUCEPH,
+    Opcode::VREDUCESH,
+    Opcode::VRNDSCALEPH,
+    Opcode::VRNDSCALESH,
+    Opcode::VFMADD132PH,
+    Opcode::VFMADD132SH,
+    Opcode::VFMADD213PH,
+    Opcode::VFMADD213SH,
+    Opcode::VFMADD231PH,
+    Opcode::VFMADD231SH,
+    Opcode::VFMSUB132PH,
+    Opcode::VFMSUB132SH,
+    Opcode::VFMSUB213PH,
+    Opcode::VFMSUB213SH,
+    Opcode::VFMSUB231PH,
+    Opcode::VFMSUB231SH,
+    Opcode::VFNMADD132PH,
+    Opcode::VFNMADD132SH,
+    Opcode::VFNMADD213PH,
+    Opcode::VFNMADD213SH,
+    Opcode::VFNMADD231PH,
+    Opcode::VFNMADD231SH,
+    Opcode::VFNMSUB132PH,
+    Opcode::VFNMSUB132SH,
+    Opcode::VFNMSUB213PH,
+    Opcode::VFNMSUB213SH,
+    Opcode::VFNMSUB231PH,
+    Opcode::VFNMSUB231SH,
+    Opcode::VFMADDSUB132PH,
+    Opcode::VFMADDSUB213PH,
+    Opcode::VFMADDSUB231PH,
+    Opcode::VFMSUBADD132PH,
+    Opcode::VFMSUBADD213PH,
+    Opcode::VFMSUBADD231PH,
+    Opcode::VFMADDCPH,
+    Opcode::VFMADDCSH,
+    Opcode::VFCMADDCPH,
+    Opcode::VFCMADDCSH,
+    Opcode::VFMULCPH,
+    Opcode::VFMULCSH,
+    Opcode::VFCMULCPH,
+    Opcode::VFCMULCSH,
+    Opcode::VCVTPH2PSX,
+    Opcode::VCVTPS2PHX,
+    Opcode::VCVTPH2PD,
+    Opcode::VCVTPD2PH,
+    Opcode::VCVTSH2SS,
+    Opcode::VCVTSS2SH,
+    Opcode::VCVTSH2SD,
+    Opcode::VCVTSD2SH,
+    Opcode::VCVTPH2DQ,
+    Opcode::VCVTTPH2DQ,
+    Opcode::VCVTDQ2PH,
+    Opcode::VCVTPH2UDQ,
+    Opcode::VCVTTPH2UDQ,
+    Opcode::VCVTUDQ2PH,
+    Opcode::VCVTPH2QQ,
+    Opcode::VCVTTPH2QQ,
+    Opcode::VCVTQQ2PH,
+    Opcode::VCVTPH2UQQ,
+    Opcode::VCVTTPH2UQQ,
+    Opcode::VCVTUQQ2PH,
+    Opcode::VCVTPH2W,
+    Opcode::VCVTTPH2W,
+    Opcode::VCVTW2PH,
+    Opcode::VCVTPH2UW,
+    Opcode::VCVTTPH2UW,
+    Opcode::VCVTUW2PH,
+    Opcode::VCVTSH2SI,
+    Opcode::VCVTTSH2SI,
+    Opcode::VCVTSI2SH,
+    Opcode::VCVTSH2USI,
+    Opcode::VCVTTSH2USI,
+    Opcode::VCVTUSI2SH,
+    Opcode::VCMPPH,
+    Opcode::VCMPSH,
+    Opcode::VCOMISH,
+    Opcode::VUCOMISH,
+    Opcode::VFPCLASSPH,
+    Opcode::VFPCLASSSH,
+    Opcode::VMOVSH,
+    Opcode::VMOVW,
+];
+
+const TBM: [Opcode; 9] = [
+    Opcode::BLCFILL,
+    Opcode::BLSFILL,
+    Opcode::BLCS,
+    Opcode::TZMSK,
+    Opcode::BLCIC,
+    Opcode::BLSIC,
+    Opcode::T1MSKC,
+    Opcode::BLCMSK,
+    Opcode::BLCI,
+];
+
+const _3DNOW: [Opcode; 25] = [
+    Opcode::FEMMS,
+    Opcode::PI2FW,
+    Opcode::PI2FD,
+    Opcode::PF2IW,
+    Opcode::PF2ID,
+    Opcode::PMULHRW,
+    Opcode::PFCMPGE,
+    Opcode::PFMIN,
+    Opcode::PFRCP,
+    Opcode::PFRSQRT,
+    Opcode::PFSUB,
+    Opcode::PFADD,
+    Opcode::PFCMPGT,
+    Opcode::PFMAX,
+    Opcode::PFRCPIT1,
+    Opcode::PFRSQIT1,
+    Opcode::PFSUBR,
+    Opcode::PFACC,
+    Opcode::PFCMPEQ,
+    Opcode::PFMUL,
+    Opcode::PFRCPIT2,
+    Opcode::PFNACC,
+    Opcode::PFPNACC,
+    Opcode::PSWAPD,
+    Opcode::PAVGUSB,
+];
+
 #[allow(dead_code)]
 const XSAVE: [Opcode; 10] = [
     Opcode::XGETBV,
@@ -1058,6 +1472,7 @@ const XSAVE: [Opcode; 10] = [
 ];
 
 /// an `x86_64` opcode. there sure are a lot of these.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[allow(non_camel_case_types)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
@@ -2135,6 +2550,7 @@ pub enum Opcode {
     WRUSS,
     WRSS,
     INCSSP,
+    RDSSP,
     SAVEPREVSSP,
     SETSSBSY,
     CLRSSBSY,
@@ -2164,6 +2580,9 @@ pub enum Opcode {
     XSUSLDTRK,
     XRESLDTRK,
 
+    // SERIALIZE
+    SERIALIZE,
+
     // AVX512F
     VALIGND,
     VALIGNQ,
@@ -2538,6 +2957,208 @@ pub enum Opcode {
     PVALIDATE,
     RMPADJUST,
     RMPUPDATE,
+    VMGEXIT,
+    JMPABS,
+    PUSHP,
+    POPP,
+
+    LDTILECFG,
+    STTILECFG,
+    TILERELEASE,
+    TILEZERO,
+    TILELOADD,
+    TILELOADDT1,
+    TILESTORED,
+    TDPBSSD,
+    TDPBSUD,
+    TDPBUSD,
+    TDPBUUD,
+    TDPBF16PS,
+
+    VCVTNE2PH2BF8,
+    VCVTNEPH2BF8,
+    VCVTBIASPH2BF8,
+    VCVT2PS2PHX,
+
+    // avx512-fp16
+    VADDPH,
+    VADDSH,
+    VSUBPH,
+    VSUBSH,
+    VMULPH,
+    VMULSH,
+    VDIVPH,
+    VDIVSH,
+    VMINPH,
+    VMINSH,
+    VMAXPH,
+    VMAXSH,
+    VSQRTPH,
+    VSQRTSH,
+    VRCPPH,
+    VRCPSH,
+    VRSQRTPH,
+    VRSQRTSH,
+    VSCALEFPH,
+    VSCALEFSH,
+    VGETEXPPH,
+    VGETEXPSH,
+    VGETMANTPH,
+    VGETMANTSH,
+    VREDUCEPH,
+    VREDUCESH,
+    VRNDSCALEPH,
+    VRNDSCALESH,
+    VFMADD132PH,
+    VFMADD132SH,
+    VFMADD213PH,
+    VFMADD213SH,
+    VFMADD231PH,
+    VFMADD231SH,
+    VFMSUB132PH,
+    VFMSUB132SH,
+    VFMSUB213PH,
+    VFMSUB213SH,
+    VFMSUB231PH,
+    VFMSUB231SH,
+    VFNMADD132PH,
+    VFNMADD132SH,
+    VFNMADD213PH,
+    VFNMADD213SH,
+    VFNMADD231PH,
+    VFNMADD231SH,
+    VFNMSUB132PH,
+    VFNMSUB132SH,
+    VFNMSUB213PH,
+    VFNMSUB213SH,
+    VFNMSUB231PH,
+    VFNMSUB231SH,
+    VFMADDSUB132PH,
+    VFMADDSUB213PH,
+    VFMADDSUB231PH,
+    VFMSUBADD132PH,
+    VFMSUBADD213PH,
+    VFMSUBADD231PH,
+    VFMADDCPH,
+    VFMADDCSH,
+    VFCMADDCPH,
+    VFCMADDCSH,
+    VFMULCPH,
+    VFMULCSH,
+    VFCMULCPH,
+    VFCMULCSH,
+    VCVTPH2PSX,
+    VCVTPS2PHX,
+    VCVTPH2PD,
+    VCVTPD2PH,
+    VCVTSH2SS,
+    VCVTSS2SH,
+    VCVTSH2SD,
+    VCVTSD2SH,
+    VCVTPH2DQ,
+    VCVTTPH2DQ,
+    VCVTDQ2PH,
+    VCVTPH2UDQ,
+    VCVTTPH2UDQ,
+    VCVTUDQ2PH,
+    VCVTPH2QQ,
+    VCVTTPH2QQ,
+    VCVTQQ2PH,
+    VCVTPH2UQQ,
+    VCVTTPH2UQQ,
+    VCVTUQQ2PH,
+    VCVTPH2W,
+    VCVTTPH2W,
+    VCVTW2PH,
+    VCVTPH2UW,
+    VCVTTPH2UW,
+    VCVTUW2PH,
+    VCVTSH2SI,
+    VCVTTSH2SI,
+    VCVTSI2SH,
+    VCVTSH2USI,
+    VCVTTSH2USI,
+    VCVTUSI2SH,
+    VCMPPH,
+    VCMPSH,
+    VCOMISH,
+    VUCOMISH,
+    VFPCLASSPH,
+    VFPCLASSSH,
+    VMOVSH,
+    VMOVW,
+
+    // xop
+    VFRCZPS,
+    VFRCZPD,
+    VFRCZSS,
+    VFRCZSD,
+    VPCMOV,
+    VPPERM,
+    VPCOMB,
+    VPCOMW,
+    VPCOMD,
+    VPCOMQ,
+    VPCOMUB,
+    VPCOMUW,
+    VPCOMUD,
+    VPCOMUQ,
+    VPHADDBW,
+    VPHADDBD,
+    VPHADDBQ,
+    VPHADDWD,
+    VPHADDWQ,
+    VPHADDDQ,
+    VPHADDUBW,
+    VPHADDUBD,
+    VPHADDUBQ,
+    VPHADDUWD,
+    VPHADDUWQ,
+    VPHADDUDQ,
+    VPHSUBBW,
+    VPHSUBWD,
+    VPHSUBDQ,
+    VPMACSSWW,
+    VPMACSSWD,
+    VPMACSSDQL,
+    VPMACSSDD,
+    VPMACSSDQH,
+    VPMACSWW,
+    VPMACSWD,
+    VPMACSDQL,
+    VPMACSDD,
+    VPMACSDQH,
+    VPMADCSSWD,
+    VPMADCSWD,
+    VPROTB,
+    VPROTW,
+    VPROTD,
+    VPROTQ,
+    VPSHLB,
+    VPSHLW,
+    VPSHLD,
+    VPSHLQ,
+    VPSHAB,
+    VPSHAW,
+    VPSHAD,
+    VPSHAQ,
+
+    // tbm
+    BLCFILL,
+    BLSFILL,
+    BLCS,
+    TZMSK,
+    BLCIC,
+    BLSIC,
+    T1MSKC,
+    BLCMSK,
+    BLCI,
+
+    // lwp
+    LLWPCB,
+    SLWPCB,
+    LWPINS,
+    LWPVAL,
 }
 
 impl PartialEq for Instruction {
@@ -2577,6 +3198,7 @@ impl PartialEq for Instruction {
 /// typically an opcode will be inspected by [`Instruction::opcode()`], and an instruction has
 /// [`Instruction::operand_count()`] many operands. operands are provided by
 /// [`Instruction::operand()`].
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, Copy, Eq)]
 pub struct Instruction {
     pub prefixes: Prefixes,
@@ -2595,6 +3217,11 @@ pub struct Instruction {
     disp: u64,
     opcode: Opcode,
     mem_size: u8,
+    // does the memory operand encode a displacement of zero? `operands` and `disp` describe it
+    // the same as no displacement, but AT&T syntax writes it out.
+    zero_disp: bool,
+    // only present if the decoder was built `with_retain_bytes()`.
+    bytes: Option<[u8; 15]>,
 }
 
 impl yaxpeax_arch::Instruction for Instruction {
@@ -2640,6 +3267,7 @@ impl std::error::Error for DecodeError {
     }
 }
 
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[allow(non_camel_case_types)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum OperandSpec {
@@ -2807,7 +3435,23 @@ pub struct InstDecoder {
     // 61. prefetchw
     // 62. tsx
     // 63. lzcnt
-    flags: u64,
+    // 64. apx (rex2-prefixed instructions and extended gprs r16-r31)
+    // 65. amx (amx-tile, amx-int8, amx-bf16)
+    // 66. avx10.2 (256-bit embedded rounding and the avx10.2 converts)
+    // 67. retain bytes (not an extension: keep the bytes of each decoded instruction)
+    // 68. 3dnow (`femms` and the `0f 0f` instructions)
+    // 69. invlpgb (`invlpgb` and `tlbsync`)
+    // 70. snp (sev-snp: `pvalidate`, `psmash`, `rmpadjust`, `rmpupdate`)
+    // 71. tdx (`seamcall`, `seamret`, `seamops`, `tdcall`)
+    // 72. smm (`rsm`)
+    // 73. uintr (`uiret`, `testui`, `clui`, `stui`, `senduipi`)
+    // 74. enqcmd (`enqcmd`, `enqcmds`)
+    // 75. serialize (`serialize`)
+    // 76. hreset (`hreset`)
+    // 77. permissive lock (not an extension: accept `lock` where the processor would `#UD`)
+    // 78. emit invalid bytes (not an extension: decode an invalid byte as `Opcode::Invalid`)
+    // 79. permissive length (not an extension: accept instructions longer than 15 bytes)
+    flags: u128,
 }
 
 impl InstDecoder {
@@ -3476,15 +4120,234 @@ impl InstDecoder {
         self
     }
 
+    pub fn apx(&self) -> bool {
+        self.flags & (1 << 64) != 0
+    }
+
+    pub fn with_apx(mut self) -> Self {
+        self.flags |= 1 << 64;
+        self
+    }
+
+    pub fn amx(&self) -> bool {
+        self.flags & (1 << 65) != 0
+    }
+
+    pub fn with_amx(mut self) -> Self {
+        self.flags |= 1 << 65;
+        self
+    }
+
+    pub fn avx10_2(&self) -> bool {
+        self.flags & (1 << 66) != 0
+    }
+
+    pub fn with_avx10_2(mut self) -> Self {
+        self.flags |= 1 << 66;
+        self
+    }
+
+    /// whether decoded instructions keep the bytes they were decoded from, available through
+    /// [`Instruction::bytes`]. this is not an extension, and is off by default.
+    pub fn retain_bytes(&self) -> bool {
+        self.flags & (1 << 67) != 0
+    }
+
+    pub fn with_retain_bytes(mut self) -> Self {
+        self.flags |= 1 << 67;
+        self
+    }
+
+    /// `femms` and the `0f 0f`-escaped 3dnow! instructions, whose opcode is selected by a
+    /// trailing byte.
+    pub fn _3dnow(&self) -> bool {
+        self.flags & (1 << 68) != 0
+    }
+
+    pub fn with_3dnow(mut self) -> Self {
+        self.flags |= 1 << 68;
+        self
+    }
+
+    /// amd's broadcast tlb invalidation, `invlpgb` and `tlbsync`.
+    pub fn invlpgb(&self) -> bool {
+        self.flags & (1 << 69) != 0
+    }
+
+    pub fn with_invlpgb(mut self) -> Self {
+        self.flags |= 1 << 69;
+        self
+    }
+
+    /// amd's sev-snp instructions for managing the reverse map table: `pvalidate`, `psmash`,
+    /// `rmpadjust`, and `rmpupdate`.
+    pub fn snp(&self) -> bool {
+        self.flags & (1 << 70) != 0
+    }
+
+    pub fn with_snp(mut self) -> Self {
+        self.flags |= 1 << 70;
+        self
+    }
+
+    /// intel's trust domain extensions: `seamcall`, `seamret`, and `seamops` for the tdx module,
+    /// and `tdcall` for trust domains.
+    pub fn tdx(&self) -> bool {
+        self.flags & (1 << 71) != 0
+    }
+
+    pub fn with_tdx(mut self) -> Self {
+        self.flags |= 1 << 71;
+        self
+    }
+
+    /// `rsm`, which only system management mode code can execute. every x86_64 processor has
+    /// `smm`, but this is not enabled by cpu presets or `from_cpuid_leaves`, since `rsm` is `#UD`
+    /// outside system management mode.
+    pub fn smm(&self) -> bool {
+        self.flags & (1 << 72) != 0
+    }
+
+    pub fn with_smm(mut self) -> Self {
+        self.flags |= 1 << 72;
+        self
+    }
+
+    /// user interrupts: `uiret`, `testui`, `clui`, `stui`, and `senduipi`.
+    pub fn uintr(&self) -> bool {
+        self.flags & (1 << 73) != 0
+    }
+
+    pub fn with_uintr(mut self) -> Self {
+        self.flags |= 1 << 73;
+        self
+    }
+
+    /// `enqcmd` and `enqcmds`, which submit work to a shared device queue.
+    pub fn enqcmd(&self) -> bool {
+        self.flags & (1 << 74) != 0
+    }
+
+    pub fn with_enqcmd(mut self) -> Self {
+        self.flags |= 1 << 74;
+        self
+    }
+
+    /// `serialize`, a serializing instruction that, unlike `cpuid`, does not modify registers.
+    pub fn serialize(&self) -> bool {
+        self.flags & (1 << 75) != 0
+    }
+
+    pub fn with_serialize(mut self) -> Self {
+        self.flags |= 1 << 75;
+        self
+    }
+
+    /// `hreset`, which resets history the processor keeps for thread director.
+    pub fn hreset(&self) -> bool {
+        self.flags & (1 << 76) != 0
+    }
+
+    pub fn with_hreset(mut self) -> Self {
+        self.flags |= 1 << 76;
+        self
+    }
+
+    /// whether a `lock` prefix on an instruction that cannot be locked, or whose destination is
+    /// not memory, is accepted rather than rejected with `DecodeError::InvalidPrefixes`. the
+    /// processor raises `#UD` for these; [`Instruction::lock_valid`] tells which decoded
+    /// instructions it would. this is not an extension, and is off by default.
+    pub fn permissive_lock(&self) -> bool {
+        self.flags & (1 << 77) != 0
+    }
+
+    pub fn with_permissive_lock(mut self) -> Self {
+        self.flags |= 1 << 77;
+        self
+    }
+
+    /// whether bytes that do not begin a valid instruction decode as a one-byte pseudo-instruction
+    /// rather than an error, so a listing can show them as data between the instructions around
+    /// them. the pseudo-instruction has opcode `Opcode::Invalid`, length 1, and the byte as its
+    /// only operand; see [`Instruction::invalid_byte`]. the next instruction is decoded from the
+    /// byte after it, however far the failed decode read. running out of input is still
+    /// `DecodeError::ExhaustedInput`. to have the byte to report, this also retains instruction
+    /// bytes as [`with_retain_bytes()`](InstDecoder::with_retain_bytes) does. this is not an
+    /// extension, and is off by default.
+    pub fn emit_invalid_bytes(&self) -> bool {
+        self.flags & (1 << 78) != 0
+    }
+
+    pub fn with_emit_invalid_bytes(mut self) -> Self {
+        self.flags |= 1 << 78;
+        self
+    }
+
+    /// whether instructions longer than the architectural limit of 15 bytes, which only redundant
+    /// prefixes can make, are decoded rather than rejected with `DecodeError::TooLong`. the
+    /// processor raises `#GP` for these; [`Instruction::length_valid`] tells which decoded
+    /// instructions it would. instructions longer than 255 bytes are rejected all the same, and
+    /// the bytes of instructions longer than 15 bytes are not retained. this is not an extension,
+    /// and is off by default.
+    pub fn permissive_length(&self) -> bool {
+        self.flags & (1 << 79) != 0
+    }
+
+    pub fn with_permissive_length(mut self) -> Self {
+        self.flags |= 1 << 79;
+        self
+    }
+
+    /// whether `avx512-fp16` instructions, half-precision arithmetic and conversions such as
+    /// `vaddph` and `vcvtph2psx`, are decoded. most of these are in evex opcode maps 5 and 6,
+    /// which no other extension uses.
+    pub fn avx512_fp16(&self) -> bool {
+        self.flags & (1 << 80) != 0
+    }
+
+    pub fn with_avx512_fp16(mut self) -> Self {
+        self.flags |= 1 << 80;
+        self
+    }
+
+    /// whether an instruction `len` bytes long is rejected as `DecodeError::TooLong`.
+    /// `permissive_length` is only tested past 15 bytes, so that it costs nothing when decoding
+    /// instructions within the limit.
+    #[inline(always)]
+    fn too_long(&self, len: usize) -> bool {
+        len > 15 && (!self.permissive_length() || len > u8::MAX as usize)
+    }
+
     /// optionally reject or reinterpret instruction according to the decoder's
     /// declared extensions.
     fn revise_instruction(&self, inst: &mut Instruction) -> Result<(), DecodeError> {
-        if inst.prefixes.evex().is_some() {
-            if !self.avx512() {
+        if let Some(evex) = inst.prefixes.evex() {
+            // every cpu with `evex` instructions has at least `avx512_f`; subsets beyond that
+            // are not distinguished.
+            if !self.avx512_f() {
+                return Err(DecodeError::InvalidOpcode);
+            }
+            if !self.avx10_2() && (!evex.u() || AVX10_2.contains(&inst.opcode)) {
+                return Err(DecodeError::InvalidOpcode);
+            }
+            if !self.avx512_fp16() && AVX512_FP16.contains(&inst.opcode) {
+                return Err(DecodeError::InvalidOpcode);
+            }
+            return Ok(());
+        }
+        if inst.prefixes.xop() {
+            // `tbm` instructions are `xop`-encoded, but only need `tbm`.
+            if TBM.contains(&inst.opcode) || inst.opcode == Opcode::BEXTR {
+                if !self.tbm() {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            } else if !self.xop() {
                 return Err(DecodeError::InvalidOpcode);
-            } else {
-                return Ok(());
             }
+            return Ok(());
+        }
+        if _3DNOW.contains(&inst.opcode) && !self._3dnow() {
+            return Err(DecodeError::InvalidOpcode);
         }
         match inst.opcode {
             Opcode::TZCNT => {
@@ -4066,11 +4929,81 @@ impl InstDecoder {
             Opcode::VMSAVE |
             Opcode::CLGI |
             Opcode::VMMCALL |
+            Opcode::VMGEXIT |
             Opcode::INVLPGA => {
                 if !self.svm() {
                     return Err(DecodeError::InvalidOpcode);
                 }
             }
+            Opcode::VMREAD |
+            Opcode::VMWRITE |
+            Opcode::VMLAUNCH |
+            Opcode::VMRESUME |
+            Opcode::VMCALL |
+            Opcode::VMXON |
+            Opcode::VMXOFF |
+            Opcode::VMPTRLD |
+            Opcode::VMPTRST |
+            Opcode::VMCLEAR |
+            Opcode::VMFUNC |
+            Opcode::INVEPT |
+            Opcode::INVVPID => {
+                if !self.vmx() {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            }
+            Opcode::INVLPGB |
+            Opcode::TLBSYNC => {
+                if !self.invlpgb() {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            }
+            Opcode::PVALIDATE |
+            Opcode::PSMASH |
+            Opcode::RMPADJUST |
+            Opcode::RMPUPDATE => {
+                if !self.snp() {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            }
+            Opcode::SEAMCALL |
+            Opcode::SEAMRET |
+            Opcode::SEAMOPS |
+            Opcode::TDCALL => {
+                if !self.tdx() {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            }
+            Opcode::RSM => {
+                if !self.smm() {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            }
+            Opcode::UIRET |
+            Opcode::TESTUI |
+            Opcode::CLUI |
+            Opcode::STUI |
+            Opcode::SENDUIPI => {
+                if !self.uintr() {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            }
+            Opcode::ENQCMD |
+            Opcode::ENQCMDS => {
+                if !self.enqcmd() {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            }
+            Opcode::SERIALIZE => {
+                if !self.serialize() {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            }
+            Opcode::HRESET => {
+                if !self.hreset() {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            }
             Opcode::STGI |
             Opcode::SKINIT => {
                 if !self.svm() || !self.skinit() {
@@ -4114,6 +5047,22 @@ impl InstDecoder {
                     return Err(DecodeError::InvalidOpcode);
                 }
             }
+            Opcode::LDTILECFG |
+            Opcode::STTILECFG |
+            Opcode::TILERELEASE |
+            Opcode::TILEZERO |
+            Opcode::TILELOADD |
+            Opcode::TILELOADDT1 |
+            Opcode::TILESTORED |
+            Opcode::TDPBSSD |
+            Opcode::TDPBSUD |
+            Opcode::TDPBUSD |
+            Opcode::TDPBUUD |
+            Opcode::TDPBF16PS => {
+                if !self.amx() {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            }
             Opcode::MONITORX | Opcode::MWAITX | // these are gated on the `monitorx` and `mwaitx` cpuid bits, but are AMD-only.
             Opcode::CLZERO | Opcode::RDPRU => { // again, gated on specific cpuid bits, but AMD-only.
                 if !self.amd_quirks() {
@@ -4141,53 +5090,65 @@ impl Default for InstDecoder {
     /// Instantiates an x86_64 decoder that probably decodes what you want.
     ///
     /// Attempts to match real processors in interpretation of undefined sequences, and decodes any
-    /// instruction defined in any extension.
+    /// instruction defined in any extension except `mpx`. `mpx` has been removed from recent
+    /// processors, which execute its encodings as the reserved `nop`s they were before it; use
+    /// `with_mpx` to decode them as `mpx` instead.
     fn default() -> Self {
         Self {
-            flags: 0xffffffff_ffffffff,
+            // every extension, but not retaining instruction bytes, which costs a copy per byte,
+            // `mpx`, whose encodings are otherwise `nop`, accepting invalid `lock` prefixes, or
+            // emitting invalid bytes as instructions.
+            flags: !((1 << 67) | (1 << 18) | (1 << 77) | (1 << 78) | (1 << 79)),
         }
     }
 }
 
 impl Decoder<Arch> for InstDecoder {
+    #[inline]
     fn decode<T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as yaxpeax_arch::Arch>::Word>>(&self, words: &mut T) -> Result<Instruction, <Arch as yaxpeax_arch::Arch>::DecodeError> {
         let mut instr = Instruction::invalid();
-        read_with_annotations(self, words, &mut instr, &mut NullSink)?;
-
-        instr.length = words.offset() as u8;
-        if words.offset() > 15 {
-            return Err(DecodeError::TooLong);
-        }
-
-        if self != &InstDecoder::default() {
-            self.revise_instruction(&mut instr)?;
-        }
-
+        self.decode_with_sink(&mut instr, words, &mut NullSink)?;
         Ok(instr)
     }
     #[inline(always)]
     fn decode_into<T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as yaxpeax_arch::Arch>::Word>>(&self, instr: &mut Instruction, words: &mut T) -> Result<(), <Arch as yaxpeax_arch::Arch>::DecodeError> {
-        self.decode_with_annotation(instr, words, &mut NullSink)
+        self.decode_with_sink(instr, words, &mut NullSink)
     }
 }
 
 impl AnnotatingDecoder<Arch> for InstDecoder {
     type FieldDescription = FieldDescription;
 
-    #[inline(always)]
     fn decode_with_annotation<
         T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as yaxpeax_arch::Arch>::Word>,
         S: DescriptionSink<Self::FieldDescription>
     >(&self, instr: &mut Instruction, words: &mut T, sink: &mut S) -> Result<(), <Arch as yaxpeax_arch::Arch>::DecodeError> {
-        read_with_annotations(self, words, instr, sink)?;
+        bytes::decode_annotated(self, instr, words, sink)
+    }
+}
+
+impl InstDecoder {
+    /// decode an instruction into `instr`, describing fields to `sink` as the decoder reads them.
+    /// `decode_with_annotation` also describes the fields the decoder does not, once the
+    /// instruction is decoded; `decode` and `decode_into` pass a `NullSink` and skip that.
+    #[inline(always)]
+    pub(crate) fn decode_with_sink<
+        T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as yaxpeax_arch::Arch>::Word>,
+        S: DescriptionSink<FieldDescription>
+    >(&self, instr: &mut Instruction, words: &mut T, sink: &mut S) -> Result<(), DecodeError> {
+        if let Err(e) = bytes::read_instruction(self, words, instr, sink) {
+            return bytes::emit_invalid_byte(self, instr, e);
+        }
 
         instr.length = words.offset() as u8;
-        if words.offset() > 15 {
-            return Err(DecodeError::TooLong);
+        if self.too_long(words.offset() as usize) {
+            return bytes::emit_invalid_byte(self, instr, DecodeError::TooLong);
         }
 
         if self != &InstDecoder::default() {
-            self.revise_instruction(instr)?;
+            if let Err(e) = self.revise_instruction(instr) {
+                return bytes::emit_invalid_byte(self, instr, e);
+            }
         }
 
         Ok(())
@@ -4196,7 +5157,8 @@ impl AnnotatingDecoder<Arch> for InstDecoder {
 
 impl Opcode {
     /// get the [`ConditionCode`] for this instruction, if it is in fact conditional. x86's
-    /// conditional instructions are `Jcc`, `CMOVcc`, andd `SETcc`.
+    /// conditional instructions are `Jcc`, `CMOVcc`, and `SETcc`, as well as the x87 `FCMOVcc`,
+    /// whose conditions only test `CF`, `ZF`, and `PF`.
     pub fn condition(&self) -> Option<ConditionCode> {
         match self {
             Opcode::JO |
@@ -4207,22 +5169,28 @@ impl Opcode {
             Opcode::SETNO => { Some(ConditionCode::NO) },
             Opcode::JB |
             Opcode::CMOVB |
-            Opcode::SETB => { Some(ConditionCode::B) },
+            Opcode::SETB |
+            Opcode::FCMOVB => { Some(ConditionCode::B) },
             Opcode::JNB |
             Opcode::CMOVNB |
-            Opcode::SETAE => { Some(ConditionCode::AE) },
+            Opcode::SETAE |
+            Opcode::FCMOVNB => { Some(ConditionCode::AE) },
             Opcode::JZ |
             Opcode::CMOVZ |
-            Opcode::SETZ => { Some(ConditionCode::Z) },
+            Opcode::SETZ |
+            Opcode::FCMOVE => { Some(ConditionCode::Z) },
             Opcode::JNZ |
             Opcode::CMOVNZ |
-            Opcode::SETNZ => { Some(ConditionCode::NZ) },
+            Opcode::SETNZ |
+            Opcode::FCMOVNE => { Some(ConditionCode::NZ) },
             Opcode::JA |
             Opcode::CMOVA |
-            Opcode::SETA => { Some(ConditionCode::A) },
+            Opcode::SETA |
+            Opcode::FCMOVNBE => { Some(ConditionCode::A) },
             Opcode::JNA |
             Opcode::CMOVNA |
-            Opcode::SETBE => { Some(ConditionCode::BE) },
+            Opcode::SETBE |
+            Opcode::FCMOVBE => { Some(ConditionCode::BE) },
             Opcode::JS |
             Opcode::CMOVS |
             Opcode::SETS => { Some(ConditionCode::S) },
@@ -4231,10 +5199,12 @@ impl Opcode {
             Opcode::SETNS => { Some(ConditionCode::NS) },
             Opcode::JP |
             Opcode::CMOVP |
-            Opcode::SETP => { Some(ConditionCode::P) },
+            Opcode::SETP |
+            Opcode::FCMOVU => { Some(ConditionCode::P) },
             Opcode::JNP |
             Opcode::CMOVNP |
-            Opcode::SETNP => { Some(ConditionCode::NP) },
+            Opcode::SETNP |
+            Opcode::FCMOVNU => { Some(ConditionCode::NP) },
             Opcode::JL |
             Opcode::CMOVL |
             Opcode::SETL => { Some(ConditionCode::L) },
@@ -4264,6 +5234,79 @@ impl Instruction {
         self.opcode
     }
 
+    /// get the [`ConditionCode`] this instruction tests, if it is conditional. this is
+    /// [`Opcode::condition`] of this instruction's opcode.
+    pub fn condition(&self) -> Option<ConditionCode> {
+        self.opcode.condition()
+    }
+
+    /// get the prefixes this instruction was decoded with, in a form that does not require
+    /// knowing how `Prefixes` stores them.
+    pub fn prefixes(&self) -> InstructionPrefixes {
+        let evex = self.prefixes.evex();
+        InstructionPrefixes {
+            segment_override: self.prefixes.segment_override(),
+            lock: self.prefixes.lock(),
+            rep: self.prefixes.rep(),
+            repnz: self.prefixes.repnz(),
+            operand_size: self.prefixes.operand_size(),
+            address_size: self.prefixes.address_size(),
+            rex: self.prefixes.rex(),
+            rex2: self.prefixes.rex2(),
+            vex: if evex.is_some() { None } else { self.prefixes.vex() },
+            xop: self.prefixes.xop(),
+            evex,
+        }
+    }
+
+    /// get the encoding this instruction was decoded from: legacy, `vex`, `xop`, or `evex`, along
+    /// with the opcode map, `pp`, vector length, and `W` fields of a `vex`-like prefix.
+    pub fn encoding_info(&self) -> EncodingInfo {
+        let (encoding, vector_length, w) = if let Some(evex) = self.prefixes.evex() {
+            (Encoding::Evex, ((evex.lp() as u8) << 1) | evex.vex().l() as u8, evex.vex().w())
+        } else if let Some(vex) = self.prefixes.vex() {
+            let encoding = if self.prefixes.xop() { Encoding::Xop } else { Encoding::Vex };
+            (encoding, vex.l() as u8, vex.w())
+        } else {
+            return EncodingInfo {
+                encoding: Encoding::Legacy,
+                map: 0,
+                pp: 0,
+                vector_length: 0,
+                w: self.prefixes.rex().map(|rex| rex.w()).unwrap_or(false),
+            };
+        };
+        EncodingInfo {
+            encoding,
+            map: self.prefixes.map_pp & 0b11111,
+            pp: self.prefixes.map_pp >> 5,
+            vector_length,
+            w,
+        }
+    }
+
+    /// get the bytes this instruction was decoded from, if it was decoded by an `InstDecoder`
+    /// built [`with_retain_bytes()`](InstDecoder::with_retain_bytes) or
+    /// [`with_emit_invalid_bytes()`](InstDecoder::with_emit_invalid_bytes), and it is no longer
+    /// than 15 bytes.
+    pub fn bytes(&self) -> Option<&[u8]> {
+        if !self.length_valid() {
+            return None;
+        }
+        self.bytes.as_ref().map(|bytes| &bytes[..self.length as usize])
+    }
+
+    /// get the byte this pseudo-instruction stands in for, if it was decoded by an `InstDecoder`
+    /// built [`with_emit_invalid_bytes()`](InstDecoder::with_emit_invalid_bytes) from a byte that
+    /// does not begin a valid instruction.
+    pub fn invalid_byte(&self) -> Option<u8> {
+        if self.opcode == Opcode::Invalid && self.operand_count == 1 {
+            Some(self.imm as u8)
+        } else {
+            None
+        }
+    }
+
     /// get the `Operand` at the provided index.
     ///
     /// panics if the index is `>= 4`.
@@ -4313,6 +5356,7 @@ impl Instruction {
             prefixes: Prefixes::new(0),
             opcode: Opcode::NOP,
             mem_size: 0,
+            zero_disp: false,
             regs: [RegSpec::rax(); 4],
             scale: 0,
             length: 0,
@@ -4320,6 +5364,7 @@ impl Instruction {
             imm: 0,
             operand_count: 0,
             operands: [OperandSpec::Nothing; 4],
+            bytes: None,
         }
     }
 
@@ -4375,25 +5420,47 @@ impl Instruction {
     }
 
     #[cfg(feature = "fmt")]
-    /// wrap a reference to this instruction with a `DisplayStyle` to format the instruction with
-    /// later. see the documentation on [`display::DisplayStyle`] for more.
+    /// wrap a reference to this instruction with a `DisplayStyle`, or more detailed
+    /// `DisplayOptions`, to format the instruction with later. see the documentation on
+    /// [`display::DisplayStyle`] and [`display::DisplayOptions`] for more.
     ///
     /// ```
-    /// use yaxpeax_x86::long_mode::{InstDecoder, DisplayStyle};
+    /// use yaxpeax_x86::long_mode::{InstDecoder, DisplayOptions, DisplayStyle};
     ///
     /// let decoder = InstDecoder::default();
     /// let inst = decoder.decode_slice(&[0x33, 0xc1]).unwrap();
     ///
     /// assert_eq!("eax ^= ecx", inst.display_with(DisplayStyle::C).to_string());
     /// assert_eq!("xor eax, ecx", inst.display_with(DisplayStyle::Intel).to_string());
+    /// assert_eq!("xor %ecx,%eax", inst.display_with(DisplayOptions::att()).to_string());
     /// ```
-    pub fn display_with<'a>(&'a self, style: display::DisplayStyle) -> display::InstructionDisplayer<'a> {
+    pub fn display_with<'a, O: Into<display::DisplayOptions>>(&'a self, options: O) -> display::InstructionDisplayer<'a> {
         display::InstructionDisplayer {
-            style,
+            options: options.into(),
             instr: self,
         }
     }
 
+    /// whether this instruction's `lock` prefix is one the processor accepts: on an instruction
+    /// that can be locked, with a memory destination. the processor raises `#UD` otherwise.
+    /// always `true` for instructions without `lock`.
+    ///
+    /// decoders reject instructions with invalid `lock` prefixes unless they are
+    /// [`permissive_lock`](InstDecoder::permissive_lock).
+    pub fn lock_valid(&self) -> bool {
+        !self.prefixes.lock() ||
+            (LOCKABLE_INSTRUCTIONS.contains(&self.opcode) && self.operands[0].is_memory())
+    }
+
+    /// whether this instruction is within the architectural limit of 15 bytes. the processor
+    /// raises `#GP` otherwise.
+    ///
+    /// decoders reject instructions longer than 15 bytes unless they are
+    /// [`permissive_length`](InstDecoder::permissive_length).
+    pub fn length_valid(&self) -> bool {
+        self.length <= 15
+    }
+
     /// does this instruction include the `xacquire` hint for hardware lock elision?
     pub fn xacquire(&self) -> bool {
         if self.prefixes.repnz() {
@@ -4415,13 +5482,14 @@ impl Instruction {
     pub fn xrelease(&self) -> bool {
         if self.prefixes.rep() {
             // xrelease is permitted on typical `lock` instructions, OR `xchg` with memory operand,
-            // regardless of `lock` prefix. additionally, xrelease is permitted on some forms of mov.
+            // regardless of `lock` prefix. additionally, xrelease is permitted on `mov` to memory from
+            // a register or immediate, but not on `mov` to a register.
             if self.prefixes.lock() {
                 true
             } else if self.opcode == Opcode::XCHG {
                 self.operands[0] != OperandSpec::RegMMM && self.operands[1] != OperandSpec::RegMMM
             } else if self.opcode == Opcode::MOV {
-                self.operands[0] != OperandSpec::RegMMM && (
+                Operand::from_spec(self, self.operands[0]).is_memory() && (
                     self.operands[1] == OperandSpec::RegRRR ||
                     self.operands[1] == OperandSpec::ImmI8 ||
                     self.operands[1] == OperandSpec::ImmI16 ||
@@ -4437,25 +5505,69 @@ impl Instruction {
     }
 }
 
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 struct EvexData {
     // data: present, z, b, Lp, Rp. aaa
     bits: u8,
 }
 
+/// the prefixes of an instruction as the decoder interpreted them, returned by
+/// [`Instruction::prefixes()`].
+///
+/// a `66` prefix that selects an opcode, like the `66` of `66 0f 38 00` (`pshufb`), is part of
+/// that opcode and is not reported as an operand-size override. `f2` and `f3` are reported even
+/// where they select an opcode, like the `f3` of `f3 0f 10` (`movss`). the register a `vex` or
+/// `evex` prefix names in `vvvv` is an operand of the instruction, not part of its prefix.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct InstructionPrefixes {
+    /// the segment named by a segment override prefix, if any.
+    pub segment_override: Option<Segment>,
+    /// is there a `lock` (`f0`) prefix?
+    pub lock: bool,
+    /// is there a `rep` (`f3`) prefix? `xrelease` is also encoded as `f3`.
+    pub rep: bool,
+    /// is there a `repnz` (`f2`) prefix? `xacquire` is also encoded as `f2`.
+    pub repnz: bool,
+    /// is there an operand-size override (`66`) prefix?
+    pub operand_size: bool,
+    /// is there an address-size override (`67`) prefix?
+    pub address_size: bool,
+    /// the `rex` prefix, if there is one. a `rex` prefix followed by another prefix is ignored
+    /// and not reported.
+    pub rex: Option<PrefixRex>,
+    /// the `rex2` prefix, if there is one. its low four bits are reported through `rex`.
+    pub rex2: Option<PrefixRex2>,
+    /// the `vex` prefix, if the instruction is vex-encoded. this also holds the fields of an `xop`
+    /// prefix.
+    pub vex: Option<PrefixVex>,
+    /// was `vex` an amd `xop` (`8f`) prefix, rather than `c4` or `c5`?
+    pub xop: bool,
+    /// the `evex` prefix, if the instruction is evex-encoded.
+    pub evex: Option<PrefixEvex>,
+}
+
 /// the prefixes on an instruction.
 ///
 /// `rep`, `repnz`, `lock`, and segment override prefixes are directly accessible here. `rex`,
 /// `vex`, and `evex` prefixes are available through their associated helpers.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Prefixes {
     bits: u8,
     rex: PrefixRex,
+    rex2: PrefixRex2,
     segment: Segment,
     evex_data: EvexData,
+    // the `mmmmm` and `pp` fields of a `vex`, `xop`, or `evex` prefix, as `0ppmmmmm`.
+    map_pp: u8,
+    // the last `2e` or `3e` prefix, kept as a possible branch hint, or `0` if there was neither.
+    branch_hint: u8,
 }
 
 /// the `avx512`-related data from an [`evex`](https://en.wikipedia.org/wiki/EVEX_prefix) prefix.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct PrefixEvex {
     vex: PrefixVex,
@@ -4489,9 +5601,15 @@ impl PrefixEvex {
     pub fn rp(&self) -> bool {
         self.evex_data.rp()
     }
+    /// the `evex` `U` bit. this is set in all instructions but avx10.2's 256-bit operations with
+    /// embedded rounding or `sae`.
+    pub fn u(&self) -> bool {
+        (self.vex.bits & 0x40) == 0
+    }
 }
 
 /// bits specified in an avx/avx2 [`vex`](https://en.wikipedia.org/wiki/VEX_prefix) prefix, `L`, `W`, `R`, `X`, and `B`.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct PrefixVex {
     bits: u8,
@@ -4517,18 +5635,33 @@ impl PrefixVex {
 
 /// bits specified in an x86_64
 /// [`rex`](https://wiki.osdev.org/X86-64_Instruction_Encoding#REX_prefix) prefix.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct PrefixRex {
     bits: u8
 }
 
+/// the bits of an apx `rex2` prefix beyond those it shares with [`PrefixRex`].
+///
+/// `rex2`-prefixed instructions also report a [`PrefixRex`] with the prefix's `w`, `r`, `x`, and
+/// `b` bits; this carries the opcode map selector and the high bits of extended register numbers.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PrefixRex2 {
+    // present, m0, r4, x4, b4
+    bits: u8
+}
+
 impl Prefixes {
     fn new(bits: u8) -> Prefixes {
         Prefixes {
             bits: bits,
             rex: PrefixRex { bits: 0 },
+            rex2: PrefixRex2 { bits: 0 },
             segment: Segment::DS,
             evex_data: EvexData { bits: 0 },
+            map_pp: 0,
+            branch_hint: 0,
         }
     }
     #[inline]
@@ -4541,20 +5674,36 @@ impl Prefixes {
     fn set_repnz(&mut self) { self.bits = (self.bits & 0xcf) | 0x30 }
     #[inline]
     pub fn rep_any(&self) -> bool { self.bits & 0x30 != 0x00 }
+    /// did an `f2` or `f3` prefix select this instruction's opcode, rather than act as `rep`?
+    #[inline]
+    fn mandatory_rep(&self) -> bool { self.bits & 0x80 != 0 }
+    #[inline]
+    fn set_mandatory_rep(&mut self) { self.bits |= 0x80 }
+    /// is there an operand-size override (`66`) prefix? a `66` prefix that selects an opcode, as
+    /// for `pshufb`, is part of that opcode and is not reported here.
     #[inline]
-    fn operand_size(&self) -> bool { self.bits & 0x1 == 1 }
+    pub fn operand_size(&self) -> bool { self.bits & 0x1 == 1 }
     #[inline]
     fn set_operand_size(&mut self) { self.bits = self.bits | 0x1 }
     #[inline]
     fn unset_operand_size(&mut self) { self.bits = self.bits & !0x1 }
+    /// is there an address-size override (`67`) prefix?
     #[inline]
-    fn address_size(&self) -> bool { self.bits & 0x2 == 2 }
+    pub fn address_size(&self) -> bool { self.bits & 0x2 == 2 }
     #[inline]
     fn set_address_size(&mut self) { self.bits = self.bits | 0x2 }
     #[inline]
+    fn unset_address_size(&mut self) { self.bits &= !0x2 }
+    #[inline]
     fn set_lock(&mut self) { self.bits |= 0x4 }
     #[inline]
     pub fn lock(&self) -> bool { self.bits & 0x4 == 4 }
+    /// was this instruction encoded with an amd `xop` prefix? `xop` lays out its fields like a
+    /// three-byte `vex` prefix, and they are reported through [`Prefixes::vex`].
+    #[inline]
+    pub fn xop(&self) -> bool { self.bits & 0x40 != 0 }
+    #[inline]
+    fn set_xop(&mut self) { self.bits |= 0x40 }
     #[inline]
     pub fn cs(&mut self) { self.segment = Segment::CS }
     #[inline]
@@ -4564,13 +5713,23 @@ impl Prefixes {
     #[inline]
     pub fn fs(&self) -> bool { self.segment == Segment::FS }
     #[inline]
-    fn set_fs(&mut self) { self.segment = Segment::FS }
+    fn set_fs(&mut self) { self.segment = Segment::FS; self.bits |= 0x8 }
     #[inline]
     pub fn gs(&self) -> bool { self.segment == Segment::GS }
     #[inline]
-    fn set_gs(&mut self) { self.segment = Segment::GS }
+    fn set_gs(&mut self) { self.segment = Segment::GS; self.bits |= 0x8 }
     #[inline]
     pub fn ss(&self) -> bool { self.segment == Segment::SS }
+    /// the segment selected by a segment override prefix, if there is one. in 64-bit code, only `fs` and `gs` overrides
+    /// have an effect; `cs`, `ds`, `es`, and `ss` prefixes are ignored and not reported.
+    #[inline]
+    pub fn segment_override(&self) -> Option<Segment> {
+        if self.bits & 0x8 != 0 {
+            Some(self.segment)
+        } else {
+            None
+        }
+    }
     #[inline]
     fn rex_unchecked(&self) -> PrefixRex { self.rex }
     #[inline]
@@ -4583,6 +5742,14 @@ impl Prefixes {
         }
     }
     #[inline]
+    pub fn rex2(&self) -> Option<PrefixRex2> {
+        if self.rex2.present() {
+            Some(self.rex2)
+        } else {
+            None
+        }
+    }
+    #[inline]
     fn vex_unchecked(&self) -> PrefixVex { PrefixVex { bits: self.rex.bits } }
     #[inline]
     pub fn vex(&self) -> Option<PrefixVex> {
@@ -4619,8 +5786,16 @@ impl Prefixes {
         self.rex.bits = bits;
     }
 
+    #[inline]
+    fn rex2_from(&mut self, payload: u8) {
+        // collect m0 and the high register bits; the low nibble is tracked by `rex`.
+        self.rex2.bits = 0x80 | ((payload >> 4) & 0x08) | ((payload >> 4) & 0x07);
+    }
+
     #[inline]
     fn vex_from_c5(&mut self, bits: u8) {
+        // `c5` implies the `0f` map.
+        self.map_pp = 0x01 | ((bits & 0b11) << 5);
         // collect rex bits
         let r = bits & 0x80;
         let wrxb = (r >> 5) ^ 0x04;
@@ -4631,6 +5806,7 @@ impl Prefixes {
 
     #[inline]
     fn vex_from_c4(&mut self, high: u8, low: u8) {
+        self.map_pp = (high & 0b11111) | ((low & 0b11) << 5);
         let w = low & 0x80;
         let rxb = (high >> 5) ^ 0x07;
         let wrxb = rxb | (w >> 4);
@@ -4641,11 +5817,14 @@ impl Prefixes {
 
     #[inline]
     fn evex_from(&mut self, b1: u8, b2: u8, b3: u8) {
+        self.map_pp = (b1 & 0b111) | ((b2 & 0b11) << 5);
         let w = b2 & 0x80;
         let rxb = ((b1 >> 5) & 0b111) ^ 0b111; // `rxb` is provided in inverted form
         let wrxb = rxb | (w >> 4);
         let l = (b3 & 0x20) >> 1;
-        let synthetic_rex = wrxb | l | 0x80;
+        // `U` is normally set; only avx10.2 clears it, so record a clear `U` instead.
+        let u_clear = ((b2 & 0x04) ^ 0x04) << 4;
+        let synthetic_rex = wrxb | l | u_clear | 0x80;
         self.rex.from(synthetic_rex);
 
         // R' is provided in inverted form
@@ -4714,6 +5893,24 @@ impl PrefixRex {
     }
 }
 
+impl PrefixRex2 {
+    #[inline]
+    fn present(&self) -> bool { (self.bits & 0x80) == 0x80 }
+    /// the `b4` bit, selecting registers 16-31 for the register in `modrm.rm`, `sib.base`, or the
+    /// opcode.
+    #[inline]
+    pub fn b4(&self) -> bool { (self.bits & 0x01) == 0x01 }
+    /// the `x4` bit, selecting registers 16-31 for the register in `sib.index`.
+    #[inline]
+    pub fn x4(&self) -> bool { (self.bits & 0x02) == 0x02 }
+    /// the `r4` bit, selecting registers 16-31 for the register in `modrm.reg`.
+    #[inline]
+    pub fn r4(&self) -> bool { (self.bits & 0x04) == 0x04 }
+    /// the `m0` bit, selecting opcode map 1 (`0f`-prefixed opcodes) rather than map 0.
+    #[inline]
+    pub fn m0(&self) -> bool { (self.bits & 0x08) == 0x08 }
+}
+
 #[derive(Debug)]
 struct OperandCodeBuilder {
     bits: u16
@@ -4989,6 +6186,8 @@ enum OperandCode {
     ModRM_0xf20f78 = OperandCodeBuilder::new().read_modrm().special_case(58).bits(),
     ModRM_0x660f78 = OperandCodeBuilder::new().read_modrm().special_case(59).bits(),
     ModRM_0xf30f1e = OperandCodeBuilder::new().special_case(60).bits(),
+    ModRM_0x0f1a = OperandCodeBuilder::new().special_case(110).bits(),
+    ModRM_0x0f1b = OperandCodeBuilder::new().special_case(111).bits(),
 //    ModRM_0x660f72 = OperandCodeBuilder::new().read_modrm().special_case(61).bits(),
 //    ModRM_0x660f73 = OperandCodeBuilder::new().read_modrm().special_case(62).bits(),
 //    ModRM_0x660fc7 = OperandCodeBuilder::new().read_modrm().special_case(63).bits(),
@@ -5745,6 +6944,9 @@ fn read_sib<
     let scale = 1u8 << (sibbyte >> 6);
     instr.scale = scale;
 
+    // with `mod` 00, there is only a displacement if `bbb` selects one.
+    instr.zero_disp = disp == 0 && (modbits != 0b00 || (sibbyte & 7) == 0b101);
+
     let op_spec = if disp == 0 {
         if (sibbyte & 7) == 0b101 {
             sink.record(
@@ -5803,6 +7005,12 @@ fn read_sib<
                 }
             }
         } else {
+            sink.record(
+                sib_start,
+                sib_start + 2,
+                InnerDescription::RegisterNumber("bbb", instr.regs[1].num & 0b111, instr.regs[1])
+                    .with_id(sib_start)
+            );
             if instr.regs[2].num == 0b0100 {
                 sink.record(
                     sib_start + 3,
@@ -5883,7 +7091,7 @@ fn read_sib<
             sink.record(
                 sib_start + 0,
                 sib_start + 2,
-                InnerDescription::RegisterNumber("bbb", instr.regs[1].num & 0b111, instr.regs[2])
+                InnerDescription::RegisterNumber("bbb", instr.regs[1].num & 0b111, instr.regs[1])
                     .with_id(sib_start + 0)
             );
             if instr.regs[2].num == 0b0100 {
@@ -5982,6 +7190,7 @@ fn read_M<
         instr.regs[1] =
             if !instr.prefixes.address_size() { RegSpec::rip() } else { RegSpec::eip() };
         if disp == 0 {
+            instr.zero_disp = true;
             OperandSpec::Deref
         } else {
             instr.disp = disp as i64 as u64;
@@ -6037,6 +7246,7 @@ fn read_M<
                     .with_id(words.offset() as u32 * 8 + 3)
             );
             if disp == 0 {
+                instr.zero_disp = true;
                 OperandSpec::Deref
             } else {
                 instr.disp = disp as i64 as u64;
@@ -6058,6 +7268,23 @@ fn width_to_gp_reg_bank(width: u8, rex: bool) -> RegisterBank {
     }
 }
 
+/// look up an opcode after a `0f`, `0f38`, or `0f3a` escape with `read`, noting in `prefixes`
+/// whether an `f2` or `f3` prefix selected it: if the opcode is different without the prefix, the
+/// prefix is part of the opcode rather than a `rep`.
+#[inline(always)]
+fn read_escaped_opcode(opcode: u8, prefixes: &mut Prefixes, read: fn(u8, &mut Prefixes) -> OpcodeRecord) -> OpcodeRecord {
+    if !prefixes.rep_any() {
+        return read(opcode, prefixes);
+    }
+    let mut unprefixed = *prefixes;
+    unprefixed.bits &= !0x30;
+    let record = read(opcode, prefixes);
+    if read(opcode, &mut unprefixed) != record {
+        prefixes.set_mandatory_rep();
+    }
+    record
+}
+
 fn read_0f_opcode(opcode: u8, prefixes: &mut Prefixes) -> OpcodeRecord {
     // seems like f2 takes priority, then f3, then 66, then "no prefix".  for SOME instructions an
     // invalid prefix is in fact an invalid instruction. so just duplicate for the four kinds of
@@ -6091,8 +7318,8 @@ fn read_0f_opcode(opcode: u8, prefixes: &mut Prefixes) -> OpcodeRecord {
             0x17 => OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::Nothing),
             0x18 => OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::ModRM_0x0f18),
             0x19 => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
+            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1a),
+            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1b),
             0x1c => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1d => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1e => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
@@ -6368,8 +7595,8 @@ fn read_0f_opcode(opcode: u8, prefixes: &mut Prefixes) -> OpcodeRecord {
             0x17 => OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::Nothing),
             0x18 => OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::ModRM_0x0f18),
             0x19 => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
+            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1a),
+            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1b),
             0x1c => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1d => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1e => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0xf30f1e),
@@ -6645,8 +7872,8 @@ fn read_0f_opcode(opcode: u8, prefixes: &mut Prefixes) -> OpcodeRecord {
             0x17 => OpcodeRecord(Interpretation::Instruction(Opcode::MOVHPD), OperandCode::PMOVX_E_G_xmm),
             0x18 => OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::ModRM_0x0f18),
             0x19 => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
+            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1a),
+            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1b),
             0x1c => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1d => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1e => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
@@ -6919,8 +8146,8 @@ fn read_0f_opcode(opcode: u8, prefixes: &mut Prefixes) -> OpcodeRecord {
             0x17 => OpcodeRecord(Interpretation::Instruction(Opcode::MOVHPS), OperandCode::PMOVX_E_G_xmm),
             0x18 => OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::ModRM_0x0f18),
             0x19 => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
+            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1a),
+            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1b),
             0x1c => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1d => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1e => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
@@ -7312,7 +8539,8 @@ fn read_0f38_opcode(opcode: u8, prefixes: &mut Prefixes) -> OpcodeRecord {
 
 fn read_0f3a_opcode(opcode: u8, prefixes: &mut Prefixes) -> OpcodeRecord {
     if prefixes.rep() {
-        if prefixes != &Prefixes::new(0x10) {
+        // a `2e` or `3e` prefix is ignored here, as it is everywhere else in 64-bit code.
+        if (Prefixes { branch_hint: 0, ..*prefixes }) != Prefixes::new(0x10) {
             return OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::Nothing);
         }
         return match opcode {
@@ -7503,9 +8731,12 @@ fn read_with_annotations<
     // default x86_64 registers to `[rax; 4]`
     instruction.regs = unsafe { core::mem::transmute(0u64) };
     instruction.mem_size = 0;
+    instruction.zero_disp = false;
     // default operands to [RegRRR, Nothing, Nothing, Nothing]
     instruction.operands = unsafe { core::mem::transmute(0x00_00_00_01) };
     instruction.operand_count = 2;
+    // only meaningful if `prefixes` ends up with a `rex2` prefix.
+    let mut rex2_payload = 0;
 
     let record: OpcodeRecord = loop {
         let record = next_rec;
@@ -7515,7 +8746,7 @@ fn read_with_annotations<
                 desc: InnerDescription::RexPrefix(b),
                 id: words.offset() as u32 * 8 - 8,
             });
-            if words.offset() >= 15 {
+            if decoder.too_long(words.offset() as usize + 1) {
                 return Err(DecodeError::TooLong);
             }
             nextb = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
@@ -7523,6 +8754,89 @@ fn read_with_annotations<
                 core::ptr::read_volatile(&OPCODES[nextb as usize])
             };
             prefixes.rex_from(b);
+        } else if nextb == 0xd5 && decoder.apx() {
+            // like `rex`, `rex2` must be the last prefix before an opcode, and an instruction can
+            // only have one of the two.
+            if prefixes.rex_unchecked().present() {
+                return Err(DecodeError::InvalidPrefixes);
+            }
+            sink.record((words.offset() - 1) as u32 * 8, (words.offset() - 1) as u32 * 8 + 7, FieldDescription {
+                desc: InnerDescription::Misc("rex2 prefix (0xd5)"),
+                id: words.offset() as u32 * 8 - 8,
+            });
+            let payload = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
+            sink.record((words.offset() - 1) as u32 * 8, (words.offset() - 1) as u32 * 8 + 7, FieldDescription {
+                desc: InnerDescription::Misc("rex2 payload (m0 r4 x4 b4 w r3 x3 b3)"),
+                id: words.offset() as u32 * 8 - 8,
+            });
+            prefixes.rex2_from(payload);
+            // operands are read as if `r`, `x`, and `b` were set wherever `r4`, `x4`, and `b4`
+            // are. `apply_rex2` repairs register numbers once operands are read.
+            prefixes.rex_from(0x40 | (payload & 0x0f) | ((payload >> 4) & 0x07));
+            rex2_payload = payload;
+            let b = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
+            bytes::record_opcode_map(
+                sink,
+                words.offset() as u32 * 8 - 8,
+                words.offset() as u32 * 8 - 1,
+                Encoding::Legacy,
+                payload >> 7,
+            );
+            if payload & 0x80 != 0 {
+                // map 1, as if the opcode were preceded by `0f`. rows 3 and 8 (including jumps and
+                // the `0f38`/`0f3a` escapes) are not available with `rex2`, nor are 3dnow and
+                // `femms`.
+                if b & 0xf0 == 0x30 || b & 0xf0 == 0x80 || b == 0x0e || b == 0x0f {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+                break read_escaped_opcode(b, &mut prefixes, read_0f_opcode);
+            }
+            // rows 4, 7, a, and e of map 0 are not available with `rex2`, other than `jmpabs`.
+            match b {
+                0x40..=0x4f | 0x70..=0x7f | 0xa0 | 0xa2..=0xaf | 0xe0..=0xef => {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+                0xa1 => {
+                    // `jmpabs imm64` takes the place of `mov eax, moffs` for `rex2.w=0`.
+                    if payload & 0x08 != 0 {
+                        return Err(DecodeError::InvalidOpcode);
+                    }
+                    if prefixes.lock() || prefixes.operand_size() || prefixes.address_size() || prefixes.rep_any() {
+                        return Err(DecodeError::InvalidPrefixes);
+                    }
+                    prefixes.rex_from(0x40 | (payload & 0x0f));
+                    instruction.prefixes = prefixes;
+                    instruction.opcode = Opcode::JMPABS;
+                    instruction.imm = read_num(words, 8)?;
+                    sink.record(
+                        words.offset() as u32 * 8 - 64,
+                        words.offset() as u32 * 8 - 1,
+                        InnerDescription::Number("8-byte immediate", instruction.imm as i64)
+                            .with_id(words.offset() as u32 * 8 - 64)
+                    );
+                    instruction.operands[0] = OperandSpec::ImmI64;
+                    instruction.operand_count = 1;
+                    return Ok(());
+                }
+                _ => {}
+            }
+            let record = OPCODES[b as usize];
+            match record.0 {
+                // `rex2.w` turns `push` and `pop` into their "push-pop acceleration" forms.
+                Interpretation::Instruction(Opcode::PUSH) if payload & 0x08 != 0 && b < 0x58 => {
+                    break OpcodeRecord(Interpretation::Instruction(Opcode::PUSHP), record.1);
+                }
+                Interpretation::Instruction(Opcode::POP) if payload & 0x08 != 0 && (0x58..0x60).contains(&b) => {
+                    break OpcodeRecord(Interpretation::Instruction(Opcode::POPP), record.1);
+                }
+                Interpretation::Instruction(_) => {
+                    break record;
+                }
+                // the `0f` escape, other prefixes, or `vex`/`evex`.
+                _ => {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            }
         } else if let Interpretation::Instruction(opc) = record.0 {
             if words.offset() > 1 {
                 sink.record(
@@ -7531,7 +8845,18 @@ fn read_with_annotations<
                         .with_id(words.offset() as u32 * 8 - 9)
                 );
             }
-            if opc != Opcode::Invalid {
+            bytes::record_opcode_map(
+                sink,
+                words.offset() as u32 * 8 - 8,
+                words.offset() as u32 * 8 - 1,
+                Encoding::Legacy,
+                0,
+            );
+            // `8f` is an `xop` prefix rather than `pop` if its `modrm` says so, and a description
+            // of `pop` could not be taken back. `decode_with_annotation` describes the opcode once
+            // the instruction is decoded.
+            let provisional = record.1 == OperandCode::ModRM_0x8f_Ev;
+            if opc != Opcode::Invalid && !provisional {
                 sink.record((words.offset() - 1) as u32 * 8, (words.offset() - 1) as u32 * 8 + 7, FieldDescription {
                     desc: InnerDescription::Opcode(opc),
                     id: words.offset() as u32 * 8 - 8,
@@ -7552,15 +8877,19 @@ fn read_with_annotations<
                             .with_id(words.offset() as u32 * 8 - 9)
                     );
                 }
+                let escape_start = words.offset() as u32 * 8 - 8;
                 let b = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
                 if b == 0x38 {
+                    bytes::record_opcode_map(sink, escape_start, escape_start + 15, Encoding::Legacy, 2);
                     let b = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
-                    break read_0f38_opcode(b, &mut prefixes);
+                    break read_escaped_opcode(b, &mut prefixes, read_0f38_opcode);
                 } else if b == 0x3a {
+                    bytes::record_opcode_map(sink, escape_start, escape_start + 15, Encoding::Legacy, 3);
                     let b = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
-                    break read_0f3a_opcode(b, &mut prefixes);
+                    break read_escaped_opcode(b, &mut prefixes, read_0f3a_opcode);
                 } else {
-                    break read_0f_opcode(b, &mut prefixes);
+                    bytes::record_opcode_map(sink, escape_start, escape_start + 7, Encoding::Legacy, 1);
+                    break read_escaped_opcode(b, &mut prefixes, read_0f_opcode);
                 }
             }
             // some prefix seen after we saw rex, but before the 0f escape or an actual
@@ -7629,7 +8958,7 @@ fn read_with_annotations<
             next_rec = unsafe {
                 core::ptr::read_volatile(&OPCODES[nextb as usize])
             };
-            if words.offset() >= 15 {
+            if decoder.too_long(words.offset() as usize + 1) {
                 return Err(DecodeError::TooLong);
             }
             if prefixes.rex.bits != 0 {
@@ -7640,10 +8969,17 @@ fn read_with_annotations<
             }
             prefixes.rex_from(0);
             match b {
-                0x26 |
                 0x2e |
-                0x36 |
                 0x3e => {
+                    /* no-op in amd64, except as a branch hint */
+                    sink.record((words.offset() - 2) as u32 * 8, (words.offset() - 2) as u32 * 8 + 7, FieldDescription {
+                        desc: InnerDescription::Misc("ignored prefix in 64-bit mode"),
+                        id: words.offset() as u32 * 8 - 16,
+                    });
+                    prefixes.branch_hint = b;
+                },
+                0x26 |
+                0x36 => {
                     /* no-op in amd64 */
                     sink.record((words.offset() - 2) as u32 * 8, (words.offset() - 2) as u32 * 8 + 7, FieldDescription {
                         desc: InnerDescription::Misc("ignored prefix in 64-bit mode"),
@@ -7713,14 +9049,45 @@ fn read_with_annotations<
     instruction.prefixes = prefixes;
     read_operands(decoder, words, instruction, record.1, sink)?;
 
-    if instruction.prefixes.lock() {
-        if !LOCKABLE_INSTRUCTIONS.contains(&instruction.opcode) || !instruction.operands[0].is_memory() {
-            return Err(DecodeError::InvalidPrefixes);
-        }
+    if instruction.prefixes.rex2().is_some() {
+        apply_rex2(instruction, record.1, rex2_payload);
+    }
+
+    if !instruction.lock_valid() && !decoder.permissive_lock() {
+        return Err(DecodeError::InvalidPrefixes);
     }
 
     Ok(())
 }
+
+/// `rex2` carries a fourth bit for each of `modrm.reg`, `sib.index`, and `modrm.rm` (or `sib.base`,
+/// or an opcode-embedded register). operands are read with each `rex` bit set if its fourth bit
+/// is, so a register selected by such a field has a number of at least 8, where fixed registers
+/// never do. gprs from those fields are moved to r16-r31, while other registers, which ignore the
+/// fourth bits, are set back to the number without them.
+fn apply_rex2(instruction: &mut Instruction, operand_code: OperandCode, payload: u8) {
+    let rex2 = instruction.prefixes.rex2;
+    let operand_code = OperandCodeBuilder::from_bits(operand_code as u16);
+    let op0_is_z = operand_code.has_embedded_instructions() &&
+        operand_code.get_embedded_instructions().is_ok();
+    let fields = [
+        if op0_is_z { (rex2.b4(), payload & 0x01 != 0) } else { (rex2.r4(), payload & 0x04 != 0) },
+        (rex2.b4(), payload & 0x01 != 0),
+        (rex2.x4(), payload & 0x02 != 0),
+    ];
+    for (reg, (high, low)) in instruction.regs.iter_mut().zip(fields.iter()) {
+        if !high || reg.num < 8 {
+            continue;
+        }
+        let gpr = match reg.bank {
+            RegisterBank::Q | RegisterBank::D | RegisterBank::W | RegisterBank::rB => 0b10000,
+            _ => 0,
+        };
+        reg.num = (reg.num & 0b111) | if *low { 0b1000 } else { 0 } | gpr;
+    }
+    instruction.prefixes.rex_from(0x40 | (payload & 0x0f));
+}
+
 /* likely cases
         OperandCode::Eb_R0 => 0
         _op @ OperandCode::ModRM_0x80_Eb_Ib => 1
@@ -7793,7 +9160,7 @@ fn read_operands<
                 bank = RegisterBank::B;
             }
         };
-        modrm = read_modrm(words)?;
+        modrm = read_modrm(words, sink)?;
         instruction.regs[0].bank = bank;
         instruction.regs[0].num = ((modrm >> 3) & 7) + if instruction.prefixes.rex_unchecked().r() { 0b1000 } else { 0 };
         sink.record(
@@ -7825,14 +9192,8 @@ fn read_operands<
             if operand_code.bits() == (OperandCode::Gv_M as u16) {
                 return Err(DecodeError::InvalidOperand);
             }
-            let res = read_modrm_reg(instruction, modrm, bank)?;
-            sink.record(
-                modrm_start,
-                modrm_start + 2,
-                InnerDescription::RegisterNumber("mmm", modrm & 7, instruction.regs[1])
-                    .with_id(modrm_start + 2)
-            );
-            res
+            // as with `rrr`, the register's bank may change with the operand code.
+            read_modrm_reg(instruction, modrm, bank)?
         } else {
             read_M(words, instruction, modrm, sink)?
         };
@@ -7869,15 +9230,21 @@ fn read_operands<
                 bank = RegisterBank::B;
             }
         };
-        modrm = read_modrm(words)?;
+        modrm = read_modrm(words, sink)?;
+        if operand_code.bits() == (OperandCode::ModRM_0x8f_Ev as u16) && modrm & 0b0011_1000 != 0 {
+            // `8f` is only `pop` with an `rrr` of 0b000. otherwise, this "modrm" byte is the first
+            // byte of an amd `xop` prefix, where those bits select opcode maps 8 and up.
+            if instruction.prefixes.rex_unchecked().present() || instruction.prefixes.lock() || instruction.prefixes.operand_size() || instruction.prefixes.rep_any() {
+                // like `vex`, `xop` can't follow these prefixes.
+                return Err(DecodeError::InvalidPrefixes);
+            }
+            return xop::read_xop(words, modrm, instruction, sink);
+        }
         instruction.regs[0].bank = bank;
         instruction.regs[0].num = ((modrm >> 3) & 7) + if instruction.prefixes.rex_unchecked().r() { 0b1000 } else { 0 };
-        sink.record(
-            modrm_start + 3,
-            modrm_start + 5,
-            InnerDescription::RegisterNumber("rrr", (modrm >> 3) & 7, instruction.regs[0])
-                .with_id(modrm_start + 3)
-        );
+        // `rrr` may be part of the opcode rather than a register, and the register's bank may change
+        // with the operand code. `decode_with_annotation` describes it once the instruction is
+        // decoded.
 
         mem_oper = if modrm >= 0b11000000 {
             sink.record(
@@ -7991,7 +9358,7 @@ fn read_operands<
                         sink.record(
                             opcode_start,
                             opcode_start + 2,
-                            InnerDescription::RegisterNumber("zzz", reg, instruction.regs[0])
+                            InnerDescription::RegisterNumber("zzz", reg, instruction.regs[1])
                                 .with_id(opcode_start + 1)
                         );
                         if instruction.prefixes.rex_unchecked().b() {
@@ -8198,7 +9565,7 @@ fn read_operands<
                         );
                         imm
                     };
-                    instruction.operands[0] = OperandSpec::ImmI32;
+                    instruction.operands[0] = if opwidth == 2 { OperandSpec::ImmI16 } else { OperandSpec::ImmI32 };
                     instruction.operand_count = 1;
                     return Ok(());
                 }
@@ -8396,7 +9763,7 @@ fn read_operands<
                 2
             };
             let opwidth = imm_width_from_prefixes_64(SizeCode::vqp, instruction.prefixes);
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E(words, instruction, modrm, w, sink)?;
             instruction.regs[0] =
@@ -8414,7 +9781,7 @@ fn read_operands<
         },
         17 => {
             let opwidth = 8;
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E(words, instruction, modrm, 4 /* opwidth */, sink)?;
             instruction.mem_size = 4;
@@ -8475,7 +9842,7 @@ fn read_operands<
             }
         },
         22 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E_xmm(words, instruction, modrm, sink)?;
             instruction.regs[0] =
@@ -8622,11 +9989,11 @@ fn read_operands<
                 sink.record(
                     modrm_start + 3,
                     modrm_start + 5,
-                    InnerDescription::Misc("rrr field > 0b000 for this opcode is illegal, except with XOP extensions")
+                    InnerDescription::Misc("rrr field > 0b000 for this opcode is illegal")
                         .with_id(modrm_start - 8)
                 );
-                // TODO: this is where XOP decoding would occur
-                return Err(DecodeError::IncompleteDecoder);
+                // `xop` prefixes, where `rrr` is not 0b000, are decoded before operands are read.
+                return Err(DecodeError::InvalidOpcode);
             }
             instruction.opcode = [
                 Opcode::POP,
@@ -8672,7 +10039,7 @@ fn unlikely_operands<
 >(decoder: &InstDecoder, words: &mut T, instruction: &mut Instruction, operand_code: OperandCode, mem_oper: OperandSpec, sink: &mut S) -> Result<(), DecodeError> {
     match operand_code {
         OperandCode::G_E_mm_Ib => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E_mm(words, instruction, modrm, sink)?;
             instruction.regs[0] =
@@ -8686,7 +10053,7 @@ fn unlikely_operands<
             instruction.operand_count = 3;
         }
         OperandCode::G_Ev_xmm_Ib => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E_xmm(words, instruction, modrm, sink)?;
             instruction.regs[0] =
@@ -8710,7 +10077,7 @@ fn unlikely_operands<
             instruction.operand_count = 3;
         }
         OperandCode::PMOVX_E_G_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.rex_unchecked().r(), RegisterBank::X);
@@ -8729,7 +10096,7 @@ fn unlikely_operands<
             }
         }
         OperandCode::PMOVX_G_E_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.rex_unchecked().r(), RegisterBank::X);
@@ -8750,7 +10117,7 @@ fn unlikely_operands<
             }
         }
         OperandCode::INV_Gv_M => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm >= 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -8772,7 +10139,7 @@ fn unlikely_operands<
             }
         }
         OperandCode::G_U_xmm_Ub => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E_xmm(words, instruction, modrm, sink)?;
             if instruction.operands[1] != OperandSpec::RegMMM {
@@ -8788,7 +10155,7 @@ fn unlikely_operands<
         OperandCode::ModRM_0xf20f78 => {
             instruction.opcode = Opcode::INSERTQ;
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             if modrm < 0b11_000_000 {
                 return Err(DecodeError::InvalidOperand);
@@ -8811,7 +10178,7 @@ fn unlikely_operands<
         OperandCode::ModRM_0x660f78 => {
             instruction.opcode = Opcode::EXTRQ;
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             if modrm < 0b11_000_000 {
                 return Err(DecodeError::InvalidOperand);
@@ -8834,7 +10201,7 @@ fn unlikely_operands<
 
         }
         OperandCode::ModRM_0xf30f1e => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             match modrm {
                 0xfa => {
                     instruction.opcode = Opcode::ENDBR64;
@@ -8844,6 +10211,17 @@ fn unlikely_operands<
                     instruction.opcode = Opcode::ENDBR32;
                     instruction.operand_count = 0;
                 },
+                0xc8..=0xcf => {
+                    instruction.opcode = Opcode::RDSSP;
+                    let bank = if instruction.prefixes.rex_unchecked().w() {
+                        RegisterBank::Q
+                    } else {
+                        RegisterBank::D
+                    };
+                    instruction.regs[1] = RegSpec::from_parts(modrm & 7, instruction.prefixes.rex_unchecked().b(), bank);
+                    instruction.operands[0] = OperandSpec::RegMMM;
+                    instruction.operand_count = 1;
+                },
                 _ => {
                     let (sz, bank) = if instruction.prefixes.rex_unchecked().w() {
                         (8, RegisterBank::Q)
@@ -8862,8 +10240,80 @@ fn unlikely_operands<
                 }
             };
         }
+        OperandCode::ModRM_0x0f1a |
+        OperandCode::ModRM_0x0f1b => {
+            let modrm = read_modrm(words, sink)?;
+            if !decoder.mpx() {
+                // without mpx, these are the reserved `nop Ev` they execute as on processors that
+                // predate it or have removed it.
+                let opwidth = imm_width_from_prefixes_64(SizeCode::vqp, instruction.prefixes);
+                instruction.operands[0] = read_E(words, instruction, modrm, opwidth, sink)?;
+                instruction.mem_size = opwidth;
+                instruction.operand_count = 1;
+                return Ok(());
+            }
+
+            let r = (modrm >> 3) & 7;
+            if r >= 4 || instruction.prefixes.rex_unchecked().r() {
+                // there are only four bound registers.
+                return Err(DecodeError::InvalidOperand);
+            }
+            instruction.regs[0] = RegSpec::bnd(r);
+            let store = operand_code == OperandCode::ModRM_0x0f1b;
+            // as with the rest of `0f`, `f2` takes priority over `f3`, which takes priority over
+            // `66`.
+            // only `bndmov` accesses its memory operand; the others use just its address, and
+            // `bndldx` and `bndstx` a bound table entry found from it.
+            instruction.mem_size = 63;
+            let rm = if instruction.prefixes.repnz() || instruction.prefixes.rep() {
+                instruction.opcode = match (instruction.prefixes.repnz(), store) {
+                    (true, false) => Opcode::BNDCU,
+                    (true, true) => Opcode::BNDCN,
+                    (false, false) => Opcode::BNDCL,
+                    (false, true) => Opcode::BNDMK,
+                };
+                if instruction.opcode == Opcode::BNDMK {
+                    // `bndmk` takes its bounds from an address computation, not a register.
+                    if modrm >= 0xc0 {
+                        return Err(DecodeError::InvalidOperand);
+                    }
+                    read_M(words, instruction, modrm, sink)?
+                } else {
+                    // bound checks compare an address, which is always 64 bits here.
+                    read_E(words, instruction, modrm, 8, sink)?
+                }
+            } else if instruction.prefixes.operand_size() {
+                instruction.opcode = Opcode::BNDMOV;
+                if modrm >= 0xc0 {
+                    let spec = read_modrm_reg(instruction, modrm, RegisterBank::Bnd)?;
+                    if instruction.regs[1].num >= 4 {
+                        return Err(DecodeError::InvalidOperand);
+                    }
+                    spec
+                } else {
+                    instruction.mem_size = 16;
+                    read_M(words, instruction, modrm, sink)?
+                }
+            } else {
+                // `bndldx` and `bndstx` find a bound table entry from the base and index of their
+                // memory operand, so there must be one.
+                instruction.opcode = if store { Opcode::BNDSTX } else { Opcode::BNDLDX };
+                if modrm >= 0xc0 {
+                    return Err(DecodeError::InvalidOperand);
+                }
+                read_M(words, instruction, modrm, sink)?
+            };
+            if store && instruction.opcode != Opcode::BNDCN && instruction.opcode != Opcode::BNDMK {
+                instruction.operands[0] = rm;
+                instruction.operands[1] = OperandSpec::RegRRR;
+            } else {
+                instruction.operands[0] = OperandSpec::RegRRR;
+                instruction.operands[1] = rm;
+            }
+            instruction.operand_count = 2;
+        }
         OperandCode::G_E_xmm_Ub => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E_xmm(words, instruction, modrm, sink)?;
             instruction.regs[0] =
@@ -8950,7 +10400,7 @@ fn unlikely_operands<
             instruction.operand_count = 3;
         }
         OperandCode::G_mm_Ew_Ib => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E(words, instruction, modrm, 4, sink)?;
             instruction.regs[0] =
@@ -8989,7 +10439,7 @@ fn unlikely_operands<
         },
         OperandCode::Gv_Ew_LSL => {
             let opwidth = imm_width_from_prefixes_64(SizeCode::vqp, instruction.prefixes);
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E(words, instruction, modrm, 2, sink)?;
             // lsl is weird. the full register width is written, but only the low 16 bits are used.
@@ -9003,7 +10453,7 @@ fn unlikely_operands<
         },
         OperandCode::Gdq_Ev => {
             let opwidth = imm_width_from_prefixes_64(SizeCode::vqp, instruction.prefixes);
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E(words, instruction, modrm, opwidth, sink)?;
             // `opwidth` can be 2, 4, or 8 here. if opwidth is 2, the first operand is a dword.
@@ -9099,7 +10549,7 @@ fn unlikely_operands<
         }
         OperandCode::Mdq_Gdq => {
             let opwidth = if instruction.prefixes.rex_unchecked().w() { 8 } else { 4 };
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = instruction.operands[0];
             instruction.operands[0] = read_E(words, instruction, modrm, opwidth, sink)?;
@@ -9128,7 +10578,7 @@ fn unlikely_operands<
                 return Err(DecodeError::InvalidOpcode);
             }
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[0] = read_E(words, instruction, modrm, 8, sink)?;
             instruction.operands[1] = OperandSpec::RegRRR;
@@ -9144,7 +10594,7 @@ fn unlikely_operands<
                 return Err(DecodeError::InvalidOpcode);
             }
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[0] = OperandSpec::RegRRR;
             instruction.regs[0] =
@@ -9176,7 +10626,7 @@ fn unlikely_operands<
             //
             // anyway, there are two operands, and the primary concern here is "what are they?".
             instruction.operand_count = 2;
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.rex_unchecked().r(), RegisterBank::X);
             instruction.operands[1] = read_E_xmm(words, instruction, modrm, sink)?;
@@ -9198,7 +10648,7 @@ fn unlikely_operands<
             }
         }
         OperandCode::ModRM_0x0f0d => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let r = (modrm >> 3) & 0b111;
 
             let opwidth = imm_width_from_prefixes_64(SizeCode::vq, instruction.prefixes);
@@ -9221,7 +10671,7 @@ fn unlikely_operands<
             // 3dnow instructions are WILD, the opcode is encoded as an imm8 trailing the
             // instruction.
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.operands[1] = read_E_mm(words, instruction, modrm, sink)?;
             instruction.operands[0] = OperandSpec::RegRRR;
             instruction.regs[0] = RegSpec { bank: RegisterBank::MM, num: (modrm >> 3) & 7 };
@@ -9229,7 +10679,7 @@ fn unlikely_operands<
                 instruction.mem_size = 8;
             }
 
-            let opcode = read_modrm(words)?;
+            let opcode = read_modrm(words, sink)?;
             match opcode {
                 0x0c => {
                     instruction.opcode = Opcode::PI2FW;
@@ -9310,7 +10760,7 @@ fn unlikely_operands<
         }
         OperandCode::ModRM_0x0fc7 => {
             if instruction.prefixes.repnz() {
-                let modrm = read_modrm(words)?;
+                let modrm = read_modrm(words, sink)?;
                 let is_reg = (modrm & 0xc0) == 0xc0;
 
                 let r = (modrm >> 3) & 7;
@@ -9339,7 +10789,7 @@ fn unlikely_operands<
             }
             if instruction.prefixes.operand_size() {
                 let opwidth = imm_width_from_prefixes_64(SizeCode::vqp, instruction.prefixes);
-                let modrm = read_modrm(words)?;
+                let modrm = read_modrm(words, sink)?;
                 let is_reg = (modrm & 0xc0) == 0xc0;
 
                 let r = (modrm >> 3) & 7;
@@ -9398,7 +10848,7 @@ fn unlikely_operands<
 
             if instruction.prefixes.rep() {
                 let opwidth = imm_width_from_prefixes_64(SizeCode::vqp, instruction.prefixes);
-                let modrm = read_modrm(words)?;
+                let modrm = read_modrm(words, sink)?;
                 let is_reg = (modrm & 0xc0) == 0xc0;
 
                 let r = (modrm >> 3) & 7;
@@ -9447,7 +10897,7 @@ fn unlikely_operands<
                 return Ok(());
             }
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let is_reg = (modrm & 0xc0) == 0xc0;
 
             let r = (modrm >> 3) & 0b111;
@@ -9535,7 +10985,7 @@ fn unlikely_operands<
 
             instruction.operand_count = 2;
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -9572,7 +11022,7 @@ fn unlikely_operands<
 
             instruction.operand_count = 2;
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -9609,7 +11059,7 @@ fn unlikely_operands<
 
             instruction.operand_count = 2;
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -9649,7 +11099,7 @@ fn unlikely_operands<
             instruction.operands[1] = OperandSpec::ImmU8;
         },
         OperandCode::ModRM_0xf30f38d8 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let r = (modrm >> 3) & 7;
             match r {
                 0b000 => {
@@ -9742,7 +11192,7 @@ fn unlikely_operands<
             instruction.regs[1].bank = RegisterBank::D;
         }
         OperandCode::ModRM_0xf30f3af0 => {
-            let modrm = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 return Err(DecodeError::InvalidOpcode);
                 // invalid
@@ -10080,7 +11530,7 @@ fn unlikely_operands<
         }
         OperandCode::Ew_Sw => {
             let opwidth = 2;
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             // check r
             if ((modrm >> 3) & 7) > 5 {
@@ -10104,7 +11554,7 @@ fn unlikely_operands<
             }
         },
         OperandCode::Sw_Ew => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             // check r
             if ((modrm >> 3) & 7) > 5 {
@@ -10176,7 +11626,7 @@ fn unlikely_operands<
         }
         OperandCode::ModRM_0x0f00 => {
             instruction.operand_count = 1;
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let r = (modrm >> 3) & 7;
             if r == 0 {
                 instruction.opcode = Opcode::SLDT;
@@ -10209,7 +11659,7 @@ fn unlikely_operands<
         }
         OperandCode::ModRM_0x0f01 => {
             let opwidth = imm_width_from_prefixes_64(SizeCode::vq, instruction.prefixes);
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let r = (modrm >> 3) & 7;
             if r == 0 {
                 let mod_bits = modrm >> 6;
@@ -10356,7 +11806,13 @@ fn unlikely_operands<
                             instruction.operands[0] = OperandSpec::RegRRR;
                         },
                         0b001 => {
-                            instruction.opcode = Opcode::VMMCALL;
+                            // `vmgexit` is `vmmcall` with a `rep` or `repnz` prefix. processors
+                            // without sev-es ignore the prefix, and execute it as `vmmcall`.
+                            if instruction.prefixes.rep() || instruction.prefixes.repnz() {
+                                instruction.opcode = Opcode::VMGEXIT;
+                            } else {
+                                instruction.opcode = Opcode::VMMCALL;
+                            }
                             instruction.operands[0] = OperandSpec::Nothing;
                             instruction.operand_count = 0;
                         },
@@ -10437,8 +11893,14 @@ fn unlikely_operands<
                             instruction.operand_count = 0;
                             return Ok(());
                         }
-                        if !instruction.prefixes.rep() || instruction.prefixes.repnz() {
-                            return Err(DecodeError::InvalidOpcode);
+                        if !instruction.prefixes.rep() {
+                            if instruction.prefixes.operand_size() {
+                                return Err(DecodeError::InvalidOpcode);
+                            }
+                            instruction.opcode = Opcode::SERIALIZE;
+                            instruction.operands[0] = OperandSpec::Nothing;
+                            instruction.operand_count = 0;
+                            return Ok(());
                         }
                         instruction.opcode = Opcode::SETSSBSY;
                         instruction.operands[0] = OperandSpec::Nothing;
@@ -10614,7 +12076,7 @@ fn unlikely_operands<
             }
         }
         OperandCode::ModRM_0x0fae => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let r = (modrm >> 3) & 7;
             let m = modrm & 7;
 
@@ -10843,7 +12305,7 @@ fn unlikely_operands<
         }
         OperandCode::ModRM_0x0fba => {
             let opwidth = imm_width_from_prefixes_64(SizeCode::vq, instruction.prefixes);
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let r = (modrm >> 3) & 7;
             match r {
                 0 | 1 | 2 | 3 => {
@@ -10879,7 +12341,7 @@ fn unlikely_operands<
         op @ OperandCode::Rq_Dq_0 |
         op @ OperandCode::Cq_Rq_0 |
         op @ OperandCode::Dq_Rq_0 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let mut m = modrm & 7;
             let mut r = (modrm >> 3) & 7;
             if instruction.prefixes.rex_unchecked().r() {
@@ -11137,7 +12599,7 @@ fn decode_x87<
     }
 
     // every x87 instruction is conditional on rrr bits
-    let modrm = read_modrm(words)?;
+    let modrm = read_modrm(words, sink)?;
     let r = (modrm >> 3) & 0b111;
 
     let (opcode, x87_operands) = match operand_code {
@@ -11620,7 +13082,13 @@ fn decode_x87<
             if instruction.operands[0] == OperandSpec::RegMMM {
                 return Err(DecodeError::InvalidOperand);
             }
-            instruction.mem_size = 63;
+            // the x87 environment is 14 bytes with a 16-bit operand size, and 28 bytes
+            // otherwise. `fnsave` and `frstor` follow it with the eight 10-byte registers.
+            let env_size = if instruction.prefixes.operand_size() { 14 } else { 28 };
+            instruction.mem_size = match instruction.opcode {
+                Opcode::FNSAVE | Opcode::FRSTOR => env_size + 80,
+                _ => env_size,
+            };
         }
         OperandCodeX87::Nothing => {
             instruction.operand_count = 0;
@@ -11717,6 +13185,16 @@ fn imm_width_from_prefixes_64(interpretation: SizeCode, prefixes: Prefixes) -> u
 }
 
 #[inline]
-fn read_modrm<T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as yaxpeax_arch::Arch>::Word>>(words: &mut T) -> Result<u8, DecodeError> {
-    words.next().ok().ok_or(DecodeError::ExhaustedInput)
+fn read_modrm<
+    T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as yaxpeax_arch::Arch>::Word>,
+    S: DescriptionSink<FieldDescription>,
+>(words: &mut T, sink: &mut S) -> Result<u8, DecodeError> {
+    let modrm = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
+    sink.record(
+        words.offset() as u32 * 8 - 8,
+        words.offset() as u32 * 8 - 1,
+        InnerDescription::Misc(bytes::MODRM_DESCRIPTION)
+            .with_id(words.offset() as u32 * 8 - 8)
+    );
+    Ok(modrm)
 }
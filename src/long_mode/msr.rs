@@ -0,0 +1,8 @@
+#[cfg(feature = "fmt")]
+use core::fmt;
+
+use crate::long_mode::{Instruction, Opcode, RegSpec, RegValueSource};
+#[cfg(feature = "fmt")]
+use crate::long_mode::{DisplaySink, InstructionDisplayer};
+
+include!("../shared/msr.in");
@@ -0,0 +1,11 @@
+use crate::NopStrictness;
+use crate::long_mode::{Instruction, Opcode, Operand, RegSpec, RegisterBank};
+
+fn self_write_is_nop(reg: RegSpec) -> bool {
+    // writing a 32-bit register zeroes the upper half of the 64-bit register, so `mov edi, edi`
+    // is not a no-op here as it is in 32-bit code.
+    matches!(reg.bank,
+        RegisterBank::Q | RegisterBank::W | RegisterBank::B | RegisterBank::rB)
+}
+
+include!("../shared/nop.in");
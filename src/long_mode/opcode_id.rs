@@ -0,0 +1,22 @@
+use crate::long_mode::Opcode;
+
+include!("../shared/opcode_id.in");
+
+opcode_ids!(mode_opcode_id, mode_opcode_from_id, {
+    JRCXZ = 1485,
+    JMPABS = 1486,
+    PUSHP = 1487,
+    POPP = 1488,
+    LDTILECFG = 1489,
+    STTILECFG = 1490,
+    TILERELEASE = 1491,
+    TILEZERO = 1492,
+    TILELOADD = 1493,
+    TILELOADDT1 = 1494,
+    TILESTORED = 1495,
+    TDPBSSD = 1496,
+    TDPBSUD = 1497,
+    TDPBUSD = 1498,
+    TDPBUUD = 1499,
+    TDPBF16PS = 1500,
+});
@@ -0,0 +1,93 @@
+use crate::long_mode::{AccessKind, Instruction, MergeMode, Opcode, Operand, RegSpec, RegisterBank};
+use crate::long_mode::scan::immediate_value;
+
+fn mode_access_override(opcode: Opcode, i: u8) -> Option<AccessKind> {
+    match opcode {
+        Opcode::JRCXZ | Opcode::JMPABS | Opcode::PUSHP | Opcode::LDTILECFG => Some(AccessKind::Read),
+        Opcode::POPP => Some(AccessKind::Write),
+        Opcode::STTILECFG | Opcode::TILEZERO | Opcode::TILELOADD | Opcode::TILELOADDT1 |
+        Opcode::TILESTORED if i == 0 => Some(AccessKind::Write),
+        _ => None,
+    }
+}
+
+fn mode_implicit_operands(inst: &Instruction, ops: &mut ImplicitOperands) {
+    match inst.opcode() {
+        Opcode::JRCXZ => {
+            ops.reg(gp_reg(1, address_width(inst)), AccessKind::Read);
+        }
+        Opcode::PUSHP => {
+            ops.reg(RegSpec::rsp(), AccessKind::ReadWrite);
+            ops.mem(RegSpec::rsp(), -8, 8, AccessKind::Write);
+        }
+        Opcode::POPP => {
+            ops.reg(RegSpec::rsp(), AccessKind::ReadWrite);
+            ops.mem(RegSpec::rsp(), 0, 8, AccessKind::Read);
+        }
+        Opcode::CDQE => {
+            ops.reg(RegSpec::rax(), AccessKind::ReadWrite);
+        }
+        Opcode::CQO => {
+            ops.reg(RegSpec::rax(), AccessKind::Read);
+            ops.reg(RegSpec::rdx(), AccessKind::Write);
+        }
+        // `syscall` saves `rip` and `rflags` in `rcx` and `r11`, and `sysret` restores them.
+        Opcode::SYSCALL => {
+            ops.reg(RegSpec::rcx(), AccessKind::Write);
+            ops.reg(RegSpec::r11(), AccessKind::Write);
+        }
+        Opcode::SYSRET => {
+            ops.reg(RegSpec::rcx(), AccessKind::Read);
+            ops.reg(RegSpec::r11(), AccessKind::Read);
+        }
+        _ => {}
+    }
+}
+
+fn gp_reg(num: u8, width: u8) -> RegSpec {
+    let bank = match width {
+        8 => RegisterBank::Q,
+        4 => RegisterBank::D,
+        2 => RegisterBank::W,
+        _ => RegisterBank::B,
+    };
+    RegSpec { num, bank }
+}
+
+fn stack_pointer() -> RegSpec {
+    RegSpec::rsp()
+}
+
+fn flags_register() -> RegSpec {
+    RegSpec::rflags()
+}
+
+/// near `call` and `ret` push and pop a full `rip` in 64-bit mode, ignoring a `66` prefix; only
+/// pushes, pops, and far transfers of control take a 16-bit operand size.
+fn stack_width(inst: &Instruction) -> u8 {
+    match inst.opcode() {
+        Opcode::PUSH | Opcode::POP | Opcode::PUSHF | Opcode::POPF | Opcode::ENTER | Opcode::LEAVE |
+        Opcode::CALLF | Opcode::RETF | Opcode::IRET if inst.prefixes.operand_size() => 2,
+        _ => 8,
+    }
+}
+
+fn address_width(inst: &Instruction) -> u8 {
+    if inst.prefixes.address_size() { 4 } else { 8 }
+}
+
+/// the bytes an interrupt pushes. in 64-bit mode `ss`, `rsp`, `rflags`, `cs`, and `rip` are always
+/// pushed as qwords, whatever the operand size.
+fn interrupt_frame_size() -> u8 {
+    40
+}
+
+fn mode_stack_slots(opcode: Opcode) -> Option<i32> {
+    match opcode {
+        Opcode::PUSHP => Some(-1),
+        Opcode::POPP => Some(1),
+        _ => None,
+    }
+}
+
+include!("../shared/operands.in");
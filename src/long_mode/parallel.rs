@@ -0,0 +1,7 @@
+extern crate std;
+
+use alloc::vec::Vec;
+
+use crate::long_mode::{DecodeError, ErrorRecovery, InstDecoder, Instruction};
+
+include!("../shared/parallel.in");
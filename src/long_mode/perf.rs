@@ -0,0 +1,3 @@
+use crate::long_mode::{Opcode, PerfInfo, Uarch};
+
+include!("../shared/perf.in");
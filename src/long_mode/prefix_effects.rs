@@ -0,0 +1,64 @@
+use crate::{Category, PrefixEffect, PrefixEffects};
+use crate::long_mode::{Instruction, Opcode, Operand, RegSpec, RegisterBank, Segment};
+
+const OVERRIDDEN_BANK: RegisterBank = RegisterBank::W;
+const OVERRIDDEN_WIDTH: u8 = 2;
+
+fn operand_size_opcode(opcode: Opcode) -> bool {
+    matches!(opcode,
+        Opcode::CBW | Opcode::CWD | Opcode::IRET)
+}
+
+fn inherently_16bit(opcode: Opcode) -> bool {
+    matches!(opcode,
+        Opcode::SLDT | Opcode::STR | Opcode::SMSW | Opcode::LLDT | Opcode::LTR | Opcode::LMSW |
+        Opcode::VERR | Opcode::VERW)
+}
+
+/// opcodes whose operands are 64 bits wide in 64-bit code without `rex.w`.
+fn default_64bit(opcode: Opcode) -> bool {
+    matches!(opcode,
+        Opcode::PUSH | Opcode::POP | Opcode::CALL | Opcode::JMP | Opcode::RETURN |
+        Opcode::ENTER | Opcode::LEAVE | Opcode::PUSHF | Opcode::POPF)
+}
+
+fn rex_w_effect(inst: &Instruction) -> PrefixEffect {
+    match inst.prefixes.rex() {
+        Some(rex) if rex.w() => {}
+        _ => { return PrefixEffect::Absent; }
+    }
+    match inst.opcode {
+        Opcode::CDQE | Opcode::CQO | Opcode::IRETQ | Opcode::CMPXCHG16B |
+        Opcode::CVTSI2SS | Opcode::CVTSI2SD | Opcode::MOVD => { return PrefixEffect::Effective; }
+        Opcode::XSAVEC64 | Opcode::XSAVES64 | Opcode::XRSTORS64 => { return PrefixEffect::Opcode; }
+        Opcode::VMREAD | Opcode::VMWRITE | Opcode::VMPTRLD | Opcode::VMPTRST |
+        Opcode::NOP | Opcode::PREFETCHW => { return PrefixEffect::Ignored; }
+        opcode if default_64bit(opcode) => { return PrefixEffect::Ignored; }
+        _ => {}
+    }
+    if system_move(inst) {
+        return PrefixEffect::Ignored;
+    }
+    let gpr = |bank| bank == RegisterBank::Q || bank == RegisterBank::D || bank == RegisterBank::W ||
+        bank == RegisterBank::B || bank == RegisterBank::rB;
+    let only_gprs = inst.opcode.category() != Category::X87 && (0..inst.operand_count).all(|i| {
+        match inst.operand(i) {
+            Operand::Register(reg) => gpr(reg.bank),
+            _ => true,
+        }
+    });
+    let sized = (0..inst.operand_count).any(|i| {
+        match inst.operand(i) {
+            Operand::Register(reg) => reg.bank == RegisterBank::Q,
+            op if op.is_memory() => only_gprs && inst.mem_size == 8,
+            _ => false,
+        }
+    });
+    if sized {
+        PrefixEffect::Effective
+    } else {
+        PrefixEffect::Ignored
+    }
+}
+
+include!("../shared/prefix_effects.in");
@@ -0,0 +1,62 @@
+use crate::RelocateError;
+use crate::long_mode::branch::branch_target_mask;
+use crate::long_mode::{Category, Instruction, Opcode, Operand, OperandSpec, RegSpec};
+
+/// the width of the immediate an operand `spec` is encoded as, or the `is4` byte that selects a
+/// `Reg4` operand. these are the last bytes of an instruction.
+pub(crate) fn trailing_width(spec: OperandSpec) -> Option<u8> {
+    match spec {
+        OperandSpec::ImmI8 | OperandSpec::ImmU8 | OperandSpec::Reg4 => Some(1),
+        OperandSpec::ImmI16 | OperandSpec::ImmU16 => Some(2),
+        OperandSpec::ImmI32 => Some(4),
+        OperandSpec::ImmI64 => Some(8),
+        _ => None,
+    }
+}
+
+const NEAR_DISPLACEMENT_WIDTH: u8 = 4;
+
+/// where the displacement of `inst`'s `rip`-relative memory operand is, as an offset into the
+/// instruction, and the mask of the address it is relative to: `eip`-relative operands wrap at 4g.
+fn rip_relative_field(inst: &Instruction) -> Option<(u8, u64)> {
+    let mask = (0..inst.operand_count()).find_map(|i| match inst.operand(i) {
+        Operand::RegDeref(base) |
+        Operand::RegDerefMasked(base, _) |
+        Operand::RegDisp(base, _) |
+        Operand::RegDispMasked(base, _, _) => {
+            if base == RegSpec::rip() {
+                Some(u64::MAX)
+            } else if base == RegSpec::eip() {
+                Some(0xffff_ffff)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    })?;
+    let bytes = inst.bytes()?;
+    let mut end = bytes.len();
+    // 3dnow! instructions select their opcode with a byte after the displacement.
+    if inst.isa_extension() == crate::IsaExtension::Amd3DNow && inst.opcode != Opcode::FEMMS {
+        end = end.checked_sub(1)?;
+    }
+    // the displacement is otherwise followed only by an immediate, but shifts and rotates by one
+    // have an immediate `1` that is not encoded. the displacement is wherever its bytes follow a
+    // `modrm` byte selecting `rip`-relative addressing, which is always 32 bits.
+    let disp = (inst.disp as u32).to_le_bytes();
+    let holds_disp = |end: usize| {
+        end >= 5 && bytes[end - 4..end] == disp && bytes[end - 5] & 0xc7 == 0x05
+    };
+    let operands = &inst.operands[..inst.operand_count as usize];
+    let trailing = operands.iter().find_map(|spec| trailing_width(*spec)).unwrap_or(0) as usize;
+    let end = [end.checked_sub(trailing)?, end].iter().copied().find(|end| holds_disp(*end))?;
+    Some(((end - 4) as u8, mask))
+}
+
+/// `jmp` and `jcc` with 32-bit displacements cannot have a `rex2` prefix, though `jmp` with an
+/// 8-bit displacement can.
+fn can_widen(inst: &Instruction) -> bool {
+    inst.prefixes.rex2().is_none()
+}
+
+include!("../shared/relocate.in");
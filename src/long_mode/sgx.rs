@@ -0,0 +1,4 @@
+use crate::SgxFunction;
+use crate::long_mode::{Instruction, Opcode, RegSpec, RegValueSource};
+
+include!("../shared/sgx.in");
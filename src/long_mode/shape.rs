@@ -0,0 +1,3 @@
+use crate::long_mode::{Category, ElementType, Instruction, Operand, RegisterBank, VectorShape};
+
+include!("../shared/shape.in");
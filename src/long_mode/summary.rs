@@ -0,0 +1,17 @@
+use crate::{Encoding, FlowClass, InstructionSummary};
+use crate::long_mode::{DecodeError, InstDecoder};
+
+fn is_rex(b: u8) -> bool {
+    b & 0xf0 == 0x40
+}
+
+fn is_rex2(decoder: &InstDecoder, b: u8) -> bool {
+    b == 0xd5 && decoder.apx()
+}
+
+// `les`, `lds`, and `bound` are not instructions in 64-bit mode.
+fn is_vex_or_evex(_next: u8) -> bool {
+    true
+}
+
+include!("../shared/summary.in");
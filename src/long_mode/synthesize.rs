@@ -0,0 +1,21 @@
+use crate::SynthesizeError;
+use crate::long_mode::{Instruction, Opcode, Operand, OperandSpec, RegSpec};
+
+fn synthesize_mode_operand(synthesis: &mut Synthesis, op: &Operand) -> Result<OperandSpec, SynthesizeError> {
+    match *op {
+        Operand::ImmediateI64(imm) => synthesis.immediate(OperandSpec::ImmI64, imm as u64),
+        Operand::DisplacementU64(disp) => synthesis.memory(OperandSpec::DispU64, None, None, disp),
+        // `long_mode` has no operand specs for an unscaled index; a decoded `sib` byte with a
+        // scale of 1 is reported this way too.
+        Operand::RegIndexBase(base, index) => {
+            synthesis.memory(OperandSpec::RegIndexBaseScale, Some(base), Some((index, 1)), 0)
+        }
+        Operand::RegIndexBaseDisp(base, index, disp) => {
+            synthesis.memory(OperandSpec::RegIndexBaseScaleDisp, Some(base), Some((index, 1)), disp as u64)
+        }
+        Operand::ImmediateU64(_) => Err(SynthesizeError::UnsupportedOperand),
+        _ => unreachable!("operand is handled by Instruction::synthesize"),
+    }
+}
+
+include!("../shared/synthesize.in");
@@ -0,0 +1,26 @@
+//! generation of valid encodings, for building decode and round-trip tests and differential tests
+//! against hardware. see [`enumerate`] and [`Generator`].
+
+use alloc::vec::Vec;
+
+use crate::long_mode::{InstDecoder, Instruction, Opcode, Operand, RegisterClass};
+
+const MODE_BITS: u8 = 64;
+
+/// the one-byte map, `0f`, `0f38`, and `0f3a`, each with and without `rex.w`, and the one-byte
+/// and `0f` maps selected by a `rex2` prefix with and without `rex2.w`.
+const LEGACY_HEADS: &[(&[u8], bool)] = &[
+    (&[], true), (&[0x0f], false), (&[0x0f, 0x38], false), (&[0x0f, 0x3a], false),
+    (&[0x48], true), (&[0x48, 0x0f], false), (&[0x48, 0x0f, 0x38], false), (&[0x48, 0x0f, 0x3a], false),
+    (&[0xd5, 0x00], true), (&[0xd5, 0x08], true), (&[0xd5, 0x80], false), (&[0xd5, 0x88], false),
+];
+
+/// legacy prefixes, `0f`, and in 64-bit code, `rex`, `rex2`, `vex`, and `evex`.
+fn is_escape(b: u8) -> bool {
+    matches!(b,
+        0x26 | 0x2e | 0x36 | 0x3e | 0x64 | 0x65 | 0x66 | 0x67 | 0xf0 | 0xf2 | 0xf3 | 0x0f |
+        0x40..=0x4f | 0xc4 | 0xc5 | 0x62 | 0xd5
+    )
+}
+
+include!("../shared/testgen.in");
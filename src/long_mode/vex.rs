@@ -1,6 +1,7 @@
 use yaxpeax_arch::Reader;
 use yaxpeax_arch::annotation::DescriptionSink;
 
+use crate::Encoding;
 use crate::long_mode::Arch;
 use crate::long_mode::OperandSpec;
 use crate::long_mode::DecodeError;
@@ -10,6 +11,7 @@ use crate::long_mode::RegisterBank;
 use crate::long_mode::InnerDescription;
 use crate::long_mode::Instruction;
 use crate::long_mode::Opcode;
+use crate::long_mode::bytes;
 use crate::long_mode::read_modrm;
 use crate::long_mode::read_E;
 use crate::long_mode::read_E_xmm;
@@ -100,6 +102,11 @@ enum VEXOperandCode {
     VCVT_Gq_Eq_xmm,
     BMI1_F3,
     MXCSR,
+    M_tilecfg,
+    G_tmm,
+    G_tmm_Msib,
+    Msib_G_tmm,
+    G_E_V_tmm,
 }
 
 #[inline(never)]
@@ -154,13 +161,8 @@ pub(crate) fn three_byte_vex<
         bank: RegisterBank::X,
         num: ((vex_byte_two >> 3) & 0b1111) ^ 0b1111,
     };
-    sink.record(
-        vex_start + 11,
-        vex_start + 14,
-        InnerDescription::RegisterNumber("vvvv", instruction.regs[3].num, instruction.regs[3])
-            .with_id(vex_start + 2)
-    );
-
+    // `vvvv` may not name a register, and its bank depends on the opcode. `decode_with_annotation`
+    // describes it once the instruction is decoded.
     sink.record(
         vex_start + 7,
         vex_start + 7,
@@ -221,6 +223,9 @@ pub(crate) fn three_byte_vex<
             .with_id(vex_start + 23)
     );
 
+    // the `c4` or `c5` byte and the rest of the prefix select an opcode map together.
+    bytes::record_opcode_map(sink, vex_start - 8, vex_start + 23, Encoding::Vex, vex_byte_one & 0b11111);
+
     read_vex_instruction(m, words, instruction, p, sink)
 }
 
@@ -255,13 +260,8 @@ pub(crate) fn two_byte_vex<
             .with_id(vex_start)
     );
 
-    sink.record(
-        vex_start + 3,
-        vex_start + 6,
-        InnerDescription::RegisterNumber("vvvv", instruction.regs[3].num, instruction.regs[3])
-            .with_id(vex_start + 2)
-    );
-
+    // `vvvv` may not name a register, and its bank depends on the opcode. `decode_with_annotation`
+    // describes it once the instruction is decoded.
     sink.record(
         vex_start + 2,
         vex_start + 2,
@@ -292,6 +292,9 @@ pub(crate) fn two_byte_vex<
             .with_id(vex_start + 15)
     );
 
+    // the `c4` or `c5` byte and the rest of the prefix select an opcode map together.
+    bytes::record_opcode_map(sink, vex_start - 8, vex_start + 15, Encoding::Vex, 1);
+
     read_vex_instruction(VEXOpcodeMap::Map0F, words, instruction, p, sink)
 }
 
@@ -302,7 +305,7 @@ fn read_vex_operands<
 //    println!("operand code: {:?}", operand_code);
     match operand_code {
         VEXOperandCode::VPS_71 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
@@ -332,7 +335,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::VPS_71_L => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
@@ -365,7 +368,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::VPS_72 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
@@ -395,7 +398,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::VPS_72_L => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
@@ -425,7 +428,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::VPS_73 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
@@ -458,7 +461,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::VPS_73_L => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
@@ -498,7 +501,7 @@ fn read_vex_operands<
         }
         VEXOperandCode::VMOVSS_10 |
         VEXOperandCode::VMOVSD_10 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -527,7 +530,7 @@ fn read_vex_operands<
         },
         VEXOperandCode::VMOVSS_11 |
         VEXOperandCode::VMOVSD_11 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -556,7 +559,7 @@ fn read_vex_operands<
             Ok(())
         },
         VEXOperandCode::VMOVLPS_12 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.opcode = if modrm & 0xc0 == 0xc0 {
                 Opcode::VMOVHLPS
             } else {
@@ -572,7 +575,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::VMOVHPS_16 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.opcode = if modrm & 0xc0 == 0xc0 {
                 Opcode::VMOVLHPS
             } else {
@@ -602,7 +605,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E(words, instruction, modrm, 4, sink)?;
@@ -637,7 +640,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E(words, instruction, modrm, 8, sink)?;
@@ -654,7 +657,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E(words, instruction, modrm, 4, sink)?;
@@ -671,7 +674,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E(words, instruction, modrm, 8, sink)?;
@@ -688,7 +691,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E(words, instruction, modrm, 4, sink)?;
@@ -705,7 +708,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::D);
             let mem_oper = read_E(words, instruction, modrm, 4, sink)?;
@@ -724,7 +727,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::Q);
             let mem_oper = read_E(words, instruction, modrm, 4, sink)?;
@@ -748,7 +751,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -781,7 +784,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::D);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -798,7 +801,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::D);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -815,7 +818,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::D);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -834,7 +837,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -853,7 +856,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::Y);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -873,7 +876,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::D);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -890,7 +893,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::D);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -910,7 +913,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             match (op, modrm & 0xc0) {
                 (VEXOperandCode::G_U_xmm, 0xc0) => {
                     /* this is the only accepted operand */
@@ -946,7 +949,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -963,7 +966,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -981,7 +984,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 == 0xc0 {
                 if let VEXOperandCode::G_ymm_M_xmm = op {
                     return Err(DecodeError::InvalidOperand);
@@ -1009,7 +1012,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::Y);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -1028,7 +1031,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             match (op, modrm & 0xc0) {
                 (VEXOperandCode::M_G_ymm, 0xc0) => {
                     return Err(DecodeError::InvalidOperand);
@@ -1056,7 +1059,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             match (op, modrm & 0xc0) {
                 (VEXOperandCode::G_M_ymm, 0xc0) => {
                     return Err(DecodeError::InvalidOperand);
@@ -1079,7 +1082,7 @@ fn read_vex_operands<
         }
         op @ VEXOperandCode::G_V_E_ymm |
         op @ VEXOperandCode::G_V_M_ymm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if let VEXOperandCode::G_V_M_ymm = op {
                 if modrm & 0xc0 == 0xc0 {
                     return Err(DecodeError::InvalidOperand);
@@ -1099,7 +1102,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_E_ymm_imm8 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::Y);
             instruction.regs[3].bank = RegisterBank::Y;
@@ -1116,7 +1119,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::M_V_G_ymm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 == 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -1134,7 +1137,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_M_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 == 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -1155,7 +1158,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_E_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -1175,7 +1178,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_xmm_Ed => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E(words, instruction, modrm, 4, sink)?;
@@ -1189,7 +1192,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_xmm_Eq => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E(words, instruction, modrm, 8, sink)?;
@@ -1203,7 +1206,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_E_xmm_imm8 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -1219,7 +1222,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_ymm_V_ymm_E_xmm_imm8 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::Y);
             instruction.regs[3].bank = RegisterBank::Y;
@@ -1236,7 +1239,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::M_V_G_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 == 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -1255,7 +1258,7 @@ fn read_vex_operands<
         }
 
         VEXOperandCode::G_Ex_V_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -1270,7 +1273,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_Ey_V_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -1286,7 +1289,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_Ey_V_ymm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::Y);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -1306,7 +1309,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_E => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let (opwidth, bank) = if instruction.prefixes.vex_unchecked().w() {
                 (8, RegisterBank::Q)
             } else {
@@ -1325,8 +1328,94 @@ fn read_vex_operands<
             instruction.operand_count = 3;
             Ok(())
         }
+        VEXOperandCode::M_tilecfg => {
+            let modrm = read_modrm(words, sink)?;
+            if (modrm >> 3) & 7 != 0 {
+                instruction.opcode = Opcode::Invalid;
+                return Err(DecodeError::InvalidOperand);
+            }
+            if modrm >= 0b11_000_000 {
+                // `tilerelease` is the only register form, and has no operands at all.
+                if instruction.opcode != Opcode::LDTILECFG || modrm != 0xc0 {
+                    instruction.opcode = Opcode::Invalid;
+                    return Err(DecodeError::InvalidOperand);
+                }
+                instruction.opcode = Opcode::TILERELEASE;
+                instruction.operand_count = 0;
+                return Ok(());
+            }
+            let mem_oper = read_E(words, instruction, modrm, 8, sink)?;
+            instruction.mem_size = 63;
+            instruction.operands[0] = mem_oper;
+            instruction.operand_count = 1;
+            Ok(())
+        }
+        VEXOperandCode::G_tmm => {
+            let modrm = read_modrm(words, sink)?;
+            if modrm & 0b11_000_111 != 0b11_000_000 {
+                instruction.opcode = Opcode::Invalid;
+                return Err(DecodeError::InvalidOperand);
+            }
+            instruction.regs[0] =
+                RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::T);
+            if instruction.regs[0].num >= 8 {
+                instruction.opcode = Opcode::Invalid;
+                return Err(DecodeError::InvalidOperand);
+            }
+            instruction.operands[0] = OperandSpec::RegRRR;
+            instruction.operand_count = 1;
+            Ok(())
+        }
+        op @ VEXOperandCode::G_tmm_Msib |
+        op @ VEXOperandCode::Msib_G_tmm => {
+            let modrm = read_modrm(words, sink)?;
+            // tile loads and stores address memory with a sib byte, where the index register
+            // is the stride between rows rather than a scaled index.
+            if modrm >= 0b11_000_000 || modrm & 7 != 0b100 {
+                instruction.opcode = Opcode::Invalid;
+                return Err(DecodeError::InvalidOperand);
+            }
+            instruction.regs[0] =
+                RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::T);
+            if instruction.regs[0].num >= 8 {
+                instruction.opcode = Opcode::Invalid;
+                return Err(DecodeError::InvalidOperand);
+            }
+            let mem_oper = read_E(words, instruction, modrm, 8, sink)?;
+            instruction.mem_size = 63;
+            if let VEXOperandCode::G_tmm_Msib = op {
+                instruction.operands[0] = OperandSpec::RegRRR;
+                instruction.operands[1] = mem_oper;
+            } else {
+                instruction.operands[0] = mem_oper;
+                instruction.operands[1] = OperandSpec::RegRRR;
+            }
+            instruction.operand_count = 2;
+            Ok(())
+        }
+        VEXOperandCode::G_E_V_tmm => {
+            let modrm = read_modrm(words, sink)?;
+            if modrm < 0b11_000_000 {
+                instruction.opcode = Opcode::Invalid;
+                return Err(DecodeError::InvalidOperand);
+            }
+            instruction.regs[0] =
+                RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::T);
+            instruction.regs[1] =
+                RegSpec::from_parts(modrm & 7, instruction.prefixes.vex_unchecked().b(), RegisterBank::T);
+            instruction.regs[3].bank = RegisterBank::T;
+            if instruction.regs[0].num >= 8 || instruction.regs[1].num >= 8 || instruction.regs[3].num >= 8 {
+                instruction.opcode = Opcode::Invalid;
+                return Err(DecodeError::InvalidOperand);
+            }
+            instruction.operands[0] = OperandSpec::RegRRR;
+            instruction.operands[1] = OperandSpec::RegMMM;
+            instruction.operands[2] = OperandSpec::RegVex;
+            instruction.operand_count = 3;
+            Ok(())
+        }
         VEXOperandCode::G_E_V => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let (opwidth, bank) = if instruction.prefixes.vex_unchecked().w() {
                 (8, RegisterBank::Q)
             } else {
@@ -1346,7 +1435,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_E_Ib => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let (opwidth, bank) = if instruction.prefixes.vex_unchecked().w() {
                 (8, RegisterBank::Q)
             } else {
@@ -1366,7 +1455,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::BMI1_F3 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.opcode = match (modrm >> 3) & 7 {
                 1 => {
                     Opcode::BLSR
@@ -1400,7 +1489,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::MXCSR => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.opcode = match (modrm >> 3) & 7 {
                 2 => {
                     Opcode::VLDMXCSR
@@ -1428,7 +1517,7 @@ fn read_vex_operands<
             if instruction.regs[3].num != 0 {
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -1446,7 +1535,7 @@ fn read_vex_operands<
             if instruction.regs[3].num != 0 {
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, instruction.prefixes.vex_unchecked().r(), RegisterBank::Y);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -1461,7 +1550,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_E_ymm_ymm4 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7,instruction.prefixes.vex_unchecked().x(), RegisterBank::Y);
             instruction.regs[3].bank = RegisterBank::Y;
@@ -1478,7 +1567,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_E_xmm_xmm4 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7,instruction.prefixes.vex_unchecked().x(), RegisterBank::X);
             instruction.regs[3].bank = RegisterBank::X;
@@ -1495,7 +1584,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_ymm_E_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7,instruction.prefixes.vex_unchecked().x(), RegisterBank::Y);
             instruction.regs[3].bank = RegisterBank::Y;
@@ -1510,7 +1599,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_xmm_Ev_imm8 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7,instruction.prefixes.vex_unchecked().x(), RegisterBank::X);
             instruction.regs[3].bank = RegisterBank::X;
@@ -2738,6 +2827,18 @@ fn read_vex_instruction<
                             VEXOperandCode::G_V_E_xmm
                         })
                     },
+                    0x49 => (Opcode::STTILECFG, if L || instruction.prefixes.vex_unchecked().w() {
+                        instruction.opcode = Opcode::Invalid;
+                        return Err(DecodeError::InvalidOpcode);
+                    } else {
+                        VEXOperandCode::M_tilecfg
+                    }),
+                    0x4B => (Opcode::TILELOADDT1, if L || instruction.prefixes.vex_unchecked().w() {
+                        instruction.opcode = Opcode::Invalid;
+                        return Err(DecodeError::InvalidOpcode);
+                    } else {
+                        VEXOperandCode::G_tmm_Msib
+                    }),
                     0x58 => (Opcode::VPBROADCASTD, if L {
                         VEXOperandCode::G_E_ymm
                     } else {
@@ -2757,6 +2858,12 @@ fn read_vex_instruction<
                         instruction.opcode = Opcode::Invalid;
                         return Err(DecodeError::InvalidOpcode);
                     }),
+                    0x5E => (Opcode::TDPBUSD, if L || instruction.prefixes.vex_unchecked().w() {
+                        instruction.opcode = Opcode::Invalid;
+                        return Err(DecodeError::InvalidOpcode);
+                    } else {
+                        VEXOperandCode::G_E_V_tmm
+                    }),
                     0x78 => (Opcode::VPBROADCASTB, if L {
                         VEXOperandCode::G_E_ymm
                     } else {
@@ -3226,6 +3333,24 @@ fn read_vex_instruction<
                 }
             } else if let VEXOpcodePrefix::PrefixF2 = p {
                 match opc {
+                    0x49 => (Opcode::TILEZERO, if L || instruction.prefixes.vex_unchecked().w() {
+                        instruction.opcode = Opcode::Invalid;
+                        return Err(DecodeError::InvalidOpcode);
+                    } else {
+                        VEXOperandCode::G_tmm
+                    }),
+                    0x4B => (Opcode::TILELOADD, if L || instruction.prefixes.vex_unchecked().w() {
+                        instruction.opcode = Opcode::Invalid;
+                        return Err(DecodeError::InvalidOpcode);
+                    } else {
+                        VEXOperandCode::G_tmm_Msib
+                    }),
+                    0x5E => (Opcode::TDPBSSD, if L || instruction.prefixes.vex_unchecked().w() {
+                        instruction.opcode = Opcode::Invalid;
+                        return Err(DecodeError::InvalidOpcode);
+                    } else {
+                        VEXOperandCode::G_E_V_tmm
+                    }),
                     0xF5 => (Opcode::PDEP, if L {
                         instruction.opcode = Opcode::Invalid;
                         return Err(DecodeError::InvalidOpcode);
@@ -3251,6 +3376,24 @@ fn read_vex_instruction<
                 }
             } else if let VEXOpcodePrefix::PrefixF3 = p {
                 match opc {
+                    0x4B => (Opcode::TILESTORED, if L || instruction.prefixes.vex_unchecked().w() {
+                        instruction.opcode = Opcode::Invalid;
+                        return Err(DecodeError::InvalidOpcode);
+                    } else {
+                        VEXOperandCode::Msib_G_tmm
+                    }),
+                    0x5C => (Opcode::TDPBF16PS, if L || instruction.prefixes.vex_unchecked().w() {
+                        instruction.opcode = Opcode::Invalid;
+                        return Err(DecodeError::InvalidOpcode);
+                    } else {
+                        VEXOperandCode::G_E_V_tmm
+                    }),
+                    0x5E => (Opcode::TDPBSUD, if L || instruction.prefixes.vex_unchecked().w() {
+                        instruction.opcode = Opcode::Invalid;
+                        return Err(DecodeError::InvalidOpcode);
+                    } else {
+                        VEXOperandCode::G_E_V_tmm
+                    }),
                     0xF5 => (Opcode::PEXT, if L {
                         instruction.opcode = Opcode::Invalid;
                         return Err(DecodeError::InvalidOpcode);
@@ -3270,6 +3413,18 @@ fn read_vex_instruction<
                 }
             } else {
                 match opc {
+                    0x49 => (Opcode::LDTILECFG, if L || instruction.prefixes.vex_unchecked().w() {
+                        instruction.opcode = Opcode::Invalid;
+                        return Err(DecodeError::InvalidOpcode);
+                    } else {
+                        VEXOperandCode::M_tilecfg
+                    }),
+                    0x5E => (Opcode::TDPBUUD, if L || instruction.prefixes.vex_unchecked().w() {
+                        instruction.opcode = Opcode::Invalid;
+                        return Err(DecodeError::InvalidOpcode);
+                    } else {
+                        VEXOperandCode::G_E_V_tmm
+                    }),
                     0xF2 => (Opcode::ANDN, if L {
                         instruction.opcode = Opcode::Invalid;
                         return Err(DecodeError::InvalidOpcode);
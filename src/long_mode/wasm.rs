@@ -0,0 +1,15 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::wasm::{DecodedInstruction, OperandDescriptor};
+use crate::long_mode::{InstDecoder, Instruction, Operand};
+
+fn mode_operand_kind(op: &Operand) -> &'static str {
+    match op {
+        Operand::ImmediateI64(_) |
+        Operand::ImmediateU64(_) => "immediate",
+        _ => unreachable!("operand is handled by operand_kind"),
+    }
+}
+
+include!("../shared/wasm.in");
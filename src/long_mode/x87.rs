@@ -0,0 +1,3 @@
+use crate::long_mode::{Category, Instruction, Opcode, RegSpec, RegisterBank};
+
+include!("../shared/x87.in");
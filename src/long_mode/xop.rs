@@ -0,0 +1,19 @@
+use yaxpeax_arch::Reader;
+use yaxpeax_arch::annotation::DescriptionSink;
+
+use crate::long_mode::{Arch, DecodeError, FieldDescription, InnerDescription, Instruction, Opcode, OperandSpec, RegSpec, RegisterBank};
+use crate::Encoding;
+use crate::long_mode::bytes;
+use crate::long_mode::{read_modrm, read_E, read_E_xmm, read_E_ymm, read_imm_unsigned};
+
+const XOP_REGISTER_MASK: u8 = 0b1111;
+
+fn gpr_size(instruction: &Instruction) -> (RegisterBank, u8) {
+    if instruction.prefixes.vex_unchecked().w() {
+        (RegisterBank::Q, 8)
+    } else {
+        (RegisterBank::D, 4)
+    }
+}
+
+include!("../shared/xop.in");
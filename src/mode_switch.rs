@@ -0,0 +1,208 @@
+//! decoding for processes that switch between 64-bit and compatibility mode code segments.
+//!
+//! a 64-bit process runs 32-bit code by far-jumping to a code segment with `CS.L` clear, as wow64
+//! does on windows, and 32-bit code reaches 64-bit code by far-jumping back, as in the "heaven's
+//! gate" technique. which of `long_mode`, `protected_mode`, or `real_mode` decodes an
+//! instruction then depends on the code segment selector it runs under, and `ModeSwitchingDecoder`
+//! picks the decoder from a map of selectors to `CodeSegmentMode`.
+//!
+//! ```
+//! use yaxpeax_x86::mode_switch::{CodeSegmentMode, ModeSwitchingDecoder};
+//!
+//! let decoder = ModeSwitchingDecoder::new();
+//! // `48 89 e5` is `mov rbp, rsp` in a 64-bit code segment...
+//! let instr = decoder.decode_slice(0x33, &[0x48, 0x89, 0xe5]).unwrap();
+//! assert_eq!(instr.to_string(), "mov rbp, rsp");
+//! // ...and `dec eax` in a 32-bit one.
+//! let instr = decoder.decode_slice(0x23, &[0x48, 0x89, 0xe5]).unwrap();
+//! assert_eq!(instr.to_string(), "dec eax");
+//! assert_eq!(decoder.mode_for(0x23), Some(CodeSegmentMode::Compatibility32));
+//! ```
+
+use core::fmt;
+
+use alloc::collections::BTreeMap;
+
+use yaxpeax_arch::LengthedInstruction;
+
+use crate::{long_mode, protected_mode, real_mode};
+
+/// how a code segment's descriptor has the processor decode instructions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CodeSegmentMode {
+    /// a 64-bit code segment, `CS.L = 1`: decoded by `long_mode`.
+    Long,
+    /// a 32-bit compatibility mode code segment, `CS.L = 0` and `CS.D = 1`: decoded by
+    /// `protected_mode`.
+    Compatibility32,
+    /// a 16-bit compatibility mode code segment, `CS.L = 0` and `CS.D = 0`: decoded by
+    /// `real_mode`.
+    Compatibility16,
+}
+
+impl CodeSegmentMode {
+    /// the mode of a code segment with the `L` and `D` bits of its descriptor as given. `L` and
+    /// `D` both set is reserved, and `None`.
+    pub fn from_descriptor_bits(l: bool, d: bool) -> Option<CodeSegmentMode> {
+        match (l, d) {
+            (true, false) => Some(CodeSegmentMode::Long),
+            (false, true) => Some(CodeSegmentMode::Compatibility32),
+            (false, false) => Some(CodeSegmentMode::Compatibility16),
+            (true, true) => None,
+        }
+    }
+}
+
+/// an instruction decoded by a `ModeSwitchingDecoder`, from whichever mode its code segment
+/// selected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SegmentInstruction {
+    Long(long_mode::Instruction),
+    Compatibility32(protected_mode::Instruction),
+    Compatibility16(real_mode::Instruction),
+}
+
+impl SegmentInstruction {
+    /// the mode this instruction was decoded in.
+    pub fn mode(&self) -> CodeSegmentMode {
+        match self {
+            SegmentInstruction::Long(_) => CodeSegmentMode::Long,
+            SegmentInstruction::Compatibility32(_) => CodeSegmentMode::Compatibility32,
+            SegmentInstruction::Compatibility16(_) => CodeSegmentMode::Compatibility16,
+        }
+    }
+
+    /// the length of this instruction, in bytes.
+    pub fn len(&self) -> usize {
+        match self {
+            SegmentInstruction::Long(instr) => instr.len().to_const() as usize,
+            SegmentInstruction::Compatibility32(instr) => instr.len().to_const() as usize,
+            SegmentInstruction::Compatibility16(instr) => instr.len().to_const() as usize,
+        }
+    }
+
+    /// is this instruction zero bytes long? decoded instructions never are, so this is always
+    /// `false`; it exists to go with `len`.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl fmt::Display for SegmentInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SegmentInstruction::Long(instr) => fmt::Display::fmt(instr, f),
+            SegmentInstruction::Compatibility32(instr) => fmt::Display::fmt(instr, f),
+            SegmentInstruction::Compatibility16(instr) => fmt::Display::fmt(instr, f),
+        }
+    }
+}
+
+/// why a `ModeSwitchingDecoder` could not decode an instruction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SegmentDecodeError {
+    /// the selector has no mode in the decoder's map.
+    UnknownSelector(u16),
+    Long(long_mode::DecodeError),
+    Compatibility32(protected_mode::DecodeError),
+    Compatibility16(real_mode::DecodeError),
+}
+
+impl fmt::Display for SegmentDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SegmentDecodeError::UnknownSelector(selector) => {
+                write!(f, "no code segment mode for selector {:#x}", selector)
+            }
+            SegmentDecodeError::Long(e) => fmt::Display::fmt(e, f),
+            SegmentDecodeError::Compatibility32(e) => fmt::Display::fmt(e, f),
+            SegmentDecodeError::Compatibility16(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+/// a decoder for each of `long_mode`, `protected_mode`, and `real_mode`, and which code segment
+/// selectors select which of them.
+#[derive(Clone)]
+pub struct ModeSwitchingDecoder {
+    long: long_mode::InstDecoder,
+    compatibility32: protected_mode::InstDecoder,
+    compatibility16: real_mode::InstDecoder,
+    selectors: BTreeMap<u16, CodeSegmentMode>,
+}
+
+impl Default for ModeSwitchingDecoder {
+    fn default() -> Self {
+        ModeSwitchingDecoder::new()
+    }
+}
+
+impl ModeSwitchingDecoder {
+    /// a decoder with each mode's default `InstDecoder`, and the user code segment selectors that
+    /// both windows and linux use on x86_64: `0x33` for 64-bit code, and `0x23` for 32-bit code.
+    pub fn new() -> Self {
+        let mut decoder = ModeSwitchingDecoder::with_decoders(
+            long_mode::InstDecoder::default(),
+            protected_mode::InstDecoder::default(),
+            real_mode::InstDecoder::default(),
+        );
+        decoder.map_selector(0x33, CodeSegmentMode::Long);
+        decoder.map_selector(0x23, CodeSegmentMode::Compatibility32);
+        decoder
+    }
+
+    /// a decoder with the given decoders for each mode, and no selectors mapped to any of them.
+    pub fn with_decoders(long: long_mode::InstDecoder, compatibility32: protected_mode::InstDecoder, compatibility16: real_mode::InstDecoder) -> Self {
+        ModeSwitchingDecoder {
+            long,
+            compatibility32,
+            compatibility16,
+            selectors: BTreeMap::new(),
+        }
+    }
+
+    /// decode code run under `selector` in `mode`, replacing any mode `selector` had before.
+    /// selectors are matched exactly, including their requested privilege level bits.
+    pub fn map_selector(&mut self, selector: u16, mode: CodeSegmentMode) -> &mut Self {
+        self.selectors.insert(selector, mode);
+        self
+    }
+
+    /// stop decoding code run under `selector`, returning the mode it had.
+    pub fn unmap_selector(&mut self, selector: u16) -> Option<CodeSegmentMode> {
+        self.selectors.remove(&selector)
+    }
+
+    /// the mode code run under `selector` is decoded in, if `selector` is mapped.
+    pub fn mode_for(&self, selector: u16) -> Option<CodeSegmentMode> {
+        self.selectors.get(&selector).cloned()
+    }
+
+    /// decode the instruction at the start of `data`, run under code segment `selector`.
+    pub fn decode_slice(&self, selector: u16, data: &[u8]) -> Result<SegmentInstruction, SegmentDecodeError> {
+        let mode = self.mode_for(selector).ok_or(SegmentDecodeError::UnknownSelector(selector))?;
+        self.decode_slice_in(mode, data)
+    }
+
+    /// decode the instruction at the start of `data` in `mode`, regardless of selector.
+    pub fn decode_slice_in(&self, mode: CodeSegmentMode, data: &[u8]) -> Result<SegmentInstruction, SegmentDecodeError> {
+        match mode {
+            CodeSegmentMode::Long => {
+                self.long.decode_slice(data)
+                    .map(SegmentInstruction::Long)
+                    .map_err(SegmentDecodeError::Long)
+            }
+            CodeSegmentMode::Compatibility32 => {
+                self.compatibility32.decode_slice(data)
+                    .map(SegmentInstruction::Compatibility32)
+                    .map_err(SegmentDecodeError::Compatibility32)
+            }
+            CodeSegmentMode::Compatibility16 => {
+                self.compatibility16.decode_slice(data)
+                    .map(SegmentInstruction::Compatibility16)
+                    .map_err(SegmentDecodeError::Compatibility16)
+            }
+        }
+    }
+}
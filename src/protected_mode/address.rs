@@ -0,0 +1,26 @@
+use crate::protected_mode::{AddressingForm, Instruction, Operand, RegSpec, Segment};
+
+fn displacement_address(op: &Operand) -> Option<u64> {
+    match *op {
+        Operand::DisplacementU16(address) => Some(address as u64),
+        Operand::DisplacementU32(address) => Some(address as u64),
+        _ => None,
+    }
+}
+
+fn linear_address(segment: Segment, offset: u64, regs: &dyn RegValueSource) -> Option<u64> {
+    Some(regs.segment_base(segment)?.wrapping_add(offset) & 0xffff_ffff)
+}
+
+fn displacement_addressing_form(op: &Operand) -> Option<AddressingForm> {
+    match *op {
+        Operand::DisplacementU16(_) => Some(AddressingForm::Bits16),
+        _ => Some(AddressingForm::Bits32),
+    }
+}
+
+fn instruction_addressing_form(inst: &Instruction) -> AddressingForm {
+    if inst.prefixes.address_size() { AddressingForm::Bits16 } else { AddressingForm::Bits32 }
+}
+
+include!("../shared/address.in");
@@ -0,0 +1,14 @@
+use crate::BranchHint;
+use crate::protected_mode::{Category, Instruction, Opcode, Operand};
+
+pub(crate) fn branch_target_mask(inst: &Instruction) -> u64 {
+    if inst.prefixes.operand_size() { 0xffff } else { 0xffff_ffff }
+}
+
+fn absolute_branch_target(_inst: &Instruction) -> Option<u64> {
+    None
+}
+
+const CET_LANDING_PAD: Opcode = Opcode::ENDBR32;
+
+include!("../shared/branch.in");
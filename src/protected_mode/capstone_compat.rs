@@ -0,0 +1,35 @@
+use crate::{AccessKind, Category, Flags, IsaExtension};
+use crate::protected_mode::{Instruction, MergeMode, Opcode, Operand, RegSpec, SaeMode, Segment};
+
+fn push_mode_operand(x86: &mut X86Detail, inst: &Instruction, op: &Operand, size: u8, access: AccessKind) {
+    match *op {
+        Operand::DisplacementU16(disp) => {
+            x86.push(X86Op {
+                value: mem(inst, None, None, disp as i64),
+                size,
+                access,
+                avx_bcast: None,
+                avx_zero_opmask: false,
+            });
+        }
+        Operand::AbsoluteFarAddress { segment, address } => {
+            // capstone reports a far address as two immediates, the segment first.
+            for imm in [segment as i64, address as i64].iter() {
+                x86.push(X86Op {
+                    value: X86OpValue::Imm(*imm),
+                    size: 0,
+                    access,
+                    avx_bcast: None,
+                    avx_zero_opmask: false,
+                });
+            }
+        }
+        _ => unreachable!("operand is handled by to_capstone"),
+    }
+}
+
+fn address_size(inst: &Instruction) -> u8 {
+    if inst.prefixes.address_size() { 2 } else { 4 }
+}
+
+include!("../shared/capstone_compat.in");
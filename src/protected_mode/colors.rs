@@ -0,0 +1,3 @@
+use crate::protected_mode::{DisplaySink, InstructionDisplayer, Opcode, RegSpec};
+
+include!("../shared/colors.in");
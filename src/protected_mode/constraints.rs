@@ -0,0 +1,3 @@
+use crate::protected_mode::{Instruction, Operand, RegisterClass};
+
+include!("../shared/constraints.in");
@@ -0,0 +1,17 @@
+use crate::protected_mode::{AccessKind, Instruction, Operand, RegSpec, RegisterBank, WriteBehavior, X86Operand};
+
+fn written_register(inst: &Instruction, reg: RegSpec) -> (RegSpec, WriteBehavior) {
+    match reg.bank {
+        // `vex`, `xop`, and `evex`-encoded instructions clear the upper bits of the vector
+        // registers they write.
+        RegisterBank::X | RegisterBank::Y if inst.prefixes.vex().is_some() => {
+            (RegSpec { bank: RegisterBank::Z, num: reg.num }, WriteBehavior::ZeroExtend)
+        }
+        RegisterBank::B | RegisterBank::W | RegisterBank::X | RegisterBank::Y => {
+            (reg, WriteBehavior::Merge)
+        }
+        _ => (reg, WriteBehavior::Full),
+    }
+}
+
+include!("../shared/dataflow.in");
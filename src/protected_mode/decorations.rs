@@ -0,0 +1,3 @@
+use crate::protected_mode::{Instruction, MergeMode, Opcode, Operand, RegisterBank, RegSpec, SaeMode};
+
+include!("../shared/decorations.in");
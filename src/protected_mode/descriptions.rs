@@ -0,0 +1,19 @@
+use crate::protected_mode::Opcode;
+
+include!("../shared/descriptions.in");
+
+opcode_descriptions!(mode_description, {
+    LES => "Load far pointer using ES",
+    LDS => "Load far pointer using DS",
+    JECXZ => "Jump if ECX register is 0",
+    PUSHA => "Push all general-purpose registers",
+    POPA => "Pop all general-purpose registers",
+    BOUND => "Check array index against bounds",
+    ARPL => "Adjust RPL field of segment selector",
+    AAS => "ASCII adjust AL after subtraction",
+    AAA => "ASCII adjust after addition",
+    DAS => "Decimal adjust AL after subtraction",
+    DAA => "Decimal adjust AL after addition",
+    AAM => "ASCII adjust AX after multiply",
+    AAD => "ASCII adjust AX before division",
+});
@@ -4,8 +4,10 @@ use crate::safer_unchecked::GetSaferUnchecked as _;
 use yaxpeax_arch::{Colorize, ShowContextual, NoColors, YaxColors};
 use yaxpeax_arch::display::*;
 
-use crate::MEM_SIZE_STRINGS;
-use crate::protected_mode::{RegSpec, Opcode, Operand, MergeMode, InstDecoder, Instruction, Segment, PrefixVex, OperandSpec};
+use crate::{MemoryAccessSize, MnemonicAliases, MEM_SIZE_STRINGS};
+use crate::protected_mode::decorations::broadcast_scale;
+use crate::protected_mode::relocate::trailing_width;
+use crate::protected_mode::{Arch, DecodeError, RegSpec, RegisterBank, Opcode, Operand, MergeMode, InstDecoder, Instruction, BranchHint, BranchTarget, Segment, PrefixVex, OperandSpec};
 
 impl fmt::Display for InstDecoder {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -70,6 +72,12 @@ impl fmt::Display for InstDecoder {
         if self.intel_quirks() { write!(f, "intel_quirks ")? }
         if self.amd_quirks() { write!(f, "amd_quirks ")? }
         if self.avx() { write!(f, "avx ")? }
+        if self.avx10_2() { write!(f, "avx10_2 ")? }
+        if self.avx512_fp16() { write!(f, "avx512_fp16 ")? }
+        if self.retain_bytes() { write!(f, "retain_bytes ")? }
+        if self._3dnow() { write!(f, "_3dnow ")? }
+        if self.permissive_lock() { write!(f, "permissive_lock ")? }
+        if self.emit_invalid_bytes() { write!(f, "emit_invalid_bytes ")? }
         Ok(())
     }
 }
@@ -121,6 +129,7 @@ const REG_NAMES: &[&'static str] = &[
     "k0", "k1", "k2", "k3", "k4", "k5", "k6", "k7",
     "eip", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG",
     "eflags", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG", "BUG",
+    "bnd0", "bnd1", "bnd2", "bnd3", "BUG", "BUG", "BUG", "BUG",
 ];
 
 pub(crate) fn regspec_label(spec: &RegSpec) -> &'static str {
@@ -141,195 +150,223 @@ impl fmt::Display for Operand {
 
 impl <T: fmt::Write, Y: YaxColors> Colorize<T, Y> for Operand {
     fn colorize(&self, colors: &Y, f: &mut T) -> fmt::Result {
-        match self {
-            &Operand::ImmediateU8(imm) => {
-                write!(f, "{}", colors.number(u8_hex(imm)))
-            }
-            &Operand::ImmediateI8(imm) => {
-                write!(f, "{}",
-                    colors.number(signed_i8_hex(imm)))
-            },
-            &Operand::ImmediateU16(imm) => {
-                write!(f, "{}", colors.number(u16_hex(imm)))
-            }
-            &Operand::ImmediateI16(imm) => {
-                write!(f, "{}",
-                    colors.number(signed_i16_hex(imm)))
-            },
-            &Operand::ImmediateU32(imm) => {
-                write!(f, "{}", colors.number(u32_hex(imm)))
-            }
-            &Operand::ImmediateI32(imm) => {
-                write!(f, "{}",
-                    colors.number(signed_i32_hex(imm)))
-            },
-            &Operand::Register(ref spec) => {
-                f.write_str(regspec_label(spec))
-            }
-            &Operand::RegisterMaskMerge(ref spec, ref mask, merge_mode) => {
-                f.write_str(regspec_label(spec))?;
-                if mask.num != 0 {
-                    f.write_str("{")?;
-                    f.write_str(regspec_label(mask))?;
-                    f.write_str("}")?;
-                }
-                if let MergeMode::Zero = merge_mode {
-                    f.write_str("{z}")?;
-                }
-                Ok(())
-            }
-            &Operand::RegisterMaskMergeSae(ref spec, ref mask, merge_mode, sae_mode) => {
-                f.write_str(regspec_label(spec))?;
-                if mask.num != 0 {
-                    f.write_str("{")?;
-                    f.write_str(regspec_label(mask))?;
-                    f.write_str("}")?;
-                }
-                if let MergeMode::Zero = merge_mode {
-                    f.write_str("{z}")?;
-                }
-                f.write_str(sae_mode.label())?;
-                Ok(())
-            }
-            &Operand::RegisterMaskMergeSaeNoround(ref spec, ref mask, merge_mode) => {
-                f.write_str(regspec_label(spec))?;
-                if mask.num != 0 {
-                    f.write_str("{")?;
-                    f.write_str(regspec_label(mask))?;
-                    f.write_str("}")?;
-                }
-                if let MergeMode::Zero = merge_mode {
-                    f.write_str("{z}")?;
-                }
-                f.write_str("{sae}")?;
-                Ok(())
-            }
-            &Operand::DisplacementU16(imm) => {
-                write!(f, "[{}]", colors.address(u16_hex(imm)))
+        write_operand(self, colors, &mut PlainSink(f))
+    }
+}
+
+fn write_operand<T: DisplaySink, Y: YaxColors>(op: &Operand, colors: &Y, f: &mut T) -> fmt::Result {
+    match op {
+        &Operand::ImmediateU8(imm) => {
+            f.write_immediate(imm as i64, &colors.number(u8_hex(imm)))
+        }
+        &Operand::ImmediateI8(imm) => {
+            f.write_immediate(imm as i64, &colors.number(signed_i8_hex(imm)))
+        },
+        &Operand::ImmediateU16(imm) => {
+            f.write_immediate(imm as i64, &colors.number(u16_hex(imm)))
+        }
+        &Operand::ImmediateI16(imm) => {
+            f.write_immediate(imm as i64, &colors.number(signed_i16_hex(imm)))
+        },
+        &Operand::ImmediateU32(imm) => {
+            f.write_immediate(imm as i64, &colors.number(u32_hex(imm)))
+        }
+        &Operand::ImmediateI32(imm) => {
+            f.write_immediate(imm as i64, &colors.number(signed_i32_hex(imm)))
+        },
+        &Operand::Register(ref spec) => {
+            write_reg(f, spec)
+        }
+        &Operand::RegisterMaskMerge(ref spec, ref mask, merge_mode) => {
+            write_reg(f, spec)?;
+            if mask.num != 0 {
+                f.write_str("{")?;
+                write_reg(f, mask)?;
+                f.write_str("}")?;
             }
-            &Operand::DisplacementU32(imm) => {
-                write!(f, "[{}]", colors.address(u32_hex(imm)))
+            if let MergeMode::Zero = merge_mode {
+                f.write_str("{z}")?;
             }
-            &Operand::RegDisp(ref spec, disp) => {
-                write!(f, "[{} ", regspec_label(spec))?;
-                format_number_i32(colors, f, disp, NumberStyleHint::HexSignedWithSignSplit)?;
-                write!(f, "]")
-            },
-            &Operand::RegDeref(ref spec) => {
-                f.write_str("[")?;
-                f.write_str(regspec_label(spec))?;
-                f.write_str("]")
-            },
-            &Operand::RegScale(ref spec, scale) => {
-                write!(f, "[{} * {}]",
-                    regspec_label(spec),
-                    colors.number(scale)
-                )
-            },
-            &Operand::RegScaleDisp(ref spec, scale, disp) => {
-                write!(f, "[{} * {} ",
-                    regspec_label(spec),
-                    colors.number(scale),
-                )?;
-                format_number_i32(colors, f, disp, NumberStyleHint::HexSignedWithSignSplit)?;
-                write!(f, "]")
-            },
-            &Operand::RegIndexBase(ref base, ref index) => {
-                f.write_str("[")?;
-                f.write_str(regspec_label(base))?;
-                f.write_str(" + ")?;
-                f.write_str(regspec_label(index))?;
-                f.write_str("]")
+            Ok(())
+        }
+        &Operand::RegisterMaskMergeSae(ref spec, ref mask, merge_mode, sae_mode) => {
+            write_reg(f, spec)?;
+            if mask.num != 0 {
+                f.write_str("{")?;
+                write_reg(f, mask)?;
+                f.write_str("}")?;
             }
-            &Operand::RegIndexBaseDisp(ref base, ref index, disp) => {
-                write!(f, "[{} + {} ",
-                    regspec_label(base),
-                    regspec_label(index),
-                )?;
-                format_number_i32(colors, f, disp, NumberStyleHint::HexSignedWithSignSplit)?;
-                write!(f, "]")
-            },
-            &Operand::RegIndexBaseScale(ref base, ref index, scale) => {
-                write!(f, "[{} + {} * {}]",
-                    regspec_label(base),
-                    regspec_label(index),
-                    colors.number(scale)
-                )
+            if let MergeMode::Zero = merge_mode {
+                f.write_str("{z}")?;
             }
-            &Operand::RegIndexBaseScaleDisp(ref base, ref index, scale, disp) => {
-                write!(f, "[{} + {} * {} ",
-                    regspec_label(base),
-                    regspec_label(index),
-                    colors.number(scale),
-                )?;
-                format_number_i32(colors, f, disp, NumberStyleHint::HexSignedWithSignSplit)?;
-                write!(f, "]")
-            },
-            &Operand::RegDispMasked(ref spec, disp, ref mask_reg) => {
-                write!(f, "[{} ", regspec_label(spec))?;
-                format_number_i32(colors, f, disp, NumberStyleHint::HexSignedWithSignSplit)?;
-                write!(f, "]")?;
-                write!(f, "{{{}}}", regspec_label(mask_reg))
-            },
-            &Operand::RegDerefMasked(ref spec, ref mask_reg) => {
-                f.write_str("[")?;
-                f.write_str(regspec_label(spec))?;
-                f.write_str("]")?;
-                write!(f, "{{{}}}", regspec_label(mask_reg))
-            },
-            &Operand::RegScaleMasked(ref spec, scale, ref mask_reg) => {
-                write!(f, "[{} * {}]",
-                    regspec_label(spec),
-                    colors.number(scale)
-                )?;
-                write!(f, "{{{}}}", regspec_label(mask_reg))
-            },
-            &Operand::RegScaleDispMasked(ref spec, scale, disp, ref mask_reg) => {
-                write!(f, "[{} * {} ",
-                    regspec_label(spec),
-                    colors.number(scale),
-                )?;
-                format_number_i32(colors, f, disp, NumberStyleHint::HexSignedWithSignSplit)?;
-                write!(f, "]")?;
-                write!(f, "{{{}}}", regspec_label(mask_reg))
-            },
-            &Operand::RegIndexBaseMasked(ref base, ref index, ref mask_reg) => {
-                f.write_str("[")?;
-                f.write_str(regspec_label(base))?;
-                f.write_str(" + ")?;
-                f.write_str(regspec_label(index))?;
-                f.write_str("]")?;
-                write!(f, "{{{}}}", regspec_label(mask_reg))
+            f.write_str(sae_mode.label())?;
+            Ok(())
+        }
+        &Operand::RegisterMaskMergeSaeNoround(ref spec, ref mask, merge_mode) => {
+            write_reg(f, spec)?;
+            if mask.num != 0 {
+                f.write_str("{")?;
+                write_reg(f, mask)?;
+                f.write_str("}")?;
             }
-            &Operand::RegIndexBaseDispMasked(ref base, ref index, disp, ref mask_reg) => {
-                write!(f, "[{} + {} ",
-                    regspec_label(base),
-                    regspec_label(index),
-                )?;
-                format_number_i32(colors, f, disp, NumberStyleHint::HexSignedWithSignSplit)?;
-                write!(f, "]")?;
-                write!(f, "{{{}}}", regspec_label(mask_reg))
-            },
-            &Operand::RegIndexBaseScaleMasked(ref base, ref index, scale, ref mask_reg) => {
-                write!(f, "[{} + {} * {}]",
-                    regspec_label(base),
-                    regspec_label(index),
-                    colors.number(scale)
-                )?;
-                write!(f, "{{{}}}", regspec_label(mask_reg))
+            if let MergeMode::Zero = merge_mode {
+                f.write_str("{z}")?;
             }
-            &Operand::RegIndexBaseScaleDispMasked(ref base, ref index, scale, disp, ref mask_reg) => {
-                write!(f, "[{} + {} * {} ",
-                    regspec_label(base),
-                    regspec_label(index),
-                    colors.number(scale),
-                )?;
-                format_number_i32(colors, f, disp, NumberStyleHint::HexSignedWithSignSplit)?;
-                write!(f, "]")?;
-                write!(f, "{{{}}}", regspec_label(mask_reg))
-            },
-            &Operand::Nothing => { Ok(()) },
+            f.write_str("{sae}")?;
+            Ok(())
+        }
+        &Operand::DisplacementU16(imm) => {
+            f.write_str("[")?;
+            f.write_address(imm as u64, &colors.address(u16_hex(imm)))?;
+            f.write_str("]")
+        }
+        &Operand::DisplacementU32(imm) => {
+            f.write_str("[")?;
+            f.write_address(imm as u64, &colors.address(u32_hex(imm)))?;
+            f.write_str("]")
+        }
+        &Operand::AbsoluteFarAddress { segment, address } => {
+            f.write_immediate(segment as i64, &colors.number(u16_hex(segment)))?;
+            f.write_str(":")?;
+            f.write_address(address as u64, &colors.address(u32_hex(address)))
+        }
+        &Operand::RegDisp(ref spec, disp) => {
+            f.write_str("[")?;
+            write_reg(f, spec)?;
+            f.write_str(" ")?;
+            write_disp(colors, f, disp)?;
+            write!(f, "]")
+        },
+        &Operand::RegDeref(ref spec) => {
+            f.write_str("[")?;
+            write_reg(f, spec)?;
+            f.write_str("]")
+        },
+        &Operand::RegScale(ref spec, scale) => {
+            f.write_str("[")?;
+            write_reg(f, spec)?;
+            write!(f, " * {}]", colors.number(scale))
+        },
+        &Operand::RegScaleDisp(ref spec, scale, disp) => {
+            f.write_str("[")?;
+            write_reg(f, spec)?;
+            write!(f, " * {} ", colors.number(scale))?;
+            write_disp(colors, f, disp)?;
+            write!(f, "]")
+        },
+        &Operand::RegIndexBase(ref base, ref index) => {
+            f.write_str("[")?;
+            write_reg(f, base)?;
+            f.write_str(" + ")?;
+            write_reg(f, index)?;
+            f.write_str("]")
+        }
+        &Operand::RegIndexBaseDisp(ref base, ref index, disp) => {
+            f.write_str("[")?;
+            write_reg(f, base)?;
+            f.write_str(" + ")?;
+            write_reg(f, index)?;
+            f.write_str(" ")?;
+            write_disp(colors, f, disp)?;
+            write!(f, "]")
+        },
+        &Operand::RegIndexBaseScale(ref base, ref index, scale) => {
+            f.write_str("[")?;
+            write_reg(f, base)?;
+            f.write_str(" + ")?;
+            write_reg(f, index)?;
+            write!(f, " * {}]", colors.number(scale))
         }
+        &Operand::RegIndexBaseScaleDisp(ref base, ref index, scale, disp) => {
+            f.write_str("[")?;
+            write_reg(f, base)?;
+            f.write_str(" + ")?;
+            write_reg(f, index)?;
+            write!(f, " * {} ", colors.number(scale))?;
+            write_disp(colors, f, disp)?;
+            write!(f, "]")
+        },
+        &Operand::RegDispMasked(ref spec, disp, ref mask_reg) => {
+            f.write_str("[")?;
+            write_reg(f, spec)?;
+            f.write_str(" ")?;
+            write_disp(colors, f, disp)?;
+            write!(f, "]")?;
+            f.write_str("{")?;
+            write_reg(f, mask_reg)?;
+            f.write_str("}")
+        },
+        &Operand::RegDerefMasked(ref spec, ref mask_reg) => {
+            f.write_str("[")?;
+            write_reg(f, spec)?;
+            f.write_str("]")?;
+            f.write_str("{")?;
+            write_reg(f, mask_reg)?;
+            f.write_str("}")
+        },
+        &Operand::RegScaleMasked(ref spec, scale, ref mask_reg) => {
+            f.write_str("[")?;
+            write_reg(f, spec)?;
+            write!(f, " * {}]", colors.number(scale))?;
+            f.write_str("{")?;
+            write_reg(f, mask_reg)?;
+            f.write_str("}")
+        },
+        &Operand::RegScaleDispMasked(ref spec, scale, disp, ref mask_reg) => {
+            f.write_str("[")?;
+            write_reg(f, spec)?;
+            write!(f, " * {} ", colors.number(scale))?;
+            write_disp(colors, f, disp)?;
+            write!(f, "]")?;
+            f.write_str("{")?;
+            write_reg(f, mask_reg)?;
+            f.write_str("}")
+        },
+        &Operand::RegIndexBaseMasked(ref base, ref index, ref mask_reg) => {
+            f.write_str("[")?;
+            write_reg(f, base)?;
+            f.write_str(" + ")?;
+            write_reg(f, index)?;
+            f.write_str("]")?;
+            f.write_str("{")?;
+            write_reg(f, mask_reg)?;
+            f.write_str("}")
+        }
+        &Operand::RegIndexBaseDispMasked(ref base, ref index, disp, ref mask_reg) => {
+            f.write_str("[")?;
+            write_reg(f, base)?;
+            f.write_str(" + ")?;
+            write_reg(f, index)?;
+            f.write_str(" ")?;
+            write_disp(colors, f, disp)?;
+            write!(f, "]")?;
+            f.write_str("{")?;
+            write_reg(f, mask_reg)?;
+            f.write_str("}")
+        },
+        &Operand::RegIndexBaseScaleMasked(ref base, ref index, scale, ref mask_reg) => {
+            f.write_str("[")?;
+            write_reg(f, base)?;
+            f.write_str(" + ")?;
+            write_reg(f, index)?;
+            write!(f, " * {}]", colors.number(scale))?;
+            f.write_str("{")?;
+            write_reg(f, mask_reg)?;
+            f.write_str("}")
+        }
+        &Operand::RegIndexBaseScaleDispMasked(ref base, ref index, scale, disp, ref mask_reg) => {
+            f.write_str("[")?;
+            write_reg(f, base)?;
+            f.write_str(" + ")?;
+            write_reg(f, index)?;
+            write!(f, " * {} ", colors.number(scale))?;
+            write_disp(colors, f, disp)?;
+            write!(f, "]")?;
+            f.write_str("{")?;
+            write_reg(f, mask_reg)?;
+            f.write_str("}")
+        },
+        &Operand::Nothing => { Ok(()) },
     }
 }
 
@@ -1403,6 +1440,7 @@ const MNEMONICS: &[&'static str] = &[
     "wruss",
     "wrss",
     "incssp",
+    "rdssp",
     "saveprevssp",
     "setssbsy",
     "clrssbsy",
@@ -1431,6 +1469,7 @@ const MNEMONICS: &[&'static str] = &[
     // TSXLDTRK
     "xsusldtrk",
     "xresldtrk",
+    "serialize",
 
     // AVX512F
     "valignd",
@@ -1807,10 +1846,208 @@ const MNEMONICS: &[&'static str] = &[
     "pvalidate",
     "rmpadjust",
     "rmpupdate",
+    "vmgexit",
+
+    "vcvtne2ph2bf8",
+    "vcvtneph2bf8",
+    "vcvtbiasph2bf8",
+    "vcvt2ps2phx",
+
+    "vaddph",
+    "vaddsh",
+    "vsubph",
+    "vsubsh",
+    "vmulph",
+    "vmulsh",
+    "vdivph",
+    "vdivsh",
+    "vminph",
+    "vminsh",
+    "vmaxph",
+    "vmaxsh",
+    "vsqrtph",
+    "vsqrtsh",
+    "vrcpph",
+    "vrcpsh",
+    "vrsqrtph",
+    "vrsqrtsh",
+    "vscalefph",
+    "vscalefsh",
+    "vgetexpph",
+    "vgetexpsh",
+    "vgetmantph",
+    "vgetmantsh",
+    "vreduceph",
+    "vreducesh",
+    "vrndscaleph",
+    "vrndscalesh",
+    "vfmadd132ph",
+    "vfmadd132sh",
+    "vfmadd213ph",
+    "vfmadd213sh",
+    "vfmadd231ph",
+    "vfmadd231sh",
+    "vfmsub132ph",
+    "vfmsub132sh",
+    "vfmsub213ph",
+    "vfmsub213sh",
+    "vfmsub231ph",
+    "vfmsub231sh",
+    "vfnmadd132ph",
+    "vfnmadd132sh",
+    "vfnmadd213ph",
+    "vfnmadd213sh",
+    "vfnmadd231ph",
+    "vfnmadd231sh",
+    "vfnmsub132ph",
+    "vfnmsub132sh",
+    "vfnmsub213ph",
+    "vfnmsub213sh",
+    "vfnmsub231ph",
+    "vfnmsub231sh",
+    "vfmaddsub132ph",
+    "vfmaddsub213ph",
+    "vfmaddsub231ph",
+    "vfmsubadd132ph",
+    "vfmsubadd213ph",
+    "vfmsubadd231ph",
+    "vfmaddcph",
+    "vfmaddcsh",
+    "vfcmaddcph",
+    "vfcmaddcsh",
+    "vfmulcph",
+    "vfmulcsh",
+    "vfcmulcph",
+    "vfcmulcsh",
+    "vcvtph2psx",
+    "vcvtps2phx",
+    "vcvtph2pd",
+    "vcvtpd2ph",
+    "vcvtsh2ss",
+    "vcvtss2sh",
+    "vcvtsh2sd",
+    "vcvtsd2sh",
+    "vcvtph2dq",
+    "vcvttph2dq",
+    "vcvtdq2ph",
+    "vcvtph2udq",
+    "vcvttph2udq",
+    "vcvtudq2ph",
+    "vcvtph2qq",
+    "vcvttph2qq",
+    "vcvtqq2ph",
+    "vcvtph2uqq",
+    "vcvttph2uqq",
+    "vcvtuqq2ph",
+    "vcvtph2w",
+    "vcvttph2w",
+    "vcvtw2ph",
+    "vcvtph2uw",
+    "vcvttph2uw",
+    "vcvtuw2ph",
+    "vcvtsh2si",
+    "vcvttsh2si",
+    "vcvtsi2sh",
+    "vcvtsh2usi",
+    "vcvttsh2usi",
+    "vcvtusi2sh",
+    "vcmpph",
+    "vcmpsh",
+    "vcomish",
+    "vucomish",
+    "vfpclassph",
+    "vfpclasssh",
+    "vmovsh",
+    "vmovw",
+
+    "vfrczps",
+    "vfrczpd",
+    "vfrczss",
+    "vfrczsd",
+    "vpcmov",
+    "vpperm",
+    "vpcomb",
+    "vpcomw",
+    "vpcomd",
+    "vpcomq",
+    "vpcomub",
+    "vpcomuw",
+    "vpcomud",
+    "vpcomuq",
+    "vphaddbw",
+    "vphaddbd",
+    "vphaddbq",
+    "vphaddwd",
+    "vphaddwq",
+    "vphadddq",
+    "vphaddubw",
+    "vphaddubd",
+    "vphaddubq",
+    "vphadduwd",
+    "vphadduwq",
+    "vphaddudq",
+    "vphsubbw",
+    "vphsubwd",
+    "vphsubdq",
+    "vpmacssww",
+    "vpmacsswd",
+    "vpmacssdql",
+    "vpmacssdd",
+    "vpmacssdqh",
+    "vpmacsww",
+    "vpmacswd",
+    "vpmacsdql",
+    "vpmacsdd",
+    "vpmacsdqh",
+    "vpmadcsswd",
+    "vpmadcswd",
+    "vprotb",
+    "vprotw",
+    "vprotd",
+    "vprotq",
+    "vpshlb",
+    "vpshlw",
+    "vpshld",
+    "vpshlq",
+    "vpshab",
+    "vpshaw",
+    "vpshad",
+    "vpshaq",
+
+    "blcfill",
+    "blsfill",
+    "blcs",
+    "tzmsk",
+    "blcic",
+    "blsic",
+    "t1mskc",
+    "blcmsk",
+    "blci",
+
+    "llwpcb",
+    "slwpcb",
+    "lwpins",
+    "lwpval",
 ];
 
+/// the `Opcode` with discriminant `index`. `Opcode` is `repr(u16)` with discriminants counting
+/// up from zero, so `index` must be less than `MNEMONICS.len()`.
+unsafe fn opcode_at(index: usize) -> Opcode {
+    core::mem::transmute(index as u16)
+}
+
+/// the accumulator a one-byte `nop` exchanges with itself: `ax` with an operand-size prefix, and
+/// `eax` otherwise.
+fn xchg_nop_register(instr: &Instruction) -> RegSpec {
+    if instr.prefixes.operand_size() {
+        RegSpec::ax()
+    } else {
+        RegSpec::eax()
+    }
+}
+
 impl Opcode {
-    fn name(&self) -> &'static str {
+    pub(crate) fn name(&self) -> &'static str {
         unsafe {
             MNEMONICS.get_kinda_unchecked(*self as usize)
         }
@@ -1849,6 +2086,67 @@ impl <T: fmt::Write, Y: YaxColors> Colorize<T, Y> for Opcode {
             Opcode::V4FMADDSS |
             Opcode::V4FMADDPS |
             Opcode::VCVTNE2PS2BF16 |
+            Opcode::VCVTNE2PH2BF8 |
+            Opcode::VCVTNEPH2BF8 |
+            Opcode::VCVTBIASPH2BF8 |
+            Opcode::VCVT2PS2PHX |
+            Opcode::VADDPH |
+            Opcode::VADDSH |
+            Opcode::VSUBPH |
+            Opcode::VSUBSH |
+            Opcode::VMULPH |
+            Opcode::VMULSH |
+            Opcode::VDIVPH |
+            Opcode::VDIVSH |
+            Opcode::VSQRTPH |
+            Opcode::VSQRTSH |
+            Opcode::VRCPPH |
+            Opcode::VRCPSH |
+            Opcode::VRSQRTPH |
+            Opcode::VRSQRTSH |
+            Opcode::VSCALEFPH |
+            Opcode::VSCALEFSH |
+            Opcode::VRNDSCALEPH |
+            Opcode::VRNDSCALESH |
+            Opcode::VFMADD132PH |
+            Opcode::VFMADD132SH |
+            Opcode::VFMADD213PH |
+            Opcode::VFMADD213SH |
+            Opcode::VFMADD231PH |
+            Opcode::VFMADD231SH |
+            Opcode::VFMSUB132PH |
+            Opcode::VFMSUB132SH |
+            Opcode::VFMSUB213PH |
+            Opcode::VFMSUB213SH |
+            Opcode::VFMSUB231PH |
+            Opcode::VFMSUB231SH |
+            Opcode::VFNMADD132PH |
+            Opcode::VFNMADD132SH |
+            Opcode::VFNMADD213PH |
+            Opcode::VFNMADD213SH |
+            Opcode::VFNMADD231PH |
+            Opcode::VFNMADD231SH |
+            Opcode::VFNMSUB132PH |
+            Opcode::VFNMSUB132SH |
+            Opcode::VFNMSUB213PH |
+            Opcode::VFNMSUB213SH |
+            Opcode::VFNMSUB231PH |
+            Opcode::VFNMSUB231SH |
+            Opcode::VFMADDSUB132PH |
+            Opcode::VFMADDSUB213PH |
+            Opcode::VFMADDSUB231PH |
+            Opcode::VFMSUBADD132PH |
+            Opcode::VFMSUBADD213PH |
+            Opcode::VFMSUBADD231PH |
+            Opcode::VFMADDCPH |
+            Opcode::VFMADDCSH |
+            Opcode::VFCMADDCPH |
+            Opcode::VFCMADDCSH |
+            Opcode::VFMULCPH |
+            Opcode::VFMULCSH |
+            Opcode::VFCMULCPH |
+            Opcode::VFCMULCSH |
+            Opcode::VCVTUSI2SH |
             Opcode::VP2INTERSECTD |
             Opcode::VP2INTERSECTQ |
             Opcode::VP4DPWSSDS |
@@ -2370,6 +2668,58 @@ impl <T: fmt::Write, Y: YaxColors> Colorize<T, Y> for Opcode {
             Opcode::KSHIFTRQ |
             Opcode::KXNORQ |
             Opcode::KXORQ |
+            Opcode::VFRCZPS |
+            Opcode::VFRCZPD |
+            Opcode::VFRCZSS |
+            Opcode::VFRCZSD |
+            Opcode::VPHADDBW |
+            Opcode::VPHADDBD |
+            Opcode::VPHADDBQ |
+            Opcode::VPHADDWD |
+            Opcode::VPHADDWQ |
+            Opcode::VPHADDDQ |
+            Opcode::VPHADDUBW |
+            Opcode::VPHADDUBD |
+            Opcode::VPHADDUBQ |
+            Opcode::VPHADDUWD |
+            Opcode::VPHADDUWQ |
+            Opcode::VPHADDUDQ |
+            Opcode::VPHSUBBW |
+            Opcode::VPHSUBWD |
+            Opcode::VPHSUBDQ |
+            Opcode::VPMACSSWW |
+            Opcode::VPMACSSWD |
+            Opcode::VPMACSSDQL |
+            Opcode::VPMACSSDD |
+            Opcode::VPMACSSDQH |
+            Opcode::VPMACSWW |
+            Opcode::VPMACSWD |
+            Opcode::VPMACSDQL |
+            Opcode::VPMACSDD |
+            Opcode::VPMACSDQH |
+            Opcode::VPMADCSSWD |
+            Opcode::VPMADCSWD |
+            Opcode::VPROTB |
+            Opcode::VPROTW |
+            Opcode::VPROTD |
+            Opcode::VPROTQ |
+            Opcode::VPSHLB |
+            Opcode::VPSHLW |
+            Opcode::VPSHLD |
+            Opcode::VPSHLQ |
+            Opcode::VPSHAB |
+            Opcode::VPSHAW |
+            Opcode::VPSHAD |
+            Opcode::VPSHAQ |
+            Opcode::BLCFILL |
+            Opcode::BLSFILL |
+            Opcode::BLCS |
+            Opcode::TZMSK |
+            Opcode::BLCIC |
+            Opcode::BLSIC |
+            Opcode::T1MSKC |
+            Opcode::BLCMSK |
+            Opcode::BLCI |
             Opcode::IMUL => { write!(out, "{}", colors.arithmetic_op(self)) }
             Opcode::POPF |
             Opcode::PUSHF |
@@ -2912,6 +3262,47 @@ impl <T: fmt::Write, Y: YaxColors> Colorize<T, Y> for Opcode {
             Opcode::SETL |
             Opcode::SETGE |
             Opcode::SETLE |
+            Opcode::VPCMOV |
+            Opcode::VPPERM |
+            Opcode::VGETEXPPH |
+            Opcode::VGETEXPSH |
+            Opcode::VGETMANTPH |
+            Opcode::VGETMANTSH |
+            Opcode::VREDUCEPH |
+            Opcode::VREDUCESH |
+            Opcode::VCVTPH2PSX |
+            Opcode::VCVTPS2PHX |
+            Opcode::VCVTPH2PD |
+            Opcode::VCVTPD2PH |
+            Opcode::VCVTSH2SS |
+            Opcode::VCVTSS2SH |
+            Opcode::VCVTSH2SD |
+            Opcode::VCVTSD2SH |
+            Opcode::VCVTPH2DQ |
+            Opcode::VCVTTPH2DQ |
+            Opcode::VCVTDQ2PH |
+            Opcode::VCVTPH2UDQ |
+            Opcode::VCVTTPH2UDQ |
+            Opcode::VCVTUDQ2PH |
+            Opcode::VCVTPH2QQ |
+            Opcode::VCVTTPH2QQ |
+            Opcode::VCVTQQ2PH |
+            Opcode::VCVTPH2UQQ |
+            Opcode::VCVTTPH2UQQ |
+            Opcode::VCVTUQQ2PH |
+            Opcode::VCVTPH2W |
+            Opcode::VCVTTPH2W |
+            Opcode::VCVTW2PH |
+            Opcode::VCVTPH2UW |
+            Opcode::VCVTTPH2UW |
+            Opcode::VCVTUW2PH |
+            Opcode::VCVTSH2SI |
+            Opcode::VCVTTSH2SI |
+            Opcode::VCVTSI2SH |
+            Opcode::VCVTSH2USI |
+            Opcode::VCVTTSH2USI |
+            Opcode::VMOVSH |
+            Opcode::VMOVW |
             Opcode::SETG => { write!(out, "{}", colors.data_op(self)) }
 
             Opcode::VCOMISD |
@@ -3059,6 +3450,24 @@ impl <T: fmt::Write, Y: YaxColors> Colorize<T, Y> for Opcode {
             Opcode::CMPPD |
             Opcode::CMPXCHG8B |
             Opcode::CMPXCHG16B |
+            Opcode::VPCOMB |
+            Opcode::VPCOMW |
+            Opcode::VPCOMD |
+            Opcode::VPCOMQ |
+            Opcode::VPCOMUB |
+            Opcode::VPCOMUW |
+            Opcode::VPCOMUD |
+            Opcode::VPCOMUQ |
+            Opcode::VMINPH |
+            Opcode::VMINSH |
+            Opcode::VMAXPH |
+            Opcode::VMAXSH |
+            Opcode::VCMPPH |
+            Opcode::VCMPSH |
+            Opcode::VCOMISH |
+            Opcode::VUCOMISH |
+            Opcode::VFPCLASSPH |
+            Opcode::VFPCLASSSH |
             Opcode::CMPXCHG => { write!(out, "{}", colors.comparison_op(self)) }
 
             Opcode::WRMSR |
@@ -3153,6 +3562,7 @@ impl <T: fmt::Write, Y: YaxColors> Colorize<T, Y> for Opcode {
             Opcode::VMRESUME |
             Opcode::VMLOAD |
             Opcode::VMMCALL |
+            Opcode::VMGEXIT |
             Opcode::VMSAVE |
             Opcode::VMRUN |
             Opcode::VMXOFF |
@@ -3190,6 +3600,7 @@ impl <T: fmt::Write, Y: YaxColors> Colorize<T, Y> for Opcode {
             Opcode::SENDUIPI |
             Opcode::XSUSLDTRK |
             Opcode::XRESLDTRK |
+            Opcode::SERIALIZE |
             Opcode::BOUND |
             Opcode::ARPL |
             Opcode::BNDMK |
@@ -3198,6 +3609,10 @@ impl <T: fmt::Write, Y: YaxColors> Colorize<T, Y> for Opcode {
             Opcode::BNDCN |
             Opcode::BNDLDX |
             Opcode::BNDSTX |
+            Opcode::LLWPCB |
+            Opcode::SLWPCB |
+            Opcode::LWPINS |
+            Opcode::LWPVAL |
             Opcode::LAR => { write!(out, "{}", colors.platform_op(self)) }
 
             Opcode::CRC32 |
@@ -3232,6 +3647,7 @@ impl <T: fmt::Write, Y: YaxColors> Colorize<T, Y> for Opcode {
             Opcode::WRUSS |
             Opcode::WRSS |
             Opcode::INCSSP |
+            Opcode::RDSSP |
             Opcode::SAVEPREVSSP |
             Opcode::SETSSBSY |
             Opcode::CLRSSBSY |
@@ -3259,14 +3675,24 @@ impl <T: fmt::Write, Y: YaxColors> Colorize<T, Y> for Opcode {
     }
 }
 
+/// with the alternate flag, `{:#}`, the instruction is written after its bytes, like a line of
+/// `objdump -d` without an address. see [`InstructionDisplayer::write_with_bytes`].
 impl fmt::Display for Instruction {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        self.display_with(DisplayStyle::Intel).colorize(&NoColors, fmt)
+        if fmt.alternate() {
+            return self.display_with(DisplayStyle::Intel).write_with_bytes(fmt);
+        }
+        // the same as `display_with(DisplayStyle::Intel)`, without dispatching on a style that is
+        // known here.
+        contextualize_intel(self, &NoColors, 0, Some(&NoContext), &DisplayOptions::new(DisplayStyle::Intel), &mut PlainSink(fmt))
     }
 }
 
 impl<'instr> fmt::Display for InstructionDisplayer<'instr> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if fmt.alternate() {
+            return self.write_with_bytes(fmt);
+        }
         self.colorize(&NoColors, fmt)
     }
 }
@@ -3274,7 +3700,7 @@ impl<'instr> fmt::Display for InstructionDisplayer<'instr> {
 /// enum controlling how `Instruction::display_with` renders instructions. `Intel` is more or less
 /// intel syntax, though memory operand sizes are elided if they can be inferred from other
 /// operands.
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DisplayStyle {
     /// intel-style syntax for instructions, like
     /// `add eax, [edx + ecx * 2 + 0x1234]`
@@ -3282,19 +3708,117 @@ pub enum DisplayStyle {
     /// C-style syntax for instructions, like
     /// `eax += [edx + ecx * 2 + 0x1234]`
     C,
-    // one might imagine an ATT style here, which is mostly interesting for reversing operand
-    // order.
-    // well.
-    // it also complicates memory operands in an offset-only operand, and is just kind of awful, so
-    // it's just not implemented yet.
-    // ATT,
+}
+
+/// the syntax a [`DisplayOptions`] renders instructions in: one of the [`DisplayStyle`]s, or AT&T
+/// syntax, which is only selected by [`DisplayOptions::att`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Syntax {
+    Intel,
+    C,
+    Att,
+}
+
+/// options controlling how `Instruction::display_with` renders instructions. a [`DisplayStyle`]
+/// converts into the default options for that style, so `display_with(DisplayStyle::C)` and
+/// `display_with(DisplayOptions::new(DisplayStyle::C))` are equivalent. AT&T syntax is selected by
+/// [`DisplayOptions::att`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DisplayOptions {
+    pub(crate) syntax: Syntax,
+    pub(crate) att_suffixes: bool,
+    pub(crate) qualified_mnemonics: bool,
+    pub(crate) branch_hints: bool,
+    pub(crate) aliases: MnemonicAliases,
+}
+
+impl DisplayOptions {
+    /// the default options for rendering instructions in `style`.
+    pub fn new(style: DisplayStyle) -> Self {
+        let syntax = match style {
+            DisplayStyle::Intel => Syntax::Intel,
+            DisplayStyle::C => Syntax::C,
+        };
+        DisplayOptions::with_syntax(syntax)
+    }
+
+    /// the default options for rendering instructions in AT&T syntax, like
+    /// `add 0x1234(%edx,%ecx,2),%eax`.
+    ///
+    /// operands are listed in the opposite order from intel syntax, and mnemonics take a size
+    /// suffix when operand size is not implied by a register operand. mnemonics use the
+    /// [`MnemonicAliases::gnu`] spellings by default.
+    ///
+    /// this follows gnu `objdump`'s output where the decoded instruction says enough to, but is not
+    /// identical to it:
+    /// * relative branch targets are written relative to the end of the instruction, like
+    ///   `jmp $+0x10`, rather than as an absolute address.
+    /// * prefixes that do not change the instruction, which `objdump` writes as `data16`, `ds`,
+    ///   `addr16` and the like, are not written.
+    /// * `int3` and `int1` are written `int $0x3` and `int $0x1`, and shifts and rotates by one
+    ///   are written with a `$0x1` count, as the encodings with an immediate are.
+    /// * a `sib` byte that names no index register is not written as an index of `%eiz`.
+    /// * register operands are written at the size the decoder reports, which for a few
+    ///   instructions, like `lss` or `mov` from a segment register, is not the size `objdump` writes.
+    pub fn att() -> Self {
+        DisplayOptions::with_syntax(Syntax::Att).with_aliases(MnemonicAliases::gnu())
+    }
+
+    fn with_syntax(syntax: Syntax) -> Self {
+        DisplayOptions {
+            syntax,
+            att_suffixes: false,
+            qualified_mnemonics: false,
+            branch_hints: false,
+            aliases: MnemonicAliases::new(),
+        }
+    }
+
+    /// under AT&T syntax, always include a size suffix on mnemonics that take one, even
+    /// if operand size is implied by a register operand. this is like `objdump -M suffix`. other
+    /// styles ignore this option.
+    pub fn with_att_suffixes(mut self) -> Self {
+        self.att_suffixes = true;
+        self
+    }
+
+    /// under `DisplayStyle::Intel`, write each mnemonic as [`Instruction::qualified_mnemonic`]
+    /// does, with the suffix that names its operation size: `movsq` rather than `movs`, and
+    /// `vcvtsi2ssq` rather than `vcvtsi2ss`. AT&T syntax has `with_att_suffixes` instead,
+    /// and the `C` style ignores this option.
+    pub fn with_qualified_mnemonics(mut self) -> Self {
+        self.qualified_mnemonics = true;
+        self
+    }
+
+    /// under `DisplayStyle::Intel` and AT&T syntax, follow the mnemonic of a conditional
+    /// branch with its [`Instruction::branch_hint`], as gnu `as` writes them: `jz,pt` for a
+    /// taken hint and `jz,pn` for a not-taken hint. without this option, hints are not shown.
+    pub fn with_branch_hints(mut self) -> Self {
+        self.branch_hints = true;
+        self
+    }
+
+    /// under `DisplayStyle::Intel` and AT&T syntax, write mnemonics with the alternate
+    /// spellings `aliases` selects, like `jne` for `jnz` or `repz ret`. the `C` style ignores this
+    /// option.
+    pub fn with_aliases(mut self, aliases: MnemonicAliases) -> Self {
+        self.aliases = aliases;
+        self
+    }
+}
+
+impl From<DisplayStyle> for DisplayOptions {
+    fn from(style: DisplayStyle) -> Self {
+        DisplayOptions::new(style)
+    }
 }
 
 /// implementation of [`Display`](fmt::Display) that renders instructions using a specified display
 /// style.
 pub struct InstructionDisplayer<'instr> {
     pub(crate) instr: &'instr Instruction,
-    pub(crate) style: DisplayStyle,
+    pub(crate) options: DisplayOptions,
 }
 
 /*
@@ -3327,7 +3851,15 @@ impl Instruction {
     }
 }
 
-fn contextualize_intel<T: fmt::Write, Y: YaxColors>(instr: &Instruction, colors: &Y, _address: u32, _context: Option<&NoContext>, out: &mut T) -> fmt::Result {
+fn write_branch_hint<T: DisplaySink>(instr: &Instruction, out: &mut T) -> fmt::Result {
+    match instr.branch_hint() {
+        Some(BranchHint::Taken) => out.write_str(",pt"),
+        Some(BranchHint::NotTaken) => out.write_str(",pn"),
+        None => Ok(()),
+    }
+}
+
+fn contextualize_intel<T: DisplaySink, Y: YaxColors>(instr: &Instruction, colors: &Y, _address: u32, _context: Option<&NoContext>, options: &DisplayOptions, out: &mut T) -> fmt::Result {
     if instr.xacquire() {
         out.write_str("xacquire ")?;
     }
@@ -3348,14 +3880,35 @@ fn contextualize_intel<T: fmt::Write, Y: YaxColors>(instr: &Instruction, colors:
         }
     }
 
-    out.write_str(instr.opcode.name())?;
+    if options.aliases.repz_ret && instr.opcode == Opcode::RETURN && instr.prefixes.rep() {
+        out.write_str("repz ")?;
+    }
+
+    if is_xchg_nop(instr, &options.aliases) {
+        let reg = Operand::Register(xchg_nop_register(instr));
+        out.write_opcode(instr.opcode, "xchg")?;
+        out.write_str(" ")?;
+        write_operand(&reg, colors, out)?;
+        out.write_str(", ")?;
+        return write_operand(&reg, colors, out);
+    }
+
+    let mnemonic = if let Some(alias) = alias_mnemonic(instr, &options.aliases) {
+        alias
+    } else if options.qualified_mnemonics {
+        instr.qualified_mnemonic()
+    } else {
+        instr.opcode.name()
+    };
+    out.write_opcode(instr.opcode, mnemonic)?;
+
+    if options.branch_hints {
+        write_branch_hint(instr, out)?;
+    }
 
     if instr.opcode == Opcode::XBEGIN {
-        if (instr.imm as i32) >= 0 {
-            return write!(out, " $+{}", colors.number(signed_i32_hex(instr.imm as i32)));
-        } else {
-            return write!(out, " ${}", colors.number(signed_i32_hex(instr.imm as i32)));
-        }
+        out.write_str(" ")?;
+        return write_rel(colors, out, instr.imm as i32);
     }
 
     if instr.operand_count > 0 {
@@ -3380,18 +3933,10 @@ fn contextualize_intel<T: fmt::Write, Y: YaxColors>(instr: &Instruction, colors:
             if RELATIVE_BRANCHES.contains(&instr.opcode) {
                 return match x {
                     Operand::ImmediateI8(rel) => {
-                        if rel >= 0 {
-                            write!(out, "$+{}", colors.number(signed_i32_hex(rel as i32)))
-                        } else {
-                            write!(out, "${}", colors.number(signed_i32_hex(rel as i32)))
-                        }
+                        write_rel(colors, out, rel as i32)
                     }
                     Operand::ImmediateI32(rel) => {
-                        if rel >= 0 {
-                            write!(out, "$+{}", colors.number(signed_i32_hex(rel)))
-                        } else {
-                            write!(out, "${}", colors.number(signed_i32_hex(rel)))
-                        }
+                        write_rel(colors, out, rel)
                     }
                     _ => { unreachable!() }
                 };
@@ -3406,7 +3951,7 @@ fn contextualize_intel<T: fmt::Write, Y: YaxColors>(instr: &Instruction, colors:
         if let Some(prefix) = instr.segment_override_for_op(0) {
             write!(out, "{}:", prefix)?;
         }
-        x.colorize(colors, out)?;
+        write_operand(&x, colors, out)?;
 
         for i in 1..instr.operand_count {
             match instr.opcode {
@@ -3425,52 +3970,9 @@ fn contextualize_intel<T: fmt::Write, Y: YaxColors>(instr: &Instruction, colors:
                             if let Some(prefix) = instr.segment_override_for_op(i) {
                                 write!(out, "{}:", prefix)?;
                             }
-                            x.colorize(colors, out)?;
-                            if let Some(evex) = instr.prefixes.evex() {
-                                if evex.broadcast() && x.is_memory() {
-                                    let scale = if instr.opcode == Opcode::VCVTPD2PS || instr.opcode == Opcode::VCVTTPD2UDQ || instr.opcode == Opcode::VCVTPD2UDQ || instr.opcode == Opcode::VCVTUDQ2PD || instr.opcode == Opcode::VCVTPS2PD || instr.opcode == Opcode::VCVTQQ2PS || instr.opcode == Opcode::VCVTDQ2PD || instr.opcode == Opcode::VCVTTPD2DQ || instr.opcode == Opcode::VFPCLASSPS || instr.opcode == Opcode::VFPCLASSPD || instr.opcode == Opcode::VCVTNEPS2BF16 || instr.opcode == Opcode::VCVTUQQ2PS || instr.opcode == Opcode::VCVTPD2DQ || instr.opcode == Opcode::VCVTTPS2UQQ || instr.opcode == Opcode::VCVTPS2UQQ || instr.opcode == Opcode::VCVTTPS2QQ || instr.opcode == Opcode::VCVTPS2QQ {
-                                        if instr.opcode == Opcode::VFPCLASSPS || instr.opcode ==  Opcode::VCVTNEPS2BF16 {
-                                            if evex.vex().l() {
-                                                8
-                                            } else if evex.lp() {
-                                                16
-                                            } else {
-                                                4
-                                            }
-                                        } else if instr.opcode == Opcode::VFPCLASSPD {
-                                            if evex.vex().l() {
-                                                4
-                                            } else if evex.lp() {
-                                                8
-                                            } else {
-                                                2
-                                            }
-                                        } else {
-                                            // vcvtpd2ps is "cool": in broadcast mode, it can read a
-                                            // double-precision float (qword), resize to single-precision,
-                                            // then broadcast that to the whole destination register. this
-                                            // means we need to show `xmm, qword [addr]{1to4}` if vector
-                                            // size is 256. likewise, scale of 8 for the same truncation
-                                            // reason if vector size is 512.
-                                            // vcvtudq2pd is the same story.
-                                            // vfpclassp{s,d} is a mystery to me.
-                                            if evex.vex().l() {
-                                                4
-                                            } else if evex.lp() {
-                                                8
-                                            } else {
-                                                2
-                                            }
-                                        }
-                                    } else {
-                                        // this should never be `None` - that would imply two
-                                        // memory operands for a broadcasted operation.
-                                        if let Some(width) = Operand::from_spec(instr, instr.operands[i as usize - 1]).width() {
-                                            width / instr.mem_size
-                                        } else {
-                                            0
-                                        }
-                                    };
+                            write_operand(&x, colors, out)?;
+                            if x.is_memory() {
+                                if let Some(scale) = broadcast_scale(instr, i) {
                                     write!(out, "{{1to{}}}", scale)?;
                                 }
                             }
@@ -3483,7 +3985,7 @@ fn contextualize_intel<T: fmt::Write, Y: YaxColors>(instr: &Instruction, colors:
     Ok(())
 }
 
-fn contextualize_c<T: fmt::Write, Y: YaxColors>(instr: &Instruction, colors: &Y, _address: u32, _context: Option<&NoContext>, out: &mut T) -> fmt::Result {
+fn contextualize_c<T: DisplaySink, Y: YaxColors>(instr: &Instruction, colors: &Y, _address: u32, _context: Option<&NoContext>, out: &mut T) -> fmt::Result {
     let mut brace_count = 0;
 
     let mut prefixed = false;
@@ -3529,21 +4031,13 @@ fn contextualize_c<T: fmt::Write, Y: YaxColors>(instr: &Instruction, colors: &Y,
         }
     }
 
-    fn write_jmp_operand<T: fmt::Write, Y: YaxColors>(op: Operand, colors: &Y, out: &mut T) -> fmt::Result {
+    fn write_jmp_operand<T: DisplaySink, Y: YaxColors>(op: Operand, colors: &Y, out: &mut T) -> fmt::Result {
         match op {
             Operand::ImmediateI8(rel) => {
-                if rel >= 0 {
-                    write!(out, "$+{}", colors.number(signed_i32_hex(rel as i32)))
-                } else {
-                    write!(out, "${}", colors.number(signed_i32_hex(rel as i32)))
-                }
+                write_rel(colors, out, rel as i32)
             }
             Operand::ImmediateI32(rel) => {
-                if rel >= 0 {
-                    write!(out, "$+{}", colors.number(signed_i32_hex(rel)))
-                } else {
-                    write!(out, "${}", colors.number(signed_i32_hex(rel)))
-                }
+                write_rel(colors, out, rel)
             }
             other => {
                 write!(out, "{}", other)
@@ -3817,20 +4311,640 @@ fn contextualize_c<T: fmt::Write, Y: YaxColors>(instr: &Instruction, colors: &Y,
     Ok(())
 }
 
+const ATT_DEBUG_REGISTERS: [&str; 16] = [
+    "db0", "db1", "db2", "db3", "db4", "db5", "db6", "db7",
+    "db8", "db9", "db10", "db11", "db12", "db13", "db14", "db15",
+];
+
+/// render `reg` as an AT&T-style register operand, like `%rax`. debug registers are `%db0`
+/// through `%db15`, as gnu `objdump` writes them.
+fn write_att_reg<T: DisplaySink>(reg: &RegSpec, out: &mut T) -> fmt::Result {
+    out.write_str("%")?;
+    if reg.bank == RegisterBank::DR {
+        return out.write_register(*reg, ATT_DEBUG_REGISTERS[reg.num as usize & 0xf]);
+    }
+    write_reg(out, reg)
+}
+
+fn write_att_mask<T: DisplaySink>(mask: &RegSpec, out: &mut T) -> fmt::Result {
+    out.write_str("{")?;
+    write_att_reg(mask, out)?;
+    out.write_str("}")
+}
+
+/// render the parenthesized `(base,index,scale)` part of an AT&T memory operand, preceded by a
+/// displacement if there is one. a displacement of zero is written as `0x0`.
+fn write_att_mem<T: DisplaySink, Y: YaxColors>(colors: &Y, base: Option<&RegSpec>, index: Option<(&RegSpec, u8)>, disp: Option<i32>, out: &mut T) -> fmt::Result {
+    if let Some(disp) = disp {
+        out.write_displacement(disp, &colors.number(signed_i32_hex(disp)))?;
+    }
+    out.write_str("(")?;
+    if let Some(base) = base {
+        write_att_reg(base, out)?;
+    }
+    if let Some((index, scale)) = index {
+        out.write_str(",")?;
+        write_att_reg(index, out)?;
+        // 16-bit addressing has no scale, so don't pretend it does.
+        if index.bank != RegisterBank::W {
+            write!(out, ",{}", colors.number(scale))?;
+        }
+    }
+    out.write_str(")")
+}
+
+/// render a single operand in AT&T syntax: `$`-prefixed immediates, `%`-prefixed registers, and
+/// `disp(base,index,scale)` memory operands.
+fn colorize_att_operand<T: DisplaySink, Y: YaxColors>(op: &Operand, colors: &Y, f: &mut T) -> fmt::Result {
+    match *op {
+        Operand::ImmediateU8(imm) => {
+            f.write_str("$")?;
+            f.write_immediate(imm as i64, &colors.number(u8_hex(imm)))
+        }
+        Operand::ImmediateI8(imm) => {
+            f.write_str("$")?;
+            f.write_immediate(imm as i64, &colors.number(signed_i8_hex(imm)))
+        },
+        Operand::ImmediateU16(imm) => {
+            f.write_str("$")?;
+            f.write_immediate(imm as i64, &colors.number(u16_hex(imm)))
+        }
+        Operand::ImmediateI16(imm) => {
+            f.write_str("$")?;
+            f.write_immediate(imm as i64, &colors.number(signed_i16_hex(imm)))
+        },
+        Operand::ImmediateU32(imm) => {
+            f.write_str("$")?;
+            f.write_immediate(imm as i64, &colors.number(u32_hex(imm)))
+        }
+        Operand::ImmediateI32(imm) => {
+            f.write_str("$")?;
+            f.write_immediate(imm as i64, &colors.number(signed_i32_hex(imm)))
+        },
+        Operand::Register(ref spec) => {
+            write_att_reg(spec, f)
+        }
+        Operand::RegisterMaskMerge(ref spec, ref mask, merge_mode) => {
+            write_att_reg(spec, f)?;
+            if mask.num != 0 {
+                write_att_mask(mask, f)?;
+            }
+            if let MergeMode::Zero = merge_mode {
+                f.write_str("{z}")?;
+            }
+            Ok(())
+        }
+        Operand::RegisterMaskMergeSae(ref spec, ref mask, merge_mode, sae_mode) => {
+            write_att_reg(spec, f)?;
+            if mask.num != 0 {
+                write_att_mask(mask, f)?;
+            }
+            if let MergeMode::Zero = merge_mode {
+                f.write_str("{z}")?;
+            }
+            f.write_str(sae_mode.label())
+        }
+        Operand::RegisterMaskMergeSaeNoround(ref spec, ref mask, merge_mode) => {
+            write_att_reg(spec, f)?;
+            if mask.num != 0 {
+                write_att_mask(mask, f)?;
+            }
+            if let MergeMode::Zero = merge_mode {
+                f.write_str("{z}")?;
+            }
+            f.write_str("{sae}")
+        }
+        Operand::DisplacementU16(imm) => {
+            f.write_address(imm as u64, &colors.address(u16_hex(imm)))
+        }
+        Operand::DisplacementU32(imm) => {
+            f.write_address(imm as u64, &colors.address(u32_hex(imm)))
+        }
+        Operand::AbsoluteFarAddress { segment, address } => {
+            f.write_str("$")?;
+            f.write_immediate(segment as i64, &colors.number(u16_hex(segment)))?;
+            f.write_str(",$")?;
+            f.write_address(address as u64, &colors.address(u32_hex(address)))
+        }
+        Operand::RegDisp(ref spec, disp) => {
+            write_att_mem(colors, Some(spec), None, Some(disp), f)
+        },
+        Operand::RegDeref(ref spec) => {
+            write_att_mem(colors, Some(spec), None, None, f)
+        },
+        Operand::RegScale(ref spec, scale) => {
+            write_att_mem(colors, None, Some((spec, scale)), None, f)
+        },
+        Operand::RegScaleDisp(ref spec, scale, disp) => {
+            write_att_mem(colors, None, Some((spec, scale)), Some(disp), f)
+        },
+        Operand::RegIndexBase(ref base, ref index) => {
+            write_att_mem(colors, Some(base), Some((index, 1)), None, f)
+        }
+        Operand::RegIndexBaseDisp(ref base, ref index, disp) => {
+            write_att_mem(colors, Some(base), Some((index, 1)), Some(disp), f)
+        },
+        Operand::RegIndexBaseScale(ref base, ref index, scale) => {
+            write_att_mem(colors, Some(base), Some((index, scale)), None, f)
+        }
+        Operand::RegIndexBaseScaleDisp(ref base, ref index, scale, disp) => {
+            write_att_mem(colors, Some(base), Some((index, scale)), Some(disp), f)
+        },
+        Operand::RegDispMasked(ref spec, disp, ref mask_reg) => {
+            write_att_mem(colors, Some(spec), None, Some(disp), f)?;
+            write_att_mask(mask_reg, f)
+        },
+        Operand::RegDerefMasked(ref spec, ref mask_reg) => {
+            write_att_mem(colors, Some(spec), None, None, f)?;
+            write_att_mask(mask_reg, f)
+        },
+        Operand::RegScaleMasked(ref spec, scale, ref mask_reg) => {
+            write_att_mem(colors, None, Some((spec, scale)), None, f)?;
+            write_att_mask(mask_reg, f)
+        },
+        Operand::RegScaleDispMasked(ref spec, scale, disp, ref mask_reg) => {
+            write_att_mem(colors, None, Some((spec, scale)), Some(disp), f)?;
+            write_att_mask(mask_reg, f)
+        },
+        Operand::RegIndexBaseMasked(ref base, ref index, ref mask_reg) => {
+            write_att_mem(colors, Some(base), Some((index, 1)), None, f)?;
+            write_att_mask(mask_reg, f)
+        }
+        Operand::RegIndexBaseDispMasked(ref base, ref index, disp, ref mask_reg) => {
+            write_att_mem(colors, Some(base), Some((index, 1)), Some(disp), f)?;
+            write_att_mask(mask_reg, f)
+        },
+        Operand::RegIndexBaseScaleMasked(ref base, ref index, scale, ref mask_reg) => {
+            write_att_mem(colors, Some(base), Some((index, scale)), None, f)?;
+            write_att_mask(mask_reg, f)
+        }
+        Operand::RegIndexBaseScaleDispMasked(ref base, ref index, scale, disp, ref mask_reg) => {
+            write_att_mem(colors, Some(base), Some((index, scale)), Some(disp), f)?;
+            write_att_mask(mask_reg, f)
+        },
+        Operand::Nothing => { Ok(()) },
+    }
+}
+
+/// instructions whose AT&T mnemonic takes a `b`/`w`/`l`/`q` suffix for its operand size.
+fn att_sized_opcode(opcode: Opcode) -> bool {
+    matches!(opcode,
+        Opcode::ADD | Opcode::OR | Opcode::ADC | Opcode::SBB |
+        Opcode::AND | Opcode::SUB | Opcode::XOR | Opcode::CMP |
+        Opcode::MOV | Opcode::TEST | Opcode::XCHG | Opcode::LEA |
+        Opcode::INC | Opcode::DEC | Opcode::NOT | Opcode::NEG |
+        Opcode::MUL | Opcode::IMUL | Opcode::DIV | Opcode::IDIV |
+        Opcode::ROL | Opcode::ROR | Opcode::RCL | Opcode::RCR |
+        Opcode::SHL | Opcode::SHR | Opcode::SAL | Opcode::SAR |
+        Opcode::SHLD | Opcode::SHRD |
+        Opcode::BT | Opcode::BTS | Opcode::BTR | Opcode::BTC |
+        Opcode::PUSH | Opcode::POP | Opcode::NOP |
+        Opcode::XADD | Opcode::CMPXCHG |
+        Opcode::MOVS | Opcode::CMPS | Opcode::LODS | Opcode::STOS |
+        Opcode::SCAS | Opcode::INS | Opcode::OUTS,
+    )
+}
+
+fn att_size_suffix(size: u8) -> &'static str {
+    match size {
+        1 => "b",
+        2 => "w",
+        4 => "l",
+        8 => "q",
+        _ => "",
+    }
+}
+
+fn is_gp_register(op: &Operand) -> bool {
+    if let Operand::Register(reg) = op {
+        matches!(reg.bank, RegisterBank::D | RegisterBank::W | RegisterBank::B)
+    } else {
+        false
+    }
+}
+
+/// the size suffix for `instr`'s mnemonic, in AT&T syntax. unless `always` is set, this is only
+/// non-empty when operand size is not implied by a register operand.
+fn att_suffix(instr: &Instruction, always: bool) -> &'static str {
+    let mut has_memory = false;
+    let mut sized_by_register = None;
+    for i in 0..instr.operand_count {
+        let op = Operand::from_spec(instr, instr.operands[i as usize]);
+        if op.is_memory() {
+            has_memory = true;
+        } else if is_gp_register(&op) && sized_by_register.is_none() {
+            // the count in `shl eax, cl` does not size the operation.
+            let is_shift_count = i == 1 && [
+                Opcode::ROL, Opcode::ROR, Opcode::RCL, Opcode::RCR,
+                Opcode::SHL, Opcode::SHR, Opcode::SAL, Opcode::SAR,
+            ].contains(&instr.opcode);
+            // nor does `dx` in `ins`/`outs`.
+            let is_port = [Opcode::INS, Opcode::OUTS].contains(&instr.opcode);
+            if !is_shift_count && !is_port {
+                if let Some(width) = op.width() {
+                    sized_by_register = Some(width);
+                }
+            }
+        }
+    }
+
+    let name = instr.opcode.name();
+    if has_memory && name.starts_with('f') {
+        // x87 memory operands select a suffix by the type of value loaded or stored.
+        if name.starts_with("fi") {
+            return match instr.mem_size {
+                2 => "s",
+                4 => "l",
+                8 => "ll",
+                _ => "",
+            };
+        } else if instr.opcode != Opcode::FBLD && instr.opcode != Opcode::FBSTP {
+            return match instr.mem_size {
+                4 => "s",
+                8 => "l",
+                10 => "t",
+                _ => "",
+            };
+        }
+        return "";
+    }
+
+    if has_memory && sized_by_register.is_none() && [Opcode::CVTSI2SS, Opcode::CVTSI2SD, Opcode::VCVTSI2SS, Opcode::VCVTSI2SD, Opcode::VCVTUSI2SS, Opcode::VCVTUSI2SD].contains(&instr.opcode) {
+        return att_size_suffix(instr.mem_size);
+    }
+
+    if !att_sized_opcode(instr.opcode) {
+        return "";
+    }
+
+    // a move to or from a segment register is sized by it, like a general-purpose register.
+    let moves_segment = instr.opcode == Opcode::MOV && (0..instr.operand_count).any(|i| {
+        matches!(Operand::from_spec(instr, instr.operands[i as usize]), Operand::Register(reg) if reg.bank == RegisterBank::S)
+    });
+    if moves_segment && !always {
+        return "";
+    }
+
+    // other pushes and pops are sized by the stack, so gnu `objdump` only suffixes a 16-bit one.
+    if [Opcode::PUSH, Opcode::POP].contains(&instr.opcode) && sized_by_register.is_none() {
+        return if instr.prefixes.operand_size() {
+            "w"
+        } else if always {
+            "l"
+        } else {
+            ""
+        };
+    }
+
+    match sized_by_register {
+        Some(width) if always => att_size_suffix(width),
+        Some(_) => "",
+        None if has_memory => att_size_suffix(instr.mem_size),
+        None => "",
+    }
+}
+
+/// the AT&T mnemonic for `instr`, for the handful of instructions where it is not the intel
+/// mnemonic with an optional size suffix.
+fn att_special_mnemonic(instr: &Instruction) -> Option<&'static str> {
+    let src_size = |instr: &Instruction| {
+        let src = Operand::from_spec(instr, instr.operands[1]);
+        if src.is_memory() { instr.mem_size } else { src.width().unwrap_or(0) }
+    };
+    let dest_size = |instr: &Instruction| Operand::from_spec(instr, instr.operands[0]).width().unwrap_or(0);
+
+    let name = match instr.opcode {
+        Opcode::CBW => "cbtw",
+        Opcode::CWDE => "cwtl",
+        Opcode::CDQE => "cltq",
+        Opcode::CWD => "cwtd",
+        Opcode::CDQ => "cltd",
+        Opcode::CQO => "cqto",
+        Opcode::CALLF => if instr.prefixes.operand_size() { "lcallw" } else { "lcall" },
+        Opcode::JMPF => if instr.prefixes.operand_size() { "ljmpw" } else { "ljmp" },
+        Opcode::PUSHA if instr.prefixes.operand_size() => "pushaw",
+        Opcode::POPA if instr.prefixes.operand_size() => "popaw",
+        Opcode::RETF => if instr.prefixes.operand_size() { "lretw" } else { "lret" },
+        Opcode::RETURN if instr.prefixes.operand_size() => "retw",
+        Opcode::IRET => "iretw",
+        Opcode::IRETD => "iret",
+        Opcode::WAIT => "fwait",
+        Opcode::PUSHF if instr.prefixes.operand_size() => "pushfw",
+        Opcode::POPF if instr.prefixes.operand_size() => "popfw",
+        Opcode::ENTER if instr.prefixes.operand_size() => "enterw",
+        Opcode::LEAVE if instr.prefixes.operand_size() => "leavew",
+        Opcode::MOVZX | Opcode::MOVSX | Opcode::MOVSXD => {
+            let zx = instr.opcode == Opcode::MOVZX;
+            match (src_size(instr), dest_size(instr)) {
+                (1, 2) => if zx { "movzbw" } else { "movsbw" },
+                (1, 4) => if zx { "movzbl" } else { "movsbl" },
+                (1, 8) => if zx { "movzbq" } else { "movsbq" },
+                (2, 2) => if zx { "movzww" } else { "movsww" },
+                (2, 4) => if zx { "movzwl" } else { "movswl" },
+                (2, 8) => if zx { "movzwq" } else { "movswq" },
+                (4, 2) => "movslw",
+                (4, 4) => "movsll",
+                (4, 8) => "movslq",
+                _ => { return None; }
+            }
+        }
+        // the infamous AT&T quirk: when the destination is `st(i)` rather than the implied
+        // `st(0)`, the `r` forms of x87 subtraction and division swap mnemonics.
+        Opcode::FSUB | Opcode::FSUBR | Opcode::FSUBP | Opcode::FSUBRP |
+        Opcode::FDIV | Opcode::FDIVR | Opcode::FDIVP | Opcode::FDIVRP => {
+            if instr.operands[0] != OperandSpec::RegMMM {
+                return None;
+            }
+            match instr.opcode {
+                Opcode::FSUB => "fsubr",
+                Opcode::FSUBR => "fsub",
+                Opcode::FSUBP => "fsubrp",
+                Opcode::FSUBRP => "fsubp",
+                Opcode::FDIV => "fdivr",
+                Opcode::FDIVR => "fdiv",
+                Opcode::FDIVP => "fdivrp",
+                _ => "fdivp",
+            }
+        }
+        _ => { return None; }
+    };
+    Some(name)
+}
+
+/// a sign-extended immediate operand of `instr` as AT&T syntax writes it, as gnu `objdump` does:
+/// the unsigned value of the operation's size, like `$0xfffffff0` for `add eax, -0x10`. the size
+/// is that of the first register or memory operand, or of the memory `instr` accesses otherwise.
+fn att_immediate(instr: &Instruction, op: Operand) -> Operand {
+    const SIGN_EXTENDS_IMM8: [Opcode; 10] = [
+        Opcode::ADD, Opcode::OR, Opcode::ADC, Opcode::SBB,
+        Opcode::AND, Opcode::SUB, Opcode::XOR, Opcode::CMP,
+        Opcode::IMUL, Opcode::PUSH,
+    ];
+
+    let value = match op {
+        // elsewhere, like the counts of `shrd` or `pinsrw`, an 8-bit immediate is just a byte.
+        Operand::ImmediateI8(imm) if !SIGN_EXTENDS_IMM8.contains(&instr.opcode) => {
+            return Operand::ImmediateU8(imm as u8);
+        }
+        Operand::ImmediateI8(imm) => imm as i32,
+        Operand::ImmediateI16(imm) => imm as i32,
+        Operand::ImmediateI32(imm) => imm,
+        other => { return other; }
+    };
+    let mut size = if instr.opcode == Opcode::PUSH {
+        if instr.prefixes.operand_size() { 2 } else { 4 }
+    } else {
+        instr.mem_size
+    };
+    for i in 0..instr.operand_count {
+        match Operand::from_spec(instr, instr.operands[i as usize]) {
+            Operand::Register(reg) => {
+                size = reg.width();
+                break;
+            }
+            x if x.is_memory() => {
+                break;
+            }
+            _ => {}
+        }
+    }
+    match size {
+        1 => Operand::ImmediateU8(value as u8),
+        2 => Operand::ImmediateU16(value as u16),
+        4 => Operand::ImmediateU32(value as u32),
+        _ => op,
+    }
+}
+
+/// `op` with the zero displacement its encoding carries, which decoding folds away, written back
+/// out: gnu `objdump` writes `nopl 0x0(%rax)`, not `nopl (%rax)`.
+fn att_zero_disp(op: Operand) -> Operand {
+    match op {
+        Operand::RegDeref(base) => Operand::RegDisp(base, 0),
+        Operand::RegScale(index, scale) => Operand::RegScaleDisp(index, scale, 0),
+        Operand::RegIndexBase(base, index) => Operand::RegIndexBaseDisp(base, index, 0),
+        Operand::RegIndexBaseScale(base, index, scale) => Operand::RegIndexBaseScaleDisp(base, index, scale, 0),
+        Operand::RegDerefMasked(base, mask) => Operand::RegDispMasked(base, 0, mask),
+        Operand::RegScaleMasked(index, scale, mask) => Operand::RegScaleDispMasked(index, scale, 0, mask),
+        Operand::RegIndexBaseMasked(base, index, mask) => Operand::RegIndexBaseDispMasked(base, index, 0, mask),
+        Operand::RegIndexBaseScaleMasked(base, index, scale, mask) => Operand::RegIndexBaseScaleDispMasked(base, index, scale, 0, mask),
+        other => other,
+    }
+}
+
+/// the segment written before operand `i` of `instr`. gnu `objdump` names the segment of every
+/// memory operand of a string instruction: `%es` for the one addressed by `edi`, and the data
+/// segment for the one addressed by `esi`.
+fn att_segment_for_op(instr: &Instruction, i: u8, op: &Operand) -> Option<Segment> {
+    if [Opcode::CMPS, Opcode::SCAS, Opcode::INS, Opcode::OUTS].contains(&instr.opcode) {
+        match op {
+            Operand::RegDeref(reg) if reg.num == 7 => Some(Segment::ES),
+            Operand::RegDeref(_) => Some(instr.prefixes.segment),
+            _ => None,
+        }
+    } else {
+        instr.segment_override_for_op(i)
+    }
+}
+
+/// is `op` the `dx` port number of an `in`, `out`, `ins` or `outs`? AT&T syntax writes it as
+/// `(%dx)`.
+fn is_att_port(instr: &Instruction, op: &Operand) -> bool {
+    if ![Opcode::IN, Opcode::OUT, Opcode::INS, Opcode::OUTS].contains(&instr.opcode) {
+        return false;
+    }
+    matches!(op, Operand::Register(reg) if reg.num == 2 && (reg.bank == RegisterBank::W || reg.bank == RegisterBank::B))
+}
+
+fn contextualize_att<T: DisplaySink, Y: YaxColors>(instr: &Instruction, colors: &Y, _address: u32, _context: Option<&NoContext>, options: &DisplayOptions, out: &mut T) -> fmt::Result {
+    if instr.xacquire() {
+        out.write_str("xacquire ")?;
+    }
+    if instr.xrelease() {
+        out.write_str("xrelease ")?;
+    }
+    if instr.prefixes.lock() {
+        out.write_str("lock ")?;
+    }
+
+    if instr.prefixes.rep_any() {
+        if [Opcode::MOVS, Opcode::LODS, Opcode::STOS, Opcode::INS, Opcode::OUTS].contains(&instr.opcode) {
+            if instr.prefixes.rep() {
+                write!(out, "rep ")?;
+            } else if instr.prefixes.repnz() {
+                write!(out, "repnz ")?;
+            }
+        } else if [Opcode::CMPS, Opcode::SCAS].contains(&instr.opcode) {
+            // the comparisons repeat while equal, or while not equal.
+            if instr.prefixes.rep() {
+                write!(out, "repz ")?;
+            } else if instr.prefixes.repnz() {
+                write!(out, "repnz ")?;
+            }
+        }
+    }
+
+    if options.aliases.repz_ret && instr.opcode == Opcode::RETURN && instr.prefixes.rep() {
+        out.write_str("repz ")?;
+    }
+
+    // gnu `objdump` writes a `nop` with an operand-size prefix as the `xchg %ax,%ax` it encodes.
+    let is_xchg_ax = instr.opcode == Opcode::NOP && instr.operand_count == 0 &&
+        !instr.prefixes.rep_any() && instr.prefixes.operand_size();
+    if is_xchg_nop(instr, &options.aliases) || is_xchg_ax {
+        let reg = Operand::Register(xchg_nop_register(instr));
+        out.write_opcode(instr.opcode, "xchg")?;
+        out.write_str(" ")?;
+        colorize_att_operand(&reg, colors, out)?;
+        out.write_str(",")?;
+        return colorize_att_operand(&reg, colors, out);
+    }
+
+    if let Some(name) = att_special_mnemonic(instr) {
+        out.write_opcode(instr.opcode, name)?;
+    } else if let Some(alias) = alias_mnemonic(instr, &options.aliases) {
+        write_suffixed_opcode(out, instr.opcode, alias, att_suffix(instr, options.att_suffixes))?;
+    } else if instr.opcode == Opcode::SAL {
+        // gnu `objdump` does not distinguish the `/6` encoding of `shl`.
+        write_suffixed_opcode(out, instr.opcode, "shl", att_suffix(instr, options.att_suffixes))?;
+    } else {
+        write_suffixed_opcode(out, instr.opcode, instr.opcode.name(), att_suffix(instr, options.att_suffixes))?;
+    }
+
+    if options.branch_hints {
+        write_branch_hint(instr, out)?;
+    }
+
+    // with no address to resolve relative branches against, these are rendered the same way as
+    // in intel syntax: relative to the end of the instruction.
+    if instr.opcode == Opcode::XBEGIN {
+        out.write_str(" ")?;
+        return write_rel(colors, out, instr.imm as i32);
+    }
+
+    // `xlat` has no explicit operands, but gnu `objdump` writes the table it reads from.
+    if instr.opcode == Opcode::XLAT {
+        let table = if instr.prefixes.address_size() { RegSpec::w(3) } else { RegSpec::d(3) };
+        write!(out, " %{}:", instr.prefixes.segment)?;
+        return colorize_att_operand(&Operand::RegDeref(table), colors, out);
+    }
+
+    if instr.operand_count == 0 {
+        return Ok(());
+    }
+
+    out.write_str(" ")?;
+
+    const RELATIVE_BRANCHES: [Opcode; 22] = [
+        Opcode::JMP, Opcode::JECXZ, Opcode::CALL,
+        Opcode::LOOP, Opcode::LOOPZ, Opcode::LOOPNZ,
+        Opcode::JO, Opcode::JNO,
+        Opcode::JB, Opcode::JNB,
+        Opcode::JZ, Opcode::JNZ,
+        Opcode::JNA, Opcode::JA,
+        Opcode::JS, Opcode::JNS,
+        Opcode::JP, Opcode::JNP,
+        Opcode::JL, Opcode::JGE,
+        Opcode::JLE, Opcode::JG,
+    ];
+
+    if RELATIVE_BRANCHES.contains(&instr.opcode) {
+        match Operand::from_spec(instr, instr.operands[0]) {
+            Operand::ImmediateI8(rel) if instr.operands[0] == OperandSpec::ImmI8 => {
+                return write_rel(colors, out, rel as i32);
+            }
+            Operand::ImmediateI32(rel) if instr.operands[0] == OperandSpec::ImmI32 => {
+                return write_rel(colors, out, rel);
+            }
+            _ => {}
+        }
+    }
+
+    // indirect branches mark their target operand with `*`.
+    if [Opcode::CALL, Opcode::JMP, Opcode::CALLF, Opcode::JMPF].contains(&instr.opcode) {
+        let x = Operand::from_spec(instr, instr.operands[0]);
+        if x.is_memory() || matches!(x, Operand::Register(_)) {
+            out.write_str("*")?;
+        }
+    }
+
+    // AT&T syntax lists operands in the reverse of intel order, with the notable exceptions of
+    // `enter` and `bound`. gnu `objdump` also keeps the string comparisons and the one-byte
+    // `xchg` with the accumulator in intel order.
+    let reverse = !(matches!(instr.opcode, Opcode::ENTER | Opcode::BOUND | Opcode::CMPS | Opcode::SCAS) ||
+        (instr.opcode == Opcode::XCHG && instr.operands[0] == OperandSpec::RegRRR));
+    // x87 instructions with a memory operand, and those that gas takes a single register for,
+    // leave the `st(0)` they operate on implied.
+    let implied_st0 = instr.opcode.name().starts_with('f') && (
+        instr.operands[..instr.operand_count as usize].iter().any(|spec| spec.is_memory()) ||
+        [
+            Opcode::FLD, Opcode::FST, Opcode::FSTP, Opcode::FXCH,
+            Opcode::FCOM, Opcode::FCOMP, Opcode::FUCOM, Opcode::FUCOMP,
+        ].contains(&instr.opcode)
+    );
+    let mut first = true;
+    for idx in 0..instr.operand_count {
+        let i = if reverse { instr.operand_count - 1 - idx } else { idx };
+        let mut x = att_immediate(instr, Operand::from_spec(instr, instr.operands[i as usize]));
+        if instr.zero_disp {
+            x = att_zero_disp(x);
+        }
+        let is_implied_st0 = x == Operand::Register(RegSpec::st(0)) &&
+            instr.operands[i as usize] != OperandSpec::RegMMM;
+        if implied_st0 && is_implied_st0 {
+            continue;
+        }
+        if !first {
+            out.write_str(",")?;
+        }
+        first = false;
+        // where it is written, an implied `st(0)` is just `%st`.
+        if is_implied_st0 {
+            out.write_str("%")?;
+            out.write_register(RegSpec::st(0), "st")?;
+            continue;
+        }
+        if let Some(prefix) = att_segment_for_op(instr, i, &x) {
+            write!(out, "%{}:", prefix)?;
+        }
+        if is_att_port(instr, &x) {
+            out.write_str("(%dx)")?;
+            continue;
+        }
+        colorize_att_operand(&x, colors, out)?;
+        if x.is_memory() && i > 0 {
+            if let Some(scale) = broadcast_scale(instr, i) {
+                write!(out, "{{1to{}}}", scale)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 impl <'instr, T: fmt::Write, Y: YaxColors> ShowContextual<u32, NoContext, T, Y> for InstructionDisplayer<'instr> {
-    fn contextualize(&self, colors: &Y, address: u32, context: Option<&NoContext>, out: &mut T) -> fmt::Result {
+    fn contextualize(&self, colors: &Y, address: u32, _context: Option<&NoContext>, out: &mut T) -> fmt::Result {
+        self.write_sink(colors, address, &mut PlainSink(out))
+    }
+}
+
+impl<'instr> InstructionDisplayer<'instr> {
+    fn write_sink<T: DisplaySink, Y: YaxColors>(&self, colors: &Y, address: u32, out: &mut T) -> fmt::Result {
         let InstructionDisplayer {
             instr,
-            style,
+            options,
         } = self;
+        let context = Some(&NoContext);
 
-        match style {
-            DisplayStyle::Intel => {
-                contextualize_intel(instr, colors, address, context, out)
+        match options.syntax {
+            Syntax::Intel => {
+                contextualize_intel(instr, colors, address, context, options, out)
             }
-            DisplayStyle::C => {
+            Syntax::C => {
                 contextualize_c(instr, colors, address, context, out)
             }
+            Syntax::Att => {
+                contextualize_att(instr, colors, address, context, options, out)
+            }
         }
     }
 }
@@ -3895,3 +5009,42 @@ impl <T: fmt::Write, Y: YaxColors> ShowContextual<u64, [Option<alloc::string::St
         Ok(())
     }
 }
+
+include!("../shared/display_sink.in");
+include!("../shared/symbols.in");
+/// the width of a displacement the memory operand `spec` can be encoded with to hold a
+/// relocation, and the spec that writes that displacement even when it is zero.
+fn relocatable_displacement(instr: &Instruction, spec: OperandSpec) -> Option<(u8, OperandSpec)> {
+    let spec = match spec {
+        OperandSpec::DispU16 => { return Some((2, spec)); }
+        OperandSpec::DispU32 => { return Some((4, spec)); }
+        OperandSpec::RegDisp |
+        OperandSpec::RegIndexBaseDisp |
+        OperandSpec::RegScaleDisp |
+        OperandSpec::RegIndexBaseScaleDisp |
+        OperandSpec::RegDisp_mask |
+        OperandSpec::RegIndexBaseDisp_mask |
+        OperandSpec::RegScaleDisp_mask |
+        OperandSpec::RegIndexBaseScaleDisp_mask => spec,
+        OperandSpec::Deref => OperandSpec::RegDisp,
+        OperandSpec::RegIndexBase => OperandSpec::RegIndexBaseDisp,
+        OperandSpec::RegScale => OperandSpec::RegScaleDisp,
+        OperandSpec::RegIndexBaseScale => OperandSpec::RegIndexBaseScaleDisp,
+        OperandSpec::Deref_mask => OperandSpec::RegDisp_mask,
+        OperandSpec::RegIndexBase_mask => OperandSpec::RegIndexBaseDisp_mask,
+        OperandSpec::RegScale_mask => OperandSpec::RegScaleDisp_mask,
+        OperandSpec::RegIndexBaseScale_mask => OperandSpec::RegIndexBaseScaleDisp_mask,
+        _ => { return None; }
+    };
+    // 16-bit addresses have 16-bit displacements.
+    if instr.prefixes.address_size() {
+        Some((2, spec))
+    } else {
+        Some((4, spec))
+    }
+}
+
+include!("../shared/relocs.in");
+include!("../shared/listing.in");
+include!("../shared/operand_display.in");
+include!("../shared/mnemonics.in");
@@ -0,0 +1,16 @@
+use crate::protected_mode::{AccessKind, DescriptorTable, Instruction, Opcode, Operand};
+
+const FAR_POINTER_LOADS: &[Opcode] = &[Opcode::LDS, Opcode::LES, Opcode::LSS, Opcode::LFS, Opcode::LGS];
+
+// with a 16-bit operand size only the low 24 bits of the base are used, but all four bytes are
+// still read or written.
+const PSEUDO_DESCRIPTOR_BASE_WIDTH: u8 = 4;
+
+fn immediate_far_pointer(op: &Operand) -> Option<(u16, u32)> {
+    match *op {
+        Operand::AbsoluteFarAddress { segment, address } => Some((segment, address)),
+        _ => None,
+    }
+}
+
+include!("../shared/far.in");
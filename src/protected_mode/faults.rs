@@ -0,0 +1,18 @@
+use crate::protected_mode::{Category, FaultClasses, Opcode};
+
+fn mode_fault_classes(opcode: Opcode) -> Option<FaultClasses> {
+    let faults = match opcode {
+        // `aam` divides by its immediate.
+        Opcode::AAM => FaultClasses::DE,
+        Opcode::AAA | Opcode::AAD | Opcode::AAS | Opcode::DAA | Opcode::DAS => FaultClasses::empty(),
+        // `bound` with a register operand is `#UD`.
+        Opcode::BOUND => FaultClasses::BR | FaultClasses::UD | FaultClasses::MEMORY,
+        // `arpl` is `#UD` outside of protected mode.
+        Opcode::ARPL => FaultClasses::UD | FaultClasses::MEMORY,
+        Opcode::LDS | Opcode::LES => FaultClasses::NP | FaultClasses::MEMORY,
+        _ => { return None; }
+    };
+    Some(faults)
+}
+
+include!("../shared/faults.in");
@@ -0,0 +1,5 @@
+use crate::{OperandRegisters, X86Instruction, X86Operand};
+use crate::protected_mode::{Instruction, Operand, RegSpec};
+use crate::protected_mode::scan::immediate_value;
+
+include!("../shared/generic.in");
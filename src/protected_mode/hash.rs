@@ -0,0 +1,14 @@
+use crate::HashOptions;
+use crate::protected_mode::{Instruction, MergeMode, Opcode, Operand, RegSpec, SaeMode};
+
+fn semantic_mode_operand(op: &Operand) -> SemanticOperand {
+    match *op {
+        Operand::DisplacementU16(disp) => SemanticOperand::Memory(None, None, disp as i64, None),
+        Operand::AbsoluteFarAddress { segment, address } => {
+            SemanticOperand::FarAddress(((segment as u64) << 32) | address as u64)
+        }
+        _ => unreachable!("operand is handled by semantic_operand"),
+    }
+}
+
+include!("../shared/hash.in");
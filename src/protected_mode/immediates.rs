@@ -0,0 +1,28 @@
+use crate::{ImmExtension, ImmValue};
+use crate::protected_mode::{Instruction, Operand, OperandSpec};
+
+impl Operand {
+    /// this operand's immediate, with the number of bytes it is encoded in and how it is
+    /// extended, if this operand is an immediate. `ImmediateI*` variants are sign-extended and
+    /// `ImmediateU*` variants zero-extended.
+    pub fn immediate_value(&self) -> Option<ImmValue> {
+        let (size, extension, bits) = match *self {
+            Operand::ImmediateI8(imm) => (1, ImmExtension::Sign, imm as u64),
+            Operand::ImmediateU8(imm) => (1, ImmExtension::Zero, imm as u64),
+            Operand::ImmediateI16(imm) => (2, ImmExtension::Sign, imm as u64),
+            Operand::ImmediateU16(imm) => (2, ImmExtension::Zero, imm as u64),
+            Operand::ImmediateI32(imm) => (4, ImmExtension::Sign, imm as u64),
+            Operand::ImmediateU32(imm) => (4, ImmExtension::Zero, imm as u64),
+            _ => { return None; }
+        };
+        Some(ImmValue::new(size, extension, bits))
+    }
+}
+
+/// `imm` as operand `spec` of `instr` encoded it. 32-bit code has no immediates wider than
+/// their encoding, so this is always `imm`.
+fn encoded_immediate(_instr: &Instruction, _spec: OperandSpec, imm: ImmValue) -> ImmValue {
+    imm
+}
+
+include!("../shared/immediates.in");
@@ -0,0 +1,3 @@
+use crate::protected_mode::{DecodeError, InstDecoder, Instruction};
+
+include!("../shared/matrix.in");
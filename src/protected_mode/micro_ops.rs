@@ -0,0 +1,15 @@
+use crate::protected_mode::{AccessKind, Category, Instruction, Opcode, Operand, RegSpec, X86Operand};
+
+fn stack_pointer() -> RegSpec {
+    RegSpec::esp()
+}
+
+fn flags_register() -> RegSpec {
+    RegSpec::eflags()
+}
+
+fn tests_counter(opcode: Opcode) -> bool {
+    matches!(opcode, Opcode::LOOP | Opcode::LOOPZ | Opcode::LOOPNZ | Opcode::JECXZ)
+}
+
+include!("../shared/micro_ops.in");
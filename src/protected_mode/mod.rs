@@ -2,12 +2,126 @@ mod vex;
 mod evex;
 #[cfg(feature = "fmt")]
 mod display;
+mod operands;
+mod dataflow;
+mod flags;
+mod category;
+mod faults;
+mod branch;
+mod relocate;
+mod iter;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "micro-ops")]
+mod micro_ops;
+mod bytes;
+mod xop;
+mod length;
+mod isa;
+mod address;
+mod cpuid;
+mod scan;
+mod hash;
+mod nop;
+mod privilege;
+mod decorations;
+mod x87;
+mod synthesize;
+mod aliases;
+mod rep;
+mod opcode_id;
+mod constraints;
+mod xsave;
+mod matrix;
+mod far;
+mod summary;
+mod prefix_effects;
+mod convert;
+mod generic;
+mod sgx;
+mod immediates;
+mod tsx;
+#[cfg(feature = "msr-names")]
+mod msr;
+#[cfg(feature = "fmt")]
+mod shape;
+#[cfg(all(feature = "fmt", feature = "colors"))]
+mod colors;
+#[cfg(feature = "uarch-data")]
+mod perf;
+#[cfg(feature = "opcode-descriptions")]
+mod descriptions;
 pub mod uarch;
+#[cfg(feature = "capstone-compat")]
+pub mod capstone_compat;
+#[cfg(feature = "wasm")]
+pub(crate) mod wasm;
+#[cfg(feature = "fuzz-oracle")]
+pub mod fuzz_oracle;
+#[cfg(feature = "testgen")]
+pub mod testgen;
 
 pub use crate::MemoryAccessSize;
+pub use crate::AccessKind;
+pub use crate::WriteBehavior;
+pub use crate::DescriptorTable;
+pub use crate::AddressingForm;
+pub use crate::Flags;
+pub use crate::Category;
+pub use crate::FaultClasses;
+pub use crate::RepSemantics;
+pub use crate::IsaExtension;
+pub use crate::ErrorRecovery;
+pub use crate::HashOptions;
+pub use crate::SynthesizeError;
+pub use crate::ConversionError;
+pub use crate::RelocateError;
+pub use crate::{OperandRegisters, X86Instruction, X86Operand};
+pub use crate::SgxFunction;
+pub use crate::{ImmExtension, ImmValue};
+pub use crate::{HleHint, RtmEffect};
+pub use crate::BranchHint;
+pub use crate::NopStrictness;
+pub use crate::{ElementType, VectorShape};
+pub use crate::PrivilegeLevel;
+pub use crate::{XsaveComponent, XsaveFormat, XsaveLayout};
+pub use crate::{Encoding, EncodingInfo};
+pub use crate::{PrefixEffect, PrefixEffects};
+pub use crate::{FlowClass, InstructionSummary};
+pub use crate::DecodeHooks;
+pub use crate::{Cpu, CpuidLeaf};
+#[cfg(feature = "uarch-data")]
+pub use crate::{PerfInfo, Uarch};
 
 #[cfg(feature = "fmt")]
-pub use self::display::{DisplayStyle, InstructionDisplayer};
+pub use self::display::{DisplayListingLine, DisplayOptions, DisplaySink, DisplayStyle, InstructionDisplayer, Opcodes, OperandDisplayer, RelocDisplayer, SymbolDisplayer};
+#[cfg(feature = "fmt")]
+pub use crate::SymbolResolver;
+#[cfg(feature = "fmt")]
+pub use crate::MnemonicAliases;
+#[cfg(feature = "fmt")]
+pub use crate::RelocResolver;
+#[cfg(all(feature = "fmt", feature = "colors"))]
+pub use self::colors::ColorDisplayer;
+#[cfg(all(feature = "fmt", feature = "colors"))]
+pub use crate::Palette;
+pub use self::operands::{ImplicitOperands, MemoryAccess, MemoryAccesses, Operands};
+pub use self::dataflow::RegList;
+#[cfg(feature = "micro-ops")]
+pub use self::micro_ops::{MicroOp, MicroOps, Temp, Temps};
+pub use self::branch::BranchTarget;
+pub use self::relocate::RelocatedInstruction;
+pub use self::iter::{DecodeIterator, Decoded};
+pub use self::scan::{InstructionPattern, OperandPattern, PatternScan};
+pub use self::decorations::{Avx512Decorations, EvexDispInfo, Masking};
+pub use self::aliases::RegisterAliases;
+pub use self::constraints::OperandConstraint;
+pub use self::matrix::DecodeMatrix;
+pub use self::far::{FarPointer, PseudoDescriptor};
+#[cfg(all(feature = "fmt", feature = "msr-names"))]
+pub use self::msr::MsrDisplayer;
+pub use self::address::RegValueSource;
+pub use self::bytes::{DecodeErrorDetail, DecodeErrorReason, DecodeState, ResumableDecode};
 
 use core::cmp::PartialEq;
 use crate::safer_unchecked::unreachable_kinda_unchecked as unreachable_unchecked;
@@ -51,11 +165,12 @@ impl Hash for RegSpec {
 
 /// the condition for a conditional instruction.
 ///
-/// these are only obtained through [`Opcode::condition()`]:
+/// these are only obtained through [`Opcode::condition()`] or [`Instruction::condition()`]:
 /// ```
 /// use yaxpeax_x86::long_mode::{Opcode, ConditionCode};
 ///
 /// assert_eq!(Opcode::JB.condition(), Some(ConditionCode::B));
+/// assert_eq!(Opcode::JB.condition().unwrap().invert(), ConditionCode::AE);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ConditionCode {
@@ -77,6 +192,31 @@ pub enum ConditionCode {
     LE,
 }
 
+impl ConditionCode {
+    /// the condition that holds exactly when this one does not: `NZ` for `Z`, `AE` for `B`, and
+    /// so on. flipping a conditional branch is replacing its condition with this.
+    pub fn invert(&self) -> ConditionCode {
+        match self {
+            ConditionCode::O => ConditionCode::NO,
+            ConditionCode::NO => ConditionCode::O,
+            ConditionCode::B => ConditionCode::AE,
+            ConditionCode::AE => ConditionCode::B,
+            ConditionCode::Z => ConditionCode::NZ,
+            ConditionCode::NZ => ConditionCode::Z,
+            ConditionCode::A => ConditionCode::BE,
+            ConditionCode::BE => ConditionCode::A,
+            ConditionCode::S => ConditionCode::NS,
+            ConditionCode::NS => ConditionCode::S,
+            ConditionCode::P => ConditionCode::NP,
+            ConditionCode::NP => ConditionCode::P,
+            ConditionCode::L => ConditionCode::GE,
+            ConditionCode::GE => ConditionCode::L,
+            ConditionCode::G => ConditionCode::LE,
+            ConditionCode::LE => ConditionCode::G,
+        }
+    }
+}
+
 macro_rules! register {
     ($bank:ident, $name:ident => $num:expr, $($tail:tt)+) => {
         #[inline]
@@ -189,6 +329,19 @@ impl RegSpec {
         }
     }
 
+    /// construct a `RegSpec` for mpx bound reg `num`
+    #[inline]
+    pub fn bnd(num: u8) -> RegSpec {
+        if num >= 4 {
+            panic!("invalid x86 bound reg {}", num);
+        }
+
+        RegSpec {
+            num,
+            bank: RegisterBank::Bnd
+        }
+    }
+
     /// construct a `RegSpec` for dword reg `num`
     #[inline]
     pub fn d(num: u8) -> RegSpec {
@@ -257,6 +410,8 @@ impl RegSpec {
         ah => 4, ch => 5, dh => 6, bh => 7
     );
 
+    register!(Bnd, bnd0 => 0, bnd1 => 1, bnd2 => 2, bnd3 => 3);
+
     #[inline]
     pub const fn zmm0() -> RegSpec {
         RegSpec { bank: RegisterBank::Z, num: 0 }
@@ -300,6 +455,7 @@ enum SizeCode {
 ///
 /// `Operand::Nothing` should be unreachable in practice; any such instructions should have an
 /// operand count of 0 (or at least one fewer than the `Nothing` operand's position).
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq)]
 #[non_exhaustive]
 pub enum Operand {
@@ -342,6 +498,9 @@ pub enum Operand {
     /// instruction uses this mode, but plausibe. for example, `fs:[0x14]`. segment overrides,
     /// however, are maintained on the instruction itself.
     DisplacementU32(u32),
+    /// an absolute far address, the target of a direct `jmpf` or `callf`, such as
+    /// `0x1234:0x5678`. `segment` is loaded into `cs`, and `address` into the instruction pointer.
+    AbsoluteFarAddress { segment: u16, address: u32 },
     /// a simple dereference of the address held in some register. for example: `[esi]`.
     RegDeref(RegSpec),
     /// a dereference of the address held in some register with offset. for example: `[esi + 0x14]`.
@@ -450,6 +609,7 @@ impl OperandSpec {
             OperandSpec::RegVex_maskmerge |
             OperandSpec::Reg4 |
             OperandSpec::ImmInDispField |
+            OperandSpec::AbsoluteFarAddress |
             OperandSpec::Nothing => {
                 false
             }
@@ -461,6 +621,7 @@ impl OperandSpec {
 /// the behavior for non-`avx512` instructions is equivalent to `merge`.  `zero` is only useful in
 /// conjunction with a mask register, where bits specified in the mask register correspond to
 /// unmodified items in the instruction's desination.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MergeMode {
     Merge,
@@ -476,6 +637,7 @@ impl From<bool> for MergeMode {
     }
 }
 /// an `avx512` custom rounding mode.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum SaeMode {
     RoundNearest,
@@ -591,6 +753,10 @@ impl Operand {
             OperandSpec::ImmInDispField => Operand::ImmediateU16(inst.disp as u16),
             OperandSpec::DispU16 => Operand::DisplacementU16(inst.disp as u16),
             OperandSpec::DispU32 => Operand::DisplacementU32(inst.disp),
+            OperandSpec::AbsoluteFarAddress => Operand::AbsoluteFarAddress {
+                segment: inst.disp as u16,
+                address: inst.imm,
+            },
             OperandSpec::Deref => {
                 Operand::RegDeref(inst.regs[1])
             }
@@ -721,6 +887,7 @@ impl Operand {
             Operand::RegisterMaskMerge(_, _, _) |
             Operand::RegisterMaskMergeSae(_, _, _, _) |
             Operand::RegisterMaskMergeSaeNoround(_, _, _) |
+            Operand::AbsoluteFarAddress { .. } |
             Operand::Nothing => {
                 false
             }
@@ -800,6 +967,7 @@ const REGISTER_CLASS_NAMES: &[&'static str] = &[
     "k",
     "eip",
     "eflags",
+    "bnd",
 ];
 
 /// high-level register classes in an x86 machine, such as "4-byte general purpose", "xmm", "x87",
@@ -876,6 +1044,8 @@ pub mod register_class {
     pub const MM: RegisterClass = RegisterClass { kind: RegisterBank::MM };
     /// `AVX512` mask registers k0 through k7.
     pub const K: RegisterClass = RegisterClass { kind: RegisterBank::K };
+    /// `mpx` bound registers bnd0 through bnd3.
+    pub const BND: RegisterClass = RegisterClass { kind: RegisterBank::Bnd };
     /// the full instruction pointer register.
     pub const EIP: RegisterClass = RegisterClass { kind: RegisterBank::EIP };
     /// the full cpu flags register.
@@ -925,8 +1095,38 @@ impl RegisterClass {
             RegisterBank::K => {
                 8
             }
+            RegisterBank::Bnd => {
+                16
+            }
         }
     }
+
+    /// the number of registers in this class.
+    pub fn count(&self) -> u8 {
+        match self.kind {
+            RegisterBank::D |
+            RegisterBank::W |
+            RegisterBank::B => 8,
+            RegisterBank::CR |
+            RegisterBank::DR => 8,
+            RegisterBank::S => 6,
+            RegisterBank::EIP |
+            RegisterBank::EFlags => 1,
+            RegisterBank::X |
+            RegisterBank::Y |
+            RegisterBank::Z => 8,
+            RegisterBank::ST |
+            RegisterBank::MM |
+            RegisterBank::K => 8,
+            RegisterBank::Bnd => 4,
+        }
+    }
+
+    /// every register in this class, in order of their numbers: `xmm0` through `xmm7` for `register_class::X`.
+    pub fn registers(&self) -> impl Iterator<Item = RegSpec> {
+        let bank = self.kind;
+        (0..self.count()).map(move |num| RegSpec { num, bank })
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -938,6 +1138,7 @@ enum RegisterBank {
     X = 6, Y = 10, Z = 14,    // XMM, YMM, ZMM
     ST = 18, MM = 19,     // ST, MM regs (x87, mmx)
     K = 20, // AVX512 mask registers
+    Bnd = 23, // MPX bound registers
 }
 
 /// the segment register used by the corresponding instruction.
@@ -945,6 +1146,7 @@ enum RegisterBank {
 /// typically this will be `ds` but can be overridden. some instructions have specific segment
 /// registers used regardless of segment prefixes, and in these cases `yaxpeax-x86` will report the
 /// actual segment register a physical processor would use.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Segment {
     DS = 0, CS, ES, FS, GS, SS
@@ -970,6 +1172,162 @@ const BMI2: [Opcode; 8] = [
     Opcode::SHLX,
 ];
 
+const AVX10_2: [Opcode; 4] = [
+    Opcode::VCVTNE2PH2BF8,
+    Opcode::VCVTNEPH2BF8,
+    Opcode::VCVTBIASPH2BF8,
+    Opcode::VCVT2PS2PHX,
+];
+
+const AVX512_FP16: [Opcode; 106] = [
+    Opcode::VADDPH,
+    Opcode::VADDSH,
+    Opcode::VSUBPH,
+    Opcode::VSUBSH,
+    Opcode::VMULPH,
+    Opcode::VMULSH,
+    Opcode::VDIVPH,
+    Opcode::VDIVSH,
+    Opcode::VMINPH,
+    Opcode::VMINSH,
+    Opcode::VMAXPH,
+    Opcode::VMAXSH,
+    Opcode::VSQRTPH,
+    Opcode::VSQRTSH,
+    Opcode::VRCPPH,
+    Opcode::VRCPSH,
+    Opcode::VRSQRTPH,
+    Opcode::VRSQRTSH,
+    Opcode::VSCALEFPH,
+    Opcode::VSCALEFSH,
+    Opcode::VGETEXPPH,
+    Opcode::VGETEXPSH,
+    Opcode::VGETMANTPH,
+    Opcode::VGETMANTSH,
+    Opcode::VREDUCEPH,
+    Opcode::VREDUCESH,
+    Opcode::VRNDSCALEPH,
+    Opcode::VRNDSCALESH,
+    Opcode::VFMADD132PH,
+    Opcode::VFMADD132SH,
+    Opcode::VFMADD213PH,
+    Opcode::VFMADD213SH,
+    Opcode::VFMADD231PH,
+    Opcode::VFMADD231SH,
+    Opcode::VFMSUB132PH,
+    Opcode::VFMSUB132SH,
+    Opcode::VFMSUB213PH,
+    Opcode::VFMSUB213SH,
+    Opcode::VFMSUB231PH,
+    Opcode::VFMSUB231SH,
+    Opcode::VFNMADD132PH,
+    Opcode::VFNMADD132SH,
+    Opcode::VFNMADD213PH,
+    Opcode::VFNMADD213SH,
+    Opcode::VFNMADD231PH,
+    Opcode::VFNMADD231SH,
+    Opcode::VFNMSUB132PH,
+    Opcode::VFNMSUB132SH,
+    Opcode::VFNMSUB213PH,
+    Opcode::VFNMSUB213SH,
+    Opcode::VFNMSUB231PH,
+    Opcode::VFNMSUB231SH,
+    Opcode::VFMADDSUB132PH,
+    Opcode::VFMADDSUB213PH,
+    Opcode::VFMADDSUB231PH,
+    Opcode::VFMSUBADD132PH,
+    Opcode::VFMSUBADD213PH,
+    Opcode::VFMSUBADD231PH,
+    Opcode::VFMADDCPH,
+    Opcode::VFMADDCSH,
+    Opcode::VFCMADDCPH,
+    Opcode::VFCMADDCSH,
+    Opcode::VFMULCPH,
+    Opcode::VFMULCSH,
+    Opcode::VFCMULCPH,
+    Opcode::VFCMULCSH,
+    Opcode::VCVTPH2PSX,
+    Opcode::VCVTPS2PHX,
+    Opcode::VCVTPH2PD,
+    Opcode::VCVTPD2PH,
+    Opcode::VCVTSH2SS,
+    Opcode::VCVTSS2SH,
+    Opcode::VCVTSH2SD,
+    Opcode::VCVTSD2SH,
+    Opcode::VCVTPH2DQ,
+    Opcode::VCVTTPH2DQ,
+    Opcode::VCVTDQ2PH,
+    Opcode::VCVTPH2UDQ,
+    Opcode::VCVTTPH2UDQ,
+    Opcode::VCVTUDQ2PH,
+    Opcode::VCVTPH2QQ,
+    Opcode::VCVTTPH2QQ,
+    Opcode::VCVTQQ2PH,
+    Opcode::VCVTPH2UQQ,
+    Opcode::VCVTTPH2UQQ,
+    Opcode::VCVTUQQ2PH,
+    Opcode::VCVTPH2W,
+    Opcode::VCVTTPH2W,
+    Opcode::VCVTW2PH,
+    Opcode::VCVTPH2UW,
+    Opcode::VCVTTPH2UW,
+    Opcode::VCVTUW2PH,
+    Opcode::VCVTSH2SI,
+    Opcode::VCVTTSH2SI,
+    Opcode::VCVTSI2SH,
+    Opcode::VCVTSH2USI,
+    Opcode::VCVTTSH2USI,
+    Opcode::VCVTUSI2SH,
+    Opcode::VCMPPH,
+    Opcode::VCMPSH,
+    Opcode::VCOMISH,
+    Opcode::VUCOMISH,
+    Opcode::VFPCLASSPH,
+    Opcode::VFPCLASSSH,
+    Opcode::VMOVSH,
+    Opcode::VMOVW,
+];
+
+const TBM: [Opcode; 9] = [
+    Opcode::BLCFILL,
+    Opcode::BLSFILL,
+    Opcode::BLCS,
+    Opcode::TZMSK,
+    Opcode::BLCIC,
+    Opcode::BLSIC,
+    Opcode::T1MSKC,
+    Opcode::BLCMSK,
+    Opcode::BLCI,
+];
+
+const _3DNOW: [Opcode; 25] = [
+    Opcode::FEMMS,
+    Opcode::PI2FW,
+    Opcode::PI2FD,
+    Opcode::PF2IW,
+    Opcode::PF2ID,
+    Opcode::PMULHRW,
+    Opcode::PFCMPGE,
+    Opcode::PFMIN,
+    Opcode::PFRCP,
+    Opcode::PFRSQRT,
+    Opcode::PFSUB,
+    Opcode::PFADD,
+    Opcode::PFCMPGT,
+    Opcode::PFMAX,
+    Opcode::PFRCPIT1,
+    Opcode::PFRSQIT1,
+    Opcode::PFSUBR,
+    Opcode::PFACC,
+    Opcode::PFCMPEQ,
+    Opcode::PFMUL,
+    Opcode::PFRCPIT2,
+    Opcode::PFNACC,
+    Opcode::PFPNACC,
+    Opcode::PSWAPD,
+    Opcode::PAVGUSB,
+];
+
 #[allow(dead_code)]
 const XSAVE: [Opcode; 10] = [
     Opcode::XGETBV,
@@ -984,9 +1342,11 @@ const XSAVE: [Opcode; 10] = [
     Opcode::XSETBV,
 ];
 
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[allow(non_camel_case_types)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
+#[repr(u16)]
 pub enum Opcode {
     Invalid,
     ADD,
@@ -2073,6 +2433,7 @@ pub enum Opcode {
     WRUSS,
     WRSS,
     INCSSP,
+    RDSSP,
     SAVEPREVSSP,
     SETSSBSY,
     CLRSSBSY,
@@ -2102,6 +2463,9 @@ pub enum Opcode {
     XSUSLDTRK,
     XRESLDTRK,
 
+    // SERIALIZE
+    SERIALIZE,
+
     // AVX512F
     VALIGND,
     VALIGNQ,
@@ -2476,6 +2840,192 @@ pub enum Opcode {
     PVALIDATE,
     RMPADJUST,
     RMPUPDATE,
+    VMGEXIT,
+
+    VCVTNE2PH2BF8,
+    VCVTNEPH2BF8,
+    VCVTBIASPH2BF8,
+    VCVT2PS2PHX,
+
+    // avx512-fp16
+    VADDPH,
+    VADDSH,
+    VSUBPH,
+    VSUBSH,
+    VMULPH,
+    VMULSH,
+    VDIVPH,
+    VDIVSH,
+    VMINPH,
+    VMINSH,
+    VMAXPH,
+    VMAXSH,
+    VSQRTPH,
+    VSQRTSH,
+    VRCPPH,
+    VRCPSH,
+    VRSQRTPH,
+    VRSQRTSH,
+    VSCALEFPH,
+    VSCALEFSH,
+    VGETEXPPH,
+    VGETEXPSH,
+    VGETMANTPH,
+    VGETMANTSH,
+    VREDUCEPH,
+    VREDUCESH,
+    VRNDSCALEPH,
+    VRNDSCALESH,
+    VFMADD132PH,
+    VFMADD132SH,
+    VFMADD213PH,
+    VFMADD213SH,
+    VFMADD231PH,
+    VFMADD231SH,
+    VFMSUB132PH,
+    VFMSUB132SH,
+    VFMSUB213PH,
+    VFMSUB213SH,
+    VFMSUB231PH,
+    VFMSUB231SH,
+    VFNMADD132PH,
+    VFNMADD132SH,
+    VFNMADD213PH,
+    VFNMADD213SH,
+    VFNMADD231PH,
+    VFNMADD231SH,
+    VFNMSUB132PH,
+    VFNMSUB132SH,
+    VFNMSUB213PH,
+    VFNMSUB213SH,
+    VFNMSUB231PH,
+    VFNMSUB231SH,
+    VFMADDSUB132PH,
+    VFMADDSUB213PH,
+    VFMADDSUB231PH,
+    VFMSUBADD132PH,
+    VFMSUBADD213PH,
+    VFMSUBADD231PH,
+    VFMADDCPH,
+    VFMADDCSH,
+    VFCMADDCPH,
+    VFCMADDCSH,
+    VFMULCPH,
+    VFMULCSH,
+    VFCMULCPH,
+    VFCMULCSH,
+    VCVTPH2PSX,
+    VCVTPS2PHX,
+    VCVTPH2PD,
+    VCVTPD2PH,
+    VCVTSH2SS,
+    VCVTSS2SH,
+    VCVTSH2SD,
+    VCVTSD2SH,
+    VCVTPH2DQ,
+    VCVTTPH2DQ,
+    VCVTDQ2PH,
+    VCVTPH2UDQ,
+    VCVTTPH2UDQ,
+    VCVTUDQ2PH,
+    VCVTPH2QQ,
+    VCVTTPH2QQ,
+    VCVTQQ2PH,
+    VCVTPH2UQQ,
+    VCVTTPH2UQQ,
+    VCVTUQQ2PH,
+    VCVTPH2W,
+    VCVTTPH2W,
+    VCVTW2PH,
+    VCVTPH2UW,
+    VCVTTPH2UW,
+    VCVTUW2PH,
+    VCVTSH2SI,
+    VCVTTSH2SI,
+    VCVTSI2SH,
+    VCVTSH2USI,
+    VCVTTSH2USI,
+    VCVTUSI2SH,
+    VCMPPH,
+    VCMPSH,
+    VCOMISH,
+    VUCOMISH,
+    VFPCLASSPH,
+    VFPCLASSSH,
+    VMOVSH,
+    VMOVW,
+
+    // xop
+    VFRCZPS,
+    VFRCZPD,
+    VFRCZSS,
+    VFRCZSD,
+    VPCMOV,
+    VPPERM,
+    VPCOMB,
+    VPCOMW,
+    VPCOMD,
+    VPCOMQ,
+    VPCOMUB,
+    VPCOMUW,
+    VPCOMUD,
+    VPCOMUQ,
+    VPHADDBW,
+    VPHADDBD,
+    VPHADDBQ,
+    VPHADDWD,
+    VPHADDWQ,
+    VPHADDDQ,
+    VPHADDUBW,
+    VPHADDUBD,
+    VPHADDUBQ,
+    VPHADDUWD,
+    VPHADDUWQ,
+    VPHADDUDQ,
+    VPHSUBBW,
+    VPHSUBWD,
+    VPHSUBDQ,
+    VPMACSSWW,
+    VPMACSSWD,
+    VPMACSSDQL,
+    VPMACSSDD,
+    VPMACSSDQH,
+    VPMACSWW,
+    VPMACSWD,
+    VPMACSDQL,
+    VPMACSDD,
+    VPMACSDQH,
+    VPMADCSSWD,
+    VPMADCSWD,
+    VPROTB,
+    VPROTW,
+    VPROTD,
+    VPROTQ,
+    VPSHLB,
+    VPSHLW,
+    VPSHLD,
+    VPSHLQ,
+    VPSHAB,
+    VPSHAW,
+    VPSHAD,
+    VPSHAQ,
+
+    // tbm
+    BLCFILL,
+    BLSFILL,
+    BLCS,
+    TZMSK,
+    BLCIC,
+    BLSIC,
+    T1MSKC,
+    BLCMSK,
+    BLCI,
+
+    // lwp
+    LLWPCB,
+    SLWPCB,
+    LWPINS,
+    LWPVAL,
 }
 
 impl PartialEq for Instruction {
@@ -2515,6 +3065,7 @@ impl PartialEq for Instruction {
 /// typically an opcode will be inspected by [`Instruction::opcode()`], and an instruction has
 /// [`Instruction::operand_count()`] many operands. operands are provided by
 /// [`Instruction::operand()`].
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, Copy, Eq)]
 pub struct Instruction {
     pub prefixes: Prefixes,
@@ -2533,6 +3084,11 @@ pub struct Instruction {
     disp: u32,
     opcode: Opcode,
     mem_size: u8,
+    // does the memory operand encode a displacement of zero? `operands` and `disp` describe it
+    // the same as no displacement, but AT&T syntax writes it out.
+    zero_disp: bool,
+    // only present if the decoder was built `with_retain_bytes()`.
+    bytes: Option<[u8; 15]>,
 }
 
 impl yaxpeax_arch::Instruction for Instruction {
@@ -2578,6 +3134,7 @@ impl std::error::Error for DecodeError {
     }
 }
 
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[allow(non_camel_case_types)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum OperandSpec {
@@ -2617,6 +3174,8 @@ enum OperandSpec {
     ImmInDispField,
     DispU16,
     DispU32,
+    // a `segment:offset` far pointer, with the offset in `imm` and segment in `disp`.
+    AbsoluteFarAddress,
     Deref,
     Deref_si,
     Deref_di,
@@ -2748,7 +3307,13 @@ pub struct InstDecoder {
     // 61. prefetchw
     // 62. tsx
     // 63. lzcnt
-    flags: u64,
+    // 64. avx10.2 (256-bit embedded rounding and the avx10.2 converts)
+    // 65. retain bytes (not an extension: keep the bytes of each decoded instruction)
+    // 66. 3dnow (`femms` and the `0f 0f` instructions)
+    // 67. permissive lock (not an extension: accept `lock` where the processor would `#UD`)
+    // 68. emit invalid bytes (not an extension: decode an invalid byte as `Opcode::Invalid`)
+    // 69. permissive length (not an extension: accept instructions longer than 15 bytes)
+    flags: u128,
 }
 
 impl InstDecoder {
@@ -3417,15 +3982,132 @@ impl InstDecoder {
         self
     }
 
+    pub fn avx10_2(&self) -> bool {
+        self.flags & (1 << 64) != 0
+    }
+
+    pub fn with_avx10_2(mut self) -> Self {
+        self.flags |= 1 << 64;
+        self
+    }
+
+    /// whether decoded instructions keep the bytes they were decoded from, available through
+    /// [`Instruction::bytes`]. this is not an extension, and is off by default.
+    pub fn retain_bytes(&self) -> bool {
+        self.flags & (1 << 65) != 0
+    }
+
+    pub fn with_retain_bytes(mut self) -> Self {
+        self.flags |= 1 << 65;
+        self
+    }
+
+    /// `femms` and the `0f 0f`-escaped 3dnow! instructions, whose opcode is selected by a
+    /// trailing byte.
+    pub fn _3dnow(&self) -> bool {
+        self.flags & (1 << 66) != 0
+    }
+
+    pub fn with_3dnow(mut self) -> Self {
+        self.flags |= 1 << 66;
+        self
+    }
+
+    /// whether a `lock` prefix on an instruction that cannot be locked, or whose destination is
+    /// not memory, is accepted rather than rejected with `DecodeError::InvalidPrefixes`. the
+    /// processor raises `#UD` for these; [`Instruction::lock_valid`] tells which decoded
+    /// instructions it would. this is not an extension, and is off by default.
+    pub fn permissive_lock(&self) -> bool {
+        self.flags & (1 << 67) != 0
+    }
+
+    pub fn with_permissive_lock(mut self) -> Self {
+        self.flags |= 1 << 67;
+        self
+    }
+
+    /// whether bytes that do not begin a valid instruction decode as a one-byte pseudo-instruction
+    /// rather than an error, so a listing can show them as data between the instructions around
+    /// them. the pseudo-instruction has opcode `Opcode::Invalid`, length 1, and the byte as its
+    /// only operand; see [`Instruction::invalid_byte`]. the next instruction is decoded from the
+    /// byte after it, however far the failed decode read. running out of input is still
+    /// `DecodeError::ExhaustedInput`. to have the byte to report, this also retains instruction
+    /// bytes as [`with_retain_bytes()`](InstDecoder::with_retain_bytes) does. this is not an
+    /// extension, and is off by default.
+    pub fn emit_invalid_bytes(&self) -> bool {
+        self.flags & (1 << 68) != 0
+    }
+
+    pub fn with_emit_invalid_bytes(mut self) -> Self {
+        self.flags |= 1 << 68;
+        self
+    }
+
+    /// whether instructions longer than the architectural limit of 15 bytes, which only redundant
+    /// prefixes can make, are decoded rather than rejected with `DecodeError::TooLong`. the
+    /// processor raises `#GP` for these; [`Instruction::length_valid`] tells which decoded
+    /// instructions it would. instructions longer than 255 bytes are rejected all the same, and
+    /// the bytes of instructions longer than 15 bytes are not retained. this is not an extension,
+    /// and is off by default.
+    pub fn permissive_length(&self) -> bool {
+        self.flags & (1 << 69) != 0
+    }
+
+    pub fn with_permissive_length(mut self) -> Self {
+        self.flags |= 1 << 69;
+        self
+    }
+
+    /// whether `avx512-fp16` instructions, half-precision arithmetic and conversions such as
+    /// `vaddph` and `vcvtph2psx`, are decoded. most of these are in evex opcode maps 5 and 6,
+    /// which no other extension uses.
+    pub fn avx512_fp16(&self) -> bool {
+        self.flags & (1 << 70) != 0
+    }
+
+    pub fn with_avx512_fp16(mut self) -> Self {
+        self.flags |= 1 << 70;
+        self
+    }
+
+    /// whether an instruction `len` bytes long is rejected as `DecodeError::TooLong`.
+    /// `permissive_length` is only tested past 15 bytes, so that it costs nothing when decoding
+    /// instructions within the limit.
+    #[inline(always)]
+    fn too_long(&self, len: usize) -> bool {
+        len > 15 && (!self.permissive_length() || len > u8::MAX as usize)
+    }
+
     /// Optionally reject or reinterpret instruction according to the decoder's
     /// declared extensions.
     fn revise_instruction(&self, inst: &mut Instruction) -> Result<(), DecodeError> {
-        if inst.prefixes.evex().is_some() {
-            if !self.avx512() {
+        if let Some(evex) = inst.prefixes.evex() {
+            // every cpu with `evex` instructions has at least `avx512_f`; subsets beyond that
+            // are not distinguished.
+            if !self.avx512_f() {
+                return Err(DecodeError::InvalidOpcode);
+            }
+            if !self.avx10_2() && (!evex.u() || AVX10_2.contains(&inst.opcode)) {
                 return Err(DecodeError::InvalidOpcode);
-            } else {
-                return Ok(());
             }
+            if !self.avx512_fp16() && AVX512_FP16.contains(&inst.opcode) {
+                return Err(DecodeError::InvalidOpcode);
+            }
+            return Ok(());
+        }
+        if inst.prefixes.xop() {
+            // `tbm` instructions are `xop`-encoded, but only need `tbm`.
+            if TBM.contains(&inst.opcode) || inst.opcode == Opcode::BEXTR {
+                if !self.tbm() {
+                    return Err(DecodeError::InvalidOpcode);
+                }
+            } else if !self.xop() {
+                return Err(DecodeError::InvalidOpcode);
+            }
+            return Ok(());
+        }
+        if _3DNOW.contains(&inst.opcode) && !self._3dnow() {
+            return Err(DecodeError::InvalidOpcode);
         }
         match inst.opcode {
             Opcode::TZCNT => {
@@ -4082,32 +4764,28 @@ impl Default for InstDecoder {
     /// Instantiates an x86 decoder that probably decodes what you want.
     ///
     /// Attempts to match real processors in interpretation of undefined sequences, and decodes any
-    /// instruction defined in any extension.
+    /// instruction defined in any extension except `mpx`. `mpx` has been removed from recent
+    /// processors, which execute its encodings as the reserved `nop`s they were before it; use
+    /// `with_mpx` to decode them as `mpx` instead.
     fn default() -> Self {
         Self {
-            flags: 0xffffffff_ffffffff,
+            // every extension, but not retaining instruction bytes, which costs a copy per byte,
+            // `mpx`, whose encodings are otherwise `nop`, accepting invalid `lock` prefixes, or
+            // emitting invalid bytes as instructions.
+            flags: !((1 << 65) | (1 << 18) | (1 << 67) | (1 << 68) | (1 << 69)),
         }
     }
 }
 
 impl Decoder<Arch> for InstDecoder {
+    #[inline]
     fn decode<T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as yaxpeax_arch::Arch>::Word>>(&self, words: &mut T) -> Result<Instruction, <Arch as yaxpeax_arch::Arch>::DecodeError> {
         let mut instr = Instruction::invalid();
-        read_with_annotations(self, words, &mut instr, &mut NullSink)?;
-
-        instr.length = words.offset() as u8;
-        if words.offset() > 15 {
-            return Err(DecodeError::TooLong);
-        }
-
-        if self != &InstDecoder::default() {
-            self.revise_instruction(&mut instr)?;
-        }
-
+        self.decode_with_sink(&mut instr, words, &mut NullSink)?;
         Ok(instr)
     }
     fn decode_into<T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as yaxpeax_arch::Arch>::Word>>(&self, instr: &mut Instruction, words: &mut T) -> Result<(), <Arch as yaxpeax_arch::Arch>::DecodeError> {
-        self.decode_with_annotation(instr, words, &mut NullSink)
+        self.decode_with_sink(instr, words, &mut NullSink)
     }
 }
 
@@ -4118,15 +4796,32 @@ impl AnnotatingDecoder<Arch> for InstDecoder {
         T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as yaxpeax_arch::Arch>::Word>,
         S: DescriptionSink<Self::FieldDescription>
     >(&self, instr: &mut Instruction, words: &mut T, sink: &mut S) -> Result<(), <Arch as yaxpeax_arch::Arch>::DecodeError> {
-        read_with_annotations(self, words, instr, sink)?;
+        bytes::decode_annotated(self, instr, words, sink)
+    }
+}
+
+impl InstDecoder {
+    /// decode an instruction into `instr`, describing fields to `sink` as the decoder reads them.
+    /// `decode_with_annotation` also describes the fields the decoder does not, once the
+    /// instruction is decoded; `decode` and `decode_into` pass a `NullSink` and skip that.
+    #[inline(always)]
+    pub(crate) fn decode_with_sink<
+        T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as yaxpeax_arch::Arch>::Word>,
+        S: DescriptionSink<FieldDescription>
+    >(&self, instr: &mut Instruction, words: &mut T, sink: &mut S) -> Result<(), DecodeError> {
+        if let Err(e) = bytes::read_instruction(self, words, instr, sink) {
+            return bytes::emit_invalid_byte(self, instr, e);
+        }
 
         instr.length = words.offset() as u8;
-        if words.offset() > 15 {
-            return Err(DecodeError::TooLong);
+        if self.too_long(words.offset() as usize) {
+            return bytes::emit_invalid_byte(self, instr, DecodeError::TooLong);
         }
 
         if self != &InstDecoder::default() {
-            self.revise_instruction(instr)?;
+            if let Err(e) = self.revise_instruction(instr) {
+                return bytes::emit_invalid_byte(self, instr, e);
+            }
         }
 
         Ok(())
@@ -4135,7 +4830,8 @@ impl AnnotatingDecoder<Arch> for InstDecoder {
 
 impl Opcode {
     /// get the [`ConditionCode`] for this instruction, if it is in fact conditional. x86's
-    /// conditional instructions are `Jcc`, `CMOVcc`, andd `SETcc`.
+    /// conditional instructions are `Jcc`, `CMOVcc`, and `SETcc`, as well as the x87 `FCMOVcc`,
+    /// whose conditions only test `CF`, `ZF`, and `PF`.
     pub fn condition(&self) -> Option<ConditionCode> {
         match self {
             Opcode::JO |
@@ -4146,22 +4842,28 @@ impl Opcode {
             Opcode::SETNO => { Some(ConditionCode::NO) },
             Opcode::JB |
             Opcode::CMOVB |
-            Opcode::SETB => { Some(ConditionCode::B) },
+            Opcode::SETB |
+            Opcode::FCMOVB => { Some(ConditionCode::B) },
             Opcode::JNB |
             Opcode::CMOVNB |
-            Opcode::SETAE => { Some(ConditionCode::AE) },
+            Opcode::SETAE |
+            Opcode::FCMOVNB => { Some(ConditionCode::AE) },
             Opcode::JZ |
             Opcode::CMOVZ |
-            Opcode::SETZ => { Some(ConditionCode::Z) },
+            Opcode::SETZ |
+            Opcode::FCMOVE => { Some(ConditionCode::Z) },
             Opcode::JNZ |
             Opcode::CMOVNZ |
-            Opcode::SETNZ => { Some(ConditionCode::NZ) },
+            Opcode::SETNZ |
+            Opcode::FCMOVNE => { Some(ConditionCode::NZ) },
             Opcode::JA |
             Opcode::CMOVA |
-            Opcode::SETA => { Some(ConditionCode::A) },
+            Opcode::SETA |
+            Opcode::FCMOVNBE => { Some(ConditionCode::A) },
             Opcode::JNA |
             Opcode::CMOVNA |
-            Opcode::SETBE => { Some(ConditionCode::BE) },
+            Opcode::SETBE |
+            Opcode::FCMOVBE => { Some(ConditionCode::BE) },
             Opcode::JS |
             Opcode::CMOVS |
             Opcode::SETS => { Some(ConditionCode::S) },
@@ -4170,10 +4872,12 @@ impl Opcode {
             Opcode::SETNS => { Some(ConditionCode::NS) },
             Opcode::JP |
             Opcode::CMOVP |
-            Opcode::SETP => { Some(ConditionCode::P) },
+            Opcode::SETP |
+            Opcode::FCMOVU => { Some(ConditionCode::P) },
             Opcode::JNP |
             Opcode::CMOVNP |
-            Opcode::SETNP => { Some(ConditionCode::NP) },
+            Opcode::SETNP |
+            Opcode::FCMOVNU => { Some(ConditionCode::NP) },
             Opcode::JL |
             Opcode::CMOVL |
             Opcode::SETL => { Some(ConditionCode::L) },
@@ -4203,6 +4907,77 @@ impl Instruction {
         self.opcode
     }
 
+    /// get the [`ConditionCode`] this instruction tests, if it is conditional. this is
+    /// [`Opcode::condition`] of this instruction's opcode.
+    pub fn condition(&self) -> Option<ConditionCode> {
+        self.opcode.condition()
+    }
+
+    /// get the prefixes this instruction was decoded with, in a form that does not require
+    /// knowing how `Prefixes` stores them.
+    pub fn prefixes(&self) -> InstructionPrefixes {
+        let evex = self.prefixes.evex();
+        InstructionPrefixes {
+            segment_override: self.prefixes.segment_override(),
+            lock: self.prefixes.lock(),
+            rep: self.prefixes.rep(),
+            repnz: self.prefixes.repnz(),
+            operand_size: self.prefixes.operand_size(),
+            address_size: self.prefixes.address_size(),
+            vex: if evex.is_some() { None } else { self.prefixes.vex() },
+            xop: self.prefixes.xop(),
+            evex,
+        }
+    }
+
+    /// get the encoding this instruction was decoded from: legacy, `vex`, `xop`, or `evex`, along
+    /// with the opcode map, `pp`, vector length, and `W` fields of a `vex`-like prefix.
+    pub fn encoding_info(&self) -> EncodingInfo {
+        let (encoding, vector_length, w) = if let Some(evex) = self.prefixes.evex() {
+            (Encoding::Evex, ((evex.lp() as u8) << 1) | evex.vex().l() as u8, evex.vex().w())
+        } else if let Some(vex) = self.prefixes.vex() {
+            let encoding = if self.prefixes.xop() { Encoding::Xop } else { Encoding::Vex };
+            (encoding, vex.l() as u8, vex.w())
+        } else {
+            return EncodingInfo {
+                encoding: Encoding::Legacy,
+                map: 0,
+                pp: 0,
+                vector_length: 0,
+                w: false,
+            };
+        };
+        EncodingInfo {
+            encoding,
+            map: self.prefixes.map_pp & 0b11111,
+            pp: self.prefixes.map_pp >> 5,
+            vector_length,
+            w,
+        }
+    }
+
+    /// get the bytes this instruction was decoded from, if it was decoded by an `InstDecoder`
+    /// built [`with_retain_bytes()`](InstDecoder::with_retain_bytes) or
+    /// [`with_emit_invalid_bytes()`](InstDecoder::with_emit_invalid_bytes), and it is no longer
+    /// than 15 bytes.
+    pub fn bytes(&self) -> Option<&[u8]> {
+        if !self.length_valid() {
+            return None;
+        }
+        self.bytes.as_ref().map(|bytes| &bytes[..self.length as usize])
+    }
+
+    /// get the byte this pseudo-instruction stands in for, if it was decoded by an `InstDecoder`
+    /// built [`with_emit_invalid_bytes()`](InstDecoder::with_emit_invalid_bytes) from a byte that
+    /// does not begin a valid instruction.
+    pub fn invalid_byte(&self) -> Option<u8> {
+        if self.opcode == Opcode::Invalid && self.operand_count == 1 {
+            Some(self.imm as u8)
+        } else {
+            None
+        }
+    }
+
     /// get the `Operand` at the provided index.
     ///
     /// panics if the index is `>= 4`.
@@ -4260,6 +5035,7 @@ impl Instruction {
             prefixes: Prefixes::new(0),
             opcode: Opcode::NOP,
             mem_size: 0,
+            zero_disp: false,
             regs: [RegSpec::eax(); 4],
             scale: 0,
             length: 0,
@@ -4267,6 +5043,7 @@ impl Instruction {
             imm: 0,
             operand_count: 0,
             operands: [OperandSpec::Nothing; 4],
+            bytes: None,
         }
     }
 
@@ -4321,26 +5098,65 @@ impl Instruction {
         }
     }
 
+    /// get the size, in bytes, of the address used for the memory operand at index `op`, or
+    /// `None` if that operand is not in memory.
+    ///
+    /// instructions default to 32-bit addresses, and a `67` prefix selects 16-bit addresses. the
+    /// address size applies to every memory operand, including the `esi`/`edi` operands of string
+    /// instructions and the address `lea` computes.
+    pub fn address_size_for_op(&self, op: u8) -> Option<u8> {
+        if op >= self.operand_count || !self.operands[op as usize].is_memory() {
+            return None;
+        }
+        if self.prefixes.address_size() {
+            Some(2)
+        } else {
+            Some(4)
+        }
+    }
+
     #[cfg(feature = "fmt")]
-    /// wrap a reference to this instruction with a `DisplayStyle` to format the instruction with
-    /// later. see the documentation on [`display::DisplayStyle`] for more.
+    /// wrap a reference to this instruction with a `DisplayStyle`, or more detailed
+    /// `DisplayOptions`, to format the instruction with later. see the documentation on
+    /// [`display::DisplayStyle`] and [`display::DisplayOptions`] for more.
     ///
     /// ```
-    /// use yaxpeax_x86::long_mode::{InstDecoder, DisplayStyle};
+    /// use yaxpeax_x86::long_mode::{InstDecoder, DisplayOptions, DisplayStyle};
     ///
     /// let decoder = InstDecoder::default();
     /// let inst = decoder.decode_slice(&[0x33, 0xc1]).unwrap();
     ///
     /// assert_eq!("eax ^= ecx", inst.display_with(DisplayStyle::C).to_string());
     /// assert_eq!("xor eax, ecx", inst.display_with(DisplayStyle::Intel).to_string());
+    /// assert_eq!("xor %ecx,%eax", inst.display_with(DisplayOptions::att()).to_string());
     /// ```
-    pub fn display_with<'a>(&'a self, style: display::DisplayStyle) -> display::InstructionDisplayer<'a> {
+    pub fn display_with<'a, O: Into<display::DisplayOptions>>(&'a self, options: O) -> display::InstructionDisplayer<'a> {
         display::InstructionDisplayer {
-            style,
+            options: options.into(),
             instr: self,
         }
     }
 
+    /// whether this instruction's `lock` prefix is one the processor accepts: on an instruction
+    /// that can be locked, with a memory destination. the processor raises `#UD` otherwise.
+    /// always `true` for instructions without `lock`.
+    ///
+    /// decoders reject instructions with invalid `lock` prefixes unless they are
+    /// [`permissive_lock`](InstDecoder::permissive_lock).
+    pub fn lock_valid(&self) -> bool {
+        !self.prefixes.lock() ||
+            (LOCKABLE_INSTRUCTIONS.contains(&self.opcode) && self.operands[0].is_memory())
+    }
+
+    /// whether this instruction is within the architectural limit of 15 bytes. the processor
+    /// raises `#GP` otherwise.
+    ///
+    /// decoders reject instructions longer than 15 bytes unless they are
+    /// [`permissive_length`](InstDecoder::permissive_length).
+    pub fn length_valid(&self) -> bool {
+        self.length <= 15
+    }
+
     /// does this instruction include the `xacquire` hint for hardware lock elision?
     pub fn xacquire(&self) -> bool {
         if self.prefixes.repnz() {
@@ -4362,13 +5178,14 @@ impl Instruction {
     pub fn xrelease(&self) -> bool {
         if self.prefixes.rep() {
             // xrelease is permitted on typical `lock` instructions, OR `xchg` with memory operand,
-            // regardless of `lock` prefix. additionally, xrelease is permitted on some forms of mov.
+            // regardless of `lock` prefix. additionally, xrelease is permitted on `mov` to memory from
+            // a register or immediate, but not on `mov` to a register.
             if self.prefixes.lock() {
                 true
             } else if self.opcode == Opcode::XCHG {
                 self.operands[0] != OperandSpec::RegMMM && self.operands[1] != OperandSpec::RegMMM
             } else if self.opcode == Opcode::MOV {
-                self.operands[0] != OperandSpec::RegMMM && (
+                Operand::from_spec(self, self.operands[0]).is_memory() && (
                     self.operands[1] == OperandSpec::RegRRR ||
                     self.operands[1] == OperandSpec::ImmI8 ||
                     self.operands[1] == OperandSpec::ImmI16 ||
@@ -4383,24 +5200,62 @@ impl Instruction {
     }
 }
 
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 struct EvexData {
     // data: present, z, b, Lp, Rp. aaa
     bits: u8,
 }
 
+/// the prefixes of an instruction as the decoder interpreted them, returned by
+/// [`Instruction::prefixes()`].
+///
+/// a `66` prefix that selects an opcode, like the `66` of `66 0f 38 00` (`pshufb`), is part of
+/// that opcode and is not reported as an operand-size override. `f2` and `f3` are reported even
+/// where they select an opcode, like the `f3` of `f3 0f 10` (`movss`). the register a `vex` or
+/// `evex` prefix names in `vvvv` is an operand of the instruction, not part of its prefix.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct InstructionPrefixes {
+    /// the segment named by a segment override prefix, if any.
+    pub segment_override: Option<Segment>,
+    /// is there a `lock` (`f0`) prefix?
+    pub lock: bool,
+    /// is there a `rep` (`f3`) prefix? `xrelease` is also encoded as `f3`.
+    pub rep: bool,
+    /// is there a `repnz` (`f2`) prefix? `xacquire` is also encoded as `f2`.
+    pub repnz: bool,
+    /// is there an operand-size override (`66`) prefix?
+    pub operand_size: bool,
+    /// is there an address-size override (`67`) prefix?
+    pub address_size: bool,
+    /// the `vex` prefix, if the instruction is vex-encoded. this also holds the fields of an `xop`
+    /// prefix.
+    pub vex: Option<PrefixVex>,
+    /// was `vex` an amd `xop` (`8f`) prefix, rather than `c4` or `c5`?
+    pub xop: bool,
+    /// the `evex` prefix, if the instruction is evex-encoded.
+    pub evex: Option<PrefixEvex>,
+}
+
 /// the prefixes on an instruction.
 ///
 /// `rep`, `repnz`, `lock`, and segment override prefixes are directly accessible here.  `vex` and
 /// `evex` prefixes are available through their associated helpers.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Prefixes {
     bits: u8,
     vex: PrefixVex,
     segment: Segment,
     evex_data: EvexData,
+    // the `mmmmm` and `pp` fields of a `vex`, `xop`, or `evex` prefix, as `0ppmmmmm`.
+    map_pp: u8,
+    // the last `2e` or `3e` prefix, kept as a possible branch hint, or `0` if there was neither.
+    branch_hint: u8,
 }
 
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct PrefixEvex {
     vex: PrefixVex,
@@ -4434,9 +5289,15 @@ impl PrefixEvex {
     pub fn rp(&self) -> bool {
         self.evex_data.rp()
     }
+    /// the `evex` `U` bit. this is set in all instructions but avx10.2's 256-bit operations with
+    /// embedded rounding or `sae`.
+    pub fn u(&self) -> bool {
+        (self.vex.bits & 0x40) == 0
+    }
 }
 
 /// bits specified in an avx/avx2 [`vex`](https://en.wikipedia.org/wiki/VEX_prefix) prefix, `L`, `W`, `R`, `X`, and `B`.
+#[cfg_attr(feature="use-serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct PrefixVex {
     bits: u8,
@@ -4468,6 +5329,8 @@ impl Prefixes {
             vex: PrefixVex { bits: 0 },
             segment: Segment::DS,
             evex_data: EvexData { bits: 0 },
+            map_pp: 0,
+            branch_hint: 0,
         }
     }
     fn vex_from(&mut self, bits: u8) {
@@ -4485,44 +5348,69 @@ impl Prefixes {
     fn set_repnz(&mut self) { self.bits = (self.bits & 0xcf) | 0x30 }
     #[inline]
     pub fn rep_any(&self) -> bool { self.bits & 0x30 != 0x00 }
+    /// did an `f2` or `f3` prefix select this instruction's opcode, rather than act as `rep`?
+    #[inline]
+    fn mandatory_rep(&self) -> bool { self.bits & 0x80 != 0 }
+    #[inline]
+    fn set_mandatory_rep(&mut self) { self.bits |= 0x80 }
+    /// is there an operand-size override (`66`) prefix? a `66` prefix that selects an opcode, as
+    /// for `pshufb`, is part of that opcode and is not reported here.
     #[inline]
-    fn operand_size(&self) -> bool { self.bits & 0x1 == 1 }
+    pub fn operand_size(&self) -> bool { self.bits & 0x1 == 1 }
     #[inline]
     fn set_operand_size(&mut self) { self.bits = self.bits | 0x1 }
     #[inline]
     fn unset_operand_size(&mut self) { self.bits = self.bits & !0x1 }
+    /// is there an address-size override (`67`) prefix?
     #[inline]
-    fn address_size(&self) -> bool { self.bits & 0x2 == 2 }
+    pub fn address_size(&self) -> bool { self.bits & 0x2 == 2 }
     #[inline]
     fn set_address_size(&mut self) { self.bits = self.bits | 0x2 }
     #[inline]
+    fn unset_address_size(&mut self) { self.bits &= !0x2 }
+    #[inline]
     fn set_lock(&mut self) { self.bits |= 0x4 }
     #[inline]
     pub fn lock(&self) -> bool { self.bits & 0x4 == 4 }
+    /// was this instruction encoded with an amd `xop` prefix? `xop` lays out its fields like a
+    /// three-byte `vex` prefix, and they are reported through [`Prefixes::vex`].
+    #[inline]
+    pub fn xop(&self) -> bool { self.bits & 0x40 != 0 }
+    #[inline]
+    fn set_xop(&mut self) { self.bits |= 0x40 }
     #[inline]
     pub fn cs(&mut self) { self.segment = Segment::CS }
     #[inline]
-    fn set_cs(&mut self) { self.segment = Segment::CS }
+    fn set_cs(&mut self) { self.segment = Segment::CS; self.bits |= 0x8 }
     #[inline]
     pub fn ds(&self) -> bool { self.segment == Segment::DS }
     #[inline]
-    fn set_ds(&mut self) { self.segment = Segment::DS }
+    fn set_ds(&mut self) { self.segment = Segment::DS; self.bits |= 0x8 }
     #[inline]
     pub fn es(&self) -> bool { self.segment == Segment::ES }
     #[inline]
-    fn set_es(&mut self) { self.segment = Segment::ES }
+    fn set_es(&mut self) { self.segment = Segment::ES; self.bits |= 0x8 }
     #[inline]
     pub fn fs(&self) -> bool { self.segment == Segment::FS }
     #[inline]
-    fn set_fs(&mut self) { self.segment = Segment::FS }
+    fn set_fs(&mut self) { self.segment = Segment::FS; self.bits |= 0x8 }
     #[inline]
     pub fn gs(&self) -> bool { self.segment == Segment::GS }
     #[inline]
-    fn set_gs(&mut self) { self.segment = Segment::GS }
+    fn set_gs(&mut self) { self.segment = Segment::GS; self.bits |= 0x8 }
     #[inline]
     pub fn ss(&self) -> bool { self.segment == Segment::SS }
+    /// the segment selected by a segment override prefix, if there is one.
+    #[inline]
+    pub fn segment_override(&self) -> Option<Segment> {
+        if self.bits & 0x8 != 0 {
+            Some(self.segment)
+        } else {
+            None
+        }
+    }
     #[inline]
-    fn set_ss(&mut self) { self.segment = Segment::SS }
+    fn set_ss(&mut self) { self.segment = Segment::SS; self.bits |= 0x8 }
     #[inline]
     fn vex_unchecked(&self) -> PrefixVex { PrefixVex { bits: self.vex.bits } }
     #[inline]
@@ -4557,6 +5445,8 @@ impl Prefixes {
 
     #[inline]
     fn vex_from_c5(&mut self, bits: u8) {
+        // `c5` implies the `0f` map.
+        self.map_pp = 0x01 | ((bits & 0b11) << 5);
         // collect rex bits
         let r = bits & 0x80;
         let wrxb = (r >> 5) ^ 0x04;
@@ -4567,6 +5457,7 @@ impl Prefixes {
 
     #[inline]
     fn vex_from_c4(&mut self, high: u8, low: u8) {
+        self.map_pp = (high & 0b11111) | ((low & 0b11) << 5);
         let w = low & 0x80;
         let rxb = (high >> 5) ^ 0x07;
         let wrxb = rxb | w >> 4;
@@ -4577,11 +5468,14 @@ impl Prefixes {
 
     #[inline]
     fn evex_from(&mut self, b1: u8, b2: u8, b3: u8) {
+        self.map_pp = (b1 & 0b111) | ((b2 & 0b11) << 5);
         let w = b2 & 0x80;
         let rxb = ((b1 >> 5) & 0b111) ^ 0b111; // `rxb` is provided in inverted form
         let wrxb = rxb | (w >> 4);
         let l = (b3 & 0x20) >> 1;
-        let synthetic_vex = wrxb | l | 0x80;
+        // `U` is normally set; only avx10.2 clears it, so record a clear `U` instead.
+        let u_clear = ((b2 & 0x04) ^ 0x04) << 4;
+        let synthetic_vex = wrxb | l | u_clear | 0x80;
         self.vex_from(synthetic_vex);
 
         // R' is provided in inverted form
@@ -4908,6 +5802,8 @@ enum OperandCode {
     ModRM_0xf20f78 = OperandCodeBuilder::new().read_modrm().special_case(58).bits(),
     ModRM_0x660f78 = OperandCodeBuilder::new().read_modrm().special_case(59).bits(),
     ModRM_0xf30f1e = OperandCodeBuilder::new().special_case(60).bits(),
+    ModRM_0x0f1a = OperandCodeBuilder::new().special_case(114).bits(),
+    ModRM_0x0f1b = OperandCodeBuilder::new().special_case(115).bits(),
 //    ModRM_0x660f72 = OperandCodeBuilder::new().read_modrm().special_case(61).bits(),
 //    ModRM_0x660f73 = OperandCodeBuilder::new().read_modrm().special_case(62).bits(),
 //    ModRM_0x660fc7 = OperandCodeBuilder::new().read_modrm().special_case(63).bits(),
@@ -5161,6 +6057,7 @@ enum OperandCode {
     I_1 = OperandCodeBuilder::new().special_case(93).bits(),
     Iw = OperandCodeBuilder::new().special_case(94).bits(),
     Iw_Ib = OperandCodeBuilder::new().special_case(95).bits(),
+    Ap = OperandCodeBuilder::new().special_case(113).bits(),
     Ob_AL = OperandCodeBuilder::new().special_case(96).bits(),
     Ov_AX = OperandCodeBuilder::new().special_case(97).bits(),
     Sw_Ew = OperandCodeBuilder::new().special_case(98).bits(),
@@ -5402,7 +6299,7 @@ const OPCODES: [OpcodeRecord; 256] = [
     OpcodeRecord(Interpretation::Instruction(Opcode::XCHG), OperandCode::Zv_AX_R7),
     OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::CVT_AA),
     OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::CVT_DA),
-    OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::Nothing),
+    OpcodeRecord(Interpretation::Instruction(Opcode::CALLF), OperandCode::Ap),
     OpcodeRecord(Interpretation::Instruction(Opcode::WAIT), OperandCode::Nothing),
     OpcodeRecord(Interpretation::Instruction(Opcode::PUSHF), OperandCode::Nothing),
     OpcodeRecord(Interpretation::Instruction(Opcode::POPF), OperandCode::Nothing),
@@ -5496,7 +6393,7 @@ const OPCODES: [OpcodeRecord; 256] = [
 // 0xe8
     OpcodeRecord(Interpretation::Instruction(Opcode::CALL), OperandCode::Jvds),
     OpcodeRecord(Interpretation::Instruction(Opcode::JMP), OperandCode::Jvds),
-    OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::Nothing),
+    OpcodeRecord(Interpretation::Instruction(Opcode::JMPF), OperandCode::Ap),
     OpcodeRecord(Interpretation::Instruction(Opcode::JMP), OperandCode::Ibs),
     OpcodeRecord(Interpretation::Instruction(Opcode::IN), OperandCode::AL_DX),
     OpcodeRecord(Interpretation::Instruction(Opcode::IN), OperandCode::AX_DX),
@@ -5669,6 +6566,9 @@ fn read_sib<
     let scale = 1u8 << (sibbyte >> 6);
     instr.scale = scale;
 
+    // with `mod` 00, there is only a displacement if `bbb` selects one.
+    instr.zero_disp = disp == 0 && (modbits != 0b00 || (sibbyte & 7) == 0b101);
+
     let op_spec = if (sibbyte & 7) == 0b101 {
         sink.record(
             sib_start,
@@ -5769,7 +6669,7 @@ fn read_sib<
         sink.record(
             sib_start + 0,
             sib_start + 2,
-            InnerDescription::RegisterNumber("bbb", instr.regs[1].num & 0b111, instr.regs[2])
+            InnerDescription::RegisterNumber("bbb", instr.regs[1].num & 0b111, instr.regs[1])
                 .with_id(sib_start + 0)
         );
 
@@ -5914,6 +6814,7 @@ fn read_M_16bit<
         0b01 => {
             let disp_start = words.offset() as u32 * 8;
             instr.disp = read_num(words, 1)? as i8 as i32 as u32;
+            instr.zero_disp = instr.disp == 0;
             let disp_end = words.offset() as u32 * 8;
             sink.record(
                 modrm_start + 6,
@@ -5944,6 +6845,7 @@ fn read_M_16bit<
         0b10 => {
             let disp_start = words.offset() as u32 * 8;
             instr.disp = read_num(words, 2)? as i16 as i32 as u32;
+            instr.zero_disp = instr.disp == 0;
             let disp_end = words.offset() as u32 * 8;
             sink.record(
                 modrm_start + 6,
@@ -6059,6 +6961,7 @@ fn read_M<
                     .with_id(words.offset() as u32 * 8 + 3)
             );
             if disp == 0 {
+                instr.zero_disp = true;
                 OperandSpec::Deref
             } else {
                 instr.disp = disp as i32 as u32;
@@ -6079,6 +6982,23 @@ fn width_to_gp_reg_bank(width: u8) -> RegisterBank {
     }
 }
 
+/// look up an opcode after a `0f`, `0f38`, or `0f3a` escape with `read`, noting in `prefixes`
+/// whether an `f2` or `f3` prefix selected it: if the opcode is different without the prefix, the
+/// prefix is part of the opcode rather than a `rep`.
+#[inline(always)]
+fn read_escaped_opcode(opcode: u8, prefixes: &mut Prefixes, read: fn(u8, &mut Prefixes) -> OpcodeRecord) -> OpcodeRecord {
+    if !prefixes.rep_any() {
+        return read(opcode, prefixes);
+    }
+    let mut unprefixed = *prefixes;
+    unprefixed.bits &= !0x30;
+    let record = read(opcode, prefixes);
+    if read(opcode, &mut unprefixed) != record {
+        prefixes.set_mandatory_rep();
+    }
+    record
+}
+
 fn read_0f_opcode(opcode: u8, prefixes: &mut Prefixes) -> OpcodeRecord {
     // seems like f2 takes priority, then f3, then 66, then "no prefix".  for SOME instructions an
     // invalid prefix is in fact an invalid instruction. so just duplicate for the four kinds of
@@ -6112,8 +7032,8 @@ fn read_0f_opcode(opcode: u8, prefixes: &mut Prefixes) -> OpcodeRecord {
             0x17 => OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::Nothing),
             0x18 => OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::ModRM_0x0f18),
             0x19 => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
+            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1a),
+            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1b),
             0x1c => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1d => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1e => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
@@ -6389,8 +7309,8 @@ fn read_0f_opcode(opcode: u8, prefixes: &mut Prefixes) -> OpcodeRecord {
             0x17 => OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::Nothing),
             0x18 => OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::ModRM_0x0f18),
             0x19 => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
+            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1a),
+            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1b),
             0x1c => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1d => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1e => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0xf30f1e),
@@ -6666,8 +7586,8 @@ fn read_0f_opcode(opcode: u8, prefixes: &mut Prefixes) -> OpcodeRecord {
             0x17 => OpcodeRecord(Interpretation::Instruction(Opcode::MOVHPD), OperandCode::PMOVX_E_G_xmm),
             0x18 => OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::ModRM_0x0f18),
             0x19 => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
+            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1a),
+            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1b),
             0x1c => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1d => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1e => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
@@ -6940,8 +7860,8 @@ fn read_0f_opcode(opcode: u8, prefixes: &mut Prefixes) -> OpcodeRecord {
             0x17 => OpcodeRecord(Interpretation::Instruction(Opcode::MOVHPS), OperandCode::PMOVX_E_G_xmm),
             0x18 => OpcodeRecord(Interpretation::Instruction(Opcode::Invalid), OperandCode::ModRM_0x0f18),
             0x19 => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
-            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
+            0x1a => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1a),
+            0x1b => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::ModRM_0x0f1b),
             0x1c => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1d => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
             0x1e => OpcodeRecord(Interpretation::Instruction(Opcode::NOP), OperandCode::Ev),
@@ -7519,6 +8439,7 @@ fn read_with_annotations<
     // default registers to `[eax; 4]`
     instruction.regs = unsafe { core::mem::transmute(0u64) };
     instruction.mem_size = 0;
+    instruction.zero_disp = false;
     // default operands to [RegRRR, Nothing, Nothing, Nothing]
     instruction.operands = unsafe { core::mem::transmute(0x00_00_00_01) };
     instruction.operand_count = 2;
@@ -7534,7 +8455,24 @@ fn read_with_annotations<
                         .with_id(words.offset() as u32 * 8 - 9)
                 );
             }
-            if opc != Opcode::Invalid {
+            bytes::record_opcode_map(
+                sink,
+                words.offset() * 8 - 8,
+                words.offset() * 8 - 1,
+                Encoding::Legacy,
+                0,
+            );
+            // `62`, `c4`, `c5`, and `8f` are `evex`, `vex`, or `xop` prefixes rather than
+            // instructions if their `modrm` says so, and a description of `bound`, `les`, `lds`, or
+            // `pop` could not be taken back. `decode_with_annotation` describes the opcode once the
+            // instruction is decoded.
+            let provisional = [
+                OperandCode::ModRM_0x62,
+                OperandCode::ModRM_0xc4,
+                OperandCode::ModRM_0xc5,
+                OperandCode::ModRM_0x8f_Ev,
+            ].contains(&record.1);
+            if opc != Opcode::Invalid && !provisional {
                 sink.record((words.offset() - 1) as u32 * 8, (words.offset() - 1) as u32 * 8 + 7, FieldDescription {
                     desc: InnerDescription::Opcode(opc),
                     id: words.offset() as u32 * 8 - 8,
@@ -7547,7 +8485,7 @@ fn read_with_annotations<
             break record;
         } else {
             let b = nextb;
-            if words.offset() >= 15 {
+            if decoder.too_long(words.offset() as usize + 1) {
                 return Err(DecodeError::TooLong);
             }
             if b == 0x0f {
@@ -7558,15 +8496,19 @@ fn read_with_annotations<
                             .with_id(words.offset() as u32 * 8 - 9)
                     );
                 }
+                let escape_start = words.offset() * 8 - 8;
                 let b = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
                 if b == 0x38 {
+                    bytes::record_opcode_map(sink, escape_start, escape_start + 15, Encoding::Legacy, 2);
                     let b = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
-                    break read_0f38_opcode(b, &mut prefixes);
+                    break read_escaped_opcode(b, &mut prefixes, read_0f38_opcode);
                 } else if b == 0x3a {
+                    bytes::record_opcode_map(sink, escape_start, escape_start + 15, Encoding::Legacy, 3);
                     let b = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
-                    break read_0f3a_opcode(b, &mut prefixes);
+                    break read_escaped_opcode(b, &mut prefixes, read_0f3a_opcode);
                 } else {
-                    break read_0f_opcode(b, &mut prefixes);
+                    bytes::record_opcode_map(sink, escape_start, escape_start + 7, Encoding::Legacy, 1);
+                    break read_escaped_opcode(b, &mut prefixes, read_0f_opcode);
                 }
             }
 
@@ -7588,6 +8530,7 @@ fn read_with_annotations<
                         id: words.offset() as u32 * 8 - 16,
                     });
                     prefixes.set_cs();
+                    prefixes.branch_hint = 0x2e;
                 },
                 0x36 => {
                     sink.record((words.offset() - 2) as u32 * 8, (words.offset() - 2) as u32 * 8 + 7, FieldDescription {
@@ -7602,6 +8545,7 @@ fn read_with_annotations<
                         id: words.offset() as u32 * 8 - 16,
                     });
                     prefixes.set_ds();
+                    prefixes.branch_hint = 0x3e;
                 },
                 0x64 => {
                     sink.record((words.offset() - 2) as u32 * 8, (words.offset() - 2) as u32 * 8 + 7, FieldDescription {
@@ -7667,14 +8611,12 @@ fn read_with_annotations<
     instruction.prefixes = prefixes;
     read_operands(decoder, words, instruction, record.1, sink)?;
     instruction.length = words.offset() as u8;
-    if instruction.length > 15 {
+    if decoder.too_long(words.offset() as usize) {
         return Err(DecodeError::TooLong);
     }
 
-    if instruction.prefixes.lock() {
-        if !LOCKABLE_INSTRUCTIONS.contains(&instruction.opcode) || !instruction.operands[0].is_memory() {
-            return Err(DecodeError::InvalidPrefixes);
-        }
+    if !instruction.lock_valid() && !decoder.permissive_lock() {
+        return Err(DecodeError::InvalidPrefixes);
     }
 
     if decoder != &InstDecoder::default() {
@@ -7878,15 +8820,21 @@ fn read_operands<
             instruction.mem_size = 1;
             bank = RegisterBank::B;
         };
-        modrm = read_modrm(words)?;
+        modrm = read_modrm(words, sink)?;
+        if operand_code.bits() == (OperandCode::ModRM_0x8f_Ev as u16) && modrm & 0b0011_1000 != 0 {
+            // `8f` is only `pop` with an `rrr` of 0b000. otherwise, this "modrm" byte is the first
+            // byte of an amd `xop` prefix, where those bits select opcode maps 8 and up.
+            if instruction.prefixes.lock() || instruction.prefixes.operand_size() || instruction.prefixes.rep_any() {
+                // like `vex`, `xop` can't follow these prefixes.
+                return Err(DecodeError::InvalidPrefixes);
+            }
+            return xop::read_xop(words, modrm, instruction, sink);
+        }
         instruction.regs[0].bank = bank;
         instruction.regs[0].num = (modrm >> 3) & 7;
-        sink.record(
-            modrm_start + 3,
-            modrm_start + 5,
-            InnerDescription::RegisterNumber("rrr", (modrm >> 3) & 7, instruction.regs[0])
-                .with_id(modrm_start + 1)
-        );
+        // `rrr` may be part of the opcode rather than a register, and the register's bank may change
+        // with the operand code. `decode_with_annotation` describes it once the instruction is
+        // decoded.
 
         mem_oper = if modrm >= 0b11000000 {
             sink.record(
@@ -7898,14 +8846,8 @@ fn read_operands<
             if operand_code.bits() == (OperandCode::Gv_M as u16) {
                 return Err(DecodeError::InvalidOperand);
             }
-            let res = read_modrm_reg(instruction, modrm, bank)?;
-            sink.record(
-                modrm_start,
-                modrm_start + 2,
-                InnerDescription::RegisterNumber("mmm", modrm & 7, instruction.regs[1])
-                    .with_id(modrm_start + 2)
-            );
-            res
+            // as with `rrr`, the register's bank may change with the operand code.
+            read_modrm_reg(instruction, modrm, bank)?
         } else {
             read_M(words, instruction, modrm, sink)?
         };
@@ -8059,7 +9001,7 @@ fn read_operands<
                     );
                     read_imm_signed(words, 4)? as i32 as u32
                 };
-                instruction.operands[0] = OperandSpec::ImmI32;
+                instruction.operands[0] = if instruction.prefixes.operand_size() { OperandSpec::ImmI16 } else { OperandSpec::ImmI32 };
                 instruction.operand_count = 1;
                 return Ok(());
             }
@@ -8273,7 +9215,7 @@ fn read_operands<
             instruction.operand_count = 1;
         }
         15 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E(words, instruction, modrm, 1, sink)?;
             instruction.regs[0] = if instruction.prefixes.operand_size() {
@@ -8293,7 +9235,7 @@ fn read_operands<
             instruction.operand_count = 2;
         },
         16 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E(words, instruction, modrm, 2, sink)?;
             instruction.regs[0] = if instruction.prefixes.operand_size() {
@@ -8360,7 +9302,7 @@ fn read_operands<
             }
         },
         22 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E_xmm(words, instruction, modrm, sink)?;
             instruction.regs[0] =
@@ -8508,11 +9450,11 @@ fn read_operands<
                 sink.record(
                     modrm_start + 3,
                     modrm_start + 5,
-                    InnerDescription::Misc("rrr field > 0b000 for this opcode is illegal, except with XOP extensions")
+                    InnerDescription::Misc("rrr field > 0b000 for this opcode is illegal")
                         .with_id(modrm_start - 8)
                 );
-                // TODO: this is where XOP decoding would occur
-                return Err(DecodeError::IncompleteDecoder);
+                // `xop` prefixes, where `rrr` is not 0b000, are decoded before operands are read.
+                return Err(DecodeError::InvalidOpcode);
             }
             instruction.opcode = [
                 Opcode::POP,
@@ -8556,7 +9498,7 @@ fn unlikely_operands<
 >(decoder: &InstDecoder, words: &mut T, instruction: &mut Instruction, operand_code: OperandCode, mem_oper: OperandSpec, sink: &mut S) -> Result<(), DecodeError> {
     match operand_code {
         OperandCode::G_E_mm_Ib => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E_mm(words, instruction, modrm, sink)?;
             instruction.regs[0] = RegSpec { bank: RegisterBank::MM, num: (modrm >> 3) & 7 };
@@ -8570,7 +9512,7 @@ fn unlikely_operands<
             instruction.operand_count = 3;
         }
         OperandCode::G_Ev_xmm_Ib => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E_xmm(words, instruction, modrm, sink)?;
             instruction.regs[0] = RegSpec { bank: RegisterBank::X, num: (modrm >> 3) & 7 };
@@ -8592,7 +9534,7 @@ fn unlikely_operands<
             instruction.operand_count = 3;
         }
         OperandCode::PMOVX_E_G_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.regs[0] = RegSpec { bank: RegisterBank::X, num: (modrm >> 3) & 7 };
             instruction.operands[1] = OperandSpec::RegRRR;
@@ -8610,7 +9552,7 @@ fn unlikely_operands<
             }
         }
         OperandCode::PMOVX_G_E_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.regs[0] = RegSpec { bank: RegisterBank::X, num: (modrm >> 3) & 7 };
             instruction.operands[0] = OperandSpec::RegRRR;
@@ -8633,7 +9575,7 @@ fn unlikely_operands<
             }
         }
         OperandCode::INV_Gv_M => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm >= 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -8654,7 +9596,7 @@ fn unlikely_operands<
             }
         }
         OperandCode::ModRM_0xc4 => {
-            let modrm = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0b11000000 == 0b11000000 {
                 // interpret the c4 as a vex prefix
                 if instruction.prefixes.lock() || instruction.prefixes.operand_size() || instruction.prefixes.rep() || instruction.prefixes.repnz() {
@@ -8687,7 +9629,7 @@ fn unlikely_operands<
             }
         },
         OperandCode::ModRM_0xc5 => {
-            let modrm = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
+            let modrm = read_modrm(words, sink)?;
             if (modrm & 0b1100_0000) == 0b1100_0000 {
                 // interpret the c5 as a vex prefix
                 if instruction.prefixes.lock() || instruction.prefixes.operand_size() || instruction.prefixes.rep() || instruction.prefixes.repnz() {
@@ -8720,7 +9662,7 @@ fn unlikely_operands<
             }
         },
         OperandCode::G_U_xmm_Ub => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E_xmm(words, instruction, modrm, sink)?;
             if instruction.operands[1] != OperandSpec::RegMMM {
@@ -8736,7 +9678,7 @@ fn unlikely_operands<
         OperandCode::ModRM_0xf20f78 => {
             instruction.opcode = Opcode::INSERTQ;
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             if modrm < 0b11_000_000 {
                 return Err(DecodeError::InvalidOperand);
@@ -8759,7 +9701,7 @@ fn unlikely_operands<
         OperandCode::ModRM_0x660f78 => {
             instruction.opcode = Opcode::EXTRQ;
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             if modrm < 0b11_000_000 {
                 return Err(DecodeError::InvalidOperand);
@@ -8782,7 +9724,7 @@ fn unlikely_operands<
 
         }
         OperandCode::ModRM_0xf30f1e => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             match modrm {
                 0xfa => {
                     instruction.opcode = Opcode::ENDBR64;
@@ -8792,6 +9734,12 @@ fn unlikely_operands<
                     instruction.opcode = Opcode::ENDBR32;
                     instruction.operand_count = 0;
                 },
+                0xc8..=0xcf => {
+                    instruction.opcode = Opcode::RDSSP;
+                    instruction.regs[1] = RegSpec::from_parts(modrm & 7, RegisterBank::D);
+                    instruction.operands[0] = OperandSpec::RegMMM;
+                    instruction.operand_count = 1;
+                },
                 _ => {
                     let (sz, bank) = if !instruction.prefixes.operand_size() {
                         (4, RegisterBank::D)
@@ -8807,8 +9755,83 @@ fn unlikely_operands<
                 }
             };
         }
+        OperandCode::ModRM_0x0f1a |
+        OperandCode::ModRM_0x0f1b => {
+            let modrm = read_modrm(words, sink)?;
+            if !decoder.mpx() {
+                // without mpx, these are the reserved `nop Ev` they execute as on processors that
+                // predate it or have removed it.
+                let opwidth = if instruction.prefixes.operand_size() {
+                    2
+                } else {
+                    4
+                };
+                instruction.operands[0] = read_E(words, instruction, modrm, opwidth, sink)?;
+                instruction.mem_size = opwidth;
+                instruction.operand_count = 1;
+                return Ok(());
+            }
+
+            let r = (modrm >> 3) & 7;
+            if r >= 4 {
+                // there are only four bound registers.
+                return Err(DecodeError::InvalidOperand);
+            }
+            instruction.regs[0] = RegSpec::bnd(r);
+            let store = operand_code == OperandCode::ModRM_0x0f1b;
+            // as with the rest of `0f`, `f2` takes priority over `f3`, which takes priority over
+            // `66`.
+            // only `bndmov` accesses its memory operand; the others use just its address, and
+            // `bndldx` and `bndstx` a bound table entry found from it.
+            instruction.mem_size = 63;
+            let rm = if instruction.prefixes.repnz() || instruction.prefixes.rep() {
+                instruction.opcode = match (instruction.prefixes.repnz(), store) {
+                    (true, false) => Opcode::BNDCU,
+                    (true, true) => Opcode::BNDCN,
+                    (false, false) => Opcode::BNDCL,
+                    (false, true) => Opcode::BNDMK,
+                };
+                if instruction.opcode == Opcode::BNDMK {
+                    // `bndmk` takes its bounds from an address computation, not a register.
+                    if modrm >= 0xc0 {
+                        return Err(DecodeError::InvalidOperand);
+                    }
+                    read_M(words, instruction, modrm, sink)?
+                } else {
+                    // bound checks compare an address, which is always 32 bits here.
+                    read_E(words, instruction, modrm, 4, sink)?
+                }
+            } else if instruction.prefixes.operand_size() {
+                instruction.opcode = Opcode::BNDMOV;
+                if modrm >= 0xc0 {
+                    if modrm & 7 >= 4 {
+                        return Err(DecodeError::InvalidOperand);
+                    }
+                    read_modrm_reg(instruction, modrm, RegisterBank::Bnd)?
+                } else {
+                    instruction.mem_size = 8;
+                    read_M(words, instruction, modrm, sink)?
+                }
+            } else {
+                // `bndldx` and `bndstx` find a bound table entry from the base and index of their
+                // memory operand, so there must be one.
+                instruction.opcode = if store { Opcode::BNDSTX } else { Opcode::BNDLDX };
+                if modrm >= 0xc0 {
+                    return Err(DecodeError::InvalidOperand);
+                }
+                read_M(words, instruction, modrm, sink)?
+            };
+            if store && instruction.opcode != Opcode::BNDCN && instruction.opcode != Opcode::BNDMK {
+                instruction.operands[0] = rm;
+                instruction.operands[1] = OperandSpec::RegRRR;
+            } else {
+                instruction.operands[0] = OperandSpec::RegRRR;
+                instruction.operands[1] = rm;
+            }
+            instruction.operand_count = 2;
+        }
         OperandCode::G_E_xmm_Ub => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E_xmm(words, instruction, modrm, sink)?;
             instruction.regs[0] =
@@ -8881,7 +9904,7 @@ fn unlikely_operands<
             instruction.operand_count = 3;
         }
         OperandCode::G_mm_Ew_Ib => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[1] = read_E(words, instruction, modrm, 4, sink)?;
             instruction.regs[0] =
@@ -8921,7 +9944,7 @@ fn unlikely_operands<
             instruction.operand_count = 2;
         },
         OperandCode::Gv_Ew_LSL => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if instruction.prefixes.operand_size() {
                 instruction.regs[0] =
                     RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::W);
@@ -8940,7 +9963,7 @@ fn unlikely_operands<
             instruction.operand_count = 2;
         },
         OperandCode::Gd_Ev => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             let opwidth = if instruction.prefixes.operand_size() {
                 2
@@ -9026,6 +10049,17 @@ fn unlikely_operands<
             return Err(DecodeError::IncompleteDecoder);
         }
         */
+        OperandCode::Ap => {
+            // the offset is as wide as the operand size, followed by a 16-bit segment.
+            if !instruction.prefixes.operand_size() {
+                instruction.imm = read_num(words, 4)?;
+            } else {
+                instruction.imm = read_num(words, 2)?;
+            }
+            instruction.disp = read_num(words, 2)?;
+            instruction.operands[0] = OperandSpec::AbsoluteFarAddress;
+            instruction.operand_count = 1;
+        }
         OperandCode::Iw_Ib => {
             instruction.disp = read_num(words, 2)?;
             instruction.imm = read_num(words, 1)?;
@@ -9056,7 +10090,7 @@ fn unlikely_operands<
                 return Err(DecodeError::InvalidOpcode);
             }
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[0] = read_E(words, instruction, modrm, 4, sink)?;
             instruction.operands[1] = OperandSpec::RegRRR;
@@ -9072,7 +10106,7 @@ fn unlikely_operands<
                 return Err(DecodeError::InvalidOpcode);
             }
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.operands[0] = OperandSpec::RegRRR;
             instruction.regs[0] =
@@ -9097,7 +10131,7 @@ fn unlikely_operands<
         },
         OperandCode::MOVQ_f30f => {
             instruction.operand_count = 2;
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             instruction.operands[1] = read_E_xmm(words, instruction, modrm, sink)?;
@@ -9106,7 +10140,7 @@ fn unlikely_operands<
             }
         }
         OperandCode::ModRM_0x0f0d => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let r = (modrm >> 3) & 0b111;
 
             let opwidth = if instruction.prefixes.operand_size() {
@@ -9133,7 +10167,7 @@ fn unlikely_operands<
             // 3dnow instructions are WILD, the opcode is encoded as an imm8 trailing the
             // instruction.
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.operands[1] = read_E_mm(words, instruction, modrm, sink)?;
             instruction.operands[0] = OperandSpec::RegRRR;
             instruction.regs[0] = RegSpec { bank: RegisterBank::MM, num: (modrm >> 3) & 7 };
@@ -9141,7 +10175,7 @@ fn unlikely_operands<
                 instruction.mem_size = 8;
             }
 
-            let opcode = read_modrm(words)?;
+            let opcode = read_modrm(words, sink)?;
             match opcode {
                 0x0c => {
                     instruction.opcode = Opcode::PI2FW;
@@ -9222,7 +10256,7 @@ fn unlikely_operands<
         }
         OperandCode::ModRM_0x0fc7 => {
             if instruction.prefixes.repnz() {
-                let modrm = read_modrm(words)?;
+                let modrm = read_modrm(words, sink)?;
                 let is_reg = (modrm & 0xc0) == 0xc0;
 
                 let r = (modrm >> 3) & 7;
@@ -9249,7 +10283,7 @@ fn unlikely_operands<
                 } else {
                     4
                 };
-                let modrm = read_modrm(words)?;
+                let modrm = read_modrm(words, sink)?;
                 let is_reg = (modrm & 0xc0) == 0xc0;
 
                 let r = (modrm >> 3) & 7;
@@ -9306,7 +10340,7 @@ fn unlikely_operands<
                 } else {
                     4
                 };
-                let modrm = read_modrm(words)?;
+                let modrm = read_modrm(words, sink)?;
                 let is_reg = (modrm & 0xc0) == 0xc0;
 
                 let r = (modrm >> 3) & 7;
@@ -9349,7 +10383,7 @@ fn unlikely_operands<
                 return Ok(());
             }
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let is_reg = (modrm & 0xc0) == 0xc0;
 
             let r = (modrm >> 3) & 0b111;
@@ -9424,7 +10458,7 @@ fn unlikely_operands<
 
             instruction.operand_count = 2;
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -9461,7 +10495,7 @@ fn unlikely_operands<
 
             instruction.operand_count = 2;
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -9498,7 +10532,7 @@ fn unlikely_operands<
 
             instruction.operand_count = 2;
 
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -9538,7 +10572,7 @@ fn unlikely_operands<
             instruction.operands[1] = OperandSpec::ImmU8;
         },
         OperandCode::ModRM_0xf30f38d8 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let r = (modrm >> 3) & 7;
             instruction.mem_size = 63;
             match r {
@@ -9632,7 +10666,7 @@ fn unlikely_operands<
             instruction.regs[1].bank = RegisterBank::D;
         }
         OperandCode::ModRM_0xf30f3af0 => {
-            let modrm = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 return Err(DecodeError::InvalidOpcode);
                 // invalid
@@ -9925,7 +10959,7 @@ fn unlikely_operands<
             instruction.regs[0].bank = RegisterBank::X;
         }
         OperandCode::Ew_Gw => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             instruction.regs[0] =
                 RegSpec { bank: RegisterBank::W, num: (modrm >> 3) & 7 };
@@ -9936,7 +10970,7 @@ fn unlikely_operands<
         },
         OperandCode::Ew_Sw => {
             let opwidth = 2;
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             // check r
             if ((modrm >> 3) & 7) > 5 {
@@ -9960,7 +10994,7 @@ fn unlikely_operands<
             }
         },
         OperandCode::Sw_Ew => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             // check r
             if ((modrm >> 3) & 7) > 5 {
@@ -10028,7 +11062,7 @@ fn unlikely_operands<
         }
         OperandCode::ModRM_0x0f00 => {
             instruction.operand_count = 1;
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let r = (modrm >> 3) & 7;
             if r == 0 {
                 instruction.opcode = Opcode::SLDT;
@@ -10065,7 +11099,7 @@ fn unlikely_operands<
             } else {
                 4
             };
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let r = (modrm >> 3) & 7;
             if r == 0 {
                 let mod_bits = modrm >> 6;
@@ -10209,7 +11243,13 @@ fn unlikely_operands<
                             instruction.operands[0] = OperandSpec::RegRRR;
                         },
                         0b001 => {
-                            instruction.opcode = Opcode::VMMCALL;
+                            // `vmgexit` is `vmmcall` with a `rep` or `repnz` prefix. processors
+                            // without sev-es ignore the prefix, and execute it as `vmmcall`.
+                            if instruction.prefixes.rep() || instruction.prefixes.repnz() {
+                                instruction.opcode = Opcode::VMGEXIT;
+                            } else {
+                                instruction.opcode = Opcode::VMMCALL;
+                            }
                             instruction.operands[0] = OperandSpec::Nothing;
                             instruction.operand_count = 0;
                         },
@@ -10290,8 +11330,14 @@ fn unlikely_operands<
                             instruction.operand_count = 0;
                             return Ok(());
                         }
-                        if !instruction.prefixes.rep() || instruction.prefixes.repnz() {
-                            return Err(DecodeError::InvalidOpcode);
+                        if !instruction.prefixes.rep() {
+                            if instruction.prefixes.operand_size() {
+                                return Err(DecodeError::InvalidOpcode);
+                            }
+                            instruction.opcode = Opcode::SERIALIZE;
+                            instruction.operands[0] = OperandSpec::Nothing;
+                            instruction.operand_count = 0;
+                            return Ok(());
                         }
                         instruction.opcode = Opcode::SETSSBSY;
                         instruction.operands[0] = OperandSpec::Nothing;
@@ -10466,7 +11512,7 @@ fn unlikely_operands<
             }
         }
         OperandCode::ModRM_0x0fae => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let r = (modrm >> 3) & 7;
             let m = modrm & 7;
 
@@ -10672,7 +11718,7 @@ fn unlikely_operands<
             } else {
                 4
             };
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let r = (modrm >> 3) & 7;
             match r {
                 0 | 1 | 2 | 3 => {
@@ -10708,7 +11754,7 @@ fn unlikely_operands<
         op @ OperandCode::Rq_Dq_0 |
         op @ OperandCode::Cq_Rq_0 |
         op @ OperandCode::Dq_Rq_0 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let m = modrm & 7;
             let r = (modrm >> 3) & 7;
 
@@ -10911,7 +11957,7 @@ fn unlikely_operands<
             instruction.operands[0] = temp;
         }
         OperandCode::ModRM_0x62 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
 
             if modrm < 0xc0 {
                 instruction.regs[0] =
@@ -10992,7 +12038,7 @@ fn decode_x87<
     }
 
     // every x87 instruction is conditional on rrr bits
-    let modrm = read_modrm(words)?;
+    let modrm = read_modrm(words, sink)?;
     let r = (modrm >> 3) & 0b111;
 
     let (opcode, x87_operands) = match operand_code {
@@ -11475,7 +12521,13 @@ fn decode_x87<
             if instruction.operands[0] == OperandSpec::RegMMM {
                 return Err(DecodeError::InvalidOperand);
             }
-            instruction.mem_size = 63;
+            // the x87 environment is 14 bytes with a 16-bit operand size, and 28 bytes
+            // otherwise. `fnsave` and `frstor` follow it with the eight 10-byte registers.
+            let env_size = if instruction.prefixes.operand_size() { 14 } else { 28 };
+            instruction.mem_size = match instruction.opcode {
+                Opcode::FNSAVE | Opcode::FRSTOR => env_size + 80,
+                _ => env_size,
+            };
         }
         OperandCodeX87::Nothing => {
             instruction.operand_count = 0;
@@ -11522,6 +12574,16 @@ fn read_imm_unsigned<T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as y
 }
 
 #[inline]
-fn read_modrm<T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as yaxpeax_arch::Arch>::Word>>(words: &mut T) -> Result<u8, DecodeError> {
-    words.next().ok().ok_or(DecodeError::ExhaustedInput)
+fn read_modrm<
+    T: Reader<<Arch as yaxpeax_arch::Arch>::Address, <Arch as yaxpeax_arch::Arch>::Word>,
+    S: DescriptionSink<FieldDescription>,
+>(words: &mut T, sink: &mut S) -> Result<u8, DecodeError> {
+    let modrm = words.next().ok().ok_or(DecodeError::ExhaustedInput)?;
+    sink.record(
+        words.offset() * 8 - 8,
+        words.offset() * 8 - 1,
+        InnerDescription::Misc(bytes::MODRM_DESCRIPTION)
+            .with_id(words.offset() * 8 - 8)
+    );
+    Ok(modrm)
 }
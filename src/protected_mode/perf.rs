@@ -0,0 +1,3 @@
+use crate::protected_mode::{Opcode, PerfInfo, Uarch};
+
+include!("../shared/perf.in");
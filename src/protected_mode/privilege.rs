@@ -0,0 +1,3 @@
+use crate::protected_mode::{Instruction, Opcode, Operand, PrivilegeLevel, RegisterBank};
+
+include!("../shared/privilege.in");
@@ -0,0 +1,27 @@
+use crate::RelocateError;
+use crate::protected_mode::branch::branch_target_mask;
+use crate::protected_mode::{Category, Instruction, Opcode, OperandSpec};
+
+/// the width of the immediate an operand `spec` is encoded as, or the `is4` byte that selects a
+/// `Reg4` operand. these are the last bytes of an instruction.
+pub(crate) fn trailing_width(spec: OperandSpec) -> Option<u8> {
+    match spec {
+        OperandSpec::ImmI8 | OperandSpec::ImmU8 | OperandSpec::Reg4 => Some(1),
+        OperandSpec::ImmI16 | OperandSpec::ImmU16 => Some(2),
+        OperandSpec::ImmI32 => Some(4),
+        _ => None,
+    }
+}
+
+const NEAR_DISPLACEMENT_WIDTH: u8 = 4;
+
+/// there is no `rip`-relative addressing outside of 64-bit code.
+fn rip_relative_field(_inst: &Instruction) -> Option<(u8, u64)> {
+    None
+}
+
+fn can_widen(_inst: &Instruction) -> bool {
+    true
+}
+
+include!("../shared/relocate.in");
@@ -0,0 +1,3 @@
+use crate::protected_mode::{Category, Instruction, Opcode, RepSemantics};
+
+include!("../shared/rep.in");
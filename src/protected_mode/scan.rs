@@ -0,0 +1,9 @@
+use yaxpeax_arch::{Decoder, U8Reader};
+
+use crate::protected_mode::{InstDecoder, Instruction, Opcode, Operand, RegSpec};
+
+pub(crate) fn immediate_value(op: &Operand) -> Option<i64> {
+    op.immediate_value().map(|imm| imm.signed())
+}
+
+include!("../shared/scan.in");
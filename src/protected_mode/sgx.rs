@@ -0,0 +1,4 @@
+use crate::SgxFunction;
+use crate::protected_mode::{Instruction, Opcode, RegSpec, RegValueSource};
+
+include!("../shared/sgx.in");
@@ -0,0 +1,3 @@
+use crate::protected_mode::{Category, ElementType, Instruction, Operand, RegisterBank, VectorShape};
+
+include!("../shared/shape.in");
@@ -0,0 +1,28 @@
+use crate::SynthesizeError;
+use crate::protected_mode::{Instruction, Opcode, Operand, OperandSpec, RegSpec};
+
+fn synthesize_mode_operand(synthesis: &mut Synthesis, op: &Operand) -> Result<OperandSpec, SynthesizeError> {
+    match *op {
+        Operand::DisplacementU16(disp) => synthesis.memory(OperandSpec::DispU16, None, None, disp as u64),
+        Operand::RegIndexBase(base, index) => {
+            synthesis.memory(OperandSpec::RegIndexBase, Some(base), Some((index, 1)), 0)
+        }
+        Operand::RegIndexBaseDisp(base, index, disp) => {
+            synthesis.memory(OperandSpec::RegIndexBaseDisp, Some(base), Some((index, 1)), disp as u64)
+        }
+        Operand::AbsoluteFarAddress { segment, address } => {
+            // the segment is held in the displacement, which an instruction with a far address has
+            // no other use for.
+            if synthesis.imm_used || synthesis.disp_used {
+                return Err(SynthesizeError::TooManyImmediates);
+            }
+            synthesis.immediate(OperandSpec::AbsoluteFarAddress, address as u64)?;
+            synthesis.disp_used = true;
+            synthesis.inst.disp = segment as u32;
+            Ok(OperandSpec::AbsoluteFarAddress)
+        }
+        _ => unreachable!("operand is handled by Instruction::synthesize"),
+    }
+}
+
+include!("../shared/synthesize.in");
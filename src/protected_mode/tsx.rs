@@ -0,0 +1,4 @@
+use crate::{HleHint, RtmEffect};
+use crate::protected_mode::{BranchTarget, Instruction, Opcode};
+
+include!("../shared/tsx.in");
@@ -1,3 +1,39 @@
+use crate::Cpu;
+use crate::protected_mode::InstDecoder;
+
+impl InstDecoder {
+    /// instantiates a decoder that accepts the instructions `cpu` does, and rejects those it
+    /// would not decode. see the functions in [`amd`] and [`intel`] for what each preset includes.
+    pub fn for_cpu(cpu: Cpu) -> InstDecoder {
+        match cpu {
+            Cpu::K8 => amd::k8(),
+            Cpu::K10 => amd::k10(),
+            Cpu::Bulldozer => amd::bulldozer(),
+            Cpu::Piledriver => amd::piledriver(),
+            Cpu::Steamroller => amd::steamroller(),
+            Cpu::Excavator => amd::excavator(),
+            Cpu::Zen => amd::zen(),
+            Cpu::Zen2 => amd::zen2(),
+            Cpu::Zen3 => amd::zen3(),
+            Cpu::Zen4 => amd::zen4(),
+            Cpu::Netburst => intel::netburst(),
+            Cpu::Core => intel::core(),
+            Cpu::Penryn => intel::penryn(),
+            Cpu::Nehalem => intel::nehalem(),
+            Cpu::Westmere => intel::westmere(),
+            Cpu::SandyBridge => intel::sandybridge(),
+            Cpu::IvyBridge => intel::ivybridge(),
+            Cpu::Haswell => intel::haswell(),
+            Cpu::HaswellEx => intel::haswell_ex(),
+            Cpu::Broadwell => intel::broadwell(),
+            Cpu::Skylake => intel::skylake(),
+            Cpu::KabyLake => intel::kabylake(),
+            Cpu::IceLake => intel::icelake(),
+            Cpu::AlderLake => intel::alderlake(),
+        }
+    }
+}
+
 pub mod amd {
     //! most information about instruction set extensions for microarchitectures here was sourced
     //! from
@@ -23,12 +59,19 @@ pub mod amd {
     /// support - SSE2 and no later.
     pub fn k8() -> InstDecoder {
         InstDecoder::minimal()
+            .with_3dnow()
     }
 
     /// `k10` was the successor to `k8`, launched in 2007. `k10` cores extended SSE support through
     /// to SSE4.2a, as well as consistent `cmov` support, among other features.
     pub fn k10() -> InstDecoder {
-        k8()
+        k10_extensions()
+            .with_3dnow()
+    }
+
+    /// the extensions `k10` supports, less 3dnow!, which amd dropped after `k10`.
+    fn k10_extensions() -> InstDecoder {
+        InstDecoder::minimal()
             .with_cmov()
             .with_cmpxchg16b()
             .with_svm()
@@ -44,7 +87,7 @@ pub mod amd {
     /// `Bulldozer` was the successor to `K10`, launched in 2011. `Bulldozer` cores include AVX
     /// support among other extensions, and are notable for including `AESNI`.
     pub fn bulldozer() -> InstDecoder {
-        k10()
+        k10_extensions()
             .with_bmi1()
             .with_aesni()
             .with_pclmulqdq()
@@ -86,7 +129,7 @@ pub mod amd {
     /// instructions to AVX2 and discarded FMA4, TBM, and XOP extensions. they also gained ADX,
     /// SHA, RDSEED, and other extensions.
     pub fn zen() -> InstDecoder {
-        k10()
+        k10_extensions()
             .with_avx()
             .with_avx2()
             .with_bmi1()
@@ -102,6 +145,40 @@ pub mod amd {
             .with_fma3()
             // TODO: XSAVEC, XSAVES, XRSTORS, CLFLUSHOPT, CLZERO?
     }
+
+    /// `Zen 2` was the successor to `Zen`, launched in 2019. it added `CLWB`, alongside
+    /// `CLFLUSHOPT` that `Zen` already supported.
+    pub fn zen2() -> InstDecoder {
+        zen()
+            .with_clflushopt()
+            .with_clwb()
+    }
+
+    /// `Zen 3` was the successor to `Zen 2`, launched in 2020. it added `VAES`, 256-bit
+    /// `VPCLMULQDQ`, and `INVPCID`.
+    pub fn zen3() -> InstDecoder {
+        zen2()
+            .with_vaes()
+            .with_invpcid()
+    }
+
+    /// `Zen 4` was the successor to `Zen 3`, launched in 2022. it was the first amd
+    /// microarchitecture with AVX512, supporting the subsets from `Ice Lake` and later, as well as
+    /// `GFNI`.
+    pub fn zen4() -> InstDecoder {
+        zen3()
+            .with_avx512_f()
+            .with_avx512_dq()
+            .with_avx512_cd()
+            .with_avx512_bw()
+            .with_avx512_vl()
+            .with_avx512_fma()
+            .with_avx512_vbmi()
+            .with_avx512_vbmi2()
+            .with_avx512_bitalg()
+            .with_avx512_vpopcntdq()
+            .with_gfni()
+    }
 }
 
 pub mod intel {
@@ -119,8 +196,8 @@ pub mod intel {
             .with_sse3()
     }
 
-    /// `Core` was the successor to `Netburst`, launched in 2006. it included up to SSE4, with
-    /// processors using this architecture shipped under the names "Merom", "Conroe", and
+    /// `Core` was the successor to `Netburst`, launched in 2006. it included up to SSE4 and VMX,
+    /// with processors using this architecture shipped under the names "Merom", "Conroe", and
     /// "Woodcrest", for mobile, desktop, and server processors respectively. not to be confused
     /// with the later `Nehalem` microarchitecture that introduced the `Core i*` product lines,
     /// `Core 2 *` processors used the `Core` architecture.
@@ -128,6 +205,7 @@ pub mod intel {
         netburst()
             .with_ssse3()
             .with_sse4()
+            .with_vmx()
     }
 
     /// `Penryn` was the successor to `Core`, launched in early 2008. it added SSE4.1, along with
@@ -222,5 +300,44 @@ pub mod intel {
     pub fn kabylake() -> InstDecoder {
         skylake()
     }
-    // ice lake is shipping so that should probably be included...
+
+    /// `Ice Lake` was the successor to `Kaby Lake` for client parts, launched in 2019. it dropped
+    /// MPX, and was the first client microarchitecture with AVX512, in its `F`, `DQ`, `CD`, `BW`,
+    /// `VL`, `IFMA`, `VBMI`, `VBMI2`, `BITALG`, and `VPOPCNTDQ` subsets. it also added `GFNI`,
+    /// `VAES`, and `SHA`.
+    pub fn icelake() -> InstDecoder {
+        broadwell()
+            .with_sgx()
+            .with_clflushopt()
+            .with_avx512_f()
+            .with_avx512_dq()
+            .with_avx512_cd()
+            .with_avx512_bw()
+            .with_avx512_vl()
+            .with_avx512_fma()
+            .with_avx512_vbmi()
+            .with_avx512_vbmi2()
+            .with_avx512_bitalg()
+            .with_avx512_vpopcntdq()
+            .with_gfni()
+            .with_vaes()
+            .with_sha()
+    }
+
+    /// `Alder Lake` was launched in 2021, pairing performance and efficiency cores. both kinds
+    /// of core must decode the same instructions, so AVX512 is not supported, nor are TSX, MPX, or
+    /// SGX. it does add the `vex`-encoded `AVX-VNNI`, along with `GFNI`, `VAES`, `SHA`, and
+    /// `CLWB`.
+    pub fn alderlake() -> InstDecoder {
+        haswell()
+            .with_adx()
+            .with_rdseed()
+            .with_prefetchw()
+            .with_clflushopt()
+            .with_clwb()
+            .with_gfni()
+            .with_vaes()
+            .with_sha()
+            .with_avx_vnni()
+    }
 }
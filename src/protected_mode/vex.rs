@@ -1,6 +1,7 @@
 use yaxpeax_arch::Reader;
 use yaxpeax_arch::annotation::DescriptionSink;
 
+use crate::Encoding;
 use crate::protected_mode::Arch;
 use crate::protected_mode::OperandSpec;
 use crate::protected_mode::DecodeError;
@@ -10,6 +11,7 @@ use crate::protected_mode::RegisterBank;
 use crate::protected_mode::InnerDescription;
 use crate::protected_mode::Instruction;
 use crate::protected_mode::Opcode;
+use crate::protected_mode::bytes;
 use crate::protected_mode::read_modrm;
 use crate::protected_mode::read_E;
 use crate::protected_mode::read_E_xmm;
@@ -151,13 +153,8 @@ pub(crate) fn three_byte_vex<
         num: ((vex_byte_two >> 3) & 0b1111) ^ 0b1111,
     };
 
-    sink.record(
-        vex_start + 11,
-        vex_start + 14,
-        InnerDescription::RegisterNumber("vvvv", instruction.regs[3].num, instruction.regs[3])
-            .with_id(vex_start + 2)
-    );
-
+    // `vvvv` may not name a register, and its bank depends on the opcode. `decode_with_annotation`
+    // describes it once the instruction is decoded.
     sink.record(
         vex_start + 7,
         vex_start + 7,
@@ -211,6 +208,9 @@ pub(crate) fn three_byte_vex<
 
     instruction.prefixes.vex_from_c4(vex_byte_one, vex_byte_two);
 
+    // the `c4` or `c5` byte and the rest of the prefix select an opcode map together.
+    bytes::record_opcode_map(sink, vex_start - 8, vex_start + 23, Encoding::Vex, vex_byte_one & 0b11111);
+
     read_vex_instruction(m, words, instruction, p, sink)?;
     instruction.length = words.offset() as u8;
     instruction.regs[3].num &= 0b0111; // ignore bit 4 in 32-bit mode
@@ -247,13 +247,8 @@ pub(crate) fn two_byte_vex<
             .with_id(vex_start)
     );
 
-    sink.record(
-        vex_start + 3,
-        vex_start + 6,
-        InnerDescription::RegisterNumber("vvvv", instruction.regs[3].num, instruction.regs[3])
-            .with_id(vex_start + 2)
-    );
-
+    // `vvvv` may not name a register, and its bank depends on the opcode. `decode_with_annotation`
+    // describes it once the instruction is decoded.
     sink.record(
         vex_start + 2,
         vex_start + 2,
@@ -277,6 +272,9 @@ pub(crate) fn two_byte_vex<
 
     instruction.prefixes.vex_from_c5(vex_byte);
 
+    // the `c4` or `c5` byte and the rest of the prefix select an opcode map together.
+    bytes::record_opcode_map(sink, vex_start - 8, vex_start + 15, Encoding::Vex, 1);
+
     read_vex_instruction(VEXOpcodeMap::Map0F, words, instruction, p, sink)?;
     instruction.length = words.offset() as u8;
     instruction.regs[3].num &= 0b0111; // ignore bit 4 in 32-bit mode
@@ -290,7 +288,7 @@ fn read_vex_operands<
 //    println!("operand code: {:?}", operand_code);
     match operand_code {
         VEXOperandCode::VPS_71 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
@@ -320,7 +318,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::VPS_71_L => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
@@ -353,7 +351,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::VPS_72 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
@@ -383,7 +381,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::VPS_72_L => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
@@ -413,7 +411,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::VPS_73 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
@@ -446,7 +444,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::VPS_73_L => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 != 0xc0 {
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
@@ -486,7 +484,7 @@ fn read_vex_operands<
         }
         VEXOperandCode::VMOVSS_10 |
         VEXOperandCode::VMOVSD_10 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -515,7 +513,7 @@ fn read_vex_operands<
         },
         VEXOperandCode::VMOVSS_11 |
         VEXOperandCode::VMOVSD_11 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -544,7 +542,7 @@ fn read_vex_operands<
             Ok(())
         },
         VEXOperandCode::VMOVLPS_12 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.opcode = if modrm & 0xc0 == 0xc0 {
                 Opcode::VMOVHLPS
             } else {
@@ -560,7 +558,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::VMOVHPS_16 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.opcode = if modrm & 0xc0 == 0xc0 {
                 Opcode::VMOVLHPS
             } else {
@@ -590,7 +588,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E(words, instruction, modrm, 4, sink)?;
@@ -623,7 +621,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E(words, instruction, modrm, 4, sink)?;
@@ -640,7 +638,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E(words, instruction, modrm, 4, sink)?;
@@ -657,7 +655,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::D);
             let mem_oper = read_E(words, instruction, modrm, 4, sink)?;
@@ -676,7 +674,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::D);
             let mem_oper = read_E(words, instruction, modrm, 4, sink)?;
@@ -696,7 +694,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -729,7 +727,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::D);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -746,7 +744,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::D);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -763,7 +761,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::D);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -782,7 +780,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -801,7 +799,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::Y);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -821,7 +819,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::D);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -838,7 +836,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::D);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -858,7 +856,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             match (op, modrm & 0xc0) {
                 (VEXOperandCode::G_U_xmm, 0xc0) => {
                     /* this is the only accepted operand */
@@ -894,7 +892,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -911,7 +909,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -929,7 +927,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 == 0xc0 {
                 if let VEXOperandCode::G_ymm_M_xmm = op {
                     return Err(DecodeError::InvalidOperand);
@@ -957,7 +955,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::Y);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -976,7 +974,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             match (op, modrm & 0xc0) {
                 (VEXOperandCode::M_G_ymm, 0xc0) => {
                     return Err(DecodeError::InvalidOperand);
@@ -1004,7 +1002,7 @@ fn read_vex_operands<
                 instruction.opcode = Opcode::Invalid;
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             match (op, modrm & 0xc0) {
                 (VEXOperandCode::G_M_ymm, 0xc0) => {
                     return Err(DecodeError::InvalidOperand);
@@ -1027,7 +1025,7 @@ fn read_vex_operands<
         }
         op @ VEXOperandCode::G_V_E_ymm |
         op @ VEXOperandCode::G_V_M_ymm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if let VEXOperandCode::G_V_M_ymm = op {
                 if modrm & 0xc0 == 0xc0 {
                     return Err(DecodeError::InvalidOperand);
@@ -1047,7 +1045,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_E_ymm_imm8 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::Y);
             instruction.regs[3].bank = RegisterBank::Y;
@@ -1064,7 +1062,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::M_V_G_ymm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 == 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -1082,7 +1080,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_M_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 == 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -1103,7 +1101,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_E_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -1123,7 +1121,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_xmm_Ed => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E(words, instruction, modrm, 4, sink)?;
@@ -1137,7 +1135,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_E_xmm_imm8 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -1153,7 +1151,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_ymm_V_ymm_E_xmm_imm8 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::Y);
             instruction.regs[3].bank = RegisterBank::Y;
@@ -1170,7 +1168,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::M_V_G_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             if modrm & 0xc0 == 0xc0 {
                 return Err(DecodeError::InvalidOperand);
             }
@@ -1189,7 +1187,7 @@ fn read_vex_operands<
         }
 
         VEXOperandCode::G_Ex_V_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -1204,7 +1202,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_Ey_V_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -1220,7 +1218,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_Ey_V_ymm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::Y);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -1240,7 +1238,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_E => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let (opwidth, bank) = (4, RegisterBank::D);
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, bank);
@@ -1256,7 +1254,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_E_V => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let (opwidth, bank) = (4, RegisterBank::D);
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, bank);
@@ -1272,7 +1270,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_E_Ib => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             let (opwidth, bank) = (4, RegisterBank::D);
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, bank);
@@ -1288,7 +1286,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::BMI1_F3 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.opcode = match (modrm >> 3) & 7 {
                 1 => {
                     Opcode::BLSR
@@ -1318,7 +1316,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::MXCSR => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.opcode = match (modrm >> 3) & 7 {
                 2 => {
                     Opcode::VLDMXCSR
@@ -1346,7 +1344,7 @@ fn read_vex_operands<
             if instruction.regs[3].num != 0 {
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             let mem_oper = read_E_xmm(words, instruction, modrm, sink)?;
@@ -1364,7 +1362,7 @@ fn read_vex_operands<
             if instruction.regs[3].num != 0 {
                 return Err(DecodeError::InvalidOperand);
             }
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::Y);
             let mem_oper = read_E_ymm(words, instruction, modrm, sink)?;
@@ -1379,7 +1377,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_E_ymm_ymm4 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::Y);
             instruction.regs[3].bank = RegisterBank::Y;
@@ -1396,7 +1394,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_E_xmm_xmm4 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             instruction.regs[3].bank = RegisterBank::X;
@@ -1413,7 +1411,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_ymm_E_xmm => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::Y);
             instruction.regs[3].bank = RegisterBank::Y;
@@ -1428,7 +1426,7 @@ fn read_vex_operands<
             Ok(())
         }
         VEXOperandCode::G_V_xmm_Ev_imm8 => {
-            let modrm = read_modrm(words)?;
+            let modrm = read_modrm(words, sink)?;
             instruction.regs[0] =
                 RegSpec::from_parts((modrm >> 3) & 7, RegisterBank::X);
             instruction.regs[3].bank = RegisterBank::X;
@@ -0,0 +1,14 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::wasm::{DecodedInstruction, OperandDescriptor};
+use crate::protected_mode::{InstDecoder, Instruction, Operand};
+
+fn mode_operand_kind(op: &Operand) -> &'static str {
+    match op {
+        Operand::AbsoluteFarAddress { .. } => "far",
+        _ => unreachable!("operand is handled by operand_kind"),
+    }
+}
+
+include!("../shared/wasm.in");
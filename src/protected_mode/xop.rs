@@ -0,0 +1,17 @@
+use yaxpeax_arch::Reader;
+use yaxpeax_arch::annotation::DescriptionSink;
+
+use crate::protected_mode::{Arch, DecodeError, FieldDescription, InnerDescription, Instruction, Opcode, OperandSpec, RegSpec, RegisterBank};
+use crate::Encoding;
+use crate::protected_mode::bytes;
+use crate::protected_mode::{read_modrm, read_E, read_E_xmm, read_E_ymm, read_imm_unsigned};
+
+// `xop.r`, `xop.x`, `xop.b`, and the high bits of `vvvv` and `is4` are ignored outside 64-bit mode.
+const XOP_REGISTER_MASK: u8 = 0b0111;
+
+// `xop.w` does not select 64-bit operands outside 64-bit mode.
+fn gpr_size(_instruction: &Instruction) -> (RegisterBank, u8) {
+    (RegisterBank::D, 4)
+}
+
+include!("../shared/xop.in");
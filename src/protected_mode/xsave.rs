@@ -0,0 +1,3 @@
+use crate::protected_mode::{Instruction, Opcode, XsaveFormat, XsaveLayout};
+
+include!("../shared/xsave.in");
@@ -0,0 +1,40 @@
+use crate::real_mode::{RegSpec, RegisterBank};
+
+fn alias_bytes(reg: &RegSpec) -> Option<(AliasFamily, u8, u8)> {
+    let bytes = match reg.bank {
+        RegisterBank::D => (AliasFamily::Gpr(reg.num), 0, 4),
+        RegisterBank::W => (AliasFamily::Gpr(reg.num), 0, 2),
+        // byte registers 4 through 7 are `ah`, `ch`, `dh`, and `bh`.
+        RegisterBank::B if reg.num >= 4 => (AliasFamily::Gpr(reg.num - 4), 1, 1),
+        RegisterBank::B => (AliasFamily::Gpr(reg.num), 0, 1),
+        RegisterBank::Z => (AliasFamily::Vector(reg.num), 0, 64),
+        RegisterBank::Y => (AliasFamily::Vector(reg.num), 0, 32),
+        RegisterBank::X => (AliasFamily::Vector(reg.num), 0, 16),
+        RegisterBank::EIP => (AliasFamily::Ip, 0, 4),
+        RegisterBank::EFlags => (AliasFamily::Flags, 0, 4),
+        _ => {
+            return None;
+        }
+    };
+    Some(bytes)
+}
+
+fn family_member(family: AliasFamily, start: u8, width: u8) -> Option<RegSpec> {
+    let reg = match (family, start, width) {
+        (AliasFamily::Gpr(num), 0, 4) => RegSpec::d(num),
+        (AliasFamily::Gpr(num), 0, 2) => RegSpec::w(num),
+        (AliasFamily::Gpr(num), 0, 1) if num < 4 => RegSpec::b(num),
+        (AliasFamily::Gpr(num), 1, 1) if num < 4 => RegSpec::b(num + 4),
+        (AliasFamily::Vector(num), 0, 64) => RegSpec::zmm(num),
+        (AliasFamily::Vector(num), 0, 32) => RegSpec::ymm(num),
+        (AliasFamily::Vector(num), 0, 16) => RegSpec::xmm(num),
+        (AliasFamily::Ip, 0, 4) => RegSpec::eip(),
+        (AliasFamily::Flags, 0, 4) => RegSpec::eflags(),
+        _ => {
+            return None;
+        }
+    };
+    Some(reg)
+}
+
+include!("../shared/aliases.in");
@@ -0,0 +1,7 @@
+use yaxpeax_arch::{Decoder, Reader, ReadError};
+use yaxpeax_arch::annotation::{AnnotatingDecoder, DescriptionSink};
+
+use crate::{DecodeHooks, Encoding};
+use crate::real_mode::{read_with_annotations, Arch, DecodeError, FieldDescription, InnerDescription, InstDecoder, Instruction, Opcode, OperandSpec, RegSpec, RegisterBank};
+
+include!("../shared/bytes.in");
@@ -0,0 +1,17 @@
+use crate::real_mode::{Category, Opcode};
+
+fn mode_category(opcode: Opcode) -> Category {
+    match opcode {
+        Opcode::JCXZ => Category::Branch,
+        Opcode::AAA | Opcode::AAD | Opcode::AAM | Opcode::AAS |
+        Opcode::DAA | Opcode::DAS => Category::Arithmetic,
+        // `bound` raises `#BR` if its index is out of range.
+        Opcode::BOUND => Category::Interrupt,
+        Opcode::ARPL => Category::System,
+        Opcode::LDS | Opcode::LES => Category::DataTransfer,
+        Opcode::PUSHA | Opcode::POPA => Category::Stack,
+        _ => Category::Invalid,
+    }
+}
+
+include!("../shared/category.in");
@@ -0,0 +1,3 @@
+use crate::real_mode::{DisplaySink, InstructionDisplayer, Opcode, RegSpec};
+
+include!("../shared/colors.in");
@@ -0,0 +1,3 @@
+use crate::real_mode::{Instruction, Operand, RegisterClass};
+
+include!("../shared/constraints.in");
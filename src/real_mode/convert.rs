@@ -0,0 +1,57 @@
+use crate::convert::RegisterKind;
+use crate::real_mode::{RegSpec, RegisterBank, RegisterClass};
+
+impl RegSpec {
+    /// the bank and number of this register, as every mode's `RegSpec` can be built from.
+    pub(crate) fn kind(&self) -> (RegisterKind, u8) {
+        let kind = match self.bank {
+            RegisterBank::D => RegisterKind::D,
+            RegisterBank::W => RegisterKind::W,
+            RegisterBank::B => RegisterKind::B,
+            RegisterBank::CR => RegisterKind::CR,
+            RegisterBank::DR => RegisterKind::DR,
+            RegisterBank::S => RegisterKind::S,
+            RegisterBank::EIP => RegisterKind::Eip,
+            RegisterBank::EFlags => RegisterKind::EFlags,
+            RegisterBank::X => RegisterKind::X,
+            RegisterBank::Y => RegisterKind::Y,
+            RegisterBank::Z => RegisterKind::Z,
+            RegisterBank::ST => RegisterKind::ST,
+            RegisterBank::MM => RegisterKind::MM,
+            RegisterBank::K => RegisterKind::K,
+            RegisterBank::Bnd => RegisterKind::Bnd,
+        };
+        (kind, self.num)
+    }
+
+    /// the register numbered `num` in the bank `kind`, if 16-bit code has such a register.
+    pub(crate) fn from_kind(kind: RegisterKind, num: u8) -> Option<RegSpec> {
+        let bank = match kind {
+            RegisterKind::D => RegisterBank::D,
+            RegisterKind::W => RegisterBank::W,
+            RegisterKind::B => RegisterBank::B,
+            // `al` through `bl` are the same registers with or without a `rex` prefix, but `spl`
+            // through `dil` are not `ah` through `bh`.
+            RegisterKind::rB if num < 4 => RegisterBank::B,
+            RegisterKind::CR => RegisterBank::CR,
+            RegisterKind::DR => RegisterBank::DR,
+            RegisterKind::S => RegisterBank::S,
+            RegisterKind::Eip => RegisterBank::EIP,
+            RegisterKind::EFlags => RegisterBank::EFlags,
+            RegisterKind::X => RegisterBank::X,
+            RegisterKind::Y => RegisterBank::Y,
+            RegisterKind::Z => RegisterBank::Z,
+            RegisterKind::ST => RegisterBank::ST,
+            RegisterKind::MM => RegisterBank::MM,
+            RegisterKind::K => RegisterBank::K,
+            RegisterKind::Bnd => RegisterBank::Bnd,
+            RegisterKind::Q | RegisterKind::rB | RegisterKind::Rip | RegisterKind::RFlags |
+            RegisterKind::T => { return None; }
+        };
+        if num < (RegisterClass { kind: bank }).count() {
+            Some(RegSpec { bank, num })
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use crate::CpuidLeaf;
+use crate::real_mode::InstDecoder;
+
+fn mode_cpuid_features(decoder: InstDecoder, _leaves: &[CpuidLeaf]) -> InstDecoder {
+    // `apx` and `amx` are only decoded in 64-bit mode.
+    decoder
+}
+
+include!("../shared/cpuid.in");
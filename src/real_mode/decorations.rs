@@ -0,0 +1,3 @@
+use crate::real_mode::{Instruction, MergeMode, Opcode, Operand, RegisterBank, RegSpec, SaeMode};
+
+include!("../shared/decorations.in");
@@ -1,5 +1,8 @@
 // use crate::long_mode::{OperandSpec, DecodeError, RegSpec, RegisterBank, Instruction, Opcode};
 use crate::real_mode::{Arch, DecodeError, RegSpec, RegisterBank, Instruction, Opcode};
+use crate::Encoding;
+use crate::real_mode::bytes;
+use crate::real_mode::decorations::compressed_disp_scale;
 use crate::real_mode::{read_modrm, read_E_vex, read_imm_unsigned};
 use yaxpeax_arch::Reader;
 
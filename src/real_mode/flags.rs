@@ -0,0 +1,15 @@
+use crate::real_mode::{Flags, Instruction, Opcode, Operand};
+
+fn mode_flags(opcode: Opcode) -> Option<(Flags, Flags)> {
+    let flags = match opcode {
+        Opcode::AAA | Opcode::AAS => (Flags::AF, Flags::STATUS),
+        Opcode::DAA | Opcode::DAS => (Flags::AF | Flags::CF, Flags::STATUS),
+        Opcode::AAM | Opcode::AAD => (Flags::empty(), Flags::STATUS),
+        Opcode::ARPL => (Flags::empty(), Flags::ZF),
+        Opcode::PUSHA | Opcode::POPA | Opcode::BOUND => (Flags::empty(), Flags::empty()),
+        _ => { return None; }
+    };
+    Some(flags)
+}
+
+include!("../shared/flags.in");
@@ -0,0 +1,20 @@
+//! a canonical description of decoded instructions, for differential fuzzing against other
+//! decoders. see [`oracle`].
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use yaxpeax_arch::LengthedInstruction;
+
+use crate::AccessKind;
+use crate::real_mode::{DecodeError, InstDecoder, Instruction, Operand};
+
+const MODE_BITS: u8 = 16;
+
+/// there is no `rex` prefix outside of 64-bit code; `40`-`4f` are `inc` and `dec`.
+fn is_discarded_prefix(_b: u8) -> bool {
+    false
+}
+
+include!("../shared/fuzz_oracle.in");
@@ -0,0 +1,5 @@
+use crate::{OperandRegisters, X86Instruction, X86Operand};
+use crate::real_mode::{Instruction, Operand, RegSpec};
+use crate::real_mode::scan::immediate_value;
+
+include!("../shared/generic.in");
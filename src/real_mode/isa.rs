@@ -0,0 +1,8 @@
+use crate::real_mode::{Instruction, IsaExtension, Opcode, Operand, RegisterBank};
+
+fn mode_isa_extension(_inst: &Instruction) -> Option<IsaExtension> {
+    // every opcode specific to this mode is part of the base instruction set.
+    None
+}
+
+include!("../shared/isa.in");
@@ -0,0 +1,3 @@
+use crate::real_mode::{DecodeError, InstDecoder, Instruction};
+
+include!("../shared/matrix.in");
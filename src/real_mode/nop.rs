@@ -0,0 +1,9 @@
+use crate::NopStrictness;
+use crate::real_mode::{Instruction, Opcode, Operand, RegSpec, RegisterBank};
+
+fn self_write_is_nop(reg: RegSpec) -> bool {
+    matches!(reg.bank,
+        RegisterBank::D | RegisterBank::W | RegisterBank::B)
+}
+
+include!("../shared/nop.in");
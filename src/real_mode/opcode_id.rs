@@ -0,0 +1,19 @@
+use crate::real_mode::Opcode;
+
+include!("../shared/opcode_id.in");
+
+opcode_ids!(mode_opcode_id, mode_opcode_from_id, {
+    LES = 1501,
+    LDS = 1502,
+    PUSHA = 1504,
+    POPA = 1505,
+    BOUND = 1506,
+    ARPL = 1507,
+    AAS = 1508,
+    AAA = 1509,
+    DAS = 1510,
+    DAA = 1511,
+    AAM = 1512,
+    AAD = 1513,
+    JCXZ = 1514,
+});
@@ -0,0 +1,89 @@
+use crate::real_mode::{AccessKind, Instruction, MergeMode, Opcode, Operand, RegSpec, RegisterBank};
+use crate::real_mode::scan::immediate_value;
+
+fn mode_access_override(opcode: Opcode, i: u8) -> Option<AccessKind> {
+    match opcode {
+        Opcode::JCXZ | Opcode::BOUND => Some(AccessKind::Read),
+        Opcode::LDS | Opcode::LES if i == 0 => Some(AccessKind::Write),
+        _ => None,
+    }
+}
+
+fn mode_implicit_operands(inst: &Instruction, ops: &mut ImplicitOperands) {
+    match inst.opcode() {
+        Opcode::JCXZ => {
+            ops.reg(gp_reg(1, address_width(inst)), AccessKind::Read);
+        }
+        Opcode::LDS => {
+            ops.reg(RegSpec::ds(), AccessKind::Write);
+        }
+        Opcode::LES => {
+            ops.reg(RegSpec::es(), AccessKind::Write);
+        }
+        Opcode::AAA | Opcode::AAS | Opcode::AAM | Opcode::AAD => {
+            ops.reg(RegSpec::ax(), AccessKind::ReadWrite);
+        }
+        Opcode::DAA | Opcode::DAS => {
+            ops.reg(RegSpec::al(), AccessKind::ReadWrite);
+        }
+        // `pusha` pushes all eight general-purpose registers, including the stack pointer as it
+        // was before the instruction. `popa` restores all of them but the stack pointer.
+        Opcode::PUSHA => {
+            let sw = stack_width(inst);
+            ops.reg(stack_pointer(), AccessKind::ReadWrite);
+            ops.mem(stack_pointer(), -(sw as i32) * 8, sw * 8, AccessKind::Write);
+            for num in [0, 1, 2, 3, 5, 6, 7] {
+                ops.reg(gp_reg(num, sw), AccessKind::Read);
+            }
+        }
+        Opcode::POPA => {
+            let sw = stack_width(inst);
+            ops.reg(stack_pointer(), AccessKind::ReadWrite);
+            ops.mem(stack_pointer(), 0, sw * 8, AccessKind::Read);
+            for num in [0, 1, 2, 3, 5, 6, 7] {
+                ops.reg(gp_reg(num, sw), AccessKind::Write);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn gp_reg(num: u8, width: u8) -> RegSpec {
+    let bank = match width {
+        4 => RegisterBank::D,
+        2 => RegisterBank::W,
+        _ => RegisterBank::B,
+    };
+    RegSpec { num, bank }
+}
+
+fn stack_pointer() -> RegSpec {
+    RegSpec::sp()
+}
+
+fn flags_register() -> RegSpec {
+    RegSpec::eflags()
+}
+
+fn stack_width(inst: &Instruction) -> u8 {
+    if inst.prefixes.operand_size() { 4 } else { 2 }
+}
+
+fn address_width(inst: &Instruction) -> u8 {
+    if inst.prefixes.address_size() { 4 } else { 2 }
+}
+
+/// the bytes an interrupt pushes: `flags`, `cs`, and `ip`, as words whatever the operand size.
+fn interrupt_frame_size() -> u8 {
+    6
+}
+
+fn mode_stack_slots(opcode: Opcode) -> Option<i32> {
+    match opcode {
+        Opcode::PUSHA => Some(-8),
+        Opcode::POPA => Some(8),
+        _ => None,
+    }
+}
+
+include!("../shared/operands.in");
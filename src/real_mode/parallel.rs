@@ -0,0 +1,7 @@
+extern crate std;
+
+use alloc::vec::Vec;
+
+use crate::real_mode::{DecodeError, ErrorRecovery, InstDecoder, Instruction};
+
+include!("../shared/parallel.in");
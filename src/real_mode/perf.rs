@@ -0,0 +1,3 @@
+use crate::real_mode::{Opcode, PerfInfo, Uarch};
+
+include!("../shared/perf.in");
@@ -0,0 +1,22 @@
+use crate::{Category, PrefixEffect, PrefixEffects};
+use crate::real_mode::{Instruction, Opcode, Operand, RegSpec, RegisterBank, Segment};
+
+const OVERRIDDEN_BANK: RegisterBank = RegisterBank::D;
+const OVERRIDDEN_WIDTH: u8 = 4;
+
+fn operand_size_opcode(opcode: Opcode) -> bool {
+    matches!(opcode,
+        Opcode::CWDE | Opcode::CDQ | Opcode::IRETD)
+}
+
+fn inherently_16bit(opcode: Opcode) -> bool {
+    matches!(opcode,
+        Opcode::SLDT | Opcode::STR | Opcode::SMSW | Opcode::LLDT | Opcode::LTR | Opcode::LMSW |
+        Opcode::VERR | Opcode::VERW | Opcode::ARPL)
+}
+
+fn rex_w_effect(_inst: &Instruction) -> PrefixEffect {
+    PrefixEffect::Absent
+}
+
+include!("../shared/prefix_effects.in");
@@ -0,0 +1,116 @@
+use core::fmt;
+
+use crate::real_mode::{Instruction, Opcode, Operand, RegSpec, RegValueSource, Segment};
+
+/// a real-mode `segment:offset` address, such as `07c0:0000`.
+///
+/// the linear address is `segment * 16 + offset`; many `segment:offset` pairs name the same
+/// linear address. offsets are usually 16 bits, but an operand- or address-size prefix allows 32
+/// bits, as used in "unreal" mode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SegmentedAddress {
+    pub segment: u16,
+    pub offset: u32,
+}
+
+impl SegmentedAddress {
+    pub fn new(segment: u16, offset: u32) -> Self {
+        SegmentedAddress { segment, offset }
+    }
+
+    /// the entry for interrupt `vector` in the real-mode interrupt vector table, at
+    /// `0000:vector * 4`. the entry holds the far pointer `int vector` transfers control to.
+    pub fn interrupt_vector(vector: u8) -> Self {
+        SegmentedAddress::new(0, vector as u32 * 4)
+    }
+
+    /// the linear address, wrapped to 20 bits as on an 8086 or with the a20 line disabled:
+    /// `ffff:0010` is `0x00000`.
+    pub fn linear(&self) -> u32 {
+        self.linear_a20() & 0xf_ffff
+    }
+
+    /// the linear address with the a20 line enabled, where addresses past 1M reach the high
+    /// memory area rather than wrapping: `ffff:0010` is `0x100000`.
+    pub fn linear_a20(&self) -> u32 {
+        ((self.segment as u32) << 4).wrapping_add(self.offset)
+    }
+
+    /// the `segment:offset` pair for the same 20-bit linear address with the largest segment, so
+    /// the offset is less than 16: `0000:7c00` normalizes to `07c0:0000`.
+    pub fn normalized(&self) -> Self {
+        let linear = self.linear();
+        SegmentedAddress::new((linear >> 4) as u16, linear & 0xf)
+    }
+}
+
+impl fmt::Display for SegmentedAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04x}:{:04x}", self.segment, self.offset)
+    }
+}
+
+/// where a far branch, far call, or interrupt transfers control to. see
+/// [`Instruction::far_target`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum FarTarget {
+    /// the target is encoded in the instruction, as in `jmpf 0xf000:0xe05b`.
+    Direct(SegmentedAddress),
+    /// the target is a far pointer, an offset followed by a segment, read from this memory
+    /// operand.
+    Indirect(Operand),
+    /// the target is the far pointer in this entry of the interrupt vector table; see
+    /// [`SegmentedAddress::interrupt_vector`].
+    Interrupt(u8),
+}
+
+fn segment_register(segment: Segment) -> RegSpec {
+    match segment {
+        Segment::DS => RegSpec::ds(),
+        Segment::CS => RegSpec::cs(),
+        Segment::ES => RegSpec::es(),
+        Segment::FS => RegSpec::fs(),
+        Segment::GS => RegSpec::gs(),
+        Segment::SS => RegSpec::ss(),
+    }
+}
+
+impl Instruction {
+    /// where this `callf`, `jmpf`, `int`, or `into` transfers control to. `int3` and `int1` are
+    /// reported as interrupts `3` and `1`, and `into`, if it traps, as interrupt `4`.
+    ///
+    /// returns `None` for any other instruction.
+    pub fn far_target(&self) -> Option<FarTarget> {
+        match self.opcode() {
+            Opcode::CALLF | Opcode::JMPF => {
+                match self.operand(0) {
+                    Operand::AbsoluteFarAddress { segment, address } => {
+                        Some(FarTarget::Direct(SegmentedAddress::new(segment, address)))
+                    }
+                    op if op.is_memory() => Some(FarTarget::Indirect(op)),
+                    _ => None,
+                }
+            }
+            Opcode::INT => {
+                match self.operand(0) {
+                    Operand::ImmediateU8(vector) => Some(FarTarget::Interrupt(vector)),
+                    _ => None,
+                }
+            }
+            Opcode::INTO => Some(FarTarget::Interrupt(4)),
+            _ => None,
+        }
+    }
+
+    /// compute the `segment:offset` address of memory operand `i`: the offset from
+    /// [`Operand::evaluate`], and the value of the segment register the operand is accessed
+    /// through, as read from `regs`. see [`Instruction::linear_address`] for how the segment is
+    /// chosen.
+    ///
+    /// `i` must be less than `self.operand_count()`.
+    pub fn segmented_address(&self, i: u8, regs: &dyn RegValueSource) -> Option<SegmentedAddress> {
+        let offset = self.operand(i).evaluate(regs)?;
+        let segment = regs.reg_value(segment_register(self.memory_segment(i)))?;
+        Some(SegmentedAddress::new(segment as u16, offset as u32))
+    }
+}
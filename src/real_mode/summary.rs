@@ -0,0 +1,19 @@
+use crate::{Encoding, FlowClass, InstructionSummary};
+use crate::real_mode::{DecodeError, InstDecoder};
+
+// `40` through `4f` are `inc` and `dec` outside 64-bit mode.
+fn is_rex(_b: u8) -> bool {
+    false
+}
+
+fn is_rex2(_decoder: &InstDecoder, _b: u8) -> bool {
+    false
+}
+
+// outside 64-bit mode, `c4`, `c5`, and `62` are `les`, `lds`, and `bound` unless their
+// `modrm` byte would select a register, which those instructions cannot take.
+fn is_vex_or_evex(next: u8) -> bool {
+    next >= 0xc0
+}
+
+include!("../shared/summary.in");
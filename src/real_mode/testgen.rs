@@ -0,0 +1,21 @@
+//! generation of valid encodings, for building decode and round-trip tests and differential tests
+//! against hardware. see [`enumerate`] and [`Generator`].
+
+use alloc::vec::Vec;
+
+use crate::real_mode::{InstDecoder, Instruction, Opcode, Operand, RegisterClass};
+
+const MODE_BITS: u8 = 16;
+
+/// the one-byte map, `0f`, `0f38`, and `0f3a`.
+const LEGACY_HEADS: &[(&[u8], bool)] = &[
+    (&[], true), (&[0x0f], false), (&[0x0f, 0x38], false), (&[0x0f, 0x3a], false),
+];
+
+/// legacy prefixes and `0f`. `c4`, `c5`, and `62` are `les`, `lds`, and `bound` unless
+/// followed by a byte with `mod` of `11`, so they are opcodes here too.
+fn is_escape(b: u8) -> bool {
+    matches!(b, 0x26 | 0x2e | 0x36 | 0x3e | 0x64 | 0x65 | 0x66 | 0x67 | 0xf0 | 0xf2 | 0xf3 | 0x0f)
+}
+
+include!("../shared/testgen.in");
@@ -0,0 +1,3 @@
+use crate::real_mode::{Instruction, Opcode, XsaveFormat, XsaveLayout};
+
+include!("../shared/xsave.in");
@@ -0,0 +1,146 @@
+//! a decoding interface for javascript, through `wasm-bindgen`. instructions are decoded into
+//! plain descriptions, with text for their mnemonic and operands, so a disassembler in a web page
+//! can show them without knowing about `Opcode`, `Operand`, or `RegSpec`.
+//!
+//! from javascript, with the `wasm-pack` defaults:
+//! ```text
+//! import { decode, Mode } from "yaxpeax_x86";
+//!
+//! const instr = decode(Mode.Long, new Uint8Array([0x48, 0x89, 0xe5]));
+//! instr.text;                  // "mov rbp, rsp"
+//! instr.length;                // 3
+//! instr.operands[0].write;     // true
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+/// which of the x86 modes to decode in.
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// 16-bit code, decoded by `real_mode`.
+    Real = 16,
+    /// 32-bit code, decoded by `protected_mode`.
+    Protected = 32,
+    /// 64-bit code, decoded by `long_mode`.
+    Long = 64,
+}
+
+/// one decoded instruction. see [`decode`].
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedInstruction {
+    pub(crate) length: u8,
+    pub(crate) mnemonic: String,
+    pub(crate) text: String,
+    pub(crate) operands: Vec<OperandDescriptor>,
+}
+
+#[wasm_bindgen]
+impl DecodedInstruction {
+    /// the length of this instruction, in bytes.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> u8 {
+        self.length
+    }
+
+    /// the instruction's mnemonic, without prefixes: `movs` of a `rep movs`.
+    #[wasm_bindgen(getter)]
+    pub fn mnemonic(&self) -> String {
+        self.mnemonic.clone()
+    }
+
+    /// the whole instruction, as `Display` writes it.
+    #[wasm_bindgen(getter)]
+    pub fn text(&self) -> String {
+        self.text.clone()
+    }
+
+    /// the instruction's explicit operands, in the order they are written.
+    #[wasm_bindgen(getter)]
+    pub fn operands(&self) -> Vec<OperandDescriptor> {
+        self.operands.clone()
+    }
+}
+
+/// one operand of a [`DecodedInstruction`].
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperandDescriptor {
+    pub(crate) kind: &'static str,
+    pub(crate) text: String,
+    pub(crate) read: bool,
+    pub(crate) write: bool,
+    pub(crate) width: Option<u8>,
+}
+
+#[wasm_bindgen]
+impl OperandDescriptor {
+    /// what sort of operand this is: `"register"`, `"immediate"`, `"memory"`, or, outside of
+    /// `Mode.Long`, `"far"` for the `segment:address` of a far `call` or `jmp`.
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> String {
+        self.kind.to_string()
+    }
+
+    /// the operand as it is written in the instruction's text.
+    #[wasm_bindgen(getter)]
+    pub fn text(&self) -> String {
+        self.text.clone()
+    }
+
+    /// whether the instruction reads this operand. registers used to compute a memory operand's
+    /// address are always read, whether or not the memory itself is.
+    #[wasm_bindgen(getter)]
+    pub fn read(&self) -> bool {
+        self.read
+    }
+
+    /// whether the instruction writes this operand, including if it only may be written.
+    #[wasm_bindgen(getter)]
+    pub fn write(&self) -> bool {
+        self.write
+    }
+
+    /// the size of this operand in bytes, or `undefined` if it has no single width.
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> Option<u8> {
+        self.width
+    }
+}
+
+/// decode the instruction at the start of `bytes` in `mode`, with that mode's default decoder.
+/// trailing bytes after the instruction are ignored.
+///
+/// if the bytes do not decode, the error is a description of why.
+#[wasm_bindgen]
+pub fn decode(mode: Mode, bytes: &[u8]) -> Result<DecodedInstruction, String> {
+    match mode {
+        Mode::Real => crate::real_mode::wasm::decode(bytes),
+        Mode::Protected => crate::protected_mode::wasm::decode(bytes),
+        Mode::Long => crate::long_mode::wasm::decode(bytes),
+    }
+}
+
+/// decode instructions from `bytes` in `mode` until the end of `bytes` or the first bytes that
+/// do not decode, whichever comes first. each instruction starts where the one before it ends.
+#[wasm_bindgen(js_name = decodeAll)]
+pub fn decode_all(mode: Mode, bytes: &[u8]) -> Vec<DecodedInstruction> {
+    let mut instrs = Vec::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        match decode(mode, &bytes[offset..]) {
+            Ok(instr) => {
+                offset += instr.length as usize;
+                instrs.push(instr);
+            }
+            Err(_) => {
+                break;
+            }
+        }
+    }
+    instrs
+}
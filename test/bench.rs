@@ -34,6 +34,34 @@ fn bench_1020000_instrs(b: &mut Bencher) {
     })
 }
 
+#[bench]
+fn bench_decode_then_display(b: &mut Bencher) {
+    use std::fmt::Write;
+
+    let decoder = yaxpeax_x86::long_mode::InstDecoder::default();
+    let mut text = String::new();
+    b.iter(|| {
+        text.clear();
+        let mut reader = yaxpeax_arch::U8Reader::new(&DECODE_DATA[..]);
+        while let Ok(instr) = decoder.decode(&mut reader) {
+            write!(text, "{}", instr).unwrap();
+        }
+        test::black_box(&text);
+    })
+}
+
+#[bench]
+fn bench_decode_and_format_into(b: &mut Bencher) {
+    let decoder = yaxpeax_x86::long_mode::InstDecoder::default();
+    let mut text = String::new();
+    b.iter(|| {
+        text.clear();
+        let mut reader = yaxpeax_arch::U8Reader::new(&DECODE_DATA[..]);
+        while let Ok(Ok(_)) = decoder.decode_and_format_into(&mut reader, &mut text) {}
+        test::black_box(&text);
+    })
+}
+
 #[cfg(feature = "capstone_bench")]
 #[bench]
 fn bench_102000_intrs_capstone(b: &mut Bencher) {
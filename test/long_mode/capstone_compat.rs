@@ -0,0 +1,58 @@
+use yaxpeax_x86::long_mode::capstone_compat::{Group, X86OpMem, X86OpValue};
+use yaxpeax_x86::long_mode::{InstDecoder, Opcode, RegSpec};
+use yaxpeax_x86::{AccessKind, Flags, IsaExtension};
+
+#[test]
+fn test_capstone_detail() {
+    // add qword fs:[rax + rcx * 4 + 0x10], 0x7f
+    let instr = InstDecoder::default().with_retain_bytes()
+        .decode_slice(&[0x64, 0x48, 0x83, 0x44, 0x88, 0x10, 0x7f]).unwrap();
+    let insn = instr.to_capstone(0x1000);
+    assert_eq!(insn.id, Opcode::ADD);
+    assert_eq!(insn.address, 0x1000);
+    assert_eq!(insn.size, 7);
+    assert_eq!(&insn.bytes[..7], &[0x64, 0x48, 0x83, 0x44, 0x88, 0x10, 0x7f]);
+    assert_eq!(insn.detail.x86.prefix, [0, 0x64, 0, 0]);
+    assert_eq!(insn.detail.x86.addr_size, 8);
+    let ops = insn.detail.x86.operands();
+    assert_eq!(ops.len(), 2);
+    assert_eq!(ops[0].value, X86OpValue::Mem(X86OpMem {
+        segment: Some(RegSpec::fs()),
+        base: Some(RegSpec::rax()),
+        index: Some(RegSpec::rcx()),
+        scale: 4,
+        disp: 0x10,
+    }));
+    assert_eq!(ops[0].size, 8);
+    assert_eq!(ops[0].access, AccessKind::ReadWrite);
+    assert_eq!(ops[1].value, X86OpValue::Imm(0x7f));
+    assert_eq!(ops[1].size, 8);
+    assert!(insn.detail.x86.eflags_written.contains(Flags::CF));
+    assert_eq!(insn.detail.regs_read(), &[]);
+    assert_eq!(insn.detail.regs_write(), &[RegSpec::rflags()]);
+    assert_eq!(insn.detail.groups(), &[]);
+
+    // push rbp reads and writes rsp without naming it.
+    let insn = InstDecoder::default().decode_slice(&[0x55]).unwrap().to_capstone(0);
+    assert_eq!(insn.detail.regs_read(), &[RegSpec::rsp()]);
+    assert_eq!(insn.detail.regs_write(), &[RegSpec::rsp()]);
+    assert_eq!(insn.detail.x86.operands()[0].value, X86OpValue::Reg(RegSpec::rbp()));
+
+    // call $+0x10
+    let insn = InstDecoder::default().decode_slice(&[0xe8, 0x10, 0x00, 0x00, 0x00]).unwrap().to_capstone(0);
+    assert_eq!(insn.detail.groups(), &[Group::Call, Group::BranchRelative]);
+    // jmp rax
+    let insn = InstDecoder::default().decode_slice(&[0xff, 0xe0]).unwrap().to_capstone(0);
+    assert_eq!(insn.detail.groups(), &[Group::Jump]);
+    // mov cr0, rax
+    let insn = InstDecoder::default().decode_slice(&[0x0f, 0x22, 0xc0]).unwrap().to_capstone(0);
+    assert_eq!(insn.detail.groups(), &[Group::Privilege]);
+
+    // vaddps zmm1{k2}{z}, zmm3, dword [rax]{1to16}
+    let insn = InstDecoder::default().decode_slice(&[0x62, 0xf1, 0x64, 0xda, 0x58, 0x08]).unwrap().to_capstone(0);
+    let ops = insn.detail.x86.operands();
+    assert!(ops[0].avx_zero_opmask);
+    assert_eq!(ops[2].avx_bcast, Some(16));
+    assert_eq!(insn.detail.regs_read(), &[RegSpec::mask(2)]);
+    assert_eq!(insn.detail.groups(), &[Group::Isa(IsaExtension::Avx512F)]);
+}
@@ -0,0 +1,89 @@
+use yaxpeax_x86::long_mode::{Category, FaultClasses, InstDecoder, Opcode};
+
+fn category_of(data: &[u8]) -> Category {
+    InstDecoder::default().decode_slice(data).unwrap().opcode().category()
+}
+
+#[test]
+fn test_categories() {
+    assert_eq!(category_of(&[0xeb, 0xfe]), Category::Branch);
+    assert_eq!(category_of(&[0x74, 0x10]), Category::Branch);
+    assert_eq!(category_of(&[0xe3, 0x10]), Category::Branch);
+    assert_eq!(category_of(&[0xe8, 0x00, 0x00, 0x00, 0x00]), Category::Call);
+    assert_eq!(category_of(&[0xc3]), Category::Ret);
+    assert_eq!(category_of(&[0xcc]), Category::Interrupt);
+    assert_eq!(category_of(&[0x0f, 0x05]), Category::Interrupt);
+    assert_eq!(category_of(&[0x0f, 0x0b]), Category::Interrupt);
+    assert_eq!(category_of(&[0x48, 0x01, 0xd8]), Category::Arithmetic);
+    assert_eq!(category_of(&[0x48, 0x31, 0xc0]), Category::Logic);
+    assert_eq!(category_of(&[0x48, 0x89, 0xc8]), Category::DataTransfer);
+    assert_eq!(category_of(&[0x0f, 0x44, 0xc1]), Category::DataTransfer);
+    assert_eq!(category_of(&[0xfc]), Category::Flag);
+    assert_eq!(category_of(&[0x55]), Category::Stack);
+    assert_eq!(category_of(&[0xf3, 0xa4]), Category::String);
+    assert_eq!(category_of(&[0xe4, 0x60]), Category::Io);
+    assert_eq!(category_of(&[0xd9, 0x00]), Category::X87);
+    assert_eq!(category_of(&[0x0f, 0x58, 0xc1]), Category::Simd);
+    assert_eq!(category_of(&[0xc5, 0xf0, 0x58, 0xc2]), Category::Simd);
+    assert_eq!(category_of(&[0x66, 0x0f, 0x38, 0xdc, 0xc1]), Category::Crypto);
+    assert_eq!(category_of(&[0x0f, 0xa2]), Category::System);
+    assert_eq!(category_of(&[0x90]), Category::Nop);
+    assert_eq!(category_of(&[0xf3, 0x0f, 0x1e, 0xfa]), Category::Nop);
+
+    assert_eq!(Opcode::Invalid.category(), Category::Invalid);
+    assert!(Opcode::JRCXZ.category().is_control_flow());
+    assert!(Opcode::RETURN.category().is_control_flow());
+    assert!(!Opcode::MOV.category().is_control_flow());
+}
+
+#[test]
+fn test_fault_classes() {
+    fn faults_of(data: &[u8]) -> FaultClasses {
+        InstDecoder::default().decode_slice(data).unwrap().opcode().fault_classes()
+    }
+
+    // div rcx
+    assert!(faults_of(&[0x48, 0xf7, 0xf1]).contains(FaultClasses::DE | FaultClasses::PF));
+    // add rax, rbx
+    assert_eq!(faults_of(&[0x48, 0x01, 0xd8]), FaultClasses::MEMORY);
+    assert!(!faults_of(&[0x48, 0x01, 0xd8]).contains(FaultClasses::DE));
+    // int3
+    assert!(faults_of(&[0xcc]).contains(FaultClasses::BP));
+    // ud2
+    assert_eq!(faults_of(&[0x0f, 0x0b]), FaultClasses::UD);
+    // addps xmm0, xmm1
+    assert!(faults_of(&[0x0f, 0x58, 0xc1]).contains(FaultClasses::NM | FaultClasses::XM));
+    // fld dword [rax]
+    assert!(faults_of(&[0xd9, 0x00]).contains(FaultClasses::MF));
+    // jz $+0x10
+    assert_eq!(faults_of(&[0x74, 0x10]), FaultClasses::GP);
+    // cld, nop
+    assert!(faults_of(&[0xfc]).is_empty());
+    assert!(faults_of(&[0x90]).is_empty());
+
+    assert_eq!(FaultClasses::PF.bits(), 1 << 14);
+    assert_eq!(Opcode::Invalid.fault_classes(), FaultClasses::UD);
+}
+
+#[test]
+fn test_opcode_ids() {
+    // these ids are stable; if one of these fails, an id was changed, which must not happen.
+    assert_eq!(Opcode::Invalid.id(), 0);
+    assert_eq!(Opcode::ADD.id(), 1);
+    assert_eq!(Opcode::VADDPD.id(), 422);
+    assert_eq!(Opcode::PSHUFB.id(), 826);
+    assert_eq!(Opcode::JRCXZ.id(), 1485);
+    assert_eq!(Opcode::VADDPH.id(), 1515);
+
+    let mut opcodes = 0;
+    for id in 0..=u16::MAX {
+        if let Some(opcode) = Opcode::from_id(id) {
+            assert_eq!(opcode.id(), id);
+            opcodes += 1;
+        }
+    }
+    assert_eq!(opcodes, 1607);
+
+    // 1509 is `aaa`, which long mode does not have.
+    assert_eq!(Opcode::from_id(1509), None);
+}
@@ -0,0 +1,50 @@
+use yaxpeax_x86::conformance::{self, Expected, Mode, ParseError, TestVector, VECTORS};
+
+#[test]
+fn test_conformance_self_test() {
+    let mismatches = conformance::self_test();
+    assert!(mismatches.is_empty(), "mismatches:\n{}", mismatches.iter().map(|m| m.to_string()).collect::<Vec<_>>().join("\n"));
+
+    for mode in [Mode::Long, Mode::Protected, Mode::Real] {
+        assert!(VECTORS.iter().any(|v| v.mode == mode && v.expected == Expected::Invalid));
+        assert!(VECTORS.iter().any(|v| v.mode == mode && v.expected != Expected::Invalid));
+    }
+}
+
+#[test]
+fn test_conformance_text_form() {
+    for vector in VECTORS.iter() {
+        assert_eq!(TestVector::parse(&vector.to_string()).as_ref(), Ok(vector));
+    }
+
+    let vector = TestVector::parse("real 0f0b ud2").unwrap();
+    assert_eq!(vector.mode, Mode::Real);
+    assert_eq!(&vector.bytes[..], &[0x0f, 0x0b]);
+    assert_eq!(vector.to_string(), "real 0f0b ud2");
+    assert_eq!(TestVector::parse("long 0f !invalid").unwrap().expected, Expected::Invalid);
+
+    assert_eq!(TestVector::parse("arm 0f0b ud2"), Err(ParseError::BadMode));
+    assert_eq!(TestVector::parse("long 0f0 ud2"), Err(ParseError::BadBytes));
+    assert_eq!(TestVector::parse("long zz ud2"), Err(ParseError::BadBytes));
+    assert_eq!(TestVector::parse("long 0f0b"), Err(ParseError::MissingText));
+}
+
+#[test]
+fn test_conformance_mismatches() {
+    let vectors = [
+        TestVector::parse("long 4889e5 mov rbp, rsp").unwrap(),
+        TestVector::parse("long 0f0b ud2").unwrap(),
+        TestVector::parse("long 90 !invalid").unwrap(),
+    ];
+    // a decoder that only knows `ud2`, and only the first byte of it.
+    let mut decode = |_mode: Mode, bytes: &[u8]| -> Option<(usize, String)> {
+        if bytes[0] == 0x0f { Some((1, "ud2".to_string())) } else { None }
+    };
+    let mismatches = conformance::run(&vectors, &mut decode);
+    // rejecting `nop` is right, for once.
+    assert_eq!(mismatches.len(), 2);
+    assert_eq!(mismatches[0].actual, None);
+    assert_eq!(mismatches[1].to_string(), "long 0f0b ud2: decoded 1 bytes as `ud2`");
+    assert!(conformance::check(&vectors[2], &mut conformance::decode).is_err());
+    assert!(conformance::check(&vectors[0], &mut conformance::decode).is_ok());
+}
@@ -0,0 +1,30 @@
+use yaxpeax_x86::long_mode::{InstDecoder, Opcode};
+
+#[test]
+fn test_descriptions() {
+    assert_eq!(Opcode::MOVZX.description(), "Move with zero-extend");
+    assert_eq!(Opcode::JNZ.description(), "Jump if not zero");
+    assert_eq!(Opcode::VFMADD231PS.description(), "Fused multiply-add of packed single precision floating-point values");
+    // vex and evex forms of sse instructions share their descriptions.
+    assert_eq!(Opcode::VPADDD.description(), Opcode::PADDD.description());
+
+    let decoder = InstDecoder::default();
+    let inst = decoder.decode_slice(&[0x0f, 0xa2]).unwrap();
+    assert_eq!(inst.opcode().description(), "CPU identification");
+
+    // every opcode in each mode is described, on one line.
+    fn check(descriptions: impl Iterator<Item = &'static str>) -> usize {
+        let mut count = 0;
+        for description in descriptions {
+            assert!(!description.is_empty());
+            assert!(!description.contains('\n'));
+            assert!(!description.ends_with('.'));
+            count += 1;
+        }
+        count
+    }
+    assert_eq!(check((0..2048).filter_map(Opcode::from_id).map(|op| op.description())), 1607);
+    assert_eq!(check((0..2048).filter_map(yaxpeax_x86::protected_mode::Opcode::from_id).map(|op| op.description())), 1604);
+    assert_eq!(check((0..2048).filter_map(yaxpeax_x86::real_mode::Opcode::from_id).map(|op| op.description())), 1604);
+    assert_eq!(yaxpeax_x86::protected_mode::Opcode::AAA.description(), "ASCII adjust after addition");
+}
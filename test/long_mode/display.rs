@@ -1,8 +1,9 @@
 use std::fmt::Write;
 
 use yaxpeax_arch::{AddressBase, Decoder, LengthedInstruction};
-use yaxpeax_x86::long_mode::{DisplayStyle, InstDecoder};
+use yaxpeax_x86::long_mode::{DisplayOptions, DisplaySink, DisplayStyle, InstDecoder, Opcode, RegSpec, RelocResolver, SymbolResolver};
 
+#[allow(dead_code)]
 fn test_display(data: &[u8], expected: &'static str) {
     test_display_under(&InstDecoder::default(), DisplayStyle::Intel, data, expected);
 }
@@ -11,7 +12,12 @@ fn test_c_display(data: &[u8], expected: &'static str) {
     test_display_under(&InstDecoder::default(), DisplayStyle::C, data, expected);
 }
 
-fn test_display_under(decoder: &InstDecoder, style: DisplayStyle, data: &[u8], expected: &'static str) {
+fn test_att_display(data: &[u8], expected: &'static str) {
+    test_display_under(&InstDecoder::default(), DisplayOptions::att(), data, expected);
+}
+
+fn test_display_under<O: Into<DisplayOptions>>(decoder: &InstDecoder, options: O, data: &[u8], expected: &'static str) {
+    let options = options.into();
     let mut hex = String::new();
     for b in data {
         write!(hex, "{:02x}", b).unwrap();
@@ -19,7 +25,7 @@ fn test_display_under(decoder: &InstDecoder, style: DisplayStyle, data: &[u8], e
     let mut reader = yaxpeax_arch::U8Reader::new(data);
     match decoder.decode(&mut reader) {
         Ok(instr) => {
-            let text = format!("{}", instr.display_with(style));
+            let text = format!("{}", instr.display_with(options));
             assert!(
                 text == expected,
                 "display error for {}:\n  decoded: {:?} under decoder {}\n displayed: {}\n expected: {}\n",
@@ -39,59 +45,171 @@ fn test_display_under(decoder: &InstDecoder, style: DisplayStyle, data: &[u8], e
     }
 }
 
-// decided i do not like at&t syntax much at all. not going to write a formatter for it. some test
-// cases will live on in case someone else feels like adding one, or i get mad enough to do it.
-#[ignore]
 #[test]
-fn test_instructions_atnt() {
+fn test_instructions_att() {
     // just modrm
-    test_display(&[0x33, 0x08], "xor (%rax), %ecx");
-    test_display(&[0x33, 0x20], "xor (%rax), %esp");
-    test_display(&[0x33, 0x05, 0x78, 0x56, 0x34, 0x12], "xor 0x12345678(%rip), %eax");
-    test_display(&[0x33, 0x41, 0x23], "xor 0x23(%rcx), %eax");
-    test_display(&[0x33, 0x81, 0x23, 0x01, 0x65, 0x43], "xor %0x43650123, %eax");
-    test_display(&[0x33, 0xc1], "xor %ecx, %eax");
+    test_att_display(&[0x33, 0x08], "xor (%rax),%ecx");
+    test_att_display(&[0x33, 0x20], "xor (%rax),%esp");
+    test_att_display(&[0x33, 0x05, 0x78, 0x56, 0x34, 0x12], "xor 0x12345678(%rip),%eax");
+    test_att_display(&[0x33, 0x41, 0x23], "xor 0x23(%rcx),%eax");
+    test_att_display(&[0x33, 0x81, 0x23, 0x01, 0x65, 0x43], "xor 0x43650123(%rcx),%eax");
+    test_att_display(&[0x33, 0xc1], "xor %ecx,%eax");
 
     // modrm + rex.w
-    test_display(&[0x48, 0x33, 0x08], "xor (%rax), %rcx");
-    test_display(&[0x48, 0x33, 0x20], "xor (%rax), %rsp");
-    test_display(&[0x48, 0x33, 0x05, 0x78, 0x56, 0x34, 0x12], "xor 0x12345678(%rip), %rax");
-    test_display(&[0x48, 0x33, 0x41, 0x23], "xor 0x23(%rcx), %rax");
-    test_display(&[0x48, 0x33, 0x81, 0x23, 0x01, 0x65, 0x43], "xor 0x43650123(%rcx), %rax");
-    test_display(&[0x48, 0x33, 0xc1], "xor %rcx, %rax");
+    test_att_display(&[0x48, 0x33, 0x08], "xor (%rax),%rcx");
+    test_att_display(&[0x48, 0x33, 0x20], "xor (%rax),%rsp");
+    test_att_display(&[0x48, 0x33, 0x05, 0x78, 0x56, 0x34, 0x12], "xor 0x12345678(%rip),%rax");
+    test_att_display(&[0x48, 0x33, 0x41, 0x23], "xor 0x23(%rcx),%rax");
+    test_att_display(&[0x48, 0x33, 0x81, 0x23, 0x01, 0x65, 0x43], "xor 0x43650123(%rcx),%rax");
+    test_att_display(&[0x48, 0x33, 0xc1], "xor %rcx,%rax");
 
     // modrm + rex.r
-    test_display(&[0x44, 0x33, 0x08], "xor (%rax), %r9d");
-    test_display(&[0x44, 0x33, 0x20], "xor (%rax), %r12d");
-    test_display(&[0x44, 0x33, 0x05, 0x78, 0x56, 0x34, 0x12], "xor 0x12345678(%rip), %r8d");
-    test_display(&[0x44, 0x33, 0x41, 0x23], "xor 0x23(%rcx), %r8d");
-    test_display(&[0x44, 0x33, 0x81, 0x23, 0x01, 0x65, 0x43], "xor 0x43650123(%rcx), %r8d");
-    test_display(&[0x44, 0x33, 0xc1], "xor %ecx, %r8d");
+    test_att_display(&[0x44, 0x33, 0x08], "xor (%rax),%r9d");
+    test_att_display(&[0x44, 0x33, 0x20], "xor (%rax),%r12d");
+    test_att_display(&[0x44, 0x33, 0x05, 0x78, 0x56, 0x34, 0x12], "xor 0x12345678(%rip),%r8d");
+    test_att_display(&[0x44, 0x33, 0x41, 0x23], "xor 0x23(%rcx),%r8d");
+    test_att_display(&[0x44, 0x33, 0x81, 0x23, 0x01, 0x65, 0x43], "xor 0x43650123(%rcx),%r8d");
+    test_att_display(&[0x44, 0x33, 0xc1], "xor %ecx,%r8d");
 
     // modrm + rex.rb
-    test_display(&[0x45, 0x33, 0x08], "xor (%r8), %r9d");
-    test_display(&[0x45, 0x33, 0x20], "xor (%r8), %r12d");
-    test_display(&[0x45, 0x33, 0x05, 0x78, 0x56, 0x34, 0x12], "xor 0x12345678(%rip), %r8d");
-    test_display(&[0x45, 0x33, 0x41, 0x23], "xor 0x23(%r9), %r8d");
-    test_display(&[0x45, 0x33, 0x81, 0x23, 0x01, 0x65, 0x43], "xor 0x43650123(%r9), %r8d");
-    test_display(&[0x45, 0x33, 0xc1], "xor %r9d, %r8d");
+    test_att_display(&[0x45, 0x33, 0x08], "xor (%r8),%r9d");
+    test_att_display(&[0x45, 0x33, 0x20], "xor (%r8),%r12d");
+    test_att_display(&[0x45, 0x33, 0x05, 0x78, 0x56, 0x34, 0x12], "xor 0x12345678(%rip),%r8d");
+    test_att_display(&[0x45, 0x33, 0x41, 0x23], "xor 0x23(%r9),%r8d");
+    test_att_display(&[0x45, 0x33, 0x81, 0x23, 0x01, 0x65, 0x43], "xor 0x43650123(%r9),%r8d");
+    test_att_display(&[0x45, 0x33, 0xc1], "xor %r9d,%r8d");
 
     // sib
-    test_display(&[0x33, 0x04, 0x25, 0x11, 0x22, 0x33, 0x44], "xor (0x44332211), %eax");
-    test_display(&[0x41, 0x33, 0x04, 0x25, 0x11, 0x22, 0x33, 0x44], "xor (0x44332211), %eax");
+    test_att_display(&[0x33, 0x04, 0x25, 0x11, 0x22, 0x33, 0x44], "xor 0x44332211,%eax");
+    test_att_display(&[0x41, 0x33, 0x04, 0x25, 0x11, 0x22, 0x33, 0x44], "xor 0x44332211,%eax");
+
+    test_att_display(&[0x41, 0x33, 0x44, 0x65, 0x11], "xor 0x11(%r13),%eax");
+
+    test_att_display(&[0x42, 0x33, 0x34, 0x25, 0x20, 0x30, 0x40, 0x50], "xor 0x50403020(,%r12,1),%esi");
+    test_att_display(&[0x0f, 0x1f, 0x44, 0x00, 0x00], "nopl 0x0(%rax,%rax,1)");
+
+    test_att_display(&[0x4f, 0x0f, 0xe7, 0x03], "movntq %mm0,(%r11)");
+    test_att_display(&[0x0f, 0xe7, 0x03], "movntq %mm0,(%rbx)");
+
+    test_att_display(&[0x4f, 0x0f, 0x7f, 0x0f], "movq %mm1,(%r15)");
+    test_att_display(&[0x0f, 0xc4, 0xc0, 0x14], "pinsrw $0x14,%eax,%mm0");
+
+    test_att_display(&[0x4f, 0x0f, 0xd1, 0x00], "psrlw (%r8),%mm0");
+    test_att_display(&[0x0f, 0xe5, 0x3d, 0xaa, 0xbb, 0xcc, 0x77], "pmulhw 0x77ccbbaa(%rip),%mm7");
+
+    // size suffixes, only where operand size is otherwise ambiguous
+    test_att_display(&[0xc7, 0x00, 0x01, 0x00, 0x00, 0x00], "movl $0x1,(%rax)");
+    test_att_display(&[0x48, 0xc7, 0x00, 0x01, 0x00, 0x00, 0x00], "movq $0x1,(%rax)");
+    test_att_display(&[0xc6, 0x00, 0x01], "movb $0x1,(%rax)");
+    test_att_display(&[0xd3, 0x20], "shll %cl,(%rax)");
+    test_att_display(&[0xd3, 0xe0], "shl %cl,%eax");
+    test_att_display(&[0xff, 0x30], "push (%rax)");
+    test_att_display(&[0xf0, 0x48, 0x01, 0x08], "lock add %rcx,(%rax)");
+    test_att_display(&[0x64, 0x48, 0x8b, 0x04, 0x25, 0x28, 0x00, 0x00, 0x00], "mov %fs:0x28,%rax");
+    test_att_display(&[0xf3, 0xa4], "rep movsb %ds:(%rsi),%es:(%rdi)");
+    test_att_display(&[0xf2, 0x0f, 0x2a, 0x00], "cvtsi2sdl (%rax),%xmm0");
+
+    // mnemonics that are spelled differently
+    test_att_display(&[0x0f, 0xb6, 0xc0], "movzbl %al,%eax");
+    test_att_display(&[0x48, 0x0f, 0xbf, 0x00], "movswq (%rax),%rax");
+    test_att_display(&[0x48, 0x63, 0xc7], "movslq %edi,%rax");
+    test_att_display(&[0x48, 0x99], "cqto");
+    test_att_display(&[0x98], "cwtl");
+    test_att_display(&[0xcb], "lret");
+    test_att_display(&[0xde, 0xe9], "fsubrp %st,%st(1)");
+    test_att_display(&[0xdd, 0x00], "fldl (%rax)");
+    test_att_display(&[0xdf, 0x28], "fildll (%rax)");
+
+    // branches
+    test_att_display(&[0xff, 0xd0], "call *%rax");
+    test_att_display(&[0xff, 0x20], "jmp *(%rax)");
+    test_att_display(&[0xeb, 0x10], "jmp $+0x10");
+    test_att_display(&[0xe2, 0xfe], "loop $-0x2");
+
+    // `enter` keeps operand order, and evex decorations stay with their operand
+    test_att_display(&[0xc8, 0x10, 0x00, 0x00], "enter $0x10,$0x0");
+    test_att_display(&[0x62, 0xf1, 0xfd, 0x49, 0x58, 0xc1], "vaddpd %zmm1,%zmm0,%zmm0{%k1}");
+}
 
-    test_display(&[0x41, 0x33, 0x44, 0x65, 0x11], "xor 0x11(%r13), %eax");
+#[test]
+fn test_instructions_att_objdump() {
+    // 64-bit immediates and absolute addresses are `movabs`
+    test_att_display(&[0x48, 0xb8, 0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01], "movabs $0x123456789abcdef,%rax");
+    test_att_display(&[0xa1, 0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01], "movabs 0x123456789abcdef,%eax");
+    // an encoded zero displacement is written out
+    test_att_display(&[0x0f, 0x1f, 0x40, 0x00], "nopl 0x0(%rax)");
+    test_att_display(&[0x48, 0x8b, 0x05, 0x00, 0x00, 0x00, 0x00], "mov 0x0(%rip),%rax");
+    // immediates are unsigned at the operation size
+    test_att_display(&[0x83, 0xc0, 0xff], "add $0xffffffff,%eax");
+    test_att_display(&[0x66, 0x68, 0x90, 0xff], "pushw $0xff90");
+    test_att_display(&[0x0f, 0x94, 0xc0], "sete %al");
+    test_att_display(&[0x0f, 0x44, 0xc1], "cmove %ecx,%eax");
+    test_att_display(&[0xe1, 0xfe], "loope $-0x2");
+    // string instructions name every operand and its segment
+    test_att_display(&[0xf3, 0xa6], "repz cmpsb %es:(%rdi),%ds:(%rsi)");
+    test_att_display(&[0xae], "scas %es:(%rdi),%al");
+    test_att_display(&[0x6c], "insb (%dx),%es:(%rdi)");
+    test_att_display(&[0x6e], "outsb %ds:(%rsi),(%dx)");
+    test_att_display(&[0xec], "in (%dx),%al");
+    test_att_display(&[0xd7], "xlat %ds:(%rbx)");
+    test_att_display(&[0x66, 0x90], "xchg %ax,%ax");
+    test_att_display(&[0x66, 0xc3], "retw");
+    test_att_display(&[0x66, 0xcf], "iretw");
+    test_att_display(&[0x9b], "fwait");
+    // x87 writes `%st(0)` only where it is not implied
+    test_att_display(&[0xd9, 0xc1], "fld %st(1)");
+    test_att_display(&[0xd8, 0xc0], "fadd %st(0),%st");
+    // segment register moves are written at the size the decoder reports, where objdump writes
+    // `%eax`
+    test_att_display(&[0x8e, 0xd8], "mov %ax,%ds");
+    test_att_display(&[0x8c, 0xd8], "mov %ds,%ax");
+}
 
-    test_display(&[0x42, 0x33, 0x34, 0x25, 0x20, 0x30, 0x40, 0x50], "xor 0x50403020(,%r12,1), %esi");
+#[test]
+fn test_instructions_att_suffixes() {
+    let options = DisplayOptions::att().with_att_suffixes();
+    test_display_under(&InstDecoder::default(), options, &[0x33, 0xc1], "xorl %ecx,%eax");
+    test_display_under(&InstDecoder::default(), options, &[0x50], "pushq %rax");
+    test_display_under(&InstDecoder::default(), options, &[0x48, 0xd1, 0xe0], "shlq $0x1,%rax");
+    test_display_under(&InstDecoder::default(), options, &[0xd3, 0xe0], "shll %cl,%eax");
+    // instructions without a general-purpose operand size are unchanged
+    test_display_under(&InstDecoder::default(), options, &[0x0f, 0xe7, 0x03], "movntq %mm0,(%rbx)");
+    test_display_under(&InstDecoder::default(), options, &[0x90], "nop");
+}
 
-    test_display(&[0x4f, 0x0f, 0xe7, 0x03], "movntq %mm0, (%r11)");
-    test_display(&[0x0f, 0xe7, 0x03], "movntq %mm0, (%rbx)");
+#[test]
+fn test_qualified_mnemonics() {
+    fn qualified(data: &[u8]) -> &'static str {
+        InstDecoder::default().decode_slice(data).unwrap().qualified_mnemonic()
+    }
 
-    test_display(&[0x4f, 0x0f, 0x7f, 0x0f], "movq %mm1, (%r15)");
-    test_display(&[0x0f, 0xc4, 0xc0, 0x14], "pinsrw $0x14, %eax, %mm0");
+    assert_eq!(qualified(&[0x48, 0xa5]), "movsq");
+    assert_eq!(qualified(&[0xf3, 0xaa]), "stosb");
+    assert_eq!(qualified(&[0x66, 0xad]), "lodsw");
+    assert_eq!(qualified(&[0xa7]), "cmpsd");
+    assert_eq!(qualified(&[0xd7]), "xlatb");
+    // vcvtsi2ss xmm0, xmm0, rax; cvtsi2sd xmm0, dword [rax]
+    assert_eq!(qualified(&[0xc4, 0xe1, 0xfa, 0x2a, 0xc0]), "vcvtsi2ssq");
+    assert_eq!(qualified(&[0xf2, 0x0f, 0x2a, 0x00]), "cvtsi2sdl");
+    assert_eq!(qualified(&[0x48, 0x01, 0xc8]), "add");
+
+    let options = DisplayOptions::new(DisplayStyle::Intel).with_qualified_mnemonics();
+    test_display_under(&InstDecoder::default(), options, &[0xf3, 0x48, 0xa5], "rep movsq qword es:[rdi], qword ds:[rsi]");
+    test_display_under(&InstDecoder::default(), options, &[0xc4, 0xe1, 0xfa, 0x2a, 0xc0], "vcvtsi2ssq xmm0, xmm0, rax");
+    test_display_under(&InstDecoder::default(), options, &[0x48, 0x01, 0xc8], "add rax, rcx");
+    test_display(&[0xf3, 0x48, 0xa5], "rep movs qword es:[rdi], qword ds:[rsi]");
+}
 
-    test_display(&[0x4f, 0x0f, 0xd1, 0x00], "psrlw (%r8), %mm0");
-    test_display(&[0x0f, 0xe5, 0x3d, 0xaa, 0xbb, 0xcc, 0x77], "pmulhw 0x77ccbbaa(%rip), %mm7");
+#[test]
+fn test_branch_hints() {
+    let options = DisplayOptions::new(DisplayStyle::Intel).with_branch_hints();
+    test_display_under(&InstDecoder::default(), options, &[0x3e, 0x74, 0x05], "jz,pt $+0x5");
+    test_display_under(&InstDecoder::default(), options, &[0x2e, 0x0f, 0x85, 0x00, 0x01, 0x00, 0x00], "jnz,pn $+0x100");
+    test_display_under(&InstDecoder::default(), options, &[0x3e, 0xeb, 0x05], "jmp $+0x5");
+    let options = DisplayOptions::att().with_branch_hints();
+    test_display_under(&InstDecoder::default(), options, &[0x3e, 0x74, 0x05], "je,pt $+0x5");
+    // hints are not shown by default.
+    test_display(&[0x3e, 0x74, 0x05], "jz $+0x5");
 }
 
 #[test]
@@ -190,3 +308,362 @@ fn test_instructions_c() {
 
     test_c_display(&[0xff, 0xe0], "jmp rax");
 }
+
+/// marks each token a `DisplaySink` is told about, so tests can see how text was split up.
+struct TaggingSink(String);
+
+impl Write for TaggingSink {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0.write_str(s)
+    }
+}
+
+impl DisplaySink for TaggingSink {
+    fn write_opcode(&mut self, _opcode: Opcode, text: &str) -> std::fmt::Result {
+        write!(self.0, "<op:{}>", text)
+    }
+
+    fn write_register(&mut self, reg: RegSpec, text: &str) -> std::fmt::Result {
+        assert_eq!(reg.name(), text);
+        write!(self.0, "<reg:{}>", text)
+    }
+
+    fn write_immediate<D: std::fmt::Display + ?Sized>(&mut self, value: i64, _text: &D) -> std::fmt::Result {
+        write!(self.0, "<imm:{}>", value)
+    }
+
+    fn write_displacement<D: std::fmt::Display + ?Sized>(&mut self, disp: i32, _text: &D) -> std::fmt::Result {
+        write!(self.0, "<disp:{}>", disp)
+    }
+
+    fn write_address<D: std::fmt::Display + ?Sized>(&mut self, address: u64, _text: &D) -> std::fmt::Result {
+        write!(self.0, "<addr:{:x}>", address)
+    }
+
+    fn write_rip_relative<D: std::fmt::Display + ?Sized>(&mut self, disp: i32, _text: &D) -> std::fmt::Result {
+        write!(self.0, "<rip:{}>", disp)
+    }
+
+    fn write_branch_offset<D: std::fmt::Display + ?Sized>(&mut self, offset: i64, _text: &D) -> std::fmt::Result {
+        write!(self.0, "<rel:{}>", offset)
+    }
+}
+
+fn test_sink_under<O: Into<DisplayOptions>>(style: O, data: &[u8], expected: &'static str) {
+    let mut reader = yaxpeax_arch::U8Reader::new(data);
+    let instr = InstDecoder::default().decode(&mut reader).expect("instruction decodes");
+    let mut sink = TaggingSink(String::new());
+    instr.display_with(style).write_to_sink(&mut sink).unwrap();
+    assert_eq!(sink.0, expected);
+}
+
+#[test]
+fn test_display_sink() {
+    test_sink_under(DisplayStyle::Intel, &[0x48, 0x8b, 0x44, 0x24, 0xf8], "<op:mov> <reg:rax>, qword [<reg:rsp> - <disp:-8>]");
+    test_sink_under(DisplayStyle::Intel, &[0x8b, 0x04, 0x25, 0x34, 0x12, 0x00, 0x00], "<op:mov> <reg:eax>, dword [<addr:1234>]");
+    test_sink_under(DisplayStyle::Intel, &[0x48, 0x83, 0xc0, 0xf0], "<op:add> <reg:rax>, <imm:-16>");
+    test_sink_under(DisplayStyle::Intel, &[0xe9, 0x10, 0x00, 0x00, 0x00], "<op:jmp> <rel:16>");
+    test_sink_under(DisplayStyle::Intel, &[0x62, 0xf1, 0xfd, 0x49, 0x58, 0xc1], "<op:vaddpd> <reg:zmm0>{<reg:k1>}, <reg:zmm0>, <reg:zmm1>");
+    test_sink_under(DisplayOptions::att(), &[0x48, 0x8b, 0x44, 0x24, 0xf8], "<op:mov> <disp:-8>(%<reg:rsp>),%<reg:rax>");
+    test_sink_under(DisplayOptions::att(), &[0x48, 0x83, 0xc0, 0xf0], "<op:add> $<imm:-16>,%<reg:rax>");
+    test_sink_under(DisplayOptions::att(), &[0xc6, 0x00, 0x01], "<op:movb> $<imm:1>,(%<reg:rax>)");
+    test_sink_under(DisplayOptions::att(), &[0x74, 0xfe], "<op:je> <rel:-2>");
+    test_sink_under(DisplayStyle::Intel, &[0x48, 0x8b, 0x05, 0x10, 0x00, 0x00, 0x00], "<op:mov> <reg:rax>, qword [<rip:16>]");
+    test_sink_under(DisplayOptions::att(), &[0x48, 0x8b, 0x05, 0x10, 0x00, 0x00, 0x00], "<op:mov> <rip:16>,%<reg:rax>");
+    test_sink_under(DisplayStyle::C, &[0x74, 0xfe], "if zero(rflags) then jmp <rel:-2>");
+}
+
+#[test]
+fn test_display_sink_defaults_match_display() {
+    struct Plain(String);
+
+    impl Write for Plain {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            self.0.write_str(s)
+        }
+    }
+
+    impl DisplaySink for Plain {}
+
+    let cases: &[&[u8]] = &[
+        &[0x48, 0x8b, 0x44, 0x24, 0xf8],
+        &[0x8b, 0x04, 0x25, 0x34, 0x12, 0x00, 0x00],
+        &[0xe8, 0x10, 0x00, 0x00, 0x00],
+        &[0x62, 0xf1, 0xfd, 0x49, 0x58, 0xc1],
+        &[0xc7, 0x80, 0x00, 0x00, 0x00, 0x80, 0x01, 0x00, 0x00, 0x00],
+        &[0x48, 0x8b, 0x05, 0xf0, 0xff, 0xff, 0xff],
+        &[0x48, 0x8b, 0x05, 0x00, 0x00, 0x00, 0x00],
+    ];
+    for data in cases {
+        let mut reader = yaxpeax_arch::U8Reader::new(data);
+        let instr = InstDecoder::default().decode(&mut reader).expect("instruction decodes");
+        for style in [DisplayOptions::new(DisplayStyle::Intel), DisplayOptions::new(DisplayStyle::C), DisplayOptions::att()] {
+            let mut sink = Plain(String::new());
+            instr.display_with(style).write_to_sink(&mut sink).unwrap();
+            assert_eq!(sink.0, instr.display_with(style).to_string());
+        }
+    }
+}
+
+struct Symbols;
+
+impl SymbolResolver for Symbols {
+    fn resolve(&self, address: u64) -> Option<(&str, u64)> {
+        match address {
+            0x1000..=0x10ff => Some(("foo", address - 0x1000)),
+            0x2000..=0x2fff => Some(("data", address - 0x2000)),
+            _ => None,
+        }
+    }
+}
+
+fn test_symbols_under<O: Into<DisplayOptions>>(style: O, address: u64, data: &[u8], expected: &'static str) {
+    let mut reader = yaxpeax_arch::U8Reader::new(data);
+    let instr = InstDecoder::default().decode(&mut reader).expect("instruction decodes");
+    let text = instr.display_with(style).with_symbols(address, &Symbols).to_string();
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn test_display_with_symbols() {
+    // jmp to 0x1000 + 2 + 0x0e = 0x1010
+    test_symbols_under(DisplayStyle::Intel, 0x1000, &[0xeb, 0x0e], "jmp foo+0x10");
+    test_symbols_under(DisplayOptions::att(), 0x1000, &[0xeb, 0x0e], "jmp foo+0x10");
+    test_symbols_under(DisplayStyle::C, 0x1000, &[0xeb, 0x0e], "jmp foo+0x10");
+    test_symbols_under(DisplayStyle::Intel, 0x1010, &[0x75, 0xee], "jnz foo");
+    // targets without a symbol are written as usual
+    test_symbols_under(DisplayStyle::Intel, 0x3000, &[0xeb, 0x0e], "jmp $+0xe");
+    test_symbols_under(DisplayOptions::att(), 0x4000, &[0xe9, 0x00, 0x10, 0x00, 0x00], "jmp $+0x1000");
+    // rip-relative operands are relative to the end of the instruction
+    test_symbols_under(DisplayStyle::Intel, 0x1ff9, &[0x48, 0x8b, 0x05, 0x08, 0x00, 0x00, 0x00], "mov rax, qword [rip + data+0x8]");
+    test_symbols_under(DisplayOptions::att(), 0x1ff9, &[0x48, 0x8b, 0x05, 0x08, 0x00, 0x00, 0x00], "mov data+0x8(%rip),%rax");
+    test_symbols_under(DisplayStyle::Intel, 0x2010, &[0x48, 0x8d, 0x05, 0xe9, 0xff, 0xff, 0xff], "lea rax, qword [rip + data]");
+    test_symbols_under(DisplayStyle::Intel, 0x5000, &[0x48, 0x8b, 0x05, 0x08, 0x00, 0x00, 0x00], "mov rax, qword [rip + 0x8]");
+    test_symbols_under(DisplayOptions::att(), 0x5000, &[0x48, 0x8b, 0x05, 0x08, 0x00, 0x00, 0x00], "mov 0x8(%rip),%rax");
+}
+
+/// relocations at fixed offsets into an instruction, as an object file would list them.
+struct Relocs(&'static [(u8, u8, &'static str, i64)]);
+
+impl RelocResolver for Relocs {
+    fn resolve(&self, offset: u8, width: u8) -> Option<(&str, i64)> {
+        self.0.iter()
+            .find(|reloc| reloc.0 == offset && reloc.1 == width)
+            .map(|reloc| (reloc.2, reloc.3))
+    }
+}
+
+fn test_relocs_under<O: Into<DisplayOptions>>(style: O, relocs: &'static [(u8, u8, &'static str, i64)], data: &[u8], expected: &'static str) {
+    let instr = InstDecoder::default().decode_slice(data).expect("instruction decodes");
+    let text = instr.display_with(style).with_relocations(&Relocs(relocs)).to_string();
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn test_display_with_relocations() {
+    // mov rax, imm64
+    let imm64 = &[0x48, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0];
+    test_relocs_under(DisplayStyle::Intel, &[(2, 8, "foo", 0)], imm64, "mov rax, offset foo");
+    test_relocs_under(DisplayOptions::att(), &[(2, 8, "foo", 0)], imm64, "movabs $foo,%rax");
+    // call rel32, with the pc-relative addend left to the caller
+    test_relocs_under(DisplayStyle::Intel, &[(1, 4, "foo", -4)], &[0xe8, 0, 0, 0, 0], "call foo-0x4");
+    test_relocs_under(DisplayStyle::C, &[(1, 4, "foo", 0)], &[0xe9, 0, 0, 0, 0], "jmp foo");
+    // mov eax, dword [rbx + disp32]: a zero placeholder is still replaced
+    let disp32 = &[0x8b, 0x83, 0, 0, 0, 0];
+    test_relocs_under(DisplayStyle::Intel, &[(2, 4, "foo", 0)], disp32, "mov eax, dword [rbx + foo]");
+    test_relocs_under(DisplayOptions::att(), &[(2, 4, "foo", 8)], disp32, "mov foo+0x8(%rbx),%eax");
+    test_relocs_under(DisplayStyle::Intel, &[(2, 4, "foo", -4)], &[0x8b, 0x83, 0xfc, 0xff, 0xff, 0xff], "mov eax, dword [rbx + foo-0x4]");
+    // rip-relative, and an immediate after the displacement
+    test_relocs_under(DisplayStyle::Intel, &[(2, 4, "foo", 0)], &[0x8b, 0x05, 0, 0, 0, 0], "mov eax, dword [rip + foo]");
+    test_relocs_under(DisplayStyle::Intel, &[(2, 4, "foo", 0), (6, 4, "bar", 0)], &[0xc7, 0x83, 0, 0, 0, 0, 0, 0, 0, 0], "mov dword [rbx + foo], offset bar");
+    // fields without relocations are written as usual
+    test_relocs_under(DisplayStyle::Intel, &[(2, 4, "foo", 0)], &[0x8b, 0x43, 0x08], "mov eax, dword [rbx + 0x8]");
+    test_relocs_under(DisplayStyle::Intel, &[(6, 4, "bar", 0)], &[0xc7, 0x83, 0x10, 0, 0, 0, 0, 0, 0, 0], "mov dword [rbx + 0x10], offset bar");
+    test_relocs_under(DisplayStyle::Intel, &[], &[0x8b, 0x43, 0xf8], "mov eax, dword [rbx - 0x8]");
+}
+
+#[cfg(feature = "colors")]
+#[test]
+fn test_display_with_colors() {
+    use yaxpeax_x86::long_mode::Palette;
+
+    fn colored<O: Into<DisplayOptions>>(palette: Palette, style: O, data: &[u8]) -> String {
+        let instr = InstDecoder::default().decode_slice(data).expect("instruction decodes");
+        instr.display_with(style).with_colors(palette).to_string()
+    }
+
+    let mov = &[0x48, 0x8b, 0x43, 0x08];
+    assert_eq!(
+        colored(Palette::new(), DisplayStyle::Intel, mov),
+        "\x1b[1mmov\x1b[0m \x1b[34mrax\x1b[0m, qword [\x1b[34mrbx\x1b[0m + \x1b[33m0x8\x1b[0m]"
+    );
+    assert_eq!(
+        colored(Palette::new(), DisplayOptions::att(), mov),
+        "\x1b[1mmov\x1b[0m \x1b[33m0x8\x1b[0m(%\x1b[34mrbx\x1b[0m),%\x1b[34mrax\x1b[0m"
+    );
+    assert_eq!(
+        colored(Palette::new(), DisplayStyle::Intel, &[0x48, 0x8b, 0x05, 0x08, 0x00, 0x00, 0x00]),
+        "\x1b[1mmov\x1b[0m \x1b[34mrax\x1b[0m, qword [\x1b[36mrip + 0x8\x1b[0m]"
+    );
+    assert_eq!(
+        colored(Palette::plain().with_address("4"), DisplayStyle::Intel, &[0xeb, 0x0e]),
+        "jmp \x1b[4m$+0xe\x1b[0m"
+    );
+    assert_eq!(
+        colored(Palette::plain().with_immediate("31"), DisplayStyle::Intel, &[0x83, 0xc0, 0x01]),
+        "add eax, \x1b[31m0x1\x1b[0m"
+    );
+    // an uncolored palette writes the same text as `Display`.
+    assert_eq!(colored(Palette::plain(), DisplayStyle::Intel, mov), "mov rax, qword [rbx + 0x8]");
+}
+
+fn listing_line<O: Into<DisplayOptions>>(address: u64, data: &[u8], style: O) -> String {
+    let instr = InstDecoder::default().decode_slice(data).expect("instruction decodes");
+    instr.display_with(style).listing_line(address, data).to_string()
+}
+
+#[test]
+fn test_listing_lines() {
+    assert_eq!(listing_line(0x401000, &[0x48, 0x89, 0xe5], DisplayStyle::Intel),
+        "00401000: 48 89 e5              mov    rbp, rsp");
+    assert_eq!(listing_line(0x401000, &[0x48, 0x89, 0xe5], DisplayOptions::att()),
+        "00401000: 48 89 e5              mov    %rsp,%rbp");
+    // bytes past the end of the instruction are not shown
+    assert_eq!(listing_line(0x401000, &[0x90, 0x90], DisplayStyle::Intel),
+        "00401000: 90                    nop");
+    assert_eq!(listing_line(0x401000, &[0xf0, 0x01, 0x08], DisplayStyle::Intel),
+        "00401000: f0 01 08              lock add dword [rax], ecx");
+    // bytes that do not fit continue on the next line
+    assert_eq!(listing_line(0x401003, &[0x48, 0xb8, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11], DisplayStyle::Intel),
+        "00401003: 48 b8 88 77 66 55 44  mov    rax, 0x1122334455667788\n0040100a: 33 22 11");
+
+    let instr = InstDecoder::default().decode_slice(&[0x48, 0x89, 0xe5]).unwrap();
+    let line = instr.display_with(DisplayStyle::Intel).listing_line(0x401000, &[0x48, 0x89, 0xe5])
+        .with_address_digits(16)
+        .with_bytes_per_line(4)
+        .with_mnemonic_width(0);
+    assert_eq!(line.to_string(), "0000000000401000: 48 89 e5     mov rbp, rsp");
+    let mut text = String::new();
+    line.with_bytes_per_line(0).write_to(&mut text).unwrap();
+    assert_eq!(text, "0000000000401000: mov rbp, rsp");
+}
+
+#[test]
+fn test_alternate_display() {
+    let data = [0x48, 0x89, 0xe5];
+    let instr = InstDecoder::default().with_retain_bytes().decode_slice(&data).unwrap();
+    assert_eq!(format!("{:#}", instr), "48 89 e5              mov    rbp, rsp");
+    assert_eq!(format!("{:#}", instr.display_with(DisplayOptions::att())), "48 89 e5              mov    %rsp,%rbp");
+    assert_eq!(format!("{}", instr), "mov rbp, rsp");
+
+    // without retained bytes, the bytes are placeholders
+    let instr = InstDecoder::default().decode_slice(&data).unwrap();
+    assert_eq!(format!("{:#}", instr), "?? ?? ??              mov    rbp, rsp");
+
+    let data = [0x48, 0xb8, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11];
+    let instr = InstDecoder::default().with_retain_bytes().decode_slice(&data).unwrap();
+    assert_eq!(format!("{:#}", instr), "48 b8 88 77 66 55 44 33 22 11  mov    rax, 0x1122334455667788");
+}
+
+#[test]
+fn opcode_mnemonics() {
+    assert_eq!(Opcode::from_mnemonic("mov"), Some(Opcode::MOV));
+    assert_eq!(Opcode::from_mnemonic("VPADDD"), Some(Opcode::VPADDD));
+    assert_eq!(Opcode::from_mnemonic("lock add"), None);
+    assert_eq!(Opcode::from_mnemonic("invalid"), None);
+    assert_eq!(Opcode::from_mnemonic(""), None);
+
+    let mut count = 0;
+    for (opcode, mnemonic) in Opcode::all() {
+        assert_ne!(opcode, Opcode::Invalid);
+        assert_eq!(opcode.to_string(), mnemonic);
+        assert_eq!(Opcode::from_mnemonic(mnemonic), Some(opcode));
+        count += 1;
+    }
+    assert_eq!(Opcode::all().len(), count);
+}
+
+#[test]
+fn mnemonic_aliases() {
+    use yaxpeax_x86::long_mode::MnemonicAliases;
+
+    assert_eq!(Opcode::JNZ.aliases(), &["jne"]);
+    assert_eq!(Opcode::MOV.aliases(), &[] as &[&str]);
+    assert_eq!(Opcode::from_mnemonic("jne"), Some(Opcode::JNZ));
+    assert_eq!(Opcode::from_mnemonic("SETNAE"), Some(Opcode::SETB));
+    // `sal` names `Opcode::SAL` before it is an alias of `Opcode::SHL`.
+    assert_eq!(Opcode::from_mnemonic("sal"), Some(Opcode::SAL));
+    for (opcode, _) in Opcode::all() {
+        for alias in opcode.aliases() {
+            assert!(Opcode::from_mnemonic(alias).is_some(), "alias {} of {:?}", alias, opcode);
+        }
+    }
+
+    let gnu = DisplayOptions::new(DisplayStyle::Intel).with_aliases(MnemonicAliases::gnu());
+    test_display_under(&InstDecoder::default(), gnu, &[0x75, 0x05], "jne $+0x5");
+    test_display_under(&InstDecoder::default(), gnu, &[0x0f, 0x43, 0xc1], "cmovae eax, ecx");
+    test_display_under(&InstDecoder::default(), gnu, &[0x0f, 0x94, 0xc0], "sete al");
+    test_display_under(&InstDecoder::default(), gnu, &[0x72, 0x05], "jb $+0x5");
+    test_display_under(&InstDecoder::default(), gnu, &[0xf3, 0xc3], "repz ret");
+    test_display_under(&InstDecoder::default(), gnu, &[0x90], "nop");
+    test_display_under(&InstDecoder::default(), gnu, &[0xd1, 0xe0], "shl eax, 0x1");
+    test_display(&[0xf3, 0xc3], "ret");
+    test_display(&[0x75, 0x05], "jnz $+0x5");
+
+    let others = MnemonicAliases::new().with_sal().with_xchg_nop();
+    let intel = DisplayOptions::new(DisplayStyle::Intel).with_aliases(others);
+    test_display_under(&InstDecoder::default(), intel, &[0xd1, 0xe0], "sal eax, 0x1");
+    test_display_under(&InstDecoder::default(), intel, &[0x90], "xchg eax, eax");
+    test_display_under(&InstDecoder::default(), intel, &[0x66, 0x90], "xchg ax, ax");
+    test_display_under(&InstDecoder::default(), intel, &[0xf3, 0x90], "nop");
+    let att = DisplayOptions::att().with_aliases(others.with_gnu_conditions());
+    test_display_under(&InstDecoder::default(), att, &[0x48, 0xd1, 0xe0], "sal $0x1,%rax");
+    test_display_under(&InstDecoder::default(), att, &[0x66, 0x90], "xchg %ax,%ax");
+    test_display_under(&InstDecoder::default(), att, &[0x0f, 0x95, 0x00], "setne (%rax)");
+}
+
+#[test]
+fn operand_display() {
+    use yaxpeax_x86::long_mode::{Operand, Segment};
+
+    // the operands of an instruction, joined, are what the instruction writes after its mnemonic.
+    for data in [
+        &[0x64, 0x8b, 0x44, 0x8b, 0x10][..],
+        &[0x48, 0x89, 0x05, 0x10, 0x00, 0x00, 0x00],
+        &[0x62, 0xf1, 0x7c, 0xdd, 0x14, 0x0a],
+        &[0xc4, 0xe3, 0x79, 0x4a, 0xc1, 0x20],
+        &[0xa4],
+    ].iter() {
+        let instr = InstDecoder::default().decode_slice(data).unwrap();
+        for style in [DisplayOptions::new(DisplayStyle::Intel), DisplayOptions::att()].iter() {
+            let mut operands: Vec<String> = (0..instr.operand_count())
+                .map(|i| instr.display_operand(i, *style).to_string())
+                .collect();
+            let separator = if *style == DisplayOptions::att() {
+                operands.reverse();
+                ","
+            } else {
+                ", "
+            };
+            let text = instr.display_with(*style).to_string();
+            assert!(text.ends_with(&format!(" {}", operands.join(separator))), "{} vs {:?}", text, operands);
+        }
+    }
+
+    let instr = InstDecoder::default().decode_slice(&[0x64, 0x8b, 0x44, 0x8b, 0x10]).unwrap();
+    assert_eq!(instr.display_operand(1, DisplayStyle::Intel).to_string(), "dword fs:[rbx + rcx * 4 + 0x10]");
+    assert_eq!(instr.display_operand(1, DisplayOptions::att()).to_string(), "%fs:0x10(%rbx,%rcx,4)");
+
+    // an operand alone has no size or segment, unless given them.
+    let mem = Operand::RegIndexBaseScaleDisp(RegSpec::rbx(), RegSpec::rcx(), 4, 0x10);
+    assert_eq!(mem.display_with(DisplayStyle::Intel).to_string(), "[rbx + rcx * 4 + 0x10]");
+    assert_eq!(mem.display_with(DisplayOptions::att()).to_string(), "0x10(%rbx,%rcx,4)");
+    let qualified = mem.display_with(DisplayStyle::Intel)
+        .with_mem_size(instr.mem_size().unwrap())
+        .with_segment(Segment::GS);
+    assert_eq!(qualified.to_string(), "dword gs:[rbx + rcx * 4 + 0x10]");
+    // registers and immediates ignore both.
+    let reg = Operand::Register(RegSpec::rax()).display_with(DisplayOptions::att()).with_segment(Segment::GS);
+    assert_eq!(reg.to_string(), "%rax");
+    assert_eq!(Operand::ImmediateI8(-1).display_with(DisplayOptions::att()).to_string(), "$-0x1");
+}
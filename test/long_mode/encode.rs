@@ -0,0 +1,210 @@
+use yaxpeax_x86::long_mode::{EncodeError, InstDecoder, InstEncoder, Opcode, Operand, RegSpec};
+
+fn test_roundtrip(data: &[u8]) {
+    let inst = InstDecoder::default().decode_slice(data).unwrap_or_else(|e| {
+        panic!("failed to decode {:02x?}: {}", data, e);
+    });
+    let encoded = InstEncoder::from_instruction(&inst).encode().unwrap_or_else(|e| {
+        panic!("failed to encode {} (from {:02x?}): {}", inst, data, e);
+    });
+    assert_eq!(
+        encoded.bytes(), data,
+        "re-encoding {} produced {:02x?}, expected {:02x?}", inst, encoded.bytes(), data
+    );
+}
+
+#[test]
+fn test_roundtrip_gp() {
+    // alu, both directions, immediates of each width
+    test_roundtrip(&[0x31, 0xc0]);
+    test_roundtrip(&[0x33, 0xc0]);
+    test_roundtrip(&[0x48, 0x01, 0xd8]);
+    test_roundtrip(&[0x33, 0x44, 0x24, 0x10]);
+    test_roundtrip(&[0x48, 0x83, 0xc0, 0x01]);
+    test_roundtrip(&[0x48, 0x81, 0xe9, 0x00, 0x01, 0x00, 0x00]);
+    test_roundtrip(&[0x05, 0x78, 0x56, 0x34, 0x12]);
+    test_roundtrip(&[0x3c, 0x7f]);
+    test_roundtrip(&[0x66, 0x83, 0x38, 0xff]);
+    test_roundtrip(&[0x41, 0x80, 0x00, 0x05]);
+    test_roundtrip(&[0xf0, 0x48, 0x01, 0x08]);
+    // mov
+    test_roundtrip(&[0x48, 0x89, 0xe5]);
+    test_roundtrip(&[0x4c, 0x8b, 0x44, 0x24, 0xf8]);
+    test_roundtrip(&[0x48, 0xc7, 0xc0, 0x01, 0x00, 0x00, 0x00]);
+    test_roundtrip(&[0x48, 0xb8, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11]);
+    test_roundtrip(&[0xb8, 0x01, 0x00, 0x00, 0x00]);
+    test_roundtrip(&[0x41, 0xb0, 0x12]);
+    test_roundtrip(&[0xc6, 0x00, 0x12]);
+    test_roundtrip(&[0x66, 0xc7, 0x00, 0x34, 0x12]);
+    test_roundtrip(&[0x40, 0x88, 0xf0]);
+    test_roundtrip(&[0x88, 0xe0]);
+    test_roundtrip(&[0x8c, 0xd8]);
+    test_roundtrip(&[0x0f, 0x20, 0xd8]);
+    test_roundtrip(&[0x64, 0x48, 0x8b, 0x04, 0x25, 0x28, 0x00, 0x00, 0x00]);
+    test_roundtrip(&[0xa1, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11]);
+    // addressing forms
+    test_roundtrip(&[0x48, 0x8b, 0x05, 0x10, 0x00, 0x00, 0x00]);
+    test_roundtrip(&[0x8b, 0x45, 0x00]);
+    test_roundtrip(&[0x41, 0x8b, 0x45, 0x00]);
+    test_roundtrip(&[0x41, 0x8b, 0x04, 0x24]);
+    test_roundtrip(&[0x8b, 0x04, 0x88]);
+    test_roundtrip(&[0x42, 0x8b, 0x44, 0xa8, 0x10]);
+    test_roundtrip(&[0x8b, 0x04, 0xcd, 0x00, 0x10, 0x00, 0x00]);
+    test_roundtrip(&[0x8b, 0x80, 0x00, 0x01, 0x00, 0x00]);
+    test_roundtrip(&[0x67, 0x8b, 0x00]);
+    test_roundtrip(&[0x48, 0x8d, 0x04, 0x40]);
+    // unary, shifts, multiply
+    test_roundtrip(&[0x48, 0xf7, 0xd8]);
+    test_roundtrip(&[0xf7, 0xe1]);
+    test_roundtrip(&[0x48, 0xff, 0xc0]);
+    test_roundtrip(&[0xfe, 0x08]);
+    test_roundtrip(&[0x48, 0xd1, 0xe0]);
+    test_roundtrip(&[0x48, 0xc1, 0xf8, 0x3f]);
+    test_roundtrip(&[0xd3, 0xe8]);
+    // the width of a shift comes from its destination, not the `cl` count.
+    test_roundtrip(&[0xd3, 0x20]);
+    test_roundtrip(&[0x48, 0xd3, 0x20]);
+    test_roundtrip(&[0xd3, 0x10]);
+    test_roundtrip(&[0x48, 0x0f, 0xaf, 0xc1]);
+    test_roundtrip(&[0x6b, 0xc0, 0x0a]);
+    test_roundtrip(&[0x69, 0xc0, 0x00, 0x01, 0x00, 0x00]);
+    test_roundtrip(&[0x48, 0x85, 0xc0]);
+    test_roundtrip(&[0xa8, 0x01]);
+    test_roundtrip(&[0xf6, 0xc1, 0x01]);
+    test_roundtrip(&[0x48, 0x87, 0xd8]);
+    test_roundtrip(&[0x48, 0x93]);
+    test_roundtrip(&[0x0f, 0xb6, 0xc0]);
+    test_roundtrip(&[0x48, 0x0f, 0xbf, 0x00]);
+    test_roundtrip(&[0x48, 0x63, 0xc7]);
+    test_roundtrip(&[0x48, 0x0f, 0xa3, 0xc8]);
+    test_roundtrip(&[0x48, 0x0f, 0xba, 0xe8, 0x05]);
+    test_roundtrip(&[0x0f, 0xbc, 0xc1]);
+    test_roundtrip(&[0x48, 0x0f, 0xc8]);
+    test_roundtrip(&[0xf0, 0x0f, 0xb1, 0x0a]);
+    test_roundtrip(&[0xf0, 0x48, 0x0f, 0xc1, 0x02]);
+}
+
+#[test]
+fn test_roundtrip_control_flow() {
+    test_roundtrip(&[0x55]);
+    test_roundtrip(&[0x41, 0x5d]);
+    test_roundtrip(&[0x6a, 0x01]);
+    test_roundtrip(&[0x68, 0x00, 0x01, 0x00, 0x00]);
+    test_roundtrip(&[0xff, 0x30]);
+    test_roundtrip(&[0x8f, 0x00]);
+    test_roundtrip(&[0xe8, 0x00, 0x00, 0x00, 0x00]);
+    test_roundtrip(&[0xff, 0xd0]);
+    test_roundtrip(&[0xff, 0x25, 0x00, 0x10, 0x00, 0x00]);
+    test_roundtrip(&[0xeb, 0xfe]);
+    test_roundtrip(&[0xe9, 0x00, 0x01, 0x00, 0x00]);
+    test_roundtrip(&[0x74, 0x10]);
+    test_roundtrip(&[0x0f, 0x8f, 0x00, 0x01, 0x00, 0x00]);
+    test_roundtrip(&[0xe2, 0xfe]);
+    test_roundtrip(&[0x0f, 0x94, 0xc0]);
+    test_roundtrip(&[0x48, 0x0f, 0x44, 0xc1]);
+    test_roundtrip(&[0xc3]);
+    test_roundtrip(&[0xc2, 0x08, 0x00]);
+    test_roundtrip(&[0xcc]);
+    test_roundtrip(&[0xcd, 0x80]);
+    test_roundtrip(&[0x90]);
+    test_roundtrip(&[0xc9]);
+    test_roundtrip(&[0x48, 0x99]);
+    test_roundtrip(&[0x0f, 0x05]);
+    test_roundtrip(&[0x0f, 0xa2]);
+    test_roundtrip(&[0x0f, 0xae, 0xf0]);
+    test_roundtrip(&[0xf3, 0x48, 0xa5]);
+    test_roundtrip(&[0xf3, 0xaa]);
+    test_roundtrip(&[0xf2, 0xae]);
+    // string instructions with a 32-bit address size.
+    test_roundtrip(&[0x67, 0xa5]);
+    test_roundtrip(&[0xf3, 0x67, 0xa4]);
+    test_roundtrip(&[0x66, 0x67, 0xa5]);
+}
+
+#[test]
+fn test_roundtrip_sse() {
+    test_roundtrip(&[0x0f, 0x10, 0xc1]);
+    test_roundtrip(&[0x0f, 0x11, 0x00]);
+    test_roundtrip(&[0x66, 0x0f, 0x6f, 0x00]);
+    test_roundtrip(&[0xf3, 0x0f, 0x7f, 0x00]);
+    test_roundtrip(&[0xf2, 0x0f, 0x10, 0x44, 0x24, 0x08]);
+    test_roundtrip(&[0xf3, 0x44, 0x0f, 0x58, 0xc1]);
+    test_roundtrip(&[0x0f, 0x57, 0xc0]);
+    test_roundtrip(&[0x66, 0x0f, 0x2e, 0xc1]);
+    test_roundtrip(&[0xf2, 0x48, 0x0f, 0x2a, 0xc0]);
+    test_roundtrip(&[0xf2, 0x0f, 0x2c, 0xc0]);
+    test_roundtrip(&[0x66, 0x0f, 0xef, 0xc0]);
+    test_roundtrip(&[0x0f, 0xef, 0xc0]);
+    test_roundtrip(&[0x66, 0x0f, 0x70, 0xc0, 0x1b]);
+    test_roundtrip(&[0x0f, 0x6e, 0xc0]);
+    test_roundtrip(&[0x66, 0x0f, 0x7e, 0xc0]);
+    test_roundtrip(&[0x66, 0x48, 0x0f, 0x6e, 0xc0]);
+    test_roundtrip(&[0xf3, 0x0f, 0x7e, 0xc1]);
+    test_roundtrip(&[0x66, 0x0f, 0xd6, 0x00]);
+    test_roundtrip(&[0x0f, 0x6f, 0xc1]);
+    // `rex.w` selects a 64-bit memory operand.
+    test_roundtrip(&[0x48, 0x0f, 0x7e, 0x00]);
+    test_roundtrip(&[0x48, 0x0f, 0x6e, 0x00]);
+    test_roundtrip(&[0xf2, 0x48, 0x0f, 0x2a, 0x00]);
+}
+
+#[test]
+fn test_encode_builder() {
+    let encoded = InstEncoder::new(Opcode::SUB)
+        .with_operand(Operand::Register(RegSpec::rsp()))
+        .with_operand(Operand::ImmediateI8(0x28))
+        .encode()
+        .unwrap();
+    assert_eq!(encoded.bytes(), &[0x48, 0x83, 0xec, 0x28]);
+
+    // with no register operand, the memory size must be specified.
+    let encoder = InstEncoder::new(Opcode::ADD)
+        .with_operand(Operand::RegDeref(RegSpec::rax()))
+        .with_operand(Operand::ImmediateI8(1));
+    assert_eq!(encoder.clone().encode(), Err(EncodeError::AmbiguousSize));
+    assert_eq!(encoder.with_mem_size(4).encode().unwrap().bytes(), &[0x83, 0x00, 0x01]);
+
+    let mut buf = [0u8; 2];
+    assert_eq!(InstEncoder::new(Opcode::NOP).encode_into(&mut buf), Ok(1));
+    assert_eq!(buf[0], 0x90);
+}
+
+#[test]
+fn test_encode_invalid() {
+    // `ah` can't be encoded alongside a rex prefix, which `sil` requires.
+    assert_eq!(
+        InstEncoder::new(Opcode::MOV)
+            .with_operand(Operand::Register(RegSpec::ah()))
+            .with_operand(Operand::Register(RegSpec::sil()))
+            .encode(),
+        Err(EncodeError::InvalidOperands)
+    );
+    // `rsp` can't be an index register.
+    assert_eq!(
+        InstEncoder::new(Opcode::MOV)
+            .with_operand(Operand::Register(RegSpec::eax()))
+            .with_operand(Operand::RegIndexBase(RegSpec::rax(), RegSpec::rsp()))
+            .encode(),
+        Err(EncodeError::InvalidOperands)
+    );
+    assert_eq!(
+        InstEncoder::new(Opcode::MOV)
+            .with_operand(Operand::Register(RegSpec::eax()))
+            .with_operand(Operand::ImmediateI32(0))
+            .with_operand(Operand::ImmediateI32(0))
+            .encode(),
+        Err(EncodeError::InvalidOperands)
+    );
+    // apx extended gprs need a `rex2` prefix.
+    assert_eq!(
+        InstEncoder::new(Opcode::MOV)
+            .with_operand(Operand::Register(RegSpec::r16()))
+            .with_operand(Operand::Register(RegSpec::rax()))
+            .encode(),
+        Err(EncodeError::InvalidOperands)
+    );
+    assert_eq!(
+        InstEncoder::new(Opcode::VADDPS).encode(),
+        Err(EncodeError::UnsupportedOpcode)
+    );
+}
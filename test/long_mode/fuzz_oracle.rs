@@ -0,0 +1,36 @@
+use yaxpeax_x86::long_mode::InstDecoder;
+use yaxpeax_x86::long_mode::fuzz_oracle::oracle;
+
+#[test]
+fn test_oracle_canonical() {
+    let decoder = InstDecoder::default();
+
+    // add dword [rax + 0x10], ecx
+    let decode = oracle(&decoder, &[0x01, 0x48, 0x10]);
+    assert!(decode.decoded.is_ok());
+    assert_eq!(
+        decode.canonical,
+        "mode=64 len=3 iclass=ADD encoding=Legacy map=0 prefixes=- prefix_bytes=- redundant=- op0=MEM:[rax + 0x10]:4:rw op1=REG:ecx:4:r"
+    );
+
+    // duplicated and overridden prefixes: `66 66 f2 f3 2e 64 nop`.
+    let decode = oracle(&decoder, &[0x66, 0x66, 0xf2, 0xf3, 0x2e, 0x64, 0x90]);
+    assert_eq!(decode.legacy_prefixes, vec![0x66, 0x66, 0xf2, 0xf3, 0x2e, 0x64]);
+    assert_eq!(decode.redundant_prefixes, vec![0x66, 0xf2, 0x2e]);
+    assert!(decode.canonical.contains(" prefixes=rep,fs,osz "));
+    assert!(decode.canonical.contains(" redundant=66,f2,2e"));
+
+    // redundant prefixes change nothing but the length and the prefixes listed.
+    let once = oracle(&decoder, &[0x66, 0x90]).canonical;
+    let twice = oracle(&decoder, &[0x66, 0x66, 0x90]).canonical;
+    assert_eq!(once.replace("len=2", "").replace(" prefix_bytes=66 redundant=-", ""),
+        twice.replace("len=3", "").replace(" prefix_bytes=66,66 redundant=66", ""));
+
+    // a `rex` prefix followed by another prefix is skipped over.
+    let decode = oracle(&decoder, &[0x48, 0x66, 0x90]);
+    assert_eq!(decode.legacy_prefixes, vec![0x66]);
+
+    let decode = oracle(&decoder, &[0xf0, 0x0f]);
+    assert!(decode.decoded.is_err());
+    assert_eq!(decode.canonical, "mode=64 error=ExhaustedInput prefix_bytes=f0");
+}
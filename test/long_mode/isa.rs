@@ -0,0 +1,49 @@
+use yaxpeax_x86::long_mode::{InstDecoder, IsaExtension};
+
+fn extension_of(data: &[u8]) -> (IsaExtension, bool) {
+    let inst = InstDecoder::default().decode_slice(data).unwrap();
+    (inst.isa_extension(), inst.requires_avx512vl())
+}
+
+#[test]
+fn test_isa_extensions() {
+    assert_eq!(extension_of(&[0x31, 0xc0]), (IsaExtension::Base, false));
+    assert_eq!(extension_of(&[0x9f]), (IsaExtension::LahfSahf, false));
+    assert_eq!(extension_of(&[0xd9, 0x00]), (IsaExtension::X87, false));
+    assert_eq!(extension_of(&[0x0f, 0x44, 0xc1]), (IsaExtension::Cmov, false));
+    assert_eq!(extension_of(&[0x48, 0x0f, 0xc7, 0x0f]), (IsaExtension::Cx16, false));
+    assert_eq!(extension_of(&[0x0f, 0xef, 0xc0]), (IsaExtension::Mmx, false));
+    assert_eq!(extension_of(&[0x66, 0x0f, 0xef, 0xc0]), (IsaExtension::Sse2, false));
+    assert_eq!(extension_of(&[0x0f, 0xe0, 0xc1]), (IsaExtension::Sse, false));
+    assert_eq!(extension_of(&[0x0f, 0x58, 0xc1]), (IsaExtension::Sse, false));
+    assert_eq!(extension_of(&[0x66, 0x0f, 0x38, 0x00, 0xc1]), (IsaExtension::Ssse3, false));
+    assert_eq!(extension_of(&[0xf2, 0x0f, 0x38, 0xf1, 0xc1]), (IsaExtension::Sse4_2, false));
+    assert_eq!(extension_of(&[0xf3, 0x0f, 0xb8, 0xc1]), (IsaExtension::Popcnt, false));
+    assert_eq!(extension_of(&[0x0f, 0x38, 0xc8, 0xc1]), (IsaExtension::Sha, false));
+    assert_eq!(extension_of(&[0xf3, 0x0f, 0x1e, 0xfa]), (IsaExtension::CetIbt, false));
+    assert_eq!(extension_of(&[0xc4, 0xe2, 0x78, 0xf2, 0xc1]), (IsaExtension::Bmi1, false));
+    assert_eq!(extension_of(&[0xc4, 0xe2, 0x73, 0xf6, 0xc2]), (IsaExtension::Bmi2, false));
+    // vex-encoded instructions: integer operations on `ymm` registers came with avx2.
+    assert_eq!(extension_of(&[0xc5, 0xf0, 0x58, 0xc2]), (IsaExtension::Avx, false));
+    assert_eq!(extension_of(&[0xc5, 0xf1, 0xfe, 0xc2]), (IsaExtension::Avx, false));
+    assert_eq!(extension_of(&[0xc5, 0xf5, 0xfe, 0xc2]), (IsaExtension::Avx2, false));
+    assert_eq!(extension_of(&[0xc4, 0xe2, 0x71, 0x98, 0xc2]), (IsaExtension::Fma3, false));
+    // evex-encoded instructions.
+    assert_eq!(extension_of(&[0x62, 0xf1, 0x74, 0x48, 0x58, 0xc2]), (IsaExtension::Avx512F, false));
+    assert_eq!(extension_of(&[0x62, 0xf1, 0x74, 0x28, 0x58, 0xc2]), (IsaExtension::Avx512F, true));
+    assert_eq!(extension_of(&[0x62, 0xf1, 0x76, 0x08, 0x58, 0xc2]), (IsaExtension::Avx512F, false));
+    assert_eq!(extension_of(&[0x62, 0xf1, 0x75, 0x48, 0xfc, 0xc2]), (IsaExtension::Avx512BW, false));
+    assert_eq!(extension_of(&[0x62, 0xf1, 0x75, 0x08, 0xfc, 0xc2]), (IsaExtension::Avx512BW, true));
+    // avx10.2 covers every vector length, including 256-bit vectors with embedded rounding.
+    assert_eq!(extension_of(&[0x62, 0xf2, 0x6f, 0x08, 0x74, 0xcb]), (IsaExtension::Avx10_2, false));
+    assert_eq!(extension_of(&[0x62, 0xf1, 0x70, 0x18, 0x58, 0xc2]), (IsaExtension::Avx10_2, false));
+    assert_eq!(extension_of(&[0x62, 0xf5, 0x6c, 0x48, 0x58, 0xcb]), (IsaExtension::Avx512Fp16, false));
+    assert_eq!(extension_of(&[0x62, 0xf5, 0x6c, 0x28, 0x58, 0xcb]), (IsaExtension::Avx512Fp16, true));
+    assert_eq!(extension_of(&[0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30]), (IsaExtension::Xop, false));
+    assert_eq!(extension_of(&[0x8f, 0xea, 0x78, 0x10, 0xc1, 0x04, 0x03, 0x02, 0x01]), (IsaExtension::Tbm, false));
+    assert_eq!(extension_of(&[0x8f, 0xe9, 0x78, 0x12, 0xc8]), (IsaExtension::Lwp, false));
+    // anything with a `rex2` prefix needs apx.
+    assert_eq!(extension_of(&[0xd5, 0x10, 0x01, 0xc0]), (IsaExtension::Apx, false));
+    assert_eq!(extension_of(&[0xc4, 0xe2, 0x78, 0x49, 0xc0]), (IsaExtension::AmxTile, false));
+    assert_eq!(extension_of(&[0xc4, 0xe2, 0x63, 0x5e, 0xca]), (IsaExtension::AmxInt8, false));
+}
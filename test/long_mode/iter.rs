@@ -0,0 +1,88 @@
+use yaxpeax_arch::LengthedInstruction;
+
+use yaxpeax_x86::long_mode::{DecodeError, DecodeIterator, Decoded, ErrorRecovery, InstDecoder, Opcode};
+
+// nop; (invalid in 64-bit mode); xor eax, eax; ret; and a truncated `mov`.
+const CODE: &[u8] = &[0x90, 0x06, 0x31, 0xc0, 0xc3, 0x48, 0x8b];
+
+fn listing(iter: DecodeIterator) -> Vec<(u64, String)> {
+    iter.map(|(address, item)| (address, item.to_string())).collect()
+}
+
+#[test]
+fn test_decode_iter_stop() {
+    let mut iter = DecodeIterator::new(InstDecoder::default(), CODE, 0x1000);
+    let (address, item) = iter.next().expect("first instruction decodes");
+    assert_eq!(address, 0x1000);
+    assert_eq!(item.instruction().map(|i| i.opcode()), Some(Opcode::NOP));
+    assert_eq!(item.length(), 1);
+    assert!(iter.next().is_none());
+    assert_eq!(iter.error(), Some(DecodeError::InvalidOpcode));
+    assert_eq!(iter.offset(), 1);
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_decode_iter_skip_byte() {
+    let iter = DecodeIterator::new(InstDecoder::default(), CODE, 0x1000)
+        .with_recovery(ErrorRecovery::SkipByte);
+    assert_eq!(listing(iter), vec![
+        (0x1000, "nop".to_string()),
+        (0x1002, "xor eax, eax".to_string()),
+        (0x1004, "ret".to_string()),
+    ]);
+}
+
+#[test]
+fn test_decode_iter_emit_byte() {
+    let iter = DecodeIterator::new(InstDecoder::default(), CODE, 0x1000)
+        .with_recovery(ErrorRecovery::EmitByte);
+    assert_eq!(listing(iter), vec![
+        (0x1000, "nop".to_string()),
+        (0x1001, ".byte 0x06".to_string()),
+        (0x1002, "xor eax, eax".to_string()),
+        (0x1004, "ret".to_string()),
+        (0x1005, ".byte 0x48".to_string()),
+        (0x1006, ".byte 0x8b".to_string()),
+    ]);
+
+    let bytes: Vec<Decoded> = DecodeIterator::new(InstDecoder::default(), &[0x06], 0)
+        .with_recovery(ErrorRecovery::EmitByte)
+        .map(|(_, item)| item)
+        .collect();
+    assert_eq!(bytes, vec![Decoded::Byte(0x06)]);
+}
+
+#[test]
+fn test_decode_all() {
+    let decoder = InstDecoder::default();
+
+    let mut out = Vec::new();
+    assert_eq!(decoder.decode_all(CODE, 0x1000, &mut out), Err(DecodeError::InvalidOpcode));
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].0, 0x1000);
+    assert_eq!(out[0].1.opcode(), Opcode::NOP);
+
+    // instructions are appended to whatever `out` already holds.
+    decoder.decode_all_with_recovery(CODE, 0x1000, ErrorRecovery::SkipByte, &mut out).unwrap();
+    let listing: Vec<(u64, String)> = out.iter().map(|(address, instr)| (*address, instr.to_string())).collect();
+    assert_eq!(listing, vec![
+        (0x1000, "nop".to_string()),
+        (0x1000, "nop".to_string()),
+        (0x1002, "xor eax, eax".to_string()),
+        (0x1004, "ret".to_string()),
+    ]);
+
+    out.clear();
+    decoder.decode_all_with_recovery(CODE, 0x1000, ErrorRecovery::EmitByte, &mut out).unwrap();
+    let opcodes: Vec<(u64, Opcode)> = out.iter().map(|(address, instr)| (*address, instr.opcode())).collect();
+    assert_eq!(opcodes, vec![
+        (0x1000, Opcode::NOP),
+        (0x1001, Opcode::Invalid),
+        (0x1002, Opcode::XOR),
+        (0x1004, Opcode::RETURN),
+        (0x1005, Opcode::Invalid),
+        (0x1006, Opcode::Invalid),
+    ]);
+    assert_eq!(out[1].1.len().to_const(), 1);
+}
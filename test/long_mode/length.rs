@@ -0,0 +1,146 @@
+use yaxpeax_arch::{AddressBase, Decoder, LengthedInstruction, Reader, U8Reader};
+use yaxpeax_x86::long_mode::{DecodeError, InstDecoder};
+
+fn length_of(data: &[u8]) -> Result<u8, DecodeError> {
+    InstDecoder::default().length_of(&mut U8Reader::new(data))
+}
+
+#[test]
+fn test_length_of() {
+    assert_eq!(length_of(&[0x90]), Ok(1));
+    assert_eq!(length_of(&[0x48, 0x8b, 0x44, 0x24, 0xf8]), Ok(5));
+    assert_eq!(length_of(&[0x48, 0x8b, 0x05, 0x78, 0x56, 0x34, 0x12]), Ok(7));
+    assert_eq!(length_of(&[0x66, 0x81, 0xc0, 0x34, 0x12]), Ok(5));
+    assert_eq!(length_of(&[0x48, 0xb8, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11]), Ok(10));
+    assert_eq!(length_of(&[0x66, 0xe8, 0x00, 0x00, 0x00, 0x00]), Ok(6));
+    assert_eq!(length_of(&[0xf6, 0x00, 0x12]), Ok(3));
+    assert_eq!(length_of(&[0xf6, 0x18]), Ok(2));
+    assert_eq!(length_of(&[0x0f, 0x20, 0x00]), Ok(3));
+    assert_eq!(length_of(&[0x66, 0x0f, 0x3a, 0x0f, 0xc1, 0x08]), Ok(6));
+    assert_eq!(length_of(&[0xc5, 0xf8, 0x77]), Ok(3));
+    assert_eq!(length_of(&[0x62, 0xf1, 0xfd, 0x49, 0x58, 0xc1]), Ok(6));
+
+    assert_eq!(length_of(&[0x06]), Err(DecodeError::InvalidOpcode));
+    assert_eq!(length_of(&[0x48, 0x8b]), Err(DecodeError::ExhaustedInput));
+    assert_eq!(length_of(&[0x66; 16]), Err(DecodeError::TooLong));
+    // bytes `decode` rejects have no length, even where their layout is regular.
+    assert_eq!(length_of(&[0x0f, 0x38, 0xff, 0xc0]), Err(DecodeError::InvalidOpcode));
+    assert_eq!(length_of(&[0x8d, 0xc0]), Err(DecodeError::InvalidOperand));
+    assert_eq!(length_of(&[0xf0, 0x01, 0xc8]), Err(DecodeError::InvalidPrefixes));
+
+    // the reader is left after the instruction.
+    let data = [0x48, 0x8b, 0x05, 0x78, 0x56, 0x34, 0x12, 0xc5, 0xf8, 0x77, 0xc3];
+    let mut reader = U8Reader::new(&data);
+    let decoder = InstDecoder::default();
+    assert_eq!(decoder.length_of(&mut reader), Ok(7));
+    assert_eq!(decoder.length_of(&mut reader), Ok(3));
+    assert_eq!(<U8Reader as Reader<u64, u8>>::total_offset(&mut reader), 10);
+}
+
+#[test]
+fn test_length_of_matches_decode() {
+    let decoder = InstDecoder::default();
+    // xorshift, for reproducible "random" instructions.
+    let mut state: u64 = 0x2545f4914f6cdd1d;
+    for _ in 0..200_000 {
+        let mut data = [0u8; 16];
+        for b in data.iter_mut() {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            *b = state as u8;
+        }
+        // random bytes rarely hit interesting prefixes, so add some.
+        match (state >> 40) % 8 {
+            0 => { data[0] = 0x66; }
+            1 => { data[0] = 0x48; }
+            2 => { data[0] = 0x0f; }
+            3 => { data[0] = 0x66; data[1] = 0x0f; }
+            4 => { data[0] = 0x67; }
+            5 => { data[0] = 0x41; data[1] = 0x66; }
+            _ => {}
+        }
+        // and rarely end early, so try a truncated copy as well.
+        let cut = (state >> 48) as usize % data.len();
+        for data in [&data[..], &data[..cut]].iter() {
+            let expected = decoder.decode(&mut U8Reader::new(data))
+                .map(|instr| 0u64.wrapping_offset(instr.len()).to_linear() as u8);
+            assert_eq!(decoder.length_of(&mut U8Reader::new(data)), expected, "length of {:02x?}", data);
+        }
+    }
+}
+
+#[test]
+fn test_summarize() {
+    use yaxpeax_x86::long_mode::{Encoding, FlowClass};
+
+    let decoder = InstDecoder::default();
+    let summarize = |data: &[u8]| decoder.summarize(data);
+
+    let add = summarize(&[0x48, 0x01, 0xc8]).unwrap();
+    assert_eq!(add.encoding(), Encoding::Legacy);
+    assert_eq!(add.map(), 0);
+    assert_eq!(add.opcode(), 0x01);
+    assert_eq!(add.modrm(), Some(0xc8));
+    assert_eq!(add.opcode_offset(), 1);
+    assert_eq!(add.class(), FlowClass::Sequential);
+
+    let call = summarize(&[0x2e, 0xff, 0x15, 0x00, 0x00, 0x00, 0x00]).unwrap();
+    assert_eq!(call.class(), FlowClass::Call);
+    assert_eq!(call.opcode_offset(), 1);
+    assert_eq!(summarize(&[0xff, 0xe0]).unwrap().class(), FlowClass::Jump);
+    assert_eq!(summarize(&[0xff, 0xc0]).unwrap().class(), FlowClass::Sequential);
+    // the summary only needs the opcode, not its immediate.
+    assert_eq!(summarize(&[0xe8]).unwrap().class(), FlowClass::Call);
+    assert_eq!(summarize(&[0xe8]).unwrap().modrm(), None);
+    assert_eq!(summarize(&[0x0f, 0x84]).unwrap().class(), FlowClass::ConditionalJump);
+    assert_eq!(summarize(&[0x0f, 0x84]).unwrap().map(), 1);
+    assert_eq!(summarize(&[0xc3]).unwrap().class(), FlowClass::Return);
+    assert_eq!(summarize(&[0x0f, 0x05]).unwrap().class(), FlowClass::Interrupt);
+
+    let pshufb = summarize(&[0x66, 0x0f, 0x38, 0x00, 0xc1]).unwrap();
+    assert_eq!((pshufb.map(), pshufb.opcode(), pshufb.modrm(), pshufb.opcode_offset()), (2, 0x00, Some(0xc1), 3));
+
+    let vzeroupper = summarize(&[0xc5, 0xf8, 0x77]).unwrap();
+    assert_eq!((vzeroupper.encoding(), vzeroupper.map(), vzeroupper.opcode(), vzeroupper.has_modrm()), (Encoding::Vex, 1, 0x77, false));
+    let vaddpd = summarize(&[0x62, 0xf1, 0xfd, 0x49, 0x58, 0xc1]).unwrap();
+    assert_eq!((vaddpd.encoding(), vaddpd.map(), vaddpd.opcode(), vaddpd.modrm()), (Encoding::Evex, 1, 0x58, Some(0xc1)));
+    let vpcmov = summarize(&[0x8f, 0xe8, 0x78, 0xa2, 0xc1, 0x20]).unwrap();
+    assert_eq!((vpcmov.encoding(), vpcmov.map(), vpcmov.opcode()), (Encoding::Xop, 8, 0xa2));
+    // `8f /0` is `pop`, not `xop`.
+    assert_eq!(summarize(&[0x8f, 0xc0]).unwrap().encoding(), Encoding::Legacy);
+
+    // equal encodings summarize to equal bits.
+    assert_eq!(summarize(&[0x01, 0xc8]).unwrap().bits() & 0xffff, 0xc801);
+
+    assert_eq!(summarize(&[0x48]), Err(DecodeError::ExhaustedInput));
+    assert_eq!(summarize(&[0x01]), Err(DecodeError::ExhaustedInput));
+    assert_eq!(summarize(&[0x66; 16]), Err(DecodeError::TooLong));
+
+    // `summarize` does not follow `with_emit_invalid_bytes`: too many prefixes are still an error,
+    // and an invalid opcode is summarized rather than reported as one byte.
+    let emitting = InstDecoder::default().with_emit_invalid_bytes();
+    assert_eq!(emitting.decode_slice(&[0x66; 16]).unwrap().invalid_byte(), Some(0x66));
+    assert_eq!(emitting.summarize(&[0x66; 16]), Err(DecodeError::TooLong));
+    assert_eq!(emitting.decode_slice(&[0x06]).unwrap().invalid_byte(), Some(0x06));
+    assert_eq!(emitting.summarize(&[0x06]).unwrap().opcode(), 0x06);
+
+    // a summary's class agrees with the category of the decoded instruction.
+    let branches: &[&[u8]] = &[
+        &[0xeb, 0x10], &[0xe9, 0, 0, 0, 0], &[0x75, 0x10], &[0xe3, 0x10], &[0xe2, 0x10],
+        &[0xe8, 0, 0, 0, 0], &[0xff, 0xd0], &[0xff, 0x20], &[0xc2, 0x08, 0x00], &[0xcb],
+        &[0x48, 0xcf], &[0xcc], &[0xcd, 0x80], &[0x0f, 0x8f, 0, 0, 0, 0],
+    ];
+    for bytes in branches {
+        let instr = decoder.decode_slice(bytes).unwrap();
+        let summary = summarize(bytes).unwrap();
+        let expected = match instr.opcode().to_string().as_str() {
+            "jmp" => FlowClass::Jump,
+            "call" => FlowClass::Call,
+            "ret" | "retf" | "iretq" => FlowClass::Return,
+            "int" => FlowClass::Interrupt,
+            _ => FlowClass::ConditionalJump,
+        };
+        assert_eq!(summary.class(), expected, "{:02x?} is {}", bytes, instr);
+    }
+}
@@ -0,0 +1,177 @@
+use yaxpeax_x86::long_mode::{InstDecoder, Instruction, MicroOp, Opcode, RegSpec, Temp};
+
+fn decode(data: &[u8]) -> Instruction {
+    InstDecoder::default().decode_slice(data).unwrap()
+}
+
+fn srcs_of(op: &MicroOp) -> Vec<u8> {
+    match op {
+        MicroOp::Address { srcs, .. } | MicroOp::Alu { srcs, .. } => srcs.iter().map(|t| t.0).collect(),
+        _ => panic!("{:?} has no sources", op),
+    }
+}
+
+#[test]
+fn arithmetic() {
+    // add rax, qword [rbx + rcx * 4]
+    let ops = decode(&[0x48, 0x03, 0x04, 0x8b]).micro_ops();
+    let ops = ops.as_slice();
+    assert_eq!(ops[0], MicroOp::ReadReg { dst: Temp(0), reg: RegSpec::rax() });
+    assert_eq!(ops[1], MicroOp::ReadReg { dst: Temp(1), reg: RegSpec::rbx() });
+    assert_eq!(ops[2], MicroOp::ReadReg { dst: Temp(2), reg: RegSpec::rcx() });
+    assert_eq!(srcs_of(&ops[3]), vec![1, 2]);
+    assert_eq!(ops[4], MicroOp::Load { dst: Temp(4), addr: Temp(3), size: Some(8) });
+    match ops[5] {
+        MicroOp::Alu { dst: Temp(5), opcode: Opcode::ADD, .. } => {}
+        other => panic!("expected add, got {:?}", other),
+    }
+    // the address feeds the load, not the sum.
+    assert_eq!(srcs_of(&ops[5]), vec![0, 4]);
+    assert_eq!(&ops[6..], &[
+        MicroOp::WriteReg { reg: RegSpec::rax(), src: Temp(5) },
+        MicroOp::WriteReg { reg: RegSpec::rflags(), src: Temp(5) },
+    ]);
+}
+
+#[test]
+fn moves() {
+    // mov dword [rax], ebx: no alu, and the stored value is only `ebx`.
+    let ops = decode(&[0x89, 0x18]).micro_ops();
+    let ops = ops.as_slice();
+    assert_eq!(ops[0], MicroOp::ReadReg { dst: Temp(0), reg: RegSpec::rax() });
+    assert_eq!(srcs_of(&ops[1]), vec![0]);
+    assert_eq!(ops[2], MicroOp::ReadReg { dst: Temp(2), reg: RegSpec::ebx() });
+    assert_eq!(&ops[3..], &[MicroOp::Store { addr: Temp(1), src: Temp(2), size: Some(4) }]);
+
+    // mov eax, 0x1234
+    assert_eq!(decode(&[0xb8, 0x34, 0x12, 0x00, 0x00]).micro_ops().as_slice(), &[
+        MicroOp::Const { dst: Temp(0), value: 0x1234 },
+        MicroOp::WriteReg { reg: RegSpec::eax(), src: Temp(0) },
+    ]);
+
+    // lea rax, [rbx + 8]: the address is the value.
+    let ops = decode(&[0x48, 0x8d, 0x43, 0x08]).micro_ops();
+    assert_eq!(ops.len(), 3);
+    assert_eq!(ops.as_slice()[2], MicroOp::WriteReg { reg: RegSpec::rax(), src: Temp(1) });
+
+    assert!(decode(&[0x90]).micro_ops().is_empty());
+}
+
+#[test]
+fn stack() {
+    // push rbx: the pushed value is `rbx`, and `rsp` moves on its own.
+    let ops = decode(&[0x53]).micro_ops();
+    let ops = ops.as_slice();
+    assert_eq!(ops[0], MicroOp::ReadReg { dst: Temp(0), reg: RegSpec::rbx() });
+    assert_eq!(ops[1], MicroOp::ReadReg { dst: Temp(1), reg: RegSpec::rsp() });
+    assert_eq!(srcs_of(&ops[2]), vec![1]);
+    assert_eq!(ops[3], MicroOp::Store { addr: Temp(2), src: Temp(0), size: Some(8) });
+    assert_eq!(srcs_of(&ops[4]), vec![1]);
+    assert_eq!(ops[5], MicroOp::WriteReg { reg: RegSpec::rsp(), src: Temp(3) });
+    assert_eq!(ops.len(), 6);
+
+    // call rax pushes the return address, not the target.
+    let ops = decode(&[0xff, 0xd0]).micro_ops();
+    let ops = ops.as_slice();
+    assert!(ops.contains(&MicroOp::ReturnAddress { dst: Temp(3) }));
+    assert!(ops.contains(&MicroOp::Store { addr: Temp(2), src: Temp(3), size: Some(8) }));
+    assert_eq!(ops.last(), Some(&MicroOp::Branch { target: Temp(0), conditional: false }));
+
+    // ret branches to what it pops.
+    let ops = decode(&[0xc3]).micro_ops();
+    let ops = ops.as_slice();
+    assert_eq!(ops[2], MicroOp::Load { dst: Temp(2), addr: Temp(1), size: Some(8) });
+    assert_eq!(ops.last(), Some(&MicroOp::Branch { target: Temp(2), conditional: false }));
+
+    // ret 0x8: the immediate only moves `rsp`, and the branch is still to what is popped.
+    let ops = decode(&[0xc2, 0x08, 0x00]).micro_ops();
+    let ops = ops.as_slice();
+    assert_eq!(ops[0], MicroOp::Const { dst: Temp(0), value: 8 });
+    assert_eq!(ops[3], MicroOp::Load { dst: Temp(3), addr: Temp(2), size: Some(8) });
+    assert_eq!(srcs_of(&ops[4]), vec![0, 1]);
+    assert_eq!(ops[5], MicroOp::WriteReg { reg: RegSpec::rsp(), src: Temp(4) });
+    assert_eq!(ops[6], MicroOp::Branch { target: Temp(3), conditional: false });
+    assert_eq!(ops.len(), 7);
+}
+
+#[test]
+fn strings() {
+    // movs dword es:[rdi], dword ds:[rsi]: the loaded value is stored as-is, and `rsi` and `rdi`
+    // each step from themselves and the direction flag.
+    let ops = decode(&[0xa5]).micro_ops();
+    let ops = ops.as_slice();
+    assert_eq!(ops[0], MicroOp::ReadReg { dst: Temp(0), reg: RegSpec::rdi() });
+    assert_eq!(ops[2], MicroOp::ReadReg { dst: Temp(2), reg: RegSpec::rsi() });
+    assert_eq!(ops[4], MicroOp::ReadReg { dst: Temp(4), reg: RegSpec::rflags() });
+    assert_eq!(ops[5], MicroOp::Load { dst: Temp(5), addr: Temp(3), size: Some(4) });
+    assert_eq!(ops[6], MicroOp::Store { addr: Temp(1), src: Temp(5), size: Some(4) });
+    assert_eq!(srcs_of(&ops[7]), vec![2, 4]);
+    assert_eq!(ops[8], MicroOp::WriteReg { reg: RegSpec::rsi(), src: Temp(6) });
+    assert_eq!(srcs_of(&ops[9]), vec![0, 4]);
+    assert_eq!(ops[10], MicroOp::WriteReg { reg: RegSpec::rdi(), src: Temp(7) });
+    assert_eq!(ops.len(), 11);
+
+    // rep stosb stores `al`, and `rcx` only counts down.
+    let ops = decode(&[0xf3, 0xaa]).micro_ops();
+    let ops = ops.as_slice();
+    assert!(ops.contains(&MicroOp::Store { addr: Temp(1), src: Temp(2), size: Some(1) }));
+    let rcx = ops.iter().find_map(|op| match op {
+        MicroOp::ReadReg { dst, reg } if *reg == RegSpec::rcx() => Some(dst.0),
+        _ => None,
+    }).unwrap();
+    let write = ops.iter().position(|op| matches!(op, MicroOp::WriteReg { reg, .. } if *reg == RegSpec::rcx())).unwrap();
+    assert_eq!(srcs_of(&ops[write - 1]), vec![rcx]);
+}
+
+#[test]
+fn branches() {
+    // jz $+0x10 depends on the flags.
+    let ops = decode(&[0x74, 0x10]).micro_ops();
+    let ops = ops.as_slice();
+    assert!(ops.contains(&MicroOp::ReadReg { dst: Temp(1), reg: RegSpec::rflags() }));
+    assert_eq!(srcs_of(&ops[2]), vec![0, 1]);
+    assert_eq!(ops[3], MicroOp::Branch { target: Temp(2), conditional: true });
+
+    // loop depends on `rcx`, which it also writes.
+    let ops = decode(&[0xe2, 0xfe]).micro_ops();
+    assert!(ops.as_slice().contains(&MicroOp::ReadReg { dst: Temp(1), reg: RegSpec::rcx() }));
+    assert!(matches!(ops.as_slice().last(), Some(MicroOp::Branch { conditional: true, .. })));
+}
+
+#[test]
+fn every_temp_is_defined_before_use() {
+    let mut state = 0x1234_5678u32;
+    let decoder = InstDecoder::default();
+    for _ in 0..200_000 {
+        let mut bytes = [0u8; 15];
+        for b in bytes.iter_mut() {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            *b = state as u8;
+        }
+        let inst = match decoder.decode_slice(&bytes) {
+            Ok(inst) => inst,
+            Err(_) => continue,
+        };
+        let mut defined = 0u8;
+        for op in inst.micro_ops().iter() {
+            let (dst, uses): (Option<Temp>, Vec<Temp>) = match *op {
+                MicroOp::ReadReg { dst, .. } | MicroOp::Const { dst, .. } |
+                MicroOp::ReturnAddress { dst } => (Some(dst), vec![]),
+                MicroOp::Address { dst, srcs } | MicroOp::Alu { dst, srcs, .. } => (Some(dst), srcs.iter().collect()),
+                MicroOp::Load { dst, addr, .. } => (Some(dst), vec![addr]),
+                MicroOp::Store { addr, src, .. } => (None, vec![addr, src]),
+                MicroOp::WriteReg { src, .. } => (None, vec![src]),
+                MicroOp::Branch { target, .. } => (None, vec![target]),
+            };
+            for temp in uses {
+                assert!(temp.0 < defined, "{} uses {:?} before it is defined: {:?}", inst, temp, inst.micro_ops());
+            }
+            if let Some(dst) = dst {
+                assert_eq!(dst.0, defined, "{} defines temporaries out of order", inst);
+                defined += 1;
+            }
+        }
+    }
+}
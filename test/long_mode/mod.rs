@@ -5,6 +5,34 @@ mod operand;
 mod display;
 mod evex_generated;
 mod reuse_test;
+mod encode;
+mod category;
+mod isa;
+mod iter;
+mod scan;
+mod length;
+#[cfg(feature = "std")]
+mod mode_switch;
+#[cfg(feature = "uarch-data")]
+mod perf;
+#[cfg(feature = "capstone-compat")]
+mod capstone_compat;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "fuzz-oracle")]
+mod fuzz_oracle;
+#[cfg(feature = "testgen")]
+mod testgen;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "micro-ops")]
+mod micro_ops;
+#[cfg(feature = "msr-names")]
+mod msr;
+#[cfg(feature = "conformance")]
+mod conformance;
+#[cfg(feature = "opcode-descriptions")]
+mod descriptions;
 
 use std::fmt::Write;
 
@@ -918,8 +946,8 @@ fn test_0f01() {
     test_display(&[0x0f, 0x01, 0xe5], "smsw bp");
     test_display(&[0x0f, 0x01, 0xe6], "smsw si");
     test_display(&[0x0f, 0x01, 0xe7], "smsw di");
-    test_invalid(&[0x0f, 0x01, 0xe8]);
-    test_invalid(&[0x0f, 0x01, 0xe8]);
+    test_display(&[0x0f, 0x01, 0xe8], "serialize");
+    test_invalid(&[0x66, 0x0f, 0x01, 0xe8]);
     test_invalid(&[0x0f, 0x01, 0xe9]);
     test_invalid(&[0x0f, 0x01, 0xea]);
     test_invalid(&[0x0f, 0x01, 0xeb]);
@@ -997,9 +1025,9 @@ fn test_0fae() {
 
 #[test]
 fn test_system() {
-    test_display(&[0x66, 0x4f, 0x0f, 0xb2, 0x00], "lss r8, mword [r8]");
-    test_display(&[0x67, 0x4f, 0x0f, 0xb2, 0x00], "lss r8, mword [r8d]");
-    test_display(&[0x4f, 0x0f, 0xb2, 0x00], "lss r8, mword [r8]");
+    test_display(&[0x66, 0x4f, 0x0f, 0xb2, 0x00], "lss r8, tword [r8]");
+    test_display(&[0x67, 0x4f, 0x0f, 0xb2, 0x00], "lss r8, tword [r8d]");
+    test_display(&[0x4f, 0x0f, 0xb2, 0x00], "lss r8, tword [r8]");
     test_display(&[0x0f, 0xb2, 0x00], "lss rax, dword [rax]");
     test_invalid(&[0x45, 0x0f, 0x22, 0xc8]);
     test_invalid(&[0x45, 0x0f, 0x20, 0xc8]);
@@ -1208,6 +1236,527 @@ fn test_prefixes() {
     test_display(&[0xf3, 0x0f, 0xc0, 0xcc], "xadd ah, cl");
 }
 
+#[test]
+fn test_retain_bytes() {
+    use yaxpeax_arch::U8Reader;
+    use yaxpeax_x86::long_mode::Instruction;
+
+    let data = [0x48, 0x8b, 0x05, 0x78, 0x56, 0x34, 0x12, 0x62, 0xf1, 0xfd, 0x49, 0x58, 0xc1, 0x90];
+
+    // the default decoder does not keep instruction bytes.
+    let instr = InstDecoder::default().decode_slice(&data).unwrap();
+    assert_eq!(instr.bytes(), None);
+
+    let decoder = InstDecoder::default().with_retain_bytes();
+    let mut reader = U8Reader::new(&data);
+    let mut instr = Instruction::default();
+    decoder.decode_into(&mut instr, &mut reader).unwrap();
+    assert_eq!(instr.bytes(), Some(&data[..7]));
+    decoder.decode_into(&mut instr, &mut reader).unwrap();
+    assert_eq!(instr.bytes(), Some(&data[7..13]));
+    assert_eq!(instr.to_string(), "vaddpd zmm0{k1}, zmm0, zmm1");
+    decoder.decode_into(&mut instr, &mut reader).unwrap();
+    assert_eq!(instr.bytes(), Some(&data[13..]));
+
+    // retaining bytes does not change how an instruction decodes or compares.
+    assert_eq!(decoder.decode_slice(&data), InstDecoder::default().decode_slice(&data));
+
+    // nor does it change which instructions a restricted decoder accepts.
+    let decoder = InstDecoder::minimal().with_retain_bytes();
+    assert!(decoder.decode_slice(&data[7..]).is_err());
+    assert_eq!(decoder.decode_slice(&[0x90]).unwrap().bytes(), Some(&[0x90][..]));
+}
+
+#[test]
+fn test_relocate() {
+    use yaxpeax_x86::long_mode::RelocateError;
+
+    let decoder = InstDecoder::default().with_retain_bytes();
+
+    // jz $+0x10, moved 0x10 bytes later, still reaches the same target.
+    let jz = decoder.decode_slice(&[0x74, 0x0e]).unwrap();
+    let moved = jz.relocate(0x1000, 0x1010).unwrap();
+    assert_eq!(moved.bytes(), &[0x74, 0xfe]);
+    assert!(!moved.widened());
+    // a target out of reach of 8 bits widens it, keeping prefixes.
+    let jz = decoder.decode_slice(&[0x3e, 0x74, 0x0e]).unwrap();
+    let moved = jz.relocate(0x1000, 0x2000).unwrap();
+    assert_eq!(moved.bytes(), &[0x3e, 0x0f, 0x84, 0x0a, 0xf0, 0xff, 0xff]);
+    assert!(moved.widened());
+    assert_eq!(decoder.decode_slice(moved.bytes()).unwrap().branch_target(0x2000), jz.branch_target(0x1000));
+    // jmp $+2
+    let jmp = decoder.decode_slice(&[0xeb, 0x00]).unwrap();
+    assert_eq!(jmp.retarget(0x1000, 0x1002 + 0x1000).unwrap().bytes(), &[0xe9, 0xfd, 0x0f, 0x00, 0x00]);
+    // `loop` has no wider form.
+    let lp = decoder.decode_slice(&[0xe2, 0xfe]).unwrap();
+    assert_eq!(lp.retarget(0x1000, 0x2000), Err(RelocateError::OutOfRange));
+
+    // call $+0x100
+    let call = decoder.decode_slice(&[0xe8, 0xfb, 0x00, 0x00, 0x00]).unwrap();
+    assert_eq!(call.relocate(0x1000, 0x800).unwrap().bytes(), &[0xe8, 0xfb, 0x08, 0x00, 0x00]);
+    assert_eq!(call.retarget(0x1000, 0x1_0000_1000), Err(RelocateError::OutOfRange));
+
+    // mov rax, qword [rip + 0x12345678]; the displacement is before the immediate, if any.
+    let mov = decoder.decode_slice(&[0x48, 0x8b, 0x05, 0x78, 0x56, 0x34, 0x12]).unwrap();
+    let moved = mov.relocate(0x1000, 0x2000).unwrap();
+    assert_eq!(moved.bytes(), &[0x48, 0x8b, 0x05, 0x78, 0x46, 0x34, 0x12]);
+    let cmp = decoder.decode_slice(&[0x81, 0x3d, 0x10, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00]).unwrap();
+    let target = cmp.rip_relative_target(0x1000);
+    let moved = cmp.relocate(0x1000, 0x1100).unwrap();
+    assert_eq!(moved.bytes(), &[0x81, 0x3d, 0x10, 0xff, 0xff, 0xff, 0x01, 0x00, 0x00, 0x00]);
+    assert_eq!(decoder.decode_slice(moved.bytes()).unwrap().rip_relative_target(0x1100), target);
+
+    assert_eq!(decoder.decode_slice(&[0x48, 0x8b, 0x00]).unwrap().relocate(0, 0x10), Err(RelocateError::NotRelative));
+    assert_eq!(InstDecoder::default().decode_slice(&[0x74, 0x0e]).unwrap().relocate(0, 0x10), Err(RelocateError::NoBytes));
+}
+
+#[test]
+fn test_decode_detailed() {
+    use yaxpeax_x86::long_mode::{DecodeError, DecodeErrorReason};
+
+    fn detail_of(decoder: &InstDecoder, data: &[u8]) -> (DecodeErrorReason, u8) {
+        let detail = decoder.decode_detailed(&mut yaxpeax_arch::U8Reader::new(data)).unwrap_err();
+        (detail.reason, detail.offset)
+    }
+
+    let decoder = InstDecoder::default();
+    assert_eq!(decoder.decode_detailed(&mut yaxpeax_arch::U8Reader::new(&[0x33, 0xc0])).unwrap().to_string(), "xor eax, eax");
+    assert_eq!(detail_of(&decoder, &[0x48, 0x8b]), (DecodeErrorReason::ExhaustedInput, 2));
+    assert_eq!(detail_of(&decoder, &[0x66; 16]), (DecodeErrorReason::TooLong, 15));
+    assert_eq!(detail_of(&decoder, &[0x06]), (DecodeErrorReason::InvalidOpcode, 1));
+    assert_eq!(detail_of(&decoder, &[0x66, 0xc5, 0xf8, 0x77]), (DecodeErrorReason::InvalidPrefixes, 2));
+    assert_eq!(detail_of(&decoder, &[0x8d, 0xc0]), (DecodeErrorReason::InvalidOperand, 2));
+
+    // `decode` reports an invalid opcode either way.
+    let minimal = InstDecoder::minimal();
+    let detail = minimal.decode_detailed(&mut yaxpeax_arch::U8Reader::new(&[0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30])).unwrap_err();
+    assert_eq!(detail.error, DecodeError::InvalidOpcode);
+    assert_eq!((detail.reason, detail.offset), (DecodeErrorReason::FeatureDisabled, 6));
+    assert_eq!(detail_of(&minimal, &[0x06]), (DecodeErrorReason::InvalidOpcode, 1));
+}
+
+#[test]
+fn test_decode_and_format_into() {
+    use yaxpeax_x86::long_mode::DecodeError;
+
+    let decoder = InstDecoder::default();
+    let bytes = [0x48, 0x8b, 0x05, 0x78, 0x56, 0x34, 0x12, 0xc3, 0x06];
+    let mut reader = yaxpeax_arch::U8Reader::new(&bytes[..]);
+    let mut text = String::new();
+
+    assert_eq!(decoder.decode_and_format_into(&mut reader, &mut text), Ok(Ok(7)));
+    assert_eq!(text, "mov rax, qword [rip + 0x12345678]");
+    text.push_str("; ");
+    assert_eq!(decoder.decode_and_format_into(&mut reader, &mut text), Ok(Ok(1)));
+    assert_eq!(text, "mov rax, qword [rip + 0x12345678]; ret");
+    assert_eq!(decoder.decode_and_format_into(&mut reader, &mut text), Ok(Err(DecodeError::InvalidOpcode)));
+    assert_eq!(text, "mov rax, qword [rip + 0x12345678]; ret");
+}
+
+#[test]
+fn test_decode_resumable() {
+    use yaxpeax_x86::long_mode::{DecodeError, DecodeState, ResumableDecode};
+
+    let decoder = InstDecoder::default();
+
+    // `mov rax, [rip + 0x12345678]`, split after its modrm byte.
+    let state = match decoder.decode_resumable(DecodeState::default(), &[0x48, 0x8b, 0x05]).unwrap() {
+        ResumableDecode::Incomplete(state) => state,
+        other => panic!("unexpected decode: {:?}", other),
+    };
+    assert_eq!(state.pending(), &[0x48, 0x8b, 0x05]);
+    let state = match decoder.decode_resumable(state, &[0x78, 0x56]).unwrap() {
+        ResumableDecode::Incomplete(state) => state,
+        other => panic!("unexpected decode: {:?}", other),
+    };
+    match decoder.decode_resumable(state, &[0x34, 0x12, 0x90, 0x90]).unwrap() {
+        ResumableDecode::Complete { instr, consumed } => {
+            assert_eq!(instr.to_string(), "mov rax, qword [rip + 0x12345678]");
+            assert_eq!(consumed, 2);
+        }
+        other => panic!("unexpected decode: {:?}", other),
+    }
+
+    match decoder.decode_resumable(DecodeState::default(), &[0x90, 0x90]).unwrap() {
+        ResumableDecode::Complete { instr, consumed } => {
+            assert_eq!(instr.to_string(), "nop");
+            assert_eq!(consumed, 1);
+        }
+        other => panic!("unexpected decode: {:?}", other),
+    }
+
+    assert_eq!(decoder.decode_resumable(DecodeState::default(), &[0x06]).unwrap_err(), DecodeError::InvalidOpcode);
+    let state = match decoder.decode_resumable(DecodeState::default(), &[0x66; 8]).unwrap() {
+        ResumableDecode::Incomplete(state) => state,
+        other => panic!("unexpected decode: {:?}", other),
+    };
+    assert_eq!(decoder.decode_resumable(state, &[0x66; 8]).unwrap_err(), DecodeError::TooLong);
+}
+
+#[test]
+fn test_decode_hooks() {
+    use yaxpeax_x86::long_mode::{DecodeError, DecodeHooks, Encoding};
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Prefix(u8, u8),
+        Map(Encoding, u8),
+        ModRM(u8, u8),
+    }
+
+    #[derive(Default)]
+    struct Events(Vec<Event>);
+
+    impl DecodeHooks for Events {
+        fn prefix(&mut self, offset: u8, byte: u8) {
+            self.0.push(Event::Prefix(offset, byte));
+        }
+        fn opcode_map(&mut self, encoding: Encoding, map: u8) {
+            self.0.push(Event::Map(encoding, map));
+        }
+        fn modrm(&mut self, offset: u8, byte: u8) {
+            self.0.push(Event::ModRM(offset, byte));
+        }
+    }
+
+    fn events(data: &[u8]) -> (Result<String, DecodeError>, Vec<Event>) {
+        let mut events = Events::default();
+        let res = InstDecoder::default()
+            .decode_with_hooks(&mut yaxpeax_arch::U8Reader::new(data), &mut events)
+            .map(|instr| instr.to_string());
+        (res, events.0)
+    }
+
+    assert_eq!(events(&[0x90]), (Ok("nop".to_string()), vec![Event::Map(Encoding::Legacy, 0)]));
+    assert_eq!(events(&[0xf0, 0x48, 0x01, 0x08]), (Ok("lock add qword [rax], rcx".to_string()), vec![
+        Event::Prefix(0, 0xf0),
+        Event::Prefix(1, 0x48),
+        Event::Map(Encoding::Legacy, 0),
+        Event::ModRM(3, 0x08),
+    ]));
+    assert_eq!(events(&[0x66, 0x0f, 0x38, 0x00, 0xc1]), (Ok("pshufb xmm0, xmm1".to_string()), vec![
+        Event::Prefix(0, 0x66),
+        Event::Map(Encoding::Legacy, 2),
+        Event::ModRM(4, 0xc1),
+    ]));
+    assert_eq!(events(&[0xc5, 0xf8, 0x28, 0xc1]), (Ok("vmovaps xmm0, xmm1".to_string()), vec![
+        Event::Map(Encoding::Vex, 1),
+        Event::ModRM(3, 0xc1),
+    ]));
+    assert_eq!(events(&[0x62, 0xf1, 0x7c, 0x48, 0x28, 0xc1]), (Ok("vmovaps zmm0, zmm1".to_string()), vec![
+        Event::Map(Encoding::Evex, 1),
+        Event::ModRM(5, 0xc1),
+    ]));
+    // `8f` is `pop` until its `modrm` turns out to be the second byte of an `xop` prefix.
+    assert_eq!(events(&[0x8f, 0xe9, 0x78, 0x81, 0xc1]), (Ok("vfrczpd xmm0, xmm1".to_string()), vec![
+        Event::Map(Encoding::Xop, 9),
+        Event::ModRM(4, 0xc1),
+    ]));
+
+    // a failed decode reports what it read before failing.
+    assert_eq!(events(&[0x66, 0x0f]), (Err(DecodeError::ExhaustedInput), vec![Event::Prefix(0, 0x66)]));
+    assert_eq!(events(&[0x48, 0x8b]), (Err(DecodeError::ExhaustedInput), vec![
+        Event::Prefix(0, 0x48),
+        Event::Map(Encoding::Legacy, 0),
+    ]));
+}
+
+#[test]
+fn test_decode_annotations() {
+    use yaxpeax_arch::annotation::{AnnotatingDecoder, DescriptionSink};
+    use yaxpeax_x86::long_mode::{FieldDescription, InnerDescription, Instruction, Opcode, RegSpec};
+
+    #[derive(Default)]
+    struct Fields(Vec<(u32, u32, InnerDescription)>);
+
+    impl DescriptionSink<FieldDescription> for Fields {
+        fn record(&mut self, start: u32, end: u32, description: FieldDescription) {
+            self.0.push((start, end, description.desc().clone()));
+        }
+    }
+
+    fn annotate(data: &[u8]) -> (String, Vec<(u32, u32, InnerDescription)>) {
+        let mut instr = Instruction::default();
+        let mut fields = Fields::default();
+        InstDecoder::default()
+            .decode_with_annotation(&mut instr, &mut yaxpeax_arch::U8Reader::new(data), &mut fields)
+            .unwrap();
+        (instr.to_string(), fields.0)
+    }
+
+    fn registers(fields: &[(u32, u32, InnerDescription)]) -> Vec<(u32, u32, &'static str, RegSpec)> {
+        fields.iter().filter_map(|(start, end, desc)| match desc {
+            InnerDescription::RegisterNumber(name, _, reg) => Some((*start, *end, *name, *reg)),
+            _ => None,
+        }).collect()
+    }
+
+    fn opcodes(fields: &[(u32, u32, InnerDescription)]) -> Vec<(u32, u32, Opcode)> {
+        fields.iter().filter_map(|(start, end, desc)| match desc {
+            InnerDescription::Opcode(opcode) => Some((*start, *end, *opcode)),
+            _ => None,
+        }).collect()
+    }
+
+    let (text, fields) = annotate(&[0x48, 0x89, 0xf8]);
+    assert_eq!(text, "mov rax, rdi");
+    assert_eq!(opcodes(&fields), [(8, 15, Opcode::MOV)]);
+    let mut regs = registers(&fields);
+    regs.sort_by_key(|r| r.0);
+    assert_eq!(regs, [(16, 18, "mmm", RegSpec::rax()), (19, 21, "rrr", RegSpec::rdi())]);
+
+    // the `sib` byte's base, and an `rrr` that selects an `xmm` register rather than a gpr.
+    let (text, fields) = annotate(&[0x0f, 0x28, 0x04, 0x0b]);
+    assert_eq!(text, "movaps xmm0, xmmword [rbx + rcx * 1]");
+    assert_eq!(opcodes(&fields), [(8, 15, Opcode::MOVAPS)]);
+    let regs = registers(&fields);
+    assert!(regs.contains(&(19, 21, "rrr", RegSpec::xmm0())));
+    assert!(regs.iter().any(|r| r.2 == "bbb" && r.3 == RegSpec::rbx()));
+    assert!(regs.iter().any(|r| r.2 == "iii" && r.3 == RegSpec::rcx()));
+
+    // `rrr` that extends the opcode is not a register.
+    let (text, fields) = annotate(&[0x83, 0xc0, 0x01]);
+    assert_eq!(text, "add eax, 0x1");
+    assert_eq!(registers(&fields), [(8, 10, "mmm", RegSpec::eax())]);
+
+    // `vvvv` is described with the bank the opcode gives it, and only when it names a register.
+    let (text, fields) = annotate(&[0xc5, 0xf4, 0x58, 0xc2]);
+    assert_eq!(text, "vaddps ymm0, ymm1, ymm2");
+    assert_eq!(opcodes(&fields), [(16, 23, Opcode::VADDPS)]);
+    assert!(registers(&fields).contains(&(11, 14, "vvvv", RegSpec::ymm(1))));
+    let (text, fields) = annotate(&[0xc5, 0xf8, 0x28, 0xc1]);
+    assert_eq!(text, "vmovaps xmm0, xmm1");
+    assert!(!registers(&fields).iter().any(|r| r.2 == "vvvv"));
+}
+
+#[test]
+fn test_prefix_inspection() {
+    use yaxpeax_x86::long_mode::Segment;
+
+    let decoder = InstDecoder::default();
+
+    let inst = decoder.decode_slice(&[0xf0, 0x66, 0x67, 0x01, 0x00]).unwrap();
+    let prefixes = inst.prefixes();
+    assert!(prefixes.lock);
+    assert!(prefixes.operand_size);
+    assert!(prefixes.address_size);
+    assert!(!prefixes.rep && !prefixes.repnz);
+    assert_eq!(prefixes.segment_override, None);
+    assert!(prefixes.rex.is_none() && prefixes.vex.is_none() && prefixes.evex.is_none());
+
+    let prefixes = decoder.decode_slice(&[0x64, 0x48, 0x8b, 0x00]).unwrap().prefixes();
+    assert_eq!(prefixes.segment_override, Some(Segment::FS));
+    assert!(prefixes.rex.unwrap().w());
+    // `cs`, `ds`, `es`, and `ss` overrides do nothing in 64-bit code.
+    assert_eq!(decoder.decode_slice(&[0x2e, 0x8b, 0x00]).unwrap().prefixes().segment_override, None);
+
+    assert!(decoder.decode_slice(&[0xf3, 0xa4]).unwrap().prefixes().rep);
+    assert!(decoder.decode_slice(&[0xf2, 0xae]).unwrap().prefixes().repnz);
+    // the `66` of `pshufb` is part of its opcode.
+    assert!(!decoder.decode_slice(&[0x66, 0x0f, 0x38, 0x00, 0xc1]).unwrap().prefixes().operand_size);
+
+    let prefixes = decoder.decode_slice(&[0xd5, 0x10, 0x01, 0xc0]).unwrap().prefixes();
+    assert!(prefixes.rex2.unwrap().b4());
+    // the low bits of a `rex2` payload are reported like a `rex` prefix.
+    assert!(!prefixes.rex.unwrap().b());
+
+    let prefixes = decoder.decode_slice(&[0xc4, 0xe2, 0x7d, 0x18, 0x00]).unwrap().prefixes();
+    assert!(prefixes.vex.unwrap().l());
+    assert!(prefixes.evex.is_none());
+
+    let prefixes = decoder.decode_slice(&[0x62, 0xf1, 0x74, 0xcd, 0x58, 0xc2]).unwrap().prefixes();
+    assert!(prefixes.vex.is_none());
+    let evex = prefixes.evex.unwrap();
+    assert_eq!(evex.mask_reg(), 5);
+    assert!(evex.merge());
+    assert!(evex.lp());
+    assert!(evex.u());
+}
+
+#[test]
+fn test_prefix_effects() {
+    use yaxpeax_x86::long_mode::PrefixEffect;
+
+    fn effects_of(data: &[u8]) -> yaxpeax_x86::long_mode::PrefixEffects {
+        InstDecoder::default().decode_slice(data).unwrap().prefix_effects()
+    }
+
+    let effects = effects_of(&[0x66, 0x01, 0xc0]);
+    assert_eq!(effects.operand_size, PrefixEffect::Effective);
+    assert_eq!(effects.rex_w, PrefixEffect::Absent);
+    assert!(!effects.any_ignored());
+    assert_eq!(effects_of(&[0x48, 0x01, 0xc0]).rex_w, PrefixEffect::Effective);
+    assert_eq!(effects_of(&[0xf0, 0x01, 0x00]).lock, PrefixEffect::Effective);
+    assert_eq!(effects_of(&[0x67, 0x01, 0x00]).address_size, PrefixEffect::Effective);
+
+    // `push` is 64 bits wide with or without `rex.w`.
+    assert_eq!(effects_of(&[0x48, 0x50]).rex_w, PrefixEffect::Ignored);
+    assert_eq!(effects_of(&[0x48, 0x90]).rex_w, PrefixEffect::Ignored);
+    let effects = effects_of(&[0xf3, 0xc3]);
+    assert_eq!(effects.rep, PrefixEffect::Ignored);
+    assert!(effects.any_ignored());
+    assert_eq!(effects_of(&[0xf3, 0xa4]).rep, PrefixEffect::Effective);
+    assert_eq!(effects_of(&[0xf2, 0xae]).repnz, PrefixEffect::Effective);
+    assert_eq!(effects_of(&[0xf3, 0x0f, 0xb8, 0xc1]).rep, PrefixEffect::Opcode);
+    assert_eq!(effects_of(&[0xf3, 0x90]).rep, PrefixEffect::Opcode);
+    assert_eq!(effects_of(&[0x66, 0x0f, 0x58, 0xc1]).operand_size, PrefixEffect::Opcode);
+
+    // `lea` computes an address without accessing it, so a segment does nothing.
+    assert_eq!(effects_of(&[0x64, 0x8d, 0x00]).segment, PrefixEffect::Ignored);
+    assert_eq!(effects_of(&[0x64, 0x8b, 0x00]).segment, PrefixEffect::Effective);
+    assert_eq!(effects_of(&[0x8b, 0x00]).segment, PrefixEffect::Absent);
+    assert_eq!(effects_of(&[0xf0, 0x87, 0x00]).lock, PrefixEffect::Ignored);
+    assert_eq!(effects_of(&[0x67, 0x01, 0xc0]).address_size, PrefixEffect::Ignored);
+    assert_eq!(effects_of(&[0x66, 0x0f, 0x00, 0xc1]).operand_size, PrefixEffect::Ignored);
+}
+
+#[test]
+fn test_operand_conversion() {
+    use std::convert::TryFrom;
+    use yaxpeax_x86::long_mode::{ConversionError, Operand, RegSpec};
+    use yaxpeax_x86::protected_mode;
+
+    let decoder = InstDecoder::default();
+
+    // `mov eax, dword [rbp + rcx * 4 + 0x10]`
+    let inst = decoder.decode_slice(&[0x8b, 0x44, 0x8d, 0x10]).unwrap();
+    assert_eq!(
+        protected_mode::Operand::try_from(inst.operand(0)),
+        Ok(protected_mode::Operand::Register(protected_mode::RegSpec::eax()))
+    );
+    assert_eq!(protected_mode::Operand::try_from(inst.operand(1)), Err(ConversionError::Register));
+
+    // `mov dword [ebp + 0x10], ebx` converts both ways.
+    let op = Operand::RegDisp(RegSpec::ebp(), 0x10);
+    let converted = protected_mode::Operand::try_from(op.clone()).unwrap();
+    assert_eq!(converted, protected_mode::Operand::RegDisp(protected_mode::RegSpec::ebp(), 0x10));
+    assert_eq!(Operand::try_from(converted), Ok(op));
+
+    assert_eq!(protected_mode::Operand::try_from(Operand::ImmediateI64(-1)), Err(ConversionError::Operand));
+    let far = protected_mode::Operand::AbsoluteFarAddress { segment: 0x33, address: 0x1000 };
+    assert_eq!(Operand::try_from(far), Err(ConversionError::Operand));
+
+    // `al` through `bl` are the same registers without `rex`, `spl` and `r8b` are not.
+    assert_eq!(protected_mode::RegSpec::try_from(RegSpec::rb(1)), Ok(protected_mode::RegSpec::cl()));
+    assert_eq!(protected_mode::RegSpec::try_from(RegSpec::rb(4)), Err(ConversionError::Register));
+    assert_eq!(protected_mode::RegSpec::try_from(RegSpec::xmm(8)), Err(ConversionError::Register));
+    assert_eq!(RegSpec::from(protected_mode::RegSpec::ah()), RegSpec::ah());
+}
+
+#[test]
+fn test_generic_operands() {
+    use yaxpeax_x86::{X86Instruction, X86Operand};
+    use yaxpeax_x86::long_mode::RegSpec;
+
+    fn memory_registers<I: X86Instruction>(inst: &I) -> Vec<<I::Operand as X86Operand>::Register> {
+        (0..inst.operand_count())
+            .map(|i| inst.operand(i))
+            .filter(|op| op.is_memory())
+            .flat_map(|op| op.registers())
+            .collect()
+    }
+
+    let decoder = InstDecoder::default();
+
+    // `mov eax, dword [rbp + rcx * 4 + 0x10]`
+    let inst = decoder.decode_slice(&[0x8b, 0x44, 0x8d, 0x10]).unwrap();
+    assert_eq!(memory_registers(&inst), [RegSpec::rbp(), RegSpec::rcx()]);
+    assert_eq!(X86Instruction::operand(&inst, 0).registers().collect::<Vec<_>>(), [RegSpec::eax()]);
+
+    // `mov rax, 0x1122334455667788`
+    let inst = decoder.decode_slice(&[0x48, 0xb8, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11]).unwrap();
+    assert_eq!(X86Instruction::operand(&inst, 1).immediate(), Some(0x1122334455667788));
+    assert_eq!(X86Instruction::operand(&inst, 0).immediate(), None);
+
+    // `vaddps zmm0{k5}, zmm1, zmm2`: the mask is named, but `k0` means no mask.
+    let inst = decoder.decode_slice(&[0x62, 0xf1, 0x74, 0x4d, 0x58, 0xc2]).unwrap();
+    assert_eq!(X86Instruction::operand(&inst, 0).registers().count(), 2);
+    let inst = decoder.decode_slice(&[0x62, 0xf1, 0x74, 0x48, 0x58, 0xc2]).unwrap();
+    assert_eq!(X86Instruction::operand(&inst, 0).registers().count(), 1);
+
+    let inst = yaxpeax_x86::protected_mode::InstDecoder::default().decode_slice(&[0x8b, 0x44, 0x8d, 0x10]).unwrap();
+    assert_eq!(
+        memory_registers(&inst),
+        [yaxpeax_x86::protected_mode::RegSpec::ebp(), yaxpeax_x86::protected_mode::RegSpec::ecx()]
+    );
+}
+
+#[test]
+fn test_sgx_leaves() {
+    use yaxpeax_x86::long_mode::{RegSpec, RegValueSource, SgxFunction};
+
+    struct Eax(u64);
+
+    impl RegValueSource for Eax {
+        fn reg_value(&self, reg: RegSpec) -> Option<u64> {
+            if reg == RegSpec::eax() { Some(self.0) } else { None }
+        }
+    }
+
+    let decoder = InstDecoder::default();
+    let encls = decoder.decode_slice(&[0x0f, 0x01, 0xcf]).unwrap();
+    let enclu = decoder.decode_slice(&[0x0f, 0x01, 0xd7]).unwrap();
+    let enclv = decoder.decode_slice(&[0x0f, 0x01, 0xc0]).unwrap();
+    assert_eq!(encls.sgx_function(), Some(SgxFunction::Encls));
+    assert_eq!(enclv.sgx_function(), Some(SgxFunction::Enclv));
+
+    assert_eq!(encls.sgx_leaf_name(&Eax(0)), Some("ECREATE"));
+    assert_eq!(encls.sgx_leaf_name(&Eax(0x18)), Some("EUPDATESVN"));
+    assert_eq!(enclu.sgx_leaf(&Eax(4)), Some((SgxFunction::Enclu, 4)));
+    assert_eq!(enclu.sgx_leaf_name(&Eax(4)), Some("EEXIT"));
+    assert_eq!(enclv.sgx_leaf_name(&Eax(2)), Some("ESETCONTEXT"));
+    // only `eax` selects the leaf; the upper half of `rax` is ignored.
+    assert_eq!(enclu.sgx_leaf_name(&Eax(0xffff_ffff_0000_0001)), Some("EGETKEY"));
+    assert_eq!(enclu.sgx_leaf_name(&Eax(0x20)), None);
+
+    let nop = decoder.decode_slice(&[0x90]).unwrap();
+    assert_eq!(nop.sgx_function(), None);
+    assert_eq!(nop.sgx_leaf(&Eax(0)), None);
+    // without sgx, the instructions do not decode at all.
+    assert!(InstDecoder::minimal().decode_slice(&[0x0f, 0x01, 0xcf]).is_err());
+}
+
+#[test]
+fn test_encoding_info() {
+    use yaxpeax_x86::long_mode::{Encoding, EncodingInfo};
+
+    fn encoding_of(data: &[u8]) -> EncodingInfo {
+        InstDecoder::default().decode_slice(data).unwrap().encoding_info()
+    }
+
+    assert_eq!(
+        encoding_of(&[0x48, 0x8b, 0x00]),
+        EncodingInfo { encoding: Encoding::Legacy, map: 0, pp: 0, vector_length: 0, w: true }
+    );
+    // `vmovaps xmm0, xmm1` is the same instruction in both its `vex` and `evex` forms.
+    let vex = InstDecoder::default().decode_slice(&[0xc5, 0xf8, 0x28, 0xc1]).unwrap();
+    let evex = InstDecoder::default().decode_slice(&[0x62, 0xf1, 0x7c, 0x08, 0x28, 0xc1]).unwrap();
+    assert_eq!(vex.to_string(), evex.to_string());
+    assert_eq!(
+        vex.encoding_info(),
+        EncodingInfo { encoding: Encoding::Vex, map: 1, pp: 0, vector_length: 0, w: false }
+    );
+    assert_eq!(
+        evex.encoding_info(),
+        EncodingInfo { encoding: Encoding::Evex, map: 1, pp: 0, vector_length: 0, w: false }
+    );
+    // vbroadcastss ymm0, dword [rax]
+    assert_eq!(
+        encoding_of(&[0xc4, 0xe2, 0x7d, 0x18, 0x00]),
+        EncodingInfo { encoding: Encoding::Vex, map: 2, pp: 1, vector_length: 1, w: false }
+    );
+    // vaddpd zmm0, zmm0, zmm1
+    assert_eq!(
+        encoding_of(&[0x62, 0xf1, 0xfd, 0x48, 0x58, 0xc1]),
+        EncodingInfo { encoding: Encoding::Evex, map: 1, pp: 1, vector_length: 2, w: true }
+    );
+    // vfrczps xmm0, xmm1
+    assert_eq!(
+        encoding_of(&[0x8f, 0xe9, 0x78, 0x80, 0xc1]),
+        EncodingInfo { encoding: Encoding::Xop, map: 9, pp: 0, vector_length: 0, w: false }
+    );
+}
+
 #[test]
 fn test_control_flow() {
     test_display(&[0x73, 0x31], "jnb $+0x31");
@@ -1221,7 +1770,7 @@ fn test_control_flow() {
     test_display(&[0x66, 0xff, 0xe0], "jmp rax");
     test_display(&[0x67, 0xff, 0xe0], "jmp rax");
     test_invalid(&[0xff, 0xd8]);
-    test_display(&[0xff, 0x18], "callf mword [rax]");
+    test_display(&[0xff, 0x18], "callf tword [rax]");
     test_display(&[0xe0, 0x12], "loopnz $+0x12");
     test_display(&[0xe1, 0x12], "loopz $+0x12");
     test_display(&[0xe2, 0x12], "loop $+0x12");
@@ -1261,6 +1810,165 @@ fn test_push_pop() {
     test_display(&[0x48, 0x8f, 0x00], "pop qword [rax]");
 }
 
+#[test]
+fn test_apx_rex2() {
+    test_display(&[0xd5, 0x10, 0x01, 0xc0], "add r16d, eax");
+    test_display(&[0xd5, 0x44, 0x01, 0xc0], "add eax, r24d");
+    test_display(&[0xd5, 0x7f, 0x01, 0xc0], "add r24, r24");
+    test_display(&[0x66, 0xd5, 0x10, 0x01, 0xc0], "add r16w, ax");
+    test_display(&[0xd5, 0x40, 0x88, 0xc4], "mov spl, r16b");
+    test_display(&[0xd5, 0x70, 0x8b, 0x04, 0xa0], "mov r16d, dword [r16 + r20 * 4]");
+    test_display(&[0xd5, 0x7f, 0x8b, 0x04, 0xe4], "mov r24, qword [r28 + r28 * 8]");
+    test_display(&[0xd5, 0x10, 0xb8, 0x01, 0x00, 0x00, 0x00], "mov r16d, 0x1");
+    test_display(&[0xd5, 0x11, 0x90], "xchg eax, r24d");
+    test_display(&[0xd5, 0x10, 0x50], "push r16");
+    // `m0` selects the `0f` map.
+    test_display(&[0xd5, 0xdc, 0xaf, 0xc0], "imul r24, r16");
+    // the fourth register bits are ignored for non-gpr operands.
+    test_display(&[0xd5, 0xc4, 0x10, 0xc1], "movups xmm8, xmm1");
+    test_display(&[0xd5, 0x08, 0x50], "pushp rax");
+    test_display(&[0xd5, 0x19, 0x58], "popp r24");
+    test_display(&[0xd5, 0x00, 0xa1, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11], "jmpabs 0x1122334455667788");
+
+    // `jmpabs` is only `rex2.w=0`, and other moffs forms, jccs, and relative calls and jumps are
+    // not available with `rex2`.
+    test_invalid(&[0xd5, 0x08, 0xa1, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11]);
+    test_invalid(&[0xd5, 0x00, 0xa0, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11]);
+    test_invalid(&[0xd5, 0x00, 0x74, 0x00]);
+    test_invalid(&[0xd5, 0x00, 0xe8, 0x00, 0x00, 0x00, 0x00]);
+    test_invalid(&[0xd5, 0x80, 0x84, 0x00, 0x00, 0x00, 0x00]);
+    test_invalid(&[0xd5, 0x80, 0x38, 0x00, 0xc0]);
+    test_invalid(&[0xd5, 0x00, 0x0f, 0xaf, 0xc0]);
+    test_invalid(&[0xd5, 0x00, 0x41, 0x01, 0xc0]);
+    test_invalid(&[0xd5, 0x00, 0x66, 0x01, 0xc0]);
+    test_invalid(&[0x41, 0xd5, 0x00, 0x01, 0xc0]);
+    test_invalid(&[0xd5, 0x00, 0xc5, 0xf0, 0x58, 0xc2]);
+    // all of map 0 rows 4, 7, a, and e are unavailable with rex2, except for `jmpabs`...
+    test_invalid(&[0xd5, 0x00, 0x40]);
+    test_invalid(&[0xd5, 0x00, 0x4f]);
+    test_invalid(&[0xd5, 0x00, 0x7f, 0x00]);
+    test_invalid(&[0xd5, 0x00, 0xa4]);
+    test_invalid(&[0xd5, 0x00, 0xa8, 0x01]);
+    test_invalid(&[0xd5, 0x00, 0xaf]);
+    test_invalid(&[0xd5, 0x00, 0xe4, 0x7e]);
+    test_invalid(&[0xd5, 0x00, 0xe5, 0x7e]);
+    test_invalid(&[0xd5, 0x00, 0xea]);
+    test_invalid(&[0xd5, 0x00, 0xec]);
+    test_invalid(&[0xd5, 0x00, 0xef]);
+    // ... and so are map 1 rows 3 and 8, 3dnow, and `femms`.
+    test_invalid(&[0xd5, 0x80, 0x31]);
+    test_invalid(&[0xd5, 0x80, 0x3a, 0x0f, 0xc0, 0x01]);
+    test_invalid(&[0xd5, 0x80, 0x80, 0x00, 0x00, 0x00, 0x00]);
+    test_invalid(&[0xd5, 0x80, 0x8f, 0x00, 0x00, 0x00, 0x00]);
+    test_invalid(&[0xd5, 0x80, 0x0e]);
+    test_invalid(&[0xd5, 0x80, 0x0f, 0xc1, 0x9e]);
+
+    // without apx, `d5` is still an invalid opcode in 64-bit mode.
+    test_invalid_under(&InstDecoder::minimal(), &[0xd5, 0x10, 0x01, 0xc0]);
+}
+
+#[test]
+fn test_amx() {
+    let amx = InstDecoder::minimal().with_amx();
+    test_display_under(&amx, &[0xc4, 0xe2, 0x78, 0x49, 0x00], "ldtilecfg ptr [rax]");
+    test_display_under(&amx, &[0xc4, 0xe2, 0x79, 0x49, 0x00], "sttilecfg ptr [rax]");
+    test_display_under(&amx, &[0xc4, 0xe2, 0x78, 0x49, 0xc0], "tilerelease");
+    test_display_under(&amx, &[0xc4, 0xe2, 0x7b, 0x49, 0xc8], "tilezero tmm1");
+    test_display_under(&amx, &[0xc4, 0xe2, 0x7b, 0x4b, 0x0c, 0x08], "tileloadd tmm1, ptr [rax + rcx * 1]");
+    test_display_under(&amx, &[0xc4, 0xe2, 0x79, 0x4b, 0x0c, 0x08], "tileloaddt1 tmm1, ptr [rax + rcx * 1]");
+    test_display_under(&amx, &[0xc4, 0xe2, 0x7a, 0x4b, 0x0c, 0x08], "tilestored ptr [rax + rcx * 1], tmm1");
+    test_display_under(&amx, &[0xc4, 0xe2, 0x63, 0x5e, 0xca], "tdpbssd tmm1, tmm2, tmm3");
+    test_display_under(&amx, &[0xc4, 0xe2, 0x62, 0x5e, 0xca], "tdpbsud tmm1, tmm2, tmm3");
+    test_display_under(&amx, &[0xc4, 0xe2, 0x61, 0x5e, 0xca], "tdpbusd tmm1, tmm2, tmm3");
+    test_display_under(&amx, &[0xc4, 0xe2, 0x60, 0x5e, 0xca], "tdpbuud tmm1, tmm2, tmm3");
+    test_display_under(&amx, &[0xc4, 0xe2, 0x62, 0x5c, 0xca], "tdpbf16ps tmm1, tmm2, tmm3");
+
+    // tile loads and stores require a sib byte, and dot products only take registers.
+    test_invalid_under(&amx, &[0xc4, 0xe2, 0x7b, 0x4b, 0x08]);
+    test_invalid_under(&amx, &[0xc4, 0xe2, 0x7b, 0x4b, 0xc8]);
+    test_invalid_under(&amx, &[0xc4, 0xe2, 0x63, 0x5e, 0x0a]);
+    // only `c0` is `tilerelease`, and `tilezero` has no r/m operand.
+    test_invalid_under(&amx, &[0xc4, 0xe2, 0x78, 0x49, 0xc1]);
+    test_invalid_under(&amx, &[0xc4, 0xe2, 0x7b, 0x49, 0xc9]);
+    // there are only eight tile registers.
+    test_invalid_under(&amx, &[0xc4, 0x62, 0x7b, 0x49, 0xc8]);
+    test_invalid_under(&amx, &[0xc4, 0xe2, 0x23, 0x5e, 0xca]);
+    // `vex.l` and `vex.w` must be clear.
+    test_invalid_under(&amx, &[0xc4, 0xe2, 0x7c, 0x49, 0x00]);
+    test_invalid_under(&amx, &[0xc4, 0xe2, 0xe3, 0x5e, 0xca]);
+
+    test_invalid_under(&InstDecoder::minimal(), &[0xc4, 0xe2, 0x63, 0x5e, 0xca]);
+}
+
+#[test]
+fn test_avx10_2() {
+    let avx10_2 = InstDecoder::minimal().with_avx512().with_avx10_2();
+    test_display_under(&avx10_2, &[0x62, 0xf2, 0x6f, 0x08, 0x74, 0xcb], "vcvtne2ph2bf8 xmm1, xmm2, xmm3");
+    test_display_under(&avx10_2, &[0x62, 0xf2, 0x6f, 0x58, 0x74, 0x0a], "vcvtne2ph2bf8 zmm1, zmm2, word [rdx]{1to32}");
+    test_display_under(&avx10_2, &[0x62, 0xf2, 0x7e, 0x48, 0x74, 0xca], "vcvtneph2bf8 ymm1, zmm2");
+    test_display_under(&avx10_2, &[0x62, 0xf2, 0x7e, 0x28, 0x74, 0x0a], "vcvtneph2bf8 xmm1, ymmword [rdx]");
+    test_display_under(&avx10_2, &[0x62, 0xf2, 0x7e, 0x38, 0x74, 0x0a], "vcvtneph2bf8 xmm1, word [rdx]{1to16}");
+    test_display_under(&avx10_2, &[0x62, 0xf2, 0x6c, 0x48, 0x74, 0xcb], "vcvtbiasph2bf8 ymm1, zmm2, zmm3");
+    test_display_under(&avx10_2, &[0x62, 0xf2, 0x6c, 0x3d, 0x74, 0x0a], "vcvtbiasph2bf8 xmm1{k5}, ymm2, word [rdx]{1to16}");
+    test_display_under(&avx10_2, &[0x62, 0xf2, 0x6d, 0x48, 0x67, 0xcb], "vcvt2ps2phx zmm1, zmm2, zmm3");
+    test_display_under(&avx10_2, &[0x62, 0xf2, 0x6d, 0x58, 0x67, 0x0a], "vcvt2ps2phx zmm1, zmm2, dword [rdx]{1to16}");
+    // `vcvtneph2bf8` has no second source.
+    test_invalid_under(&avx10_2, &[0x62, 0xf2, 0x76, 0x48, 0x74, 0xca]);
+
+    // a clear `evex.u` selects 256-bit vectors for register operations with embedded rounding.
+    test_display_under(&avx10_2, &[0x62, 0xf2, 0x69, 0x18, 0x67, 0xcb], "vcvt2ps2phx ymm1{rne-sae}, ymm2, ymm3");
+    test_display_under(&avx10_2, &[0x62, 0xf1, 0x70, 0x78, 0x58, 0xc2], "vaddps ymm0{rz-sae}, ymm1, ymm2");
+    test_display_under(&avx10_2, &[0x62, 0xf1, 0xf9, 0x18, 0x5a, 0xc1], "vcvtpd2ps xmm0{rne-sae}, ymm1");
+    // ... but not without rounding, with a memory operand, or for scalar operations.
+    test_invalid_under(&avx10_2, &[0x62, 0xf1, 0x70, 0x08, 0x58, 0xc2]);
+    test_invalid_under(&avx10_2, &[0x62, 0xf1, 0x70, 0x18, 0x58, 0x02]);
+    test_invalid_under(&avx10_2, &[0x62, 0xf1, 0x72, 0x18, 0x58, 0xc2]);
+
+    let avx512 = InstDecoder::minimal().with_avx512();
+    test_display_under(&avx512, &[0x62, 0xf1, 0x74, 0x18, 0x58, 0xc2], "vaddps zmm0{rne-sae}, zmm1, zmm2");
+    test_invalid_under(&avx512, &[0x62, 0xf1, 0x70, 0x18, 0x58, 0xc2]);
+    test_invalid_under(&avx512, &[0x62, 0xf2, 0x6f, 0x08, 0x74, 0xcb]);
+}
+#[test]
+fn test_avx512_fp16() {
+    let fp16 = InstDecoder::minimal().with_avx512().with_avx512_fp16();
+    // map 5
+    test_display_under(&fp16, &[0x62, 0xf5, 0x6c, 0xc9, 0x58, 0xcb], "vaddph zmm1{k1}{z}, zmm2, zmm3");
+    test_display_under(&fp16, &[0x62, 0xf5, 0x6c, 0x28, 0x58, 0x48, 0x01], "vaddph ymm1, ymm2, ymmword [rax + 0x20]");
+    test_display_under(&fp16, &[0x62, 0xf5, 0x6c, 0x1a, 0x58, 0x48, 0x01], "vaddph xmm1{k2}, xmm2, word [rax + 0x2]{1to8}");
+    test_display_under(&fp16, &[0x62, 0xf5, 0x6c, 0x18, 0x58, 0xcb], "vaddph zmm1{rne-sae}, zmm2, zmm3");
+    test_display_under(&fp16, &[0x62, 0xf5, 0xfd, 0x58, 0x5a, 0x08], "vcvtpd2ph xmm1, qword [rax]{1to8}");
+    test_display_under(&fp16, &[0x62, 0xf5, 0xfe, 0x18, 0x2d, 0xc1], "vcvtsh2si rax{rne-sae}, xmm1");
+    test_display_under(&fp16, &[0x62, 0xf5, 0x7e, 0x08, 0x2d, 0x00], "vcvtsh2si eax, word [rax]");
+    test_display_under(&fp16, &[0x62, 0xf5, 0x7d, 0x08, 0x6e, 0x48, 0x02], "vmovw xmm1, word [rax + 0x4]");
+    test_display_under(&fp16, &[0x62, 0xf5, 0x7d, 0x08, 0x7e, 0xc8], "vmovw eax, xmm1");
+    // map 6
+    test_display_under(&fp16, &[0x62, 0xf6, 0x6e, 0x58, 0x56, 0x08], "vfmaddcph zmm1, zmm2, dword [rax]{1to16}");
+    test_display_under(&fp16, &[0x62, 0xf6, 0x7d, 0x18, 0x13, 0xca], "vcvtph2psx zmm1{sae}, ymm2");
+    test_display_under(&fp16, &[0x62, 0xf6, 0x7d, 0x28, 0x13, 0x08], "vcvtph2psx ymm1, xmmword [rax]");
+    // 0f3a
+    test_display_under(&fp16, &[0x62, 0xf3, 0x7c, 0x58, 0x66, 0x08, 0x02], "vfpclassph k1, word [rax]{1to32}, 0x2");
+    // scalar imm8 ops take `{sae}` on register operands, but never broadcast from memory.
+    test_display_under(&fp16, &[0x62, 0x83, 0x04, 0x5e, 0x57, 0xc7, 0x01], "vreducesh xmm16{k6}{sae}, xmm15, xmm31, 0x1");
+    test_display_under(&fp16, &[0x62, 0x83, 0x04, 0x4e, 0x57, 0x25, 0x00, 0x00, 0x00, 0x00, 0x01], "vreducesh xmm20{k6}, xmm15, word [rip], 0x1");
+    test_invalid_under(&fp16, &[0x62, 0x83, 0x04, 0x5e, 0x57, 0x25, 0xb7, 0x2b, 0x7f, 0x64, 0x01]);
+    test_invalid_under(&fp16, &[0x62, 0x83, 0x04, 0x5e, 0x0a, 0x25, 0xb7, 0x2b, 0x7f, 0x64, 0x01]);
+    test_invalid_under(&fp16, &[0x62, 0x83, 0x04, 0x5e, 0x27, 0x25, 0xb7, 0x2b, 0x7f, 0x64, 0x01]);
+    test_display_under(&fp16, &[0x62, 0xf3, 0x45, 0x0e, 0x57, 0x08, 0x0a], "vreducess xmm1{k6}, xmm7, dword [rax], 0xa");
+    test_invalid_under(&fp16, &[0x62, 0xf3, 0x45, 0x1e, 0x57, 0x08, 0x0a]);
+    // maps 4 and 7 are not (yet) evex opcode maps.
+    test_invalid_under(&fp16, &[0x62, 0xf4, 0x6c, 0x48, 0x58, 0xcb]);
+    test_invalid_under(&fp16, &[0x62, 0xf7, 0x6c, 0x48, 0x58, 0xcb]);
+
+    let avx512 = InstDecoder::minimal().with_avx512();
+    test_invalid_under(&avx512, &[0x62, 0xf5, 0x6c, 0xc9, 0x58, 0xcb]);
+    test_invalid_under(&avx512, &[0x62, 0xf6, 0x6e, 0x58, 0x56, 0x08]);
+    test_invalid_under(&avx512, &[0x62, 0xf3, 0x7c, 0x58, 0x66, 0x08, 0x02]);
+    // ... but the non-fp16 form of the same opcode is still fine.
+    test_display_under(&avx512, &[0x62, 0xf3, 0x7d, 0x48, 0x66, 0xca, 0x01], "vfpclassps k1, zmm2, 0x1");
+}
+
+
 #[test]
 fn test_bmi1() {
     let bmi1 = InstDecoder::minimal().with_bmi1();
@@ -2722,6 +3430,8 @@ fn test_svm() {
     test_display(&[0x0f, 0x01, 0xdb], "vmsave rax");
     test_display(&[0x0f, 0x01, 0xda], "vmload rax");
     test_display(&[0x0f, 0x01, 0xd9], "vmmcall");
+    test_display(&[0xf3, 0x0f, 0x01, 0xd9], "vmgexit");
+    test_display(&[0xf2, 0x0f, 0x01, 0xd9], "vmgexit");
     test_display(&[0x0f, 0x01, 0xd8], "vmrun rax");
     test_display(&[0x0f, 0x78, 0xc4], "vmread rsp, rax");
     test_display(&[0x0f, 0x79, 0xc5], "vmwrite rax, rbp");
@@ -2789,6 +3499,44 @@ fn test_vmx() {
     test_display(&[0xf3, 0x0f, 0xc7, 0x33], "vmxon qword [rbx]");
 }
 
+#[test]
+fn test_system_features() {
+    let minimal = InstDecoder::minimal();
+    test_invalid_under(&minimal, &[0x0f, 0x78, 0xc4]);
+    test_invalid_under(&minimal, &[0x0f, 0x01, 0xc2]);
+    test_invalid_under(&minimal, &[0x66, 0x0f, 0x38, 0x80, 0x00]);
+    test_invalid_under(&minimal, &[0xf3, 0x0f, 0x01, 0xd9]);
+    test_invalid_under(&minimal, &[0x0f, 0x01, 0xfe]);
+    test_invalid_under(&minimal, &[0x0f, 0x01, 0xff]);
+    test_invalid_under(&minimal, &[0xf2, 0x0f, 0x01, 0xff]);
+    test_invalid_under(&minimal, &[0x66, 0x0f, 0x01, 0xcf]);
+    test_invalid_under(&minimal, &[0x66, 0x0f, 0x01, 0xcc]);
+    test_invalid_under(&minimal, &[0x0f, 0xaa]);
+    test_invalid_under(&minimal, &[0xf3, 0x0f, 0x01, 0xec]);
+    test_invalid_under(&minimal, &[0xf3, 0x0f, 0xc7, 0xf0]);
+    test_invalid_under(&minimal, &[0xf2, 0x0f, 0x38, 0xf8, 0x00]);
+    test_invalid_under(&minimal, &[0x0f, 0x01, 0xe8]);
+    test_invalid_under(&minimal, &[0xf3, 0x0f, 0x3a, 0xf0, 0xc0, 0x01]);
+
+    test_display_under(&minimal.with_vmx(), &[0x0f, 0x01, 0xc2], "vmlaunch");
+    test_display_under(&minimal.with_vmx(), &[0x66, 0x0f, 0x38, 0x80, 0x00], "invept rax, xmmword [rax]");
+    test_display_under(&minimal.with_svm(), &[0xf3, 0x0f, 0x01, 0xd9], "vmgexit");
+    test_display_under(&minimal.with_invlpgb(), &[0x0f, 0x01, 0xff], "tlbsync");
+    test_display_under(&minimal.with_snp(), &[0xf2, 0x0f, 0x01, 0xff], "pvalidate");
+    test_display_under(&minimal.with_tdx(), &[0x66, 0x0f, 0x01, 0xcc], "tdcall");
+    test_display_under(&minimal.with_smm(), &[0x0f, 0xaa], "rsm");
+    test_display_under(&minimal.with_uintr(), &[0xf3, 0x0f, 0xc7, 0xf0], "senduipi rax");
+    test_display_under(&minimal.with_enqcmd(), &[0xf2, 0x0f, 0x38, 0xf8, 0x00], "enqcmd rax, zmmword [rax]");
+    test_display_under(&minimal.with_serialize(), &[0x0f, 0x01, 0xe8], "serialize");
+    test_display_under(&minimal.with_hreset(), &[0xf3, 0x0f, 0x3a, 0xf0, 0xc0, 0x01], "hreset 0x1");
+    test_invalid_under(&minimal.with_svm(), &[0x0f, 0x01, 0xc2]);
+
+    test_display_under(&yaxpeax_x86::long_mode::uarch::intel::haswell(), &[0x0f, 0x01, 0xc2], "vmlaunch");
+    test_display_under(&yaxpeax_x86::long_mode::uarch::amd::zen3(), &[0x0f, 0x01, 0xfe], "invlpgb rax, edx, ecx");
+    test_invalid_under(&yaxpeax_x86::long_mode::uarch::amd::zen2(), &[0x0f, 0x01, 0xfe]);
+    test_display_under(&yaxpeax_x86::long_mode::uarch::intel::alderlake(), &[0x0f, 0x01, 0xe8], "serialize");
+}
+
 #[test]
 fn test_rdpid() {
     test_display(&[0xf3, 0x0f, 0xc7, 0xfd], "rdpid ebp");
@@ -2982,7 +3730,7 @@ fn test_x87() {
     test_invalid(&[0xdb, 0x26]);
     test_invalid(&[0xdb, 0x27]);
 //    test_display(&[0xdb, 0x2b], "fld st, ptr [rbx]");
-    test_display(&[0xdb, 0x2b], "fld st(0), mword [rbx]");
+    test_display(&[0xdb, 0x2b], "fld st(0), tword [rbx]");
     test_invalid(&[0xdb, 0x30]);
     test_invalid(&[0xdb, 0x31]);
     test_invalid(&[0xdb, 0x32]);
@@ -2992,7 +3740,7 @@ fn test_x87() {
     test_invalid(&[0xdb, 0x36]);
     test_invalid(&[0xdb, 0x37]);
 //    test_display(&[0xdb, 0x3b], "fstp ptr [rbx], st");
-    test_display(&[0xdb, 0x3b], "fstp mword [rbx], st(0)");
+    test_display(&[0xdb, 0x3b], "fstp tword [rbx], st(0)");
 //    test_display(&[0xdb, 0xc3], "fcmovnb st, st(3)");
     test_display(&[0xdb, 0xc3], "fcmovnb st(0), st(3)");
 //    test_display(&[0xdb, 0xcb], "fcmovne st, st(3)");
@@ -3149,11 +3897,11 @@ fn test_x87() {
 //    test_display(&[0xdf, 0x1b], "fistp word ptr [rbx], st");
     test_display(&[0xdf, 0x1b], "fistp word [rbx], st(0)");
 //    test_display(&[0xdf, 0x23], "fbld st, ptr [rbx]");
-    test_display(&[0xdf, 0x23], "fbld st(0), mword [rbx]");
+    test_display(&[0xdf, 0x23], "fbld st(0), tword [rbx]");
 //    test_display(&[0xdf, 0x2b], "fild st, qword ptr [rbx]");
     test_display(&[0xdf, 0x2b], "fild st(0), qword [rbx]");
 //    test_display(&[0xdf, 0x33], "fbstp ptr [rbx], st");
-    test_display(&[0xdf, 0x33], "fbstp mword [rbx], st(0)");
+    test_display(&[0xdf, 0x33], "fbstp tword [rbx], st(0)");
 //    test_display(&[0xdf, 0x3b], "fistp qword ptr [rbx], st");
     test_display(&[0xdf, 0x3b], "fistp qword [rbx], st(0)");
 //    test_display(&[0xdf, 0xc3], "ffreep st(3)");
@@ -3271,6 +4019,9 @@ fn test_cet() {
     // endbr32 f3 0f ae fb
     test_display(&[0xf3, 0x4f, 0x0f, 0xae, 0xe9], "incssp r9");
     test_display(&[0xf3, 0x0f, 0xae, 0xe9], "incssp ecx");
+    test_display(&[0xf3, 0x0f, 0x1e, 0xc8], "rdssp eax");
+    test_display(&[0xf3, 0x49, 0x0f, 0x1e, 0xcb], "rdssp r11");
+    test_display(&[0xf3, 0x0f, 0x1e, 0x08], "nop dword [rax], ecx");
     test_display(&[0x3e, 0x4f, 0x0f, 0x38, 0xf6, 0x23], "wrss qword [r11], r12");
     test_display(&[0x66, 0x0f, 0x38, 0xf5, 0x47, 0xe9], "wruss dword [rdi - 0x17], eax");
     test_invalid(&[0x0f, 0x38, 0xf5, 0x47, 0xe9]);
@@ -3286,6 +4037,30 @@ fn test_cet() {
     test_display(&[0xf3, 0x0f, 0xae, 0x30], "clrssbsy qword [rax]");
 }
 
+#[test]
+fn test_mpx() {
+    // without `mpx`, as by default, these are the reserved `nop`s current processors execute.
+    test_display(&[0xf3, 0x0f, 0x1b, 0x01], "nop dword [rcx]");
+    test_display(&[0x66, 0x0f, 0x1a, 0xc1], "nop cx");
+
+    let mpx = InstDecoder::default().with_mpx();
+    test_display_under(&mpx, &[0xf3, 0x0f, 0x1b, 0x01], "bndmk bnd0, ptr [rcx]");
+    test_invalid_under(&mpx, &[0xf3, 0x0f, 0x1b, 0xc1]);
+    test_display_under(&mpx, &[0xf3, 0x0f, 0x1a, 0xc9], "bndcl bnd1, rcx");
+    test_display_under(&mpx, &[0xf2, 0x0f, 0x1a, 0x10], "bndcu bnd2, ptr [rax]");
+    test_display_under(&mpx, &[0xf2, 0x0f, 0x1b, 0x18], "bndcn bnd3, ptr [rax]");
+    test_display_under(&mpx, &[0x66, 0x0f, 0x1a, 0xc1], "bndmov bnd0, bnd1");
+    test_display_under(&mpx, &[0x66, 0x0f, 0x1a, 0x01], "bndmov bnd0, xmmword [rcx]");
+    test_display_under(&mpx, &[0x66, 0x0f, 0x1b, 0x01], "bndmov xmmword [rcx], bnd0");
+    test_display_under(&mpx, &[0x0f, 0x1a, 0x04, 0x08], "bndldx bnd0, ptr [rax + rcx * 1]");
+    test_display_under(&mpx, &[0x0f, 0x1b, 0x04, 0x08], "bndstx ptr [rax + rcx * 1], bnd0");
+    test_invalid_under(&mpx, &[0x0f, 0x1a, 0xc0]);
+    // `bnd4` through `bnd15` do not exist.
+    test_invalid_under(&mpx, &[0xf3, 0x0f, 0x1a, 0x20]);
+    test_invalid_under(&mpx, &[0xf3, 0x44, 0x0f, 0x1a, 0x00]);
+    test_invalid_under(&mpx, &[0x66, 0x41, 0x0f, 0x1a, 0xc0]);
+}
+
 #[test]
 fn test_sse4a() {
     fn test_instr(bytes: &[u8], text: &'static str) {
@@ -3318,6 +4093,126 @@ fn test_3dnow() {
     test_display(&[0x26, 0x36, 0x0f, 0x0f, 0x70, 0xfb, 0x0c], "pi2fw mm6, qword [rax - 0x5]");
     test_display(&[0x66, 0x0f, 0x0f, 0xc6, 0xb7], "pmulhrw mm0, mm6");
     test_display(&[0x0f, 0x0f, 0xc6, 0xb7], "pmulhrw mm0, mm6");
+
+    test_invalid_under(&InstDecoder::minimal(), &[0x0f, 0x0f, 0xe0, 0x8a]);
+    test_invalid_under(&InstDecoder::minimal(), &[0x0f, 0x0e]);
+    test_display_under(&InstDecoder::minimal().with_3dnow(), &[0x0f, 0x0f, 0xe0, 0x8a], "pfnacc mm4, mm0");
+}
+
+#[test]
+fn test_xop() {
+    test_display(&[0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30], "vpcmov xmm0, xmm1, xmm2, xmm3");
+    test_display(&[0x8f, 0xe8, 0xf0, 0xa2, 0xc2, 0x30], "vpcmov xmm0, xmm1, xmm3, xmm2");
+    test_display(&[0x8f, 0xe8, 0x74, 0xa2, 0xc2, 0x30], "vpcmov ymm0, ymm1, ymm2, ymm3");
+    test_display(&[0x8f, 0xe8, 0x74, 0xa2, 0x00, 0x30], "vpcmov ymm0, ymm1, ymmword [rax], ymm3");
+    test_display(&[0x8f, 0x48, 0x30, 0xa3, 0xc2, 0xf0], "vpperm xmm8, xmm9, xmm10, xmm15");
+    test_display(&[0x8f, 0xe8, 0x70, 0x95, 0x00, 0x30], "vpmacsww xmm0, xmm1, xmmword [rax], xmm3");
+    test_display(&[0x8f, 0xe8, 0x78, 0xc0, 0xc1, 0x05], "vprotb xmm0, xmm1, 0x5");
+    test_display(&[0x8f, 0xe8, 0x70, 0xcc, 0xc2, 0x03], "vpcomb xmm0, xmm1, xmm2, 0x3");
+    test_display(&[0x8f, 0xe9, 0x78, 0x80, 0xc1], "vfrczps xmm0, xmm1");
+    test_display(&[0x8f, 0xe9, 0x7c, 0x80, 0xc1], "vfrczps ymm0, ymm1");
+    test_display(&[0x8f, 0xe9, 0x78, 0x82, 0x00], "vfrczss xmm0, dword [rax]");
+    test_display(&[0x8f, 0xe9, 0x70, 0x90, 0xc2], "vprotb xmm0, xmm2, xmm1");
+    test_display(&[0x8f, 0xe9, 0xf0, 0x90, 0xc2], "vprotb xmm0, xmm1, xmm2");
+    test_display(&[0x8f, 0xe9, 0x70, 0x9b, 0x04, 0x24], "vpshaq xmm0, xmmword [rsp], xmm1");
+    test_display(&[0x8f, 0xe9, 0x78, 0xc1, 0xc1], "vphaddbw xmm0, xmm1");
+    test_display(&[0x8f, 0xe9, 0x78, 0x01, 0xc9], "blcfill eax, ecx");
+    test_display(&[0x8f, 0xc9, 0xf8, 0x01, 0xc9], "blcfill rax, r9");
+    test_display(&[0x8f, 0xe9, 0x60, 0x02, 0x31], "blci ebx, dword [rcx]");
+    test_display(&[0x8f, 0xea, 0x78, 0x10, 0xc1, 0x04, 0x03, 0x02, 0x01], "bextr eax, ecx, 0x1020304");
+    test_display(&[0x8f, 0xe9, 0xf8, 0x12, 0xc0], "llwpcb rax");
+    test_display(&[0x8f, 0xe9, 0x78, 0x12, 0xc8], "slwpcb eax");
+    test_display(&[0x8f, 0xea, 0x70, 0x12, 0xc1, 0x04, 0x03, 0x02, 0x01], "lwpins ecx, ecx, 0x1020304");
+
+    // `8f` is still `pop` when `rrr` is 0b000.
+    test_display(&[0x8f, 0x00], "pop qword [rax]");
+    test_display(&[0x41, 0x8f, 0x04, 0x24], "pop qword [r12]");
+
+    // undefined maps, opcodes, and implied prefixes.
+    test_invalid(&[0x8f, 0xe4, 0x78, 0xa2, 0xc2, 0x30]);
+    test_invalid(&[0x8f, 0xeb, 0x78, 0x10, 0xc1, 0x04, 0x03, 0x02, 0x01]);
+    test_invalid(&[0x8f, 0xe8, 0x78, 0x00, 0xc2, 0x30]);
+    test_invalid(&[0x8f, 0xe8, 0x71, 0xa2, 0xc2, 0x30]);
+    // like `vex`, `xop` can't follow `rex`, `66`, `f2`, `f3`, or `lock`.
+    test_invalid(&[0x66, 0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30]);
+    test_invalid(&[0x48, 0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30]);
+    // `vvvv` is reserved where it does not name an operand.
+    test_invalid(&[0x8f, 0xe9, 0x70, 0x80, 0xc1]);
+    // `llwpcb` only takes a register.
+    test_invalid(&[0x8f, 0xe9, 0x78, 0x12, 0x00]);
+
+    // `tbm` instructions only need `tbm`, and everything else needs `xop`.
+    test_invalid_under(&InstDecoder::minimal(), &[0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30]);
+    test_display_under(&InstDecoder::minimal().with_xop(), &[0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30], "vpcmov xmm0, xmm1, xmm2, xmm3");
+    test_invalid_under(&InstDecoder::minimal().with_xop(), &[0x8f, 0xe9, 0x78, 0x01, 0xc9]);
+    test_display_under(&InstDecoder::minimal().with_tbm(), &[0x8f, 0xe9, 0x78, 0x01, 0xc9], "blcfill eax, ecx");
+    test_display_under(&InstDecoder::minimal().with_tbm(), &[0x8f, 0xea, 0x78, 0x10, 0xc1, 0x04, 0x03, 0x02, 0x01], "bextr eax, ecx, 0x1020304");
+}
+
+#[test]
+fn test_cpu_presets() {
+    use yaxpeax_x86::long_mode::Cpu;
+
+    // 3dnow! went away after `k10`.
+    test_display_under(&InstDecoder::for_cpu(Cpu::K8), &[0x0f, 0x0f, 0xe0, 0x8a], "pfnacc mm4, mm0");
+    test_display_under(&InstDecoder::for_cpu(Cpu::K10), &[0x0f, 0x0f, 0xe0, 0x8a], "pfnacc mm4, mm0");
+    test_invalid_under(&InstDecoder::for_cpu(Cpu::Bulldozer), &[0x0f, 0x0f, 0xe0, 0x8a]);
+    test_invalid_under(&InstDecoder::for_cpu(Cpu::Zen), &[0x0f, 0x0f, 0xe0, 0x8a]);
+
+    // as did `xop`, after `excavator`.
+    test_display_under(&InstDecoder::for_cpu(Cpu::Piledriver), &[0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30], "vpcmov xmm0, xmm1, xmm2, xmm3");
+    test_invalid_under(&InstDecoder::for_cpu(Cpu::Zen4), &[0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30]);
+
+    // vaddpd zmm0, zmm0, zmm1
+    let avx512 = &[0x62, 0xf1, 0xfd, 0x48, 0x58, 0xc1];
+    test_display_under(&InstDecoder::for_cpu(Cpu::Zen4), avx512, "vaddpd zmm0, zmm0, zmm1");
+    test_display_under(&InstDecoder::for_cpu(Cpu::IceLake), avx512, "vaddpd zmm0, zmm0, zmm1");
+    test_invalid_under(&InstDecoder::for_cpu(Cpu::Zen3), avx512);
+    test_invalid_under(&InstDecoder::for_cpu(Cpu::Skylake), avx512);
+    test_invalid_under(&InstDecoder::for_cpu(Cpu::AlderLake), avx512);
+    test_display_under(&InstDecoder::for_cpu(Cpu::AlderLake), &[0xc4, 0xe2, 0x70, 0xf2, 0xc2], "andn eax, ecx, edx");
+    test_invalid_under(&InstDecoder::for_cpu(Cpu::SandyBridge), &[0xc4, 0xe2, 0x70, 0xf2, 0xc2]);
+}
+
+#[test]
+fn test_cpuid_leaves() {
+    use yaxpeax_x86::long_mode::CpuidLeaf;
+
+    let leaves = [
+        // "AuthenticAMD"
+        CpuidLeaf { leaf: 0, subleaf: 0, eax: 0xd, ebx: 0x68747541, ecx: 0x444d4163, edx: 0x69746e65 },
+        // sse3, ssse3, sse4.1, sse4.2, popcnt, avx
+        CpuidLeaf { leaf: 1, subleaf: 0, eax: 0, ebx: 0, ecx: 0x1098_0201, edx: 0 },
+        // bmi1, serialize
+        CpuidLeaf { leaf: 7, subleaf: 0, eax: 0, ebx: 0x0000_0008, ecx: 0, edx: 0x0000_4000 },
+        // xop
+        CpuidLeaf { leaf: 0x8000_0001, subleaf: 0, eax: 0, ebx: 0, ecx: 0x0000_0800, edx: 0 },
+        // invlpgb
+        CpuidLeaf { leaf: 0x8000_0008, subleaf: 0, eax: 0, ebx: 0x0000_0008, ecx: 0, edx: 0 },
+    ];
+    let decoder = InstDecoder::from_cpuid_leaves(&leaves);
+    assert!(decoder.amd_quirks() && !decoder.intel_quirks());
+    assert!(decoder.sse4_2() && decoder.popcnt() && decoder.avx());
+    assert!(!decoder.avx2() && !decoder.bmi2() && !decoder.avx512_f());
+    assert!(decoder.invlpgb() && !decoder.snp() && !decoder.smm());
+    assert!(decoder.serialize() && !decoder.uintr() && !decoder.hreset());
+
+    test_display_under(&decoder, &[0xc4, 0xe2, 0x70, 0xf2, 0xc2], "andn eax, ecx, edx");
+    test_display_under(&decoder, &[0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30], "vpcmov xmm0, xmm1, xmm2, xmm3");
+    test_invalid_under(&decoder, &[0x62, 0xf1, 0xfd, 0x48, 0x58, 0xc1]);
+    test_invalid_under(&decoder, &[0x0f, 0x0f, 0xe0, 0x8a]);
+
+    // no leaves, no extensions.
+    assert!(InstDecoder::from_cpuid_leaves(&[]) == InstDecoder::minimal());
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+fn test_cpuid_host() {
+    // whatever the host supports, it can decode the basics.
+    let host = InstDecoder::for_host();
+    test_display_under(&host, &[0x33, 0xc0], "xor eax, eax");
+    assert!(host.cmpxchg16b() || host.sse3());
 }
 
 // first appeared in tremont
@@ -3411,19 +4306,19 @@ mod reg_masks {
     #[test]
     #[should_panic]
     fn invalid_qword_reg_panics() {
-        RegSpec::q(16);
+        RegSpec::q(32);
     }
 
     #[test]
     #[should_panic]
     fn invalid_dword_reg_panics() {
-        RegSpec::d(16);
+        RegSpec::d(32);
     }
 
     #[test]
     #[should_panic]
     fn invalid_word_reg_panics() {
-        RegSpec::w(16);
+        RegSpec::w(32);
     }
 
     #[test]
@@ -3435,7 +4330,7 @@ mod reg_masks {
     #[test]
     #[should_panic]
     fn invalid_rex_byte_reg_panics() {
-        RegSpec::rb(16);
+        RegSpec::rb(32);
     }
 
     #[test]
@@ -0,0 +1,32 @@
+use yaxpeax_x86::long_mode;
+use yaxpeax_x86::mode_switch::{CodeSegmentMode, ModeSwitchingDecoder, SegmentDecodeError, SegmentInstruction};
+
+#[test]
+fn test_mode_switching() {
+    let mut decoder = ModeSwitchingDecoder::new();
+    assert_eq!(decoder.mode_for(0x33), Some(CodeSegmentMode::Long));
+    assert_eq!(decoder.mode_for(0x2b), None);
+
+    // `inc eax` in 32-bit code, a rex prefix in 64-bit code.
+    let data = [0x40, 0x90];
+    let instr = decoder.decode_slice(0x23, &data).unwrap();
+    assert_eq!(instr.mode(), CodeSegmentMode::Compatibility32);
+    assert_eq!(instr.len(), 1);
+    assert!(!instr.is_empty());
+    let instr = decoder.decode_slice(0x33, &data).unwrap();
+    assert_eq!(instr.len(), 2);
+    match instr {
+        SegmentInstruction::Long(instr) => assert_eq!(instr.opcode(), long_mode::Opcode::NOP),
+        other => panic!("decoded in the wrong mode: {:?}", other),
+    }
+
+    assert_eq!(decoder.decode_slice(0x2b, &data), Err(SegmentDecodeError::UnknownSelector(0x2b)));
+    decoder.map_selector(0x2b, CodeSegmentMode::Compatibility16);
+    assert_eq!(decoder.decode_slice(0x2b, &[0x66, 0x40]).unwrap().to_string(), "inc eax");
+    assert_eq!(decoder.unmap_selector(0x2b), Some(CodeSegmentMode::Compatibility16));
+
+    assert!(matches!(decoder.decode_slice(0x33, &[0x0f]), Err(SegmentDecodeError::Long(_))));
+
+    assert_eq!(CodeSegmentMode::from_descriptor_bits(true, false), Some(CodeSegmentMode::Long));
+    assert_eq!(CodeSegmentMode::from_descriptor_bits(true, true), None);
+}
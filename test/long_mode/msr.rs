@@ -0,0 +1,39 @@
+use yaxpeax_x86::long_mode::{DisplayOptions, DisplayStyle, InstDecoder, RegSpec, RegValueSource};
+
+struct Ecx(Option<u64>);
+
+impl RegValueSource for Ecx {
+    fn reg_value(&self, reg: RegSpec) -> Option<u64> {
+        if reg == RegSpec::ecx() {
+            self.0
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn test_msr_names() {
+    assert_eq!(yaxpeax_x86::msr_name(0x10), Some("IA32_TIME_STAMP_COUNTER"));
+    assert_eq!(yaxpeax_x86::msr_name(0x1d9), Some("IA32_DEBUGCTL"));
+    assert_eq!(yaxpeax_x86::msr_name(0xc000_0102), Some("IA32_KERNEL_GS_BASE"));
+    assert_eq!(yaxpeax_x86::msr_name(0x11), None);
+
+    let decoder = InstDecoder::default();
+    let wrmsr = decoder.decode_slice(&[0x0f, 0x30]).unwrap();
+    let rdmsr = decoder.decode_slice(&[0x0f, 0x32]).unwrap();
+    let nop = decoder.decode_slice(&[0x90]).unwrap();
+
+    let lstar = Ecx(Some(0xc000_0082));
+    assert_eq!(wrmsr.msr_index(&lstar), Some(0xc000_0082));
+    assert_eq!(wrmsr.msr_name(&lstar), Some("IA32_LSTAR"));
+    assert_eq!(nop.msr_index(&lstar), None);
+    assert_eq!(wrmsr.msr_index(&Ecx(None)), None);
+
+    assert_eq!(wrmsr.display_with(DisplayStyle::Intel).with_msr_names(&lstar).to_string(), "wrmsr ; IA32_LSTAR");
+    assert_eq!(rdmsr.display_with(DisplayOptions::att()).with_msr_names(&Ecx(Some(0x1b))).to_string(), "rdmsr ; IA32_APIC_BASE");
+    // unknown registers, unknown `ecx`, and other instructions are written as usual.
+    assert_eq!(rdmsr.display_with(DisplayStyle::Intel).with_msr_names(&Ecx(Some(0x4000_0000))).to_string(), "rdmsr");
+    assert_eq!(rdmsr.display_with(DisplayStyle::Intel).with_msr_names(&Ecx(None)).to_string(), "rdmsr");
+    assert_eq!(nop.display_with(DisplayStyle::Intel).with_msr_names(&lstar).to_string(), "nop");
+}
@@ -1,4 +1,4 @@
-use yaxpeax_x86::long_mode::{InstDecoder, Operand, RegSpec};
+use yaxpeax_x86::long_mode::{AccessKind, Avx512Decorations, BranchTarget, ElementType, EvexDispInfo, Flags, HashOptions, ImmExtension, ImmValue, InstDecoder, Masking, MergeMode, NopStrictness, Operand, OperandConstraint, PrivilegeLevel, RegSpec, RegValueSource, RepSemantics, SaeMode, Segment, VectorShape, XsaveComponent, XsaveFormat, register_class};
 use yaxpeax_x86::MemoryAccessSize;
 
 #[test]
@@ -29,6 +29,54 @@ fn memory_widths() {
     assert_eq!(mem_size_of(&[0x48, 0x33, 0x00]).size_name(), "qword");
 }
 
+#[test]
+fn operand_widths() {
+    fn widths_of(data: &[u8]) -> Vec<Option<u8>> {
+        let inst = InstDecoder::default().decode_slice(data).unwrap();
+        (0..4).map(|i| inst.operand_width(i)).collect()
+    }
+
+    // add rax, 0x10: the immediate is sign-extended to eight bytes
+    assert_eq!(widths_of(&[0x48, 0x83, 0xc0, 0x10]), vec![Some(8), Some(8), None, None]);
+    // add word [rcx], 0x1234
+    assert_eq!(widths_of(&[0x66, 0x81, 0x01, 0x34, 0x12]), vec![Some(2), Some(2), None, None]);
+    // mov rax, -0x1: `c7` sign-extends a four-byte immediate
+    assert_eq!(widths_of(&[0x48, 0xc7, 0xc0, 0xff, 0xff, 0xff, 0xff]), vec![Some(8), Some(8), None, None]);
+    // imul eax, ecx, 0x10
+    assert_eq!(widths_of(&[0x6b, 0xc1, 0x10]), vec![Some(4), Some(4), Some(4), None]);
+    // push 0x10: eight bytes are pushed
+    assert_eq!(widths_of(&[0x6a, 0x10]), vec![Some(8), None, None, None]);
+    // shl rax, 0x3: the shift count is just a byte
+    assert_eq!(widths_of(&[0x48, 0xc1, 0xe0, 0x03]), vec![Some(8), Some(1), None, None]);
+    // vaddps ymm0, ymm1, ymmword [rax]
+    assert_eq!(widths_of(&[0xc5, 0xf4, 0x58, 0x00]), vec![Some(32), Some(32), Some(32), None]);
+    // fxsave [rax]: the save area has no single size
+    assert_eq!(widths_of(&[0x0f, 0xae, 0x00]), vec![None, None, None, None]);
+}
+
+#[test]
+fn x87_memory_sizes() {
+    fn mem_size_of(data: &[u8]) -> MemoryAccessSize {
+        let decoder = InstDecoder::default();
+        decoder.decode_slice(data).unwrap().mem_size().unwrap()
+    }
+
+    // fld tword [rax], fbld tword [rax]
+    assert_eq!(mem_size_of(&[0xdb, 0x28]).bytes_size(), Some(10));
+    assert_eq!(mem_size_of(&[0xdb, 0x28]).size_name(), "tword");
+    assert_eq!(mem_size_of(&[0xdf, 0x20]).size_name(), "tword");
+    // fnstenv and fldenv: the environment is smaller with a 16-bit operand size
+    assert_eq!(mem_size_of(&[0xd9, 0x30]).bytes_size(), Some(28));
+    assert_eq!(mem_size_of(&[0x66, 0xd9, 0x30]).bytes_size(), Some(14));
+    assert_eq!(mem_size_of(&[0xd9, 0x20]).bytes_size(), Some(28));
+    assert_eq!(mem_size_of(&[0xd9, 0x20]).size_name(), "ptr");
+    // fnsave and frstor also hold the eight registers
+    assert_eq!(mem_size_of(&[0xdd, 0x30]).bytes_size(), Some(108));
+    assert_eq!(mem_size_of(&[0x66, 0xdd, 0x30]).bytes_size(), Some(94));
+    assert_eq!(mem_size_of(&[0xdd, 0x20]).bytes_size(), Some(108));
+    assert_eq!(mem_size_of(&[0xdd, 0x30]).size_name(), "ptr");
+}
+
 #[test]
 fn test_implied_memory_width() {
     fn mem_size_of(data: &[u8]) -> Option<u8> {
@@ -49,3 +97,1197 @@ fn test_implied_memory_width() {
     assert_eq!(mem_size_of(&[0x66, 0xff, 0x10]), Some(8));
     assert_eq!(mem_size_of(&[0x66, 0xff, 0x20]), Some(8));
 }
+
+#[test]
+fn operand_access_kinds() {
+    fn operands_of(data: &[u8]) -> Vec<(Operand, AccessKind)> {
+        let decoder = InstDecoder::default();
+        decoder.decode_slice(data).unwrap().operands().collect()
+    }
+
+    // add rax, rbx
+    assert_eq!(operands_of(&[0x48, 0x01, 0xd8]), vec![
+        (Operand::Register(RegSpec::rax()), AccessKind::ReadWrite),
+        (Operand::Register(RegSpec::rbx()), AccessKind::Read),
+    ]);
+    // mov rax, qword [rcx]
+    assert_eq!(operands_of(&[0x48, 0x8b, 0x01]), vec![
+        (Operand::Register(RegSpec::rax()), AccessKind::Write),
+        (Operand::RegDeref(RegSpec::rcx()), AccessKind::Read),
+    ]);
+    // cmp eax, 1
+    assert_eq!(operands_of(&[0x83, 0xf8, 0x01]), vec![
+        (Operand::Register(RegSpec::eax()), AccessKind::Read),
+        (Operand::ImmediateI8(1), AccessKind::Read),
+    ]);
+    // xchg rax, rbx
+    assert_eq!(operands_of(&[0x48, 0x93]), vec![
+        (Operand::Register(RegSpec::rax()), AccessKind::ReadWrite),
+        (Operand::Register(RegSpec::rbx()), AccessKind::ReadWrite),
+    ]);
+    // vaddps xmm0, xmm1, xmm2
+    let kinds: Vec<AccessKind> = operands_of(&[0xc5, 0xf0, 0x58, 0xc2]).into_iter().map(|(_, k)| k).collect();
+    assert_eq!(kinds, vec![AccessKind::Write, AccessKind::Read, AccessKind::Read]);
+    // vfmadd231ps xmm0, xmm1, xmm2
+    let kinds: Vec<AccessKind> = operands_of(&[0xc4, 0xe2, 0x71, 0xb8, 0xc2]).into_iter().map(|(_, k)| k).collect();
+    assert_eq!(kinds, vec![AccessKind::ReadWrite, AccessKind::Read, AccessKind::Read]);
+    // vaddps zmm0{k1}, zmm1, zmm2 merges into zmm0
+    let kinds: Vec<AccessKind> = operands_of(&[0x62, 0xf1, 0x74, 0x49, 0x58, 0xc2]).into_iter().map(|(_, k)| k).collect();
+    assert_eq!(kinds, vec![AccessKind::ReadWrite, AccessKind::Read, AccessKind::Read]);
+
+    let inst = InstDecoder::default().decode_slice(&[0x48, 0x01, 0xd8]).unwrap();
+    let operands = inst.operands();
+    assert_eq!(operands.len(), 2);
+    assert_eq!(operands.count(), inst.operand_count() as usize);
+    let nop = InstDecoder::default().decode_slice(&[0x90]).unwrap();
+    assert_eq!(nop.operands().next(), None);
+}
+
+#[test]
+fn operand_access_by_index() {
+    fn accesses_of(data: &[u8]) -> Vec<AccessKind> {
+        let inst = InstDecoder::default().decode_slice(data).unwrap();
+        (0..inst.operand_count()).map(|i| inst.operand_access(i).unwrap()).collect()
+    }
+
+    // cmovz rax, rcx
+    assert_eq!(accesses_of(&[0x48, 0x0f, 0x44, 0xc1]), vec![AccessKind::CondWrite, AccessKind::Read]);
+    // vpmaskmovd xmmword [rax], xmm1, xmm0
+    assert_eq!(accesses_of(&[0xc4, 0xe2, 0x71, 0x8e, 0x00]), vec![AccessKind::CondWrite, AccessKind::Read, AccessKind::Read]);
+    // vpmaskmovd xmm0, xmm1, xmmword [rax]
+    assert_eq!(accesses_of(&[0xc4, 0xe2, 0x71, 0x8c, 0x00]), vec![AccessKind::Write, AccessKind::Read, AccessKind::Read]);
+    // vpgatherdq xmm1, [rax + xmm1 * 4], xmm2
+    assert_eq!(accesses_of(&[0xc4, 0xe2, 0xe9, 0x90, 0x0c, 0x88]), vec![AccessKind::ReadWrite, AccessKind::Read, AccessKind::ReadWrite]);
+    // movss xmm0, xmm1 merges, movss xmm0, [rax] does not
+    assert_eq!(accesses_of(&[0xf3, 0x0f, 0x10, 0xc1]), vec![AccessKind::ReadWrite, AccessKind::Read]);
+    assert_eq!(accesses_of(&[0xf3, 0x0f, 0x10, 0x00]), vec![AccessKind::Write, AccessKind::Read]);
+    // imul in each of its forms
+    assert_eq!(accesses_of(&[0xf7, 0xe9]), vec![AccessKind::Read]);
+    assert_eq!(accesses_of(&[0x0f, 0xaf, 0xc1]), vec![AccessKind::ReadWrite, AccessKind::Read]);
+    assert_eq!(accesses_of(&[0x6b, 0xc1, 0x0a]), vec![AccessKind::Write, AccessKind::Read, AccessKind::Read]);
+
+    // indices past the last operand have no access, rather than panicking.
+    let inst = InstDecoder::default().decode_slice(&[0x31, 0xc0]).unwrap();
+    assert_eq!(inst.operand_access(2), None);
+    assert_eq!(inst.operand_access(4), None);
+
+    assert!(AccessKind::ReadWrite.is_read() && AccessKind::ReadWrite.is_write());
+    assert!(!AccessKind::CondWrite.is_read() && AccessKind::CondWrite.is_write());
+}
+
+#[test]
+fn memory_accesses() {
+    fn accesses_of(data: &[u8]) -> Vec<(Option<u8>, Operand, Option<u8>, AccessKind)> {
+        let decoder = InstDecoder::default();
+        decoder.decode_slice(data).unwrap().memory_accesses().map(|access| {
+            (access.operand, access.address, access.size, access.access)
+        }).collect()
+    }
+
+    // movsq: a read from `[rsi]` and a write to `[rdi]`.
+    assert_eq!(accesses_of(&[0x48, 0xa5]), vec![
+        (Some(0), Operand::RegDeref(RegSpec::rdi()), Some(8), AccessKind::Write),
+        (Some(1), Operand::RegDeref(RegSpec::rsi()), Some(8), AccessKind::Read),
+    ]);
+    // push qword [rax]
+    assert_eq!(accesses_of(&[0xff, 0x30]), vec![
+        (Some(0), Operand::RegDeref(RegSpec::rax()), Some(8), AccessKind::Read),
+        (None, Operand::RegDisp(RegSpec::rsp(), -8), Some(8), AccessKind::Write),
+    ]);
+    // fld tword [rax]
+    assert_eq!(accesses_of(&[0xdb, 0x28]), vec![
+        (Some(1), Operand::RegDeref(RegSpec::rax()), Some(10), AccessKind::Read),
+    ]);
+    // call and ret with `66` still push and pop a qword `rip`.
+    assert_eq!(accesses_of(&[0x66, 0xe8, 0x00, 0x00, 0x00, 0x00]), vec![
+        (None, Operand::RegDisp(RegSpec::rsp(), -8), Some(8), AccessKind::Write),
+    ]);
+    assert_eq!(accesses_of(&[0x66, 0xc3]), vec![
+        (None, Operand::RegDeref(RegSpec::rsp()), Some(8), AccessKind::Read),
+    ]);
+    // retf pops the return address and `cs`.
+    assert_eq!(accesses_of(&[0xcb]), vec![
+        (None, Operand::RegDeref(RegSpec::rsp()), Some(16), AccessKind::Read),
+    ]);
+    // int 0x80 pushes `ss`, `rsp`, `rflags`, `cs`, and `rip`, even with a `66` prefix.
+    assert_eq!(accesses_of(&[0xcd, 0x80]), vec![
+        (None, Operand::RegDisp(RegSpec::rsp(), -0x28), Some(40), AccessKind::Write),
+    ]);
+    assert_eq!(accesses_of(&[0x66, 0xcd, 0x80]), vec![
+        (None, Operand::RegDisp(RegSpec::rsp(), -0x28), Some(40), AccessKind::Write),
+    ]);
+    // lea rax, [rbx + 0x10] computes an address, but does not access it.
+    assert_eq!(accesses_of(&[0x48, 0x8d, 0x43, 0x10]), vec![]);
+    // add rax, rcx
+    assert_eq!(accesses_of(&[0x48, 0x01, 0xc8]), vec![]);
+}
+
+#[test]
+fn rep_semantics() {
+    fn rep_of(data: &[u8]) -> RepSemantics {
+        InstDecoder::default().decode_slice(data).unwrap().rep_semantics()
+    }
+
+    // rep movsb
+    assert_eq!(rep_of(&[0xf3, 0xa4]), RepSemantics::Repeat);
+    // repnz stosb repeats all the same
+    assert_eq!(rep_of(&[0xf2, 0xaa]), RepSemantics::Repeat);
+    // repz cmpsb, repnz scasb
+    assert_eq!(rep_of(&[0xf3, 0xa6]), RepSemantics::RepeatWhileEqual);
+    assert_eq!(rep_of(&[0xf2, 0xae]), RepSemantics::RepeatWhileNotEqual);
+    // popcnt eax, ecx; movss xmm0, [rax]; endbr64; crc32 eax, cl
+    assert_eq!(rep_of(&[0xf3, 0x0f, 0xb8, 0xc1]), RepSemantics::Mandatory);
+    assert_eq!(rep_of(&[0xf3, 0x0f, 0x10, 0x00]), RepSemantics::Mandatory);
+    assert_eq!(rep_of(&[0xf3, 0x0f, 0x1e, 0xfa]), RepSemantics::Mandatory);
+    assert_eq!(rep_of(&[0xf2, 0x0f, 0x38, 0xf0, 0xc1]), RepSemantics::Mandatory);
+    // pause
+    assert_eq!(rep_of(&[0xf3, 0x90]), RepSemantics::Pause);
+    // xacquire lock add [rax], ecx; xrelease mov [rax], ecx
+    assert_eq!(rep_of(&[0xf2, 0xf0, 0x01, 0x08]), RepSemantics::Xacquire);
+    assert_eq!(rep_of(&[0xf3, 0x89, 0x08]), RepSemantics::Xrelease);
+    // bnd call, bnd jz, bnd ret
+    assert_eq!(rep_of(&[0xf2, 0xe8, 0x00, 0x00, 0x00, 0x00]), RepSemantics::Bnd);
+    assert_eq!(rep_of(&[0xf2, 0x74, 0x00]), RepSemantics::Bnd);
+    assert_eq!(rep_of(&[0xf2, 0xc3]), RepSemantics::Bnd);
+    // rep ret, and f3 on an instruction that has no use for it
+    assert_eq!(rep_of(&[0xf3, 0xc3]), RepSemantics::Ignored);
+    assert_eq!(rep_of(&[0xf3, 0x01, 0xc8]), RepSemantics::Ignored);
+    // add eax, ecx
+    assert_eq!(rep_of(&[0x01, 0xc8]), RepSemantics::None);
+}
+
+#[test]
+fn required_cpl() {
+    fn cpl_of(data: &[u8]) -> PrivilegeLevel {
+        InstDecoder::default().decode_slice(data).unwrap().required_cpl()
+    }
+
+    // mov cr3, rax; mov rax, dr7
+    assert_eq!(cpl_of(&[0x0f, 0x22, 0xd8]), PrivilegeLevel::Kernel);
+    assert_eq!(cpl_of(&[0x0f, 0x21, 0xf8]), PrivilegeLevel::Kernel);
+    // hlt, lgdt [rax], wrmsr, invd
+    assert_eq!(cpl_of(&[0xf4]), PrivilegeLevel::Kernel);
+    assert_eq!(cpl_of(&[0x0f, 0x01, 0x10]), PrivilegeLevel::Kernel);
+    assert_eq!(cpl_of(&[0x0f, 0x30]), PrivilegeLevel::Kernel);
+    assert_eq!(cpl_of(&[0x0f, 0x08]), PrivilegeLevel::Kernel);
+    // in al, dx; cli
+    assert_eq!(cpl_of(&[0xec]), PrivilegeLevel::Iopl);
+    assert_eq!(cpl_of(&[0xfa]), PrivilegeLevel::Iopl);
+    // rdtsc, sgdt [rax]
+    assert_eq!(cpl_of(&[0x0f, 0x31]), PrivilegeLevel::Configurable);
+    assert_eq!(cpl_of(&[0x0f, 0x01, 0x00]), PrivilegeLevel::Configurable);
+    // mov rax, rcx; syscall
+    assert_eq!(cpl_of(&[0x48, 0x89, 0xc8]), PrivilegeLevel::User);
+    assert_eq!(cpl_of(&[0x0f, 0x05]), PrivilegeLevel::User);
+
+    assert!(InstDecoder::default().decode_slice(&[0xf4]).unwrap().is_privileged());
+    assert!(!InstDecoder::default().decode_slice(&[0xfa]).unwrap().is_privileged());
+}
+
+#[test]
+fn implicit_operands() {
+    fn implicit_of(data: &[u8]) -> Vec<(Operand, AccessKind)> {
+        let decoder = InstDecoder::default();
+        decoder.decode_slice(data).unwrap().implicit_operands().collect()
+    }
+
+    // mul rcx
+    assert_eq!(implicit_of(&[0x48, 0xf7, 0xe1]), vec![
+        (Operand::Register(RegSpec::rax()), AccessKind::ReadWrite),
+        (Operand::Register(RegSpec::rdx()), AccessKind::Write),
+        (Operand::Register(RegSpec::rflags()), AccessKind::Write),
+    ]);
+    // div byte [rax]
+    assert_eq!(implicit_of(&[0xf6, 0x30]), vec![
+        (Operand::Register(RegSpec::ax()), AccessKind::ReadWrite),
+        (Operand::Register(RegSpec::rflags()), AccessKind::Write),
+    ]);
+    // push rbp
+    assert_eq!(implicit_of(&[0x55]), vec![
+        (Operand::Register(RegSpec::rsp()), AccessKind::ReadWrite),
+        (Operand::RegDisp(RegSpec::rsp(), -8), AccessKind::Write),
+    ]);
+    // ret
+    assert_eq!(implicit_of(&[0xc3]), vec![
+        (Operand::Register(RegSpec::rsp()), AccessKind::ReadWrite),
+        (Operand::RegDeref(RegSpec::rsp()), AccessKind::Read),
+    ]);
+    // rep movsb
+    assert_eq!(implicit_of(&[0xf3, 0xa4]), vec![
+        (Operand::Register(RegSpec::rsi()), AccessKind::ReadWrite),
+        (Operand::Register(RegSpec::rdi()), AccessKind::ReadWrite),
+        (Operand::Register(RegSpec::rcx()), AccessKind::ReadWrite),
+        (Operand::Register(RegSpec::rflags()), AccessKind::Read),
+    ]);
+    // stos with a 32-bit address size
+    assert_eq!(implicit_of(&[0x67, 0xaa]), vec![
+        (Operand::Register(RegSpec::edi()), AccessKind::ReadWrite),
+        (Operand::Register(RegSpec::rflags()), AccessKind::Read),
+    ]);
+    // cpuid
+    assert_eq!(implicit_of(&[0x0f, 0xa2]), vec![
+        (Operand::Register(RegSpec::eax()), AccessKind::ReadWrite),
+        (Operand::Register(RegSpec::ecx()), AccessKind::ReadWrite),
+        (Operand::Register(RegSpec::ebx()), AccessKind::Write),
+        (Operand::Register(RegSpec::edx()), AccessKind::Write),
+    ]);
+    // rdtsc
+    assert_eq!(implicit_of(&[0x0f, 0x31]), vec![
+        (Operand::Register(RegSpec::eax()), AccessKind::Write),
+        (Operand::Register(RegSpec::edx()), AccessKind::Write),
+    ]);
+    // syscall
+    assert_eq!(implicit_of(&[0x0f, 0x05]), vec![
+        (Operand::Register(RegSpec::rcx()), AccessKind::Write),
+        (Operand::Register(RegSpec::r11()), AccessKind::Write),
+        (Operand::Register(RegSpec::rflags()), AccessKind::ReadWrite),
+    ]);
+    // add rax, rbx
+    assert_eq!(implicit_of(&[0x48, 0x01, 0xd8]), vec![
+        (Operand::Register(RegSpec::rflags()), AccessKind::Write),
+    ]);
+    // mov rax, rbx
+    assert_eq!(implicit_of(&[0x48, 0x89, 0xd8]), vec![]);
+}
+
+#[test]
+fn flags_read_written() {
+    fn flags_of(data: &[u8]) -> (Flags, Flags) {
+        let inst = InstDecoder::default().decode_slice(data).unwrap();
+        (inst.flags_read(), inst.flags_written())
+    }
+
+    // add rax, rbx
+    assert_eq!(flags_of(&[0x48, 0x01, 0xd8]), (Flags::empty(), Flags::STATUS));
+    // adc rax, rbx
+    assert_eq!(flags_of(&[0x48, 0x11, 0xd8]), (Flags::CF, Flags::STATUS));
+    // inc eax leaves CF alone
+    let (_, written) = flags_of(&[0xff, 0xc0]);
+    assert!(!written.contains(Flags::CF));
+    assert!(written.contains(Flags::ZF | Flags::OF));
+    // jle $+0x10
+    assert_eq!(flags_of(&[0x7e, 0x10]), (Flags::ZF | Flags::SF | Flags::OF, Flags::empty()));
+    // cmova eax, ecx
+    assert_eq!(flags_of(&[0x0f, 0x47, 0xc1]), (Flags::CF | Flags::ZF, Flags::empty()));
+    // shl eax, 0 does not touch flags, but shl eax, 1 does
+    assert_eq!(flags_of(&[0xc1, 0xe0, 0x00]), (Flags::empty(), Flags::empty()));
+    assert_eq!(flags_of(&[0xd1, 0xe0]), (Flags::empty(), Flags::STATUS));
+    // rcl eax, cl
+    assert_eq!(flags_of(&[0xd3, 0xd0]), (Flags::CF, Flags::OF | Flags::CF));
+    // std; cld; movsb
+    assert_eq!(flags_of(&[0xfd]), (Flags::empty(), Flags::DF));
+    assert_eq!(flags_of(&[0xa4]), (Flags::DF, Flags::empty()));
+    // pushf
+    assert_eq!(flags_of(&[0x9c]), (Flags::ALL, Flags::empty()));
+    // mov eax, ecx
+    assert_eq!(flags_of(&[0x89, 0xc8]), (Flags::empty(), Flags::empty()));
+
+    assert_eq!(Flags::STATUS.bits(), 0x8d5);
+    assert_eq!((Flags::CF | Flags::ZF).bits(), 0x41);
+}
+
+#[test]
+fn branch_targets() {
+    fn target_of(data: &[u8], address: u64) -> Option<BranchTarget> {
+        InstDecoder::default().decode_slice(data).unwrap().branch_target(address)
+    }
+
+    // jmp $-2
+    assert_eq!(target_of(&[0xeb, 0xfe], 0x1000), Some(BranchTarget::Direct(0x1000)));
+    // jnz $+0x100
+    assert_eq!(target_of(&[0x0f, 0x85, 0x00, 0x01, 0x00, 0x00], 0x1000), Some(BranchTarget::Direct(0x1106)));
+    // call $+0
+    assert_eq!(target_of(&[0xe8, 0x00, 0x00, 0x00, 0x00], 0x1000), Some(BranchTarget::Direct(0x1005)));
+    // loop $-2, wrapping around the bottom of the address space
+    assert_eq!(target_of(&[0xe2, 0xf0], 0), Some(BranchTarget::Direct(0xffff_ffff_ffff_fff2)));
+    // jmp rax; call qword [rip + 0x10]
+    assert_eq!(target_of(&[0xff, 0xe0], 0x1000), Some(BranchTarget::Indirect(Operand::Register(RegSpec::rax()))));
+    assert_eq!(
+        target_of(&[0xff, 0x15, 0x10, 0x00, 0x00, 0x00], 0x1000),
+        Some(BranchTarget::Indirect(Operand::RegDisp(RegSpec::rip(), 0x10)))
+    );
+    // jmpabs 0x1122334455667788
+    assert_eq!(
+        target_of(&[0xd5, 0x00, 0xa1, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11], 0x1000),
+        Some(BranchTarget::Direct(0x1122334455667788))
+    );
+    // ret and add don't branch anywhere an instruction can name.
+    assert_eq!(target_of(&[0xc3], 0x1000), None);
+    assert_eq!(target_of(&[0x48, 0x01, 0xd8], 0x1000), None);
+}
+
+#[test]
+fn semantic_hashes() {
+    fn hash_of(data: &[u8], options: HashOptions) -> u64 {
+        InstDecoder::default().decode_slice(data).unwrap().semantic_hash(options)
+    }
+
+    let exact = HashOptions::new();
+    // add eax, 0x1 (imm8); add eax, 0x1 (imm32); add eax, 0x2
+    let add_1 = hash_of(&[0x83, 0xc0, 0x01], exact);
+    assert_eq!(add_1, hash_of(&[0x05, 0x01, 0x00, 0x00, 0x00], exact));
+    assert_ne!(add_1, hash_of(&[0x83, 0xc0, 0x02], exact));
+    let imms = HashOptions::new().ignore_immediates();
+    assert_eq!(hash_of(&[0x83, 0xc0, 0x01], imms), hash_of(&[0x83, 0xc0, 0x02], imms));
+    // sub eax, 0x1
+    assert_ne!(hash_of(&[0x83, 0xc0, 0x01], imms), hash_of(&[0x83, 0xe8, 0x01], imms));
+
+    // mov eax, dword [rbp - 0x8]; mov eax, dword [rbp - 0x10]; mov eax, dword [rsp - 0x8]
+    let local_8 = hash_of(&[0x8b, 0x45, 0xf8], exact);
+    assert_ne!(local_8, hash_of(&[0x8b, 0x45, 0xf0], exact));
+    let disps = HashOptions::new().ignore_displacements();
+    assert_eq!(hash_of(&[0x8b, 0x45, 0xf8], disps), hash_of(&[0x8b, 0x45, 0xf0], disps));
+    assert_ne!(hash_of(&[0x8b, 0x45, 0xf8], disps), hash_of(&[0x8b, 0x44, 0x24, 0xf8], disps));
+
+    // add eax, ecx; add edx, ebx; add rax, rcx
+    let regs = HashOptions::new().ignore_registers();
+    assert_ne!(hash_of(&[0x01, 0xc8], exact), hash_of(&[0x01, 0xda], exact));
+    assert_eq!(hash_of(&[0x01, 0xc8], regs), hash_of(&[0x01, 0xda], regs));
+    assert_ne!(hash_of(&[0x01, 0xc8], regs), hash_of(&[0x48, 0x01, 0xc8], regs));
+
+    // ret; retf
+    assert_ne!(hash_of(&[0xc3], exact), hash_of(&[0xcb], exact));
+}
+
+#[test]
+fn semantic_equality() {
+    fn eq(a: &[u8], b: &[u8]) -> bool {
+        let decoder = InstDecoder::default();
+        let (a, b) = (decoder.decode_slice(a).unwrap(), decoder.decode_slice(b).unwrap());
+        let eq = a.semantically_eq(&b);
+        if eq {
+            assert_eq!(a.semantic_hash(HashOptions::new()), b.semantic_hash(HashOptions::new()));
+        }
+        eq
+    }
+
+    // add rax, 0x1: imm8, imm32, and the short `rax` form
+    assert!(eq(&[0x48, 0x83, 0xc0, 0x01], &[0x48, 0x81, 0xc0, 0x01, 0x00, 0x00, 0x00]));
+    assert!(eq(&[0x48, 0x83, 0xc0, 0x01], &[0x48, 0x05, 0x01, 0x00, 0x00, 0x00]));
+    assert!(!eq(&[0x48, 0x83, 0xc0, 0x01], &[0x83, 0xc0, 0x01]));
+    // mov eax, ebx in both directions, and with a redundant rex
+    assert!(eq(&[0x89, 0xd8], &[0x8b, 0xc3]));
+    assert!(eq(&[0x89, 0xd8], &[0x40, 0x89, 0xd8]));
+    // mov eax, dword [rbx]; mov eax, dword [rbx + 0x0]; ds mov eax, dword [rbx]
+    assert!(eq(&[0x8b, 0x03], &[0x8b, 0x43, 0x00]));
+    assert!(eq(&[0x8b, 0x03], &[0x3e, 0x8b, 0x03]));
+    assert!(!eq(&[0x8b, 0x03], &[0x64, 0x8b, 0x03]));
+    // vmovaps xmm0, xmm1 and vmovaps xmm0, xmmword [rax], vex and evex
+    assert!(eq(&[0xc5, 0xf8, 0x28, 0xc1], &[0x62, 0xf1, 0x7c, 0x08, 0x28, 0xc1]));
+    assert!(eq(&[0xc5, 0xf8, 0x28, 0x00], &[0x62, 0xf1, 0x7c, 0x08, 0x28, 0x00]));
+    // ...but not masked
+    assert!(!eq(&[0xc5, 0xf8, 0x28, 0xc1], &[0x62, 0xf1, 0x7c, 0x09, 0x28, 0xc1]));
+    // lock changes what an instruction does
+    assert!(!eq(&[0x01, 0x08], &[0xf0, 0x01, 0x08]));
+}
+
+#[test]
+fn nops() {
+    fn strictest_nop(data: &[u8]) -> Option<NopStrictness> {
+        let instr = InstDecoder::default().decode_slice(data).unwrap();
+        [NopStrictness::Canonical, NopStrictness::Equivalent, NopStrictness::Padding]
+            .iter()
+            .cloned()
+            .find(|strictness| instr.is_nop(*strictness))
+    }
+
+    // nop; data16 nop; nop dword [rax + rax * 1 + 0x0]; cs nop word [rax + rax * 1 + 0x0]
+    assert_eq!(strictest_nop(&[0x90]), Some(NopStrictness::Canonical));
+    assert_eq!(strictest_nop(&[0x66, 0x90]), Some(NopStrictness::Canonical));
+    assert_eq!(strictest_nop(&[0x0f, 0x1f, 0x44, 0x00, 0x00]), Some(NopStrictness::Canonical));
+    assert_eq!(strictest_nop(&[0x66, 0x2e, 0x0f, 0x1f, 0x84, 0x00, 0x00, 0x00, 0x00, 0x00]), Some(NopStrictness::Canonical));
+    // xchg rax, rax; mov rdi, rdi; mov di, di; lea rsi, [rsi + 0x0]
+    assert_eq!(strictest_nop(&[0x48, 0x87, 0xc0]), Some(NopStrictness::Equivalent));
+    assert_eq!(strictest_nop(&[0x48, 0x89, 0xff]), Some(NopStrictness::Equivalent));
+    assert_eq!(strictest_nop(&[0x66, 0x89, 0xff]), Some(NopStrictness::Equivalent));
+    assert_eq!(strictest_nop(&[0x48, 0x8d, 0x76, 0x00]), Some(NopStrictness::Equivalent));
+    // int3
+    assert_eq!(strictest_nop(&[0xcc]), Some(NopStrictness::Padding));
+
+    // in 64-bit code, writing a 32-bit register clears the upper half: xchg eax, eax; mov edi, edi
+    assert_eq!(strictest_nop(&[0x87, 0xc0]), None);
+    assert_eq!(strictest_nop(&[0x89, 0xff]), None);
+    // mov rdi, rsi; lea rsi, [rsi + 0x8]; int 0x3; endbr64
+    assert_eq!(strictest_nop(&[0x48, 0x89, 0xf7]), None);
+    assert_eq!(strictest_nop(&[0x48, 0x8d, 0x76, 0x08]), None);
+    assert_eq!(strictest_nop(&[0xcd, 0x03]), None);
+    assert_eq!(strictest_nop(&[0xf3, 0x0f, 0x1e, 0xfa]), None);
+}
+
+#[test]
+fn cet_landing_pads() {
+    fn is_landing_pad(data: &[u8]) -> bool {
+        InstDecoder::default().decode_slice(data).unwrap().is_cet_landing_pad()
+    }
+
+    // endbr64
+    assert!(is_landing_pad(&[0xf3, 0x0f, 0x1e, 0xfa]));
+    // endbr32 is only a nop in 64-bit code.
+    assert!(!is_landing_pad(&[0xf3, 0x0f, 0x1e, 0xfb]));
+    // nop
+    assert!(!is_landing_pad(&[0x90]));
+}
+
+struct Regs;
+
+impl RegValueSource for Regs {
+    fn reg_value(&self, reg: RegSpec) -> Option<u64> {
+        if reg == RegSpec::rax() {
+            Some(0x1000)
+        } else if reg == RegSpec::rcx() {
+            Some(0xffff_ffff_ffff_fffe)
+        } else if reg == RegSpec::eax() {
+            Some(0xffff_fff0)
+        } else if reg == RegSpec::rsp() {
+            Some(0x7fff_0000)
+        } else if reg == RegSpec::rip() {
+            Some(0x40_0000)
+        } else {
+            None
+        }
+    }
+
+    fn segment_base(&self, segment: Segment) -> Option<u64> {
+        match segment {
+            Segment::FS => Some(0x7000_0000),
+            Segment::GS => None,
+            _ => Some(0xdead_0000),
+        }
+    }
+}
+
+#[test]
+fn evaluate_addresses() {
+    fn address_of(data: &[u8], i: u8) -> Option<u64> {
+        InstDecoder::default().decode_slice(data).unwrap().operand(i).evaluate(&Regs)
+    }
+
+    // mov eax, dword [rax + rcx * 4 + 0x10]
+    assert_eq!(address_of(&[0x8b, 0x44, 0x88, 0x10], 1), Some(0x1008));
+    // mov eax, dword [rax - 0x2000]
+    assert_eq!(address_of(&[0x8b, 0x80, 0x00, 0xe0, 0xff, 0xff], 1), Some(0xffff_ffff_ffff_f000));
+    // mov eax, dword [eax + 0x20], wrapping at 4g
+    assert_eq!(address_of(&[0x67, 0x8b, 0x40, 0x20], 1), Some(0x10));
+    // mov eax, dword [rip + 0x100]
+    assert_eq!(address_of(&[0x8b, 0x05, 0x00, 0x01, 0x00, 0x00], 1), Some(0x40_0100));
+    // mov rax, qword [0x1122334455667788]
+    assert_eq!(address_of(&[0x48, 0xa1, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11], 1), Some(0x1122334455667788));
+    // mov eax, dword [rdx]: the value of `rdx` is unknown
+    assert_eq!(address_of(&[0x8b, 0x02], 1), None);
+    // registers and immediates have no address
+    assert_eq!(address_of(&[0x8b, 0x02], 0), None);
+    assert_eq!(address_of(&[0x6a, 0x10], 0), None);
+    // vpgatherdd xmm0, dword [rax + xmm1 * 4], xmm2: a different address for each element
+    assert_eq!(address_of(&[0xc4, 0xe2, 0x69, 0x90, 0x04, 0x88], 1), None);
+}
+
+#[test]
+fn rip_relative_targets() {
+    fn target_of(data: &[u8], address: u64) -> Option<u64> {
+        InstDecoder::default().decode_slice(data).unwrap().rip_relative_target(address)
+    }
+
+    // mov eax, dword [rip + 0x100], six bytes long
+    assert_eq!(target_of(&[0x8b, 0x05, 0x00, 0x01, 0x00, 0x00], 0x40_0000), Some(0x40_0106));
+    // lea rax, qword [rip - 0x10]
+    assert_eq!(target_of(&[0x48, 0x8d, 0x05, 0xf0, 0xff, 0xff, 0xff], 0x40_0000), Some(0x3f_fff7));
+    // mov dword [rip + 0x10], 0x1: the immediate follows the displacement
+    assert_eq!(target_of(&[0xc7, 0x05, 0x10, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00], 0x1000), Some(0x101a));
+    // call qword [rip + 0x0]
+    assert_eq!(target_of(&[0xff, 0x15, 0x00, 0x00, 0x00, 0x00], 0x1000), Some(0x1006));
+    // mov eax, dword [eip + 0x10], wrapping at 4g
+    assert_eq!(target_of(&[0x67, 0x8b, 0x05, 0x10, 0x00, 0x00, 0x00], 0xffff_fff0), Some(0x7));
+    // mov eax, dword [rax + 0x100]; jmp $+0x10
+    assert_eq!(target_of(&[0x8b, 0x80, 0x00, 0x01, 0x00, 0x00], 0x1000), None);
+    assert_eq!(target_of(&[0xeb, 0x10], 0x1000), None);
+}
+
+#[test]
+fn linear_addresses() {
+    fn linear_address_of(data: &[u8], i: u8) -> Option<u64> {
+        InstDecoder::default().decode_slice(data).unwrap().linear_address(i, &Regs)
+    }
+
+    // mov eax, dword [rax]: the `ds` base is ignored in 64-bit mode
+    assert_eq!(linear_address_of(&[0x8b, 0x00], 1), Some(0x1000));
+    // mov eax, dword fs:[rax + 0x8]
+    assert_eq!(linear_address_of(&[0x64, 0x8b, 0x40, 0x08], 1), Some(0x7000_1008));
+    // mov eax, dword gs:[rax]: the `gs` base is unknown
+    assert_eq!(linear_address_of(&[0x65, 0x8b, 0x00], 1), None);
+    // mov eax, dword [rsp + 0x8]
+    assert_eq!(linear_address_of(&[0x8b, 0x44, 0x24, 0x08], 1), Some(0x7fff_0008));
+}
+
+#[test]
+fn avx512_decorations() {
+    fn decorations_of(data: &[u8]) -> Option<Avx512Decorations> {
+        InstDecoder::default().decode_slice(data).unwrap().avx512_decorations()
+    }
+
+    // vunpcklps zmm1{k5}{z}, zmm0, dword [rdx]{1to16}
+    assert_eq!(decorations_of(&[0x62, 0xf1, 0x7c, 0xdd, 0x14, 0x0a]), Some(Avx512Decorations {
+        mask: Some(RegSpec::mask(5)),
+        merge_mode: MergeMode::Zero,
+        broadcast: Some(16),
+        rounding: None,
+        sae: false,
+    }));
+    // vsqrtps zmm1{rd-sae}, zmm2
+    assert_eq!(decorations_of(&[0x62, 0xf1, 0x7c, 0x38, 0x51, 0xca]), Some(Avx512Decorations {
+        mask: None,
+        merge_mode: MergeMode::Merge,
+        broadcast: None,
+        rounding: Some(SaeMode::RoundDown),
+        sae: true,
+    }));
+    // vucomiss xmm1{sae}, xmm2
+    assert_eq!(decorations_of(&[0x62, 0xf1, 0x7c, 0x78, 0x2e, 0xca]), Some(Avx512Decorations {
+        mask: None,
+        merge_mode: MergeMode::Merge,
+        broadcast: None,
+        rounding: None,
+        sae: true,
+    }));
+    // vmovups ymm1{k5}{z}, ymmword [rdx]
+    let decorations = decorations_of(&[0x62, 0xf1, 0x7c, 0xad, 0x10, 0x0a]).unwrap();
+    assert_eq!(decorations.mask, Some(RegSpec::mask(5)));
+    assert_eq!(decorations.broadcast, None);
+
+    // not evex-encoded: vaddps ymm0, ymm1, ymm2; addps xmm0, xmm1
+    assert_eq!(decorations_of(&[0xc5, 0xf4, 0x58, 0xc2]), None);
+    assert_eq!(decorations_of(&[0x0f, 0x58, 0xc1]), None);
+}
+
+#[test]
+fn avx512_masking() {
+    fn masking_of(data: &[u8]) -> Masking {
+        InstDecoder::default().decode_slice(data).unwrap().masking()
+    }
+
+    // vunpcklps zmm1{k5}{z}, zmm0, dword [rdx]{1to16}; vcmpps k1{k2}, zmm1, zmm2, 0x0
+    assert_eq!(masking_of(&[0x62, 0xf1, 0x7c, 0xdd, 0x14, 0x0a]), Masking::Zero(RegSpec::mask(5)));
+    assert_eq!(masking_of(&[0x62, 0xf1, 0x74, 0x4a, 0xc2, 0xca, 0x00]), Masking::Zero(RegSpec::mask(2)));
+    // vmovaps zmmword [rax]{k1}, zmm0, with and without `z`: memory is only merge-masked.
+    assert_eq!(masking_of(&[0x62, 0xf1, 0x7c, 0x49, 0x29, 0x00]), Masking::Merge(RegSpec::mask(1)));
+    assert_eq!(masking_of(&[0x62, 0xf1, 0x7c, 0xc9, 0x29, 0x00]), Masking::Merge(RegSpec::mask(1)));
+    // vaddps zmm1{k2}, zmm2, zmm3
+    let merge = masking_of(&[0x62, 0xf1, 0x6c, 0x4a, 0x58, 0xcb]);
+    assert_eq!(merge, Masking::Merge(RegSpec::mask(2)));
+    assert!(merge.preserves_unselected());
+    assert_eq!(merge.mask(), Some(RegSpec::mask(2)));
+
+    // vsqrtps zmm1{rd-sae}, zmm2 is unmasked, as is anything not evex-encoded.
+    assert_eq!(masking_of(&[0x62, 0xf1, 0x7c, 0x38, 0x51, 0xca]), Masking::None);
+    assert_eq!(masking_of(&[0xc5, 0xf4, 0x58, 0xc2]), Masking::None);
+    assert!(!Masking::None.preserves_unselected());
+    assert_eq!(Masking::None.mask(), None);
+}
+
+#[test]
+fn x87_stack_effects() {
+    fn delta_of(data: &[u8]) -> Option<i8> {
+        InstDecoder::default().decode_slice(data).unwrap().x87_stack_delta()
+    }
+
+    // fld qword [rax]; fld1; fstp st(1); faddp st(1), st; fcompp
+    assert_eq!(delta_of(&[0xdd, 0x00]), Some(1));
+    assert_eq!(delta_of(&[0xd9, 0xe8]), Some(1));
+    assert_eq!(delta_of(&[0xdd, 0xd9]), Some(-1));
+    assert_eq!(delta_of(&[0xde, 0xc1]), Some(-1));
+    assert_eq!(delta_of(&[0xde, 0xd9]), Some(-2));
+    // fadd st, st(1); fxch st(1); fnstsw ax
+    assert_eq!(delta_of(&[0xd8, 0xc1]), Some(0));
+    assert_eq!(delta_of(&[0xd9, 0xc9]), Some(0));
+    assert_eq!(delta_of(&[0xdf, 0xe0]), Some(0));
+    // fninit sets the top of the stack rather than moving it; add is not x87 at all.
+    assert_eq!(delta_of(&[0xdb, 0xe3]), None);
+    assert_eq!(delta_of(&[0x01, 0xc0]), None);
+
+    // fld st(3) with the stack's top at 6 reads physical register 1, then pushes to 5.
+    let instr = InstDecoder::default().decode_slice(&[0xd9, 0xc3]).unwrap();
+    assert_eq!(instr.operand(1), Operand::Register(RegSpec::st(3)));
+    assert_eq!(RegSpec::st(3).x87_physical_register(6), Some(1));
+    assert_eq!(instr.x87_top_after(6), Some(5));
+    // fcompp with the top at 7 pops twice, wrapping around to 1.
+    let instr = InstDecoder::default().decode_slice(&[0xde, 0xd9]).unwrap();
+    assert_eq!(instr.x87_top_after(7), Some(1));
+    assert_eq!(RegSpec::rax().x87_physical_register(0), None);
+}
+
+#[test]
+fn synthesized_instructions() {
+    use yaxpeax_x86::long_mode::{Instruction, Opcode, SynthesizeError};
+
+    let instr = Instruction::synthesize(Opcode::MOV, &[Operand::Register(RegSpec::rax()), Operand::ImmediateI32(1)]).unwrap();
+    assert_eq!(instr.to_string(), "mov rax, 0x1");
+    assert_eq!(instr.operand_count(), 2);
+    assert_eq!(instr.operand(1), Operand::ImmediateI32(1));
+    assert_eq!(instr.operand_access(0), Some(AccessKind::Write));
+
+    // synthesized instructions display the same as decoded ones.
+    let decoded = InstDecoder::default().decode_slice(&[0x48, 0x01, 0x44, 0x88, 0x10]).unwrap();
+    let operands: Vec<Operand> = decoded.operands().map(|(op, _)| op).collect();
+    let instr = Instruction::synthesize(Opcode::ADD, &operands).unwrap();
+    assert_eq!(instr.to_string(), decoded.to_string());
+    assert_eq!(instr.mem_size().and_then(|size| size.bytes_size()), Some(8));
+
+    let instr = Instruction::synthesize(Opcode::ENTER, &[Operand::ImmediateU16(0x20), Operand::ImmediateU8(0)]).unwrap();
+    assert_eq!(instr.to_string(), "enter 0x20, 0x0");
+    let instr = Instruction::synthesize(Opcode::VBLENDVPS, &[
+        Operand::Register(RegSpec::xmm(1)),
+        Operand::Register(RegSpec::xmm(2)),
+        Operand::Register(RegSpec::xmm(3)),
+        Operand::Register(RegSpec::xmm(4)),
+    ]).unwrap();
+    assert_eq!(instr.to_string(), "vblendvps xmm1, xmm2, xmm3, xmm4");
+    // with nothing to size it, a memory operand is `ptr`.
+    let instr = Instruction::synthesize(Opcode::INC, &[Operand::RegDeref(RegSpec::rax())]).unwrap();
+    assert_eq!(instr.to_string(), "inc ptr [rax]");
+
+    assert_eq!(
+        Instruction::synthesize(Opcode::MOV, &[Operand::RegDeref(RegSpec::rax()), Operand::RegDeref(RegSpec::rcx())]),
+        Err(SynthesizeError::TooManyMemoryOperands),
+    );
+    assert_eq!(
+        Instruction::synthesize(Opcode::MOV, &[Operand::Register(RegSpec::rax()), Operand::ImmediateU64(1)]),
+        Err(SynthesizeError::UnsupportedOperand),
+    );
+}
+
+#[test]
+fn register_aliases() {
+    assert_eq!(RegSpec::al().widest(), RegSpec::rax());
+    assert_eq!(RegSpec::bh().widest(), RegSpec::rbx());
+    assert_eq!(RegSpec::r9w().widest(), RegSpec::r9());
+    assert_eq!(RegSpec::xmm(3).widest(), RegSpec::zmm(3));
+    assert_eq!(RegSpec::eip().widest(), RegSpec::rip());
+    assert_eq!(RegSpec::es().widest(), RegSpec::es());
+
+    assert_eq!(RegSpec::rax().narrow_to(1), Some(RegSpec::al()));
+    assert_eq!(RegSpec::rsp().narrow_to(1), Some(RegSpec::spl()));
+    assert_eq!(RegSpec::r10().narrow_to(4), Some(RegSpec::r10d()));
+    assert_eq!(RegSpec::zmm(3).narrow_to(16), Some(RegSpec::xmm(3)));
+    assert_eq!(RegSpec::eax().narrow_to(4), Some(RegSpec::eax()));
+    assert_eq!(RegSpec::eax().narrow_to(8), None);
+    assert_eq!(RegSpec::mask(1).narrow_to(1), None);
+
+    assert!(RegSpec::al().overlaps(&RegSpec::rax()));
+    assert!(RegSpec::ah().overlaps(&RegSpec::ax()));
+    assert!(!RegSpec::ah().overlaps(&RegSpec::al()));
+    assert!(!RegSpec::eax().overlaps(&RegSpec::ecx()));
+    assert!(RegSpec::ymm(1).overlaps(&RegSpec::xmm(1)));
+    assert!(!RegSpec::ymm(1).overlaps(&RegSpec::xmm(2)));
+    // `al` spelled with and without a rex prefix.
+    assert!(RegSpec::rb(0).overlaps(&RegSpec::al()));
+    assert!(RegSpec::fs().overlaps(&RegSpec::fs()));
+    assert!(!RegSpec::fs().overlaps(&RegSpec::gs()));
+
+    let aliases: Vec<RegSpec> = RegSpec::eax().aliases().collect();
+    assert_eq!(aliases, [RegSpec::rax(), RegSpec::ax(), RegSpec::al(), RegSpec::ah()]);
+    let aliases: Vec<RegSpec> = RegSpec::ah().aliases().collect();
+    assert_eq!(aliases, [RegSpec::rax(), RegSpec::eax(), RegSpec::ax()]);
+    let aliases: Vec<RegSpec> = RegSpec::sil().aliases().collect();
+    assert_eq!(aliases, [RegSpec::rsi(), RegSpec::esi(), RegSpec::si()]);
+    assert_eq!(RegSpec::rflags().aliases().collect::<Vec<_>>(), [RegSpec::eflags()]);
+    assert_eq!(RegSpec::mask(1).aliases().count(), 0);
+}
+
+#[test]
+fn vector_shapes() {
+    fn shapes(data: &[u8]) -> Vec<Option<(ElementType, u8)>> {
+        let instr = InstDecoder::default().decode_slice(data).unwrap();
+        (0..instr.operand_count()).map(|i| {
+            instr.operand_vector_shape(i).map(|shape| (shape.element, shape.count))
+        }).collect()
+    }
+
+    // pshufb xmm0, xmm1
+    let instr = InstDecoder::default().decode_slice(&[0x66, 0x0f, 0x38, 0x00, 0xc1]).unwrap();
+    assert_eq!(instr.vector_shape(), Some(VectorShape { element: ElementType::U8, count: 16 }));
+    assert_eq!(instr.vector_shape().unwrap().bits(), 128);
+    // vaddpd ymm0, ymm1, ymm2
+    let instr = InstDecoder::default().decode_slice(&[0xc5, 0xf5, 0x58, 0xc2]).unwrap();
+    assert_eq!(instr.vector_shape(), Some(VectorShape { element: ElementType::F64, count: 4 }));
+    // add eax, ecx
+    let instr = InstDecoder::default().decode_slice(&[0x01, 0xc8]).unwrap();
+    assert_eq!(instr.vector_shape(), None);
+
+    // addss xmm0, xmm1
+    assert_eq!(shapes(&[0xf3, 0x0f, 0x58, 0xc1]), vec![Some((ElementType::F32, 1)), Some((ElementType::F32, 1))]);
+    // pand xmm0, xmm1
+    assert_eq!(shapes(&[0x66, 0x0f, 0xdb, 0xc1]), vec![Some((ElementType::U64, 2)), Some((ElementType::U64, 2))]);
+    // pcmpgtb mm0, mm1
+    assert_eq!(shapes(&[0x0f, 0x64, 0xc1]), vec![Some((ElementType::I8, 8)), Some((ElementType::I8, 8))]);
+    // cvtdq2pd xmm0, xmm1
+    assert_eq!(shapes(&[0xf3, 0x0f, 0xe6, 0xc1]), vec![Some((ElementType::F64, 2)), Some((ElementType::I32, 2))]);
+    // cvtsi2sd xmm0, rax
+    assert_eq!(shapes(&[0xf2, 0x48, 0x0f, 0x2a, 0xc0]), vec![Some((ElementType::F64, 1)), None]);
+    // pmaddwd xmm0, xmm1
+    assert_eq!(shapes(&[0x66, 0x0f, 0xf5, 0xc1]), vec![Some((ElementType::I32, 4)), Some((ElementType::I16, 8))]);
+    // psllw xmm0, xmm1
+    assert_eq!(shapes(&[0x66, 0x0f, 0xf1, 0xc1]), vec![Some((ElementType::U16, 8)), Some((ElementType::U64, 1))]);
+    // vpbroadcastd ymm0, xmm1
+    assert_eq!(shapes(&[0xc4, 0xe2, 0x7d, 0x58, 0xc1]), vec![Some((ElementType::U32, 8)), Some((ElementType::U32, 1))]);
+    // vaddps zmm0, zmm1, dword [rax]{1to16}
+    assert_eq!(shapes(&[0x62, 0xf1, 0x74, 0x58, 0x58, 0x00]), vec![Some((ElementType::F32, 16)), Some((ElementType::F32, 16)), Some((ElementType::F32, 1))]);
+}
+
+#[test]
+fn evex_compressed_displacements() {
+    fn disp_info(data: &[u8]) -> Option<EvexDispInfo> {
+        InstDecoder::default().decode_slice(data).unwrap().evex_disp_info()
+    }
+
+    // vaddps zmm0, zmm1, zmmword [rax + 0x40]
+    assert_eq!(disp_info(&[0x62, 0xf1, 0x74, 0x48, 0x58, 0x40, 0x01]), Some(EvexDispInfo { disp8: 1, scale: 64, displacement: 0x40 }));
+    // vaddps zmm0, zmm1, dword [rax - 0x8]{1to16}
+    assert_eq!(disp_info(&[0x62, 0xf1, 0x74, 0x58, 0x58, 0x40, 0xfe]), Some(EvexDispInfo { disp8: -2, scale: 4, displacement: -8 }));
+    // vpexpandd zmm0, zmmword [rax + 0xc], scaled by the element size
+    assert_eq!(disp_info(&[0x62, 0xf2, 0x7d, 0x48, 0x89, 0x40, 0x03]), Some(EvexDispInfo { disp8: 3, scale: 4, displacement: 0xc }));
+    // vaddps zmm0, zmm1, zmmword [rax + 0x100], with a 32-bit displacement
+    assert_eq!(disp_info(&[0x62, 0xf1, 0x74, 0x48, 0x58, 0x80, 0x00, 0x01, 0x00, 0x00]), None);
+    // vaddps ymm0, ymm1, ymmword [rax + 0x1], vex-encoded
+    assert_eq!(disp_info(&[0xc5, 0xf4, 0x58, 0x40, 0x01]), None);
+}
+
+#[test]
+fn register_class_members() {
+    let xmms: Vec<RegSpec> = register_class::X.registers().collect();
+    assert_eq!(xmms.len(), 32);
+    assert_eq!(xmms[0], RegSpec::xmm0());
+    assert!(xmms.iter().all(|reg| reg.class() == register_class::X));
+    assert_eq!(register_class::K.registers().count(), 8);
+    assert_eq!(register_class::B.registers().last(), Some(RegSpec::bh()));
+    assert_eq!(register_class::RIP.registers().collect::<Vec<_>>(), vec![RegSpec::rip()]);
+}
+
+#[test]
+fn operand_constraints() {
+    fn constraints(data: &[u8]) -> Vec<Option<OperandConstraint>> {
+        let instr = InstDecoder::default().decode_slice(data).unwrap();
+        (0..4).map(|i| instr.operand_constraint(i)).collect()
+    }
+
+    // add eax, dword [rcx]
+    assert_eq!(constraints(&[0x03, 0x01]), vec![Some(OperandConstraint::Register(register_class::D)), Some(OperandConstraint::Memory), None, None]);
+    // add al, 0x12
+    assert_eq!(constraints(&[0x04, 0x12]), vec![Some(OperandConstraint::Register(register_class::B)), Some(OperandConstraint::Immediate(1)), None, None]);
+    // vpcmpeqd k1, zmm0, zmm1
+    let instr = InstDecoder::default().decode_slice(&[0x62, 0xf1, 0x7d, 0x48, 0x76, 0xc9]).unwrap();
+    let mask = instr.operand_constraint(0).unwrap();
+    assert_eq!(mask, OperandConstraint::Register(register_class::K));
+    assert!(mask.allows(&Operand::Register(RegSpec::mask(2))));
+    assert!(!mask.allows(&Operand::Register(RegSpec::xmm0())));
+    assert!(!mask.allows(&Operand::RegDeref(RegSpec::rax())));
+    assert!(OperandConstraint::Immediate(1).allows(&Operand::ImmediateU8(3)));
+    assert!(!OperandConstraint::Immediate(1).allows(&Operand::ImmediateI32(3)));
+}
+
+#[test]
+fn xsave_layouts() {
+    let decoder = InstDecoder::default();
+    // x87, sse, avx, and avx512 state
+    let mask = 0b1110_0111;
+
+    // xsave [rdi]
+    let instr = decoder.decode_slice(&[0x0f, 0xae, 0x27]).unwrap();
+    assert_eq!(instr.xsave_format(), Some(XsaveFormat::Standard));
+    assert_eq!(instr.xsave_area_size(0b111), Some(832));
+    assert_eq!(instr.xsave_area_size(mask), Some(2688));
+    assert_eq!(instr.xsave_layout(mask).unwrap().offset(XsaveComponent::Opmask), Some(1088));
+    // supervisor state is not saved by `xsave`.
+    assert_eq!(instr.xsave_layout(mask | 1 << 15).unwrap().mask(), mask);
+
+    // xsavec [rdi]
+    let instr = decoder.decode_slice(&[0x0f, 0xc7, 0x27]).unwrap();
+    let layout = instr.xsave_layout(mask).unwrap();
+    assert_eq!(layout.offset(XsaveComponent::Sse), Some(160));
+    assert_eq!(layout.offset(XsaveComponent::Opmask), Some(832));
+    assert_eq!(layout.offset(XsaveComponent::BndRegs), None);
+    assert_eq!(layout.size(), Some(2432));
+    assert_eq!(layout.components().collect::<Vec<_>>(), vec![
+        XsaveComponent::X87, XsaveComponent::Sse, XsaveComponent::Avx, XsaveComponent::Opmask,
+        XsaveComponent::ZmmHi256, XsaveComponent::Hi16Zmm,
+    ]);
+
+    // xsaves [rdi], with architectural lbr state of no fixed size
+    let instr = decoder.decode_slice(&[0x0f, 0xc7, 0x2f]).unwrap();
+    assert_eq!(instr.xsave_format(), Some(XsaveFormat::CompactedSupervisor));
+    assert_eq!(instr.xsave_area_size(mask | 1 << 15), None);
+    assert_eq!(instr.xsave_area_size(0b11 | 1 << 8), Some(704));
+
+    // fxsave [rdi]
+    let instr = decoder.decode_slice(&[0x0f, 0xae, 0x07]).unwrap();
+    assert_eq!(instr.xsave_area_size(mask), Some(512));
+
+    // add eax, dword [rcx]
+    let instr = decoder.decode_slice(&[0x03, 0x01]).unwrap();
+    assert_eq!(instr.xsave_layout(mask), None);
+}
+
+#[test]
+fn lock_validity() {
+    use yaxpeax_x86::long_mode::DecodeError;
+
+    let strict = InstDecoder::default();
+    let permissive = InstDecoder::default().with_permissive_lock();
+    assert!(!strict.permissive_lock());
+
+    // lock add dword [rax], ecx
+    let instr = strict.decode_slice(&[0xf0, 0x01, 0x08]).unwrap();
+    assert!(instr.lock_valid());
+    // add eax, ecx
+    assert!(strict.decode_slice(&[0x01, 0xc8]).unwrap().lock_valid());
+
+    // lock add eax, ecx: a register destination
+    assert_eq!(strict.decode_slice(&[0xf0, 0x01, 0xc8]), Err(DecodeError::InvalidPrefixes));
+    let instr = permissive.decode_slice(&[0xf0, 0x01, 0xc8]).unwrap();
+    assert!(instr.prefixes().lock);
+    assert!(!instr.lock_valid());
+
+    // lock mov dword [rax], ecx: `mov` cannot be locked
+    assert_eq!(strict.decode_slice(&[0xf0, 0x89, 0x08]), Err(DecodeError::InvalidPrefixes));
+    assert!(!permissive.decode_slice(&[0xf0, 0x89, 0x08]).unwrap().lock_valid());
+}
+
+#[test]
+fn length_validity() {
+    use yaxpeax_arch::{LengthedInstruction, U8Reader};
+    use yaxpeax_x86::long_mode::DecodeError;
+
+    let strict = InstDecoder::default();
+    let permissive = InstDecoder::default().with_permissive_length().with_retain_bytes();
+    assert!(!strict.permissive_length());
+
+    // fourteen `66` prefixes on `add eax, ecx`, as anti-disassembly padding might write.
+    let mut padded = vec![0x66; 14];
+    padded.extend_from_slice(&[0x01, 0xc8]);
+    assert_eq!(strict.decode_slice(&padded), Err(DecodeError::TooLong));
+    let instr = permissive.decode_slice(&padded).unwrap();
+    assert_eq!(instr.to_string(), "add ax, cx");
+    assert_eq!(instr.len().to_const(), 16);
+    assert!(!instr.length_valid());
+    // the bytes of an overlong instruction are not retained.
+    assert_eq!(instr.bytes(), None);
+    assert_eq!(permissive.length_of(&mut U8Reader::new(&padded)), Ok(16));
+    assert_eq!(permissive.summarize(&padded).unwrap().opcode(), 0x01);
+
+    let instr = permissive.decode_slice(&padded[2..]).unwrap();
+    assert!(instr.length_valid());
+    assert_eq!(instr.bytes().map(|bytes| bytes.len()), Some(14));
+
+    // a run of prefixes that never ends is still rejected.
+    assert_eq!(permissive.decode_slice(&[0x66; 300]), Err(DecodeError::TooLong));
+}
+
+#[test]
+fn condition_codes() {
+    use yaxpeax_x86::long_mode::ConditionCode;
+
+    let decoder = InstDecoder::default();
+    // jz $+2
+    let instr = decoder.decode_slice(&[0x74, 0x00]).unwrap();
+    assert_eq!(instr.condition(), Some(ConditionCode::Z));
+    assert_eq!(instr.condition().unwrap().invert(), ConditionCode::NZ);
+    // cmovle eax, ecx
+    assert_eq!(decoder.decode_slice(&[0x0f, 0x4e, 0xc1]).unwrap().condition(), Some(ConditionCode::LE));
+    // seta al
+    assert_eq!(decoder.decode_slice(&[0x0f, 0x97, 0xc0]).unwrap().condition(), Some(ConditionCode::A));
+    // fcmovu st(0), st(1)
+    assert_eq!(decoder.decode_slice(&[0xda, 0xd9]).unwrap().condition(), Some(ConditionCode::P));
+    // add eax, ecx
+    assert_eq!(decoder.decode_slice(&[0x01, 0xc8]).unwrap().condition(), None);
+
+    for cc in [ConditionCode::O, ConditionCode::B, ConditionCode::Z, ConditionCode::A, ConditionCode::S, ConditionCode::P, ConditionCode::L, ConditionCode::G].iter() {
+        assert_ne!(cc.invert(), *cc);
+        assert_eq!(cc.invert().invert(), *cc);
+    }
+}
+
+#[test]
+fn decode_matrices() {
+    let configs = [InstDecoder::minimal(), InstDecoder::minimal().with_bmi1(), InstDecoder::default()];
+
+    // andn eax, ecx, edx: only decodes with bmi1
+    let matrix = InstDecoder::decode_matrix(&[0xc4, 0xe2, 0x70, 0xf2, 0xc2], &configs);
+    assert!(matrix.is_feature_dependent());
+    assert!(!matrix.is_uniform());
+    assert!(matrix.rejected_by().eq([&configs[0]].iter().cloned()));
+    assert_eq!(matrix.accepted_by().count(), 2);
+    assert_eq!(matrix.get(1).unwrap().unwrap().opcode(), yaxpeax_x86::long_mode::Opcode::ANDN);
+    assert!(matrix.get(3).is_none());
+
+    // add eax, ecx: decodes everywhere
+    let matrix = InstDecoder::decode_matrix(&[0x01, 0xc8], &configs);
+    assert!(matrix.is_uniform());
+    assert!(!matrix.is_feature_dependent());
+    assert_eq!(matrix.iter().count(), 3);
+}
+
+#[test]
+fn invalid_bytes() {
+    use yaxpeax_x86::long_mode::{DecodeError, Opcode};
+
+    let decoder = InstDecoder::default().with_emit_invalid_bytes();
+    // push es is not valid in 64-bit mode
+    assert!(InstDecoder::default().decode_slice(&[0x06]).is_err());
+    let instr = decoder.decode_slice(&[0x06, 0x90]).unwrap();
+    assert_eq!(instr.opcode(), Opcode::Invalid);
+    assert_eq!(instr.invalid_byte(), Some(0x06));
+    assert_eq!(instr.bytes(), Some(&[0x06][..]));
+    assert_eq!(instr.to_string(), "invalid 0x6");
+
+    // an overlong run of prefixes is reported a byte at a time, too
+    assert_eq!(decoder.decode_slice(&[0x66; 16]).unwrap().invalid_byte(), Some(0x66));
+
+    // valid instructions are unchanged, and truncated ones are still errors
+    let instr = decoder.decode_slice(&[0x0f, 0x0b]).unwrap();
+    assert_eq!(instr.opcode(), Opcode::UD2);
+    assert_eq!(instr.invalid_byte(), None);
+    assert_eq!(decoder.decode_slice(&[0x0f]), Err(DecodeError::ExhaustedInput));
+}
+
+#[test]
+fn far_pointers_and_pseudo_descriptors() {
+    use yaxpeax_x86::long_mode::{DescriptorTable, FarPointer, PseudoDescriptor};
+
+    let decoder = InstDecoder::default();
+    let instr = decoder.decode_slice(&[0x48, 0xff, 0x28]).unwrap();
+    assert_eq!(instr.far_pointer(), Some(FarPointer::Memory { address: Operand::RegDeref(RegSpec::rax()), offset_width: 8 }));
+    assert_eq!(decoder.decode_slice(&[0xff, 0x20]).unwrap().far_pointer(), None);
+
+    let instr = decoder.decode_slice(&[0x0f, 0x01, 0x18]).unwrap();
+    assert_eq!(instr.pseudo_descriptor(), Some(PseudoDescriptor {
+        table: DescriptorTable::Interrupt,
+        access: AccessKind::Read,
+        address: Operand::RegDeref(RegSpec::rax()),
+        base_width: 8,
+    }));
+    let instr = decoder.decode_slice(&[0x0f, 0x01, 0x00]).unwrap();
+    assert_eq!(instr.pseudo_descriptor().unwrap().access, AccessKind::Write);
+}
+
+#[test]
+fn stack_deltas() {
+    let decoder = InstDecoder::default();
+    let delta = |bytes: &[u8]| decoder.decode_slice(bytes).unwrap().stack_delta();
+
+    assert_eq!(delta(&[0x50]), Some(-8));
+    assert_eq!(delta(&[0x66, 0x50]), Some(-2));
+    assert_eq!(delta(&[0x58]), Some(8));
+    assert_eq!(delta(&[0x9c]), Some(-8));
+    assert_eq!(delta(&[0xe8, 0x00, 0x00, 0x00, 0x00]), Some(-8));
+    assert_eq!(delta(&[0xff, 0xd0]), Some(-8));
+    assert_eq!(delta(&[0xc3]), Some(8));
+    assert_eq!(delta(&[0xc2, 0x10, 0x00]), Some(0x18));
+    // `66` does not shrink the return address of a near `call` or `ret`.
+    assert_eq!(delta(&[0x66, 0xe8, 0x00, 0x00, 0x00, 0x00]), Some(-8));
+    assert_eq!(delta(&[0x66, 0xc3]), Some(8));
+    assert_eq!(delta(&[0x66, 0xc2, 0x10, 0x00]), Some(0x18));
+    assert_eq!(delta(&[0xc8, 0x20, 0x00, 0x02]), Some(-(8 * 3 + 0x20)));
+    assert_eq!(delta(&[0x48, 0x83, 0xec, 0x28]), Some(-0x28));
+    assert_eq!(delta(&[0x48, 0x81, 0xc4, 0x00, 0x01, 0x00, 0x00]), Some(0x100));
+    assert_eq!(delta(&[0x48, 0x8d, 0x64, 0x24, 0xf0]), Some(-0x10));
+    // instructions that leave the stack pointer alone.
+    assert_eq!(delta(&[0x48, 0x89, 0xe5]), Some(0));
+    assert_eq!(delta(&[0x48, 0x83, 0xc0, 0x28]), Some(0));
+
+    // the new stack pointer is loaded from somewhere, rather than moved.
+    assert_eq!(delta(&[0xc9]), None);
+    assert_eq!(delta(&[0x5c]), None);
+    assert_eq!(delta(&[0x48, 0x89, 0xec]), None);
+    assert_eq!(delta(&[0x48, 0x83, 0xe4, 0xf0]), None);
+    assert_eq!(delta(&[0x48, 0x01, 0xc4]), None);
+    assert_eq!(delta(&[0xcd, 0x80]), None);
+    assert_eq!(delta(&[0x48, 0xcf]), None);
+}
+
+#[test]
+fn addressing_forms() {
+    use yaxpeax_x86::long_mode::AddressingForm;
+
+    let decoder = InstDecoder::default();
+    let form = |bytes: &[u8]| {
+        let instr = decoder.decode_slice(bytes).unwrap();
+        (instr.operand(1).addressing_form(), instr.addressing_form())
+    };
+    assert_eq!(form(&[0x8b, 0x04, 0x98]), (Some(AddressingForm::Bits64), AddressingForm::Bits64));
+    assert_eq!(form(&[0x67, 0x8b, 0x04, 0x98]), (Some(AddressingForm::Bits32), AddressingForm::Bits32));
+    assert_eq!(form(&[0x8b, 0x05, 0x78, 0x56, 0x34, 0x12]), (Some(AddressingForm::Bits64), AddressingForm::Bits64));
+    assert_eq!(form(&[0x67, 0x8b, 0x05, 0x78, 0x56, 0x34, 0x12]), (Some(AddressingForm::Bits32), AddressingForm::Bits32));
+    assert_eq!(form(&[0xa1, 1, 2, 3, 4, 5, 6, 7, 8]), (Some(AddressingForm::Bits64), AddressingForm::Bits64));
+    // a bare 32-bit displacement is ambiguous without the instruction.
+    assert_eq!(form(&[0x8b, 0x04, 0x25, 0x78, 0x56, 0x34, 0x12]), (None, AddressingForm::Bits64));
+    assert_eq!(form(&[0x67, 0xa1, 0x78, 0x56, 0x34, 0x12]), (None, AddressingForm::Bits32));
+    assert_eq!(Operand::Register(RegSpec::rax()).addressing_form(), None);
+}
+
+#[test]
+fn immediate_values() {
+    fn imm(data: &[u8], i: u8) -> ImmValue {
+        InstDecoder::default().decode_slice(data).unwrap().immediate_value(i).unwrap()
+    }
+
+    // `add rax, -1` by imm8 and by imm32: both sign-extend to all 64 bits.
+    let short = imm(&[0x48, 0x83, 0xc0, 0xff], 1);
+    assert_eq!((short.size(), short.extension()), (1, ImmExtension::Sign));
+    assert_eq!(short.value(), u64::MAX);
+    let long = imm(&[0x48, 0x05, 0xff, 0xff, 0xff, 0xff], 1);
+    assert_eq!((long.size(), long.value()), (4, u64::MAX));
+    // the operand alone reports the width it was extended to.
+    let operand = InstDecoder::default().decode_slice(&[0x48, 0x05, 0xff, 0xff, 0xff, 0xff]).unwrap().operand(1);
+    assert_eq!(operand, Operand::ImmediateI64(-1));
+    assert_eq!(operand.immediate_value().unwrap().size(), 8);
+
+    // `add eax, -1`: the extended value is only `0xffffffff` at the instruction's operand size.
+    assert_eq!(imm(&[0x83, 0xc0, 0xff], 1).value_for_size(4), 0xffff_ffff);
+    assert_eq!(imm(&[0x66, 0x83, 0xc0, 0xff], 1).value_for_size(2), 0xffff);
+
+    // `mov rax, imm64` encodes all eight bytes.
+    let movabs = imm(&[0x48, 0xb8, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x88], 1);
+    assert_eq!((movabs.size(), movabs.value()), (8, 0x8807060504030201));
+
+    // `int 0x80`, `mov al, 0xff`, and `enter 0x10, 0xff` zero-extend.
+    assert_eq!(imm(&[0xcd, 0x80], 0), ImmValue::new(1, ImmExtension::Zero, 0x80));
+    assert_eq!(imm(&[0xb0, 0xff], 1).signed(), 0xff);
+    assert_eq!(imm(&[0xc8, 0x10, 0x00, 0xff], 0), ImmValue::new(2, ImmExtension::Zero, 0x10));
+    assert_eq!(imm(&[0xc8, 0x10, 0x00, 0xff], 1).value(), 0xff);
+
+    assert_eq!(Operand::Register(RegSpec::rax()).immediate_value(), None);
+    assert_eq!(InstDecoder::default().decode_slice(&[0x90]).unwrap().immediate_value(0), None);
+}
+
+#[test]
+fn hle_and_rtm() {
+    use yaxpeax_x86::long_mode::{HleHint, RtmEffect};
+
+    fn decode(data: &[u8]) -> yaxpeax_x86::long_mode::Instruction {
+        InstDecoder::default().decode_slice(data).unwrap()
+    }
+
+    // xacquire lock add dword [rax], eax; xacquire xchg dword [rax], eax
+    assert_eq!(decode(&[0xf2, 0xf0, 0x01, 0x00]).hle_hint(), Some(HleHint::Acquire));
+    assert_eq!(decode(&[0xf2, 0x87, 0x00]).hle_operand(), Some(0));
+    // xrelease mov dword [rax], eax; xrelease mov byte [rbx], 0x0
+    assert_eq!(decode(&[0xf3, 0x89, 0x00]).hle_hint(), Some(HleHint::Release));
+    assert_eq!(decode(&[0xf3, 0xc6, 0x03, 0x00]).hle_operand(), Some(0));
+    // `f3` on `mov` to a register, and `f2` on `mov` at all, are not hints.
+    assert_eq!(decode(&[0xf3, 0xb8, 0x01, 0x00, 0x00, 0x00]).hle_hint(), None);
+    assert_eq!(decode(&[0xf3, 0x8b, 0x00]).hle_hint(), None);
+    assert_eq!(decode(&[0xf2, 0x89, 0x00]).hle_operand(), None);
+
+    let xbegin = decode(&[0xc7, 0xf8, 0x10, 0x00, 0x00, 0x00]);
+    assert_eq!(xbegin.rtm_effect(), Some(RtmEffect::Begin));
+    assert_eq!(xbegin.xbegin_fallback(0x1000), Some(0x1016));
+    // with a 16-bit operand size, the offset is 16 bits.
+    let xbegin = decode(&[0x66, 0xc7, 0xf8, 0xf0, 0xff]);
+    assert_eq!(xbegin.operand(0), Operand::ImmediateI16(-0x10));
+    assert_eq!(xbegin.immediate_value(0).unwrap().size(), 2);
+    assert_eq!(xbegin.xbegin_fallback(0x1000), Some(0xff5));
+    assert_eq!(decode(&[0xc6, 0xf8, 0xff]).rtm_effect(), Some(RtmEffect::Abort(0xff)));
+    assert_eq!(decode(&[0x0f, 0x01, 0xd5]).rtm_effect(), Some(RtmEffect::End));
+    assert_eq!(decode(&[0x0f, 0x01, 0xd6]).rtm_effect(), Some(RtmEffect::Test));
+    assert_eq!(decode(&[0x90]).rtm_effect(), None);
+    assert_eq!(decode(&[0x90]).xbegin_fallback(0x1000), None);
+}
+
+#[test]
+fn branch_hints() {
+    use yaxpeax_x86::long_mode::BranchHint;
+
+    fn decode(data: &[u8]) -> yaxpeax_x86::long_mode::Instruction {
+        InstDecoder::default().decode_slice(data).unwrap()
+    }
+
+    // `3e` and `2e` prefixes on `jz` and `jnz` rel32 are hints, and the last one counts.
+    assert_eq!(decode(&[0x3e, 0x74, 0x05]).branch_hint(), Some(BranchHint::Taken));
+    assert_eq!(decode(&[0x2e, 0x0f, 0x85, 0x00, 0x01, 0x00, 0x00]).branch_hint(), Some(BranchHint::NotTaken));
+    assert_eq!(decode(&[0x2e, 0x3e, 0x74, 0x05]).branch_hint(), Some(BranchHint::Taken));
+    assert_eq!(decode(&[0x74, 0x05]).branch_hint(), None);
+    // other segment prefixes are not hints, and neither are hint prefixes on other instructions.
+    assert_eq!(decode(&[0x64, 0x74, 0x05]).branch_hint(), None);
+    assert_eq!(decode(&[0x3e, 0xeb, 0x05]).branch_hint(), None);
+    assert_eq!(decode(&[0x3e, 0xff, 0xe0]).branch_hint(), None);
+    assert_eq!(decode(&[0x2e, 0x0f, 0x44, 0xc1]).branch_hint(), None);
+    assert_eq!(decode(&[0x2e, 0xe3, 0x05]).branch_hint(), None);
+}
+
+#[test]
+fn defs_and_uses() {
+    fn decode(data: &[u8]) -> yaxpeax_x86::long_mode::Instruction {
+        InstDecoder::default().decode_slice(data).unwrap()
+    }
+
+    // 32-bit writes define the whole 64-bit register, narrower writes only what they name.
+    let mov = decode(&[0xb8, 0x01, 0x00, 0x00, 0x00]);
+    assert_eq!(mov.defs().as_slice(), &[RegSpec::rax()]);
+    assert!(mov.uses().is_empty());
+    assert_eq!(decode(&[0xb0, 0x01]).defs().as_slice(), &[RegSpec::al()]);
+
+    // add rax, rbx
+    let add = decode(&[0x48, 0x01, 0xd8]);
+    assert_eq!(add.defs().as_slice(), &[RegSpec::rax(), RegSpec::rflags()]);
+    assert_eq!(add.uses().as_slice(), &[RegSpec::rax(), RegSpec::rbx()]);
+    // mov rax, qword [rax + rcx * 4]
+    assert_eq!(decode(&[0x48, 0x8b, 0x04, 0x88]).uses().as_slice(), &[RegSpec::rax(), RegSpec::rcx()]);
+    // mov dword [rax], eax
+    let store = decode(&[0x89, 0x00]);
+    assert!(store.defs().is_empty());
+    assert_eq!(store.uses().as_slice(), &[RegSpec::rax(), RegSpec::eax()]);
+
+    // implicit registers: push rax, mul ecx, cpuid
+    let push = decode(&[0x50]);
+    assert_eq!(push.defs().as_slice(), &[RegSpec::rsp()]);
+    assert_eq!(push.uses().as_slice(), &[RegSpec::rax(), RegSpec::rsp()]);
+    let mul = decode(&[0xf7, 0xe1]);
+    assert_eq!(mul.defs().as_slice(), &[RegSpec::rax(), RegSpec::rdx(), RegSpec::rflags()]);
+    assert_eq!(mul.uses().as_slice(), &[RegSpec::ecx(), RegSpec::eax()]);
+    let cpuid = decode(&[0x0f, 0xa2]);
+    assert_eq!(cpuid.defs().len(), 4);
+    assert!(cpuid.uses().contains(RegSpec::eax()) && cpuid.uses().contains(RegSpec::ecx()));
+
+    // a conditional move may keep the old value, so its destination is used as well.
+    let cmov = decode(&[0x0f, 0x44, 0xc1]);
+    assert_eq!(cmov.defs().as_slice(), &[RegSpec::rax()]);
+    assert_eq!(cmov.uses().as_slice(), &[RegSpec::eax(), RegSpec::ecx(), RegSpec::rflags()]);
+    assert_eq!(decode(&[0x74, 0x05]).uses().as_slice(), &[RegSpec::rflags()]);
+
+    // legacy sse writes leave the upper bits of a vector register; vex and evex writes clear them.
+    assert_eq!(decode(&[0x0f, 0x58, 0xc1]).defs().as_slice(), &[RegSpec::xmm(0)]);
+    assert_eq!(decode(&[0xc5, 0xf0, 0x58, 0xc2]).defs().as_slice(), &[RegSpec::zmm(0)]);
+    // vaddps zmm0{k1}, zmm1, zmm2 merges into zmm0; with {z}, it does not.
+    let merge = decode(&[0x62, 0xf1, 0x74, 0x49, 0x58, 0xc2]);
+    assert_eq!(merge.uses().as_slice(), &[RegSpec::zmm(0), RegSpec::zmm(1), RegSpec::zmm(2), RegSpec::mask(1)]);
+    let zero = decode(&[0x62, 0xf1, 0x74, 0xc9, 0x58, 0xc2]);
+    assert_eq!(zero.defs().as_slice(), &[RegSpec::zmm(0)]);
+    assert_eq!(zero.uses().as_slice(), &[RegSpec::zmm(1), RegSpec::zmm(2), RegSpec::mask(1)]);
+}
+
+#[test]
+fn write_behaviors() {
+    use yaxpeax_x86::long_mode::WriteBehavior;
+
+    fn decode(data: &[u8]) -> yaxpeax_x86::long_mode::Instruction {
+        InstDecoder::default().decode_slice(data).unwrap()
+    }
+
+    // mov rax, 1; mov eax, 1; mov ax, 1; mov ah, 1
+    assert_eq!(decode(&[0x48, 0xc7, 0xc0, 0x01, 0x00, 0x00, 0x00]).write_behavior(0), Some(WriteBehavior::Full));
+    assert_eq!(decode(&[0xb8, 0x01, 0x00, 0x00, 0x00]).write_behavior(0), Some(WriteBehavior::ZeroExtend));
+    assert_eq!(decode(&[0x66, 0xb8, 0x01, 0x00]).write_behavior(0), Some(WriteBehavior::Merge));
+    assert_eq!(decode(&[0xb4, 0x01]).write_behavior(0), Some(WriteBehavior::Merge));
+    // addps xmm0, xmm1; vaddps xmm0, xmm1, xmm2; vaddps zmm0, zmm1, zmm2
+    assert_eq!(decode(&[0x0f, 0x58, 0xc1]).write_behavior(0), Some(WriteBehavior::Merge));
+    assert_eq!(decode(&[0xc5, 0xf0, 0x58, 0xc2]).write_behavior(0), Some(WriteBehavior::ZeroExtend));
+    assert_eq!(decode(&[0x62, 0xf1, 0x74, 0x48, 0x58, 0xc2]).write_behavior(0), Some(WriteBehavior::Full));
+    // vaddps zmm0{k1}, zmm1, zmm2 keeps unselected elements; with {z}, it clears them.
+    assert_eq!(decode(&[0x62, 0xf1, 0x74, 0x49, 0x58, 0xc2]).write_behavior(0), Some(WriteBehavior::Merge));
+    assert_eq!(decode(&[0x62, 0xf1, 0x74, 0xc9, 0x58, 0xc2]).write_behavior(0), Some(WriteBehavior::Full));
+
+    // xchg writes both its operands; sources, memory, and missing operands are not written.
+    let xchg = decode(&[0x87, 0xca]);
+    assert_eq!(xchg.write_behavior(1), Some(WriteBehavior::ZeroExtend));
+    assert_eq!(decode(&[0x48, 0x01, 0xd8]).write_behavior(1), None);
+    assert_eq!(decode(&[0x89, 0x00]).write_behavior(0), None);
+    assert_eq!(decode(&[0x90]).write_behavior(0), None);
+}
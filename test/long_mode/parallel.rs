@@ -0,0 +1,63 @@
+use yaxpeax_x86::long_mode::{DecodeError, ErrorRecovery, InstDecoder, Instruction};
+
+// bytes that are mostly, but not entirely, real instructions: a function prologue, some moves and
+// arithmetic, an epilogue, then a few bytes of noise that put the next copy out of step.
+fn code(copies: usize) -> Vec<u8> {
+    let body: &[u8] = &[
+        0x55, 0x48, 0x89, 0xe5, 0x48, 0x83, 0xec, 0x20, 0x48, 0x8b, 0x45, 0xf8, 0x8b, 0x0c, 0x88,
+        0x48, 0x8d, 0x04, 0x49, 0x0f, 0xaf, 0xc1, 0xc5, 0xfd, 0xfe, 0x44, 0x24, 0x20, 0x48, 0x89,
+        0xec, 0x5d, 0xc3,
+    ];
+    let mut bytes = Vec::new();
+    let mut state = 0x2545f491u32;
+    for _ in 0..copies {
+        bytes.extend_from_slice(body);
+        for _ in 0..(state % 5) {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            bytes.push(state as u8);
+        }
+    }
+    bytes
+}
+
+fn serial(bytes: &[u8], recovery: ErrorRecovery) -> (Vec<(u64, Instruction)>, Result<(), DecodeError>) {
+    let mut out = Vec::new();
+    let res = InstDecoder::default().decode_all_with_recovery(bytes, 0x4000, recovery, &mut out);
+    (out, res)
+}
+
+#[test]
+fn parallel_matches_serial() {
+    let bytes = code(400);
+    for recovery in [ErrorRecovery::SkipByte, ErrorRecovery::EmitByte, ErrorRecovery::Stop] {
+        let expected = serial(&bytes, recovery);
+        for threads in [1, 2, 3, 7, 64, 1000] {
+            let mut out = Vec::new();
+            let res = InstDecoder::default().decode_all_parallel_with_threads(&bytes, 0x4000, recovery, threads, &mut out);
+            assert_eq!((out, res), expected, "{:?} across {} threads", recovery, threads);
+        }
+        let mut out = Vec::new();
+        let res = InstDecoder::default().decode_all_parallel(&bytes, 0x4000, recovery, &mut out);
+        assert_eq!((out, res), expected);
+    }
+}
+
+#[test]
+fn parallel_stops_at_first_error() {
+    // valid code, then an instruction invalid in 64-bit mode in the last chunk.
+    let mut bytes = vec![0x90; 1000];
+    bytes[900] = 0x06;
+    let mut out = Vec::new();
+    let res = InstDecoder::default().decode_all_parallel_with_threads(&bytes, 0, ErrorRecovery::Stop, 4, &mut out);
+    assert_eq!(res, Err(DecodeError::InvalidOpcode));
+    assert_eq!(out.len(), 900);
+    assert_eq!(out.last().map(|(address, _)| *address), Some(899));
+
+    // chunks that decode to nothing, and a buffer cut off mid-instruction.
+    let mut out = Vec::new();
+    let res = InstDecoder::default().decode_all_parallel_with_threads(&[0x48, 0x8b], 0, ErrorRecovery::Stop, 2, &mut out);
+    assert_eq!(res, Err(DecodeError::ExhaustedInput));
+    assert!(out.is_empty());
+}
@@ -0,0 +1,29 @@
+use yaxpeax_x86::long_mode::{InstDecoder, Opcode, PerfInfo, Uarch};
+
+#[test]
+fn test_perf_info() {
+    let add = Opcode::ADD.perf_info(Uarch::SkylakeClient).unwrap();
+    assert_eq!(add.latency, 1);
+    assert_eq!(add.reciprocal_throughput, 0.25);
+    // ports 0, 1, 5, and 6
+    assert_eq!(add.ports, 0b0110_0011);
+
+    // vector adds went from port 1 on haswell to ports 0 and 1 on skylake.
+    assert_eq!(
+        Opcode::VADDPS.perf_info(Uarch::Haswell),
+        Some(PerfInfo { latency: 3, reciprocal_throughput: 1.0, ports: 0b10 })
+    );
+    assert_eq!(
+        Opcode::VADDPS.perf_info(Uarch::SkylakeClient),
+        Some(PerfInfo { latency: 4, reciprocal_throughput: 0.5, ports: 0b11 })
+    );
+    // and are on `FP2` and `FP3` on zen 2.
+    assert_eq!(Opcode::VADDPS.perf_info(Uarch::Zen2).unwrap().ports, 0b1100_0000_0000);
+
+    let decoder = InstDecoder::default();
+    let inst = decoder.decode_slice(&[0x48, 0x0f, 0xaf, 0xc1]).unwrap();
+    assert_eq!(inst.opcode().perf_info(Uarch::Zen2).unwrap().latency, 3);
+
+    // most instructions have no data.
+    assert_eq!(Opcode::CPUID.perf_info(Uarch::SkylakeClient), None);
+}
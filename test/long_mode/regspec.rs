@@ -19,6 +19,12 @@ fn test_labels() {
     assert_eq!(RegSpec::rbp().name(), "rbp");
     assert_eq!(RegSpec::gs().name(), "gs");
     assert_eq!(RegSpec::al().name(), "al");
+    assert_eq!(RegSpec::r16().name(), "r16");
+    assert_eq!(RegSpec::r31d().name(), "r31d");
+    assert_eq!(RegSpec::w(20).name(), "r20w");
+    assert_eq!(RegSpec::rb(27).name(), "r27b");
+    assert_eq!(RegSpec::tmm(3).name(), "tmm3");
+    assert_eq!(RegSpec::bnd(2).name(), "bnd2");
 }
 
 #[test]
@@ -33,6 +39,8 @@ fn test_bank_names() {
     assert_eq!(RegSpec::rflags().class().name(), "rflags");
     assert_eq!(RegSpec::eip().class().name(), "eip");
     assert_eq!(RegSpec::rip().class().name(), "rip");
+    assert_eq!(RegSpec::tmm0().class().name(), "tmm");
+    assert_eq!(RegSpec::bnd0().class().name(), "bnd");
     assert_eq!(RegSpec::st0().class().name(), "x87-stack");
     assert_eq!(RegSpec::mm0().class().name(), "mmx");
     assert_eq!(RegSpec::xmm0().class().name(), "xmm");
@@ -0,0 +1,72 @@
+use yaxpeax_x86::long_mode::{InstDecoder, InstructionPattern, Opcode, Operand, OperandPattern, RegSpec};
+use yaxpeax_x86::pattern;
+
+// nop; call qword [rip + 0x10]; call $+0; mov eax, 0x15ff; add byte [rax], al; mov eax, 0x3c
+const CODE: &[u8] = &[
+    0x90,
+    0xff, 0x15, 0x10, 0x00, 0x00, 0x00,
+    0xe8, 0x00, 0x00, 0x00, 0x00,
+    0xb8, 0xff, 0x15, 0x00, 0x00,
+    0x00, 0x00,
+    0xb8, 0x3c, 0x00, 0x00, 0x00,
+];
+
+fn offsets(pattern: &InstructionPattern) -> Vec<usize> {
+    InstDecoder::default().scan(CODE, pattern).map(|(offset, _)| offset).collect()
+}
+
+#[test]
+fn scan_rip_relative_calls() {
+    let pattern = InstructionPattern::new(Opcode::CALL)
+        .with_operand(0, OperandPattern::MemoryBase(RegSpec::rip()));
+    // the second match starts inside the immediate of `mov eax, 0x15ff`.
+    assert_eq!(offsets(&pattern), vec![1, 13]);
+
+    let (_, instr) = InstDecoder::default().scan(CODE, &pattern).next().unwrap();
+    assert_eq!(instr.operand(0), Operand::RegDisp(RegSpec::rip(), 0x10));
+
+    assert_eq!(offsets(&InstructionPattern::new(Opcode::CALL)), vec![1, 7, 13]);
+}
+
+#[test]
+fn scan_operand_constraints() {
+    let pattern = InstructionPattern::new(Opcode::MOV)
+        .with_operand(0, OperandPattern::Register(RegSpec::eax()))
+        .with_operand(1, OperandPattern::Immediate(0x3c));
+    assert_eq!(offsets(&pattern), vec![19]);
+
+    let pattern = InstructionPattern::new(Opcode::MOV)
+        .with_operand(1, OperandPattern::AnyImmediate);
+    assert_eq!(offsets(&pattern), vec![12, 19]);
+
+    // `nop` has no operands to match.
+    let pattern = InstructionPattern::new(Opcode::NOP)
+        .with_operand(0, OperandPattern::AnyRegister);
+    assert_eq!(offsets(&pattern), Vec::<usize>::new());
+    assert!(InstructionPattern::new(Opcode::NOP).matches(&InstDecoder::default().decode_slice(&[0x90]).unwrap()));
+}
+
+#[test]
+fn pattern_macro() {
+    let decoder = InstDecoder::default();
+    // sub rsp, 0x28
+    let sub_rsp = decoder.decode_slice(&[0x48, 0x83, 0xec, 0x28]).unwrap();
+    // mov rax, qword fs:[0x28]
+    let load_canary = decoder.decode_slice(&[0x64, 0x48, 0x8b, 0x04, 0x25, 0x28, 0x00, 0x00, 0x00]).unwrap();
+
+    assert!(sub_rsp.matches(&pattern!(Opcode::SUB)));
+    assert!(sub_rsp.matches(&pattern!(Opcode::SUB, RegSpec::rsp(), OperandPattern::AnyImmediate)));
+    assert!(sub_rsp.matches(&pattern!(Opcode::SUB, RegSpec::rsp(), OperandPattern::Immediate(0x28),)));
+    assert!(!sub_rsp.matches(&pattern!(Opcode::SUB, RegSpec::rbp())));
+    assert!(!sub_rsp.matches(&pattern!(Opcode::ADD, RegSpec::rsp())));
+
+    assert!(load_canary.matches(&pattern!(Opcode::MOV, OperandPattern::AnyRegister, Operand::DisplacementU32(0x28))));
+    assert!(!load_canary.matches(&pattern!(Opcode::MOV, OperandPattern::AnyRegister, OperandPattern::AnyImmediate)));
+
+    assert_eq!(
+        pattern!(Opcode::MOV, RegSpec::rax(), OperandPattern::AnyMemory),
+        InstructionPattern::new(Opcode::MOV)
+            .with_operand(0, OperandPattern::Register(RegSpec::rax()))
+            .with_operand(1, OperandPattern::AnyMemory),
+    );
+}
@@ -0,0 +1,66 @@
+use yaxpeax_x86::long_mode::{InstDecoder, Opcode};
+use yaxpeax_x86::long_mode::testgen::{enumerate, Generator};
+use yaxpeax_x86::protected_mode;
+
+#[test]
+fn test_enumerate_forms() {
+    let decoder = InstDecoder::default();
+    let found = enumerate(&decoder, |instr| {
+        instr.opcode() == Opcode::ADD || instr.opcode() == Opcode::VPADDD
+    });
+
+    for generated in found.iter() {
+        assert_eq!(decoder.decode_slice(&generated.bytes).unwrap(), generated.instruction);
+    }
+    let adds: Vec<&[u8]> = found.iter()
+        .filter(|g| g.instruction.opcode() == Opcode::ADD)
+        .map(|g| &g.bytes[..])
+        .collect();
+    // register and memory forms of `01`, but not one for each register.
+    assert!(adds.contains(&&[0x01, 0xc1][..]));
+    assert!(adds.contains(&&[0x01, 0x00][..]));
+    assert!(!adds.contains(&&[0x01, 0xc9][..]));
+    // `add` selected by `reg` of `80`, `81`, and `83`, and `rex.w` forms.
+    assert!(adds.contains(&&[0x83, 0xc1, 0x00][..]));
+    assert!(adds.contains(&&[0x48, 0x81, 0x00, 0x00, 0x00, 0x00, 0x00][..]));
+
+    let vpaddd: Vec<&[u8]> = found.iter()
+        .filter(|g| g.instruction.opcode() == Opcode::VPADDD)
+        .map(|g| &g.bytes[..])
+        .collect();
+    assert!(vpaddd.iter().any(|bytes| bytes[0] == 0xc4));
+    assert!(vpaddd.iter().any(|bytes| bytes[0] == 0x62));
+}
+
+#[test]
+fn test_generator() {
+    let decoder = InstDecoder::default();
+    let first: Vec<Vec<u8>> = Generator::new(decoder, 7).take(500).map(|g| g.bytes).collect();
+    let again: Vec<Vec<u8>> = Generator::new(decoder, 7).take(500).map(|g| g.bytes).collect();
+    assert_eq!(first, again);
+    // a decent spread of opcodes, not one form over and over.
+    let mut opcodes: Vec<Opcode> = Generator::new(decoder, 7).take(500).map(|g| g.instruction.opcode()).collect();
+    opcodes.sort_by_key(|opcode| *opcode as u32);
+    opcodes.dedup();
+    assert!(opcodes.len() > 100);
+
+    let mut generator = Generator::new(decoder, 11);
+    let lock = generator.generate(|instr| instr.prefixes.lock(), 100_000).unwrap();
+    assert!(decoder.decode_slice(&lock.bytes).unwrap().prefixes.lock());
+
+    // vary the operands of `cmpxchg16b`, which random candidates rarely hit.
+    let form = enumerate(&decoder, |instr| instr.opcode() == Opcode::CMPXCHG16B).remove(0);
+    for _ in 0..20 {
+        let variant = generator.variant_of(&form, 1000).unwrap();
+        assert_eq!(variant.instruction.opcode(), Opcode::CMPXCHG16B);
+        assert_eq!(variant.bytes[..variant.opcode_len], form.bytes[..form.opcode_len]);
+    }
+}
+
+#[test]
+fn test_generator_protected_mode() {
+    let decoder = protected_mode::InstDecoder::default();
+    for generated in protected_mode::testgen::Generator::new(decoder, 3).take(500) {
+        assert_eq!(decoder.decode_slice(&generated.bytes).unwrap(), generated.instruction);
+    }
+}
@@ -0,0 +1,33 @@
+use yaxpeax_x86::wasm::{decode, decode_all, Mode};
+
+#[test]
+fn test_wasm_decode() {
+    // add dword [rax + 0x10], ecx
+    let instr = decode(Mode::Long, &[0x01, 0x48, 0x10, 0x90]).unwrap();
+    assert_eq!(instr.length(), 3);
+    assert_eq!(instr.mnemonic(), "add");
+    assert_eq!(instr.text(), "add dword [rax + 0x10], ecx");
+    let operands = instr.operands();
+    assert_eq!(operands.len(), 2);
+    assert_eq!(operands[0].kind(), "memory");
+    assert_eq!(operands[0].text(), "[rax + 0x10]");
+    assert!(operands[0].read());
+    assert!(operands[0].write());
+    assert_eq!(operands[0].width(), Some(4));
+    assert_eq!(operands[1].kind(), "register");
+    assert_eq!(operands[1].text(), "ecx");
+    assert!(operands[1].read());
+    assert!(!operands[1].write());
+
+    // the same bytes mean something else in 16-bit code
+    assert_eq!(decode(Mode::Real, &[0x01, 0x48, 0x10]).unwrap().text(), "add word [bx + si + 0x10], cx");
+
+    let far = decode(Mode::Protected, &[0xea, 0x78, 0x56, 0x34, 0x12, 0x00, 0x10]).unwrap();
+    assert_eq!(far.operands()[0].kind(), "far");
+
+    assert!(decode(Mode::Long, &[0x0f]).is_err());
+
+    let instrs = decode_all(Mode::Long, &[0x55, 0x48, 0x89, 0xe5, 0xc3, 0x0f]);
+    let texts: Vec<String> = instrs.iter().map(|i| i.text()).collect();
+    assert_eq!(texts, ["push rbp", "mov rbp, rsp", "ret"]);
+}
@@ -1,8 +1,9 @@
 use std::fmt::Write;
 
 use yaxpeax_arch::{AddressBase, Decoder, LengthedInstruction};
-use yaxpeax_x86::protected_mode::{DisplayStyle, InstDecoder};
+use yaxpeax_x86::protected_mode::{DisplayOptions, DisplayStyle, InstDecoder, RelocResolver};
 
+#[allow(dead_code)]
 fn test_display(data: &[u8], expected: &'static str) {
     test_display_under(&InstDecoder::default(), DisplayStyle::Intel, data, expected);
 }
@@ -11,7 +12,11 @@ fn test_c_display(data: &[u8], expected: &'static str) {
     test_display_under(&InstDecoder::default(), DisplayStyle::C, data, expected);
 }
 
-fn test_display_under(decoder: &InstDecoder, style: DisplayStyle, data: &[u8], expected: &'static str) {
+fn test_att_display(data: &[u8], expected: &'static str) {
+    test_display_under(&InstDecoder::default(), DisplayOptions::att(), data, expected);
+}
+
+fn test_display_under<O: Into<DisplayOptions>>(decoder: &InstDecoder, style: O, data: &[u8], expected: &'static str) {
     let mut hex = String::new();
     for b in data {
         write!(hex, "{:02x}", b).unwrap();
@@ -39,34 +44,54 @@ fn test_display_under(decoder: &InstDecoder, style: DisplayStyle, data: &[u8], e
     }
 }
 
-// decided i do not like at&t syntax much at all. not going to write a formatter for it. some test
-// cases will live on in case someone else feels like adding one, or i get mad enough to do it.
-#[ignore]
 #[test]
-fn test_instructions_atnt() {
+fn test_instructions_att() {
     // just modrm
-    test_display(&[0x33, 0x08], "xor (%eax), %ecx");
-    test_display(&[0x33, 0x20], "xor (%eax), %esp");
-    test_display(&[0x33, 0x05, 0x78, 0x56, 0x34, 0x12], "xor (0x12345678), %eax");
-    test_display(&[0x33, 0x41, 0x23], "xor 0x23(%ecx), %eax");
-    test_display(&[0x33, 0x81, 0x23, 0x01, 0x65, 0x43], "xor %0x43650123, %eax");
-    test_display(&[0x33, 0xc1], "xor %ecx, %eax");
+    test_att_display(&[0x33, 0x08], "xor (%eax),%ecx");
+    test_att_display(&[0x33, 0x20], "xor (%eax),%esp");
+    test_att_display(&[0x33, 0x05, 0x78, 0x56, 0x34, 0x12], "xor 0x12345678,%eax");
+    test_att_display(&[0x33, 0x41, 0x23], "xor 0x23(%ecx),%eax");
+    test_att_display(&[0x33, 0x81, 0x23, 0x01, 0x65, 0x43], "xor 0x43650123(%ecx),%eax");
+    test_att_display(&[0x33, 0xc1], "xor %ecx,%eax");
 
     // sib
-    test_display(&[0x33, 0x04, 0x25, 0x11, 0x22, 0x33, 0x44], "xor (0x44332211), %eax");
-    test_display(&[0x41, 0x33, 0x04, 0x25, 0x11, 0x22, 0x33, 0x44], "xor (0x44332211), %eax");
+    test_att_display(&[0x33, 0x04, 0x25, 0x11, 0x22, 0x33, 0x44], "xor 0x44332211,%eax");
+
+    test_att_display(&[0x33, 0x44, 0x65, 0x11], "xor 0x11(%ebp),%eax");
 
-    test_display(&[0x33, 0x44, 0x65, 0x11], "xor 0x11(%r13), %eax");
+    test_att_display(&[0x33, 0x34, 0x25, 0x20, 0x30, 0x40, 0x50], "xor 0x50403020,%esi");
 
-    test_display(&[0x33, 0x34, 0x25, 0x20, 0x30, 0x40, 0x50], "xor 0x50403020, %esi");
+    test_att_display(&[0x0f, 0xe7, 0x03], "movntq %mm0,(%ebx)");
 
-    test_display(&[0x0f, 0xe7, 0x03], "movntq %mm0, (%ebx)");
+    test_att_display(&[0x0f, 0x7f, 0x0f], "movq %mm1,(%edi)");
+    test_att_display(&[0x0f, 0xc4, 0xc0, 0x14], "pinsrw $0x14,%eax,%mm0");
 
-    test_display(&[0x0f, 0x7f, 0x0f], "movq %mm1, (%edi)");
-    test_display(&[0x0f, 0xc4, 0xc0, 0x14], "pinsrw $0x14, %eax, %mm0");
+    test_att_display(&[0x0f, 0xd1, 0x00], "psrlw (%eax),%mm0");
+    test_att_display(&[0x0f, 0xe5, 0x3d, 0xaa, 0xbb, 0xcc, 0x77], "pmulhw 0x77ccbbaa,%mm7");
+
+    test_att_display(&[0xc7, 0x00, 0x01, 0x00, 0x00, 0x00], "movl $0x1,(%eax)");
+    test_att_display(&[0x0f, 0xb6, 0xc0], "movzbl %al,%eax");
+    test_att_display(&[0xff, 0xd0], "call *%eax");
+    test_att_display(&[0xff, 0x28], "ljmp *(%eax)");
+    test_att_display(&[0xf3, 0xa4], "rep movsb %ds:(%esi),%es:(%edi)");
+}
 
-    test_display(&[0x0f, 0xd1, 0x00], "psrlw (%eax), %mm0");
-    test_display(&[0x0f, 0xe5, 0x3d, 0xaa, 0xbb, 0xcc, 0x77], "pmulhw 0x77ccbbaa, %mm7");
+#[test]
+fn test_instructions_att_objdump() {
+    test_att_display(&[0x0f, 0x1f, 0x40, 0x00], "nopl 0x0(%eax)");
+    test_att_display(&[0x83, 0xc0, 0xff], "add $0xffffffff,%eax");
+    test_att_display(&[0x6a, 0x90], "push $0xffffff90");
+    test_att_display(&[0x0f, 0x94, 0xc0], "sete %al");
+    test_att_display(&[0xe1, 0xfe], "loope $-0x2");
+    test_att_display(&[0xf3, 0xa6], "repz cmpsb %es:(%edi),%ds:(%esi)");
+    test_att_display(&[0x6c], "insb (%dx),%es:(%edi)");
+    test_att_display(&[0xd7], "xlat %ds:(%ebx)");
+    test_att_display(&[0x66, 0x90], "xchg %ax,%ax");
+    test_att_display(&[0x66, 0xc3], "retw");
+    test_att_display(&[0x66, 0xcf], "iretw");
+    test_att_display(&[0xcf], "iret");
+    test_att_display(&[0x9b], "fwait");
+    test_att_display(&[0xd8, 0xc0], "fadd %st(0),%st");
 }
 
 #[test]
@@ -136,3 +161,52 @@ fn test_instructions_c() {
 
     test_c_display(&[0xff, 0xe0], "jmp eax");
 }
+
+struct Reloc(u8, u8, &'static str, i64);
+
+impl RelocResolver for Reloc {
+    fn resolve(&self, offset: u8, width: u8) -> Option<(&str, i64)> {
+        if offset == self.0 && width == self.1 {
+            Some((self.2, self.3))
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn test_display_with_relocations() {
+    let display = |data: &[u8], reloc: Reloc, options: DisplayOptions| {
+        let instr = InstDecoder::default().decode_slice(data).expect("instruction decodes");
+        instr.display_with(options).with_relocations(&reloc).to_string()
+    };
+    // mov eax, dword [disp32], with an implicit addend in the instruction
+    assert_eq!(display(&[0xa1, 0x10, 0, 0, 0], Reloc(1, 4, "foo", 0x10), DisplayStyle::Intel.into()), "mov eax, dword [foo+0x10]");
+    // mov eax, dword [ebx * 4 + disp32]
+    assert_eq!(display(&[0x8b, 0x04, 0x9d, 0, 0, 0, 0], Reloc(3, 4, "table", 0), DisplayStyle::Intel.into()), "mov eax, dword [ebx * 4 + table]");
+    assert_eq!(display(&[0x8b, 0x04, 0x9d, 0, 0, 0, 0], Reloc(3, 4, "table", 0), DisplayOptions::att()), "mov table(,%ebx,4),%eax");
+    // push imm32
+    assert_eq!(display(&[0x68, 0, 0, 0, 0], Reloc(1, 4, "str", 0), DisplayOptions::att()), "push $str");
+    // 16-bit addresses have 16-bit displacements
+    assert_eq!(display(&[0x67, 0x8b, 0x87, 0, 0], Reloc(3, 2, "foo", 0), DisplayStyle::Intel.into()), "mov eax, dword [bx + foo]");
+}
+
+#[cfg(feature = "colors")]
+#[test]
+fn test_display_with_colors() {
+    use yaxpeax_x86::protected_mode::Palette;
+
+    let instr = InstDecoder::default().decode_slice(&[0xa1, 0x10, 0, 0, 0]).expect("instruction decodes");
+    assert_eq!(
+        instr.display_with(DisplayStyle::Intel).with_colors(Palette::new()).to_string(),
+        "\x1b[1mmov\x1b[0m \x1b[34meax\x1b[0m, dword [\x1b[36m0x10\x1b[0m]"
+    );
+}
+
+#[test]
+fn operand_display() {
+    // mov ax, word es:[bx + si + 0x4]
+    let instr = InstDecoder::default().decode_slice(&[0x67, 0x66, 0x26, 0x8b, 0x40, 0x04]).unwrap();
+    assert_eq!(instr.display_operand(1, DisplayStyle::Intel).to_string(), "word es:[bx + si + 0x4]");
+    assert_eq!(instr.display_operand(1, DisplayOptions::att()).to_string(), "%es:0x4(%bx,%si)");
+}
@@ -2,6 +2,7 @@ mod regspec;
 mod operand;
 mod display;
 mod evex_generated;
+mod length;
 
 use std::fmt::Write;
 
@@ -840,8 +841,8 @@ fn test_0f01() {
     test_display(&[0x0f, 0x01, 0xe5], "smsw bp");
     test_display(&[0x0f, 0x01, 0xe6], "smsw si");
     test_display(&[0x0f, 0x01, 0xe7], "smsw di");
-    test_invalid(&[0x0f, 0x01, 0xe8]);
-    test_invalid(&[0x0f, 0x01, 0xe8]);
+    test_display(&[0x0f, 0x01, 0xe8], "serialize");
+    test_invalid(&[0x66, 0x0f, 0x01, 0xe8]);
     test_invalid(&[0x0f, 0x01, 0xe9]);
     test_invalid(&[0x0f, 0x01, 0xea]);
     test_invalid(&[0x0f, 0x01, 0xeb]);
@@ -1071,6 +1072,18 @@ fn test_stack() {
     test_display(&[0x66, 0x50], "push ax");
 }
 
+#[test]
+fn test_segment_override_prefixes() {
+    use yaxpeax_x86::protected_mode::Segment;
+
+    let decoder = InstDecoder::default();
+    assert_eq!(decoder.decode_slice(&[0x8b, 0x00]).unwrap().prefixes().segment_override, None);
+    // an explicit `ds` override is reported, even though `ds` is the default segment.
+    assert_eq!(decoder.decode_slice(&[0x3e, 0x8b, 0x00]).unwrap().prefixes().segment_override, Some(Segment::DS));
+    assert_eq!(decoder.decode_slice(&[0x26, 0x8b, 0x00]).unwrap().prefixes().segment_override, Some(Segment::ES));
+    assert_eq!(decoder.decode_slice(&[0x36, 0x64, 0x8b, 0x00]).unwrap().prefixes().segment_override, Some(Segment::FS));
+}
+
 #[test]
 fn test_prefixes() {
     test_display(&[0x66, 0x31, 0xc0], "xor ax, ax");
@@ -1106,6 +1119,8 @@ fn test_control_flow() {
     test_display(&[0x67, 0xff, 0xe0], "jmp eax");
     test_invalid(&[0xff, 0xd8]);
     test_display(&[0xff, 0x18], "callf far [eax]");
+    test_display(&[0x9a, 0x78, 0x56, 0x34, 0x12, 0x08, 0x00], "callf 0x8:0x12345678");
+    test_display(&[0x66, 0xea, 0x34, 0x12, 0x10, 0x00], "jmpf 0x10:0x1234");
     test_display(&[0xe0, 0x12], "loopnz $+0x12");
     test_display(&[0xe1, 0x12], "loopz $+0x12");
     test_display(&[0xe2, 0x12], "loop $+0x12");
@@ -1308,6 +1323,12 @@ fn test_misc() {
 fn evex() {
     test_display(&[0x62, 0xf2, 0x7d, 0x48, 0x2a, 0x44, 0x40, 0x01], "vmovntdqa zmm0, zmmword [eax + eax * 2 + 0x40]");
     test_display(&[0x62, 0xf2, 0x7d, 0x08, 0x2a, 0x44, 0x40, 0x01], "vmovntdqa xmm0, xmmword [eax + eax * 2 + 0x10]");
+    // avx512-fp16 in evex maps 5 and 6
+    test_display(&[0x62, 0xf5, 0x6c, 0xc9, 0x58, 0xcb], "vaddph zmm1{k1}{z}, zmm2, zmm3");
+    test_display(&[0x62, 0xf5, 0x6c, 0x1a, 0x58, 0x48, 0x01], "vaddph xmm1{k2}, xmm2, word [eax + 0x2]{1to8}");
+    test_display(&[0x62, 0xf6, 0x6e, 0x58, 0x56, 0x08], "vfmaddcph zmm1, zmm2, dword [eax]{1to16}");
+    test_display(&[0x62, 0xf5, 0x7d, 0x08, 0x7e, 0xc8], "vmovw eax, xmm1");
+    test_invalid_under(&InstDecoder::minimal().with_avx512(), &[0x62, 0xf5, 0x6c, 0xc9, 0x58, 0xcb]);
 }
 
 #[test]
@@ -2440,6 +2461,7 @@ fn test_svm() {
     test_display(&[0x0f, 0x01, 0xdb], "vmsave eax");
     test_display(&[0x0f, 0x01, 0xda], "vmload eax");
     test_display(&[0x0f, 0x01, 0xd9], "vmmcall");
+    test_display(&[0xf3, 0x0f, 0x01, 0xd9], "vmgexit");
     test_display(&[0x0f, 0x01, 0xd8], "vmrun eax");
     test_display(&[0x0f, 0x78, 0xc4], "vmread esp, eax");
     test_display(&[0x0f, 0x79, 0xc5], "vmwrite eax, ebp");
@@ -2690,7 +2712,7 @@ fn test_x87() {
     test_invalid(&[0xdb, 0x26]);
     test_invalid(&[0xdb, 0x27]);
 //    test_display(&[0xdb, 0x2b], "fld st, ptr [ebx]");
-    test_display(&[0xdb, 0x2b], "fld st(0), mword [ebx]");
+    test_display(&[0xdb, 0x2b], "fld st(0), tword [ebx]");
     test_invalid(&[0xdb, 0x30]);
     test_invalid(&[0xdb, 0x31]);
     test_invalid(&[0xdb, 0x32]);
@@ -2700,7 +2722,7 @@ fn test_x87() {
     test_invalid(&[0xdb, 0x36]);
     test_invalid(&[0xdb, 0x37]);
 //    test_display(&[0xdb, 0x3b], "fstp ptr [ebx], st");
-    test_display(&[0xdb, 0x3b], "fstp mword [ebx], st(0)");
+    test_display(&[0xdb, 0x3b], "fstp tword [ebx], st(0)");
 //    test_display(&[0xdb, 0xc3], "fcmovnb st, st(3)");
     test_display(&[0xdb, 0xc3], "fcmovnb st(0), st(3)");
 //    test_display(&[0xdb, 0xcb], "fcmovne st, st(3)");
@@ -2857,11 +2879,11 @@ fn test_x87() {
 //    test_display(&[0xdf, 0x1b], "fistp word ptr [ebx], st");
     test_display(&[0xdf, 0x1b], "fistp word [ebx], st(0)");
 //    test_display(&[0xdf, 0x23], "fbld st, ptr [ebx]");
-    test_display(&[0xdf, 0x23], "fbld st(0), mword [ebx]");
+    test_display(&[0xdf, 0x23], "fbld st(0), tword [ebx]");
 //    test_display(&[0xdf, 0x2b], "fild st, qword ptr [ebx]");
     test_display(&[0xdf, 0x2b], "fild st(0), qword [ebx]");
 //    test_display(&[0xdf, 0x33], "fbstp ptr [ebx], st");
-    test_display(&[0xdf, 0x33], "fbstp mword [ebx], st(0)");
+    test_display(&[0xdf, 0x33], "fbstp tword [ebx], st(0)");
 //    test_display(&[0xdf, 0x3b], "fistp qword ptr [ebx], st");
     test_display(&[0xdf, 0x3b], "fistp qword [ebx], st(0)");
 //    test_display(&[0xdf, 0xc3], "ffreep st(3)");
@@ -2961,6 +2983,7 @@ fn test_cet() {
     // endbr64 f3 0f ae fa
     // endbr32 f3 0f ae fb
     test_display(&[0xf3, 0x0f, 0xae, 0xe9], "incssp ecx");
+    test_display(&[0xf3, 0x0f, 0x1e, 0xcb], "rdssp ebx");
     test_display(&[0x3e, 0x0f, 0x38, 0xf6, 0x23], "wrss dword [ebx], esp");
     test_display(&[0x66, 0x0f, 0x38, 0xf5, 0x47, 0xe9], "wruss dword [edi - 0x17], eax");
     test_invalid(&[0x0f, 0x38, 0xf5, 0x47, 0xe9]);
@@ -2976,6 +2999,43 @@ fn test_cet() {
     test_display(&[0xf3, 0x0f, 0xae, 0x30], "clrssbsy qword [eax]");
 }
 
+#[test]
+fn test_mpx() {
+    // without `mpx`, as by default, these are the reserved `nop`s current processors execute.
+    test_display(&[0xf3, 0x0f, 0x1b, 0x01], "nop dword [ecx]");
+
+    let mpx = InstDecoder::default().with_mpx();
+    test_display_under(&mpx, &[0xf3, 0x0f, 0x1b, 0x01], "bndmk bnd0, ptr [ecx]");
+    test_display_under(&mpx, &[0xf3, 0x0f, 0x1a, 0xc9], "bndcl bnd1, ecx");
+    test_display_under(&mpx, &[0xf2, 0x0f, 0x1b, 0x18], "bndcn bnd3, ptr [eax]");
+    test_display_under(&mpx, &[0x66, 0x0f, 0x1a, 0x01], "bndmov bnd0, qword [ecx]");
+    test_display_under(&mpx, &[0x66, 0x0f, 0x1b, 0xc1], "bndmov bnd1, bnd0");
+    test_display_under(&mpx, &[0x0f, 0x1b, 0x04, 0x08], "bndstx ptr [eax + ecx * 1], bnd0");
+    test_invalid_under(&mpx, &[0x66, 0x0f, 0x1a, 0xc4]);
+    test_invalid_under(&mpx, &[0x0f, 0x1b, 0xc0]);
+}
+
+#[test]
+fn test_nop_idioms() {
+    use yaxpeax_x86::protected_mode::NopStrictness;
+
+    fn is_nop(data: &[u8], strictness: NopStrictness) -> bool {
+        InstDecoder::default().decode_slice(data).unwrap().is_nop(strictness)
+    }
+
+    // unlike in 64-bit code, 32-bit self-moves are no-ops: xchg eax, eax; mov edi, edi
+    assert!(is_nop(&[0x87, 0xc0], NopStrictness::Equivalent));
+    assert!(is_nop(&[0x8b, 0xff], NopStrictness::Equivalent));
+    assert!(!is_nop(&[0x8b, 0xff], NopStrictness::Canonical));
+    // lea esi, [esi + 0x0]; lea esi, [esi + eiz * 1 + 0x0]; lea edi, [edi + 0x0] with a 32-bit
+    // displacement
+    assert!(is_nop(&[0x8d, 0x76, 0x00], NopStrictness::Equivalent));
+    assert!(is_nop(&[0x8d, 0x74, 0x26, 0x00], NopStrictness::Equivalent));
+    assert!(is_nop(&[0x8d, 0xbf, 0x00, 0x00, 0x00, 0x00], NopStrictness::Equivalent));
+    // lea si, [esi]
+    assert!(!is_nop(&[0x66, 0x8d, 0x36], NopStrictness::Padding));
+}
+
 #[test]
 fn test_sse4a() {
     fn test_instr(bytes: &[u8], text: &'static str) {
@@ -3010,6 +3070,19 @@ fn test_3dnow() {
     test_display(&[0x0f, 0x0f, 0xc6, 0xb7], "pmulhrw mm0, mm6");
 }
 
+#[test]
+fn test_xop() {
+    test_display(&[0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30], "vpcmov xmm0, xmm1, xmm2, xmm3");
+    test_display(&[0x8f, 0xe8, 0x70, 0x95, 0x00, 0x30], "vpmacsww xmm0, xmm1, xmmword [eax], xmm3");
+    // outside 64-bit mode, the high bits of register numbers are ignored.
+    test_display(&[0x8f, 0x48, 0x30, 0xa3, 0xc2, 0xf0], "vpperm xmm0, xmm1, xmm2, xmm7");
+    // as is `xop.w` for general-purpose operands.
+    test_display(&[0x8f, 0xe9, 0xf8, 0x01, 0xc9], "blcfill eax, ecx");
+    test_display(&[0x8f, 0x00], "pop dword [eax]");
+    test_invalid(&[0xf3, 0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30]);
+    test_invalid_under(&InstDecoder::minimal(), &[0x8f, 0xe8, 0x70, 0xa2, 0xc2, 0x30]);
+}
+
 // first appeared in tremont
 #[test]
 fn test_direct_stores() {
@@ -3137,3 +3210,152 @@ mod reg_masks {
         RegSpec::zmm(32);
     }
 }
+
+#[test]
+fn opcode_mnemonics() {
+    use yaxpeax_x86::protected_mode::Opcode;
+
+    assert_eq!(Opcode::from_mnemonic("arpl"), Some(Opcode::ARPL));
+    for (opcode, mnemonic) in Opcode::all() {
+        assert_eq!(opcode.to_string(), mnemonic);
+    }
+    assert_eq!(Opcode::all().last().map(|(opcode, _)| opcode.to_string()), Some("lwpval".to_string()));
+}
+
+#[test]
+fn synthesized_instructions() {
+    use yaxpeax_x86::protected_mode::{Instruction, Opcode, Operand, RegSpec};
+
+    let instr = Instruction::synthesize(Opcode::JMPF, &[Operand::AbsoluteFarAddress { segment: 0x1000, address: 0x12345678 }]).unwrap();
+    assert_eq!(instr.to_string(), "jmpf 0x1000:0x12345678");
+    let instr = Instruction::synthesize(Opcode::MOV, &[Operand::Register(RegSpec::ax()), Operand::RegIndexBaseDisp(RegSpec::ebx(), RegSpec::esi(), 0x10)]).unwrap();
+    assert_eq!(instr.to_string(), "mov ax, word [ebx + esi + 0x10]");
+}
+
+#[test]
+fn register_aliases() {
+    use yaxpeax_x86::protected_mode::RegSpec;
+
+    assert_eq!(RegSpec::ah().widest(), RegSpec::eax());
+    assert_eq!(RegSpec::eax().narrow_to(1), Some(RegSpec::al()));
+    // there are no `spl`-style byte registers outside of `long_mode`.
+    assert_eq!(RegSpec::esp().narrow_to(1), None);
+    let aliases: Vec<RegSpec> = RegSpec::cx().aliases().collect();
+    assert_eq!(aliases, [RegSpec::ecx(), RegSpec::cl(), RegSpec::ch()]);
+}
+
+#[test]
+fn decode_hooks() {
+    use yaxpeax_x86::protected_mode::{DecodeHooks, Encoding};
+
+    #[derive(Default)]
+    struct Events {
+        prefixes: Vec<u8>,
+        map: Option<(Encoding, u8)>,
+        modrm: Option<(u8, u8)>,
+    }
+
+    impl DecodeHooks for Events {
+        fn prefix(&mut self, _offset: u8, byte: u8) {
+            self.prefixes.push(byte);
+        }
+        fn opcode_map(&mut self, encoding: Encoding, map: u8) {
+            self.map = Some((encoding, map));
+        }
+        fn modrm(&mut self, offset: u8, byte: u8) {
+            self.modrm = Some((offset, byte));
+        }
+    }
+
+    let decoder = InstDecoder::default();
+
+    // `les`, with a memory operand.
+    let mut events = Events::default();
+    let instr = decoder.decode_with_hooks(&mut yaxpeax_arch::U8Reader::new(&[0x26, 0xc4, 0x00]), &mut events).unwrap();
+    assert_eq!(instr.to_string(), "les eax, far es:[eax]");
+    assert_eq!(events.prefixes, [0x26]);
+    assert_eq!(events.map, Some((Encoding::Legacy, 0)));
+    assert_eq!(events.modrm, Some((2, 0x00)));
+
+    // with a register operand, `c4` is a `vex` prefix instead, and its "modrm" is part of it.
+    let mut events = Events::default();
+    let instr = decoder.decode_with_hooks(&mut yaxpeax_arch::U8Reader::new(&[0xc4, 0xe2, 0x79, 0x00, 0xc1]), &mut events).unwrap();
+    assert_eq!(instr.to_string(), "vpshufb xmm0, xmm0, xmm1");
+    assert!(events.prefixes.is_empty());
+    assert_eq!(events.map, Some((Encoding::Vex, 2)));
+    assert_eq!(events.modrm, Some((4, 0xc1)));
+}
+
+#[test]
+fn decode_annotations() {
+    use yaxpeax_arch::annotation::{AnnotatingDecoder, DescriptionSink};
+    use yaxpeax_x86::protected_mode::{FieldDescription, InnerDescription, Instruction, Opcode, RegSpec};
+
+    #[derive(Default)]
+    struct Fields {
+        opcodes: Vec<(u32, Opcode)>,
+        registers: Vec<(u32, &'static str, RegSpec)>,
+    }
+
+    impl DescriptionSink<FieldDescription> for Fields {
+        fn record(&mut self, start: u32, _end: u32, description: FieldDescription) {
+            match description.desc() {
+                InnerDescription::Opcode(opcode) => self.opcodes.push((start, *opcode)),
+                InnerDescription::RegisterNumber(name, _, reg) => self.registers.push((start, *name, *reg)),
+                _ => {}
+            }
+        }
+    }
+
+    let decoder = InstDecoder::default();
+
+    // `rrr` selects an `xmm` register here, though the decoder reads it before knowing as much.
+    let mut instr = Instruction::default();
+    let mut fields = Fields::default();
+    decoder.decode_with_annotation(&mut instr, &mut yaxpeax_arch::U8Reader::new(&[0x0f, 0x58, 0xc1]), &mut fields).unwrap();
+    assert_eq!(instr.to_string(), "addps xmm0, xmm1");
+    assert_eq!(fields.opcodes, [(8, Opcode::ADDPS)]);
+    fields.registers.sort_by_key(|r| r.0);
+    assert_eq!(fields.registers, [(16, "mmm", RegSpec::xmm(1)), (19, "rrr", RegSpec::xmm0())]);
+
+    let mut instr = Instruction::default();
+    let mut fields = Fields::default();
+    decoder.decode_with_annotation(&mut instr, &mut yaxpeax_arch::U8Reader::new(&[0xc4, 0xe2, 0x71, 0x00, 0xc2]), &mut fields).unwrap();
+    assert_eq!(instr.to_string(), "vpshufb xmm0, xmm1, xmm2");
+    assert_eq!(fields.opcodes, [(24, Opcode::VPSHUFB)]);
+    assert!(fields.registers.contains(&(19, "vvvv", RegSpec::xmm(1))));
+}
+
+#[test]
+fn prefix_effects() {
+    use yaxpeax_x86::protected_mode::PrefixEffect;
+
+    let decoder = InstDecoder::default();
+    let effects = decoder.decode_slice(&[0x66, 0x01, 0xc0]).unwrap().prefix_effects();
+    assert_eq!(effects.operand_size, PrefixEffect::Effective);
+    assert_eq!(effects.rex_w, PrefixEffect::Absent);
+    // `ss` is already the default segment for addresses based on `ebp`.
+    let effects = decoder.decode_slice(&[0x36, 0x8b, 0x45, 0x08]).unwrap().prefix_effects();
+    assert_eq!(effects.segment, PrefixEffect::Ignored);
+    let effects = decoder.decode_slice(&[0x36, 0x8b, 0x00]).unwrap().prefix_effects();
+    assert_eq!(effects.segment, PrefixEffect::Effective);
+    let effects = decoder.decode_slice(&[0x66, 0xea, 0x00, 0x00, 0x08, 0x00]).unwrap().prefix_effects();
+    assert_eq!(effects.operand_size, PrefixEffect::Effective);
+}
+
+#[test]
+fn operand_conversion() {
+    use yaxpeax_x86::protected_mode::{Operand, RegSpec};
+    use yaxpeax_x86::real_mode;
+
+    let far = Operand::AbsoluteFarAddress { segment: 0x10, address: 0x1234 };
+    let converted = real_mode::Operand::from(far.clone());
+    assert_eq!(converted, real_mode::Operand::AbsoluteFarAddress { segment: 0x10, address: 0x1234 });
+    assert_eq!(Operand::from(converted), far);
+
+    let op = Operand::RegIndexBaseScale(RegSpec::esi(), RegSpec::ecx(), 4);
+    assert_eq!(
+        real_mode::Operand::from(op),
+        real_mode::Operand::RegIndexBaseScale(real_mode::RegSpec::esi(), real_mode::RegSpec::ecx(), 4)
+    );
+}
@@ -1,4 +1,4 @@
-use yaxpeax_x86::protected_mode::{InstDecoder, Operand, RegSpec};
+use yaxpeax_x86::protected_mode::{AccessKind, EvexDispInfo, FaultClasses, Flags, InstDecoder, Operand, PrivilegeLevel, RegSpec, RepSemantics};
 use yaxpeax_x86::MemoryAccessSize;
 
 #[test]
@@ -27,6 +27,29 @@ fn memory_widths() {
     assert_eq!(mem_size_of(&[0x33, 0x00]).size_name(), "dword");
 }
 
+#[test]
+fn x87_memory_sizes() {
+    fn mem_size_of(data: &[u8]) -> MemoryAccessSize {
+        let decoder = InstDecoder::default();
+        decoder.decode_slice(data).unwrap().mem_size().unwrap()
+    }
+
+    // fld tword [eax], fbld tword [eax]
+    assert_eq!(mem_size_of(&[0xdb, 0x28]).bytes_size(), Some(10));
+    assert_eq!(mem_size_of(&[0xdb, 0x28]).size_name(), "tword");
+    assert_eq!(mem_size_of(&[0xdf, 0x20]).size_name(), "tword");
+    // fnstenv and fldenv: the environment is smaller with a 16-bit operand size
+    assert_eq!(mem_size_of(&[0xd9, 0x30]).bytes_size(), Some(28));
+    assert_eq!(mem_size_of(&[0x66, 0xd9, 0x30]).bytes_size(), Some(14));
+    assert_eq!(mem_size_of(&[0xd9, 0x20]).bytes_size(), Some(28));
+    assert_eq!(mem_size_of(&[0xd9, 0x20]).size_name(), "ptr");
+    // fnsave and frstor also hold the eight registers
+    assert_eq!(mem_size_of(&[0xdd, 0x30]).bytes_size(), Some(108));
+    assert_eq!(mem_size_of(&[0x66, 0xdd, 0x30]).bytes_size(), Some(94));
+    assert_eq!(mem_size_of(&[0xdd, 0x20]).bytes_size(), Some(108));
+    assert_eq!(mem_size_of(&[0xdd, 0x30]).size_name(), "ptr");
+}
+
 #[test]
 fn test_implied_memory_width() {
     fn mem_size_of(data: &[u8]) -> Option<u8> {
@@ -48,3 +71,445 @@ fn test_implied_memory_width() {
     assert_eq!(mem_size_of(&[0x66, 0xff, 0x10]), Some(2));
     assert_eq!(mem_size_of(&[0x66, 0xff, 0x20]), Some(2));
 }
+
+#[test]
+fn operand_access_kinds() {
+    fn operands_of(data: &[u8]) -> Vec<(Operand, AccessKind)> {
+        let decoder = InstDecoder::default();
+        decoder.decode_slice(data).unwrap().operands().collect()
+    }
+
+    // sub esp, 0x10
+    assert_eq!(operands_of(&[0x83, 0xec, 0x10]), vec![
+        (Operand::Register(RegSpec::esp()), AccessKind::ReadWrite),
+        (Operand::ImmediateI8(0x10), AccessKind::Read),
+    ]);
+    // push ebp
+    assert_eq!(operands_of(&[0x55]), vec![
+        (Operand::Register(RegSpec::ebp()), AccessKind::Read),
+    ]);
+    // bound eax, qword [ecx]
+    let kinds: Vec<AccessKind> = operands_of(&[0x62, 0x01]).into_iter().map(|(_, k)| k).collect();
+    assert_eq!(kinds, vec![AccessKind::Read, AccessKind::Read]);
+    // les eax, far [ecx]
+    let kinds: Vec<AccessKind> = operands_of(&[0xc4, 0x01]).into_iter().map(|(_, k)| k).collect();
+    assert_eq!(kinds, vec![AccessKind::Write, AccessKind::Read]);
+}
+
+#[test]
+fn memory_accesses() {
+    fn accesses_of(data: &[u8]) -> Vec<(Option<u8>, Operand, Option<u8>, AccessKind)> {
+        let decoder = InstDecoder::default();
+        decoder.decode_slice(data).unwrap().memory_accesses().map(|access| {
+            (access.operand, access.address, access.size, access.access)
+        }).collect()
+    }
+
+    // bound eax, qword [ecx]
+    assert_eq!(accesses_of(&[0x62, 0x01]), vec![
+        (Some(1), Operand::RegDeref(RegSpec::ecx()), Some(8), AccessKind::Read),
+    ]);
+    // cmpsw: both operands are read.
+    assert_eq!(accesses_of(&[0x66, 0xa7]), vec![
+        (Some(0), Operand::RegDeref(RegSpec::edi()), Some(2), AccessKind::Read),
+        (Some(1), Operand::RegDeref(RegSpec::esi()), Some(2), AccessKind::Read),
+    ]);
+    // pusha writes all eight registers below `esp`.
+    assert_eq!(accesses_of(&[0x60]), vec![
+        (None, Operand::RegDisp(RegSpec::esp(), -32), Some(32), AccessKind::Write),
+    ]);
+    // into pushes `eflags`, `cs`, and `eip`.
+    assert_eq!(accesses_of(&[0xce]), vec![
+        (None, Operand::RegDisp(RegSpec::esp(), -12), Some(12), AccessKind::Write),
+    ]);
+}
+
+#[test]
+fn implicit_operands() {
+    fn implicit_of(data: &[u8]) -> Vec<(Operand, AccessKind)> {
+        let decoder = InstDecoder::default();
+        decoder.decode_slice(data).unwrap().implicit_operands().collect()
+    }
+
+    // push ebp
+    assert_eq!(implicit_of(&[0x55]), vec![
+        (Operand::Register(RegSpec::esp()), AccessKind::ReadWrite),
+        (Operand::RegDisp(RegSpec::esp(), -4), AccessKind::Write),
+    ]);
+    // push bp
+    assert_eq!(implicit_of(&[0x66, 0x55]), vec![
+        (Operand::Register(RegSpec::esp()), AccessKind::ReadWrite),
+        (Operand::RegDisp(RegSpec::esp(), -2), AccessKind::Write),
+    ]);
+    // popa
+    assert_eq!(implicit_of(&[0x61]).len(), 9);
+    // add eax, ecx
+    assert_eq!(implicit_of(&[0x01, 0xc8]), vec![
+        (Operand::Register(RegSpec::eflags()), AccessKind::Write),
+    ]);
+    // jecxz $+2
+    assert_eq!(implicit_of(&[0xe3, 0x00]), vec![
+        (Operand::Register(RegSpec::ecx()), AccessKind::Read),
+    ]);
+    // cdq
+    assert_eq!(implicit_of(&[0x99]), vec![
+        (Operand::Register(RegSpec::eax()), AccessKind::Read),
+        (Operand::Register(RegSpec::edx()), AccessKind::Write),
+    ]);
+}
+
+#[test]
+fn flags_read_written() {
+    fn flags_of(data: &[u8]) -> (Flags, Flags) {
+        let inst = InstDecoder::default().decode_slice(data).unwrap();
+        (inst.flags_read(), inst.flags_written())
+    }
+
+    // daa
+    assert_eq!(flags_of(&[0x27]), (Flags::AF | Flags::CF, Flags::STATUS));
+    // arpl word [eax], ax
+    assert_eq!(flags_of(&[0x63, 0x00]), (Flags::empty(), Flags::ZF));
+    // sbb eax, 1
+    assert_eq!(flags_of(&[0x83, 0xd8, 0x01]), (Flags::CF, Flags::STATUS));
+}
+
+#[test]
+fn rep_semantics() {
+    fn rep_of(data: &[u8]) -> RepSemantics {
+        InstDecoder::default().decode_slice(data).unwrap().rep_semantics()
+    }
+
+    // rep insd, repz scasd
+    assert_eq!(rep_of(&[0xf3, 0x6d]), RepSemantics::Repeat);
+    assert_eq!(rep_of(&[0xf3, 0xaf]), RepSemantics::RepeatWhileEqual);
+    // tzcnt eax, ecx
+    assert_eq!(rep_of(&[0xf3, 0x0f, 0xbc, 0xc1]), RepSemantics::Mandatory);
+    assert_eq!(rep_of(&[0xf3, 0x90]), RepSemantics::Pause);
+    // bnd jmp eax
+    assert_eq!(rep_of(&[0xf2, 0xff, 0xe0]), RepSemantics::Bnd);
+    // rep inc eax
+    assert_eq!(rep_of(&[0xf3, 0x40]), RepSemantics::Ignored);
+}
+
+#[test]
+fn fault_classes() {
+    fn faults_of(data: &[u8]) -> FaultClasses {
+        InstDecoder::default().decode_slice(data).unwrap().opcode().fault_classes()
+    }
+
+    // aam 0x0a
+    assert_eq!(faults_of(&[0xd4, 0x0a]), FaultClasses::DE);
+    // bound eax, [ecx]
+    assert!(faults_of(&[0x62, 0x01]).contains(FaultClasses::BR | FaultClasses::PF));
+    // into
+    assert!(faults_of(&[0xce]).contains(FaultClasses::OF));
+    // daa
+    assert!(faults_of(&[0x27]).is_empty());
+}
+
+#[test]
+fn required_cpl() {
+    fn cpl_of(data: &[u8]) -> PrivilegeLevel {
+        InstDecoder::default().decode_slice(data).unwrap().required_cpl()
+    }
+
+    // mov cr0, eax
+    assert_eq!(cpl_of(&[0x0f, 0x22, 0xc0]), PrivilegeLevel::Kernel);
+    // out dx, eax; rep outsb
+    assert_eq!(cpl_of(&[0xef]), PrivilegeLevel::Iopl);
+    assert_eq!(cpl_of(&[0xf3, 0x6e]), PrivilegeLevel::Iopl);
+    // smsw eax
+    assert_eq!(cpl_of(&[0x0f, 0x01, 0xe0]), PrivilegeLevel::Configurable);
+    // arpl [eax], cx
+    assert_eq!(cpl_of(&[0x63, 0x08]), PrivilegeLevel::User);
+}
+
+#[test]
+fn address_size_for_op() {
+    fn sizes_of(data: &[u8]) -> Vec<Option<u8>> {
+        let inst = InstDecoder::default().decode_slice(data).unwrap();
+        (0..inst.operand_count()).map(|i| inst.address_size_for_op(i)).collect()
+    }
+
+    // mov eax, dword [eax]
+    assert_eq!(sizes_of(&[0x8b, 0x00]), vec![None, Some(4)]);
+    // mov eax, dword [bx + si]
+    assert_eq!(sizes_of(&[0x67, 0x8b, 0x00]), vec![None, Some(2)]);
+    // lea eax, [bx + si]
+    assert_eq!(sizes_of(&[0x67, 0x8d, 0x00]), vec![None, Some(2)]);
+    // jmpf 0x10:0x12345678 has no memory operand
+    assert_eq!(sizes_of(&[0xea, 0x78, 0x56, 0x34, 0x12, 0x10, 0x00]), vec![None]);
+    assert_eq!(InstDecoder::default().decode_slice(&[0x90]).unwrap().address_size_for_op(3), None);
+}
+
+#[test]
+fn evex_compressed_displacements() {
+    let decoder = InstDecoder::default();
+    // vaddps zmm0, zmm1, zmmword [eax - 0x40]
+    let instr = decoder.decode_slice(&[0x62, 0xf1, 0x74, 0x48, 0x58, 0x40, 0xff]).unwrap();
+    assert_eq!(instr.operand(2), Operand::RegDisp(RegSpec::eax(), -0x40));
+    assert_eq!(instr.evex_disp_info(), Some(EvexDispInfo { disp8: -1, scale: 64, displacement: -0x40 }));
+    // add eax, dword [eax + 0x1]
+    let instr = decoder.decode_slice(&[0x03, 0x40, 0x01]).unwrap();
+    assert_eq!(instr.evex_disp_info(), None);
+}
+
+#[test]
+fn opcode_ids() {
+    use yaxpeax_x86::protected_mode::Opcode;
+
+    assert_eq!(Opcode::ADD.id(), yaxpeax_x86::long_mode::Opcode::ADD.id());
+    assert_eq!(Opcode::AAA.id(), 1509);
+    assert_eq!(Opcode::from_id(1509), Some(Opcode::AAA));
+    assert_eq!(Opcode::from_id(yaxpeax_x86::long_mode::Opcode::JRCXZ.id()), None);
+}
+
+#[test]
+fn register_class_members() {
+    use yaxpeax_x86::protected_mode::{register_class, OperandConstraint};
+
+    assert_eq!(register_class::X.registers().count(), 8);
+    assert_eq!(register_class::D.registers().last(), Some(RegSpec::edi()));
+
+    // add eax, dword [ecx]
+    let instr = InstDecoder::default().decode_slice(&[0x03, 0x01]).unwrap();
+    assert_eq!(instr.operand_constraint(0), Some(OperandConstraint::Register(register_class::D)));
+    assert_eq!(instr.operand_constraint(1), Some(OperandConstraint::Memory));
+}
+
+#[test]
+fn lock_validity() {
+    // lock inc eax
+    assert!(InstDecoder::default().decode_slice(&[0xf0, 0xff, 0xc0]).is_err());
+    let instr = InstDecoder::default().with_permissive_lock().decode_slice(&[0xf0, 0xff, 0xc0]).unwrap();
+    assert!(!instr.lock_valid());
+    // lock inc dword [eax]
+    assert!(InstDecoder::default().decode_slice(&[0xf0, 0xff, 0x00]).unwrap().lock_valid());
+}
+
+#[test]
+fn length_validity() {
+    use yaxpeax_arch::LengthedInstruction;
+
+    let mut padded = vec![0x2e; 15];
+    padded.extend_from_slice(&[0x8b, 0x00]);
+    assert!(InstDecoder::default().decode_slice(&padded).is_err());
+    let instr = InstDecoder::default().with_permissive_length().decode_slice(&padded).unwrap();
+    assert_eq!(instr.len().to_const(), 17);
+    assert!(!instr.length_valid());
+}
+
+#[test]
+fn condition_codes() {
+    use yaxpeax_x86::protected_mode::ConditionCode;
+
+    // jb $+2
+    let instr = InstDecoder::default().decode_slice(&[0x72, 0x00]).unwrap();
+    assert_eq!(instr.condition(), Some(ConditionCode::B));
+    assert_eq!(instr.condition().unwrap().invert(), ConditionCode::AE);
+    // add eax, ecx
+    assert_eq!(InstDecoder::default().decode_slice(&[0x01, 0xc8]).unwrap().condition(), None);
+}
+
+#[test]
+fn invalid_bytes() {
+    // lock nop
+    let decoder = InstDecoder::default().with_emit_invalid_bytes();
+    let instr = decoder.decode_slice(&[0xf0, 0x90]).unwrap();
+    assert_eq!(instr.invalid_byte(), Some(0xf0));
+    assert_eq!(instr.bytes(), Some(&[0xf0][..]));
+    assert_eq!(decoder.decode_slice(&[0x90]).unwrap().invalid_byte(), None);
+}
+
+#[test]
+fn far_pointers_and_pseudo_descriptors() {
+    use yaxpeax_x86::protected_mode::{DescriptorTable, FarPointer, PseudoDescriptor};
+
+    let decoder = InstDecoder::default();
+    let instr = decoder.decode_slice(&[0xea, 0x78, 0x56, 0x34, 0x12, 0x34, 0x12]).unwrap();
+    assert_eq!(instr.far_pointer(), Some(FarPointer::Immediate { selector: 0x1234, offset: 0x12345678 }));
+    let instr = decoder.decode_slice(&[0x66, 0x9a, 0x78, 0x56, 0x34, 0x12]).unwrap();
+    assert_eq!(instr.far_pointer(), Some(FarPointer::Immediate { selector: 0x1234, offset: 0x5678 }));
+    let instr = decoder.decode_slice(&[0xff, 0x28]).unwrap();
+    assert_eq!(instr.far_pointer(), Some(FarPointer::Memory { address: Operand::RegDeref(RegSpec::eax()), offset_width: 4 }));
+    let instr = decoder.decode_slice(&[0x0f, 0xb2, 0x20]).unwrap();
+    assert_eq!(instr.far_pointer(), Some(FarPointer::Memory { address: Operand::RegDeref(RegSpec::eax()), offset_width: 4 }));
+    // a near indirect jump has no far pointer.
+    assert_eq!(decoder.decode_slice(&[0xff, 0x20]).unwrap().far_pointer(), None);
+
+    let instr = decoder.decode_slice(&[0x0f, 0x01, 0x10]).unwrap();
+    assert_eq!(instr.pseudo_descriptor(), Some(PseudoDescriptor {
+        table: DescriptorTable::Global,
+        access: AccessKind::Read,
+        address: Operand::RegDeref(RegSpec::eax()),
+        base_width: 4,
+    }));
+    let instr = decoder.decode_slice(&[0x0f, 0x01, 0x0b]).unwrap();
+    assert_eq!(instr.pseudo_descriptor(), Some(PseudoDescriptor {
+        table: DescriptorTable::Interrupt,
+        access: AccessKind::Write,
+        address: Operand::RegDeref(RegSpec::ebx()),
+        base_width: 4,
+    }));
+    assert_eq!(decoder.decode_slice(&[0x0f, 0x00, 0x10]).unwrap().pseudo_descriptor(), None);
+}
+
+#[test]
+fn stack_deltas() {
+    let decoder = InstDecoder::default();
+    let delta = |bytes: &[u8]| decoder.decode_slice(bytes).unwrap().stack_delta();
+
+    assert_eq!(delta(&[0x50]), Some(-4));
+    assert_eq!(delta(&[0x66, 0x50]), Some(-2));
+    assert_eq!(delta(&[0x60]), Some(-32));
+    assert_eq!(delta(&[0x61]), Some(32));
+    assert_eq!(delta(&[0xff, 0x18]), Some(-8));
+    assert_eq!(delta(&[0xca, 0x08, 0x00]), Some(0x10));
+    assert_eq!(delta(&[0x83, 0xec, 0x0c]), Some(-0xc));
+    assert_eq!(delta(&[0x89, 0xec]), None);
+}
+
+#[test]
+fn summaries() {
+    use yaxpeax_x86::protected_mode::{Encoding, FlowClass};
+
+    let decoder = InstDecoder::default();
+    // `40` is `inc eax`, not a `rex` prefix.
+    let inc = decoder.summarize(&[0x40, 0x01, 0xc8]).unwrap();
+    assert_eq!((inc.opcode(), inc.opcode_offset(), inc.has_modrm()), (0x40, 0, false));
+    // `c5` is `lds` unless it is followed by what would be a register `modrm`.
+    let lds = decoder.summarize(&[0xc5, 0x00]).unwrap();
+    assert_eq!((lds.encoding(), lds.opcode(), lds.modrm()), (Encoding::Legacy, 0xc5, Some(0x00)));
+    assert_eq!(decoder.summarize(&[0xc5, 0xf8, 0x77]).unwrap().encoding(), Encoding::Vex);
+    assert_eq!(decoder.summarize(&[0x9a]).unwrap().class(), FlowClass::Call);
+    assert_eq!(decoder.summarize(&[0x66, 0xea]).unwrap().class(), FlowClass::Jump);
+}
+
+#[test]
+fn addressing_forms() {
+    use yaxpeax_arch::LengthedInstruction;
+    use yaxpeax_x86::protected_mode::AddressingForm;
+
+    let decoder = InstDecoder::default();
+    let forms = [
+        "[bx + si]", "[bx + di]", "[bp + si]", "[bp + di]", "[si]", "[di]", "[0x1234]", "[bx]",
+    ];
+    // with an address-size prefix, each 16-bit `modrm` form, with no, 8-, and 16-bit
+    // displacements.
+    for (i, form) in forms.iter().enumerate() {
+        for mode in 0..3u8 {
+            let modrm = mode << 6 | i as u8;
+            let instr = decoder.decode_slice(&[0x67, 0x8b, modrm, 0x34, 0x12]).unwrap();
+            let expected = match (mode, *form) {
+                (0, _) => form.to_string(),
+                (1, "[0x1234]") => "[bp + 0x34]".to_string(),
+                (2, "[0x1234]") => "[bp + 0x1234]".to_string(),
+                (1, _) => format!("{} + 0x34]", &form[..form.len() - 1]),
+                _ => format!("{} + 0x1234]", &form[..form.len() - 1]),
+            };
+            assert_eq!(instr.to_string(), format!("mov eax, dword {}", expected));
+            let disp_len = if mode == 0 && i == 6 { 2 } else { mode as u32 };
+            assert_eq!(instr.len().to_const(), 3 + disp_len, "{}", expected);
+            assert_eq!(instr.operand(1).addressing_form(), Some(AddressingForm::Bits16));
+            assert_eq!(instr.addressing_form(), AddressingForm::Bits16);
+        }
+    }
+    assert_eq!(decoder.decode_slice(&[0x67, 0xa1, 0x34, 0x12]).unwrap().operand(1), Operand::DisplacementU16(0x1234));
+    assert_eq!(decoder.decode_slice(&[0x67, 0xa1, 0x34, 0x12]).unwrap().operand(1).addressing_form(), Some(AddressingForm::Bits16));
+
+    let instr = decoder.decode_slice(&[0x8b, 0x04, 0x98]).unwrap();
+    assert_eq!(instr.operand(1).addressing_form(), Some(AddressingForm::Bits32));
+    assert_eq!(instr.addressing_form(), AddressingForm::Bits32);
+    let instr = decoder.decode_slice(&[0xa1, 0x78, 0x56, 0x34, 0x12]).unwrap();
+    assert_eq!(instr.operand(1).addressing_form(), Some(AddressingForm::Bits32));
+}
+
+#[test]
+fn immediate_values() {
+    use yaxpeax_x86::protected_mode::ImmExtension;
+
+    let instr = InstDecoder::default().decode_slice(&[0x6a, 0xfe]).unwrap();
+    let imm = instr.immediate_value(0).unwrap();
+    assert_eq!((imm.size(), imm.extension()), (1, ImmExtension::Sign));
+    assert_eq!(imm.value_for_size(4), 0xffff_fffe);
+    assert_eq!(instr.operand(0).immediate_value(), Some(imm));
+
+    let instr = InstDecoder::default().decode_slice(&[0xc2, 0xfe, 0xff]).unwrap();
+    let imm = instr.immediate_value(0).unwrap();
+    assert_eq!((imm.size(), imm.extension(), imm.value()), (2, ImmExtension::Zero, 0xfffe));
+}
+
+#[test]
+fn xbegin_fallback() {
+    // xbegin with a 16-bit offset wraps within the 16-bit instruction pointer it writes.
+    let instr = InstDecoder::default().decode_slice(&[0x66, 0xc7, 0xf8, 0x00, 0x80]).unwrap();
+    assert_eq!(instr.operand(0), Operand::ImmediateI16(-0x8000));
+    assert_eq!(instr.xbegin_fallback(0x1000), Some(0x9005));
+    let instr = InstDecoder::default().decode_slice(&[0xc7, 0xf8, 0x00, 0x00, 0x01, 0x00]).unwrap();
+    assert_eq!(instr.xbegin_fallback(0x1000), Some(0x11006));
+}
+
+#[test]
+fn branch_hints() {
+    use yaxpeax_x86::protected_mode::BranchHint;
+
+    fn decode(data: &[u8]) -> yaxpeax_x86::protected_mode::Instruction {
+        InstDecoder::default().decode_slice(data).unwrap()
+    }
+
+    // hint prefixes are also `cs` and `ds` segment overrides in 32-bit code.
+    let jz = decode(&[0x2e, 0x74, 0x05]);
+    assert_eq!(jz.branch_hint(), Some(BranchHint::NotTaken));
+    assert_eq!(decode(&[0x3e, 0x0f, 0x8c, 0x00, 0x01, 0x00, 0x00]).branch_hint(), Some(BranchHint::Taken));
+    assert_eq!(decode(&[0x3e, 0x8b, 0x00]).branch_hint(), None);
+}
+
+#[test]
+fn relocate() {
+    use yaxpeax_x86::protected_mode::RelocateError;
+
+    let decoder = InstDecoder::default().with_retain_bytes();
+
+    // jz widens to a 32-bit displacement.
+    let jz = decoder.decode_slice(&[0x74, 0x00]).unwrap();
+    assert_eq!(jz.retarget(0x1000, 0x2000).unwrap().bytes(), &[0x0f, 0x84, 0xfa, 0x0f, 0x00, 0x00]);
+    assert_eq!(jz.retarget(0x1000, 0x1_0000_0000), Err(RelocateError::OutOfRange));
+    let call = decoder.decode_slice(&[0xe8, 0x00, 0x00, 0x00, 0x00]).unwrap();
+    assert_eq!(call.relocate(0x1000, 0xffff_f000).unwrap().bytes(), &[0xe8, 0x00, 0x20, 0x00, 0x00]);
+
+    // there is no `rip`-relative addressing to relocate.
+    let mov = decoder.decode_slice(&[0x8b, 0x05, 0x78, 0x56, 0x34, 0x12]).unwrap();
+    assert_eq!(mov.relocate(0x1000, 0x2000), Err(RelocateError::NotRelative));
+}
+
+#[test]
+fn defs_and_uses() {
+    fn decode(data: &[u8]) -> yaxpeax_x86::protected_mode::Instruction {
+        InstDecoder::default().decode_slice(data).unwrap()
+    }
+
+    // without a 64-bit register to extend into, 32-bit writes define the register they name.
+    let mov = decode(&[0xb8, 0x01, 0x00, 0x00, 0x00]);
+    assert_eq!(mov.defs().as_slice(), &[RegSpec::eax()]);
+    // inc ecx; push eax
+    let inc = decode(&[0x41]);
+    assert_eq!(inc.defs().as_slice(), &[RegSpec::ecx(), RegSpec::eflags()]);
+    assert_eq!(inc.uses().as_slice(), &[RegSpec::ecx()]);
+    assert_eq!(decode(&[0x50]).uses().as_slice(), &[RegSpec::eax(), RegSpec::esp()]);
+    // vaddps xmm0, xmm1, xmm2
+    assert_eq!(decode(&[0xc5, 0xf0, 0x58, 0xc2]).defs().as_slice(), &[RegSpec::zmm(0)]);
+}
+
+#[test]
+fn write_behaviors() {
+    use yaxpeax_x86::protected_mode::WriteBehavior;
+
+    fn decode(data: &[u8]) -> yaxpeax_x86::protected_mode::Instruction {
+        InstDecoder::default().decode_slice(data).unwrap()
+    }
+
+    // 32-bit registers are the widest general-purpose registers here.
+    assert_eq!(decode(&[0xb8, 0x01, 0x00, 0x00, 0x00]).write_behavior(0), Some(WriteBehavior::Full));
+    assert_eq!(decode(&[0x66, 0xb8, 0x01, 0x00]).write_behavior(0), Some(WriteBehavior::Merge));
+    assert_eq!(decode(&[0xc5, 0xf0, 0x58, 0xc2]).write_behavior(0), Some(WriteBehavior::ZeroExtend));
+}
@@ -0,0 +1,62 @@
+use std::fmt::Write;
+
+use yaxpeax_arch::{AddressBase, Decoder, LengthedInstruction};
+use yaxpeax_x86::real_mode::{DisplayOptions, InstDecoder};
+
+fn test_att_display(data: &[u8], expected: &'static str) {
+    test_display_under(&InstDecoder::default(), DisplayOptions::att(), data, expected);
+}
+
+fn test_display_under<O: Into<DisplayOptions>>(decoder: &InstDecoder, style: O, data: &[u8], expected: &'static str) {
+    let mut hex = String::new();
+    for b in data {
+        write!(hex, "{:02x}", b).unwrap();
+    }
+    let mut reader = yaxpeax_arch::U8Reader::new(data);
+    match decoder.decode(&mut reader) {
+        Ok(instr) => {
+            let text = format!("{}", instr.display_with(style));
+            assert!(
+                text == expected,
+                "display error for {}:\n  decoded: {:?} under decoder {}\n displayed: {}\n expected: {}\n",
+                hex,
+                instr,
+                decoder,
+                text,
+                expected
+            );
+            assert_eq!((0u32.wrapping_offset(instr.len()).to_linear()) as usize, data.len(), "instruction length is incorrect, wanted instruction {}", expected);
+        },
+        Err(e) => {
+            assert!(false, "decode error ({}) for {} under decoder {}:\n  expected: {}\n", e, hex, decoder, expected);
+        }
+    }
+}
+
+#[test]
+fn test_instructions_att() {
+    test_att_display(&[0x33, 0x08], "xor (%bx,%si),%cx");
+    test_att_display(&[0x33, 0xc1], "xor %cx,%ax");
+    test_att_display(&[0x8b, 0x47, 0x02], "mov 0x2(%bx),%ax");
+    test_att_display(&[0xa1, 0xaa, 0xbb], "mov 0xbbaa,%ax");
+    test_att_display(&[0x67, 0x33, 0x08], "xor (%eax),%cx");
+    test_att_display(&[0xc7, 0x00, 0x01, 0x00], "movw $0x1,(%bx,%si)");
+    test_att_display(&[0xff, 0xd0], "call *%ax");
+    test_att_display(&[0xac], "lods %ds:(%si),%al");
+    test_att_display(&[0xf3, 0xa4], "rep movsb %ds:(%si),%es:(%di)");
+}
+
+#[test]
+fn test_instructions_att_objdump() {
+    test_att_display(&[0x0f, 0x1f, 0x40, 0x00], "nopw 0x0(%bx,%si)");
+    test_att_display(&[0x83, 0xc0, 0xff], "add $0xffff,%ax");
+    test_att_display(&[0x6a, 0x90], "push $0xff90");
+    test_att_display(&[0xe1, 0xfe], "loope $-0x2");
+    test_att_display(&[0xf3, 0xa6], "repz cmpsb %es:(%di),%ds:(%si)");
+    test_att_display(&[0xd7], "xlat %ds:(%bx)");
+    test_att_display(&[0x66, 0xc3], "retl");
+    test_att_display(&[0x66, 0xcf], "iretl");
+    test_att_display(&[0xcf], "iret");
+    test_att_display(&[0x9b], "fwait");
+    test_att_display(&[0xd8, 0xc0], "fadd %st(0),%st");
+}
@@ -0,0 +1,68 @@
+use yaxpeax_arch::{AddressBase, Decoder, LengthedInstruction, Reader, U8Reader};
+use yaxpeax_x86::real_mode::{DecodeError, InstDecoder};
+
+fn length_of(data: &[u8]) -> Result<u8, DecodeError> {
+    InstDecoder::default().length_of(&mut U8Reader::new(data))
+}
+
+#[test]
+fn test_length_of() {
+    assert_eq!(length_of(&[0x90]), Ok(1));
+    // `40` is `inc ax`, not a prefix.
+    assert_eq!(length_of(&[0x40, 0x8b, 0x46, 0xf8]), Ok(1));
+    assert_eq!(length_of(&[0x8b, 0x46, 0xf8]), Ok(3));
+    assert_eq!(length_of(&[0x81, 0xc0, 0x34, 0x12]), Ok(4));
+    assert_eq!(length_of(&[0x66, 0x81, 0xc0, 0x78, 0x56, 0x34, 0x12]), Ok(7));
+    // an address-size prefix selects the 32-bit modrm layout.
+    assert_eq!(length_of(&[0x67, 0x8b, 0x44, 0x24, 0xf8]), Ok(5));
+    // `c4` is `les` unless its modrm byte selects registers.
+    assert_eq!(length_of(&[0xc4, 0x00]), Ok(2));
+    assert_eq!(length_of(&[0xc5, 0xf8, 0x77]), Ok(3));
+    assert_eq!(length_of(&[0x9a, 0x34, 0x12, 0x00, 0x10]), Ok(5));
+    assert_eq!(length_of(&[0x8b]), Err(DecodeError::ExhaustedInput));
+    assert_eq!(length_of(&[0x66; 16]), Err(DecodeError::TooLong));
+    assert_eq!(length_of(&[0x0f, 0x38, 0xff, 0xc0]), Err(DecodeError::InvalidOpcode));
+    assert_eq!(length_of(&[0x8d, 0xc0]), Err(DecodeError::InvalidOperand));
+    assert_eq!(length_of(&[0xf0, 0x01, 0xc8]), Err(DecodeError::InvalidPrefixes));
+
+    // the reader is left after the instruction.
+    let data = [0x40, 0xc5, 0xf8, 0x77, 0xc3];
+    let mut reader = U8Reader::new(&data);
+    let decoder = InstDecoder::default();
+    assert_eq!(decoder.length_of(&mut reader), Ok(1));
+    assert_eq!(decoder.length_of(&mut reader), Ok(3));
+    assert_eq!(<U8Reader as Reader<u32, u8>>::total_offset(&mut reader), 4);
+}
+
+#[test]
+fn test_length_of_matches_decode() {
+    let decoder = InstDecoder::default();
+    // xorshift, for reproducible "random" instructions.
+    let mut state: u64 = 0x2545f4914f6cdd1d;
+    for _ in 0..200_000 {
+        let mut data = [0u8; 16];
+        for b in data.iter_mut() {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            *b = state as u8;
+        }
+        // random bytes rarely hit interesting prefixes, so add some.
+        match (state >> 40) % 8 {
+            0 => { data[0] = 0x66; }
+            1 => { data[0] = 0x67; }
+            2 => { data[0] = 0x0f; }
+            3 => { data[0] = 0x66; data[1] = 0x0f; }
+            4 => { data[0] = 0xc4; }
+            5 => { data[0] = 0x62; }
+            _ => {}
+        }
+        // and rarely end early, so try a truncated copy as well.
+        let cut = (state >> 48) as usize % data.len();
+        for data in [&data[..], &data[..cut]].iter() {
+            let expected = decoder.decode(&mut U8Reader::new(data))
+                .map(|instr| 0u32.wrapping_offset(instr.len()).to_linear() as u8);
+            assert_eq!(decoder.length_of(&mut U8Reader::new(data)), expected, "length of {:02x?}", data);
+        }
+    }
+}
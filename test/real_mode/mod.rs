@@ -1,9 +1,11 @@
 mod operand;
+mod display;
+mod length;
 
 use std::fmt::Write;
 
 use yaxpeax_arch::{AddressBase, Decoder, U8Reader, LengthedInstruction};
-use yaxpeax_x86::real_mode::InstDecoder;
+use yaxpeax_x86::real_mode::{Generation, InstDecoder};
 
 fn test_invalid(data: &[u8]) {
     test_invalid_under(&InstDecoder::default(), data);
@@ -17598,6 +17600,7 @@ fn test_real_mode() {
     test_display(&[0xc6, 0xf8, 0x10], "xabort 0x10");
     test_display(&[0xc7, 0x43, 0x10, 0x00, 0x00], "mov word [bp + di + 0x10], 0x0");
     test_display(&[0xc7, 0xf8, 0x10, 0x12], "xbegin $+0x1210");
+    test_display(&[0x66, 0xc7, 0xf8, 0x10, 0x12, 0x34, 0x56], "xbegin $+0x56341210");
     test_display(&[0xc8, 0x01, 0x02, 0x03], "enter 0x201, 0x3");
     test_display(&[0xc9], "leave");
     test_display(&[0xca, 0x12, 0x34], "retf 0x3412");
@@ -17682,8 +17685,8 @@ fn test_real_mode() {
     test_display(&[0xdb, 0x0b], "fisttp dword [bp + di], st(0)");
     test_display(&[0xdb, 0x13], "fist dword [bp + di], st(0)");
     test_display(&[0xdb, 0x1b], "fistp dword [bp + di], st(0)");
-    test_display(&[0xdb, 0x2b], "fld st(0), mword [bp + di]");
-    test_display(&[0xdb, 0x3b], "fstp mword [bp + di], st(0)");
+    test_display(&[0xdb, 0x2b], "fld st(0), tword [bp + di]");
+    test_display(&[0xdb, 0x3b], "fstp tword [bp + di], st(0)");
     test_display(&[0xdb, 0xc3], "fcmovnb st(0), st(3)");
     test_display(&[0xdb, 0xcb], "fcmovne st(0), st(3)");
     test_display(&[0xdb, 0xd3], "fcmovnbe st(0), st(3)");
@@ -17744,9 +17747,9 @@ fn test_real_mode() {
     test_display(&[0xdf, 0x0b], "fisttp word [bp + di], st(0)");
     test_display(&[0xdf, 0x13], "fist word [bp + di], st(0)");
     test_display(&[0xdf, 0x1b], "fistp word [bp + di], st(0)");
-    test_display(&[0xdf, 0x23], "fbld st(0), mword [bp + di]");
+    test_display(&[0xdf, 0x23], "fbld st(0), tword [bp + di]");
     test_display(&[0xdf, 0x2b], "fild st(0), qword [bp + di]");
-    test_display(&[0xdf, 0x33], "fbstp mword [bp + di], st(0)");
+    test_display(&[0xdf, 0x33], "fbstp tword [bp + di], st(0)");
     test_display(&[0xdf, 0x3b], "fistp qword [bp + di], st(0)");
     test_display(&[0xdf, 0xc3], "ffreep st(3)");
     test_display(&[0xdf, 0xcb], "fxch st(0), st(3)");
@@ -17901,6 +17904,10 @@ fn test_real_mode() {
     // note that this call only writes two bytes, and only moves sp by two.
     test_display(&[0x66, 0xff, 0x15], "call dword [di]");
     test_display(&[0xff, 0x18], "callf dword [bx + si]");
+    test_display(&[0x9a, 0x34, 0x12, 0x78, 0x56], "callf 0x5678:0x1234");
+    test_display(&[0xea, 0x5b, 0xe0, 0x00, 0xf0], "jmpf 0xf000:0xe05b");
+    test_display(&[0x66, 0xea, 0x78, 0x56, 0x34, 0x12, 0x08, 0x00], "jmpf 0x8:0x12345678");
+    test_invalid(&[0xea, 0x5b, 0xe0, 0x00]);
     test_display(&[0xff, 0x24], "jmp word [si]");
     test_display(&[0xff, 0x75, 0x08], "push word [di + 0x8]");
     test_display(&[0xff, 0x75, 0xb8], "push word [di - 0x48]");
@@ -17917,7 +17924,7 @@ fn test_invalid_sequences() {
     test_invalid(&[0x0f, 0x01, 0xce]);
     test_invalid(&[0x0f, 0x01, 0xd2]);
     test_invalid(&[0x0f, 0x01, 0xd3]);
-    test_invalid(&[0x0f, 0x01, 0xe8]);
+    test_invalid(&[0x66, 0x0f, 0x01, 0xe8]);
     test_invalid(&[0x0f, 0x01, 0xe9]);
     test_invalid(&[0x0f, 0x01, 0xea]);
     test_invalid(&[0x0f, 0x01, 0xeb]);
@@ -18422,3 +18429,87 @@ mod reg_masks {
         RegSpec::zmm(32);
     }
 }
+
+#[test]
+fn generations() {
+    let i8086 = InstDecoder::minimal().with_generation(Generation::I8086);
+    let i80186 = InstDecoder::minimal().with_generation(Generation::I80186);
+    let i80286 = InstDecoder::minimal().with_generation(Generation::I80286);
+    let i80386 = InstDecoder::default().with_generation(Generation::I80386);
+
+    // `0f` is `pop cs` on the 8086, and invalid after.
+    test_display_under(&i8086, &[0x0f], "pop cs");
+    test_invalid_under(&i80186, &[0x0f, 0x01, 0xe0]);
+    test_display_under(&i80286, &[0x0f, 0x01, 0xe0], "smsw ax");
+    test_invalid_under(&InstDecoder::default(), &[0x0f]);
+
+    test_display_under(&i8086, &[0x8b, 0xc3], "mov ax, bx");
+    test_display_under(&i8086, &[0xd1, 0xe0], "shl ax, 0x1");
+    test_invalid_under(&i8086, &[0xc1, 0xe0, 0x04]);
+    test_display_under(&i80186, &[0xc1, 0xe0, 0x04], "shl ax, 0x4");
+    test_invalid_under(&i8086, &[0x6a, 0x01]);
+    test_display_under(&i80186, &[0x6a, 0x01], "push 0x1");
+    test_invalid_under(&i8086, &[0x60]);
+    test_display_under(&i80186, &[0x60], "pusha");
+    test_invalid_under(&i80186, &[0x63, 0xc3]);
+    test_display_under(&i80286, &[0x63, 0xc3], "arpl bx, ax");
+
+    // operand and address size prefixes, `fs`, and `gs` are from the 80386.
+    test_invalid_under(&i80286, &[0x66, 0x40]);
+    test_invalid_under(&i80286, &[0x67, 0x8b, 0x00]);
+    test_invalid_under(&i80286, &[0x64, 0x8b, 0x07]);
+    test_display_under(&i80386, &[0x66, 0x40], "inc eax");
+    test_display_under(&i80386, &[0x64, 0x8b, 0x07], "mov ax, word fs:[bx]");
+
+    test_invalid_under(&i80286, &[0x0f, 0xb6, 0xc3]);
+    test_display_under(&i80386, &[0x0f, 0xb6, 0xc3], "movzx ax, bl");
+    test_display_under(&i80386, &[0x0f, 0x84, 0x00, 0x01], "jz $+0x100");
+    test_invalid_under(&i80286, &[0x0f, 0x84, 0x00, 0x01]);
+    test_display_under(&i80386, &[0xf3, 0x0f, 0xbc, 0xc3], "bsf ax, bx");
+    test_display_under(&i80386, &[0x0f, 0x01, 0x10], "lgdt ptr [bx + si]");
+    test_invalid_under(&i80386, &[0x0f, 0x01, 0x38]);
+    test_invalid_under(&i80386, &[0x0f, 0x01, 0xc8]);
+    test_invalid_under(&i80386, &[0x0f, 0xa2]);
+    test_invalid_under(&i80386, &[0x0f, 0x0b]);
+    test_invalid_under(&i80386, &[0x0f, 0x38, 0x00, 0xc1]);
+    test_invalid_under(&i80386, &[0xc5, 0xf8, 0x77]);
+    test_invalid_under(&i80386, &[0xf1]);
+
+    test_display_under(&i8086, &[0xd9, 0xc0], "fld st(0), st(0)");
+    test_invalid_under(&i8086, &[0xdf, 0xe0]);
+    test_display_under(&i80286, &[0xdf, 0xe0], "fnstsw ax");
+    test_invalid_under(&i80286, &[0xd9, 0xfe]);
+    test_display_under(&i80386, &[0xd9, 0xfe], "fsin");
+    test_invalid_under(&i80386, &[0xdb, 0xf1]);
+}
+
+#[test]
+fn relocate() {
+    use yaxpeax_x86::real_mode::RelocateError;
+
+    let decoder = InstDecoder::default().with_retain_bytes();
+
+    // jz widens to a 16-bit displacement, and targets wrap at 64k.
+    let jz = decoder.decode_slice(&[0x74, 0x00]).unwrap();
+    assert_eq!(jz.retarget(0x1000, 0x2000).unwrap().bytes(), &[0x0f, 0x84, 0xfc, 0x0f]);
+    assert_eq!(jz.retarget(0xff00, 0x0010).unwrap().bytes(), &[0x0f, 0x84, 0x0c, 0x01]);
+    assert_eq!(jz.retarget(0x1000, 0x1_0000), Err(RelocateError::OutOfRange));
+    // call $+0x13
+    let call = decoder.decode_slice(&[0xe8, 0x10, 0x00]).unwrap();
+    assert_eq!(call.relocate(0x100, 0x200).unwrap().bytes(), &[0xe8, 0x10, 0xff]);
+}
+
+#[test]
+fn x87_memory_sizes() {
+    fn bytes_size_of(data: &[u8]) -> Option<u8> {
+        InstDecoder::default().decode_slice(data).unwrap().mem_size().unwrap().bytes_size()
+    }
+
+    // fld tword [bx + si]
+    assert_eq!(bytes_size_of(&[0xdb, 0x28]), Some(10));
+    // fnstenv and fnsave: 16-bit unless the operand size is overridden
+    assert_eq!(bytes_size_of(&[0xd9, 0x30]), Some(14));
+    assert_eq!(bytes_size_of(&[0x66, 0xd9, 0x30]), Some(28));
+    assert_eq!(bytes_size_of(&[0xdd, 0x30]), Some(94));
+    assert_eq!(bytes_size_of(&[0x66, 0xdd, 0x30]), Some(108));
+}
@@ -1,4 +1,4 @@
-use yaxpeax_x86::real_mode::{InstDecoder};
+use yaxpeax_x86::real_mode::{BranchTarget, DisplayOptions, DisplayStyle, FarTarget, InstDecoder, Operand, RegSpec, RegValueSource, Segment, SegmentedAddress};
 
 #[test]
 fn test_implied_memory_width() {
@@ -21,3 +21,195 @@ fn test_implied_memory_width() {
     assert_eq!(mem_size_of(&[0x66, 0xff, 0x10]), Some(4));
     assert_eq!(mem_size_of(&[0x66, 0xff, 0x20]), Some(4));
 }
+
+#[test]
+fn branch_targets_wrap() {
+    fn target_of(data: &[u8], address: u64) -> Option<BranchTarget> {
+        InstDecoder::default().decode_slice(data).unwrap().branch_target(address)
+    }
+
+    // jmp $+0x10, wrapping at the top of the 16-bit instruction pointer
+    assert_eq!(target_of(&[0xeb, 0x10], 0xfff0), Some(BranchTarget::Direct(0x0002)));
+    // jmp $-0x3 from address 0
+    assert_eq!(target_of(&[0xe9, 0xfd, 0xff], 0), Some(BranchTarget::Direct(0)));
+    assert_eq!(target_of(&[0xe9, 0xf0, 0xff], 0), Some(BranchTarget::Direct(0xfff3)));
+    // with an operand-size prefix, the target is 32 bits wide
+    assert_eq!(target_of(&[0x66, 0xeb, 0x10], 0xfff0), Some(BranchTarget::Direct(0x1_0003)));
+    // jmpf 0xf000:0xe05b leaves the current code segment
+    assert_eq!(target_of(&[0xea, 0x5b, 0xe0, 0x00, 0xf0], 0), None);
+}
+
+struct Regs;
+
+impl RegValueSource for Regs {
+    fn reg_value(&self, reg: RegSpec) -> Option<u64> {
+        if reg == RegSpec::bx() {
+            Some(0xfff0)
+        } else if reg == RegSpec::si() {
+            Some(0x0020)
+        } else if reg == RegSpec::bp() {
+            Some(0x0100)
+        } else if reg == RegSpec::eax() {
+            Some(0xfff0)
+        } else if reg == RegSpec::ds() {
+            Some(0x1000)
+        } else if reg == RegSpec::ss() {
+            Some(0x2000)
+        } else {
+            None
+        }
+    }
+
+    fn segment_base(&self, segment: Segment) -> Option<u64> {
+        match segment {
+            Segment::DS => Some(0x1_0000),
+            Segment::SS => Some(0x2_0000),
+            Segment::ES => Some(0x3_0000),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn evaluate_addresses_wrap() {
+    fn address_of(data: &[u8]) -> Option<u64> {
+        InstDecoder::default().decode_slice(data).unwrap().operand(1).evaluate(&Regs)
+    }
+
+    // mov ax, word [bx + si + 0x4], wrapping at the top of the 16-bit address space
+    assert_eq!(address_of(&[0x8b, 0x40, 0x04]), Some(0x0014));
+    // mov ax, word [0x1234]
+    assert_eq!(address_of(&[0x8b, 0x06, 0x34, 0x12]), Some(0x1234));
+    // mov ax, word [eax + 0x20]: with an address-size prefix, addresses are 32 bits wide
+    assert_eq!(address_of(&[0x67, 0x8b, 0x40, 0x20]), Some(0x1_0010));
+}
+
+#[test]
+fn linear_addresses() {
+    fn linear_address_of(data: &[u8], i: u8) -> Option<u64> {
+        InstDecoder::default().decode_slice(data).unwrap().linear_address(i, &Regs)
+    }
+
+    // mov ax, word [bx + si + 0x4]
+    assert_eq!(linear_address_of(&[0x8b, 0x40, 0x04], 1), Some(0x1_0014));
+    // mov ax, word [bp + 0x4]: `bp` addresses the stack segment
+    assert_eq!(linear_address_of(&[0x8b, 0x46, 0x04], 1), Some(0x2_0104));
+    // mov ax, word es:[bp + 0x4]
+    assert_eq!(linear_address_of(&[0x26, 0x8b, 0x46, 0x04], 1), Some(0x3_0104));
+    // mov ax, word fs:[bx]: the `fs` base is unknown
+    assert_eq!(linear_address_of(&[0x64, 0x8b, 0x07], 1), None);
+}
+
+#[test]
+fn segmented_addresses() {
+    let boot = SegmentedAddress::new(0, 0x7c00);
+    assert_eq!(boot.linear(), 0x7c00);
+    assert_eq!(boot.normalized(), SegmentedAddress::new(0x07c0, 0));
+    assert_eq!(boot.to_string(), "0000:7c00");
+
+    // the top of the first megabyte wraps around without a20, and reaches the high memory area
+    // with it.
+    let hma = SegmentedAddress::new(0xffff, 0x10);
+    assert_eq!(hma.linear(), 0);
+    assert_eq!(hma.linear_a20(), 0x10_0000);
+    assert_eq!(SegmentedAddress::interrupt_vector(0x21), SegmentedAddress::new(0, 0x84));
+
+    fn segmented_address_of(data: &[u8], i: u8) -> Option<SegmentedAddress> {
+        InstDecoder::default().decode_slice(data).unwrap().segmented_address(i, &Regs)
+    }
+
+    // mov ax, word [bx + si + 0x4]
+    assert_eq!(segmented_address_of(&[0x8b, 0x40, 0x04], 1), Some(SegmentedAddress::new(0x1000, 0x0014)));
+    // mov ax, word [bp + 0x4]
+    assert_eq!(segmented_address_of(&[0x8b, 0x46, 0x04], 1), Some(SegmentedAddress::new(0x2000, 0x0104)));
+    // mov ax, word es:[bp + 0x4]: the value of `es` is unknown
+    assert_eq!(segmented_address_of(&[0x26, 0x8b, 0x46, 0x04], 1), None);
+}
+
+#[test]
+fn far_targets() {
+    fn far_target_of(data: &[u8]) -> Option<FarTarget> {
+        InstDecoder::default().decode_slice(data).unwrap().far_target()
+    }
+
+    assert_eq!(far_target_of(&[0xea, 0x5b, 0xe0, 0x00, 0xf0]), Some(FarTarget::Direct(SegmentedAddress::new(0xf000, 0xe05b))));
+    assert_eq!(far_target_of(&[0x9a, 0x00, 0x7c, 0x00, 0x00]), Some(FarTarget::Direct(SegmentedAddress::new(0, 0x7c00))));
+    assert_eq!(far_target_of(&[0xff, 0x2e, 0x00, 0x10]), Some(FarTarget::Indirect(Operand::DisplacementU16(0x1000))));
+    assert_eq!(far_target_of(&[0xcd, 0x21]), Some(FarTarget::Interrupt(0x21)));
+    assert_eq!(far_target_of(&[0xcc]), Some(FarTarget::Interrupt(3)));
+    assert_eq!(far_target_of(&[0xce]), Some(FarTarget::Interrupt(4)));
+    assert_eq!(far_target_of(&[0xeb, 0x10]), None);
+}
+
+#[test]
+fn explicit_segments() {
+    fn display_of(data: &[u8], options: DisplayOptions) -> String {
+        let options = options.with_explicit_segments();
+        InstDecoder::default().decode_slice(data).unwrap().display_with(options).to_string()
+    }
+
+    assert_eq!(display_of(&[0x8b, 0x40, 0x04], DisplayStyle::Intel.into()), "mov ax, word ds:[bx + si + 0x4]");
+    assert_eq!(display_of(&[0x8b, 0x46, 0x04], DisplayStyle::Intel.into()), "mov ax, word ss:[bp + 0x4]");
+    assert_eq!(display_of(&[0x26, 0x8b, 0x46, 0x04], DisplayStyle::Intel.into()), "mov ax, word es:[bp + 0x4]");
+    assert_eq!(display_of(&[0x8b, 0xc1], DisplayStyle::Intel.into()), "mov ax, cx");
+    assert_eq!(display_of(&[0x8b, 0x46, 0x04], DisplayOptions::att()), "mov %ss:0x4(%bp),%ax");
+}
+
+#[test]
+fn address_size_for_op() {
+    fn sizes_of(data: &[u8]) -> Vec<Option<u8>> {
+        let inst = InstDecoder::default().decode_slice(data).unwrap();
+        (0..inst.operand_count()).map(|i| inst.address_size_for_op(i)).collect()
+    }
+
+    // mov ax, word [bx + si]
+    assert_eq!(sizes_of(&[0x8b, 0x00]), vec![None, Some(2)]);
+    // mov ax, word [eax]
+    assert_eq!(sizes_of(&[0x67, 0x8b, 0x00]), vec![None, Some(4)]);
+    // movsb, with and without 67
+    assert_eq!(sizes_of(&[0xa4]), vec![Some(2), Some(2)]);
+    assert_eq!(sizes_of(&[0x67, 0xa4]), vec![Some(4), Some(4)]);
+    // add ax, cx
+    assert_eq!(sizes_of(&[0x01, 0xc8]), vec![None, None]);
+}
+
+/// every 16-bit `modrm` memory form, as `mov ax, word [...]` with the displacement bytes
+/// `34 12`.
+const MODRM16_FORMS: [&str; 24] = [
+    "[bx + si]", "[bx + di]", "[bp + si]", "[bp + di]", "[si]", "[di]", "[0x1234]", "[bx]",
+    "[bx + si + 0x34]", "[bx + di + 0x34]", "[bp + si + 0x34]", "[bp + di + 0x34]",
+    "[si + 0x34]", "[di + 0x34]", "[bp + 0x34]", "[bx + 0x34]",
+    "[bx + si + 0x1234]", "[bx + di + 0x1234]", "[bp + si + 0x1234]", "[bp + di + 0x1234]",
+    "[si + 0x1234]", "[di + 0x1234]", "[bp + 0x1234]", "[bx + 0x1234]",
+];
+
+#[test]
+fn test_modrm16_forms() {
+    use yaxpeax_arch::LengthedInstruction;
+    use yaxpeax_x86::real_mode::AddressingForm;
+
+    let decoder = InstDecoder::default();
+    for (i, form) in MODRM16_FORMS.iter().enumerate() {
+        let modrm = ((i / 8) << 6 | (i % 8)) as u8;
+        let instr = decoder.decode_slice(&[0x8b, modrm, 0x34, 0x12]).unwrap();
+        assert_eq!(instr.to_string(), format!("mov ax, word {}", form));
+        // `mod = 00, rm = 110` is a bare `disp16`, not `[bp]`.
+        let disp_len = match (i / 8, i % 8) {
+            (0, 6) => 2,
+            (mode, _) => mode as u32,
+        };
+        assert_eq!(instr.len().to_const(), 2 + disp_len, "{}", form);
+        assert_eq!(instr.operand(1).addressing_form(), Some(AddressingForm::Bits16), "{}", form);
+        assert_eq!(instr.addressing_form(), AddressingForm::Bits16);
+    }
+    assert_eq!(decoder.decode_slice(&[0x8b, 0x06, 0x34, 0x12]).unwrap().operand(1), Operand::DisplacementU16(0x1234));
+    // 8-bit displacements are sign-extended.
+    assert_eq!(decoder.decode_slice(&[0x8b, 0x46, 0xfe]).unwrap().to_string(), "mov ax, word [bp - 0x2]");
+
+    // an address-size prefix selects 32-bit forms.
+    let instr = decoder.decode_slice(&[0x67, 0x8b, 0x04, 0x98]).unwrap();
+    assert_eq!(instr.to_string(), "mov ax, word [eax + ebx * 4]");
+    assert_eq!(instr.operand(1).addressing_form(), Some(AddressingForm::Bits32));
+    assert_eq!(instr.addressing_form(), AddressingForm::Bits32);
+    assert_eq!(Operand::Register(RegSpec::ax()).addressing_form(), None);
+}